@@ -1,18 +1,48 @@
-// Embeds an `asInvoker` application manifest into this crate's example and binary
-// targets on Windows (MSVC).
-//
-// Without it, Windows' Installer Detection heuristic forces UAC elevation on any
-// executable whose name contains "update"/"setup"/"install" — e.g.
-// `cargo run --example example_10_threshold_updates` produces
-// example_10_threshold_updates.exe, which then fails to launch unelevated
-// (os error 740, "The requested operation requires elevation").
-//
-// `embed-manifest` only targets `bins`, so we emit the linker args directly for
-// both `examples` and `bins`. These `rustc-link-arg-{examples,bins}` directives are
-// package-local: when this crate is consumed as a library dependency, its examples
-// are not built, so dependents (e.g. the codegen test harness) are unaffected.
-
-use std::{env, fs, path::PathBuf};
+//! Embeds an `asInvoker` application manifest into this crate's example and binary
+//! targets on Windows (MSVC).
+//!
+//! Without it, Windows' Installer Detection heuristic forces UAC elevation on any
+//! executable whose name contains "update"/"setup"/"install" — e.g.
+//! `cargo run --example example_10_threshold_updates` produces
+//! `example_10_threshold_updates.exe`, which then fails to launch unelevated
+//! (os error 740, "The requested operation requires elevation").
+//!
+//! `embed-manifest` only targets `bins`, so we emit the linker args directly for
+//! both `examples` and `bins`. These `rustc-link-arg-{examples,bins}` directives are
+//! package-local: when this crate is consumed as a library dependency, its examples
+//! are not built, so dependents (e.g. the codegen test harness) are unaffected.
+//!
+//! With the `codegen` feature and `ACCUMULATE_PROTOCOL_DIR` pointing at an
+//! accumulate `protocol/` directory, it also regenerates the enum, transaction
+//! and signature modules from that YAML into `OUT_DIR` (see `src/generated/mod.rs`).
+//! This runs the Python generators in `tooling/backends`, so it needs Python 3
+//! with `PyYAML` (`ACCUMULATE_CODEGEN_PYTHON` overrides the interpreter).
+
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Generator script, module it writes, YAML files it reads
+const GENERATORS: &[(&str, &str, &[&str])] = &[
+    ("rust_enums_codegen.py", "enums.rs", &["enums.yml"]),
+    (
+        "rust_transactions_codegen.py",
+        "transactions.rs",
+        &[
+            "transaction.yml",
+            "user_transactions.yml",
+            "system.yml",
+            "synthetic_transactions.yml",
+        ],
+    ),
+    (
+        "rust_signatures_codegen.py",
+        "signatures.rs",
+        &["signatures.yml"],
+    ),
+];
 
 const ASINVOKER_MANIFEST: &str = r#"<?xml version="1.0" encoding="UTF-8" standalone="yes"?>
 <assembly xmlns="urn:schemas-microsoft-com:asm.v1" manifestVersion="1.0">
@@ -27,18 +57,31 @@ const ASINVOKER_MANIFEST: &str = r#"<?xml version="1.0" encoding="UTF-8" standal
 "#;
 
 fn main() {
+    if let Err(err) = run() {
+        println!("cargo:warning={err}");
+        std::process::exit(1);
+    }
+}
+
+fn run() -> Result<(), String> {
     println!("cargo:rerun-if-changed=build.rs");
+    println!("cargo:rustc-check-cfg=cfg(accumulate_codegen)");
+
+    if env::var_os("CARGO_FEATURE_CODEGEN").is_some() {
+        regenerate_protocol_types().map_err(|err| format!("codegen: {err}"))?;
+    }
 
     // Only Windows MSVC needs (and supports, via link.exe) this manifest embedding.
     let is_windows = env::var_os("CARGO_CFG_WINDOWS").is_some();
     let is_msvc = env::var("CARGO_CFG_TARGET_ENV").unwrap_or_default() == "msvc";
     if !is_windows || !is_msvc {
-        return;
+        return Ok(());
     }
 
-    let out_dir = env::var("OUT_DIR").expect("OUT_DIR not set");
+    let out_dir = env::var("OUT_DIR").map_err(|_| "OUT_DIR not set".to_string())?;
     let manifest_path = PathBuf::from(&out_dir).join("accumulate-asinvoker.manifest");
-    fs::write(&manifest_path, ASINVOKER_MANIFEST).expect("failed to write app manifest");
+    fs::write(&manifest_path, ASINVOKER_MANIFEST)
+        .map_err(|err| format!("failed to write app manifest: {err}"))?;
     let input = manifest_path.display();
 
     // Plain `rustc-link-arg` applies to all binary outputs (examples, bins, tests,
@@ -46,4 +89,72 @@ fn main() {
     println!("cargo:rustc-link-arg=/MANIFEST:EMBED");
     println!("cargo:rustc-link-arg=/MANIFESTINPUT:{input}");
     println!("cargo:rustc-link-arg=/MANIFESTUAC:NO");
+    Ok(())
+}
+
+fn regenerate_protocol_types() -> Result<(), String> {
+    println!("cargo:rerun-if-env-changed=ACCUMULATE_PROTOCOL_DIR");
+    println!("cargo:rerun-if-env-changed=ACCUMULATE_CODEGEN_PYTHON");
+    let Some(protocol_dir) = env::var_os("ACCUMULATE_PROTOCOL_DIR").map(PathBuf::from) else {
+        println!(
+            "cargo:warning=codegen feature enabled without ACCUMULATE_PROTOCOL_DIR; using the bundled generated types"
+        );
+        return Ok(());
+    };
+
+    let manifest_dir = PathBuf::from(
+        env::var("CARGO_MANIFEST_DIR").map_err(|_| "CARGO_MANIFEST_DIR not set".to_string())?,
+    );
+    let backends = manifest_dir.join("tooling").join("backends");
+    let out_dir = PathBuf::from(env::var("OUT_DIR").map_err(|_| "OUT_DIR not set".to_string())?)
+        .join("generated");
+    fs::create_dir_all(&out_dir)
+        .map_err(|err| format!("cannot create {}: {err}", out_dir.display()))?;
+    let python = env::var("ACCUMULATE_CODEGEN_PYTHON").unwrap_or_else(|_| "python3".to_string());
+
+    for (script, module, inputs) in GENERATORS {
+        for input in *inputs {
+            let path = protocol_dir.join(input);
+            if !path.is_file() {
+                return Err(format!("{} not found", path.display()));
+            }
+            println!("cargo:rerun-if-changed={}", path.display());
+        }
+        let script = backends.join(script);
+        println!("cargo:rerun-if-changed={}", script.display());
+
+        let output = Command::new(&python)
+            .arg(&script)
+            .env("ACCUMULATE_PROTOCOL_DIR", &protocol_dir)
+            .env("ACCUMULATE_CODEGEN_OUT", &out_dir)
+            .output()
+            .map_err(|err| format!("cannot run {python}: {err}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "{} failed\n{}{}",
+                script.display(),
+                String::from_utf8_lossy(&output.stdout),
+                String::from_utf8_lossy(&output.stderr)
+            ));
+        }
+        strip_inner_docs(&out_dir.join(module))?;
+    }
+
+    println!("cargo:rustc-cfg=accumulate_codegen");
+    Ok(())
+}
+
+// The modules are pulled in with `include!`, which rejects inner doc comments
+fn strip_inner_docs(path: &Path) -> Result<(), String> {
+    let source = fs::read_to_string(path)
+        .map_err(|err| format!("generator wrote no {}: {err}", path.display()))?;
+    let source: String = source
+        .lines()
+        .map(|line| {
+            line.strip_prefix("//!")
+                .map_or_else(|| line.to_string(), |rest| format!("//{rest}"))
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    fs::write(path, source).map_err(|err| format!("cannot rewrite {}: {err}", path.display()))
 }
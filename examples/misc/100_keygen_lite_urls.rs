@@ -197,8 +197,9 @@ async fn test_client_creation(v2_url: &str, v3_url: &str) -> Result<(), Box<dyn
     let v3_parsed = Url::parse(v3_url)?;
 
     let options = AccOptions {
-        timeout: Duration::from_secs(10),
+        request_timeout: Duration::from_secs(10),
         headers: std::collections::HashMap::new(),
+        ..Default::default()
     };
 
     println!("  Creating Accumulate client...");
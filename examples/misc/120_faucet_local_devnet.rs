@@ -108,8 +108,9 @@ async fn create_client(v2_url: &str, v3_url: &str) -> Result<AccumulateClient, B
     let v3_parsed = Url::parse(v3_url)?;
 
     let options = AccOptions {
-        timeout: Duration::from_secs(15),
+        request_timeout: Duration::from_secs(15),
         headers: std::collections::HashMap::new(),
+        ..Default::default()
     };
 
     AccumulateClient::from_endpoints(v2_parsed, v3_parsed, options).await
@@ -10,8 +10,8 @@
 
 use accumulate_client::{
     AccumulateClient, AccOptions, TxBody, SmartSigner,
-    poll_for_balance, derive_lite_identity_url,
-    KERMIT_V2, KERMIT_V3,
+    ensure_funded, derive_lite_identity_url,
+    KERMIT_V2, KERMIT_V3, ACME_BASE_UNITS,
 };
 use sha2::{Digest, Sha256};
 use serde_json::{json, Value};
@@ -50,34 +50,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // =========================================================
     println!("--- Step 2: Fund Account via Faucet ---\n");
 
-    println!("Requesting funds from faucet (3 times)...");
-    for i in 1..=3 {
-        let params = json!({"account": &lite_token_account});
-        match client.v3_client.call_v3::<Value>("faucet", params).await {
-            Ok(response) => {
-                let txid = response.get("transactionHash")
-                    .or_else(|| response.get("txid"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("submitted");
-                println!("  Faucet {}/3: {}", i, txid);
-            }
-            Err(e) => println!("  Faucet {}/3 failed: {}", i, e),
+    println!("Requesting funds from faucet until the account holds 30 ACME...");
+    let balance = match ensure_funded(&client, &lite_token_account, 30 * ACME_BASE_UNITS, std::time::Duration::from_secs(120)).await {
+        Ok(balance) => balance,
+        Err(e) => {
+            println!("ERROR: Account not funded: {}. Stopping.", e);
+            return Ok(());
         }
-        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-    }
-
-    // Wait for faucet transactions to settle
-    println!("\nWaiting 10 seconds for faucet transactions to settle...");
-    tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-
-    // Poll for balance
-    println!("\nPolling for balance...");
-    let balance = poll_for_balance(&client, &lite_token_account, 30).await;
-    if balance.is_none() || balance == Some(0) {
-        println!("ERROR: Account not funded. Stopping.");
-        return Ok(());
-    }
-    println!("Balance confirmed: {:?}\n", balance);
+    };
+    println!("Balance confirmed: {}\n", balance);
 
     // =========================================================
     // Step 3: Add credits to lite identity
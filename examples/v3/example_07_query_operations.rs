@@ -10,8 +10,8 @@
 
 use accumulate_client::{
     AccumulateClient, AccOptions, TxBody, SmartSigner,
-    poll_for_balance, poll_for_credits, derive_lite_identity_url,
-    KERMIT_V2, KERMIT_V3,
+    ensure_funded, poll_for_credits, derive_lite_identity_url,
+    KERMIT_V2, KERMIT_V3, ACME_BASE_UNITS,
 };
 use sha2::{Digest, Sha256};
 use serde_json::{json, Value};
@@ -62,27 +62,15 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     println!("Lite Token Account: {}", lite_token_account);
 
     // Fund via faucet (5 times for sufficient balance)
-    println!("Requesting funds from faucet...");
-    for i in 1..=5 {
-        let params = json!({"account": &lite_token_account});
-        match client.v3_client.call_v3::<Value>("faucet", params).await {
-            Ok(_) => println!("  Faucet {}/5: success", i),
-            Err(e) => println!("  Faucet {}/5 failed: {}", i, e),
+    println!("Requesting funds from faucet until the account holds 50 ACME...");
+    let balance = match ensure_funded(&client, &lite_token_account, 50 * ACME_BASE_UNITS, std::time::Duration::from_secs(120)).await {
+        Ok(balance) => balance,
+        Err(e) => {
+            println!("ERROR: Account not funded: {}. Stopping.", e);
+            return Ok(());
         }
-        tokio::time::sleep(std::time::Duration::from_secs(2)).await;
-    }
-
-    // Wait for settlement and poll for balance
-    println!("\nWaiting for transactions to settle...");
-    tokio::time::sleep(std::time::Duration::from_secs(10)).await;
-
-    println!("Polling for balance...");
-    let balance = poll_for_balance(&client, &lite_token_account, 30).await;
-    if balance.is_none() || balance == Some(0) {
-        println!("ERROR: Account not funded. Stopping.");
-        return Ok(());
-    }
-    println!("Balance confirmed: {:?}\n", balance);
+    };
+    println!("Balance confirmed: {}\n", balance);
 
     // =========================================================
     // Step 3: Query Lite Token Account
@@ -18,12 +18,14 @@
 //! AFTER (just a few lines per operation):
 //!   let acc = QuickStart::kermit().await?;
 //!   let wallet = acc.create_wallet();
-//!   acc.fund_wallet(&wallet, 5).await?;
+//!   acc.ensure_funded(&wallet, 50).await?;
 //!   let adi = acc.setup_adi(&wallet, "my-adi").await?;
 //!
 //! Run with: cargo run --example example_11_quickstart_demo
 
+use accumulate_client::progress::ProgressLog;
 use accumulate_client::{AccumulateClient, QuickStart};
+use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 #[tokio::main]
@@ -36,7 +38,10 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // STEP 1: Connect to Kermit Testnet (one line!)
     // ============================================================
     println!(">>> Step 1: Connect to Kermit Testnet");
-    let acc = QuickStart::kermit().await?;
+    // Log each step of the longer flows (setup_adi) as it runs
+    let acc = QuickStart::kermit()
+        .await?
+        .with_progress(Arc::new(ProgressLog::stdout()));
     println!("    Connected to Kermit Testnet\n");
 
     // ============================================================
@@ -50,10 +55,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
     // ============================================================
     // STEP 3: Fund wallet from faucet (one line!)
     // ============================================================
-    println!(">>> Step 3: Fund Wallet (faucet x5, wait 15s)");
-    acc.fund_wallet(&wallet, 5).await?;
-    let balance = acc.get_balance(&wallet).await;
-    println!("    Balance: {:?} ACME tokens\n", balance);
+    println!(">>> Step 3: Fund Wallet (faucet until it holds 50 ACME)");
+    let balance = acc.ensure_funded(&wallet, 50).await?;
+    println!("    Balance: {} base units\n", balance);
 
     // ============================================================
     // STEP 4: Create ADI with one call (automatically handles
@@ -0,0 +1,469 @@
+//! Accumulate `acc://` URLs
+//!
+//! An [`AccUrl`] is a parsed, validated account URL: an authority (an ADI
+//! such as `alice.acme` or a lite identity), an optional user info part (the
+//! transaction hash of a transaction ID) and a path of `/`-separated
+//! segments. Comparison and hashing ignore case, as they do on the network;
+//! display keeps the case the URL was written in.
+//!
+//! ```
+//! use accumulate_client::AccUrl;
+//!
+//! let page: AccUrl = "acc://alice.acme/book/1".parse().unwrap();
+//! assert_eq!(page.authority(), "alice.acme");
+//! assert_eq!(page.segments().collect::<Vec<_>>(), ["book", "1"]);
+//! assert_eq!(page.parent().unwrap(), "acc://Alice.acme/book".parse::<AccUrl>().unwrap());
+//! assert_eq!(page.identity().join("tokens").unwrap().as_str(), "acc://alice.acme/tokens");
+//!
+//! assert!(AccUrl::parse("alice.acme").is_err());
+//! assert!(AccUrl::parse("acc://alice.acme/a b").is_err());
+//! ```
+//!
+//! `AccUrl` dereferences to `str`, so it can be passed wherever the SDK takes
+//! a URL as `&str` ([`TxBody`](crate::helpers::TxBody),
+//! [`SmartSigner::new`](crate::helpers::SmartSigner::new), queries). It
+//! serializes as a plain string and validates when deserialized.
+
+use crate::errors::ValidationError;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use sha2::{Digest, Sha256};
+use std::cmp::Ordering;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::ops::Deref;
+use std::str::FromStr;
+use thiserror::Error;
+
+const SCHEME: &str = "acc://";
+
+/// Why a string is not a valid Accumulate URL
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AccUrlError {
+    /// The string does not start with `acc://`
+    #[error("{0} does not start with acc://")]
+    MissingScheme(String),
+
+    /// Nothing between `acc://` and the path
+    #[error("{0} has no authority")]
+    MissingAuthority(String),
+
+    /// A character that cannot appear in an account URL
+    #[error("{url} contains {character:?}")]
+    InvalidCharacter {
+        /// The URL as given
+        url: String,
+        /// The offending character
+        character: char,
+    },
+
+    /// Two consecutive slashes in the path
+    #[error("{0} has an empty path segment")]
+    EmptySegment(String),
+}
+
+impl From<AccUrlError> for ValidationError {
+    fn from(error: AccUrlError) -> Self {
+        Self::InvalidUrl(error.to_string())
+    }
+}
+
+/// A validated `acc://` URL
+///
+/// Stored as `acc://[user@]authority[/path]` with no trailing slash.
+#[derive(Clone)]
+pub struct AccUrl {
+    url: String,
+    /// Offset of the authority, past the scheme and any user info
+    authority_start: usize,
+    /// Offset of the path (or the end of the URL when there is none)
+    path_start: usize,
+}
+
+impl AccUrl {
+    /// Parse and validate `url`
+    ///
+    /// The scheme is required and may be in any case; a trailing slash is
+    /// dropped. Whitespace, control and non-ASCII characters, `?`, `#`, a
+    /// port and empty path segments are rejected.
+    pub fn parse(url: &str) -> Result<Self, AccUrlError> {
+        let rest = url
+            .get(..SCHEME.len())
+            .filter(|scheme| scheme.eq_ignore_ascii_case(SCHEME))
+            .map(|_| &url[SCHEME.len()..])
+            .ok_or_else(|| AccUrlError::MissingScheme(url.to_string()))?;
+        let rest = rest.strip_suffix('/').unwrap_or(rest);
+
+        if let Some(character) = rest.chars().find(|&c| {
+            !c.is_ascii() || c.is_ascii_whitespace() || c.is_ascii_control() || matches!(c, '?' | '#' | ':')
+        }) {
+            return Err(AccUrlError::InvalidCharacter {
+                url: url.to_string(),
+                character,
+            });
+        }
+
+        let (host, path) = rest.find('/').map_or((rest, ""), |i| rest.split_at(i));
+        let (user, authority) = match host.split_once('@') {
+            Some((user, authority)) => (Some(user), authority),
+            None => (None, host),
+        };
+        if authority.is_empty() {
+            return Err(AccUrlError::MissingAuthority(url.to_string()));
+        }
+        if let Some(character) = authority.chars().find(|&c| c == '@') {
+            return Err(AccUrlError::InvalidCharacter {
+                url: url.to_string(),
+                character,
+            });
+        }
+        if path.split('/').skip(1).any(str::is_empty) {
+            return Err(AccUrlError::EmptySegment(url.to_string()));
+        }
+
+        let authority_start = SCHEME.len() + user.map_or(0, |u| u.len() + 1);
+        Ok(Self {
+            url: format!("{SCHEME}{rest}"),
+            authority_start,
+            path_start: authority_start + authority.len(),
+        })
+    }
+
+    /// The URL as a string
+    #[must_use]
+    pub fn as_str(&self) -> &str {
+        &self.url
+    }
+
+    /// The authority: an ADI name or a lite identity
+    #[must_use]
+    pub fn authority(&self) -> &str {
+        &self.url[self.authority_start..self.path_start]
+    }
+
+    /// The part before `@`, e.g. the transaction hash of a transaction ID
+    #[must_use]
+    pub fn user_info(&self) -> Option<&str> {
+        (self.authority_start > SCHEME.len())
+            .then(|| &self.url[SCHEME.len()..self.authority_start - 1])
+    }
+
+    /// The path with its leading slash, or `""` for a root URL
+    #[must_use]
+    pub fn path(&self) -> &str {
+        &self.url[self.path_start..]
+    }
+
+    /// The path segments, in order
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.path().split('/').skip(1)
+    }
+
+    /// Whether the URL has no path
+    #[must_use]
+    pub fn is_root(&self) -> bool {
+        self.path().is_empty()
+    }
+
+    /// The root URL `acc://authority`, without user info or path
+    #[must_use]
+    pub fn identity(&self) -> Self {
+        Self {
+            url: format!("{SCHEME}{}", self.authority()),
+            authority_start: SCHEME.len(),
+            path_start: SCHEME.len() + self.authority().len(),
+        }
+    }
+
+    /// The URL with its last path segment removed, or `None` for a root URL
+    #[must_use]
+    pub fn parent(&self) -> Option<Self> {
+        let end = self.url.rfind('/').filter(|&i| i >= self.path_start)?;
+        Some(Self {
+            url: self.url[..end].to_string(),
+            ..*self
+        })
+    }
+
+    /// The URL with `path` appended; `path` may hold several segments
+    pub fn join(&self, path: &str) -> Result<Self, AccUrlError> {
+        let path = path.trim_start_matches('/');
+        Self::parse(&format!("{}/{path}", self.url))
+    }
+
+    /// Without user info, e.g. the account of a transaction ID
+    #[must_use]
+    pub fn without_user_info(&self) -> Self {
+        Self::parse(&format!("{SCHEME}{}", &self.url[self.authority_start..]))
+            .unwrap_or_else(|_| self.clone())
+    }
+
+    /// Whether the authority is a lite identity: a hex key hash followed by
+    /// its 4-byte checksum
+    #[must_use]
+    pub fn is_lite(&self) -> bool {
+        let authority = self.authority();
+        let Some(split) = authority.len().checked_sub(8).filter(|&n| n > 0) else {
+            return false;
+        };
+        if !authority.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return false;
+        }
+        let (key_hash, checksum) = authority.split_at(split);
+        let digest = Sha256::digest(key_hash.to_ascii_lowercase().as_bytes());
+        hex::encode(&digest[28..]).eq_ignore_ascii_case(checksum)
+    }
+
+    /// Whether this is a lite identity URL with no path
+    #[must_use]
+    pub fn is_lite_identity(&self) -> bool {
+        self.is_root() && self.is_lite()
+    }
+
+    /// Whether this is a lite token account, e.g. `acc://<lite>/ACME`
+    #[must_use]
+    pub fn is_lite_token_account(&self) -> bool {
+        !self.is_root() && self.is_lite()
+    }
+
+    /// The token a lite token account holds: its path as a URL
+    ///
+    /// `acc://<lite>/ACME` holds `acc://ACME`, and
+    /// `acc://<lite>/alice.acme/coin` holds `acc://alice.acme/coin`.
+    #[must_use]
+    pub fn lite_token_url(&self) -> Option<Self> {
+        if !self.is_lite_token_account() {
+            return None;
+        }
+        Self::parse(&format!("{SCHEME}{}", &self.path()[1..])).ok()
+    }
+
+    fn key(&self) -> impl Iterator<Item = u8> + '_ {
+        self.url.bytes().map(|b| b.to_ascii_lowercase())
+    }
+}
+
+impl Deref for AccUrl {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.url
+    }
+}
+
+impl AsRef<str> for AccUrl {
+    fn as_ref(&self) -> &str {
+        &self.url
+    }
+}
+
+impl fmt::Display for AccUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.url)
+    }
+}
+
+impl fmt::Debug for AccUrl {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "AccUrl({:?})", self.url)
+    }
+}
+
+impl PartialEq for AccUrl {
+    fn eq(&self, other: &Self) -> bool {
+        self.url.eq_ignore_ascii_case(&other.url)
+    }
+}
+
+impl Eq for AccUrl {}
+
+impl PartialEq<str> for AccUrl {
+    fn eq(&self, other: &str) -> bool {
+        Self::parse(other).is_ok_and(|other| *self == other)
+    }
+}
+
+impl PartialEq<&str> for AccUrl {
+    fn eq(&self, other: &&str) -> bool {
+        *self == **other
+    }
+}
+
+impl Hash for AccUrl {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for byte in self.key() {
+            state.write_u8(byte);
+        }
+    }
+}
+
+impl PartialOrd for AccUrl {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for AccUrl {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key().cmp(other.key())
+    }
+}
+
+impl FromStr for AccUrl {
+    type Err = AccUrlError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<&str> for AccUrl {
+    type Error = AccUrlError;
+
+    fn try_from(url: &str) -> Result<Self, Self::Error> {
+        Self::parse(url)
+    }
+}
+
+impl TryFrom<String> for AccUrl {
+    type Error = AccUrlError;
+
+    fn try_from(url: String) -> Result<Self, Self::Error> {
+        Self::parse(&url)
+    }
+}
+
+impl From<AccUrl> for String {
+    fn from(url: AccUrl) -> Self {
+        url.url
+    }
+}
+
+impl Serialize for AccUrl {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.url)
+    }
+}
+
+impl<'de> Deserialize<'de> for AccUrl {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let url = String::deserialize(deserializer)?;
+        Self::parse(&url).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::{derive_lite_token_account_url, TxBody};
+    use std::collections::HashSet;
+
+    fn url(s: &str) -> AccUrl {
+        AccUrl::parse(s).unwrap()
+    }
+
+    #[test]
+    fn test_parse_parts() {
+        let txid = url("ACC://0123abcd@Alice.acme/book/1/");
+        assert_eq!(txid.as_str(), "acc://0123abcd@Alice.acme/book/1");
+        assert_eq!(txid.user_info(), Some("0123abcd"));
+        assert_eq!(txid.authority(), "Alice.acme");
+        assert_eq!(txid.path(), "/book/1");
+        assert_eq!(txid.without_user_info().as_str(), "acc://Alice.acme/book/1");
+        assert_eq!(txid.identity().as_str(), "acc://Alice.acme");
+
+        let root = url("acc://alice.acme");
+        assert!(root.is_root());
+        assert_eq!((root.user_info(), root.path()), (None, ""));
+        assert_eq!(root.segments().count(), 0);
+        assert!(root.parent().is_none());
+    }
+
+    #[test]
+    fn test_parse_rejects() {
+        for (input, expected) in [
+            ("alice.acme", AccUrlError::MissingScheme("alice.acme".into())),
+            ("acc://", AccUrlError::MissingAuthority("acc://".into())),
+            ("acc:///tokens", AccUrlError::MissingAuthority("acc:///tokens".into())),
+            ("acc://hash@/x", AccUrlError::MissingAuthority("acc://hash@/x".into())),
+            ("acc://a.acme//x", AccUrlError::EmptySegment("acc://a.acme//x".into())),
+        ] {
+            assert_eq!(AccUrl::parse(input).unwrap_err(), expected, "{input}");
+        }
+        for input in [
+            "acc://a b.acme",
+            "acc://a.acme/x?y",
+            "acc://a.acme:80",
+            "acc://a.acme/é",
+            "acc://a@b@c.acme",
+        ] {
+            assert!(
+                matches!(AccUrl::parse(input), Err(AccUrlError::InvalidCharacter { .. })),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parent_and_join() {
+        let book = url("acc://alice.acme/book");
+        let page = book.join("1").unwrap();
+        assert_eq!(page.as_str(), "acc://alice.acme/book/1");
+        assert_eq!(page.parent().unwrap(), book);
+        assert_eq!(book.parent().unwrap().as_str(), "acc://alice.acme");
+        assert_eq!(
+            book.identity().join("/data/log").unwrap().segments().collect::<Vec<_>>(),
+            ["data", "log"]
+        );
+        assert!(book.join("a//b").is_err());
+        assert!(book.join("a b").is_err());
+    }
+
+    #[test]
+    fn test_case_insensitive_equality() {
+        let a = url("acc://Alice.ACME/Tokens");
+        let b = url("acc://alice.acme/tokens/");
+        assert_eq!(a, b);
+        assert_eq!(a, "acc://alice.acme/tokens");
+        assert_ne!(a, "alice.acme/tokens");
+        assert_eq!(a.cmp(&b), Ordering::Equal);
+        assert_eq!(HashSet::from([a.clone(), b]).len(), 1);
+        assert_eq!(a.to_string(), "acc://Alice.ACME/Tokens");
+    }
+
+    #[test]
+    fn test_lite_detection() {
+        let lta = url(&derive_lite_token_account_url(&[7u8; 32]));
+        assert!(lta.is_lite() && lta.is_lite_token_account());
+        assert!(!lta.is_lite_identity());
+        assert!(lta.identity().is_lite_identity());
+        assert_eq!(lta.lite_token_url().unwrap(), "acc://ACME");
+
+        let custom = lta.identity().join("alice.acme/coin").unwrap();
+        assert_eq!(custom.lite_token_url().unwrap(), "acc://alice.acme/coin");
+
+        // Bad checksum, and an ADI
+        let mut tampered = lta.authority().to_string();
+        tampered.replace_range(..1, if tampered.starts_with('0') { "1" } else { "0" });
+        assert!(!url(&format!("acc://{tampered}")).is_lite());
+        assert!(!url("acc://alice.acme/ACME").is_lite());
+        assert!(url("acc://alice.acme/ACME").lite_token_url().is_none());
+    }
+
+    #[test]
+    fn test_serde_and_str_interop() {
+        let tokens = url("acc://alice.acme/tokens");
+        let json = serde_json::to_value(&tokens).unwrap();
+        assert_eq!(json, "acc://alice.acme/tokens");
+        assert_eq!(serde_json::from_value::<AccUrl>(json).unwrap(), tokens);
+        assert!(serde_json::from_value::<AccUrl>("alice.acme".into()).is_err());
+
+        // Derefs to &str for the string-based builders
+        let body = TxBody::create_token_account(&tokens, "acc://ACME");
+        assert_eq!(body["url"], "acc://alice.acme/tokens");
+        assert_eq!(
+            ValidationError::from(AccUrl::parse("x").unwrap_err()).to_string(),
+            "Invalid URL: x does not start with acc://"
+        );
+    }
+}
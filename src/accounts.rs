@@ -0,0 +1,559 @@
+//! Typed account states decoded from query results
+//!
+//! A V3 default query returns the account under `account`, shaped by its
+//! `type`. [`AccountState::from_query`] picks the matching struct, so callers
+//! can match on the variant instead of reading fields out of JSON:
+//!
+//! ```
+//! use accumulate_client::accounts::AccountState;
+//! use serde_json::json;
+//!
+//! let result = json!({"recordType": "account", "account": {
+//!     "type": "tokenAccount",
+//!     "url": "acc://alice.acme/tokens",
+//!     "tokenUrl": "acc://ACME",
+//!     "balance": "150000000",
+//!     "authorities": [{"url": "acc://alice.acme/book"}]
+//! }});
+//! match AccountState::from_query(&result).unwrap() {
+//!     AccountState::TokenAccount(account) => assert_eq!(account.balance_units(), Some(150_000_000)),
+//!     other => panic!("unexpected {other:?}"),
+//! }
+//! ```
+//!
+//! The structs follow the JSON the network sends (camelCase, hex hashes,
+//! big amounts as decimal strings), unlike the binary-oriented
+//! [`protocol_types`](crate::protocol_types). System accounts such as ledgers
+//! decode to [`AccountState::Other`].
+
+use crate::generated::enums::{AccountType, AllowedTransactionBit, BookType};
+use crate::protocol::AllowedTransactions;
+use crate::types::AccountAuthority;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Why a query result could not be decoded into an [`AccountState`]
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AccountDecodeError {
+    /// The result has no `account` field
+    #[error("Query result has no account")]
+    MissingAccount,
+
+    /// The account has no `type` field
+    #[error("Account has no type")]
+    MissingType,
+
+    /// The fields do not match the account's type
+    #[error("Invalid {account_type} account: {reason}")]
+    Invalid {
+        /// The account's `type`
+        account_type: String,
+        /// What did not decode
+        reason: String,
+    },
+}
+
+/// A lite identity, the signer behind lite token and data accounts
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiteIdentity {
+    /// Account URL
+    pub url: String,
+    /// Credits, in hundredths of a credit
+    #[serde(default)]
+    pub credit_balance: u64,
+    /// Timestamp of the last signature
+    #[serde(default)]
+    pub last_used_on: u64,
+}
+
+/// A lite token account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiteTokenAccount {
+    /// Account URL
+    pub url: String,
+    /// Token issuer URL
+    pub token_url: String,
+    /// Balance in base units, as a decimal string
+    pub balance: String,
+    /// Major block height until which the account is locked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_height: Option<u64>,
+}
+
+impl LiteTokenAccount {
+    /// Balance in base units
+    #[must_use]
+    pub fn balance_units(&self) -> Option<u128> {
+        self.balance.parse().ok()
+    }
+}
+
+/// An ADI
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Identity {
+    /// Account URL
+    pub url: String,
+    /// Authority set
+    #[serde(default)]
+    pub authorities: Vec<AccountAuthority>,
+}
+
+/// A key book
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyBook {
+    /// Account URL
+    pub url: String,
+    /// Authority set
+    #[serde(default)]
+    pub authorities: Vec<AccountAuthority>,
+    /// Book type; `normal` unless the book belongs to the network
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub book_type: Option<BookType>,
+    /// Number of pages
+    #[serde(default)]
+    pub page_count: u64,
+}
+
+/// A key on a key page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeySpec {
+    /// Hash of the public key (hex); absent for delegate-only entries
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub public_key_hash: Option<String>,
+    /// Timestamp of the key's last signature
+    #[serde(default)]
+    pub last_used_on: u64,
+    /// Authority the entry delegates to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub delegate: Option<String>,
+}
+
+/// A key page
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyPage {
+    /// Account URL
+    pub url: String,
+    /// Book the page belongs to
+    pub key_book: String,
+    /// Credits, in hundredths of a credit
+    #[serde(default)]
+    pub credit_balance: u64,
+    /// Signatures needed to execute a transaction
+    #[serde(default)]
+    pub accept_threshold: u64,
+    /// Rejections needed to reject a transaction
+    #[serde(default)]
+    pub reject_threshold: u64,
+    /// Responses needed before a transaction can execute
+    #[serde(default)]
+    pub response_threshold: u64,
+    /// Blocks a transaction must wait before it can execute
+    #[serde(default)]
+    pub block_threshold: u64,
+    /// Page version, bumped by every key or threshold change
+    #[serde(default)]
+    pub version: u64,
+    /// Keys on the page
+    #[serde(default)]
+    pub keys: Vec<KeySpec>,
+    /// Transaction types the page may not sign
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub transaction_blacklist: Vec<String>,
+}
+
+impl KeyPage {
+    /// The transaction types the page may not sign, as a mask
+    ///
+    /// Names that cannot be barred are ignored.
+    #[must_use]
+    pub fn blacklist(&self) -> AllowedTransactions {
+        self.transaction_blacklist
+            .iter()
+            .filter_map(|name| AllowedTransactionBit::for_transaction(name))
+            .collect()
+    }
+
+    /// Position of the key with `public_key_hash` on the page
+    #[must_use]
+    pub fn key_index(&self, public_key_hash: &[u8]) -> Option<usize> {
+        let hash = hex::encode(public_key_hash);
+        self.keys.iter().position(|key| {
+            key.public_key_hash
+                .as_deref()
+                .is_some_and(|h| h.eq_ignore_ascii_case(&hash))
+        })
+    }
+}
+
+/// An ADI token account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAccount {
+    /// Account URL
+    pub url: String,
+    /// Authority set
+    #[serde(default)]
+    pub authorities: Vec<AccountAuthority>,
+    /// Token issuer URL
+    pub token_url: String,
+    /// Balance in base units, as a decimal string
+    pub balance: String,
+}
+
+impl TokenAccount {
+    /// Balance in base units
+    #[must_use]
+    pub fn balance_units(&self) -> Option<u128> {
+        self.balance.parse().ok()
+    }
+}
+
+/// A lite data account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LiteDataAccount {
+    /// Account URL
+    pub url: String,
+}
+
+/// An ADI data account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DataAccount {
+    /// Account URL
+    pub url: String,
+    /// Authority set
+    #[serde(default)]
+    pub authorities: Vec<AccountAuthority>,
+    /// Entry written when the account was created, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<Value>,
+}
+
+/// A token issuer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenIssuer {
+    /// Account URL
+    pub url: String,
+    /// Authority set
+    #[serde(default)]
+    pub authorities: Vec<AccountAuthority>,
+    /// Token symbol
+    pub symbol: String,
+    /// Decimal places of one token
+    #[serde(default)]
+    pub precision: u64,
+    /// URL of the token's properties
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub properties: Option<String>,
+    /// Base units issued so far, as a decimal string
+    #[serde(default)]
+    pub issued: String,
+    /// Most base units that may ever be issued, if capped
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub supply_limit: Option<String>,
+}
+
+impl TokenIssuer {
+    /// Base units that may still be issued; `None` when uncapped
+    #[must_use]
+    pub fn remaining_supply(&self) -> Option<u128> {
+        let limit: u128 = self.supply_limit.as_ref()?.parse().ok()?;
+        let issued: u128 = self.issued.parse().unwrap_or(0);
+        Some(limit.saturating_sub(issued))
+    }
+}
+
+/// Any account, by its `type`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AccountState {
+    /// `liteIdentity`
+    LiteIdentity(LiteIdentity),
+    /// `liteTokenAccount`
+    LiteTokenAccount(LiteTokenAccount),
+    /// `identity`
+    Identity(Identity),
+    /// `keyBook`
+    KeyBook(KeyBook),
+    /// `keyPage`
+    KeyPage(KeyPage),
+    /// `tokenAccount`
+    TokenAccount(TokenAccount),
+    /// `liteDataAccount`
+    LiteDataAccount(LiteDataAccount),
+    /// `dataAccount`
+    DataAccount(DataAccount),
+    /// `tokenIssuer`
+    TokenIssuer(TokenIssuer),
+    /// Any other type (ledgers, unknown signers), kept as sent
+    Other(Value),
+}
+
+impl AccountState {
+    /// Decode the `account` field of a V3 query result
+    pub fn from_query(result: &Value) -> Result<Self, AccountDecodeError> {
+        Self::from_account(
+            result
+                .get("account")
+                .ok_or(AccountDecodeError::MissingAccount)?,
+        )
+    }
+
+    /// Decode an account by its `type`
+    pub fn from_account(account: &Value) -> Result<Self, AccountDecodeError> {
+        let account_type = account
+            .get("type")
+            .and_then(Value::as_str)
+            .ok_or(AccountDecodeError::MissingType)?;
+        Ok(match account_type {
+            "liteIdentity" => Self::LiteIdentity(decode(account_type, account)?),
+            "liteTokenAccount" => Self::LiteTokenAccount(decode(account_type, account)?),
+            "identity" => Self::Identity(decode(account_type, account)?),
+            "keyBook" => Self::KeyBook(decode(account_type, account)?),
+            "keyPage" => Self::KeyPage(decode(account_type, account)?),
+            "tokenAccount" => Self::TokenAccount(decode(account_type, account)?),
+            "liteDataAccount" => Self::LiteDataAccount(decode(account_type, account)?),
+            "dataAccount" => Self::DataAccount(decode(account_type, account)?),
+            "tokenIssuer" => Self::TokenIssuer(decode(account_type, account)?),
+            _ => Self::Other(account.clone()),
+        })
+    }
+
+    /// The account's type; `None` for an [`Other`](Self::Other) account of
+    /// a type this SDK does not know
+    #[must_use]
+    pub fn account_type(&self) -> Option<AccountType> {
+        Some(match self {
+            Self::LiteIdentity(_) => AccountType::LiteIdentity,
+            Self::LiteTokenAccount(_) => AccountType::LiteTokenAccount,
+            Self::Identity(_) => AccountType::Identity,
+            Self::KeyBook(_) => AccountType::KeyBook,
+            Self::KeyPage(_) => AccountType::KeyPage,
+            Self::TokenAccount(_) => AccountType::TokenAccount,
+            Self::LiteDataAccount(_) => AccountType::LiteDataAccount,
+            Self::DataAccount(_) => AccountType::DataAccount,
+            Self::TokenIssuer(_) => AccountType::TokenIssuer,
+            Self::Other(account) => serde_json::from_value(account.get("type")?.clone()).ok()?,
+        })
+    }
+
+    /// Account URL
+    #[must_use]
+    pub fn url(&self) -> &str {
+        match self {
+            Self::LiteIdentity(a) => &a.url,
+            Self::LiteTokenAccount(a) => &a.url,
+            Self::Identity(a) => &a.url,
+            Self::KeyBook(a) => &a.url,
+            Self::KeyPage(a) => &a.url,
+            Self::TokenAccount(a) => &a.url,
+            Self::LiteDataAccount(a) => &a.url,
+            Self::DataAccount(a) => &a.url,
+            Self::TokenIssuer(a) => &a.url,
+            Self::Other(account) => account.get("url").and_then(Value::as_str).unwrap_or(""),
+        }
+    }
+
+    /// Authority set; empty for lite accounts and key pages, which are
+    /// governed by their identity or book
+    #[must_use]
+    pub fn authorities(&self) -> &[AccountAuthority] {
+        match self {
+            Self::Identity(a) => &a.authorities,
+            Self::KeyBook(a) => &a.authorities,
+            Self::TokenAccount(a) => &a.authorities,
+            Self::DataAccount(a) => &a.authorities,
+            Self::TokenIssuer(a) => &a.authorities,
+            _ => &[],
+        }
+    }
+
+    /// The account as the network sends it, with its `type`
+    #[must_use]
+    pub fn to_value(&self) -> Value {
+        let (account_type, mut value) = match self {
+            Self::LiteIdentity(a) => ("liteIdentity", serde_json::to_value(a)),
+            Self::LiteTokenAccount(a) => ("liteTokenAccount", serde_json::to_value(a)),
+            Self::Identity(a) => ("identity", serde_json::to_value(a)),
+            Self::KeyBook(a) => ("keyBook", serde_json::to_value(a)),
+            Self::KeyPage(a) => ("keyPage", serde_json::to_value(a)),
+            Self::TokenAccount(a) => ("tokenAccount", serde_json::to_value(a)),
+            Self::LiteDataAccount(a) => ("liteDataAccount", serde_json::to_value(a)),
+            Self::DataAccount(a) => ("dataAccount", serde_json::to_value(a)),
+            Self::TokenIssuer(a) => ("tokenIssuer", serde_json::to_value(a)),
+            Self::Other(account) => return account.clone(),
+        };
+        if let Ok(Value::Object(fields)) = &mut value {
+            fields.insert("type".to_string(), Value::String(account_type.to_string()));
+        }
+        value.unwrap_or(Value::Null)
+    }
+}
+
+impl Serialize for AccountState {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.to_value().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for AccountState {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let account = Value::deserialize(deserializer)?;
+        Self::from_account(&account).map_err(serde::de::Error::custom)
+    }
+}
+
+fn decode<T: serde::de::DeserializeOwned>(
+    account_type: &str,
+    account: &Value,
+) -> Result<T, AccountDecodeError> {
+    serde_json::from_value(account.clone()).map_err(|e| AccountDecodeError::Invalid {
+        account_type: account_type.to_string(),
+        reason: e.to_string(),
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_decode_each_type() {
+        let page = AccountState::from_account(&json!({
+            "type": "keyPage",
+            "url": "acc://alice.acme/book/1",
+            "keyBook": "acc://alice.acme/book",
+            "creditBalance": 49_000,
+            "acceptThreshold": 2,
+            "version": 3,
+            "keys": [
+                {"publicKeyHash": "AB".repeat(32), "lastUsedOn": 1_700_000_000},
+                {"delegate": "acc://ops.acme/book"}
+            ],
+            "transactionBlacklist": ["updateKeyPage"]
+        }))
+        .unwrap();
+        let AccountState::KeyPage(key_page) = &page else {
+            unreachable!("decoded {page:?}");
+        };
+        assert_eq!((key_page.accept_threshold, key_page.version), (2, 3));
+        assert_eq!(key_page.key_index(&[0xab; 32]), Some(0));
+        assert_eq!(
+            key_page.blacklist(),
+            AllowedTransactions::from(AllowedTransactionBit::UpdateKeyPage)
+        );
+        assert_eq!(
+            key_page.keys[1].delegate.as_deref(),
+            Some("acc://ops.acme/book")
+        );
+        assert_eq!(page.account_type(), Some(AccountType::KeyPage));
+        assert!(page.authorities().is_empty());
+
+        let book = AccountState::from_account(&json!({
+            "type": "keyBook",
+            "url": "acc://alice.acme/book",
+            "bookType": "normal",
+            "pageCount": 1,
+            "authorities": [{"url": "acc://alice.acme/book"}]
+        }))
+        .unwrap();
+        assert_eq!(book.authorities()[0].url, "acc://alice.acme/book");
+
+        let issuer = AccountState::from_account(&json!({
+            "type": "tokenIssuer",
+            "url": "acc://alice.acme/coin",
+            "symbol": "COIN",
+            "precision": 4,
+            "issued": "7500",
+            "supplyLimit": "10000"
+        }))
+        .unwrap();
+        let AccountState::TokenIssuer(issuer) = issuer else {
+            unreachable!("decoded {issuer:?}");
+        };
+        assert_eq!(issuer.remaining_supply(), Some(2500));
+
+        let lite = AccountState::from_account(&json!({
+            "type": "liteTokenAccount",
+            "url": "acc://abc/ACME",
+            "tokenUrl": "acc://ACME",
+            "balance": "1000",
+            "lockHeight": 9
+        }))
+        .unwrap();
+        let AccountState::LiteTokenAccount(lite) = lite else {
+            unreachable!("decoded {lite:?}");
+        };
+        assert_eq!(
+            (lite.balance_units(), lite.lock_height),
+            (Some(1000), Some(9))
+        );
+
+        for (account_type, fields) in [
+            ("liteIdentity", json!({"creditBalance": 10})),
+            ("identity", json!({})),
+            ("liteDataAccount", json!({})),
+            (
+                "dataAccount",
+                json!({"entry": {"type": "doubleHash", "data": []}}),
+            ),
+        ] {
+            let mut account = json!({"type": account_type, "url": "acc://x.acme"});
+            account
+                .as_object_mut()
+                .unwrap()
+                .extend(fields.as_object().unwrap().clone());
+            let state = AccountState::from_account(&account).unwrap();
+            assert!(!matches!(state, AccountState::Other(_)), "{account_type}");
+            assert_eq!(state.url(), "acc://x.acme");
+        }
+    }
+
+    #[test]
+    fn test_unknown_types_and_errors() {
+        let ledger = json!({"type": "systemLedger", "url": "acc://dn.acme/ledger", "index": 12});
+        let state = AccountState::from_account(&ledger).unwrap();
+        assert_eq!(state, AccountState::Other(ledger.clone()));
+        assert_eq!(state.account_type(), Some(AccountType::SystemLedger));
+        assert_eq!(state.url(), "acc://dn.acme/ledger");
+
+        assert_eq!(
+            AccountState::from_query(&json!({"recordType": "account"})),
+            Err(AccountDecodeError::MissingAccount)
+        );
+        assert_eq!(
+            AccountState::from_account(&json!({"url": "acc://x.acme"})),
+            Err(AccountDecodeError::MissingType)
+        );
+        let err =
+            AccountState::from_account(&json!({"type": "tokenAccount", "url": "acc://x.acme"}))
+                .unwrap_err();
+        assert!(
+            err.to_string().starts_with("Invalid tokenAccount account"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_serde_round_trip_keeps_type() {
+        let account = json!({
+            "type": "tokenAccount",
+            "url": "acc://alice.acme/tokens",
+            "authorities": [{"url": "acc://alice.acme/book"}],
+            "tokenUrl": "acc://ACME",
+            "balance": "5"
+        });
+        let state: AccountState = serde_json::from_value(account.clone()).unwrap();
+        assert_eq!(serde_json::to_value(&state).unwrap(), account);
+    }
+}
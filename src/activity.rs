@@ -0,0 +1,532 @@
+//! Wallet activity reports for accounting exports
+//!
+//! [`AccumulateClient::activity_report`] walks an account's main chain, pulls
+//! every token movement out of the transactions it finds, looks up each
+//! token's symbol and precision, and totals inflows and outflows per token and
+//! per counterparty. Amounts stay in integer base units; use
+//! [`TokenSummary::format`] for display.
+//!
+//! The aggregation itself is [`build_activity_report`], which works on
+//! transactions already fetched from the network.
+
+use crate::amounts::{format_base_units, ACME_PRECISION};
+use crate::client::AccumulateClient;
+use crate::json_rpc_client::JsonRpcError;
+use crate::types::RangeOptions;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// URL of the ACME token issuer
+pub const ACME_TOKEN_URL: &str = "acc://ACME";
+
+/// Symbol and precision of a token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TokenInfo {
+    /// Token issuer URL
+    pub url: String,
+    /// Ticker symbol, or the issuer URL when metadata could not be loaded
+    pub symbol: String,
+    /// Number of decimal places, 0 when metadata could not be loaded
+    pub precision: u32,
+}
+
+impl TokenInfo {
+    /// Metadata for ACME
+    #[must_use]
+    pub fn acme() -> Self {
+        Self {
+            url: ACME_TOKEN_URL.to_string(),
+            symbol: "ACME".to_string(),
+            precision: ACME_PRECISION,
+        }
+    }
+}
+
+/// Direction of a token movement, relative to the reported account
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Direction {
+    /// Tokens received by the account
+    Inflow,
+    /// Tokens sent, burned or spent on credits by the account
+    Outflow,
+}
+
+/// A single token movement extracted from a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Transfer {
+    /// Hash of the transaction that moved the tokens
+    pub tx_hash: String,
+    /// Transaction type, e.g. `sendTokens` or `syntheticDepositTokens`
+    pub tx_type: String,
+    /// Token issuer URL
+    pub token: String,
+    /// The other side of the movement
+    pub counterparty: String,
+    /// Whether tokens came in or went out
+    pub direction: Direction,
+    /// Amount in base units
+    pub amount: u128,
+}
+
+/// Totals for one counterparty within a token
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CounterpartySummary {
+    /// Counterparty URL
+    pub counterparty: String,
+    /// Base units received from the counterparty
+    pub inflow: u128,
+    /// Base units sent to the counterparty
+    pub outflow: u128,
+    /// Number of movements
+    pub transfers: usize,
+}
+
+/// Totals for one token
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenSummary {
+    /// Token metadata
+    pub token: TokenInfo,
+    /// Base units received
+    pub inflow: u128,
+    /// Base units sent
+    pub outflow: u128,
+    /// Per-counterparty totals, ordered by counterparty URL
+    pub counterparties: Vec<CounterpartySummary>,
+}
+
+impl TokenSummary {
+    /// Inflow minus outflow, in base units
+    #[must_use]
+    pub fn net(&self) -> i128 {
+        i128::try_from(self.inflow).unwrap_or(i128::MAX)
+            - i128::try_from(self.outflow).unwrap_or(i128::MAX)
+    }
+
+    /// Format a base-unit amount of this token as a decimal string
+    #[must_use]
+    pub fn format(&self, units: u128) -> String {
+        format_base_units(units, self.token.precision)
+    }
+}
+
+/// Inflow/outflow summary of an account over a range of its history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActivityReport {
+    /// Reported account
+    pub account: String,
+    /// Per-token totals, ordered by token URL
+    pub tokens: Vec<TokenSummary>,
+    /// Every movement, in history order
+    pub transfers: Vec<Transfer>,
+    /// Transactions in the range that moved no tokens
+    pub other_transactions: usize,
+}
+
+impl ActivityReport {
+    /// Totals for a token, by issuer URL
+    #[must_use]
+    pub fn token(&self, url: &str) -> Option<&TokenSummary> {
+        self.tokens.iter().find(|t| same_url(&t.token.url, url))
+    }
+}
+
+impl AccumulateClient {
+    /// Build an activity report for a token account over a range of its main chain
+    ///
+    /// `range` selects main-chain entries as in [`crate::types::ChainQuery`];
+    /// entries are always requested expanded. Token metadata is looked up once
+    /// per token; a token whose issuer cannot be queried is reported with its
+    /// URL as symbol and precision 0.
+    pub async fn activity_report(
+        &self,
+        account: &str,
+        range: RangeOptions,
+    ) -> Result<ActivityReport, JsonRpcError> {
+        let account_record: Value = self
+            .v3_client
+            .call_v3("query", json!({ "url": account }))
+            .await?;
+        let account_token = token_url_of(&account_record);
+
+        let range = RangeOptions {
+            expand: Some(true),
+            ..range
+        };
+        let history: Value = self
+            .v3_client
+            .call_v3(
+                "query",
+                json!({
+                    "url": account,
+                    "query": {"queryType": "chain", "name": "main", "range": range}
+                }),
+            )
+            .await?;
+        let transactions = history_transactions(&history);
+
+        let mut token_urls: Vec<String> = transactions
+            .iter()
+            .flat_map(|(hash, tx)| extract_transfers(account, account_token.as_deref(), hash, tx))
+            .map(|t| t.token)
+            .collect();
+        token_urls.sort_by_key(|u| u.to_ascii_lowercase());
+        token_urls.dedup_by(|a, b| same_url(a, b));
+
+        let mut tokens = Vec::with_capacity(token_urls.len());
+        for url in token_urls {
+            tokens.push(self.token_metadata(&url).await);
+        }
+
+        Ok(build_activity_report(
+            account,
+            account_token.as_deref(),
+            &transactions,
+            &tokens,
+        ))
+    }
+
+    async fn token_metadata(&self, url: &str) -> TokenInfo {
+        if same_url(url, ACME_TOKEN_URL) {
+            return TokenInfo::acme();
+        }
+        let issuer = self.token_info(url).await.ok();
+        TokenInfo {
+            url: url.to_string(),
+            symbol: issuer
+                .as_ref()
+                .map_or_else(|| url.to_string(), |i| i.symbol.clone()),
+            precision: issuer
+                .and_then(|i| u32::try_from(i.precision).ok())
+                .unwrap_or(0),
+        }
+    }
+}
+
+/// Aggregate transactions into an activity report
+///
+/// `transactions` are `(hash, {header, body})` pairs. `account_token` is the
+/// token URL of the reported account, needed for transaction types whose body
+/// does not name the token. Tokens missing from `tokens` are reported with
+/// their URL as symbol and precision 0.
+#[must_use]
+pub fn build_activity_report(
+    account: &str,
+    account_token: Option<&str>,
+    transactions: &[(String, Value)],
+    tokens: &[TokenInfo],
+) -> ActivityReport {
+    let mut transfers = Vec::new();
+    let mut other_transactions = 0;
+    for (hash, tx) in transactions {
+        let found = extract_transfers(account, account_token, hash, tx);
+        if found.is_empty() {
+            other_transactions += 1;
+        }
+        transfers.extend(found);
+    }
+
+    let mut by_token: BTreeMap<String, (TokenInfo, BTreeMap<String, CounterpartySummary>)> =
+        BTreeMap::new();
+    for transfer in &transfers {
+        let (_, counterparties) = by_token
+            .entry(transfer.token.to_ascii_lowercase())
+            .or_insert_with(|| {
+                let info = tokens
+                    .iter()
+                    .find(|t| same_url(&t.url, &transfer.token))
+                    .cloned()
+                    .unwrap_or_else(|| TokenInfo {
+                        url: transfer.token.clone(),
+                        symbol: transfer.token.clone(),
+                        precision: 0,
+                    });
+                (info, BTreeMap::new())
+            });
+        let summary = counterparties
+            .entry(transfer.counterparty.to_ascii_lowercase())
+            .or_insert_with(|| CounterpartySummary {
+                counterparty: transfer.counterparty.clone(),
+                ..Default::default()
+            });
+        match transfer.direction {
+            Direction::Inflow => summary.inflow += transfer.amount,
+            Direction::Outflow => summary.outflow += transfer.amount,
+        }
+        summary.transfers += 1;
+    }
+
+    let tokens = by_token
+        .into_values()
+        .map(|(token, counterparties)| {
+            let counterparties: Vec<CounterpartySummary> = counterparties.into_values().collect();
+            TokenSummary {
+                token,
+                inflow: counterparties.iter().map(|c| c.inflow).sum(),
+                outflow: counterparties.iter().map(|c| c.outflow).sum(),
+                counterparties,
+            }
+        })
+        .collect();
+
+    ActivityReport {
+        account: account.to_string(),
+        tokens,
+        transfers,
+        other_transactions,
+    }
+}
+
+/// Token movements of `account` caused by one transaction
+///
+/// Recognizes `sendTokens`, `burnTokens` and `addCredits` sent by the account,
+/// and `syntheticDepositTokens` received by it. Other types yield nothing.
+#[must_use]
+pub fn extract_transfers(
+    account: &str,
+    account_token: Option<&str>,
+    tx_hash: &str,
+    transaction: &Value,
+) -> Vec<Transfer> {
+    let header = transaction.get("header").unwrap_or(&Value::Null);
+    let body = transaction.get("body").unwrap_or(&Value::Null);
+    let principal = header
+        .get("principal")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let tx_type = body.get("type").and_then(Value::as_str).unwrap_or("");
+    let is_principal = same_url(principal, account);
+
+    let transfer = |token: &str, counterparty: &str, direction, amount| Transfer {
+        tx_hash: tx_hash.to_string(),
+        tx_type: tx_type.to_string(),
+        token: token.to_string(),
+        counterparty: counterparty.to_string(),
+        direction,
+        amount,
+    };
+    let own_token = account_token.unwrap_or("");
+
+    match tx_type {
+        "sendTokens" if is_principal => body
+            .get("to")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(|to| {
+                let url = to.get("url").and_then(Value::as_str)?;
+                Some(transfer(
+                    own_token,
+                    url,
+                    Direction::Outflow,
+                    amount_of(to.get("amount"))?,
+                ))
+            })
+            .collect(),
+        "burnTokens" if is_principal => amount_of(body.get("amount"))
+            .map(|amount| transfer(own_token, own_token, Direction::Outflow, amount))
+            .into_iter()
+            .collect(),
+        "addCredits" if is_principal => {
+            let recipient = body.get("recipient").and_then(Value::as_str).unwrap_or("");
+            amount_of(body.get("amount"))
+                .map(|amount| transfer(ACME_TOKEN_URL, recipient, Direction::Outflow, amount))
+                .into_iter()
+                .collect()
+        }
+        "syntheticDepositTokens" if is_principal => {
+            let token = body
+                .get("token")
+                .and_then(Value::as_str)
+                .unwrap_or(own_token);
+            let source = body
+                .get("source")
+                .or_else(|| body.get("cause"))
+                .and_then(Value::as_str)
+                .map(source_account)
+                .unwrap_or_default();
+            amount_of(body.get("amount"))
+                .map(|amount| transfer(token, &source, Direction::Inflow, amount))
+                .into_iter()
+                .collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Pull `(hash, transaction)` pairs out of an expanded chain query response
+pub(crate) fn history_transactions(response: &Value) -> Vec<(String, Value)> {
+    response
+        .get("records")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|record| {
+            let value = record.get("value").unwrap_or(record);
+            let message = value.get("message").unwrap_or(value);
+            let tx = message.get("transaction")?;
+            let hash = record
+                .get("entry")
+                .or_else(|| value.get("id"))
+                .and_then(Value::as_str)
+                .unwrap_or("")
+                .to_string();
+            Some((hash, tx.clone()))
+        })
+        .collect()
+}
+
+fn token_url_of(record: &Value) -> Option<String> {
+    let account = record.get("account").unwrap_or(record);
+    account
+        .get("tokenUrl")
+        .or_else(|| account.get("data").and_then(|d| d.get("tokenUrl")))
+        .and_then(Value::as_str)
+        .map(str::to_string)
+}
+
+fn amount_of(value: Option<&Value>) -> Option<u128> {
+    match value? {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_u64().map(u128::from),
+        _ => None,
+    }
+}
+
+/// Account URL from a source that may be a transaction ID (`acc://hash@account`)
+fn source_account(source: &str) -> String {
+    match source
+        .strip_prefix("acc://")
+        .and_then(|rest| rest.split_once('@'))
+    {
+        Some((_, account)) => format!("acc://{account}"),
+        None => source.to_string(),
+    }
+}
+
+fn same_url(a: &str, b: &str) -> bool {
+    a.trim_end_matches('/')
+        .eq_ignore_ascii_case(b.trim_end_matches('/'))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "acc://alice.acme/tokens";
+
+    fn history() -> Vec<(String, Value)> {
+        vec![
+            (
+                "aa".to_string(),
+                json!({
+                    "header": {"principal": ALICE},
+                    "body": {"type": "syntheticDepositTokens", "token": "acc://ACME", "amount": "500000000",
+                             "source": "acc://0badf00d@bob.acme/tokens"}
+                }),
+            ),
+            (
+                "bb".to_string(),
+                json!({
+                    "header": {"principal": ALICE},
+                    "body": {"type": "sendTokens", "to": [
+                        {"url": "acc://bob.acme/tokens", "amount": "100000000"},
+                        {"url": "acc://carol.acme/tokens", "amount": "25000000"}
+                    ]}
+                }),
+            ),
+            (
+                "cc".to_string(),
+                json!({
+                    "header": {"principal": ALICE},
+                    "body": {"type": "addCredits", "recipient": "acc://alice.acme/book/1", "amount": "1000000", "oracle": 500}
+                }),
+            ),
+            (
+                "dd".to_string(),
+                json!({"header": {"principal": ALICE}, "body": {"type": "updateAccountAuth", "operations": []}}),
+            ),
+            (
+                "ee".to_string(),
+                json!({
+                    "header": {"principal": ALICE},
+                    "body": {"type": "syntheticDepositTokens", "token": "acc://foo.acme/FOO", "amount": "12345",
+                             "source": "acc://dave.acme/foo"}
+                }),
+            ),
+        ]
+    }
+
+    #[test]
+    fn test_report_totals_per_token_and_counterparty() {
+        let foo = TokenInfo {
+            url: "acc://foo.acme/FOO".to_string(),
+            symbol: "FOO".to_string(),
+            precision: 2,
+        };
+        let report = build_activity_report(
+            ALICE,
+            Some("acc://ACME"),
+            &history(),
+            &[TokenInfo::acme(), foo],
+        );
+
+        assert_eq!(report.transfers.len(), 5);
+        assert_eq!(report.other_transactions, 1);
+
+        let acme = report.token("acc://acme").unwrap();
+        assert_eq!(acme.inflow, 500_000_000);
+        assert_eq!(acme.outflow, 126_000_000);
+        assert_eq!(acme.net(), 374_000_000);
+        assert_eq!(acme.format(acme.outflow), "1.26");
+
+        let bob = acme
+            .counterparties
+            .iter()
+            .find(|c| c.counterparty == "acc://bob.acme/tokens")
+            .unwrap();
+        assert_eq!(
+            (bob.inflow, bob.outflow, bob.transfers),
+            (500_000_000, 100_000_000, 2)
+        );
+        assert_eq!(acme.counterparties.len(), 3);
+
+        let foo = report.token("acc://foo.acme/FOO").unwrap();
+        assert_eq!(foo.token.symbol, "FOO");
+        assert_eq!(foo.format(foo.inflow), "123.45");
+    }
+
+    #[test]
+    fn test_ignores_transactions_of_other_principals() {
+        let tx = json!({
+            "header": {"principal": "acc://bob.acme/tokens"},
+            "body": {"type": "sendTokens", "to": [{"url": ALICE, "amount": "1"}]}
+        });
+        assert!(extract_transfers(ALICE, Some("acc://ACME"), "ff", &tx).is_empty());
+    }
+
+    #[test]
+    fn test_unknown_token_metadata_falls_back_to_url() {
+        let report = build_activity_report(ALICE, None, &history()[4..], &[]);
+        let token = &report.tokens[0];
+        assert_eq!(token.token.symbol, "acc://foo.acme/FOO");
+        assert_eq!(token.format(token.inflow), "12345");
+    }
+
+    #[test]
+    fn test_history_transactions_from_expanded_records() {
+        let response = json!({"records": [
+            {"entry": "abcd", "value": {"message": {"transaction": {"body": {"type": "sendTokens"}}}}},
+            {"entry": "ef01", "value": {"message": {"type": "signature"}}}
+        ]});
+        let txs = history_transactions(&response);
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].0, "abcd");
+    }
+}
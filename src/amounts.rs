@@ -4,6 +4,8 @@
 //! units**. Passing whole ACME where base units are expected is the single most
 //! common integration bug. Use [`Amount`] to convert explicitly.
 
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+
 /// Number of decimal places in ACME (1 ACME = 10^[`ACME_PRECISION`] base units).
 pub const ACME_PRECISION: u32 = 8;
 
@@ -19,8 +21,16 @@ pub const ACME_BASE_UNITS: u64 = 100_000_000;
 ///
 /// assert_eq!(Amount::acme(5).to_wire(), "500000000");
 /// assert_eq!(Amount::base_units(250_000_000).to_acme(), 2.5);
+///
+/// let fee = Amount::from_acme("0.25").unwrap();
+/// let total = Amount::from_acme("5.5").unwrap().checked_add(fee).unwrap();
+/// assert_eq!(total.to_display(8), "5.75");
 /// ```
-#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+///
+/// Amounts of other tokens are base units at that token's precision; see
+/// [`Amount::parse`] and [`Amount::to_display`]. It serializes as the wire
+/// string of base units.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct Amount {
     base_units: u128,
 }
@@ -40,6 +50,21 @@ impl Amount {
         Self { base_units: units }
     }
 
+    /// Parse a decimal ACME amount such as `"5.5"`
+    ///
+    /// Returns `None` for anything [`parse_base_units`] rejects, including
+    /// more than 8 decimal places.
+    #[must_use]
+    pub fn from_acme(amount: &str) -> Option<Self> {
+        Self::parse(amount, ACME_PRECISION)
+    }
+
+    /// Parse a decimal amount of a token with `precision` decimal places
+    #[must_use]
+    pub fn parse(amount: &str, precision: u32) -> Option<Self> {
+        parse_base_units(amount, precision).map(Self::base_units)
+    }
+
     /// ACME base units needed to buy `credit_count` credits at `oracle_price`
     /// (the integer oracle value from the network oracle query).
     #[must_use]
@@ -62,6 +87,30 @@ impl Amount {
         self.base_units.to_string()
     }
 
+    /// Exact decimal string at `precision` decimal places, e.g. `"5.5"`
+    #[must_use]
+    pub fn to_display(&self, precision: u32) -> String {
+        format_base_units(self.base_units, precision)
+    }
+
+    /// Sum, or `None` on overflow
+    #[must_use]
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        self.base_units.checked_add(other.base_units).map(Self::base_units)
+    }
+
+    /// Difference, or `None` if `other` is larger
+    #[must_use]
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        self.base_units.checked_sub(other.base_units).map(Self::base_units)
+    }
+
+    /// The amount times `factor`, or `None` on overflow
+    #[must_use]
+    pub fn checked_mul(self, factor: u128) -> Option<Self> {
+        self.base_units.checked_mul(factor).map(Self::base_units)
+    }
+
     /// The amount expressed in whole ACME.
     #[must_use]
     #[allow(clippy::cast_precision_loss)]
@@ -75,3 +124,138 @@ impl std::fmt::Display for Amount {
         write!(f, "{}", self.base_units)
     }
 }
+
+/// An amount of any token, in its base units
+pub type TokenAmount = Amount;
+
+impl Serialize for Amount {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_wire())
+    }
+}
+
+impl<'de> Deserialize<'de> for Amount {
+    /// Accepts the wire string or a JSON integer
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Wire {
+            Text(String),
+            Number(u64),
+        }
+        match Wire::deserialize(deserializer)? {
+            Wire::Text(units) => units
+                .parse()
+                .map(Self::base_units)
+                .map_err(|_| de::Error::custom(format!("invalid base units: {units}"))),
+            Wire::Number(units) => Ok(Self::base_units(u128::from(units))),
+        }
+    }
+}
+
+/// Format an integer amount of base units as an exact decimal string.
+///
+/// `precision` is the token's number of decimal places (8 for ACME). Trailing
+/// zeros in the fraction are dropped, so `format_base_units(150_000_000, 8)`
+/// is `"1.5"`.
+#[must_use]
+pub fn format_base_units(units: u128, precision: u32) -> String {
+    let digits = units.to_string();
+    let precision = precision as usize;
+    if precision == 0 {
+        return digits;
+    }
+
+    let padded = format!("{digits:0>width$}", width = precision + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - precision);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{whole}.{fraction}")
+    }
+}
+
+/// Parse a decimal amount into integer base units.
+///
+/// The inverse of [`format_base_units`]: `parse_base_units("1.5", 8)` is
+/// `Some(150_000_000)`. Returns `None` for signs, exponents, more fractional
+/// digits than `precision`, or values that overflow `u128`.
+#[must_use]
+pub fn parse_base_units(amount: &str, precision: u32) -> Option<u128> {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    let precision = precision as usize;
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty())
+        || !digits(whole)
+        || !digits(fraction)
+        || fraction.len() > precision
+        || (amount.contains('.') && fraction.is_empty())
+    {
+        return None;
+    }
+
+    let scale = 10u128.checked_pow(u32::try_from(precision).ok()?)?;
+    let whole = if whole.is_empty() { 0 } else { whole.parse::<u128>().ok()? };
+    let fraction = if fraction.is_empty() {
+        0
+    } else {
+        format!("{fraction:0<precision$}").parse::<u128>().ok()?
+    };
+    whole.checked_mul(scale)?.checked_add(fraction)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_base_units() {
+        assert_eq!(format_base_units(150_000_000, ACME_PRECISION), "1.5");
+        assert_eq!(format_base_units(1, ACME_PRECISION), "0.00000001");
+        assert_eq!(format_base_units(0, ACME_PRECISION), "0");
+        assert_eq!(format_base_units(12_300, 2), "123");
+        assert_eq!(format_base_units(42, 0), "42");
+        assert_eq!(format_base_units(u128::MAX, 18), "340282366920938463463.374607431768211455");
+    }
+
+    #[test]
+    fn test_amount_parse_arithmetic_serde() {
+        let amount = Amount::from_acme("5.5").unwrap();
+        assert_eq!(amount.as_base_units(), 550_000_000);
+        assert_eq!(amount.to_display(ACME_PRECISION), "5.5");
+        assert_eq!(Amount::parse("1.25", 2).unwrap().to_wire(), "125");
+        assert!(Amount::from_acme("0.000000001").is_none());
+
+        let max = Amount::base_units(u128::MAX);
+        assert_eq!(max.checked_add(Amount::base_units(1)), None);
+        assert_eq!(Amount::acme(1).checked_sub(amount), None);
+        assert_eq!(amount.checked_sub(Amount::acme(5)).unwrap().to_display(8), "0.5");
+        assert_eq!(amount.checked_mul(2), Some(Amount::acme(11)));
+        assert_eq!(max.checked_mul(2), None);
+
+        assert_eq!(serde_json::to_string(&amount).unwrap(), "\"550000000\"");
+        let parsed: Amount = serde_json::from_str("\"550000000\"").unwrap();
+        assert_eq!(parsed, amount);
+        assert_eq!(serde_json::from_str::<Amount>("7").unwrap(), Amount::base_units(7));
+        assert!(serde_json::from_str::<Amount>("\"1.5\"").is_err());
+    }
+
+    #[test]
+    fn test_parse_base_units() {
+        assert_eq!(parse_base_units("1.5", ACME_PRECISION), Some(150_000_000));
+        assert_eq!(parse_base_units("0.00000001", ACME_PRECISION), Some(1));
+        assert_eq!(parse_base_units(".5", 1), Some(5));
+        assert_eq!(parse_base_units("42", 0), Some(42));
+        assert_eq!(parse_base_units("0.000000001", ACME_PRECISION), None);
+        assert_eq!(parse_base_units("1.", ACME_PRECISION), None);
+        assert_eq!(parse_base_units("-1", ACME_PRECISION), None);
+        assert_eq!(parse_base_units("1e8", ACME_PRECISION), None);
+        assert_eq!(parse_base_units("", ACME_PRECISION), None);
+        assert_eq!(parse_base_units("340282366920938463464", 18), None);
+        for units in [0u128, 1, 150_000_000, u128::MAX] {
+            assert_eq!(parse_base_units(&format_base_units(units, 18), 18), Some(units));
+        }
+    }
+}
@@ -0,0 +1,317 @@
+//! Anchor ledger queries for operators
+//!
+//! Every partition keeps an anchor ledger at `<partition>/anchors`; the
+//! directory's ledger ([`DN_ANCHOR_POOL_URL`]) is the anchor pool the BVNs
+//! anchor into. The ledger's per-partition sequence records how many anchors
+//! were produced for, received from and delivered from each peer, so
+//! [`AnchorLedger::lag`] shows which partitions are falling behind. The
+//! `anchor-sequence` and `anchor(<partition>)-root` chains hold the anchors
+//! themselves, newest last.
+
+use crate::client::AccumulateClient;
+use crate::json_rpc_client::JsonRpcError;
+use crate::types::RangeOptions;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Directory network anchor pool
+pub const DN_ANCHOR_POOL_URL: &str = "acc://dn.acme/anchors";
+
+/// Chain of anchors a partition has sent
+pub const ANCHOR_SEQUENCE_CHAIN: &str = "anchor-sequence";
+
+/// URL of a partition
+///
+/// `dn` (or `directory`) names the directory network; anything else is a BVN
+/// ID such as `Apollo`.
+#[must_use]
+pub fn partition_url(partition: &str) -> String {
+    if partition.eq_ignore_ascii_case("dn") || partition.eq_ignore_ascii_case("directory") {
+        "acc://dn.acme".to_string()
+    } else {
+        format!("acc://bvn-{partition}.acme")
+    }
+}
+
+/// URL of a partition's anchor ledger, named as for [`partition_url`]
+#[must_use]
+pub fn anchor_ledger_url(partition: &str) -> String {
+    format!("{}/anchors", partition_url(partition))
+}
+
+/// Name of the chain holding root anchors received from `partition`
+#[must_use]
+pub fn root_anchor_chain(partition: &str) -> String {
+    format!("anchor({})-root", partition.to_ascii_lowercase())
+}
+
+/// A partition's anchor ledger account
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnchorLedger {
+    /// Ledger URL
+    #[serde(default)]
+    pub url: String,
+    /// Sequence number of the last minor block anchor produced
+    #[serde(default)]
+    pub minor_block_sequence_number: u64,
+    /// Index of the last major block
+    #[serde(default)]
+    pub major_block_index: u64,
+    /// Time of the last major block
+    #[serde(default)]
+    pub major_block_time: Option<DateTime<Utc>>,
+    /// Partitions whose anchor for the pending major block has not arrived
+    #[serde(default)]
+    pub pending_major_block_anchors: Vec<String>,
+    /// Anchor exchange with each peer partition
+    #[serde(default)]
+    pub sequence: Vec<PartitionAnchorSequence>,
+}
+
+/// Anchor counters for one peer partition
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct PartitionAnchorSequence {
+    /// Peer partition URL
+    #[serde(default)]
+    pub url: String,
+    /// Anchors produced for the peer
+    #[serde(default)]
+    pub produced: u64,
+    /// Highest anchor sequence number received from the peer
+    #[serde(default)]
+    pub received: u64,
+    /// Highest anchor sequence number executed from the peer
+    #[serde(default)]
+    pub delivered: u64,
+    /// IDs of received anchors that have not executed yet
+    #[serde(default)]
+    pub pending: Vec<String>,
+}
+
+impl PartitionAnchorSequence {
+    /// Anchors received from the peer but not yet executed
+    #[must_use]
+    pub const fn backlog(&self) -> u64 {
+        self.received.saturating_sub(self.delivered)
+    }
+}
+
+/// How far behind one peer partition's anchors are
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnchorLag {
+    /// Peer partition URL
+    pub partition: String,
+    /// Anchors received but not executed
+    pub backlog: u64,
+    /// Anchors waiting on this ledger
+    pub pending: usize,
+}
+
+impl AnchorLedger {
+    /// Anchoring lag per peer partition, largest backlog first
+    #[must_use]
+    pub fn lag(&self) -> Vec<AnchorLag> {
+        let mut lag: Vec<AnchorLag> = self
+            .sequence
+            .iter()
+            .map(|s| AnchorLag {
+                partition: s.url.clone(),
+                backlog: s.backlog(),
+                pending: s.pending.len(),
+            })
+            .collect();
+        lag.sort_by(|a, b| {
+            b.backlog
+                .cmp(&a.backlog)
+                .then_with(|| a.partition.cmp(&b.partition))
+        });
+        lag
+    }
+
+    /// Largest backlog of any peer partition
+    #[must_use]
+    pub fn max_backlog(&self) -> u64 {
+        self.sequence
+            .iter()
+            .map(PartitionAnchorSequence::backlog)
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Total anchors waiting to execute
+    #[must_use]
+    pub fn pending_count(&self) -> usize {
+        self.sequence.iter().map(|s| s.pending.len()).sum()
+    }
+}
+
+/// One entry of an anchor chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AnchorEntry {
+    /// Position on the chain
+    pub index: u64,
+    /// Entry hash, hex
+    pub entry: String,
+}
+
+impl AccumulateClient {
+    /// Anchor ledger of a partition (`dn` or a BVN ID)
+    pub async fn anchor_ledger(&self, partition: &str) -> Result<AnchorLedger, JsonRpcError> {
+        let url = anchor_ledger_url(partition);
+        let result: Value = self
+            .call_query(json!({
+                "scope": &url,
+                "query": {"queryType": "default"}
+            }))
+            .await?;
+        let account = result.get("account").cloned().unwrap_or(Value::Null);
+        match account.get("type").and_then(Value::as_str) {
+            Some("anchorLedger") => Ok(serde_json::from_value(account)?),
+            other => Err(JsonRpcError::General(format!(
+                "{} is not an anchor ledger (type {})",
+                url,
+                other.unwrap_or("unknown")
+            ))),
+        }
+    }
+
+    /// Anchor ledger of the directory network, which BVNs anchor into
+    pub async fn dn_anchor_pool(&self) -> Result<AnchorLedger, JsonRpcError> {
+        self.anchor_ledger("dn").await
+    }
+
+    /// The latest `count` anchors a partition has sent, newest last
+    pub async fn latest_anchors_sent(
+        &self,
+        partition: &str,
+        count: u64,
+    ) -> Result<Vec<AnchorEntry>, JsonRpcError> {
+        self.latest_chain_entries(&anchor_ledger_url(partition), ANCHOR_SEQUENCE_CHAIN, count)
+            .await
+    }
+
+    /// The latest `count` root anchors `partition` has received from `source`,
+    /// newest last
+    pub async fn latest_anchors_received(
+        &self,
+        partition: &str,
+        source: &str,
+        count: u64,
+    ) -> Result<Vec<AnchorEntry>, JsonRpcError> {
+        self.latest_chain_entries(
+            &anchor_ledger_url(partition),
+            &root_anchor_chain(source),
+            count,
+        )
+        .await
+    }
+
+    async fn latest_chain_entries(
+        &self,
+        url: &str,
+        chain: &str,
+        count: u64,
+    ) -> Result<Vec<AnchorEntry>, JsonRpcError> {
+        let range = RangeOptions {
+            start: None,
+            count: Some(count),
+            expand: None,
+            from_end: Some(true),
+        };
+        let response: Value = self
+            .call_query(json!({
+                "scope": url,
+                "query": {"queryType": "chain", "name": chain, "range": range}
+            }))
+            .await?;
+        Ok(parse_anchor_entries(&response))
+    }
+}
+
+/// Read chain entries out of a chain query response, oldest first
+#[must_use]
+pub fn parse_anchor_entries(response: &Value) -> Vec<AnchorEntry> {
+    let start = response.get("start").and_then(Value::as_u64).unwrap_or(0);
+    let mut entries: Vec<AnchorEntry> = response
+        .get("records")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(offset, record)| {
+            Some(AnchorEntry {
+                index: record
+                    .get("index")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(start + offset as u64),
+                entry: record.get("entry")?.as_str()?.to_ascii_lowercase(),
+            })
+        })
+        .collect();
+    entries.sort_by_key(|e| e.index);
+    entries
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_anchor_ledger_urls() {
+        assert_eq!(anchor_ledger_url("dn"), DN_ANCHOR_POOL_URL);
+        assert_eq!(anchor_ledger_url("Directory"), DN_ANCHOR_POOL_URL);
+        assert_eq!(anchor_ledger_url("Apollo"), "acc://bvn-Apollo.acme/anchors");
+        assert_eq!(partition_url("DN"), "acc://dn.acme");
+        assert_eq!(root_anchor_chain("Apollo"), "anchor(apollo)-root");
+    }
+
+    #[test]
+    fn test_anchor_ledger_lag() {
+        let ledger: AnchorLedger = serde_json::from_value(json!({
+            "type": "anchorLedger",
+            "url": "acc://dn.acme/anchors",
+            "minorBlockSequenceNumber": 120,
+            "majorBlockIndex": 3,
+            "majorBlockTime": "2024-05-01T12:00:00Z",
+            "pendingMajorBlockAnchors": ["acc://bvn-Chandrayaan.acme"],
+            "sequence": [
+                {"url": "acc://bvn-Apollo.acme", "produced": 118, "received": 120, "delivered": 120},
+                {"url": "acc://bvn-Chandrayaan.acme", "produced": 118, "received": 125, "delivered": 119,
+                 "pending": ["acc://aa@dn.acme/anchors", "acc://bb@dn.acme/anchors"]},
+                {"url": "acc://bvn-Yutu.acme", "produced": 118, "received": 121, "delivered": 120}
+            ]
+        }))
+        .unwrap();
+
+        assert_eq!(ledger.max_backlog(), 6);
+        assert_eq!(ledger.pending_count(), 2);
+        let lag = ledger.lag();
+        assert_eq!(lag[0].partition, "acc://bvn-Chandrayaan.acme");
+        assert_eq!(lag[0].pending, 2);
+        assert_eq!(lag[1].backlog, 1);
+        assert_eq!(lag[2].backlog, 0);
+        assert!(ledger.major_block_time.is_some());
+    }
+
+    #[test]
+    fn test_parse_anchor_entries() {
+        let response = json!({
+            "recordType": "range",
+            "start": 40,
+            "records": [
+                {"recordType": "chainEntry", "index": 41, "entry": "BB"},
+                {"recordType": "chainEntry", "index": 40, "entry": "aa"},
+                {"recordType": "chainEntry"}
+            ]
+        });
+        let entries = parse_anchor_entries(&response);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].index, 40);
+        assert_eq!(entries[1].entry, "bb");
+    }
+}
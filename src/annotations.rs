@@ -0,0 +1,431 @@
+//! Structured annotations carried in transaction memos
+//!
+//! A memo is free text, so applications that put metadata in it need a
+//! convention to find their own memos again. An annotation memo starts with a
+//! namespace and version tag, followed by the fields in the namespace's
+//! format:
+//!
+//! ```text
+//! @invoice/v1 {"id":"42","due":"2024-06-01"}     JSON
+//! @order/v1 sku=x-100;qty=3                      key=value
+//! ```
+//!
+//! An [`AnnotationRegistry`] lists the namespaces an application understands,
+//! their format and the newest version it can read. Decoding refuses unknown
+//! namespaces and newer versions instead of guessing, and
+//! [`AnnotationRegistry::filter`] picks annotated transactions out of an
+//! account's history.
+
+use crate::client::AccumulateClient;
+use crate::json_rpc_client::JsonRpcError;
+use crate::types::RangeOptions;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// First character of every annotation memo
+pub const ANNOTATION_MARKER: char = '@';
+
+/// Errors encoding or decoding an annotation memo
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AnnotationError {
+    /// The memo does not start with an annotation tag
+    #[error("Memo is not an annotation")]
+    NotAnnotated,
+
+    /// The namespace is not registered
+    #[error("Unknown annotation namespace: {0}")]
+    UnknownNamespace(String),
+
+    /// The namespace name has characters other than `a-z`, `0-9`, `.`, `_`, `-`
+    #[error("Invalid annotation namespace: {0:?}")]
+    InvalidNamespace(String),
+
+    /// The memo was written by a newer version than the registry reads
+    #[error("Annotation {namespace}/v{version} is newer than supported v{max_version}")]
+    UnsupportedVersion {
+        /// Namespace
+        namespace: String,
+        /// Version in the memo
+        version: u32,
+        /// Newest registered version
+        max_version: u32,
+    },
+
+    /// The tag or payload cannot be parsed
+    #[error("Malformed annotation: {0}")]
+    Malformed(String),
+}
+
+/// How a namespace writes its fields
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemoFormat {
+    /// A JSON object of string values
+    Json,
+    /// `key=value` pairs separated by `;`, with `%`, `;` and `=` percent-escaped
+    KeyValue,
+}
+
+/// Fields of one annotation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Annotation {
+    /// Namespace the fields belong to
+    pub namespace: String,
+    /// Version of the namespace's field layout
+    pub version: u32,
+    /// Field values by name
+    pub fields: BTreeMap<String, String>,
+}
+
+impl Annotation {
+    /// Empty annotation for a namespace version
+    #[must_use]
+    pub fn new(namespace: &str, version: u32) -> Self {
+        Self {
+            namespace: namespace.to_string(),
+            version,
+            fields: BTreeMap::new(),
+        }
+    }
+
+    /// Add a field
+    #[must_use]
+    pub fn with(mut self, key: &str, value: impl Into<String>) -> Self {
+        self.fields.insert(key.to_string(), value.into());
+        self
+    }
+
+    /// Value of a field
+    #[must_use]
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.fields.get(key).map(String::as_str)
+    }
+}
+
+/// A transaction from an account's history whose memo holds an annotation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnnotatedTransaction {
+    /// Transaction hash
+    pub hash: String,
+    /// Decoded memo
+    pub annotation: Annotation,
+    /// The transaction (`{header, body}`)
+    pub transaction: Value,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Scheme {
+    format: MemoFormat,
+    max_version: u32,
+}
+
+/// Namespaces an application reads and writes, with their formats
+#[derive(Debug, Clone, Default)]
+pub struct AnnotationRegistry {
+    schemes: BTreeMap<String, Scheme>,
+}
+
+impl AnnotationRegistry {
+    /// Empty registry
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register a namespace, readable up to `max_version`
+    ///
+    /// Fails if the namespace name is not valid in a memo tag.
+    pub fn register(
+        mut self,
+        namespace: &str,
+        format: MemoFormat,
+        max_version: u32,
+    ) -> Result<Self, AnnotationError> {
+        if !is_valid_namespace(namespace) {
+            return Err(AnnotationError::InvalidNamespace(namespace.to_string()));
+        }
+        self.schemes.insert(
+            namespace.to_string(),
+            Scheme {
+                format,
+                max_version,
+            },
+        );
+        Ok(self)
+    }
+
+    /// Encode an annotation as a memo
+    pub fn encode(&self, annotation: &Annotation) -> Result<String, AnnotationError> {
+        let scheme = self.scheme(&annotation.namespace, annotation.version)?;
+        let payload = match scheme.format {
+            MemoFormat::Json => json!(annotation.fields).to_string(),
+            MemoFormat::KeyValue => annotation
+                .fields
+                .iter()
+                .map(|(k, v)| format!("{}={}", escape(k), escape(v)))
+                .collect::<Vec<_>>()
+                .join(";"),
+        };
+        Ok(format!(
+            "{ANNOTATION_MARKER}{}/v{} {payload}",
+            annotation.namespace, annotation.version
+        ))
+    }
+
+    /// Decode a memo written by a registered namespace
+    pub fn decode(&self, memo: &str) -> Result<Annotation, AnnotationError> {
+        let tagged = memo
+            .strip_prefix(ANNOTATION_MARKER)
+            .ok_or(AnnotationError::NotAnnotated)?;
+        let (tag, payload) = tagged.split_once(' ').unwrap_or((tagged, ""));
+        let (namespace, version) = tag
+            .rsplit_once("/v")
+            .ok_or_else(|| AnnotationError::Malformed(format!("tag {tag:?} has no version")))?;
+        if !is_valid_namespace(namespace) {
+            return Err(AnnotationError::NotAnnotated);
+        }
+        let version: u32 = version
+            .parse()
+            .map_err(|_| AnnotationError::Malformed(format!("bad version in tag {tag:?}")))?;
+        let scheme = self.scheme(namespace, version)?;
+
+        let fields = match scheme.format {
+            MemoFormat::Json => decode_json(payload)?,
+            MemoFormat::KeyValue => decode_key_value(payload)?,
+        };
+        Ok(Annotation {
+            namespace: namespace.to_string(),
+            version,
+            fields,
+        })
+    }
+
+    /// Transactions whose memo decodes as an annotation in `namespace`
+    ///
+    /// `transactions` are `(hash, {header, body})` pairs, as returned by a
+    /// main-chain query. Memos that are not annotations, or that fail to
+    /// decode, are skipped.
+    #[must_use]
+    pub fn filter(
+        &self,
+        transactions: &[(String, Value)],
+        namespace: &str,
+    ) -> Vec<AnnotatedTransaction> {
+        transactions
+            .iter()
+            .filter_map(|(hash, tx)| {
+                let memo = tx.get("header")?.get("memo")?.as_str()?;
+                let annotation = self.decode(memo).ok()?;
+                (annotation.namespace == namespace).then(|| AnnotatedTransaction {
+                    hash: hash.clone(),
+                    annotation,
+                    transaction: tx.clone(),
+                })
+            })
+            .collect()
+    }
+
+    fn scheme(&self, namespace: &str, version: u32) -> Result<Scheme, AnnotationError> {
+        let scheme = *self
+            .schemes
+            .get(namespace)
+            .ok_or_else(|| AnnotationError::UnknownNamespace(namespace.to_string()))?;
+        if version > scheme.max_version {
+            return Err(AnnotationError::UnsupportedVersion {
+                namespace: namespace.to_string(),
+                version,
+                max_version: scheme.max_version,
+            });
+        }
+        Ok(scheme)
+    }
+}
+
+impl AccumulateClient {
+    /// Annotated transactions in a range of an account's main chain
+    ///
+    /// Fetches the range expanded and keeps the transactions whose memo
+    /// decodes under `namespace` with `registry`.
+    pub async fn annotated_history(
+        &self,
+        account: &str,
+        range: RangeOptions,
+        registry: &AnnotationRegistry,
+        namespace: &str,
+    ) -> Result<Vec<AnnotatedTransaction>, JsonRpcError> {
+        let range = RangeOptions {
+            expand: Some(true),
+            ..range
+        };
+        let history: Value = self
+            .v3_client
+            .call_v3(
+                "query",
+                json!({
+                    "url": account,
+                    "query": {"queryType": "chain", "name": "main", "range": range}
+                }),
+            )
+            .await?;
+        let transactions = crate::activity::history_transactions(&history);
+        Ok(registry.filter(&transactions, namespace))
+    }
+}
+
+fn is_valid_namespace(namespace: &str) -> bool {
+    !namespace.is_empty()
+        && namespace
+            .bytes()
+            .all(|b| b.is_ascii_lowercase() || b.is_ascii_digit() || b"._-".contains(&b))
+}
+
+fn decode_json(payload: &str) -> Result<BTreeMap<String, String>, AnnotationError> {
+    let value: Value = serde_json::from_str(payload)
+        .map_err(|e| AnnotationError::Malformed(format!("JSON payload: {e}")))?;
+    let object = value
+        .as_object()
+        .ok_or_else(|| AnnotationError::Malformed("JSON payload is not an object".to_string()))?;
+    Ok(object
+        .iter()
+        .map(|(k, v)| match v {
+            Value::String(s) => (k.clone(), s.clone()),
+            other => (k.clone(), other.to_string()),
+        })
+        .collect())
+}
+
+fn decode_key_value(payload: &str) -> Result<BTreeMap<String, String>, AnnotationError> {
+    payload
+        .split(';')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (k, v) = pair
+                .split_once('=')
+                .ok_or_else(|| AnnotationError::Malformed(format!("field {pair:?} has no '='")))?;
+            Ok((unescape(k)?, unescape(v)?))
+        })
+        .collect()
+}
+
+fn escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '%' => out.push_str("%25"),
+            ';' => out.push_str("%3B"),
+            '=' => out.push_str("%3D"),
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn unescape(text: &str) -> Result<String, AnnotationError> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(pos) = rest.find('%') {
+        out.push_str(&rest[..pos]);
+        let escaped = rest.get(pos + 1..pos + 3);
+        out.push(match escaped {
+            Some("25") => '%',
+            Some("3B" | "3b") => ';',
+            Some("3D" | "3d") => '=',
+            _ => {
+                return Err(AnnotationError::Malformed(format!(
+                    "bad escape in {text:?}"
+                )))
+            }
+        });
+        rest = &rest[pos + 3..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn registry() -> AnnotationRegistry {
+        AnnotationRegistry::new()
+            .register("invoice", MemoFormat::Json, 2)
+            .unwrap()
+            .register("order", MemoFormat::KeyValue, 1)
+            .unwrap()
+    }
+
+    #[test]
+    fn test_round_trip_both_formats() {
+        let registry = registry();
+
+        let invoice = Annotation::new("invoice", 2)
+            .with("id", "42")
+            .with("note", "paid \"in full\"");
+        let memo = registry.encode(&invoice).unwrap();
+        assert!(memo.starts_with("@invoice/v2 {"));
+        assert_eq!(registry.decode(&memo).unwrap(), invoice);
+
+        let order = Annotation::new("order", 1)
+            .with("sku", "a=b;c")
+            .with("qty", "100%");
+        let memo = registry.encode(&order).unwrap();
+        assert_eq!(memo, "@order/v1 qty=100%25;sku=a%3Db%3Bc");
+        assert_eq!(registry.decode(&memo).unwrap(), order);
+    }
+
+    #[test]
+    fn test_decode_rejects_unknown_and_newer() {
+        let registry = registry();
+        assert_eq!(
+            registry.decode("thanks for lunch"),
+            Err(AnnotationError::NotAnnotated)
+        );
+        assert_eq!(
+            registry.decode("@someone said hi"),
+            Err(AnnotationError::Malformed(
+                "tag \"someone\" has no version".to_string()
+            ))
+        );
+        assert_eq!(
+            registry.decode("@refund/v1 {}"),
+            Err(AnnotationError::UnknownNamespace("refund".to_string()))
+        );
+        assert!(matches!(
+            registry.decode("@order/v2 qty=1"),
+            Err(AnnotationError::UnsupportedVersion { max_version: 1, .. })
+        ));
+        assert!(matches!(
+            registry.decode("@invoice/v1 [1]"),
+            Err(AnnotationError::Malformed(_))
+        ));
+        assert!(matches!(
+            registry.encode(&Annotation::new("refund", 1)),
+            Err(AnnotationError::UnknownNamespace(_))
+        ));
+        assert!(AnnotationRegistry::new()
+            .register("Bad Name", MemoFormat::Json, 1)
+            .is_err());
+    }
+
+    #[test]
+    fn test_filter_history_by_namespace() {
+        let registry = registry();
+        let tx = |memo: &str| json!({"header": {"principal": "acc://alice.acme/tokens", "memo": memo}, "body": {"type": "sendTokens"}});
+        let transactions = vec![
+            ("aa".to_string(), tx("@invoice/v1 {\"id\":\"7\"}")),
+            ("bb".to_string(), tx("@order/v1 sku=x")),
+            ("cc".to_string(), tx("plain memo")),
+            (
+                "dd".to_string(),
+                json!({"header": {}, "body": {"type": "sendTokens"}}),
+            ),
+            ("ee".to_string(), tx("@invoice/v9 {}")),
+        ];
+
+        let invoices = registry.filter(&transactions, "invoice");
+        assert_eq!(invoices.len(), 1);
+        assert_eq!(invoices[0].hash, "aa");
+        assert_eq!(invoices[0].annotation.get("id"), Some("7"));
+    }
+}
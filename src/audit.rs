@@ -0,0 +1,343 @@
+//! Audit log of everything a signer signs
+//!
+//! Give a [`SmartSigner`](crate::helpers::SmartSigner) an [`AuditSink`] with
+//! [`with_audit_sink`](crate::helpers::SmartSigner::with_audit_sink) and it
+//! reports every envelope or signature it produces before handing it back for
+//! submission, and the final status of every transaction it submits and waits
+//! for. Signed records carry the canonical JSON bytes of what was signed
+//! along with the transaction, header, body and envelope hashes, so a record
+//! can be matched against the chain later.
+//!
+//! [`KvAuditSink`] keeps the records in a [`KvStore`], each one chained to the
+//! previous by hash, so [`KvAuditSink::verify`] detects a record that was
+//! edited, removed or reordered after the fact.
+
+use crate::canonjson::canonicalize;
+use crate::codec::hashes::{body_hash, header_hash};
+use crate::storage::{put_json, scan_json, KvStore, StorageError};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::sync::Mutex;
+use thiserror::Error;
+
+/// Key prefix of audit entries in a [`KvAuditSink`] store
+pub const AUDIT_PREFIX: &str = "audit/";
+
+/// Errors from recording or checking an audit log
+#[derive(Error, Debug)]
+pub enum AuditError {
+    /// The sink could not record an entry
+    #[error("Audit sink error: {0}")]
+    Sink(String),
+
+    /// The backing store failed
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    /// A stored entry does not chain to the one before it
+    #[error("Audit log tampered at entry {sequence}: {reason}")]
+    Tampered {
+        /// Sequence number of the first bad entry
+        sequence: u64,
+        /// What does not match
+        reason: String,
+    },
+}
+
+/// Receives audit records from a signer
+///
+/// [`AuditSink::record`] runs before a signed envelope is returned, so an
+/// error there aborts signing: nothing leaves the signer unrecorded.
+pub trait AuditSink: Send + Sync + fmt::Debug {
+    /// Record one event
+    fn record(&self, record: &AuditRecord) -> Result<(), AuditError>;
+}
+
+/// One audited event
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum AuditRecord {
+    /// An envelope or signature was signed and is about to be submitted
+    Signed(SignedRecord),
+    /// A submitted transaction reached its final status
+    Finalized(FinalStatusRecord),
+}
+
+/// Something a signer signed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SignedRecord {
+    /// Signer (key page) URL
+    pub signer: String,
+    /// Transaction principal, or `None` for a signature over a bare hash
+    pub principal: Option<String>,
+    /// Hash of the signed transaction, hex
+    pub transaction_hash: String,
+    /// Hash of the transaction header, hex, when the envelope carries it
+    pub header_hash: Option<String>,
+    /// Hash of the transaction body, hex, when the envelope carries it
+    pub body_hash: Option<String>,
+    /// SHA-256 of `canonical`, hex
+    pub envelope_hash: String,
+    /// Canonical JSON of the envelope, or of the lone signature
+    #[serde(with = "hex::serde")]
+    pub canonical: Vec<u8>,
+}
+
+impl SignedRecord {
+    /// Record for a signed envelope (`{transaction: [...], signatures: [...]}`)
+    #[must_use]
+    pub fn from_envelope(signer: &str, envelope: &Value) -> Self {
+        let transaction = &envelope["transaction"][0];
+        let hex_hash = |hash: Result<[u8; 32], _>| hash.ok().map(hex::encode);
+        Self {
+            principal: transaction["header"]["principal"]
+                .as_str()
+                .map(str::to_string),
+            header_hash: transaction
+                .get("header")
+                .and_then(|h| hex_hash(header_hash(h))),
+            body_hash: transaction.get("body").and_then(|b| hex_hash(body_hash(b))),
+            ..Self::from_signed(signer, envelope, &envelope["signatures"][0])
+        }
+    }
+
+    /// Record for a signature over an existing transaction hash
+    #[must_use]
+    pub fn from_signature(signer: &str, signature: &Value) -> Self {
+        Self::from_signed(signer, signature, signature)
+    }
+
+    fn from_signed(signer: &str, content: &Value, signature: &Value) -> Self {
+        let canonical = canonicalize(content).into_bytes();
+        Self {
+            signer: signer.to_string(),
+            principal: None,
+            transaction_hash: signature["transactionHash"]
+                .as_str()
+                .unwrap_or_default()
+                .to_ascii_lowercase(),
+            header_hash: None,
+            body_hash: None,
+            envelope_hash: hex::encode(Sha256::digest(&canonical)),
+            canonical,
+        }
+    }
+}
+
+/// Final status of a submitted transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FinalStatusRecord {
+    /// Signer (key page) URL
+    pub signer: String,
+    /// Hash of the transaction, hex
+    pub transaction_hash: String,
+    /// Envelope hash of the matching [`SignedRecord`], hex
+    pub envelope_hash: String,
+    /// Transaction ID the network returned, if submission got that far
+    pub txid: Option<String>,
+    /// Whether the transaction was delivered without error
+    pub delivered: bool,
+    /// Why it failed, timed out or was rejected
+    pub error: Option<String>,
+}
+
+/// An audit record as stored by [`KvAuditSink`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    /// Position in the log, from 0
+    pub sequence: u64,
+    /// Hash of the previous entry, hex; zeros for the first
+    pub previous: String,
+    /// `SHA256(previous || sequence || canonical JSON of record)`, hex
+    pub hash: String,
+    /// The audited event
+    pub record: AuditRecord,
+}
+
+impl AuditEntry {
+    fn chain_hash(previous: &str, sequence: u64, record: &AuditRecord) -> String {
+        let record =
+            serde_json::to_value(record).map_or_else(|_| String::new(), |v| canonicalize(&v));
+        let mut hasher = Sha256::new();
+        hasher.update(previous.as_bytes());
+        hasher.update(sequence.to_be_bytes());
+        hasher.update(record.as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
+#[derive(Debug)]
+struct ChainHead<S> {
+    store: S,
+    next: u64,
+    previous: String,
+}
+
+/// Hash-chained audit log in a [`KvStore`]
+///
+/// Entries are stored as JSON under `audit/<sequence>`, zero-padded so they
+/// scan in order.
+#[derive(Debug)]
+pub struct KvAuditSink<S: KvStore> {
+    head: Mutex<ChainHead<S>>,
+}
+
+impl<S: KvStore> KvAuditSink<S> {
+    /// Open the log in `store`, appending after any entries already there
+    pub fn open(store: S) -> Result<Self, AuditError> {
+        let entries: Vec<AuditEntry> = scan_json(&store, AUDIT_PREFIX)?;
+        let (next, previous) = entries.last().map_or_else(
+            || (0, genesis_hash()),
+            |last| (last.sequence + 1, last.hash.clone()),
+        );
+        Ok(Self {
+            head: Mutex::new(ChainHead {
+                store,
+                next,
+                previous,
+            }),
+        })
+    }
+
+    /// Every entry, oldest first
+    pub fn entries(&self) -> Result<Vec<AuditEntry>, AuditError> {
+        let head = self.lock()?;
+        Ok(scan_json(&head.store, AUDIT_PREFIX)?)
+    }
+
+    /// Check that every entry chains to the one before it
+    ///
+    /// Returns the number of entries.
+    pub fn verify(&self) -> Result<u64, AuditError> {
+        let mut previous = genesis_hash();
+        let mut count = 0;
+        for entry in self.entries()? {
+            let tampered = |reason: &str| AuditError::Tampered {
+                sequence: count,
+                reason: reason.to_string(),
+            };
+            if entry.sequence != count {
+                return Err(tampered("sequence gap"));
+            }
+            if entry.previous != previous {
+                return Err(tampered("previous hash does not match"));
+            }
+            if entry.hash != AuditEntry::chain_hash(&previous, entry.sequence, &entry.record) {
+                return Err(tampered("record hash does not match"));
+            }
+            previous = entry.hash;
+            count += 1;
+        }
+        Ok(count)
+    }
+
+    /// Give back the store
+    pub fn into_store(self) -> S {
+        match self.head.into_inner() {
+            Ok(head) => head.store,
+            Err(poisoned) => poisoned.into_inner().store,
+        }
+    }
+
+    fn lock(&self) -> Result<std::sync::MutexGuard<'_, ChainHead<S>>, AuditError> {
+        self.head
+            .lock()
+            .map_err(|_| AuditError::Sink("audit log lock poisoned".to_string()))
+    }
+}
+
+impl<S: KvStore + fmt::Debug> AuditSink for KvAuditSink<S> {
+    fn record(&self, record: &AuditRecord) -> Result<(), AuditError> {
+        let mut head = self.lock()?;
+        let sequence = head.next;
+        let entry = AuditEntry {
+            sequence,
+            previous: head.previous.clone(),
+            hash: AuditEntry::chain_hash(&head.previous, sequence, record),
+            record: record.clone(),
+        };
+        put_json(
+            &mut head.store,
+            &format!("{AUDIT_PREFIX}{sequence:020}"),
+            &entry,
+        )?;
+        head.next = sequence + 1;
+        head.previous = entry.hash;
+        drop(head);
+        Ok(())
+    }
+}
+
+fn genesis_hash() -> String {
+    hex::encode([0u8; 32])
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryKvStore;
+    use serde_json::json;
+
+    fn signed(n: u64) -> AuditRecord {
+        AuditRecord::Signed(SignedRecord::from_signature(
+            "acc://alice.acme/book/1",
+            &json!({"type": "ed25519", "transactionHash": format!("{n:064x}")}),
+        ))
+    }
+
+    #[test]
+    fn test_envelope_record_hashes() {
+        let envelope = json!({
+            "transaction": [{
+                "header": {"principal": "acc://alice.acme/tokens", "initiator": "11".repeat(32)},
+                "body": {"type": "burnCredits", "amount": 100}
+            }],
+            "signatures": [{"type": "ed25519", "transactionHash": "AB".repeat(32)}]
+        });
+        let record = SignedRecord::from_envelope("acc://alice.acme/book/1", &envelope);
+        assert_eq!(record.principal.as_deref(), Some("acc://alice.acme/tokens"));
+        assert_eq!(record.transaction_hash, "ab".repeat(32));
+        assert_eq!(
+            record.body_hash,
+            Some(hex::encode(
+                body_hash(&envelope["transaction"][0]["body"]).unwrap()
+            ))
+        );
+        assert_eq!(record.canonical, canonicalize(&envelope).into_bytes());
+        assert_eq!(
+            record.envelope_hash,
+            hex::encode(Sha256::digest(&record.canonical))
+        );
+    }
+
+    #[test]
+    fn test_kv_audit_log_chains_and_detects_tampering() {
+        let sink = KvAuditSink::open(MemoryKvStore::new()).unwrap();
+        for n in 0..3 {
+            sink.record(&signed(n)).unwrap();
+        }
+        assert_eq!(sink.verify().unwrap(), 3);
+
+        // Reopening continues the chain
+        let sink = KvAuditSink::open(sink.into_store()).unwrap();
+        sink.record(&signed(3)).unwrap();
+        assert_eq!(sink.verify().unwrap(), 4);
+
+        let mut store = sink.into_store();
+        let key = format!("{AUDIT_PREFIX}{:020}", 1);
+        let mut entry: AuditEntry = crate::storage::get_json(&store, &key).unwrap().unwrap();
+        entry.record = signed(9);
+        put_json(&mut store, &key, &entry).unwrap();
+        let sink = KvAuditSink::open(store).unwrap();
+        assert!(matches!(
+            sink.verify(),
+            Err(AuditError::Tampered { sequence: 1, .. })
+        ));
+    }
+}
@@ -0,0 +1,383 @@
+//! Account authority checks and unauthorized-submission guidance
+//!
+//! Every account names the key books that govern it. An authority can be
+//! disabled, which lets signers outside the authority set sign for the
+//! account, except for `updateKeyPage` and `updateAccountAuth`, which always
+//! need the authority itself. The network reports a mismatch as a bare
+//! "not authorized"; [`AccountAuth::check`] and [`AuthError::diagnose`] read
+//! the account record to say which authority set has to sign instead:
+//!
+//! ```
+//! use accumulate_client::auth::{AccountAuth, AuthError};
+//! use serde_json::json;
+//!
+//! let account = json!({
+//!     "type": "tokenAccount",
+//!     "url": "acc://alice.acme/tokens",
+//!     "authorities": [{"url": "acc://alice.acme/book"}]
+//! });
+//! let auth = AccountAuth::from_account("acc://alice.acme/tokens", &account);
+//! let err = auth.check("acc://bob.acme/book/1", "sendTokens").unwrap_err();
+//! assert!(matches!(err, AuthError::NotAnAuthority { .. }));
+//! ```
+
+use crate::acc_url::AccUrl;
+use crate::json_rpc_client::JsonRpcError;
+use crate::types::AccountAuthority;
+use serde_json::Value;
+use thiserror::Error;
+
+/// Go status codes for authorization failures
+const UNAUTHORIZED: i32 = 401;
+const FORBIDDEN: i32 = 403;
+
+/// Why a signer cannot authorize a transaction for an account
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum AuthError {
+    /// The signer's book is not one of the account's enabled authorities
+    #[error("{signer} cannot sign for {principal}: it is not an authority of the account. Sign with a key page of {}{}", one_of(.required), disabled_note(.disabled))]
+    NotAnAuthority {
+        /// Account the transaction is for
+        principal: String,
+        /// Authority the signer belongs to (a key book or lite identity)
+        signer: String,
+        /// Authorities whose signature would be accepted
+        required: Vec<String>,
+        /// Authorities of the account that are disabled
+        disabled: Vec<String>,
+    },
+
+    /// Auth is disabled on the account, but the transaction type ignores that
+    #[error("Auth is disabled on {principal}, but {tx_type} always requires its authorities: sign with a key page of {}", one_of(.required))]
+    AuthorityRequired {
+        /// Account the transaction is for
+        principal: String,
+        /// Transaction type, e.g. `updateAccountAuth`
+        tx_type: String,
+        /// Authorities of the account, enabled or not
+        required: Vec<String>,
+    },
+
+    /// The network refused the signer, but the account record does not say why
+    #[error("{principal} refused the signature: {message}. Its enabled authorities are {}", list(.required))]
+    Unauthorized {
+        /// Account the transaction is for
+        principal: String,
+        /// Enabled authorities of the account
+        required: Vec<String>,
+        /// Network message
+        message: String,
+    },
+}
+
+impl AuthError {
+    /// Whether a network error code or message reports an authorization failure
+    #[must_use]
+    pub fn is_auth_failure(code: i32, message: &str) -> bool {
+        let lower = message.to_ascii_lowercase();
+        code == UNAUTHORIZED
+            || code == FORBIDDEN
+            || [
+                "not authorized",
+                "unauthorized",
+                "is not an authority",
+                "forbidden",
+            ]
+            .iter()
+            .any(|needle| lower.contains(needle))
+    }
+
+    /// Explain a failed `tx_type` submission by `signer` against `auth`
+    ///
+    /// Returns `None` when `code` and `message` do not report an
+    /// authorization failure.
+    #[must_use]
+    pub fn diagnose(
+        auth: &AccountAuth,
+        signer: &str,
+        tx_type: &str,
+        code: i32,
+        message: &str,
+    ) -> Option<Self> {
+        if !Self::is_auth_failure(code, message) {
+            return None;
+        }
+        Some(
+            auth.check(signer, tx_type)
+                .err()
+                .unwrap_or_else(|| Self::Unauthorized {
+                    principal: auth.account.clone(),
+                    required: auth.enabled(),
+                    message: message.to_string(),
+                }),
+        )
+    }
+}
+
+impl From<AuthError> for JsonRpcError {
+    fn from(error: AuthError) -> Self {
+        Self::General(error.to_string())
+    }
+}
+
+fn list(urls: &[String]) -> String {
+    if urls.is_empty() {
+        "none".to_string()
+    } else {
+        urls.join(", ")
+    }
+}
+
+fn one_of(urls: &[String]) -> String {
+    match urls {
+        [only] => only.clone(),
+        _ => format!("one of: {}", list(urls)),
+    }
+}
+
+fn disabled_note(disabled: &[String]) -> String {
+    if disabled.is_empty() {
+        String::new()
+    } else {
+        format!(" (auth is disabled for {})", disabled.join(", "))
+    }
+}
+
+/// Whether `tx_type` needs the account's authorities even when they are disabled
+#[must_use]
+pub fn requires_authorization(tx_type: &str) -> bool {
+    matches!(tx_type, "updateKeyPage" | "updateAccountAuth")
+}
+
+/// Authority that a signer URL signs as: the book of a key page, or the
+/// signer itself (a lite identity or book)
+#[must_use]
+pub fn signer_authority(signer: &str) -> String {
+    let url = normalize(signer);
+    match AccUrl::parse(&url) {
+        Ok(page) if page.segments().last().is_some_and(|p| p.bytes().all(|b| b.is_ascii_digit())) => {
+            page.parent().map_or(url, String::from)
+        }
+        _ => url,
+    }
+}
+
+fn normalize(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/').to_ascii_lowercase();
+    if url.starts_with("acc://") {
+        url
+    } else {
+        format!("acc://{url}")
+    }
+}
+
+/// The authority set of an account
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccountAuth {
+    /// Account URL
+    pub account: String,
+    /// Authorities, enabled and disabled
+    pub authorities: Vec<AccountAuthority>,
+}
+
+impl AccountAuth {
+    /// Read the authority set from an account record as a V3 query returns it
+    ///
+    /// Lite accounts carry no authority list: they are governed by their
+    /// lite identity.
+    #[must_use]
+    pub fn from_account(url: &str, account: &Value) -> Self {
+        let account_type = account.get("type").and_then(Value::as_str).unwrap_or("");
+        let authorities = if matches!(account_type, "liteIdentity" | "liteTokenAccount") {
+            let path = url.strip_prefix("acc://").unwrap_or(url);
+            let identity = path.split('/').next().unwrap_or(path);
+            vec![AccountAuthority {
+                url: format!("acc://{identity}"),
+                disabled: false,
+            }]
+        } else {
+            account
+                .get("authorities")
+                .cloned()
+                .and_then(|a| serde_json::from_value(a).ok())
+                .unwrap_or_default()
+        };
+        Self {
+            account: url.to_string(),
+            authorities,
+        }
+    }
+
+    /// Authorities whose signature is required
+    #[must_use]
+    pub fn enabled(&self) -> Vec<String> {
+        self.urls(|a| !a.disabled)
+    }
+
+    /// Authorities whose signature requirement is disabled
+    #[must_use]
+    pub fn disabled(&self) -> Vec<String> {
+        self.urls(|a| a.disabled)
+    }
+
+    /// Whether every authority is disabled, so any signer may sign
+    #[must_use]
+    pub fn auth_disabled(&self) -> bool {
+        !self.authorities.is_empty() && self.authorities.iter().all(|a| a.disabled)
+    }
+
+    /// Whether `authority` is one of the account's authorities
+    #[must_use]
+    pub fn has_authority(&self, authority: &str) -> bool {
+        let authority = normalize(authority);
+        self.authorities
+            .iter()
+            .any(|a| normalize(&a.url) == authority)
+    }
+
+    /// Check that the key page or lite identity `signer` can authorize a
+    /// `tx_type` transaction for the account
+    ///
+    /// An account without a known authority set passes; the network has the
+    /// final word.
+    pub fn check(&self, signer: &str, tx_type: &str) -> Result<(), AuthError> {
+        let authority = signer_authority(signer);
+        if self.authorities.is_empty() || self.has_authority(&authority) {
+            return Ok(());
+        }
+        if requires_authorization(tx_type) {
+            if self.auth_disabled() {
+                return Err(AuthError::AuthorityRequired {
+                    principal: self.account.clone(),
+                    tx_type: tx_type.to_string(),
+                    required: self.urls(|_| true),
+                });
+            }
+        } else if self.auth_disabled() {
+            return Ok(());
+        }
+        Err(AuthError::NotAnAuthority {
+            principal: self.account.clone(),
+            signer: authority,
+            required: self.enabled(),
+            disabled: self.disabled(),
+        })
+    }
+
+    fn urls(&self, keep: impl Fn(&AccountAuthority) -> bool) -> Vec<String> {
+        self.authorities
+            .iter()
+            .filter(|a| keep(a))
+            .map(|a| a.url.clone())
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn auth(authorities: Value) -> AccountAuth {
+        AccountAuth::from_account(
+            "acc://alice.acme/tokens",
+            &json!({"type": "tokenAccount", "authorities": authorities}),
+        )
+    }
+
+    #[test]
+    fn test_check_enabled_and_disabled_authorities() {
+        let enabled = auth(json!([{"url": "acc://alice.acme/book"}]));
+        assert!(enabled
+            .check("acc://alice.acme/book/2", "sendTokens")
+            .is_ok());
+        assert!(enabled.check("alice.acme/Book/1/", "sendTokens").is_ok());
+        assert_eq!(
+            enabled.check("acc://bob.acme/book/1", "sendTokens"),
+            Err(AuthError::NotAnAuthority {
+                principal: "acc://alice.acme/tokens".to_string(),
+                signer: "acc://bob.acme/book".to_string(),
+                required: vec!["acc://alice.acme/book".to_string()],
+                disabled: Vec::new(),
+            })
+        );
+
+        // Disabled auth lets anyone sign, except for authority changes
+        let disabled = auth(json!([{"url": "acc://alice.acme/book", "disabled": true}]));
+        assert!(disabled.auth_disabled());
+        assert!(disabled
+            .check("acc://bob.acme/book/1", "sendTokens")
+            .is_ok());
+        let err = disabled
+            .check("acc://bob.acme/book/1", "updateAccountAuth")
+            .unwrap_err();
+        assert!(matches!(err, AuthError::AuthorityRequired { .. }));
+        assert_eq!(
+            err.to_string(),
+            "Auth is disabled on acc://alice.acme/tokens, but updateAccountAuth always \
+             requires its authorities: sign with a key page of acc://alice.acme/book"
+        );
+
+        // With one authority still enabled, that one has to sign
+        let mixed = auth(json!([
+            {"url": "acc://alice.acme/book", "disabled": true},
+            {"url": "acc://ops.acme/book"}
+        ]));
+        let err = mixed
+            .check("acc://bob.acme/book/1", "sendTokens")
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "acc://bob.acme/book cannot sign for acc://alice.acme/tokens: it is not an \
+             authority of the account. Sign with a key page of acc://ops.acme/book \
+             (auth is disabled for acc://alice.acme/book)"
+        );
+    }
+
+    #[test]
+    fn test_lite_accounts_and_diagnose() {
+        let lite = AccountAuth::from_account(
+            "acc://0143b52490530b90eef9b1a2405e4548a39f3dbd3bc4e6dd/ACME",
+            &json!({"type": "liteTokenAccount"}),
+        );
+        assert!(lite
+            .check(
+                "acc://0143b52490530b90eef9b1a2405e4548a39f3dbd3bc4e6dd",
+                "sendTokens"
+            )
+            .is_ok());
+
+        let enabled = auth(json!([{"url": "acc://alice.acme/book"}]));
+        assert_eq!(
+            AuthError::diagnose(
+                &enabled,
+                "acc://alice.acme/book/1",
+                "sendTokens",
+                0,
+                "insufficient balance"
+            ),
+            None
+        );
+        assert!(matches!(
+            AuthError::diagnose(
+                &enabled,
+                "acc://bob.acme/book/1",
+                "sendTokens",
+                0,
+                "acc://bob.acme/book/1 is not authorized to sign transactions for acc://alice.acme/tokens"
+            ),
+            Some(AuthError::NotAnAuthority { .. })
+        ));
+        assert!(matches!(
+            AuthError::diagnose(
+                &enabled,
+                "acc://alice.acme/book/1",
+                "sendTokens",
+                401,
+                "refused"
+            ),
+            Some(AuthError::Unauthorized { .. })
+        ));
+    }
+}
@@ -0,0 +1,28 @@
+//! Write JSON Schema and OpenRPC documents for the generated protocol types
+//!
+//! Usage: `cargo run --features json-schema --bin export_schema -- [DIR]`
+//! (defaults to `schema/`).
+
+use std::io::{self, Write};
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+fn main() -> ExitCode {
+    let dir = std::env::args_os()
+        .nth(1)
+        .map_or_else(|| PathBuf::from("schema"), PathBuf::from);
+
+    match accumulate_client::schema::export_all(&dir) {
+        Ok(written) => {
+            let mut out = io::stdout().lock();
+            for path in written {
+                let _ = writeln!(out, "{}", path.display());
+            }
+            ExitCode::SUCCESS
+        }
+        Err(e) => {
+            let _ = writeln!(io::stderr(), "export_schema: {}: {e}", dir.display());
+            ExitCode::FAILURE
+        }
+    }
+}
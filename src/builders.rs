@@ -0,0 +1,1045 @@
+//! Typed builders for transaction bodies
+//!
+//! [`TxBody`](crate::helpers::TxBody) returns raw JSON, so a misspelled field
+//! or a malformed URL is only caught by the network. The builders here produce
+//! the generated [`TransactionBody`] instead: required fields are constructor
+//! arguments, optional ones are chained setters, and `build()` runs the
+//! generated `validate()` before anything is signed.
+//!
+//! [`SmartSigner::sign_body`] and [`SmartSigner::sign_submit_body_and_wait`]
+//! take the typed body directly:
+//!
+//! ```
+//! use accumulate_client::builders::SendTokensBuilder;
+//! use accumulate_client::{Amount, TransactionBody};
+//!
+//! let body = SendTokensBuilder::new()
+//!     .to("acc://bob.acme/tokens", Amount::acme(5))
+//!     .build()
+//!     .unwrap();
+//! assert!(matches!(body, TransactionBody::SendTokens(_)));
+//!
+//! assert!(SendTokensBuilder::new().to("bob.acme", Amount::acme(5)).build().is_err());
+//! ```
+//!
+//! Only user transactions have builders. System and synthetic bodies are
+//! produced by the network and can be built from the generated structs when
+//! needed.
+
+use crate::amounts::Amount;
+use crate::errors::Error;
+use crate::generated::transactions::{
+    AddCreditsBody, BurnCreditsBody, BurnTokensBody, CreateDataAccountBody, CreateIdentityBody,
+    CreateKeyBookBody, CreateKeyPageBody, CreateTokenAccountBody, CreateTokenBody, IssueTokensBody,
+    LockAccountBody, RemoteTransactionBody, SendTokensBody, TransactionBody, TransferCreditsBody,
+    UpdateAccountAuthBody, UpdateKeyBody, UpdateKeyPageBody, WriteDataBody, WriteDataToBody,
+};
+use crate::helpers::{SmartSigner, TxResult};
+use crate::protocol::AllowedTransactions;
+use crate::json_rpc_client::JsonRpcError;
+use serde_json::{json, Map, Value};
+
+/// Validate `body` and hand it back
+fn finish(body: TransactionBody) -> Result<TransactionBody, Error> {
+    body.validate()?;
+    Ok(body)
+}
+
+/// Key page entry: a key hash or a delegate book
+fn key_spec(key_hash: Option<&[u8]>, delegate: Option<&str>) -> Value {
+    let mut entry = Map::new();
+    if let Some(hash) = key_hash {
+        entry.insert("keyHash".to_string(), json!(hex::encode(hash)));
+    }
+    if let Some(delegate) = delegate {
+        entry.insert("delegate".to_string(), json!(delegate));
+    }
+    Value::Object(entry)
+}
+
+/// Double-hash data entry over `entries`
+fn data_entry(entries: &[Vec<u8>]) -> Value {
+    let data: Vec<String> = entries.iter().map(hex::encode).collect();
+    json!({"type": "doublehash", "data": data})
+}
+
+/// JSON body as the network and the signer expect it
+///
+/// The generated types serialize with the wire's camelCase keys and hex
+/// bytes. `issueTokens` carries its recipients in `to` only, so the
+/// single-recipient fields are left out.
+pub fn to_wire(body: &TransactionBody) -> Result<Value, Error> {
+    let mut wire = serde_json::to_value(body)?;
+    if let Value::Object(fields) = &mut wire {
+        fields.retain(|_, value| !value.is_null());
+        if matches!(body, TransactionBody::IssueTokens(_)) {
+            fields.remove("recipient");
+            fields.remove("amount");
+        }
+    }
+    Ok(wire)
+}
+
+impl SmartSigner<'_> {
+    /// Validate a typed body and sign it for `principal`
+    pub fn sign_body(
+        &self,
+        principal: &str,
+        body: &TransactionBody,
+        memo: Option<&str>,
+    ) -> Result<Value, JsonRpcError> {
+        let wire = typed_to_wire(body)?;
+        self.sign(principal, &wire, memo)
+    }
+
+    /// Validate a typed body, then sign, submit and wait as
+    /// [`SmartSigner::sign_submit_and_wait`] does
+    pub async fn sign_submit_body_and_wait(
+        &mut self,
+        principal: &str,
+        body: &TransactionBody,
+        memo: Option<&str>,
+        max_attempts: u32,
+    ) -> TxResult {
+        match typed_to_wire(body) {
+            Ok(wire) => {
+                self.sign_submit_and_wait(principal, &wire, memo, max_attempts)
+                    .await
+            }
+            Err(e) => TxResult::err(format!("Invalid transaction body: {e}")),
+        }
+    }
+}
+
+fn typed_to_wire(body: &TransactionBody) -> Result<Value, JsonRpcError> {
+    body.validate()
+        .and_then(|()| to_wire(body))
+        .map_err(|e| JsonRpcError::General(e.to_string()))
+}
+
+/// Builder for `addCredits`
+#[derive(Debug, Clone)]
+pub struct AddCreditsBuilder {
+    recipient: String,
+    amount: Amount,
+    oracle: u64,
+}
+
+impl AddCreditsBuilder {
+    /// Buy credits for `recipient` with `amount` ACME at the `oracle` price
+    #[must_use]
+    pub fn new(recipient: &str, amount: Amount, oracle: u64) -> Self {
+        Self {
+            recipient: recipient.to_string(),
+            amount,
+            oracle,
+        }
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::AddCredits(AddCreditsBody {
+            recipient: self.recipient,
+            amount: self.amount.to_wire(),
+            oracle: self.oracle,
+        }))
+    }
+}
+
+/// Builder for `burnCredits`
+#[derive(Debug, Clone)]
+pub struct BurnCreditsBuilder {
+    amount: u64,
+}
+
+impl BurnCreditsBuilder {
+    /// Burn `amount` credits
+    #[must_use]
+    pub const fn new(amount: u64) -> Self {
+        Self { amount }
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::BurnCredits(BurnCreditsBody {
+            amount: self.amount,
+        }))
+    }
+}
+
+/// Builder for `burnTokens`
+#[derive(Debug, Clone)]
+pub struct BurnTokensBuilder {
+    amount: Amount,
+}
+
+impl BurnTokensBuilder {
+    /// Burn `amount` tokens from the principal
+    #[must_use]
+    pub const fn new(amount: Amount) -> Self {
+        Self { amount }
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::BurnTokens(BurnTokensBody {
+            amount: self.amount.to_wire(),
+        }))
+    }
+}
+
+/// Builder for `createDataAccount`
+#[derive(Debug, Clone)]
+pub struct CreateDataAccountBuilder {
+    url: String,
+    authorities: Vec<String>,
+}
+
+impl CreateDataAccountBuilder {
+    /// Create the data account `url`
+    #[must_use]
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            authorities: Vec::new(),
+        }
+    }
+
+    /// Add an authority besides the principal's
+    #[must_use]
+    pub fn authority(mut self, authority: &str) -> Self {
+        self.authorities.push(authority.to_string());
+        self
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::CreateDataAccount(CreateDataAccountBody {
+            url: self.url,
+            authorities: non_empty(self.authorities),
+        }))
+    }
+}
+
+/// Builder for `createIdentity`
+#[derive(Debug, Clone)]
+pub struct CreateIdentityBuilder {
+    url: String,
+    key_hash: Option<Vec<u8>>,
+    key_book_url: Option<String>,
+    authorities: Vec<String>,
+}
+
+impl CreateIdentityBuilder {
+    /// Create the ADI `url`
+    #[must_use]
+    pub fn new(url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            key_hash: None,
+            key_book_url: None,
+            authorities: Vec::new(),
+        }
+    }
+
+    /// Create the key book `url` with one page holding `key_hash`
+    #[must_use]
+    pub fn key_book(mut self, url: &str, key_hash: &[u8]) -> Self {
+        self.key_book_url = Some(url.to_string());
+        self.key_hash = Some(key_hash.to_vec());
+        self
+    }
+
+    /// Add an authority besides the new key book
+    #[must_use]
+    pub fn authority(mut self, authority: &str) -> Self {
+        self.authorities.push(authority.to_string());
+        self
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::CreateIdentity(CreateIdentityBody {
+            url: self.url,
+            key_hash: self.key_hash,
+            key_book_url: self.key_book_url,
+            authorities: non_empty(self.authorities),
+        }))
+    }
+}
+
+/// Builder for `createKeyBook`
+#[derive(Debug, Clone)]
+pub struct CreateKeyBookBuilder {
+    url: String,
+    public_key_hash: Vec<u8>,
+    authorities: Vec<String>,
+}
+
+impl CreateKeyBookBuilder {
+    /// Create the key book `url` with one page holding `public_key_hash`
+    #[must_use]
+    pub fn new(url: &str, public_key_hash: &[u8]) -> Self {
+        Self {
+            url: url.to_string(),
+            public_key_hash: public_key_hash.to_vec(),
+            authorities: Vec::new(),
+        }
+    }
+
+    /// Add an authority besides the book itself
+    #[must_use]
+    pub fn authority(mut self, authority: &str) -> Self {
+        self.authorities.push(authority.to_string());
+        self
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::CreateKeyBook(CreateKeyBookBody {
+            url: self.url,
+            public_key_hash: self.public_key_hash,
+            authorities: non_empty(self.authorities),
+        }))
+    }
+}
+
+/// Builder for `createKeyPage`
+#[derive(Debug, Clone, Default)]
+pub struct CreateKeyPageBuilder {
+    keys: Vec<Value>,
+}
+
+impl CreateKeyPageBuilder {
+    /// Empty page; add at least one key or delegate
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the key with hash `key_hash`
+    #[must_use]
+    pub fn key(mut self, key_hash: &[u8]) -> Self {
+        self.keys.push(key_spec(Some(key_hash), None));
+        self
+    }
+
+    /// Add the key book `delegate` as a delegate
+    #[must_use]
+    pub fn delegate(mut self, delegate: &str) -> Self {
+        self.keys.push(key_spec(None, Some(delegate)));
+        self
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::CreateKeyPage(CreateKeyPageBody {
+            keys: self.keys,
+        }))
+    }
+}
+
+/// Builder for `createToken`
+#[derive(Debug, Clone)]
+pub struct CreateTokenBuilder {
+    url: String,
+    symbol: String,
+    precision: u64,
+    properties: Option<String>,
+    supply_limit: Option<Amount>,
+    authorities: Vec<String>,
+}
+
+impl CreateTokenBuilder {
+    /// Create the token issuer `url`
+    #[must_use]
+    pub fn new(url: &str, symbol: &str, precision: u64) -> Self {
+        Self {
+            url: url.to_string(),
+            symbol: symbol.to_string(),
+            precision,
+            properties: None,
+            supply_limit: None,
+            authorities: Vec::new(),
+        }
+    }
+
+    /// URL of the token's properties document
+    #[must_use]
+    pub fn properties(mut self, url: &str) -> Self {
+        self.properties = Some(url.to_string());
+        self
+    }
+
+    /// Cap the supply, in base units of the new token
+    #[must_use]
+    pub const fn supply_limit(mut self, limit: Amount) -> Self {
+        self.supply_limit = Some(limit);
+        self
+    }
+
+    /// Add an authority besides the principal's
+    #[must_use]
+    pub fn authority(mut self, authority: &str) -> Self {
+        self.authorities.push(authority.to_string());
+        self
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::CreateToken(CreateTokenBody {
+            url: self.url,
+            symbol: self.symbol,
+            precision: self.precision,
+            properties: self.properties,
+            supply_limit: self.supply_limit.map(|l| l.to_wire()),
+            authorities: non_empty(self.authorities),
+        }))
+    }
+}
+
+/// Builder for `createTokenAccount`
+#[derive(Debug, Clone)]
+pub struct CreateTokenAccountBuilder {
+    url: String,
+    token_url: String,
+    authorities: Vec<String>,
+}
+
+impl CreateTokenAccountBuilder {
+    /// Create the account `url` holding `token_url` tokens
+    #[must_use]
+    pub fn new(url: &str, token_url: &str) -> Self {
+        Self {
+            url: url.to_string(),
+            token_url: token_url.to_string(),
+            authorities: Vec::new(),
+        }
+    }
+
+    /// Add an authority besides the principal's
+    #[must_use]
+    pub fn authority(mut self, authority: &str) -> Self {
+        self.authorities.push(authority.to_string());
+        self
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::CreateTokenAccount(
+            CreateTokenAccountBody {
+                url: self.url,
+                token_url: self.token_url,
+                authorities: non_empty(self.authorities),
+                proof: None,
+            },
+        ))
+    }
+}
+
+/// Builder for `issueTokens`
+#[derive(Debug, Clone, Default)]
+pub struct IssueTokensBuilder {
+    to: Vec<(String, Amount)>,
+}
+
+impl IssueTokensBuilder {
+    /// No recipients yet; add at least one
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Issue `amount` base units to `url`
+    #[must_use]
+    pub fn to(mut self, url: &str, amount: Amount) -> Self {
+        self.to.push((url.to_string(), amount));
+        self
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        let (recipient, amount) = self
+            .to
+            .first()
+            .map(|(url, amount)| (url.clone(), amount.to_wire()))
+            .unzip();
+        finish(TransactionBody::IssueTokens(IssueTokensBody {
+            recipient,
+            amount,
+            to: token_recipients(&self.to),
+        }))
+    }
+}
+
+/// Builder for `lockAccount`
+#[derive(Debug, Clone)]
+pub struct LockAccountBuilder {
+    height: u64,
+}
+
+impl LockAccountBuilder {
+    /// Lock the principal until major block `height`
+    #[must_use]
+    pub const fn new(height: u64) -> Self {
+        Self { height }
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::LockAccount(LockAccountBody {
+            height: self.height,
+        }))
+    }
+}
+
+/// Builder for `remoteTransaction`, a signature for an existing transaction
+#[derive(Debug, Clone)]
+pub struct RemoteTransactionBuilder {
+    hash: [u8; 32],
+}
+
+impl RemoteTransactionBuilder {
+    /// Refer to the transaction with hash `hash`
+    #[must_use]
+    pub const fn new(hash: [u8; 32]) -> Self {
+        Self { hash }
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::RemoteTransaction(RemoteTransactionBody {
+            hash: Some(self.hash.to_vec()),
+        }))
+    }
+}
+
+/// Builder for `sendTokens`
+#[derive(Debug, Clone, Default)]
+pub struct SendTokensBuilder {
+    to: Vec<(String, Amount)>,
+    meta: Option<Value>,
+}
+
+impl SendTokensBuilder {
+    /// No recipients yet; add at least one
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Send `amount` base units to `url`
+    #[must_use]
+    pub fn to(mut self, url: &str, amount: Amount) -> Self {
+        self.to.push((url.to_string(), amount));
+        self
+    }
+
+    /// Attach free-form metadata
+    #[must_use]
+    pub fn meta(mut self, meta: Value) -> Self {
+        self.meta = Some(meta);
+        self
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::SendTokens(SendTokensBody {
+            hash: None,
+            meta: self.meta,
+            to: token_recipients(&self.to),
+        }))
+    }
+}
+
+/// Builder for `transferCredits`
+#[derive(Debug, Clone, Default)]
+pub struct TransferCreditsBuilder {
+    to: Vec<(String, u64)>,
+}
+
+impl TransferCreditsBuilder {
+    /// No recipients yet; add at least one
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Transfer `amount` credits to `url`
+    #[must_use]
+    pub fn to(mut self, url: &str, amount: u64) -> Self {
+        self.to.push((url.to_string(), amount));
+        self
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::TransferCredits(TransferCreditsBody {
+            to: self
+                .to
+                .into_iter()
+                .map(|(url, amount)| json!({"url": url, "amount": amount}))
+                .collect(),
+        }))
+    }
+}
+
+/// Builder for `updateAccountAuth`
+#[derive(Debug, Clone, Default)]
+pub struct UpdateAccountAuthBuilder {
+    operations: Vec<Value>,
+}
+
+impl UpdateAccountAuthBuilder {
+    /// No operations yet; add at least one
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `authority` to the principal
+    #[must_use]
+    pub fn add_authority(self, authority: &str) -> Self {
+        self.operation("addAuthority", authority)
+    }
+
+    /// Remove `authority` from the principal
+    #[must_use]
+    pub fn remove_authority(self, authority: &str) -> Self {
+        self.operation("removeAuthority", authority)
+    }
+
+    /// Enable `authority` on the principal
+    #[must_use]
+    pub fn enable(self, authority: &str) -> Self {
+        self.operation("enable", authority)
+    }
+
+    /// Disable `authority` on the principal
+    #[must_use]
+    pub fn disable(self, authority: &str) -> Self {
+        self.operation("disable", authority)
+    }
+
+    fn operation(mut self, kind: &str, authority: &str) -> Self {
+        self.operations
+            .push(json!({"type": kind, "authority": authority}));
+        self
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::UpdateAccountAuth(UpdateAccountAuthBody {
+            operations: self.operations,
+        }))
+    }
+}
+
+/// Builder for `updateKey`, rotating the signing key
+#[derive(Debug, Clone)]
+pub struct UpdateKeyBuilder {
+    new_key_hash: Vec<u8>,
+}
+
+impl UpdateKeyBuilder {
+    /// Replace the signing key with the key hashing to `new_key_hash`
+    #[must_use]
+    pub fn new(new_key_hash: &[u8]) -> Self {
+        Self {
+            new_key_hash: new_key_hash.to_vec(),
+        }
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::UpdateKey(UpdateKeyBody {
+            new_key_hash: self.new_key_hash,
+        }))
+    }
+}
+
+/// Builder for `updateKeyPage`
+///
+/// Operations are applied in the order they are added, all in one
+/// transaction, so the page version changes once however many keys and
+/// thresholds are touched:
+///
+/// ```
+/// use accumulate_client::builders::UpdateKeyPageBuilder;
+///
+/// let body = UpdateKeyPageBuilder::new()
+///     .add_key(&[1u8; 32])
+///     .remove_key(&[2u8; 32])
+///     .set_threshold(2)
+///     .build()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct UpdateKeyPageBuilder {
+    operations: Vec<Value>,
+}
+
+impl UpdateKeyPageBuilder {
+    /// No operations yet; add at least one
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the key with hash `key_hash`
+    #[must_use]
+    pub fn add_key(mut self, key_hash: &[u8]) -> Self {
+        self.operations
+            .push(json!({"type": "add", "entry": key_spec(Some(key_hash), None)}));
+        self
+    }
+
+    /// Add the key book `delegate` as a delegate
+    #[must_use]
+    pub fn add_delegate(mut self, delegate: &str) -> Self {
+        self.operations
+            .push(json!({"type": "add", "entry": key_spec(None, Some(delegate))}));
+        self
+    }
+
+    /// Remove the key with hash `key_hash`
+    #[must_use]
+    pub fn remove_key(mut self, key_hash: &[u8]) -> Self {
+        self.operations
+            .push(json!({"type": "remove", "entry": key_spec(Some(key_hash), None)}));
+        self
+    }
+
+    /// Replace the key with hash `old_key_hash` by the one with `new_key_hash`
+    #[must_use]
+    pub fn update_key(mut self, old_key_hash: &[u8], new_key_hash: &[u8]) -> Self {
+        self.operations.push(json!({
+            "type": "update",
+            "oldEntry": key_spec(Some(old_key_hash), None),
+            "newEntry": key_spec(Some(new_key_hash), None),
+        }));
+        self
+    }
+
+    /// Set the accept threshold
+    #[must_use]
+    pub fn set_threshold(self, threshold: u64) -> Self {
+        self.threshold("setThreshold", threshold)
+    }
+
+    /// Set the reject threshold
+    #[must_use]
+    pub fn set_reject_threshold(self, threshold: u64) -> Self {
+        self.threshold("setRejectThreshold", threshold)
+    }
+
+    /// Set the response threshold
+    #[must_use]
+    pub fn set_response_threshold(self, threshold: u64) -> Self {
+        self.threshold("setResponseThreshold", threshold)
+    }
+
+    fn threshold(mut self, kind: &str, threshold: u64) -> Self {
+        self.operations
+            .push(json!({"type": kind, "threshold": threshold}));
+        self
+    }
+
+    /// Allow and deny transaction types, by name, e.g. `"sendTokens"`
+    #[must_use]
+    pub fn update_allowed(mut self, allow: &[&str], deny: &[&str]) -> Self {
+        let mut op = Map::new();
+        op.insert("type".to_string(), json!("updateAllowed"));
+        if !allow.is_empty() {
+            op.insert("allow".to_string(), json!(allow));
+        }
+        if !deny.is_empty() {
+            op.insert("deny".to_string(), json!(deny));
+        }
+        self.operations.push(Value::Object(op));
+        self
+    }
+
+    /// Allow and deny the transaction types in two masks
+    #[must_use]
+    pub fn update_allowed_mask(self, allow: AllowedTransactions, deny: AllowedTransactions) -> Self {
+        self.update_allowed(&allow.transaction_types(), &deny.transaction_types())
+    }
+
+    /// Number of operations added so far
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.operations.len()
+    }
+
+    /// Whether no operation has been added
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.operations.is_empty()
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::UpdateKeyPage(UpdateKeyPageBody {
+            operation: self.operations,
+        }))
+    }
+}
+
+/// Builder for `writeData`
+#[derive(Debug, Clone, Default)]
+pub struct WriteDataBuilder {
+    entries: Vec<Vec<u8>>,
+    scratch: Option<bool>,
+    write_to_state: Option<bool>,
+}
+
+impl WriteDataBuilder {
+    /// Empty entry; add its parts with [`Self::entry`]
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append one part of the data entry
+    #[must_use]
+    pub fn entry(mut self, data: &[u8]) -> Self {
+        self.entries.push(data.to_vec());
+        self
+    }
+
+    /// Write to the scratch chain
+    #[must_use]
+    pub const fn scratch(mut self, scratch: bool) -> Self {
+        self.scratch = Some(scratch);
+        self
+    }
+
+    /// Also keep the entry in the account state
+    #[must_use]
+    pub const fn write_to_state(mut self, write_to_state: bool) -> Self {
+        self.write_to_state = Some(write_to_state);
+        self
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::WriteData(WriteDataBody {
+            entry: data_entry(&self.entries),
+            scratch: self.scratch,
+            write_to_state: self.write_to_state,
+        }))
+    }
+}
+
+/// Builder for `writeDataTo`
+#[derive(Debug, Clone)]
+pub struct WriteDataToBuilder {
+    recipient: String,
+    entries: Vec<Vec<u8>>,
+}
+
+impl WriteDataToBuilder {
+    /// Write to the lite data account `recipient`
+    #[must_use]
+    pub fn new(recipient: &str) -> Self {
+        Self {
+            recipient: recipient.to_string(),
+            entries: Vec::new(),
+        }
+    }
+
+    /// Append one part of the data entry
+    #[must_use]
+    pub fn entry(mut self, data: &[u8]) -> Self {
+        self.entries.push(data.to_vec());
+        self
+    }
+
+    /// Validated body
+    pub fn build(self) -> Result<TransactionBody, Error> {
+        finish(TransactionBody::WriteDataTo(WriteDataToBody {
+            recipient: self.recipient,
+            entry: data_entry(&self.entries),
+        }))
+    }
+}
+
+fn non_empty(authorities: Vec<String>) -> Option<Vec<String>> {
+    (!authorities.is_empty()).then_some(authorities)
+}
+
+fn token_recipients(to: &[(String, Amount)]) -> Vec<Value> {
+    to.iter()
+        .map(|(url, amount)| json!({"url": url, "amount": amount.to_wire()}))
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::{sha256_hash, TxBody};
+    use crate::AccumulateClient;
+    use crate::AllowedTransactionBit;
+    use ed25519_dalek::SigningKey;
+
+    #[test]
+    fn test_builders_match_tx_body_wire_format() {
+        let key = [7u8; 32];
+        let cases = [
+            (
+                SendTokensBuilder::new()
+                    .to("acc://bob.acme/tokens", Amount::base_units(150))
+                    .build(),
+                TxBody::send_tokens_single("acc://bob.acme/tokens", "150"),
+            ),
+            (
+                CreateIdentityBuilder::new("acc://alice.acme")
+                    .key_book("acc://alice.acme/book", &key)
+                    .build(),
+                TxBody::create_identity(
+                    "acc://alice.acme",
+                    "acc://alice.acme/book",
+                    &hex::encode(key),
+                ),
+            ),
+            (
+                UpdateKeyPageBuilder::new().add_key(&key).build(),
+                TxBody::update_key_page_add_key(&key),
+            ),
+            (
+                UpdateKeyBuilder::new(&key).build(),
+                TxBody::update_key(&hex::encode(key)),
+            ),
+            (
+                WriteDataBuilder::new().entry(b"hello").build(),
+                TxBody::write_data(&["hello"]),
+            ),
+            (
+                IssueTokensBuilder::new()
+                    .to("acc://bob.acme/tokens", Amount::base_units(9))
+                    .build(),
+                TxBody::issue_tokens_single("acc://bob.acme/tokens", "9"),
+            ),
+            (
+                TransferCreditsBuilder::new()
+                    .to("acc://bob.acme/book/1", 5)
+                    .build(),
+                TxBody::transfer_credits("acc://bob.acme/book/1", 5),
+            ),
+        ];
+        for (built, expected) in cases {
+            assert_eq!(to_wire(&built.unwrap()).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn test_update_key_page_builder_marshals_every_operation() {
+        use crate::codec::signing::{
+            marshal_key_page_operation, marshal_update_allowed_operation,
+            marshal_update_key_page_body, tx_types,
+        };
+        use crate::helpers::marshal_body_to_binary;
+
+        let (a, b) = ([1u8; 32], [2u8; 32]);
+        let book = "acc://bob.acme/book";
+        let builder = UpdateKeyPageBuilder::new()
+            .add_key(&a)
+            .add_delegate(book)
+            .update_key(&a, &b)
+            .remove_key(&b)
+            .set_threshold(2)
+            .set_reject_threshold(1)
+            .set_response_threshold(1)
+            .update_allowed(&["sendTokens"], &[]);
+        assert_eq!(builder.len(), 8);
+
+        let wire = to_wire(&builder.build().unwrap()).unwrap();
+        let expected = marshal_update_key_page_body(&[
+            marshal_key_page_operation("add", Some(&a), None, None, None, None),
+            marshal_key_page_operation("add", None, Some(book), None, None, None),
+            marshal_key_page_operation("update", None, None, Some(&a), Some(&b), None),
+            marshal_key_page_operation("remove", Some(&b), None, None, None, None),
+            marshal_key_page_operation("setThreshold", None, None, None, None, Some(2)),
+            marshal_key_page_operation("setRejectThreshold", None, None, None, None, Some(1)),
+            marshal_key_page_operation("setResponseThreshold", None, None, None, None, Some(1)),
+            marshal_update_allowed_operation(&[tx_types::SEND_TOKENS], &[]),
+        ]);
+        assert_eq!(marshal_body_to_binary(&wire).unwrap(), expected);
+
+        // A delegate-only entry still carries the delegate
+        let delegate_op = marshal_key_page_operation("add", None, Some(book), None, None, None);
+        assert!(delegate_op
+            .windows(book.len())
+            .any(|w| w == book.as_bytes()));
+
+        assert!(UpdateKeyPageBuilder::new().is_empty());
+        let masked = UpdateKeyPageBuilder::new().update_allowed_mask(
+            AllowedTransactions::empty(),
+            AllowedTransactionBit::UpdateAccountAuth.into(),
+        );
+        assert_eq!(
+            to_wire(&masked.build().unwrap()).unwrap()["operation"][0],
+            json!({"type": "updateAllowed", "deny": ["updateAccountAuth"]})
+        );
+        assert!(
+            marshal_body_to_binary(&TxBody::update_key_page(&json!([{"type": "addKey"}]))).is_err()
+        );
+    }
+
+    #[test]
+    fn test_builders_validate() {
+        assert!(SendTokensBuilder::new().build().is_err());
+        assert!(BurnTokensBuilder::new(Amount::base_units(0))
+            .build()
+            .is_err());
+        assert!(CreateKeyBookBuilder::new("acc://alice.acme/book", &[1, 2])
+            .build()
+            .is_err());
+        assert!(
+            CreateTokenAccountBuilder::new("alice.acme/tokens", "acc://ACME")
+                .build()
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sign_body_matches_json_signing() {
+        let client = AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap();
+        let signer = SmartSigner::new(
+            &client,
+            SigningKey::from_bytes(&[3u8; 32]),
+            "acc://alice.acme/book/1",
+        );
+        let key_hash = sha256_hash(&[4u8; 32]);
+
+        let body = CreateKeyPageBuilder::new().key(&key_hash).build().unwrap();
+        let typed = signer
+            .sign_body("acc://alice.acme/book", &body, None)
+            .unwrap();
+        let raw = signer
+            .sign(
+                "acc://alice.acme/book",
+                &TxBody::create_key_page(&[&key_hash]),
+                None,
+            )
+            .unwrap();
+        assert_eq!(
+            typed["transaction"][0]["body"],
+            raw["transaction"][0]["body"]
+        );
+
+        // Hand-built bodies are validated too
+        let invalid = TransactionBody::BurnCredits(BurnCreditsBody { amount: 0 });
+        assert!(signer
+            .sign_body("acc://alice.acme/book/1", &invalid, None)
+            .is_err());
+    }
+}
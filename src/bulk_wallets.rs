@@ -0,0 +1,411 @@
+//! Bulk wallet generation with batched faucet funding
+//!
+//! Load tests against DevNet or a testnet need many funded lite accounts.
+//! [`WalletBatch`] generates them and funds them concurrently: at most
+//! [`WalletBatch::concurrency`] wallets are in flight, and faucet requests
+//! from all of them are spaced by [`WalletBatch::request_interval`] so the
+//! batch stays under the faucet's rate limit. Rate-limited requests are
+//! retried with the delay the faucet names; once the faucet reports that it
+//! is empty the remaining wallets are not asked for.
+//!
+//! ```no_run
+//! # async fn example(quickstart: accumulate_client::helpers::QuickStart) {
+//! use accumulate_client::bulk_wallets::WalletBatch;
+//!
+//! let funded = WalletBatch::new(50)
+//!     .concurrency(8)
+//!     .fund(quickstart.shared_client())
+//!     .await;
+//! let client = quickstart.client();
+//! let signers = funded.signers(client);
+//! # }
+//! ```
+
+use crate::client::AccumulateClient;
+use crate::faucet::FaucetError;
+use crate::helpers::{query_balance, SmartSigner, Wallet};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::task::JoinSet;
+use tokio::time::Instant;
+
+/// Wallets funded at the same time when no concurrency is set
+pub const DEFAULT_CONCURRENCY: usize = 4;
+
+/// Spacing between faucet requests when no interval is set
+pub const DEFAULT_REQUEST_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Retries of a rate-limited faucet request when no limit is set
+pub const DEFAULT_MAX_RETRIES: u32 = 5;
+
+/// Delay before retrying when the faucet names none
+const RETRY_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Delay between balance reads while waiting for faucet tokens to arrive
+const BALANCE_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Why a wallet could not be funded
+#[derive(Error, Debug)]
+pub enum FundingError {
+    /// The faucet refused a request
+    #[error(transparent)]
+    Faucet(#[from] FaucetError),
+    /// The tokens did not arrive before the confirmation timeout
+    #[error("{account} holds {balance} base units, wanted {wanted}")]
+    NotCredited {
+        /// Lite token account
+        account: String,
+        /// Last balance read
+        balance: u64,
+        /// Balance waited for
+        wanted: u64,
+    },
+    /// The funding task panicked or was cancelled
+    #[error("Funding task failed: {0}")]
+    Task(String),
+}
+
+/// A wallet that holds faucet tokens
+#[derive(Debug, Clone)]
+pub struct FundedWallet {
+    /// The wallet
+    pub wallet: Wallet,
+    /// Balance of its lite token account when funding finished, in base units
+    pub balance: u64,
+}
+
+/// Outcome of [`WalletBatch::fund`], in generation order
+#[derive(Debug)]
+pub struct BulkFunding {
+    /// Wallets that were funded
+    pub funded: Vec<FundedWallet>,
+    /// Wallets that were not, and why
+    pub failed: Vec<(Wallet, FundingError)>,
+}
+
+impl BulkFunding {
+    /// Whether every wallet was funded
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        self.failed.is_empty()
+    }
+
+    /// A signer for each funded wallet, signing as its lite identity
+    #[must_use]
+    pub fn signers<'a>(&self, client: &'a AccumulateClient) -> Vec<SmartSigner<'a>> {
+        self.funded
+            .iter()
+            .map(|f| SmartSigner::new(client, f.wallet.keypair().clone(), &f.wallet.lite_identity))
+            .collect()
+    }
+}
+
+/// Generate `count` wallets with fresh keys
+#[must_use]
+pub fn generate_wallets(count: usize) -> Vec<Wallet> {
+    (0..count).map(|_| Wallet::generate()).collect()
+}
+
+/// Settings for generating and funding a batch of wallets
+#[derive(Debug, Clone)]
+pub struct WalletBatch {
+    count: usize,
+    faucet_requests: u32,
+    concurrency: usize,
+    request_interval: Duration,
+    max_retries: u32,
+    min_balance: u64,
+    confirm_timeout: Duration,
+}
+
+impl WalletBatch {
+    /// Generate and fund `count` wallets, one faucet request each
+    #[must_use]
+    pub const fn new(count: usize) -> Self {
+        Self {
+            count,
+            faucet_requests: 1,
+            concurrency: DEFAULT_CONCURRENCY,
+            request_interval: DEFAULT_REQUEST_INTERVAL,
+            max_retries: DEFAULT_MAX_RETRIES,
+            min_balance: 1,
+            confirm_timeout: Duration::from_secs(120),
+        }
+    }
+
+    /// Faucet requests per wallet
+    #[must_use]
+    pub const fn faucet_requests(mut self, requests: u32) -> Self {
+        self.faucet_requests = requests;
+        self
+    }
+
+    /// Wallets funded at the same time (at least one)
+    #[must_use]
+    pub fn concurrency(mut self, wallets: usize) -> Self {
+        self.concurrency = wallets.max(1);
+        self
+    }
+
+    /// Minimum spacing between any two faucet requests of the batch
+    #[must_use]
+    pub const fn request_interval(mut self, interval: Duration) -> Self {
+        self.request_interval = interval;
+        self
+    }
+
+    /// Retries of a rate-limited or unavailable faucet request
+    #[must_use]
+    pub const fn max_retries(mut self, retries: u32) -> Self {
+        self.max_retries = retries;
+        self
+    }
+
+    /// Wait until each account holds `base_units`; zero skips the wait
+    #[must_use]
+    pub const fn min_balance(mut self, base_units: u64) -> Self {
+        self.min_balance = base_units;
+        self
+    }
+
+    /// How long to wait for the tokens of each wallet to arrive
+    #[must_use]
+    pub const fn confirm_timeout(mut self, timeout: Duration) -> Self {
+        self.confirm_timeout = timeout;
+        self
+    }
+
+    /// Generate the wallets and fund them
+    pub async fn fund(&self, client: Arc<AccumulateClient>) -> BulkFunding {
+        self.fund_wallets(client, generate_wallets(self.count))
+            .await
+    }
+
+    /// Fund existing wallets (the batch's count is ignored)
+    pub async fn fund_wallets(
+        &self,
+        client: Arc<AccumulateClient>,
+        wallets: Vec<Wallet>,
+    ) -> BulkFunding {
+        let shared = Arc::new(Shared {
+            limiter: RequestSpacer::new(self.request_interval),
+            drained: AtomicBool::new(false),
+        });
+        let mut results: Vec<Option<Result<u64, FundingError>>> =
+            wallets.iter().map(|_| None).collect();
+        let mut tasks = JoinSet::new();
+        let mut task_index = HashMap::new();
+        let mut pending = wallets.iter().enumerate();
+
+        loop {
+            while tasks.len() < self.concurrency {
+                let Some((index, wallet)) = pending.next() else {
+                    break;
+                };
+                let (client, shared, batch) = (client.clone(), shared.clone(), self.clone());
+                let account = wallet.lite_token_account.clone();
+                let handle =
+                    tasks.spawn(async move { batch.fund_one(&client, &shared, &account).await });
+                task_index.insert(handle.id(), index);
+            }
+            let Some(joined) = tasks.join_next_with_id().await else {
+                break;
+            };
+            let (id, result) = match joined {
+                Ok((id, result)) => (id, result),
+                Err(e) => (e.id(), Err(FundingError::Task(e.to_string()))),
+            };
+            if let Some(index) = task_index.remove(&id) {
+                results[index] = Some(result);
+            }
+        }
+
+        let mut report = BulkFunding {
+            funded: Vec::new(),
+            failed: Vec::new(),
+        };
+        for (wallet, result) in wallets.into_iter().zip(results) {
+            match result.unwrap_or_else(|| Err(FundingError::Task("not run".to_string()))) {
+                Ok(balance) => report.funded.push(FundedWallet { wallet, balance }),
+                Err(e) => report.failed.push((wallet, e)),
+            }
+        }
+        report
+    }
+
+    /// Request tokens for one account, then wait for them to arrive
+    async fn fund_one(
+        &self,
+        client: &AccumulateClient,
+        shared: &Shared,
+        account: &str,
+    ) -> Result<u64, FundingError> {
+        for _ in 0..self.faucet_requests {
+            self.request(client, shared, account).await?;
+        }
+        if self.min_balance == 0 {
+            return Ok(0);
+        }
+
+        let deadline = Instant::now() + self.confirm_timeout;
+        loop {
+            let balance = query_balance(client, account).await.unwrap_or(0);
+            if balance >= self.min_balance {
+                return Ok(balance);
+            }
+            if Instant::now() >= deadline {
+                return Err(FundingError::NotCredited {
+                    account: account.to_string(),
+                    balance,
+                    wanted: self.min_balance,
+                });
+            }
+            tokio::time::sleep(BALANCE_POLL_INTERVAL.min(deadline - Instant::now())).await;
+        }
+    }
+
+    /// One faucet request, retried while the faucet asks to slow down
+    async fn request(
+        &self,
+        client: &AccumulateClient,
+        shared: &Shared,
+        account: &str,
+    ) -> Result<(), FundingError> {
+        let mut retries = 0;
+        loop {
+            if shared.drained.load(Ordering::Relaxed) {
+                return Err(FaucetError::Empty {
+                    message: "faucet was drained earlier in the batch".to_string(),
+                }
+                .into());
+            }
+            shared.limiter.wait().await;
+            let Err(error) = client.request_faucet(account).await else {
+                return Ok(());
+            };
+            if matches!(error, FaucetError::Empty { .. }) {
+                shared.drained.store(true, Ordering::Relaxed);
+            }
+            match error.backoff(RETRY_BACKOFF) {
+                Some(delay) if retries < self.max_retries => {
+                    retries += 1;
+                    tokio::time::sleep(delay).await;
+                }
+                _ => return Err(error.into()),
+            }
+        }
+    }
+}
+
+/// State shared by the funding tasks of a batch
+#[derive(Debug)]
+struct Shared {
+    limiter: RequestSpacer,
+    drained: AtomicBool,
+}
+
+/// Hands out request slots at least `interval` apart
+#[derive(Debug)]
+struct RequestSpacer {
+    interval: Duration,
+    next: Mutex<Option<Instant>>,
+}
+
+impl RequestSpacer {
+    const fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            next: Mutex::new(None),
+        }
+    }
+
+    /// Reserve the next free slot and sleep until it
+    async fn wait(&self) {
+        let slot = {
+            let now = Instant::now();
+            let mut next = self
+                .next
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner);
+            let slot = next.map_or(now, |next| next.max(now));
+            *next = Some(slot + self.interval);
+            slot
+        };
+        tokio::time::sleep_until(slot).await;
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::derive_lite_identity_url;
+
+    #[test]
+    fn test_generate_wallets() {
+        let wallets = generate_wallets(3);
+        assert_eq!(wallets.len(), 3);
+        assert_ne!(wallets[0].public_key(), wallets[1].public_key());
+        for wallet in &wallets {
+            assert_eq!(
+                wallet.lite_identity,
+                derive_lite_identity_url(&wallet.public_key())
+            );
+            assert_eq!(
+                wallet.lite_token_account,
+                format!("{}/ACME", wallet.lite_identity)
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_request_spacer() {
+        let spacer = RequestSpacer::new(Duration::from_millis(20));
+        let start = Instant::now();
+        for _ in 0..3 {
+            spacer.wait().await;
+        }
+        assert!(start.elapsed() >= Duration::from_millis(40));
+    }
+
+    #[tokio::test]
+    async fn test_unreachable_faucet() {
+        let client = Arc::new(
+            AccumulateClient::new_with_options(
+                url::Url::parse("http://localhost:26660/v2").unwrap(),
+                url::Url::parse("http://localhost:26661/v3").unwrap(),
+                crate::AccOptions::default(),
+            )
+            .await
+            .unwrap(),
+        );
+        let wallets = generate_wallets(3);
+        let funded = WalletBatch::new(0)
+            .concurrency(2)
+            .request_interval(Duration::ZERO)
+            .max_retries(0)
+            .fund_wallets(client.clone(), wallets.clone())
+            .await;
+
+        assert!(!funded.is_complete());
+        assert!(funded.funded.is_empty());
+        assert!(funded.signers(&client).is_empty());
+        // Failures come back in generation order
+        for ((wallet, error), expected) in funded.failed.iter().zip(&wallets) {
+            assert_eq!(wallet.lite_identity, expected.lite_identity);
+            assert!(matches!(error, FundingError::Faucet(e) if e.is_retryable()));
+        }
+
+        // Nothing to request and nothing to wait for
+        let funded = WalletBatch::new(2)
+            .faucet_requests(0)
+            .min_balance(0)
+            .fund(client.clone())
+            .await;
+        assert!(funded.is_complete());
+        assert_eq!(funded.signers(&client).len(), 2);
+    }
+}
@@ -0,0 +1,270 @@
+//! Block cadence estimation from recent minor block times
+//!
+//! [`AccumulateClient::block_cadence`] samples a partition's latest minor
+//! blocks and measures the time between them. The resulting
+//! [`BlockCadence`] turns a delay into a minor block index for
+//! [`HoldUntilOptions`], and a number of blocks into a confirmation timeout
+//! that allows for the partition's jitter:
+//!
+//! ```no_run
+//! # async fn run(client: &accumulate_client::AccumulateClient) -> Result<(), Box<dyn std::error::Error>> {
+//! use std::time::Duration;
+//!
+//! let cadence = client.block_cadence("Apollo").await?;
+//! let hold = cadence.hold_until(Duration::from_secs(600));
+//! let timeout = cadence.confirmation_timeout(4);
+//! println!("{:?} per block, hold until block {:?}", cadence.average, hold.minor_block);
+//! # let _ = timeout;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::anchors::partition_url;
+use crate::client::AccumulateClient;
+use crate::generated::header::HoldUntilOptions;
+use crate::json_rpc_client::JsonRpcError;
+use crate::query::{BlockResult, MinorBlockRecord};
+use crate::types::{BlockQuery, RangeOptions};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+use thiserror::Error;
+
+/// Minor blocks sampled by [`AccumulateClient::block_cadence`]
+pub const DEFAULT_CADENCE_SAMPLES: u64 = 20;
+
+/// Why a partition's cadence could not be estimated
+#[derive(Error, Debug)]
+pub enum CadenceError {
+    /// The block query failed
+    #[error(transparent)]
+    Network(#[from] JsonRpcError),
+
+    /// Fewer than two timestamped blocks came back
+    #[error("Need at least 2 timestamped blocks from {partition}, got {found}")]
+    NotEnoughBlocks {
+        /// Partition queried
+        partition: String,
+        /// Timestamped blocks received
+        found: usize,
+    },
+
+    /// A block time is not RFC 3339
+    #[error("Block {index} has an invalid time: {time}")]
+    InvalidTime {
+        /// Block index
+        index: u64,
+        /// The time as sent
+        time: String,
+    },
+}
+
+/// Time between a partition's minor blocks
+#[derive(Debug, Clone, PartialEq)]
+pub struct BlockCadence {
+    /// Partition the blocks came from
+    pub partition: String,
+    /// Block intervals measured
+    pub samples: usize,
+    /// Mean time per block
+    pub average: Duration,
+    /// Variance of the time per block, in seconds squared
+    pub variance: f64,
+    /// Shortest time per block seen
+    pub min: Duration,
+    /// Longest time per block seen
+    pub max: Duration,
+    /// Index of the newest block sampled
+    pub latest_block: u64,
+    /// Time of the newest block sampled
+    pub latest_time: DateTime<Utc>,
+}
+
+impl BlockCadence {
+    /// Measure the cadence of `blocks`, in any order
+    ///
+    /// Blocks without a time are skipped. When empty blocks were omitted, the
+    /// time between two blocks is spread over the indices between them.
+    #[allow(clippy::cast_precision_loss)]
+    pub fn from_blocks(partition: &str, blocks: &[MinorBlockRecord]) -> Result<Self, CadenceError> {
+        let mut times = Vec::with_capacity(blocks.len());
+        for block in blocks {
+            let Some(time) = &block.time else { continue };
+            let parsed =
+                DateTime::parse_from_rfc3339(time).map_err(|_| CadenceError::InvalidTime {
+                    index: block.index,
+                    time: time.clone(),
+                })?;
+            times.push((block.index, parsed.with_timezone(&Utc)));
+        }
+        times.sort_by_key(|(index, _)| *index);
+        times.dedup_by_key(|(index, _)| *index);
+
+        let per_block: Vec<f64> = times
+            .windows(2)
+            .map(|pair| {
+                let elapsed = (pair[1].1 - pair[0].1).num_milliseconds().max(0) as f64 / 1000.0;
+                elapsed / (pair[1].0 - pair[0].0) as f64
+            })
+            .collect();
+        let (Some(&(latest_block, latest_time)), false) = (times.last(), per_block.is_empty())
+        else {
+            return Err(CadenceError::NotEnoughBlocks {
+                partition: partition.to_string(),
+                found: times.len(),
+            });
+        };
+
+        let count = per_block.len() as f64;
+        let mean = per_block.iter().sum::<f64>() / count;
+        let variance = per_block.iter().map(|s| (s - mean).powi(2)).sum::<f64>() / count;
+        let min = per_block.iter().copied().fold(f64::INFINITY, f64::min);
+        let max = per_block.iter().copied().fold(0.0, f64::max);
+        Ok(Self {
+            partition: partition.to_string(),
+            samples: per_block.len(),
+            average: Duration::from_secs_f64(mean),
+            variance,
+            min: Duration::from_secs_f64(min),
+            max: Duration::from_secs_f64(max),
+            latest_block,
+            latest_time,
+        })
+    }
+
+    /// Standard deviation of the time per block
+    #[must_use]
+    pub fn std_dev(&self) -> Duration {
+        Duration::from_secs_f64(self.variance.sqrt())
+    }
+
+    /// Minor blocks expected to pass in `delay`, rounded up
+    #[must_use]
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub fn blocks_in(&self, delay: Duration) -> u64 {
+        if self.average.is_zero() {
+            return 0;
+        }
+        (delay.as_secs_f64() / self.average.as_secs_f64()).ceil() as u64
+    }
+
+    /// Minor block expected `delay` after the newest sampled block
+    #[must_use]
+    pub fn block_after(&self, delay: Duration) -> u64 {
+        self.latest_block + self.blocks_in(delay)
+    }
+
+    /// Hold a transaction until about `delay` after the newest sampled block
+    #[must_use]
+    pub fn hold_until(&self, delay: Duration) -> HoldUntilOptions {
+        HoldUntilOptions {
+            minor_block: Some(self.block_after(delay)),
+        }
+    }
+
+    /// Time to wait for `blocks` minor blocks, allowing three standard
+    /// deviations per block
+    #[must_use]
+    pub fn confirmation_timeout(&self, blocks: u32) -> Duration {
+        (self.average + self.std_dev() * 3) * blocks
+    }
+}
+
+impl AccumulateClient {
+    /// Cadence of `partition`'s latest [`DEFAULT_CADENCE_SAMPLES`] minor
+    /// blocks
+    ///
+    /// `partition` is `dn` or a BVN ID such as `Apollo`.
+    pub async fn block_cadence(&self, partition: &str) -> Result<BlockCadence, CadenceError> {
+        self.block_cadence_with_samples(partition, DEFAULT_CADENCE_SAMPLES)
+            .await
+    }
+
+    /// Cadence of `partition`'s latest `samples` minor blocks
+    pub async fn block_cadence_with_samples(
+        &self,
+        partition: &str,
+        samples: u64,
+    ) -> Result<BlockCadence, CadenceError> {
+        let query = BlockQuery {
+            minor_range: Some(RangeOptions {
+                count: Some(samples.max(2)),
+                from_end: Some(true),
+                ..Default::default()
+            }),
+            omit_empty: Some(false),
+            ..Default::default()
+        };
+        let blocks = match self
+            .v3()
+            .query_block(&partition_url(partition), query)
+            .await?
+        {
+            BlockResult::MinorBlocks(range) => range.records,
+            BlockResult::Minor(block) => vec![block],
+            BlockResult::Major(_) | BlockResult::MajorBlocks(_) => Vec::new(),
+        };
+        BlockCadence::from_blocks(partition, &blocks)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn block(index: u64, time: &str) -> MinorBlockRecord {
+        MinorBlockRecord {
+            index,
+            time: Some(time.to_string()),
+            source: None,
+            entries: None,
+            anchored: None,
+        }
+    }
+
+    #[test]
+    fn test_cadence_from_blocks() {
+        // Out of order, with block 13 omitted as empty
+        let blocks = [
+            block(12, "2024-05-01T10:00:02Z"),
+            block(10, "2024-05-01T10:00:00Z"),
+            block(14, "2024-05-01T10:00:06Z"),
+            block(11, "2024-05-01T10:00:01Z"),
+            MinorBlockRecord {
+                time: None,
+                ..block(15, "")
+            },
+        ];
+        let cadence = BlockCadence::from_blocks("Apollo", &blocks).unwrap();
+        assert_eq!(cadence.samples, 3);
+        assert_eq!(cadence.latest_block, 14);
+        assert_eq!(cadence.min, Duration::from_secs(1));
+        assert_eq!(cadence.max, Duration::from_secs(2));
+        // Per-block times 1, 1 and 2 seconds
+        assert_eq!(cadence.average, Duration::from_secs_f64(4.0 / 3.0));
+        assert!((cadence.variance - 2.0 / 9.0).abs() < 1e-9);
+
+        assert_eq!(cadence.blocks_in(Duration::from_secs(5)), 4);
+        assert_eq!(
+            cadence.hold_until(Duration::from_secs(5)).minor_block,
+            Some(18)
+        );
+        let timeout = cadence.confirmation_timeout(2);
+        assert!(
+            timeout > cadence.average * 2 && timeout < cadence.max * 4,
+            "{timeout:?}"
+        );
+    }
+
+    #[test]
+    fn test_cadence_needs_timestamped_blocks() {
+        let err = BlockCadence::from_blocks("dn", &[block(1, "2024-05-01T10:00:00Z")]).unwrap_err();
+        assert!(
+            matches!(err, CadenceError::NotEnoughBlocks { found: 1, .. }),
+            "{err}"
+        );
+
+        let err = BlockCadence::from_blocks("dn", &[block(1, "yesterday")]).unwrap_err();
+        assert_eq!(err.to_string(), "Block 1 has an invalid time: yesterday");
+    }
+}
@@ -8,6 +8,7 @@
 use serde::Serialize;
 use serde_json::Value;
 use std::collections::BTreeMap;
+use std::fmt::Write as _;
 
 /// Convert any serializable value to canonical JSON
 /// Recursively uses BTreeMap for maps; produces compact JSON identical to TypeScript SDK
@@ -21,12 +22,245 @@ pub fn canonicalize(value: &Value) -> String {
     canonicalize_internal(value)
 }
 
+/// Escape a string as a JSON string literal, quotes included
+///
+/// Produces exactly what the TypeScript SDK's `JSON.stringify` emits: `"` and
+/// `\\` are backslash-escaped, `\b \f \n \r \t` use their short forms, the
+/// remaining C0 controls become lowercase `\u00xx`, and everything else
+/// (DEL, U+2028/U+2029, CJK, characters outside the BMP) is written as raw
+/// UTF-8. Both canonical modes and the hashes built on them use this escaper.
+///
+/// A `&str` cannot hold a lone surrogate, and `serde_json` rejects `\ud800`
+/// style escapes when parsing, so unpaired surrogates never reach this
+/// function. Use [`escape_json_utf16`] for raw UTF-16 input.
+#[must_use]
+pub fn escape_json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    write_escaped(s, &mut out);
+    out
+}
+
+/// Escape UTF-16 code units as a JSON string literal, quotes included
+///
+/// Valid surrogate pairs are decoded and written as raw UTF-8. Lone surrogates
+/// are escaped as `\udxxx`, matching the well-formed `JSON.stringify` of
+/// ES2019, so the output is valid UTF-8 and round-trips in JavaScript.
+#[must_use]
+pub fn escape_json_utf16(units: &[u16]) -> String {
+    let mut out = String::with_capacity(units.len() + 2);
+    out.push('"');
+    for decoded in char::decode_utf16(units.iter().copied()) {
+        match decoded {
+            Ok(c) => push_escaped(c, &mut out),
+            Err(e) => {
+                let _ = write!(out, "\\u{:04x}", e.unpaired_surrogate());
+            }
+        }
+    }
+    out.push('"');
+    out
+}
+
+fn write_escaped(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        push_escaped(c, out);
+    }
+    out.push('"');
+}
+
+fn push_escaped(c: char, out: &mut String) {
+    match c {
+        '"' => out.push_str("\\\""),
+        '\\' => out.push_str("\\\\"),
+        '\u{08}' => out.push_str("\\b"),
+        '\u{0c}' => out.push_str("\\f"),
+        '\n' => out.push_str("\\n"),
+        '\r' => out.push_str("\\r"),
+        '\t' => out.push_str("\\t"),
+        c if c < '\u{20}' => {
+            let _ = write!(out, "\\u{:04x}", u32::from(c));
+        }
+        c => out.push(c),
+    }
+}
+
+/// Canonicalization scheme
+///
+/// [`CanonicalMode::Legacy`] is the SDK's historical encoding and stays the
+/// default, since existing signatures and golden vectors depend on it.
+/// [`CanonicalMode::Jcs`] follows RFC 8785 (JSON Canonicalization Scheme).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CanonicalMode {
+    /// Sorted keys by UTF-8 bytes, numbers as `serde_json` prints them
+    #[default]
+    Legacy,
+    /// RFC 8785: keys sorted by UTF-16 code units, ECMAScript number formatting
+    Jcs,
+}
+
+/// Errors from RFC 8785 canonicalization
+#[derive(Debug, thiserror::Error)]
+pub enum JcsError {
+    /// An integer that would change value when converted to an IEEE 754 double
+    ///
+    /// JCS serializes every number as a double. A JavaScript implementation
+    /// silently rounds such integers; this one refuses instead.
+    #[error("integer {0} cannot be represented exactly as an IEEE 754 double")]
+    UnsafeInteger(String),
+
+    /// `NaN` or infinity, which JSON cannot represent
+    #[error("non-finite number cannot be canonicalized")]
+    NonFinite,
+
+    /// The value could not be converted to JSON
+    #[error("serialization error: {0}")]
+    Serialization(#[from] serde_json::Error),
+}
+
+/// Canonicalize a JSON value using the given scheme
+pub fn canonicalize_with_mode(value: &Value, mode: CanonicalMode) -> Result<String, JcsError> {
+    match mode {
+        CanonicalMode::Legacy => Ok(canonicalize(value)),
+        CanonicalMode::Jcs => canonicalize_jcs(value),
+    }
+}
+
+/// Convert any serializable value to RFC 8785 canonical JSON
+pub fn dumps_jcs<T: Serialize>(value: &T) -> Result<String, JcsError> {
+    canonicalize_jcs(&serde_json::to_value(value)?)
+}
+
+/// Convert a JSON value to RFC 8785 (JCS) canonical JSON
+pub fn canonicalize_jcs(value: &Value) -> Result<String, JcsError> {
+    let mut out = String::new();
+    write_jcs(value, &mut out)?;
+    Ok(out)
+}
+
+fn write_jcs(value: &Value, out: &mut String) -> Result<(), JcsError> {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(&jcs_number(n)?),
+        Value::String(s) => write_escaped(s, out),
+        Value::Array(arr) => {
+            out.push('[');
+            for (i, item) in arr.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_jcs(item, out)?;
+            }
+            out.push(']');
+        }
+        Value::Object(obj) => {
+            let mut entries: Vec<(&String, &Value)> = obj.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| a.encode_utf16().cmp(b.encode_utf16()));
+
+            out.push('{');
+            for (i, (key, val)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                write_escaped(key, out);
+                out.push(':');
+                write_jcs(val, out)?;
+            }
+            out.push('}');
+        }
+    }
+    Ok(())
+}
+
+/// Format a JSON number the way ECMAScript's `Number.prototype.toString` does
+fn jcs_number(n: &serde_json::Number) -> Result<String, JcsError> {
+    // Beyond 2^53 a double no longer holds every integer, and ECMAScript prints
+    // even exactly representable ones with trailing zeros (2^60 becomes
+    // 1152921504606847000), so the text would no longer equal the input.
+    const MAX_SAFE_INTEGER: u64 = (1 << 53) - 1;
+
+    #[allow(clippy::cast_precision_loss)]
+    let value = if let Some(u) = n.as_u64() {
+        if u > MAX_SAFE_INTEGER {
+            return Err(JcsError::UnsafeInteger(u.to_string()));
+        }
+        u as f64
+    } else if let Some(i) = n.as_i64() {
+        if i.unsigned_abs() > MAX_SAFE_INTEGER {
+            return Err(JcsError::UnsafeInteger(i.to_string()));
+        }
+        i as f64
+    } else {
+        n.as_f64().ok_or(JcsError::NonFinite)?
+    };
+    format_es_number(value)
+}
+
+/// ECMAScript `Number::toString(x)` for finite doubles (ECMA-262 section 6.1.6.1.20)
+fn format_es_number(value: f64) -> Result<String, JcsError> {
+    if !value.is_finite() {
+        return Err(JcsError::NonFinite);
+    }
+    if value == 0.0 {
+        // Covers -0, which ECMAScript prints as "0"
+        return Ok("0".to_string());
+    }
+
+    // Rust's `{:e}` yields the shortest round-tripping digit count, but when two
+    // candidates of that length exist it does not always pick the closer one.
+    // Re-formatting at that precision rounds the exact value half-to-even,
+    // which is the tie-break ECMAScript requires.
+    let shortest = format!("{:e}", value.abs());
+    let precision = shortest
+        .split_once('e')
+        .map_or(0, |(m, _)| m.chars().filter(char::is_ascii_digit).count().saturating_sub(1));
+    let sci = format!("{:.*e}", precision, value.abs());
+    let (mantissa, exponent) = sci.split_once('e').unwrap_or((sci.as_str(), "0"));
+    let digits: String = mantissa.chars().filter(char::is_ascii_digit).collect();
+    let exponent: i32 = exponent.parse().unwrap_or(0);
+
+    // k significant digits, decimal point after position n
+    let k = i32::try_from(digits.len()).unwrap_or(i32::MAX);
+    let n = exponent + 1;
+
+    let mut out = String::new();
+    if value < 0.0 {
+        out.push('-');
+    }
+
+    let pad = |count: i32| "0".repeat(usize::try_from(count).unwrap_or(0));
+    if k <= n && n <= 21 {
+        out.push_str(&digits);
+        out.push_str(&pad(n - k));
+    } else if 0 < n && n <= 21 {
+        let split = usize::try_from(n).unwrap_or(0);
+        out.push_str(&digits[..split]);
+        out.push('.');
+        out.push_str(&digits[split..]);
+    } else if -6 < n && n <= 0 {
+        out.push_str("0.");
+        out.push_str(&pad(-n));
+        out.push_str(&digits);
+    } else {
+        out.push_str(&digits[..1]);
+        if k > 1 {
+            out.push('.');
+            out.push_str(&digits[1..]);
+        }
+        out.push('e');
+        out.push(if n > 0 { '+' } else { '-' });
+        out.push_str(&(n - 1).abs().to_string());
+    }
+    Ok(out)
+}
+
 fn canonicalize_internal(value: &Value) -> String {
     match value {
         Value::Null => "null".to_string(),
         Value::Bool(b) => b.to_string(),
         Value::Number(n) => n.to_string(),
-        Value::String(s) => serde_json::to_string(s).unwrap(),
+        Value::String(s) => escape_json_string(s),
         Value::Array(arr) => {
             let elements: Vec<String> = arr.iter().map(canonicalize_internal).collect();
             format!("[{}]", elements.join(","))
@@ -40,7 +274,7 @@ fn canonicalize_internal(value: &Value) -> String {
 
             let pairs: Vec<String> = sorted
                 .iter()
-                .map(|(k, v)| format!("{}:{}", serde_json::to_string(k).unwrap(), v))
+                .map(|(k, v)| format!("{}:{}", escape_json_string(k), v))
                 .collect();
 
             format!("{{{}}}", pairs.join(","))
@@ -106,4 +340,87 @@ mod tests {
         assert_eq!(canonicalize(&json!([])), "[]");
         assert_eq!(canonicalize(&json!({})), "{}");
     }
+
+    #[test]
+    fn test_es_number_formatting() {
+        // IEEE 754 bit patterns and expected output from RFC 8785 Appendix B
+        let cases: &[(u64, &str)] = &[
+            (0x0000000000000000, "0"),
+            (0x8000000000000000, "0"),
+            (0x0000000000000001, "5e-324"),
+            (0x8000000000000001, "-5e-324"),
+            (0x7fefffffffffffff, "1.7976931348623157e+308"),
+            (0xffefffffffffffff, "-1.7976931348623157e+308"),
+            (0x4340000000000000, "9007199254740992"),
+            (0xc340000000000000, "-9007199254740992"),
+            (0x4430000000000000, "295147905179352830000"),
+            (0x44b52d02c7e14af5, "9.999999999999997e+22"),
+            (0x44b52d02c7e14af6, "1e+23"),
+            (0x44b52d02c7e14af7, "1.0000000000000001e+23"),
+            (0x444b1ae4d6e2ef4e, "999999999999999700000"),
+            (0x444b1ae4d6e2ef4f, "999999999999999900000"),
+            (0x444b1ae4d6e2ef50, "1e+21"),
+            (0x3eb0c6f7a0b5ed8c, "9.999999999999997e-7"),
+            (0x3eb0c6f7a0b5ed8d, "0.000001"),
+            (0x41b3de4355555553, "333333333.3333332"),
+            (0x41b3de4355555554, "333333333.33333325"),
+            (0x41b3de4355555555, "333333333.3333333"),
+            (0x41b3de4355555556, "333333333.3333334"),
+            (0x41b3de4355555557, "333333333.33333343"),
+            (0xbecbf647612f3696, "-0.0000033333333333333333"),
+            (0x43143ff3c1cb0959, "1424953923781206.2"),
+        ];
+        for (bits, expected) in cases {
+            let actual = format_es_number(f64::from_bits(*bits)).unwrap();
+            assert_eq!(&actual, expected, "bits {:#018x}", bits);
+        }
+        assert!(format_es_number(f64::NAN).is_err());
+        assert!(format_es_number(f64::INFINITY).is_err());
+    }
+
+    #[test]
+    fn test_jcs_integer_edge_cases() {
+        assert_eq!(canonicalize_jcs(&json!(9007199254740991u64)).unwrap(), "9007199254740991");
+        assert_eq!(canonicalize_jcs(&json!(-9007199254740991i64)).unwrap(), "-9007199254740991");
+        assert_eq!(canonicalize_jcs(&json!(-42i64)).unwrap(), "-42");
+        assert_eq!(canonicalize_jcs(&json!(1e21)).unwrap(), "1e+21");
+        // Representable as a double, but ECMAScript would print 1152921504606847000
+        assert!(matches!(canonicalize_jcs(&json!(1u64 << 60)), Err(JcsError::UnsafeInteger(_))));
+        assert!(matches!(canonicalize_jcs(&json!(u64::MAX)), Err(JcsError::UnsafeInteger(_))));
+        assert!(matches!(canonicalize_jcs(&json!(9007199254740992u64)), Err(JcsError::UnsafeInteger(_))));
+        assert!(matches!(canonicalize_jcs(&json!(i64::MIN)), Err(JcsError::UnsafeInteger(_))));
+    }
+
+    #[test]
+    fn test_jcs_sorts_keys_by_utf16() {
+        // U+1F600 is a surrogate pair (0xD83D...) and sorts before U+FB33 in
+        // UTF-16, but after it in UTF-8 byte order
+        let value = json!({"\u{fb33}": 1, "\u{1f600}": 2, "a": 3});
+        assert_eq!(canonicalize_jcs(&value).unwrap(), "{\"a\":3,\"\u{1f600}\":2,\"\u{fb33}\":1}");
+        assert_eq!(canonicalize(&value), "{\"a\":3,\"\u{fb33}\":1,\"\u{1f600}\":2}");
+    }
+
+    #[test]
+    fn test_escape_matches_serde_json_for_every_bmp_scalar() {
+        for cp in (0u32..0xd800).chain(0xe000..0x1_0000) {
+            let s = char::from_u32(cp).unwrap().to_string();
+            assert_eq!(escape_json_string(&s), serde_json::to_string(&s).unwrap(), "U+{cp:04X}");
+        }
+    }
+
+    #[test]
+    fn test_escape_json_utf16_lone_surrogates() {
+        let units: Vec<u16> = "a\u{1f600}".encode_utf16().collect();
+        assert_eq!(escape_json_utf16(&units), "\"a\u{1f600}\"");
+        assert_eq!(escape_json_utf16(&[0xd83d, 0x41]), r#""\ud83dA""#);
+        assert_eq!(escape_json_utf16(&[0xde00]), r#""\ude00""#);
+    }
+
+    #[test]
+    fn test_mode_default_is_legacy() {
+        let value = json!({"b": 1.5, "a": [true, null]});
+        assert_eq!(CanonicalMode::default(), CanonicalMode::Legacy);
+        assert_eq!(canonicalize_with_mode(&value, CanonicalMode::Legacy).unwrap(), canonicalize(&value));
+        assert_eq!(canonicalize_with_mode(&value, CanonicalMode::Jcs).unwrap(), r#"{"a":[true,null],"b":1.5}"#);
+    }
 }
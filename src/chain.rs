@@ -0,0 +1,582 @@
+//! Block explorer-style traversal of a partition's blocks
+//!
+//! [`AccumulateClient::minor_block`] and [`AccumulateClient::major_block`]
+//! read one block of a partition, and
+//! [`AccumulateClient::walk_minor_blocks`] and
+//! [`AccumulateClient::walk_major_blocks`] page through them in order. Blocks
+//! come back summarized rather than as raw records: a [`BlockSummary`] lists
+//! the IDs of the transactions recorded on main chains in the minor block and
+//! the anchors it received from other partitions, and a
+//! [`MajorBlockSummary`] lists the minor blocks a major block spans.
+//!
+//! ```no_run
+//! # async fn run(client: &accumulate_client::AccumulateClient) -> Result<(), accumulate_client::json_rpc_client::JsonRpcError> {
+//! let mut blocks = client.walk_minor_blocks("Apollo").start_at(1_000);
+//! while let Some(block) = blocks.next().await? {
+//!     for txid in &block.transactions {
+//!         println!("block {}: {txid}", block.index);
+//!     }
+//!     for anchor in &block.anchors {
+//!         println!("block {}: anchor of {} block {}", block.index, anchor.source, anchor.minor_block_index);
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! `partition` is `dn` or a BVN ID such as `Apollo`, as for
+//! [`partition_url`]. A range of blocks may carry only the first entries of
+//! each block; [`AccumulateClient::minor_block`] reads every entry of one.
+//!
+//! An indexer that keeps a [`BlockCheckpoint`] of the last block it
+//! processed calls [`AccumulateClient::scan_minor_blocks`] to read on from
+//! there. The [`BlockScan`] flags what does not line up with the checkpoint
+//! or between consecutive blocks as [`BlockAnomaly`] values: indexes the node
+//! skipped, a checkpoint block whose time changed, or blocks that go back in
+//! index or time. [`BlockScan::missing`] lists the ranges to backfill.
+
+use crate::anchors::partition_url;
+use crate::client::AccumulateClient;
+use crate::json_rpc_client::JsonRpcError;
+use crate::paging::{RangePager, DEFAULT_PAGE_SIZE};
+use crate::query::{BlockResult, MajorBlockRecord, MinorBlockRecord, RecordRange};
+use crate::types::{BlockQuery, RangeOptions};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// An anchor of another partition's block, received in a minor block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockAnchor {
+    /// Partition that sent the anchor
+    pub source: String,
+    /// Minor block of the source the anchor is for
+    pub minor_block_index: u64,
+    /// Root of the source's root chain (hex)
+    pub root_chain_anchor: Option<String>,
+    /// Root of the source's state tree (hex)
+    pub state_tree_anchor: Option<String>,
+}
+
+impl BlockAnchor {
+    /// Decode an anchor record, or the anchor it wraps
+    #[must_use]
+    pub fn from_json(record: &Value) -> Option<Self> {
+        let anchor = record.get("anchor").unwrap_or(record);
+        let text = |key: &str| anchor.get(key).and_then(Value::as_str).map(str::to_string);
+        Some(Self {
+            source: text("source")?,
+            minor_block_index: anchor.get("minorBlockIndex").and_then(Value::as_u64).unwrap_or(0),
+            root_chain_anchor: text("rootChainAnchor"),
+            state_tree_anchor: text("stateTreeAnchor"),
+        })
+    }
+}
+
+/// A minor block: when it was produced, what it recorded and what it
+/// anchored
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockSummary {
+    /// Block index
+    pub index: u64,
+    /// Block time
+    pub time: Option<DateTime<Utc>>,
+    /// Partition that produced the block, when the node reported it
+    pub source: Option<String>,
+    /// IDs of the transactions recorded on main chains, in entry order
+    pub transactions: Vec<String>,
+    /// Anchors of other partitions' blocks received in this one
+    pub anchors: Vec<BlockAnchor>,
+    /// Chain entries recorded in the block, on every chain
+    pub entries: u64,
+}
+
+impl BlockSummary {
+    /// Summarize a minor block record
+    #[must_use]
+    pub fn from_record(record: &MinorBlockRecord) -> Self {
+        let mut transactions = Vec::new();
+        let entries = record.entries.as_ref();
+        for entry in entries.map(|range| range.records.as_slice()).unwrap_or_default() {
+            if entry.name != "main" {
+                continue;
+            }
+            let txid = entry
+                .value
+                .as_ref()
+                .and_then(|value| value.get("id"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .or_else(|| {
+                    let account = entry.account.as_deref()?.trim_start_matches("acc://");
+                    Some(format!("acc://{}@{account}", entry.entry))
+                });
+            if let Some(txid) = txid.filter(|txid| !transactions.contains(txid)) {
+                transactions.push(txid);
+            }
+        }
+        let anchored = record.anchored.as_ref();
+        let anchors = anchored
+            .and_then(|anchored| anchored.get("records").unwrap_or(anchored).as_array())
+            .into_iter()
+            .flatten()
+            .filter_map(BlockAnchor::from_json)
+            .collect();
+        Self {
+            index: record.index,
+            time: parse_time(record.time.as_deref()),
+            source: record.source.clone(),
+            transactions,
+            anchors,
+            entries: entries.map_or(0, |range| range.total.max(range.records.len() as u64)),
+        }
+    }
+}
+
+/// A major block and the minor blocks it spans
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MajorBlockSummary {
+    /// Block index
+    pub index: u64,
+    /// Block time
+    pub time: Option<DateTime<Utc>>,
+    /// Indices of the minor blocks it spans, as far as the node listed them
+    pub minor_blocks: Vec<u64>,
+}
+
+impl MajorBlockSummary {
+    /// Summarize a major block record
+    #[must_use]
+    pub fn from_record(record: &MajorBlockRecord) -> Self {
+        Self {
+            index: record.index,
+            time: parse_time(record.time.as_deref()),
+            minor_blocks: record
+                .minor_blocks
+                .as_ref()
+                .map(|range| range.records.iter().map(|block| block.index).collect())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+/// The last minor block an indexer processed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockCheckpoint {
+    /// Block index
+    pub index: u64,
+    /// Block time, compared on the next scan to spot a rewritten block
+    pub time: Option<DateTime<Utc>>,
+}
+
+impl BlockCheckpoint {
+    /// Checkpoint at `block`
+    #[must_use]
+    pub const fn from_block(block: &BlockSummary) -> Self {
+        Self {
+            index: block.index,
+            time: block.time,
+        }
+    }
+}
+
+/// Something in a run of minor blocks that does not line up
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+pub enum BlockAnomaly {
+    /// Blocks `from..=to` were skipped
+    Gap {
+        /// First missing index
+        from: u64,
+        /// Last missing index
+        to: u64,
+    },
+    /// The checkpoint block is missing or its time changed
+    CheckpointMismatch {
+        /// Checkpoint index
+        index: u64,
+        /// Time recorded in the checkpoint
+        expected: Option<DateTime<Utc>>,
+        /// Time the node reports now; `None` when the block was not returned
+        found: Option<DateTime<Utc>>,
+    },
+    /// A block came back at or below an index already seen
+    OutOfOrder {
+        /// Index of the block
+        index: u64,
+        /// Index of the block before it
+        previous: u64,
+    },
+    /// A block is older than the block before it
+    TimeReversal {
+        /// Index of the block
+        index: u64,
+        /// Time of the block before it
+        previous: DateTime<Utc>,
+        /// Time of the block
+        time: DateTime<Utc>,
+    },
+}
+
+/// Minor blocks read on from a checkpoint, with what did not line up
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockScan {
+    /// Blocks after the checkpoint, in the order returned
+    pub blocks: Vec<BlockSummary>,
+    /// Anomalies, in the order found
+    pub anomalies: Vec<BlockAnomaly>,
+}
+
+impl BlockScan {
+    /// Whether the blocks follow the checkpoint without anomalies
+    #[must_use]
+    pub fn is_clean(&self) -> bool {
+        self.anomalies.is_empty()
+    }
+
+    /// Index ranges (inclusive) skipped by the node, to backfill
+    #[must_use]
+    pub fn missing(&self) -> Vec<(u64, u64)> {
+        self.anomalies
+            .iter()
+            .filter_map(|anomaly| match anomaly {
+                BlockAnomaly::Gap { from, to } => Some((*from, *to)),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Checkpoint at the last block scanned, if any
+    #[must_use]
+    pub fn checkpoint(&self) -> Option<BlockCheckpoint> {
+        self.blocks.last().map(BlockCheckpoint::from_block)
+    }
+}
+
+/// Compare `blocks`, read from `checkpoint.index` on, with the checkpoint
+/// and with each other
+///
+/// The checkpoint block itself is expected first and is not part of the
+/// returned scan.
+#[must_use]
+pub fn detect_block_anomalies(checkpoint: &BlockCheckpoint, blocks: Vec<BlockSummary>) -> BlockScan {
+    let mut anomalies = Vec::new();
+    let mut blocks = blocks.into_iter().peekable();
+    let at_checkpoint = blocks.next_if(|block| block.index == checkpoint.index);
+    let found = at_checkpoint.as_ref().and_then(|block| block.time);
+    if at_checkpoint.is_none() || (checkpoint.time.is_some() && found != checkpoint.time) {
+        anomalies.push(BlockAnomaly::CheckpointMismatch {
+            index: checkpoint.index,
+            expected: checkpoint.time,
+            found,
+        });
+    }
+
+    let (mut previous, mut previous_time) = (checkpoint.index, found.or(checkpoint.time));
+    let mut scanned = Vec::new();
+    for block in blocks {
+        if block.index <= previous {
+            anomalies.push(BlockAnomaly::OutOfOrder {
+                index: block.index,
+                previous,
+            });
+            continue;
+        }
+        if block.index > previous + 1 {
+            anomalies.push(BlockAnomaly::Gap {
+                from: previous + 1,
+                to: block.index - 1,
+            });
+        }
+        if let (Some(previous), Some(time)) = (previous_time, block.time) {
+            if time < previous {
+                anomalies.push(BlockAnomaly::TimeReversal {
+                    index: block.index,
+                    previous,
+                    time,
+                });
+            }
+        }
+        previous = block.index;
+        previous_time = block.time.or(previous_time);
+        scanned.push(block);
+    }
+    BlockScan {
+        blocks: scanned,
+        anomalies,
+    }
+}
+
+fn parse_time(time: Option<&str>) -> Option<DateTime<Utc>> {
+    DateTime::parse_from_rfc3339(time?)
+        .ok()
+        .map(|time| time.with_timezone(&Utc))
+}
+
+fn unexpected(expected: &str, found: &BlockResult) -> JsonRpcError {
+    JsonRpcError::General(format!("Expected {expected}, got {found:?}"))
+}
+
+fn summarize<R, S>(range: &RecordRange<R>, summary: fn(&R) -> S) -> RecordRange<S> {
+    RecordRange {
+        records: range.records.iter().map(summary).collect(),
+        start: range.start,
+        total: range.total,
+    }
+}
+
+impl AccumulateClient {
+    /// Minor block `index` of `partition`, with every entry it recorded
+    pub async fn minor_block(&self, partition: &str, index: u64) -> Result<BlockSummary, JsonRpcError> {
+        let query = BlockQuery {
+            minor: Some(index),
+            entry_range: Some(RangeOptions {
+                start: Some(0),
+                expand: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        match self.v3().query_block(&partition_url(partition), query).await? {
+            BlockResult::Minor(record) => Ok(BlockSummary::from_record(&record)),
+            other => Err(unexpected("a minor block", &other)),
+        }
+    }
+
+    /// Major block `index` of `partition`
+    pub async fn major_block(&self, partition: &str, index: u64) -> Result<MajorBlockSummary, JsonRpcError> {
+        let query = BlockQuery {
+            major: Some(index),
+            ..Default::default()
+        };
+        match self.v3().query_block(&partition_url(partition), query).await? {
+            BlockResult::Major(record) => Ok(MajorBlockSummary::from_record(&record)),
+            other => Err(unexpected("a major block", &other)),
+        }
+    }
+
+    /// Pager over the minor blocks of `partition`, oldest first
+    #[must_use]
+    pub fn walk_minor_blocks(&self, partition: &str) -> RangePager<'_, BlockSummary> {
+        let url = partition_url(partition);
+        RangePager::new(move |start, count| {
+            let url = url.clone();
+            async move {
+                let query = BlockQuery {
+                    minor_range: Some(block_range(start, count)),
+                    ..Default::default()
+                };
+                match self.v3().query_block(&url, query).await? {
+                    BlockResult::MinorBlocks(range) => Ok(summarize(&range, BlockSummary::from_record)),
+                    other => Err(unexpected("a range of minor blocks", &other)),
+                }
+            }
+        })
+    }
+
+    /// Pager over the major blocks of `partition`, oldest first
+    #[must_use]
+    pub fn walk_major_blocks(&self, partition: &str) -> RangePager<'_, MajorBlockSummary> {
+        let url = partition_url(partition);
+        RangePager::new(move |start, count| {
+            let url = url.clone();
+            async move {
+                let query = BlockQuery {
+                    major_range: Some(block_range(start, count)),
+                    ..Default::default()
+                };
+                match self.v3().query_block(&url, query).await? {
+                    BlockResult::MajorBlocks(range) => Ok(summarize(&range, MajorBlockSummary::from_record)),
+                    other => Err(unexpected("a range of major blocks", &other)),
+                }
+            }
+        })
+    }
+
+    /// Up to `max_blocks` minor blocks of `partition` after `checkpoint`,
+    /// checked against it with [`detect_block_anomalies`]
+    ///
+    /// Pages continue from the last index returned rather than from a count,
+    /// so a page with holes does not return blocks twice; the scan ends at
+    /// an empty page.
+    pub async fn scan_minor_blocks(
+        &self,
+        partition: &str,
+        checkpoint: &BlockCheckpoint,
+        max_blocks: u64,
+    ) -> Result<BlockScan, JsonRpcError> {
+        let url = partition_url(partition);
+        let wanted = max_blocks.saturating_add(1);
+        let mut blocks = Vec::new();
+        let mut start = checkpoint.index;
+        while (blocks.len() as u64) < wanted {
+            let count = (wanted - blocks.len() as u64).min(DEFAULT_PAGE_SIZE);
+            let query = BlockQuery {
+                minor_range: Some(block_range(start, count)),
+                omit_empty: Some(false),
+                ..Default::default()
+            };
+            let range = match self.v3().query_block(&url, query).await? {
+                BlockResult::MinorBlocks(range) => range,
+                other => return Err(unexpected("a range of minor blocks", &other)),
+            };
+            let Some(last) = range.records.last() else {
+                break;
+            };
+            start = last.index.max(start) + 1;
+            blocks.extend(range.records.iter().map(BlockSummary::from_record));
+        }
+        Ok(detect_block_anomalies(checkpoint, blocks))
+    }
+}
+
+const fn block_range(start: u64, count: u64) -> RangeOptions {
+    RangeOptions {
+        start: Some(start),
+        count: Some(count),
+        expand: Some(true),
+        from_end: None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use serde_json::json;
+
+    fn minor(index: u64) -> Value {
+        json!({
+            "recordType": "minorBlock",
+            "index": index,
+            "time": "2026-01-02T03:04:05Z",
+            "source": "acc://bvn-Apollo.acme",
+            "entries": {"recordType": "range", "total": 4, "records": [
+                {"account": "acc://alice.acme/tokens", "name": "main", "type": "transaction", "index": 3, "entry": "aa"},
+                {"account": "acc://alice.acme/tokens", "name": "signature", "type": "transaction", "index": 5, "entry": "bb"},
+                {"account": "acc://bob.acme/tokens", "name": "main", "type": "transaction", "index": 0, "entry": "cc",
+                 "value": {"id": "acc://cc@bob.acme/tokens"}},
+                {"account": "acc://bob.acme/tokens", "name": "main", "type": "transaction", "index": 0, "entry": "cc"}
+            ]},
+            "anchored": {"recordType": "range", "records": [
+                {"recordType": "anchor", "anchor": {
+                    "source": "acc://dn.acme", "minorBlockIndex": 77, "rootChainAnchor": "dd", "stateTreeAnchor": "ee"
+                }}
+            ]}
+        })
+    }
+
+    #[test]
+    fn test_summarize_blocks() {
+        let record: MinorBlockRecord = serde_json::from_value(minor(12)).unwrap();
+        let block = BlockSummary::from_record(&record);
+        assert_eq!(block.index, 12);
+        assert_eq!(block.time.unwrap().to_rfc3339(), "2026-01-02T03:04:05+00:00");
+        assert_eq!(block.transactions, ["acc://aa@alice.acme/tokens", "acc://cc@bob.acme/tokens"]);
+        assert_eq!(block.entries, 4);
+        assert_eq!(
+            block.anchors,
+            [BlockAnchor {
+                source: "acc://dn.acme".to_string(),
+                minor_block_index: 77,
+                root_chain_anchor: Some("dd".to_string()),
+                state_tree_anchor: Some("ee".to_string()),
+            }]
+        );
+
+        let record: MajorBlockRecord = serde_json::from_value(json!({
+            "recordType": "majorBlock",
+            "index": 2,
+            "minorBlocks": {"recordType": "range", "records": [{"index": 40}, {"index": 41}]}
+        }))
+        .unwrap();
+        let major = MajorBlockSummary::from_record(&record);
+        assert_eq!((major.index, major.time, major.minor_blocks), (2, None, vec![40, 41]));
+    }
+
+    fn block(index: u64, second: u32) -> BlockSummary {
+        BlockSummary {
+            index,
+            time: Some(DateTime::from_timestamp(1_700_000_000 + i64::from(second), 0).unwrap()),
+            source: None,
+            transactions: Vec::new(),
+            anchors: Vec::new(),
+            entries: 0,
+        }
+    }
+
+    #[test]
+    fn test_detect_block_anomalies() {
+        let checkpoint = BlockCheckpoint::from_block(&block(10, 0));
+        let scan = detect_block_anomalies(&checkpoint, vec![block(10, 0), block(11, 1), block(12, 2)]);
+        assert!(scan.is_clean());
+        assert_eq!(scan.checkpoint(), Some(BlockCheckpoint::from_block(&block(12, 2))));
+
+        let scan = detect_block_anomalies(
+            &checkpoint,
+            vec![block(10, 5), block(11, 6), block(14, 4), block(13, 7), block(15, 8)],
+        );
+        assert_eq!(
+            scan.anomalies,
+            [
+                BlockAnomaly::CheckpointMismatch {
+                    index: 10,
+                    expected: block(10, 0).time,
+                    found: block(10, 5).time,
+                },
+                BlockAnomaly::Gap { from: 12, to: 13 },
+                BlockAnomaly::TimeReversal {
+                    index: 14,
+                    previous: block(11, 6).time.unwrap(),
+                    time: block(14, 4).time.unwrap(),
+                },
+                BlockAnomaly::OutOfOrder { index: 13, previous: 14 },
+            ]
+        );
+        assert_eq!(scan.missing(), [(12, 13)]);
+        assert_eq!(scan.blocks.iter().map(|b| b.index).collect::<Vec<_>>(), [11, 14, 15]);
+
+        // The checkpoint block is gone
+        let scan = detect_block_anomalies(&checkpoint, vec![block(11, 1)]);
+        assert!(matches!(scan.anomalies[..], [BlockAnomaly::CheckpointMismatch { found: None, .. }]));
+    }
+
+    #[tokio::test]
+    async fn test_walk_minor_blocks() {
+        let mock = MockTransport::new();
+        let client = mock.client().await.unwrap();
+        mock.once("query", json!({"recordType": "range", "start": 1, "total": 4, "records": [minor(1), minor(2)]}));
+        mock.once("query", json!({"recordType": "range", "start": 3, "total": 4, "records": [minor(3)]}));
+        mock.once("query", minor(3));
+
+        let blocks = client.walk_minor_blocks("Apollo").start_at(1).page_size(2).collect().await.unwrap();
+        assert_eq!(blocks.iter().map(|block| block.index).collect::<Vec<_>>(), [1, 2, 3]);
+        let block = client.minor_block("Apollo", 3).await.unwrap();
+        assert_eq!(block, blocks[2]);
+
+        let calls = mock.calls_to("query");
+        assert_eq!(calls[0].params["scope"], "acc://bvn-Apollo.acme");
+        assert_eq!(calls[1].params["query"]["minorRange"]["start"], 3);
+        assert_eq!(calls[2].params["query"]["entryRange"]["expand"], true);
+
+        // A scan continues after the last index returned, past a hole
+        mock.once("query", json!({"recordType": "range", "records": [minor(3), minor(5)]}));
+        mock.once("query", json!({"recordType": "range", "records": [minor(6)]}));
+        mock.once("query", json!({"recordType": "range", "records": []}));
+        let checkpoint = BlockCheckpoint::from_block(&block);
+        let scan = client.scan_minor_blocks("Apollo", &checkpoint, 3).await.unwrap();
+        assert_eq!(scan.missing(), [(4, 4)]);
+        assert_eq!(scan.checkpoint().unwrap().index, 6);
+        let calls = mock.calls_to("query");
+        assert_eq!(calls[3].params["query"]["minorRange"], json!({"start": 3, "count": 4, "expand": true}));
+        assert_eq!(calls[4].params["query"]["minorRange"]["start"], 6);
+        assert_eq!(calls[4].params["query"]["minorRange"]["count"], 2);
+        assert_eq!(calls[5].params["query"]["minorRange"]["start"], 7);
+    }
+}
@@ -0,0 +1,241 @@
+//! Indexing progress that survives restarts
+//!
+//! A [`CheckpointStore`] keeps, per partition or account, the index of the
+//! last block or entry an indexer processed, in any [`KvStore`]. The block
+//! walker, chain pager and transaction history pager resume right after it,
+//! so an indexer that restarts picks up where it left off instead of
+//! reading everything again:
+//!
+//! ```no_run
+//! # async fn run(client: &accumulate_client::AccumulateClient) -> Result<(), Box<dyn std::error::Error>> {
+//! use accumulate_client::checkpoint::CheckpointStore;
+//! use accumulate_client::storage::FileKvStore;
+//!
+//! let mut checkpoints = CheckpointStore::new(FileKvStore::open("indexer-state")?);
+//! let mut blocks = checkpoints.walk_minor_blocks(client, "Apollo")?;
+//! while let Some(block) = blocks.next().await? {
+//!     // ... index the block ...
+//!     checkpoints.save_block("Apollo", &block)?;
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Save a checkpoint only once the block or entry has been processed: the
+//! next run starts after it.
+
+use crate::anchors::partition_url;
+use crate::chain::{BlockCheckpoint, BlockSummary};
+use crate::client::AccumulateClient;
+use crate::paging::RangePager;
+use crate::query::ChainEntryRecord;
+use crate::storage::{get_json, put_json, scan_json, KvStore, StorageError};
+use crate::tx_history::TxHistory;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Index of the first minor block of a partition
+pub const FIRST_MINOR_BLOCK: u64 = 1;
+
+/// The last block or entry processed for one scope
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Checkpoint {
+    /// What is being indexed, e.g. [`minor_blocks_scope`]
+    pub scope: String,
+    /// Index of the last block or entry processed
+    pub index: u64,
+    /// Time of that block, when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<DateTime<Utc>>,
+    /// When the checkpoint was saved
+    pub updated_at: DateTime<Utc>,
+}
+
+impl Checkpoint {
+    /// Index to resume at: the one after the checkpoint
+    #[must_use]
+    pub const fn next_index(&self) -> u64 {
+        self.index + 1
+    }
+}
+
+/// Scope of a partition's minor blocks, `partition` as for [`partition_url`]
+#[must_use]
+pub fn minor_blocks_scope(partition: &str) -> String {
+    format!("minor-blocks/{}", partition_url(partition))
+}
+
+/// Scope of the entries of chain `chain` of `account`
+#[must_use]
+pub fn chain_scope(account: &str, chain: &str) -> String {
+    format!("chain/{chain}/{account}")
+}
+
+/// Scope of the transaction history of `account`
+#[must_use]
+pub fn history_scope(account: &str) -> String {
+    format!("history/{account}")
+}
+
+/// Checkpoints kept in a [`KvStore`] under `checkpoint/`
+#[derive(Debug, Clone, Default)]
+pub struct CheckpointStore<S: KvStore> {
+    kv: S,
+}
+
+impl<S: KvStore> CheckpointStore<S> {
+    const PREFIX: &'static str = "checkpoint/";
+
+    /// Keep checkpoints in `kv`
+    pub const fn new(kv: S) -> Self {
+        Self { kv }
+    }
+
+    /// The underlying key-value store
+    pub const fn kv(&self) -> &S {
+        &self.kv
+    }
+
+    /// The checkpoint of `scope`, if one was saved
+    pub fn load(&self, scope: &str) -> Result<Option<Checkpoint>, StorageError> {
+        get_json(&self.kv, &Self::key(scope))
+    }
+
+    /// Record `index` as the last one processed for `scope`
+    ///
+    /// The checkpoint is replaced even when `index` is lower, e.g. to
+    /// re-index after a backfill.
+    pub fn save(
+        &mut self,
+        scope: &str,
+        index: u64,
+        time: Option<DateTime<Utc>>,
+    ) -> Result<Checkpoint, StorageError> {
+        let checkpoint = Checkpoint {
+            scope: scope.to_string(),
+            index,
+            time,
+            updated_at: Utc::now(),
+        };
+        put_json(&mut self.kv, &Self::key(scope), &checkpoint)?;
+        Ok(checkpoint)
+    }
+
+    /// Forget the checkpoint of `scope`, so the next run starts over
+    pub fn reset(&mut self, scope: &str) -> Result<(), StorageError> {
+        self.kv.delete(&Self::key(scope))
+    }
+
+    /// Every checkpoint, ordered by scope
+    pub fn list(&self) -> Result<Vec<Checkpoint>, StorageError> {
+        scan_json(&self.kv, Self::PREFIX)
+    }
+
+    /// Index to resume `scope` at: after its checkpoint, else `first`
+    pub fn resume_at(&self, scope: &str, first: u64) -> Result<u64, StorageError> {
+        Ok(self.load(scope)?.map_or(first, |checkpoint| checkpoint.next_index()))
+    }
+
+    /// Checkpoint of `partition`'s minor blocks, for
+    /// [`AccumulateClient::scan_minor_blocks`]
+    pub fn block_checkpoint(&self, partition: &str) -> Result<Option<BlockCheckpoint>, StorageError> {
+        Ok(self
+            .load(&minor_blocks_scope(partition))?
+            .map(|checkpoint| BlockCheckpoint {
+                index: checkpoint.index,
+                time: checkpoint.time,
+            }))
+    }
+
+    /// Record `block` as the last minor block of `partition` processed
+    pub fn save_block(&mut self, partition: &str, block: &BlockSummary) -> Result<Checkpoint, StorageError> {
+        self.save(&minor_blocks_scope(partition), block.index, block.time)
+    }
+
+    /// [`AccumulateClient::walk_minor_blocks`] from after the checkpoint of
+    /// `partition`
+    pub fn walk_minor_blocks<'a>(
+        &self,
+        client: &'a AccumulateClient,
+        partition: &str,
+    ) -> Result<RangePager<'a, BlockSummary>, StorageError> {
+        let start = self.resume_at(&minor_blocks_scope(partition), FIRST_MINOR_BLOCK)?;
+        Ok(client.walk_minor_blocks(partition).start_at(start))
+    }
+
+    /// [`AccumulateClient::chain_pager`] from after the checkpoint of chain
+    /// `chain` of `account`
+    pub fn chain_pager<'a>(
+        &self,
+        client: &'a AccumulateClient,
+        account: &str,
+        chain: &str,
+    ) -> Result<RangePager<'a, ChainEntryRecord>, StorageError> {
+        let start = self.resume_at(&chain_scope(account, chain), 0)?;
+        Ok(client.chain_pager(account, chain).start_at(start))
+    }
+
+    /// [`AccumulateClient::tx_history`] from after the checkpoint of
+    /// `account`'s history
+    pub fn tx_history<'a>(
+        &self,
+        client: &'a AccumulateClient,
+        account: &str,
+    ) -> Result<TxHistory<'a>, StorageError> {
+        let start = self.resume_at(&history_scope(account), 0)?;
+        Ok(client.tx_history(account).start_at(start))
+    }
+
+    fn key(scope: &str) -> String {
+        format!("{}{scope}", Self::PREFIX)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::mock::MockTransport;
+    use crate::storage::MemoryKvStore;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_resume_from_checkpoints() {
+        let mut checkpoints = CheckpointStore::new(MemoryKvStore::new());
+        let blocks = minor_blocks_scope("Apollo");
+        assert_eq!(blocks, "minor-blocks/acc://bvn-Apollo.acme");
+        assert_eq!(checkpoints.resume_at(&blocks, FIRST_MINOR_BLOCK).unwrap(), 1);
+        assert_eq!(checkpoints.block_checkpoint("Apollo").unwrap(), None);
+
+        let time = DateTime::from_timestamp(1_700_000_000, 0);
+        let saved = checkpoints.save(&blocks, 41, time).unwrap();
+        assert_eq!(checkpoints.load(&blocks).unwrap(), Some(saved));
+        assert_eq!(
+            checkpoints.block_checkpoint("Apollo").unwrap(),
+            Some(BlockCheckpoint { index: 41, time })
+        );
+        checkpoints.save(&history_scope("acc://alice.acme/tokens"), 9, None).unwrap();
+        let scopes: Vec<_> = checkpoints.list().unwrap().into_iter().map(|c| c.scope).collect();
+        assert_eq!(scopes, ["history/acc://alice.acme/tokens", blocks.as_str()]);
+
+        let mock = MockTransport::new();
+        let client = mock.client().await.unwrap();
+        mock.on("query", json!({"recordType": "range", "records": []}));
+        mock.on("query-tx-history", json!({"items": []}));
+        let mut walker = checkpoints.walk_minor_blocks(&client, "Apollo").unwrap();
+        assert!(walker.next().await.unwrap().is_none());
+        let mut history = checkpoints.tx_history(&client, "acc://alice.acme/tokens").unwrap();
+        assert!(history.next().await.unwrap().is_none());
+        let mut chain = checkpoints.chain_pager(&client, "acc://alice.acme/tokens", "main").unwrap();
+        assert!(chain.next().await.unwrap().is_none());
+
+        let calls = mock.calls();
+        assert_eq!(calls[0].params["query"]["minorRange"]["start"], 42);
+        assert_eq!(calls[1].params["start"], 10);
+        assert_eq!(calls[2].params["query"]["range"]["start"], 0);
+
+        checkpoints.reset(&blocks).unwrap();
+        assert_eq!(checkpoints.resume_at(&blocks, FIRST_MINOR_BLOCK).unwrap(), 1);
+    }
+}
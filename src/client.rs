@@ -1,13 +1,13 @@
-//! GENERATED BY Accumulate gen-sdk. DO NOT EDIT.
+//! The unified V2/V3 [`AccumulateClient`]
 
 #![allow(missing_docs)]
 #![allow(clippy::unwrap_used, clippy::expect_used, clippy::unused_async)]
 
 use crate::json_rpc_client::{canonical_json, JsonRpcClient, JsonRpcError};
+use crate::protocol::normalized_envelope;
 use crate::types::*;
 use crate::codec::{TransactionCodec, TransactionEnvelope as CodecTransactionEnvelope, TransactionSignature};
 use crate::AccOptions;
-use anyhow::Result;
 use ed25519_dalek::{SigningKey, Signer};
 use reqwest::Client;
 use serde_json::{json, Value};
@@ -30,7 +30,10 @@ impl AccumulateClient {
         v3_url: Url,
         options: AccOptions,
     ) -> Result<Self, JsonRpcError> {
-        let mut client_builder = Client::builder().timeout(options.timeout);
+        let mut client_builder = Client::builder()
+            .connect_timeout(options.connect_timeout)
+            .timeout(options.request_timeout)
+            .pool_max_idle_per_host(options.pool_max_idle_per_host);
 
         // Add custom headers if provided
         if !options.headers.is_empty() {
@@ -38,10 +41,10 @@ impl AccumulateClient {
             for (key, value) in &options.headers {
                 let header_name =
                     reqwest::header::HeaderName::from_bytes(key.as_bytes()).map_err(|e| {
-                        JsonRpcError::General(anyhow::anyhow!("Invalid header name: {}", e))
+                        JsonRpcError::General(format!("Invalid header name: {}", e))
                     })?;
                 let header_value = reqwest::header::HeaderValue::from_str(value).map_err(|e| {
-                    JsonRpcError::General(anyhow::anyhow!("Invalid header value: {}", e))
+                    JsonRpcError::General(format!("Invalid header value: {}", e))
                 })?;
                 headers.insert(header_name, header_value);
             }
@@ -50,8 +53,12 @@ impl AccumulateClient {
 
         let http_client = client_builder.build()?;
 
-        let v2_client = JsonRpcClient::with_client(v2_url, http_client.clone())?;
-        let v3_client = JsonRpcClient::with_client(v3_url, http_client)?;
+        let v2_client = JsonRpcClient::with_client(v2_url, http_client.clone())?
+            .with_retry(options.retry.clone())
+            .with_fallbacks(options.v2_fallbacks.clone(), options.endpoint_cooldown);
+        let v3_client = JsonRpcClient::with_client(v3_url, http_client)?
+            .with_retry(options.retry.clone())
+            .with_fallbacks(options.v3_fallbacks.clone(), options.endpoint_cooldown);
 
         Ok(Self {
             v2_client,
@@ -97,10 +104,8 @@ impl AccumulateClient {
         &self,
         envelope: &TransactionEnvelope,
     ) -> Result<V3SubmitResponse, JsonRpcError> {
-        let request = V3SubmitRequest {
-            envelope: envelope.clone(),
-        };
-        self.v3_client.call_v3("submit", json!(request)).await
+        let request = json!({ "envelope": normalized_envelope(envelope)? });
+        self.v3_client.call_v3("submit", request).await
     }
 
     /// Submit multiple transactions to V3 API
@@ -108,19 +113,49 @@ impl AccumulateClient {
         &self,
         envelopes: &[TransactionEnvelope],
     ) -> Result<Vec<V3SubmitResponse>, JsonRpcError> {
-        let requests: Vec<V3SubmitRequest> = envelopes
+        let requests = envelopes
             .iter()
-            .map(|env| V3SubmitRequest {
-                envelope: env.clone(),
-            })
-            .collect();
+            .map(|env| Ok(json!({ "envelope": normalized_envelope(env)? })))
+            .collect::<Result<Vec<Value>, serde_json::Error>>()?;
         self.v3_client.call_v3("submitMulti", json!(requests)).await
     }
 
     /// Query using V3 API
     pub async fn query(&self, url: &str) -> Result<QueryResponse<Account>, JsonRpcError> {
         let params = json!({ "url": url });
-        self.v3_client.call_v3("query", params).await
+        self.call_query(params).await
+    }
+
+    /// Query a token account and decode its balance, authorities and lock
+    /// state (V3 API)
+    pub async fn query_token_account(&self, url: &str) -> Result<TokenAccountState, JsonRpcError> {
+        let params = json!({
+            "scope": url,
+            "query": {"queryType": "default"}
+        });
+        let result: Value = self.call_query(params).await?;
+        let account = result.get("account").cloned().unwrap_or(Value::Null);
+        match account.get("type").and_then(Value::as_str) {
+            Some("tokenAccount" | "liteTokenAccount") => Ok(serde_json::from_value(account)?),
+            other => Err(JsonRpcError::General(format!(
+                "{} is not a token account (type {})",
+                url,
+                other.unwrap_or("unknown")
+            ))),
+        }
+    }
+
+    /// Send a V3 `query`, verifying returned receipts when
+    /// [`AccOptions::verify_receipts`] is set
+    pub(crate) async fn call_query<T: serde::de::DeserializeOwned>(
+        &self,
+        params: Value,
+    ) -> Result<T, JsonRpcError> {
+        let mut result: Value = self.v3_client.call_v3("query", params).await?;
+        if self.options.verify_receipts {
+            crate::receipts::annotate_receipts(&mut result);
+        }
+        Ok(serde_json::from_value(result)?)
     }
 
     /// Query block by height using V3 API
@@ -197,7 +232,7 @@ impl AccumulateClient {
         opts: crate::types::ValidateOptions,
     ) -> Result<Vec<crate::types::V3Submission>, JsonRpcError> {
         let request = json!({
-            "envelope": envelope,
+            "envelope": normalized_envelope(envelope)?,
             "options": opts
         });
         self.v3_client.call_v3("validate", request).await
@@ -226,7 +261,7 @@ impl AccumulateClient {
         opts: crate::types::SubmitOptions,
     ) -> Result<Vec<crate::types::V3Submission>, JsonRpcError> {
         let request = json!({
-            "envelope": envelope,
+            "envelope": normalized_envelope(envelope)?,
             "options": opts
         });
         self.v3_client.call_v3("submit", request).await
@@ -257,14 +292,14 @@ impl AccumulateClient {
     ) -> Result<QueryResponse<Value>, JsonRpcError> {
         // Validate query before sending
         query.validate().map_err(|e| {
-            JsonRpcError::General(anyhow::anyhow!("Query validation failed: {}", e))
+            JsonRpcError::General(format!("Query validation failed: {}", e))
         })?;
 
         let params = json!({
             "url": url,
             "query": query
         });
-        self.v3_client.call_v3("query", params).await
+        self.call_query(params).await
     }
 
     /// Query chain data for an account (V3 API)
@@ -274,7 +309,7 @@ impl AccumulateClient {
         query: crate::types::ChainQuery,
     ) -> Result<QueryResponse<Value>, JsonRpcError> {
         query.validate().map_err(|e| {
-            JsonRpcError::General(anyhow::anyhow!("Query validation failed: {}", e))
+            JsonRpcError::General(format!("Query validation failed: {}", e))
         })?;
 
         let params = json!({
@@ -288,7 +323,7 @@ impl AccumulateClient {
                 "includeReceipt": query.include_receipt
             }
         });
-        self.v3_client.call_v3("query", params).await
+        self.call_query(params).await
     }
 
     /// Query data entries for a data account (V3 API)
@@ -298,7 +333,7 @@ impl AccumulateClient {
         query: crate::types::DataQuery,
     ) -> Result<QueryResponse<Value>, JsonRpcError> {
         query.validate().map_err(|e| {
-            JsonRpcError::General(anyhow::anyhow!("Query validation failed: {}", e))
+            JsonRpcError::General(format!("Query validation failed: {}", e))
         })?;
 
         let params = json!({
@@ -310,7 +345,7 @@ impl AccumulateClient {
                 "range": query.range
             }
         });
-        self.v3_client.call_v3("query", params).await
+        self.call_query(params).await
     }
 
     /// Query directory (sub-accounts) of an identity (V3 API)
@@ -326,7 +361,7 @@ impl AccumulateClient {
                 "range": query.range
             }
         });
-        self.v3_client.call_v3("query", params).await
+        self.call_query(params).await
     }
 
     /// Query pending transactions for an account (V3 API)
@@ -342,7 +377,7 @@ impl AccumulateClient {
                 "range": query.range
             }
         });
-        self.v3_client.call_v3("query", params).await
+        self.call_query(params).await
     }
 
     /// Query block information (V3 API - advanced)
@@ -352,7 +387,7 @@ impl AccumulateClient {
         query: crate::types::BlockQuery,
     ) -> Result<QueryResponse<Value>, JsonRpcError> {
         query.validate().map_err(|e| {
-            JsonRpcError::General(anyhow::anyhow!("Query validation failed: {}", e))
+            JsonRpcError::General(format!("Query validation failed: {}", e))
         })?;
 
         let params = json!({
@@ -367,7 +402,7 @@ impl AccumulateClient {
                 "omitEmpty": query.omit_empty
             }
         });
-        self.v3_client.call_v3("query", params).await
+        self.call_query(params).await
     }
 
     // ========================================================================
@@ -386,7 +421,7 @@ impl AccumulateClient {
                 "includeReceipt": query.include_receipt
             }
         });
-        self.v3_client.call_v3("query", params).await
+        self.call_query(params).await
     }
 
     /// Search signers by public key (V3 API)
@@ -401,7 +436,7 @@ impl AccumulateClient {
                 "type": query.signature_type
             }
         });
-        self.v3_client.call_v3("query", params).await
+        self.call_query(params).await
     }
 
     /// Search signers by public key hash (V3 API)
@@ -415,7 +450,7 @@ impl AccumulateClient {
                 "publicKeyHash": query.public_key_hash
             }
         });
-        self.v3_client.call_v3("query", params).await
+        self.call_query(params).await
     }
 
     /// Search for delegated keys (V3 API)
@@ -429,7 +464,7 @@ impl AccumulateClient {
                 "delegate": query.delegate
             }
         });
-        self.v3_client.call_v3("query", params).await
+        self.call_query(params).await
     }
 
     /// Search by message/transaction hash (V3 API)
@@ -443,7 +478,7 @@ impl AccumulateClient {
                 "hash": query.hash
             }
         });
-        self.v3_client.call_v3("query", params).await
+        self.call_query(params).await
     }
 
     // Transaction Building Helpers
@@ -458,7 +493,7 @@ impl AccumulateClient {
         // Get current timestamp in microseconds
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
-            .map_err(|e| JsonRpcError::General(anyhow::anyhow!("Time error: {}", e)))?
+            .map_err(|e| JsonRpcError::General(format!("Time error: {}", e)))?
             .as_micros() as i64;
 
         // Create transaction with timestamp
@@ -515,7 +550,7 @@ impl AccumulateClient {
         // Get current timestamp in microseconds
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
-            .map_err(|e| JsonRpcError::General(anyhow::anyhow!("Time error: {}", e)))?
+            .map_err(|e| JsonRpcError::General(format!("Time error: {}", e)))?
             .as_micros() as u64;
 
         // Create transaction envelope using codec
@@ -523,7 +558,7 @@ impl AccumulateClient {
 
         // Get hash for signing using codec
         let hash = TransactionCodec::get_transaction_hash(&envelope)
-            .map_err(|e| JsonRpcError::General(anyhow::anyhow!("Hash error: {:?}", e)))?;
+            .map_err(|e| JsonRpcError::General(format!("Hash error: {:?}", e)))?;
 
         // Sign the hash using the keypair
         let signature = keypair.sign(&hash);
@@ -547,13 +582,13 @@ impl AccumulateClient {
     /// Encode transaction envelope to binary using codec
     pub fn encode_envelope(&self, envelope: &CodecTransactionEnvelope) -> Result<Vec<u8>, JsonRpcError> {
         TransactionCodec::encode_envelope(envelope)
-            .map_err(|e| JsonRpcError::General(anyhow::anyhow!("Encoding error: {:?}", e)))
+            .map_err(|e| JsonRpcError::General(format!("Encoding error: {:?}", e)))
     }
 
     /// Decode transaction envelope from binary using codec
     pub fn decode_envelope(&self, data: &[u8]) -> Result<CodecTransactionEnvelope, JsonRpcError> {
         TransactionCodec::decode_envelope(data)
-            .map_err(|e| JsonRpcError::General(anyhow::anyhow!("Decoding error: {:?}", e)))
+            .map_err(|e| JsonRpcError::General(format!("Decoding error: {:?}", e)))
     }
 
 
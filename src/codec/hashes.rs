@@ -4,6 +4,7 @@
 //! with the TypeScript SDK for deterministic transaction and data hashing.
 
 use super::{canonical_json, BinaryWriter, EncodingError};
+use crate::json_rpc_client::JsonRpcError;
 use serde_json::Value;
 use sha2::{Digest, Sha256};
 
@@ -435,11 +436,71 @@ impl MerkleHash {
     }
 }
 
+/// Hash of a transaction header, from its JSON form
+///
+/// The header is binary-encoded as the Go executor does (principal,
+/// initiator, memo, metadata, expire, holdUntil, authorities) and hashed
+/// with SHA-256. Unlike [`AccumulateHash::hash_transaction`], which hashes
+/// canonical JSON, this and [`body_hash`] and [`tx_hash`] give the hashes
+/// the network itself computes and signs.
+pub fn header_hash(header: &Value) -> Result<[u8; 32], JsonRpcError> {
+    let header_bytes = crate::helpers::marshal_header_json(header)?;
+    Ok(AccumulateHash::sha256_bytes(&header_bytes))
+}
+
+/// Hash of a transaction body, from its JSON form
+///
+/// writeData and writeDataTo bodies hash to the Merkle root of their fields
+/// and entry; every other body hashes its binary encoding.
+pub fn body_hash(body: &Value) -> Result<[u8; 32], JsonRpcError> {
+    crate::helpers::compute_body_hash(body)
+}
+
+/// Hash of a transaction (`{header, body}`), as used in transaction IDs
+///
+/// `SHA256(header_hash || body_hash)`; a remote transaction body stands in
+/// for the transaction it references, so its hash is the referenced one.
+pub fn tx_hash(transaction: &Value) -> Result<[u8; 32], JsonRpcError> {
+    let header = transaction.get("header").unwrap_or(&Value::Null);
+    let body = transaction.get("body").unwrap_or(&Value::Null);
+    let header_bytes = crate::helpers::marshal_header_json(header)?;
+    crate::helpers::compute_tx_hash(&header_bytes, body)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use serde_json::json;
 
+    #[test]
+    fn test_protocol_transaction_hashes() {
+        let transaction = json!({
+            "header": {
+                "principal": "acc://alice.acme/tokens",
+                "initiator": "11".repeat(32),
+                "memo": "rent"
+            },
+            "body": {"type": "sendTokens", "to": [{"url": "acc://bob.acme/tokens", "amount": "100"}]}
+        });
+
+        let header = header_hash(&transaction["header"]).unwrap();
+        let body = body_hash(&transaction["body"]).unwrap();
+        let combined = AccumulateHash::sha256_concat(&[&header, &body]);
+        assert_eq!(tx_hash(&transaction).unwrap(), combined);
+
+        // Remote bodies carry the referenced hash
+        let remote = json!({
+            "header": transaction["header"].clone(),
+            "body": {"type": "signPending", "hash": "ab".repeat(32)}
+        });
+        assert_eq!(tx_hash(&remote).unwrap(), [0xab; 32]);
+
+        // writeData hashes its fields, not its binary encoding
+        let write = json!({"type": "writeData", "entry": {"type": "doublehash", "data": ["68656c6c6f"]}});
+        let encoded = crate::helpers::marshal_body_to_binary(&write).unwrap();
+        assert_ne!(body_hash(&write).unwrap(), AccumulateHash::sha256_bytes(&encoded));
+    }
+
     #[test]
     fn test_sha256_consistency() {
         let data = b"hello world";
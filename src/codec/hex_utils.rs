@@ -0,0 +1,113 @@
+//! Constant-time hex decoding with field-level error context
+//!
+//! Key hashes, transaction hashes and data entries arrive as hex strings inside
+//! JSON bodies. Decoding them with `hex::decode(..).unwrap_or_default()` turns a
+//! typo into an empty byte string and a silently wrong transaction hash, so the
+//! signing path decodes through these helpers instead. Decoding does not branch
+//! on the digit values, which keeps timing independent of secret key material.
+
+use crate::errors::ValidationError;
+
+/// Decode a hex-encoded field, naming the field in the error on failure
+///
+/// Accepts upper- and lower-case digits. An empty string decodes to an empty
+/// byte vector.
+pub fn parse_hex_field(name: &str, s: &str) -> Result<Vec<u8>, ValidationError> {
+    let digits = s.as_bytes();
+    if digits.len() % 2 != 0 {
+        return Err(ValidationError::InvalidFieldValue {
+            field: name.to_string(),
+            reason: format!("hex string has odd length {}", digits.len()),
+        });
+    }
+
+    let mut out = Vec::with_capacity(digits.len() / 2);
+    let mut invalid = 0i32;
+    for pair in digits.chunks_exact(2) {
+        let (hi, hi_ok) = decode_nibble(pair[0]);
+        let (lo, lo_ok) = decode_nibble(pair[1]);
+        invalid |= (hi_ok & lo_ok) ^ 1;
+        // Both nibbles are in 0..=15 for valid input; invalid input is discarded below
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        out.push(((hi << 4) | lo) as u8);
+    }
+
+    if invalid != 0 {
+        return Err(ValidationError::InvalidFieldValue {
+            field: name.to_string(),
+            reason: "not a valid hex string".to_string(),
+        });
+    }
+
+    Ok(out)
+}
+
+/// Decode a hex-encoded 32-byte hash field
+pub fn parse_hex_hash(name: &str, s: &str) -> Result<[u8; 32], ValidationError> {
+    let bytes = parse_hex_field(name, s)?;
+    <[u8; 32]>::try_from(bytes.as_slice()).map_err(|_| ValidationError::InvalidHash {
+        expected: 32,
+        actual: bytes.len(),
+    })
+}
+
+/// Decode a single hex digit without data-dependent branches
+///
+/// Returns the nibble value and 1 if the digit was valid, 0 otherwise.
+fn decode_nibble(c: u8) -> (i32, i32) {
+    let c = i32::from(c);
+
+    // '0'..='9'
+    let num = c ^ 0x30;
+    let num_ok = ((num - 10) >> 31) & 1;
+
+    // 'a'..='f' and 'A'..='F'
+    let alpha = (c | 0x20) - 0x61;
+    let alpha_ok = ((!alpha & (alpha - 6)) >> 31) & 1;
+
+    let value = (num & -num_ok) | ((alpha + 10) & -alpha_ok);
+    (value, num_ok | alpha_ok)
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_hex_field_matches_hex_crate() {
+        let inputs = ["", "00", "ff", "FF", "0123456789abcdef", "DeadBeef"];
+        for input in inputs {
+            assert_eq!(parse_hex_field("f", input).unwrap(), hex::decode(input).unwrap());
+        }
+    }
+
+    #[test]
+    fn test_parse_hex_field_rejects_every_non_hex_byte() {
+        for c in 0u8..=255 {
+            let s = [b'0', c];
+            let Ok(s) = std::str::from_utf8(&s) else { continue };
+            let expected = c.is_ascii_hexdigit();
+            assert_eq!(parse_hex_field("f", s).is_ok(), expected, "byte {c:#04x}");
+        }
+    }
+
+    #[test]
+    fn test_parse_hex_field_error_names_field() {
+        for bad in ["abc", "zz"] {
+            assert!(matches!(
+                parse_hex_field("keyHash", bad),
+                Err(ValidationError::InvalidFieldValue { ref field, .. }) if field == "keyHash"
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_hex_hash_length() {
+        assert_eq!(parse_hex_hash("hash", &"ab".repeat(32)).unwrap(), [0xab; 32]);
+        assert!(matches!(
+            parse_hex_hash("hash", "abcd"),
+            Err(ValidationError::InvalidHash { expected: 32, actual: 2 })
+        ));
+    }
+}
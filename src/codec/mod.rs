@@ -13,9 +13,11 @@ pub mod canonical;
 pub mod crypto;
 pub mod hash_helper;
 pub mod hashes;
+pub mod hex_utils;
 pub mod reader;
 pub mod signing;
 pub mod transaction_codec;
+pub mod unmarshal;
 pub mod writer;
 
 pub use canonical::*;
@@ -23,9 +25,11 @@ pub use canonical::*;
 // pub use crypto::*;
 pub use hash_helper::*;
 pub use hashes::*;
+pub use hex_utils::*;
 pub use reader::*;
 pub use signing::*;
 pub use transaction_codec::*;
+pub use unmarshal::*;
 pub use writer::*;
 
 /// Convert a JSON value to canonical JSON string with deterministic ordering
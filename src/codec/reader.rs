@@ -3,6 +3,7 @@
 //! This module provides exact binary decoding compatibility with the TypeScript SDK,
 //! including identical varint/uvarint decoding, length prefixes, and field decoding.
 
+use super::writer::EMPTY_OBJECT;
 use thiserror::Error;
 
 /// Errors that can occur during binary decoding
@@ -28,6 +29,32 @@ pub enum DecodingError {
 
     #[error("Negative length prefix")]
     NegativeLength,
+
+    #[error("Unknown type: {0}")]
+    UnknownType(u64),
+
+    #[error("Unexpected field number: {0}")]
+    UnexpectedField(u32),
+}
+
+/// Wire shape of a field value, needed to skip it
+///
+/// The Accumulate encoding carries no type information, so skipping an unknown
+/// field requires knowing how its value is framed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValueKind {
+    /// Unsigned varint (integers, enums)
+    Uvarint,
+    /// Zigzag varint (signed integers, timestamps)
+    Varint,
+    /// Single byte
+    Bool,
+    /// Length-prefixed bytes (bytes, strings, URLs, big ints, nested values)
+    Bytes,
+    /// Raw 32-byte hash
+    Hash,
+    /// Seconds and nanoseconds as two uvarints
+    Duration,
 }
 
 /// Binary reader that matches TypeScript SDK decoding exactly
@@ -184,6 +211,101 @@ impl<'a> BinaryReader<'a> {
         Ok(bytes.to_vec())
     }
 
+    /// Decode a timestamp from a zigzag varint of whole Unix seconds
+    /// Matches Go: Reader.ReadTime
+    pub fn read_time(&mut self) -> Result<chrono::DateTime<chrono::Utc>, DecodingError> {
+        let seconds = self.read_varint()?;
+        chrono::DateTime::from_timestamp(seconds, 0).ok_or(DecodingError::ValueOverflow)
+    }
+
+    /// Decode a duration from whole seconds and a nanosecond remainder
+    /// Matches Go: Reader.ReadDuration
+    pub fn read_duration(&mut self) -> Result<std::time::Duration, DecodingError> {
+        let seconds = self.read_uvarint()?;
+        let nanos = self.read_uvarint()?;
+        let nanos = u32::try_from(nanos)
+            .ok()
+            .filter(|n| *n < 1_000_000_000)
+            .ok_or(DecodingError::ValueOverflow)?;
+        Ok(std::time::Duration::new(seconds, nanos))
+    }
+
+    /// Decode a length-prefixed nested value and return a reader over its fields
+    /// Matches Go: Reader.ReadValue
+    ///
+    /// The [`EMPTY_OBJECT`] marker yields a reader with no data.
+    pub fn read_value(&mut self) -> Result<BinaryReader<'a>, DecodingError> {
+        let bytes = self.read_bytes_with_length()?;
+        if bytes == [EMPTY_OBJECT] {
+            return Ok(Self::new(&[]));
+        }
+        Ok(Self::new(bytes))
+    }
+
+    /// Decode a field number, rejecting values outside [1, 32]
+    pub fn read_field_number(&mut self) -> Result<u32, DecodingError> {
+        let field = self.read_uvarint()?;
+        match u32::try_from(field) {
+            Ok(field @ 1..=32) => Ok(field),
+            _ => Err(DecodingError::InvalidFieldNumber(
+                u32::try_from(field).unwrap_or(u32::MAX),
+            )),
+        }
+    }
+
+    /// Peek at the next field number without consuming it
+    ///
+    /// Returns `None` at the end of the data and at the [`EMPTY_OBJECT`] marker.
+    pub fn peek_field_number(&self) -> Result<Option<u32>, DecodingError> {
+        if !self.has_remaining() || self.data[self.position..] == [EMPTY_OBJECT] {
+            return Ok(None);
+        }
+        self.clone().read_field_number().map(Some)
+    }
+
+    /// Consume the next field number if it equals `field`
+    /// Matches Go: Reader.readField
+    ///
+    /// Fields are written in ascending order and omitted when empty, so any
+    /// other number means `field` is absent and nothing is consumed.
+    pub fn read_field(&mut self, field: u32) -> Result<bool, DecodingError> {
+        if self.peek_field_number()? != Some(field) {
+            return Ok(false);
+        }
+        self.read_field_number()?;
+        Ok(true)
+    }
+
+    /// Skip a single value of the given shape
+    pub fn skip_value(&mut self, kind: ValueKind) -> Result<(), DecodingError> {
+        match kind {
+            ValueKind::Uvarint | ValueKind::Varint => {
+                self.read_uvarint()?;
+            }
+            ValueKind::Bool => {
+                self.read_byte()?;
+            }
+            ValueKind::Bytes => {
+                self.read_bytes_with_length()?;
+            }
+            ValueKind::Hash => {
+                self.read_bytes(32)?;
+            }
+            ValueKind::Duration => {
+                self.read_uvarint()?;
+                self.read_uvarint()?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Skip a field number and its value, returning the field number
+    pub fn skip_field(&mut self, kind: ValueKind) -> Result<u32, DecodingError> {
+        let field = self.read_field_number()?;
+        self.skip_value(kind)?;
+        Ok(field)
+    }
+
     /// Read the remaining bytes
     pub fn read_remaining(&mut self) -> &'a [u8] {
         let remaining = &self.data[self.position..];
@@ -611,4 +733,86 @@ mod tests {
         assert_eq!(value, "test");
         assert_eq!(bytes_read, encoded.len());
     }
+
+    #[test]
+    fn test_time_and_duration_roundtrip() {
+        let times = [0i64, -1, 1_704_067_200, 253_402_300_799, -62_135_596_800];
+        for seconds in times {
+            let time = chrono::DateTime::from_timestamp(seconds, 0).unwrap();
+            let mut writer = BinaryWriter::new();
+            writer.write_time(&time).unwrap();
+            let bytes = writer.into_bytes();
+            assert_eq!(BinaryReader::new(&bytes).read_time().unwrap(), time);
+        }
+
+        let durations = [
+            std::time::Duration::ZERO,
+            std::time::Duration::new(1, 500_000_000),
+            std::time::Duration::new(86_400, 999_999_999),
+            std::time::Duration::new(u64::MAX, 1),
+        ];
+        for duration in durations {
+            let mut writer = BinaryWriter::new();
+            writer.write_duration(duration).unwrap();
+            let bytes = writer.into_bytes();
+            assert_eq!(BinaryReader::new(&bytes).read_duration().unwrap(), duration);
+        }
+
+        // Nanoseconds must stay below one second
+        let mut reader = BinaryReader::new(&[0x00, 0x80, 0x94, 0xeb, 0xdc, 0x03]);
+        assert!(matches!(reader.read_duration(), Err(DecodingError::ValueOverflow)));
+    }
+
+    #[test]
+    fn test_nested_value_and_field_skipping() {
+        let mut writer = BinaryWriter::new();
+        writer.write_uvarint_field(42, 1).unwrap();
+        writer.write_string_field("skipped", 2).unwrap();
+        writer
+            .write_value_field(3, |w| {
+                w.write_bool_field(true, 1)?;
+                w.write_hash_field(&[7u8; 32], 2)
+            })
+            .unwrap();
+        writer.write_value_field(4, |_| Ok(())).unwrap();
+        writer
+            .write_duration_field(std::time::Duration::from_secs(5), 5)
+            .unwrap();
+        writer.write_varint_field(-3, 6).unwrap();
+        let bytes = writer.into_bytes();
+
+        let mut reader = BinaryReader::new(&bytes);
+        assert!(!reader.read_field(2).unwrap());
+        assert!(reader.read_field(1).unwrap());
+        assert_eq!(reader.read_uvarint().unwrap(), 42);
+        assert_eq!(reader.skip_field(ValueKind::Bytes).unwrap(), 2);
+
+        assert!(reader.read_field(3).unwrap());
+        let mut nested = reader.read_value().unwrap();
+        assert_eq!(nested.skip_field(ValueKind::Bool).unwrap(), 1);
+        assert_eq!(nested.skip_field(ValueKind::Hash).unwrap(), 2);
+        assert_eq!(nested.peek_field_number().unwrap(), None);
+
+        assert!(reader.read_field(4).unwrap());
+        let empty = reader.read_value().unwrap();
+        assert!(!empty.has_remaining());
+
+        assert_eq!(reader.skip_field(ValueKind::Duration).unwrap(), 5);
+        assert!(reader.read_field(6).unwrap());
+        assert_eq!(reader.read_varint().unwrap(), -3);
+        assert_eq!(reader.peek_field_number().unwrap(), None);
+    }
+
+    #[test]
+    fn test_read_field_number_range() {
+        assert!(matches!(
+            BinaryReader::new(&[33]).read_field_number(),
+            Err(DecodingError::InvalidFieldNumber(33))
+        ));
+        assert!(matches!(
+            BinaryReader::new(&[0]).read_field_number(),
+            Err(DecodingError::InvalidFieldNumber(0))
+        ));
+        assert_eq!(BinaryReader::new(&[EMPTY_OBJECT]).peek_field_number().unwrap(), None);
+    }
 }
@@ -4,6 +4,7 @@
 //! matching the Go core and Dart SDK implementations.
 
 use super::writer::BinaryWriter;
+use crate::generated::enums::SignatureType;
 use sha2::{Digest, Sha256};
 
 /// Signature type enum values matching Go core
@@ -25,6 +26,55 @@ pub mod signature_types {
     pub const PARTITION: u64 = 14;
     pub const SET: u64 = 15;
     pub const AUTHORITY: u64 = 16;
+
+    /// JSON name of a signature type number
+    pub const fn name(signature_type: u64) -> Option<&'static str> {
+        Some(match signature_type {
+            LEGACY_ED25519 => "legacyED25519",
+            ED25519 => "ed25519",
+            RCD1 => "rcd1",
+            BTC => "btc",
+            BTC_LEGACY => "btcLegacy",
+            ETH => "eth",
+            DELEGATED => "delegated",
+            INTERNAL => "internal",
+            RSA_SHA256 => "rsaSha256",
+            ECDSA_SHA256 => "ecdsaSha256",
+            TYPED_DATA => "typedData",
+            REMOTE => "remote",
+            RECEIPT => "receipt",
+            PARTITION => "partition",
+            SET => "set",
+            AUTHORITY => "authority",
+            _ => return None,
+        })
+    }
+}
+
+impl SignatureType {
+    /// Numeric value of the signature type, as in [`signature_types`]
+    #[must_use]
+    pub const fn value(&self) -> u64 {
+        match self {
+            Self::Unknown => signature_types::UNKNOWN,
+            Self::LegacyED25519 => signature_types::LEGACY_ED25519,
+            Self::ED25519 => signature_types::ED25519,
+            Self::RCD1 => signature_types::RCD1,
+            Self::BTC => signature_types::BTC,
+            Self::BTCLegacy => signature_types::BTC_LEGACY,
+            Self::ETH => signature_types::ETH,
+            Self::Delegated => signature_types::DELEGATED,
+            Self::Internal => signature_types::INTERNAL,
+            Self::RsaSha256 => signature_types::RSA_SHA256,
+            Self::EcdsaSha256 => signature_types::ECDSA_SHA256,
+            Self::TypedData => signature_types::TYPED_DATA,
+            Self::Remote => signature_types::REMOTE,
+            Self::Receipt => signature_types::RECEIPT,
+            Self::Partition => signature_types::PARTITION,
+            Self::Set => signature_types::SET,
+            Self::Authority => signature_types::AUTHORITY,
+        }
+    }
 }
 
 /// Transaction type enum values matching Go core
@@ -49,6 +99,96 @@ pub mod tx_types {
     pub const TRANSFER_CREDITS: u64 = 0x12;
     pub const UPDATE_ACCOUNT_AUTH: u64 = 0x15;
     pub const UPDATE_KEY: u64 = 0x16;
+    pub const NETWORK_MAINTENANCE: u64 = 0x2E;
+    pub const ACTIVATE_PROTOCOL_VERSION: u64 = 0x2F;
+    pub const REMOTE: u64 = 0x30;
+    pub const SYSTEM_GENESIS: u64 = 0x60;
+    pub const DIRECTORY_ANCHOR: u64 = 0x61;
+    pub const BLOCK_VALIDATOR_ANCHOR: u64 = 0x62;
+    pub const SYSTEM_WRITE_DATA: u64 = 0x63;
+
+    /// JSON name of a transaction type number
+    pub const fn name(tx_type: u64) -> Option<&'static str> {
+        Some(match tx_type {
+            CREATE_IDENTITY => "createIdentity",
+            CREATE_TOKEN_ACCOUNT => "createTokenAccount",
+            SEND_TOKENS => "sendTokens",
+            CREATE_DATA_ACCOUNT => "createDataAccount",
+            WRITE_DATA => "writeData",
+            WRITE_DATA_TO => "writeDataTo",
+            ACME_FAUCET => "acmeFaucet",
+            CREATE_TOKEN => "createToken",
+            ISSUE_TOKENS => "issueTokens",
+            BURN_TOKENS => "burnTokens",
+            CREATE_LITE_TOKEN_ACCOUNT => "createLiteTokenAccount",
+            CREATE_KEY_PAGE => "createKeyPage",
+            CREATE_KEY_BOOK => "createKeyBook",
+            ADD_CREDITS => "addCredits",
+            UPDATE_KEY_PAGE => "updateKeyPage",
+            LOCK_ACCOUNT => "lockAccount",
+            BURN_CREDITS => "burnCredits",
+            TRANSFER_CREDITS => "transferCredits",
+            UPDATE_ACCOUNT_AUTH => "updateAccountAuth",
+            UPDATE_KEY => "updateKey",
+            NETWORK_MAINTENANCE => "networkMaintenance",
+            ACTIVATE_PROTOCOL_VERSION => "activateProtocolVersion",
+            REMOTE => "remoteTransaction",
+            SYSTEM_GENESIS => "systemGenesis",
+            DIRECTORY_ANCHOR => "directoryAnchor",
+            BLOCK_VALIDATOR_ANCHOR => "blockValidatorAnchor",
+            SYSTEM_WRITE_DATA => "systemWriteData",
+            _ => return None,
+        })
+    }
+
+    /// Transaction type number of a JSON name (`signPending` is an alias of `remoteTransaction`)
+    pub fn from_name(tx_type: &str) -> Option<u64> {
+        match tx_type {
+            "signPending" | "remote" => Some(REMOTE),
+            _ => (CREATE_IDENTITY..=SYSTEM_WRITE_DATA).find(|n| name(*n) == Some(tx_type)),
+        }
+    }
+}
+
+/// ExecutorVersion enum values matching Go core
+pub mod executor_versions {
+    pub const V1: u64 = 1;
+    pub const V1_SIGNATURE_ANCHORING: u64 = 2;
+    pub const V1_DOUBLE_HASH_ENTRIES: u64 = 3;
+    pub const V1_HALT: u64 = 4;
+    pub const V2: u64 = 5;
+    pub const V2_BAIKONUR: u64 = 6;
+    pub const V2_VANDENBERG: u64 = 7;
+    pub const V2_JIUQUAN: u64 = 8;
+    pub const V_NEXT: u64 = 9;
+
+    /// JSON name of an executor version number
+    pub const fn name(version: u64) -> Option<&'static str> {
+        Some(match version {
+            V1 => "v1",
+            V1_SIGNATURE_ANCHORING => "v1SignatureAnchoring",
+            V1_DOUBLE_HASH_ENTRIES => "v1DoubleHashEntries",
+            V1_HALT => "v1Halt",
+            V2 => "v2",
+            V2_BAIKONUR => "v2Baikonur",
+            V2_VANDENBERG => "v2Vandenberg",
+            V2_JIUQUAN => "v2Jiuquan",
+            V_NEXT => "vNext",
+            _ => return None,
+        })
+    }
+
+    /// Executor version number of a JSON name (`v2-baikonur` style names are accepted too)
+    pub fn from_name(version: &str) -> Option<u64> {
+        let version = version.replace('-', "");
+        (V1..=V_NEXT).find(|n| name(*n).is_some_and(|known| known.eq_ignore_ascii_case(&version)))
+    }
+}
+
+/// NetworkMaintenanceOperation type enum values matching Go core
+pub mod network_maintenance_op_types {
+    pub const UNKNOWN: u64 = 0;
+    pub const PENDING_TRANSACTION_GC: u64 = 1;
 }
 
 /// KeyPageOperation type enum values matching Go core
@@ -109,6 +249,32 @@ pub fn compute_signature_metadata_hash(
     memo: Option<&str>,
     data: Option<&[u8]>,
 ) -> [u8; 32] {
+    sha256_bytes(&marshal_signature_metadata(
+        signature_type,
+        public_key,
+        signer,
+        signer_version,
+        timestamp,
+        vote,
+        memo,
+        data,
+    ))
+}
+
+/// Marshal the metadata of a key signature: the signature with its
+/// signature bytes and transaction hash left out
+#[must_use]
+#[allow(clippy::too_many_arguments)]
+pub fn marshal_signature_metadata(
+    signature_type: u64,
+    public_key: &[u8],
+    signer: &str,
+    signer_version: u64,
+    timestamp: u64,
+    vote: u64,
+    memo: Option<&str>,
+    data: Option<&[u8]>,
+) -> Vec<u8> {
     let mut writer = BinaryWriter::new();
 
     // Field 1: Type (enum)
@@ -171,8 +337,45 @@ pub fn compute_signature_metadata_hash(
         }
     }
 
-    // Hash the encoded metadata
-    sha256_bytes(writer.bytes())
+    writer.into_bytes()
+}
+
+/// Marshal the metadata of a delegated signature wrapping `inner_metadata`
+///
+/// Field order matches Go: `protocol/types_gen.go` `DelegatedSignature.MarshalBinary`
+/// - Field 1: Type (enum = 7 for Delegated)
+/// - Field 2: Signature (nested, the inner signature's metadata)
+/// - Field 3: Delegator (URL as string)
+#[must_use]
+pub fn marshal_delegated_signature_metadata(inner_metadata: &[u8], delegator: &str) -> Vec<u8> {
+    let mut writer = BinaryWriter::new();
+    let _ = writer.write_uvarint(1);
+    let _ = writer.write_uvarint(signature_types::DELEGATED);
+    let _ = writer.write_uvarint(2);
+    let _ = writer.write_uvarint(inner_metadata.len() as u64);
+    let _ = writer.write_bytes(inner_metadata);
+    let _ = writer.write_uvarint(3);
+    let _ = writer.write_uvarint(delegator.len() as u64);
+    let _ = writer.write_bytes(delegator.as_bytes());
+    writer.into_bytes()
+}
+
+/// Metadata hash of a key signature wrapped once per delegator
+///
+/// `delegators[0]` wraps the key signature, each later delegator wraps the
+/// one before. The hash of the outermost wrapper is what the key signs and
+/// what an initiating signature puts in the transaction header. Without
+/// delegators this is the hash of `key_metadata` itself.
+pub fn compute_delegated_signature_metadata_hash<S: AsRef<str>>(
+    key_metadata: &[u8],
+    delegators: &[S],
+) -> [u8; 32] {
+    let metadata = delegators
+        .iter()
+        .fold(key_metadata.to_vec(), |inner, delegator| {
+            marshal_delegated_signature_metadata(&inner, delegator.as_ref())
+        });
+    sha256_bytes(&metadata)
 }
 
 /// Options for extended transaction header fields (fields 5-7).
@@ -333,6 +536,22 @@ pub fn marshal_add_credits_body(
     writer.into_bytes()
 }
 
+/// Write a URL field, omitted when empty
+fn write_url_field(writer: &mut BinaryWriter, field: u64, url: &str) {
+    if !url.is_empty() {
+        let _ = writer.write_uvarint(field);
+        let _ = writer.write_uvarint(url.len() as u64);
+        let _ = writer.write_bytes(url.as_bytes());
+    }
+}
+
+/// Write a repeated Authorities field (one URL per entry)
+fn write_authorities(writer: &mut BinaryWriter, field: u64, authorities: &[&str]) {
+    for authority in authorities {
+        write_url_field(writer, field, authority);
+    }
+}
+
 /// Marshal SendTokens transaction body to binary format
 ///
 /// Field order matches Go: protocol/types_gen.go SendTokens.MarshalBinary
@@ -342,6 +561,18 @@ pub fn marshal_add_credits_body(
 /// - Field 4: To (repeated TokenRecipient)
 pub fn marshal_send_tokens_body(
     recipients: &[(String, u64)], // (url, amount)
+) -> Vec<u8> {
+    marshal_send_tokens_body_full(None, None, recipients)
+}
+
+/// Marshal SendTokens transaction body including the optional Hash and Meta
+///
+/// - Field 2: Hash (32 bytes, no length prefix)
+/// - Field 3: Meta (raw JSON bytes)
+pub fn marshal_send_tokens_body_full(
+    hash: Option<&[u8; 32]>,
+    meta: Option<&[u8]>,
+    recipients: &[(String, u64)],
 ) -> Vec<u8> {
     let mut writer = BinaryWriter::new();
 
@@ -349,6 +580,18 @@ pub fn marshal_send_tokens_body(
     let _ = writer.write_uvarint(1);
     let _ = writer.write_uvarint(tx_types::SEND_TOKENS);
 
+    // Field 2: Hash
+    if let Some(hash) = hash {
+        let _ = writer.write_hash_field(hash, 2);
+    }
+
+    // Field 3: Meta
+    if let Some(meta) = meta.filter(|m| !m.is_empty()) {
+        let _ = writer.write_uvarint(3);
+        let _ = writer.write_uvarint(meta.len() as u64);
+        let _ = writer.write_bytes(meta);
+    }
+
     // Field 4: To (repeated TokenRecipient)
     for (url, amount) in recipients {
         let recipient_bytes = marshal_token_recipient(url, *amount);
@@ -371,6 +614,18 @@ pub fn marshal_create_identity_body(
     url: &str,
     key_hash: &[u8],
     key_book_url: &str,
+) -> Vec<u8> {
+    marshal_create_identity_body_full(url, key_hash, key_book_url, &[])
+}
+
+/// Marshal CreateIdentity transaction body with additional authorities
+///
+/// - Field 5: Authorities (repeated URLs)
+pub fn marshal_create_identity_body_full(
+    url: &str,
+    key_hash: &[u8],
+    key_book_url: &str,
+    authorities: &[&str],
 ) -> Vec<u8> {
     let mut writer = BinaryWriter::new();
 
@@ -397,6 +652,9 @@ pub fn marshal_create_identity_body(
     let _ = writer.write_uvarint(book_bytes.len() as u64);
     let _ = writer.write_bytes(book_bytes);
 
+    // Field 5: Authorities
+    write_authorities(&mut writer, 5, authorities);
+
     writer.into_bytes()
 }
 
@@ -407,6 +665,11 @@ pub fn marshal_create_identity_body(
 /// - Field 2: Url (URL as string)
 /// - Field 3: Authorities (repeated URLs)
 pub fn marshal_create_data_account_body(url: &str) -> Vec<u8> {
+    marshal_create_data_account_body_full(url, &[])
+}
+
+/// Marshal CreateDataAccount transaction body with additional authorities
+pub fn marshal_create_data_account_body_full(url: &str, authorities: &[&str]) -> Vec<u8> {
     let mut writer = BinaryWriter::new();
 
     // Field 1: Type (CreateDataAccount = 0x04)
@@ -421,6 +684,9 @@ pub fn marshal_create_data_account_body(url: &str) -> Vec<u8> {
         let _ = writer.write_bytes(url_bytes);
     }
 
+    // Field 3: Authorities
+    write_authorities(&mut writer, 3, authorities);
+
     writer.into_bytes()
 }
 
@@ -495,6 +761,15 @@ fn marshal_data_entry(entries_hex: &[String]) -> Vec<u8> {
 pub fn marshal_create_token_account_body(
     url: &str,
     token_url: &str,
+) -> Vec<u8> {
+    marshal_create_token_account_body_full(url, token_url, &[])
+}
+
+/// Marshal CreateTokenAccount transaction body with additional authorities
+pub fn marshal_create_token_account_body_full(
+    url: &str,
+    token_url: &str,
+    authorities: &[&str],
 ) -> Vec<u8> {
     let mut writer = BinaryWriter::new();
 
@@ -518,6 +793,9 @@ pub fn marshal_create_token_account_body(
         let _ = writer.write_bytes(token_bytes);
     }
 
+    // Field 4: Authorities
+    write_authorities(&mut writer, 4, authorities);
+
     writer.into_bytes()
 }
 
@@ -566,7 +844,20 @@ fn amount_to_bigint_bytes(mut value: u64) -> Vec<u8> {
 /// - Field 5: Precision (uint)
 /// - Field 6: Properties (URL, optional)
 /// - Field 7: SupplyLimit (BigInt, optional)
+/// - Field 9: Authorities (repeated URLs)
 pub fn marshal_create_token_body(url: &str, symbol: &str, precision: u64, supply_limit: Option<u64>) -> Vec<u8> {
+    marshal_create_token_body_full(url, symbol, precision, None, supply_limit, &[])
+}
+
+/// Marshal CreateToken transaction body with properties and additional authorities
+pub fn marshal_create_token_body_full(
+    url: &str,
+    symbol: &str,
+    precision: u64,
+    properties: Option<&str>,
+    supply_limit: Option<u64>,
+    authorities: &[&str],
+) -> Vec<u8> {
     let mut writer = BinaryWriter::new();
 
     // Field 1: Type (CreateToken = 0x08)
@@ -593,6 +884,9 @@ pub fn marshal_create_token_body(url: &str, symbol: &str, precision: u64, supply
     let _ = writer.write_uvarint(5);
     let _ = writer.write_uvarint(precision);
 
+    // Field 6: Properties (optional)
+    write_url_field(&mut writer, 6, properties.unwrap_or_default());
+
     // Field 7: SupplyLimit (optional)
     if let Some(limit) = supply_limit {
         if limit > 0 {
@@ -603,6 +897,9 @@ pub fn marshal_create_token_body(url: &str, symbol: &str, precision: u64, supply
         }
     }
 
+    // Field 9: Authorities
+    write_authorities(&mut writer, 9, authorities);
+
     writer.into_bytes()
 }
 
@@ -679,6 +976,18 @@ pub fn sha256_bytes(data: &[u8]) -> [u8; 32] {
 /// 1. Marshal WriteData body with Entry=nil (only Type, Scratch, WriteToState)
 /// 2. Compute Merkle hash of [SHA256(marshaledBody), entryHash]
 pub fn compute_write_data_body_hash(entries_hex: &[String], scratch: bool, write_to_state: bool) -> [u8; 32] {
+    write_data_body_hash(entries_hex, scratch, write_to_state, true)
+}
+
+/// Compute WriteData body hash for an `accumulate` (single-hash) data entry
+///
+/// Executors before v1-doubleHashEntries only accept this entry type, whose
+/// hash is the Merkle root of the data hashes without the final SHA256.
+pub fn compute_legacy_write_data_body_hash(entries_hex: &[String], scratch: bool, write_to_state: bool) -> [u8; 32] {
+    write_data_body_hash(entries_hex, scratch, write_to_state, false)
+}
+
+fn write_data_body_hash(entries_hex: &[String], scratch: bool, write_to_state: bool, double_hash: bool) -> [u8; 32] {
     // Marshal body WITHOUT entry
     let body_without_entry = marshal_write_data_body_without_entry(scratch, write_to_state);
     let body_part_hash = sha256_bytes(&body_without_entry);
@@ -687,7 +996,7 @@ pub fn compute_write_data_body_hash(entries_hex: &[String], scratch: bool, write
     let entry_hash = if entries_hex.is_empty() {
         [0u8; 32]
     } else {
-        compute_data_entry_hash(entries_hex)
+        compute_data_entry_hash(entries_hex, double_hash)
     };
 
     // Merkle hash of [bodyPartHash, entryHash]
@@ -724,6 +1033,15 @@ fn marshal_write_data_body_without_entry(scratch: bool, write_to_state: bool) ->
 /// Same algorithm as WriteData but with Type=WRITE_DATA_TO and Recipient included
 /// Based on Go: protocol/transaction_hash.go WriteDataTo.GetHash()
 pub fn compute_write_data_to_body_hash(recipient: &str, entries_hex: &[String]) -> [u8; 32] {
+    write_data_to_body_hash(recipient, entries_hex, true)
+}
+
+/// Compute WriteDataTo body hash for an `accumulate` (single-hash) data entry
+pub fn compute_legacy_write_data_to_body_hash(recipient: &str, entries_hex: &[String]) -> [u8; 32] {
+    write_data_to_body_hash(recipient, entries_hex, false)
+}
+
+fn write_data_to_body_hash(recipient: &str, entries_hex: &[String], double_hash: bool) -> [u8; 32] {
     // Marshal body WITHOUT entry (but WITH recipient)
     let body_without_entry = marshal_write_data_to_body_without_entry(recipient);
     let body_part_hash = sha256_bytes(&body_without_entry);
@@ -732,7 +1050,7 @@ pub fn compute_write_data_to_body_hash(recipient: &str, entries_hex: &[String])
     let entry_hash = if entries_hex.is_empty() {
         [0u8; 32]
     } else {
-        compute_data_entry_hash(entries_hex)
+        compute_data_entry_hash(entries_hex, double_hash)
     };
 
     // Merkle hash of [bodyPartHash, entryHash]
@@ -763,7 +1081,8 @@ fn marshal_write_data_to_body_without_entry(recipient: &str) -> Vec<u8> {
 ///
 /// Based on Go: protocol/data_entry.go
 /// DoubleHashDataEntry: SHA256(MerkleHash(SHA256(data1), SHA256(data2), ...))
-fn compute_data_entry_hash(entries_hex: &[String]) -> [u8; 32] {
+/// AccumulateDataEntry: MerkleHash(SHA256(data1), SHA256(data2), ...)
+pub(crate) fn compute_data_entry_hash(entries_hex: &[String], double_hash: bool) -> [u8; 32] {
     if entries_hex.is_empty() {
         return [0u8; 32];
     }
@@ -782,6 +1101,9 @@ fn compute_data_entry_hash(entries_hex: &[String]) -> [u8; 32] {
 
     // Compute Merkle hash of data hashes
     let merkle_root = merkle_hash(&data_hashes);
+    if !double_hash {
+        return merkle_root;
+    }
 
     // For DoubleHash: return SHA256(merkleRoot)
     sha256_bytes(&merkle_root)
@@ -917,9 +1239,9 @@ pub fn marshal_key_page_operation(
 
     match op_type {
         "add" | "remove" => {
-            // Field 2: Entry (KeySpecParams)
-            if let Some(hash) = key_hash {
-                let entry_bytes = marshal_key_spec_params(hash, delegate);
+            // Field 2: Entry (KeySpecParams), a key hash and/or a delegate
+            if key_hash.is_some() || delegate.is_some() {
+                let entry_bytes = marshal_key_spec_params(key_hash.unwrap_or_default(), delegate);
                 let _ = writer.write_uvarint(2);
                 let _ = writer.write_uvarint(entry_bytes.len() as u64);
                 let _ = writer.write_bytes(&entry_bytes);
@@ -948,17 +1270,40 @@ pub fn marshal_key_page_operation(
                 let _ = writer.write_uvarint(thresh);
             }
         }
-        "updateAllowed" => {
-            // TODO: Implement Allow/Deny arrays when needed
-            // Field 2: Allow (repeated TransactionType)
-            // Field 3: Deny (repeated TransactionType)
-        }
+        // Allow/Deny lists are written by marshal_update_allowed_operation
         _ => {}
     }
 
     writer.into_bytes()
 }
 
+/// Marshal an UpdateAllowedKeyPageOperation to binary format
+///
+/// - Field 1: Type (enum, 5)
+/// - Field 2: Allow (repeated TransactionType)
+/// - Field 3: Deny (repeated TransactionType)
+pub fn marshal_update_allowed_operation(allow: &[u64], deny: &[u64]) -> Vec<u8> {
+    let mut writer = BinaryWriter::new();
+
+    // Field 1: Type (UpdateAllowed = 5)
+    let _ = writer.write_uvarint(1);
+    let _ = writer.write_uvarint(key_page_op_types::UPDATE_ALLOWED);
+
+    // Field 2: Allow
+    for tx_type in allow {
+        let _ = writer.write_uvarint(2);
+        let _ = writer.write_uvarint(*tx_type);
+    }
+
+    // Field 3: Deny
+    for tx_type in deny {
+        let _ = writer.write_uvarint(3);
+        let _ = writer.write_uvarint(*tx_type);
+    }
+
+    writer.into_bytes()
+}
+
 /// Marshal UpdateKeyPage transaction body to binary format
 ///
 /// Field order matches Go: protocol/user_transactions.yml UpdateKeyPage
@@ -1027,13 +1372,46 @@ pub fn marshal_burn_tokens_body(amount: u64) -> Vec<u8> {
     writer.into_bytes()
 }
 
+/// Marshal RemoteTransaction transaction body to binary format
+///
+/// Field order matches Go: protocol/transaction.yml RemoteTransaction
+/// - Field 1: Type (enum, 0x30)
+/// - Field 2: Hash (32 bytes, no length prefix)
+///
+/// A remote body references an existing transaction by hash. It is used to
+/// submit signature-only envelopes for a transaction that is already pending.
+pub fn marshal_remote_transaction_body(hash: &[u8; 32]) -> Vec<u8> {
+    let mut writer = BinaryWriter::new();
+
+    // Field 1: Type (Remote = 0x30)
+    let _ = writer.write_uvarint(1);
+    let _ = writer.write_uvarint(tx_types::REMOTE);
+
+    // Field 2: Hash (fixed 32 bytes, omitted when zero)
+    if hash.iter().any(|b| *b != 0) {
+        let _ = writer.write_hash_field(hash, 2);
+    }
+
+    writer.into_bytes()
+}
+
 /// Marshal CreateKeyBook transaction body to binary format
 ///
 /// Field order matches Go: protocol/user_transactions.yml CreateKeyBook
 /// - Field 1: Type (enum, 0x0D)
 /// - Field 2: Url (URL as string)
 /// - Field 3: PublicKeyHash (bytes)
+/// - Field 5: Authorities (repeated URLs)
 pub fn marshal_create_key_book_body(url: &str, public_key_hash: &[u8]) -> Vec<u8> {
+    marshal_create_key_book_body_full(url, public_key_hash, &[])
+}
+
+/// Marshal CreateKeyBook transaction body with additional authorities
+pub fn marshal_create_key_book_body_full(
+    url: &str,
+    public_key_hash: &[u8],
+    authorities: &[&str],
+) -> Vec<u8> {
     let mut writer = BinaryWriter::new();
 
     // Field 1: Type (CreateKeyBook = 0x0D)
@@ -1053,6 +1431,9 @@ pub fn marshal_create_key_book_body(url: &str, public_key_hash: &[u8]) -> Vec<u8
         let _ = writer.write_bytes(public_key_hash);
     }
 
+    // Field 5: Authorities
+    write_authorities(&mut writer, 5, authorities);
+
     writer.into_bytes()
 }
 
@@ -1251,6 +1632,132 @@ fn marshal_account_auth_operation(op_type: &str, authority_url: &str) -> Vec<u8>
     writer.into_bytes()
 }
 
+/// Marshal AcmeFaucet transaction body to binary format
+///
+/// Field order matches Go: protocol/user_transactions.yml AcmeFaucet
+/// - Field 1: Type (enum, 0x07)
+/// - Field 2: Url (URL as string)
+pub fn marshal_acme_faucet_body(url: &str) -> Vec<u8> {
+    let mut writer = BinaryWriter::new();
+
+    // Field 1: Type (AcmeFaucet = 0x07)
+    let _ = writer.write_uvarint(1);
+    let _ = writer.write_uvarint(tx_types::ACME_FAUCET);
+
+    // Field 2: Url
+    write_url_field(&mut writer, 2, url);
+
+    writer.into_bytes()
+}
+
+/// Marshal CreateLiteTokenAccount transaction body to binary format
+///
+/// The body has no fields besides its type (enum, 0x0B).
+pub fn marshal_create_lite_token_account_body() -> Vec<u8> {
+    let mut writer = BinaryWriter::new();
+
+    // Field 1: Type (CreateLiteTokenAccount = 0x0B)
+    let _ = writer.write_uvarint(1);
+    let _ = writer.write_uvarint(tx_types::CREATE_LITE_TOKEN_ACCOUNT);
+
+    writer.into_bytes()
+}
+
+/// Marshal ActivateProtocolVersion transaction body to binary format
+///
+/// Field order matches Go: protocol/user_transactions.yml ActivateProtocolVersion
+/// - Field 1: Type (enum, 0x2F)
+/// - Field 2: Version (ExecutorVersion enum)
+pub fn marshal_activate_protocol_version_body(version: u64) -> Vec<u8> {
+    let mut writer = BinaryWriter::new();
+
+    // Field 1: Type (ActivateProtocolVersion = 0x2F)
+    let _ = writer.write_uvarint(1);
+    let _ = writer.write_uvarint(tx_types::ACTIVATE_PROTOCOL_VERSION);
+
+    // Field 2: Version
+    if version > 0 {
+        let _ = writer.write_uvarint(2);
+        let _ = writer.write_uvarint(version);
+    }
+
+    writer.into_bytes()
+}
+
+/// Marshal NetworkMaintenance transaction body to binary format
+///
+/// Field order matches Go: protocol/user_transactions.yml NetworkMaintenance
+/// - Field 1: Type (enum, 0x2E)
+/// - Field 2: Operations (repeated NetworkMaintenanceOperation)
+///
+/// Each operation is (type, account); the only operation type is
+/// PendingTransactionGC, whose Account is field 2.
+pub fn marshal_network_maintenance_body(operations: &[(u64, &str)]) -> Vec<u8> {
+    let mut writer = BinaryWriter::new();
+
+    // Field 1: Type (NetworkMaintenance = 0x2E)
+    let _ = writer.write_uvarint(1);
+    let _ = writer.write_uvarint(tx_types::NETWORK_MAINTENANCE);
+
+    // Field 2: Operations (repeated, each as nested value)
+    for (op_type, account) in operations {
+        let mut op = BinaryWriter::new();
+        let _ = op.write_uvarint(1);
+        let _ = op.write_uvarint(*op_type);
+        write_url_field(&mut op, 2, account);
+        let op_bytes = op.into_bytes();
+
+        let _ = writer.write_uvarint(2);
+        let _ = writer.write_uvarint(op_bytes.len() as u64);
+        let _ = writer.write_bytes(&op_bytes);
+    }
+
+    writer.into_bytes()
+}
+
+/// Marshal SystemGenesis transaction body to binary format
+///
+/// The body has no fields besides its type (enum, 0x60).
+pub fn marshal_system_genesis_body() -> Vec<u8> {
+    let mut writer = BinaryWriter::new();
+
+    // Field 1: Type (SystemGenesis = 0x60)
+    let _ = writer.write_uvarint(1);
+    let _ = writer.write_uvarint(tx_types::SYSTEM_GENESIS);
+
+    writer.into_bytes()
+}
+
+/// Marshal SystemWriteData transaction body to binary format
+///
+/// Field order matches Go: protocol/system.yml SystemWriteData
+/// - Field 1: Type (enum, 0x63)
+/// - Field 2: Entry (nested DataEntry)
+/// - Field 3: WriteToState (bool, optional)
+pub fn marshal_system_write_data_body(entries_hex: &[String], write_to_state: bool) -> Vec<u8> {
+    let mut writer = BinaryWriter::new();
+
+    // Field 1: Type (SystemWriteData = 0x63)
+    let _ = writer.write_uvarint(1);
+    let _ = writer.write_uvarint(tx_types::SYSTEM_WRITE_DATA);
+
+    // Field 2: Entry (nested DataEntry)
+    if !entries_hex.is_empty() {
+        let entry_bytes = marshal_data_entry(entries_hex);
+        let _ = writer.write_uvarint(2);
+        let _ = writer.write_uvarint(entry_bytes.len() as u64);
+        let _ = writer.write_bytes(&entry_bytes);
+    }
+
+    // Field 3: WriteToState (only if true)
+    if write_to_state {
+        let _ = writer.write_uvarint(3);
+        let _ = writer.write_uvarint(1);
+    }
+
+    writer.into_bytes()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1282,6 +1789,36 @@ mod tests {
         assert_eq!(hash, hash2);
     }
 
+    #[test]
+    fn test_delegated_signature_metadata_hash() {
+        let key = marshal_signature_metadata(
+            signature_types::ED25519,
+            &[1u8; 32],
+            "acc://bob.acme/book/1",
+            1,
+            42,
+            0,
+            None,
+            None,
+        );
+        let none: [&str; 0] = [];
+        assert_eq!(compute_delegated_signature_metadata_hash(&key, &none), sha256_bytes(&key));
+
+        let wrapped = marshal_delegated_signature_metadata(&key, "acc://alice.acme/book/1");
+        let mut expected = vec![1, 7, 2, key.len() as u8];
+        expected.extend_from_slice(&key);
+        expected.extend_from_slice(&[3, 23]);
+        expected.extend_from_slice(b"acc://alice.acme/book/1");
+        assert_eq!(wrapped, expected);
+
+        // Later delegators wrap earlier ones
+        let twice = marshal_delegated_signature_metadata(&wrapped, "acc://corp.acme/book/1");
+        assert_eq!(
+            compute_delegated_signature_metadata_hash(&key, &["acc://alice.acme/book/1", "acc://corp.acme/book/1"]),
+            sha256_bytes(&twice)
+        );
+    }
+
     #[test]
     fn test_transaction_hash() {
         let header = b"test header";
@@ -1313,4 +1850,16 @@ mod tests {
         assert_eq!(amount_to_bigint_bytes(256), vec![1, 0]);
         assert_eq!(amount_to_bigint_bytes(0x123456), vec![0x12, 0x34, 0x56]);
     }
+
+    #[test]
+    fn test_remote_transaction_body() {
+        let hash = [0xabu8; 32];
+        let bytes = marshal_remote_transaction_body(&hash);
+
+        assert_eq!(&bytes[..4], &[0x01, 0x30, 0x02, 0xab]);
+        assert_eq!(bytes.len(), 3 + 32);
+
+        // A zero hash is omitted entirely, like any other zero-valued field
+        assert_eq!(marshal_remote_transaction_body(&[0u8; 32]), vec![0x01, 0x30]);
+    }
 }
@@ -0,0 +1,571 @@
+//! Binary decoding of transaction headers and bodies
+//!
+//! The inverse of the `marshal_*` functions in [`super::signing`]: each
+//! decoder reads the Go field layout with a [`BinaryReader`] and produces the
+//! JSON form those functions take, so a payload fetched from the network can
+//! be inspected, re-encoded and compared byte for byte:
+//!
+//! ```
+//! use accumulate_client::codec::signing::marshal_burn_credits_body;
+//! use accumulate_client::codec::unmarshal::TransactionBodyCodec;
+//!
+//! let bytes = marshal_burn_credits_body(250);
+//! let body = TransactionBodyCodec::decode(&bytes).unwrap();
+//! assert_eq!(body, serde_json::json!({"type": "burnCredits", "amount": 250}));
+//! assert_eq!(TransactionBodyCodec::encode(&body).unwrap(), bytes);
+//! ```
+//!
+//! Unknown field numbers are an error rather than skipped: the encoding does
+//! not say how a field is framed, so skipping could silently misread the rest.
+
+use super::reader::{BinaryReader, DecodingError};
+use super::signing::{
+    account_auth_op_types, executor_versions, key_page_op_types, network_maintenance_op_types,
+    tx_types,
+};
+use crate::json_rpc_client::JsonRpcError;
+use serde_json::{json, Map, Value};
+
+/// Decode a binary transaction header
+///
+/// Field order matches Go: protocol/types_gen.go TransactionHeader
+/// - Field 1: Principal (URL)
+/// - Field 2: Initiator (hash, no length prefix)
+/// - Field 3: Memo (string)
+/// - Field 4: Metadata (bytes)
+/// - Field 5: Expire (nested ExpireOptions: field 1 = atTime, varint seconds)
+/// - Field 6: HoldUntil (nested HoldUntilOptions: field 1 = minorBlock)
+/// - Field 7: Authorities (repeated URL)
+pub fn unmarshal_transaction_header(data: &[u8]) -> Result<Value, DecodingError> {
+    decode_fields(data, |field, reader, out| {
+        match field {
+            1 => put(out, "principal", reader.read_string()?),
+            2 => put(out, "initiator", hex::encode(reader.read_hash()?)),
+            3 => put(out, "memo", reader.read_string()?),
+            4 => put(out, "metadata", read_hex(reader)?),
+            5 => {
+                let expire = decode_nested(reader, |field, reader, out| {
+                    if field != 1 {
+                        return Err(DecodingError::UnexpectedField(field));
+                    }
+                    put(out, "atTime", reader.read_varint()?);
+                    Ok(())
+                })?;
+                put(out, "expire", expire);
+            }
+            6 => {
+                let hold = decode_nested(reader, |field, reader, out| {
+                    if field != 1 {
+                        return Err(DecodingError::UnexpectedField(field));
+                    }
+                    put(out, "minorBlock", reader.read_uvarint()?);
+                    Ok(())
+                })?;
+                put(out, "holdUntil", hold);
+            }
+            7 => push(out, "authorities", reader.read_string()?),
+            other => return Err(DecodingError::UnexpectedField(other)),
+        }
+        Ok(())
+    })
+}
+
+/// Decode a binary transaction body
+///
+/// The type comes from field 1; the remaining fields follow the Go layout of
+/// that type. Anchor and synthetic bodies are not supported.
+pub fn unmarshal_transaction_body(data: &[u8]) -> Result<Value, DecodingError> {
+    let mut reader = BinaryReader::new(data);
+    if !reader.read_field(1)? {
+        return Err(DecodingError::UnexpectedEof);
+    }
+    let tx_type = reader.read_uvarint()?;
+    let name = tx_types::name(tx_type).ok_or(DecodingError::UnknownType(tx_type))?;
+
+    let rest = reader.read_remaining();
+    let mut body = decode_fields(rest, |field, reader, out| {
+        decode_body_field(tx_type, field, reader, out)
+    })?;
+    if let Value::Object(fields) = &mut body {
+        fields.insert("type".to_string(), json!(name));
+    }
+    Ok(body)
+}
+
+/// Decode a binary transaction (field 1: header, field 2: body)
+pub fn unmarshal_transaction(data: &[u8]) -> Result<Value, DecodingError> {
+    decode_fields(data, |field, reader, out| {
+        match field {
+            1 => put(
+                out,
+                "header",
+                unmarshal_transaction_header(reader.read_bytes_with_length()?)?,
+            ),
+            2 => put(
+                out,
+                "body",
+                unmarshal_transaction_body(reader.read_bytes_with_length()?)?,
+            ),
+            other => return Err(DecodingError::UnexpectedField(other)),
+        }
+        Ok(())
+    })
+}
+
+/// Binary encoding and decoding of JSON transaction bodies
+///
+/// `encode` is the marshalling the signer uses to compute transaction hashes;
+/// `decode` is [`unmarshal_transaction_body`].
+#[derive(Debug, Clone, Copy)]
+pub struct TransactionBodyCodec;
+
+impl TransactionBodyCodec {
+    /// Binary encoding of a JSON body
+    pub fn encode(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
+        crate::helpers::marshal_body_to_binary(body)
+    }
+
+    /// JSON body from its binary encoding
+    pub fn decode(data: &[u8]) -> Result<Value, DecodingError> {
+        unmarshal_transaction_body(data)
+    }
+}
+
+/// Decode field `field` of a body of type `tx_type`
+///
+/// Field numbers match Go: protocol/user_transactions.yml
+#[allow(clippy::too_many_lines)]
+fn decode_body_field(
+    tx_type: u64,
+    field: u32,
+    reader: &mut BinaryReader<'_>,
+    out: &mut Map<String, Value>,
+) -> Result<(), DecodingError> {
+    use tx_types::{
+        ACME_FAUCET, ACTIVATE_PROTOCOL_VERSION, ADD_CREDITS, BURN_CREDITS, BURN_TOKENS,
+        CREATE_DATA_ACCOUNT, CREATE_IDENTITY, CREATE_KEY_BOOK, CREATE_KEY_PAGE, CREATE_TOKEN,
+        CREATE_TOKEN_ACCOUNT, ISSUE_TOKENS, LOCK_ACCOUNT, NETWORK_MAINTENANCE, REMOTE, SEND_TOKENS,
+        SYSTEM_WRITE_DATA, TRANSFER_CREDITS, UPDATE_ACCOUNT_AUTH, UPDATE_KEY, UPDATE_KEY_PAGE,
+        WRITE_DATA, WRITE_DATA_TO,
+    };
+
+    match (tx_type, field) {
+        (
+            CREATE_IDENTITY | CREATE_TOKEN_ACCOUNT | CREATE_DATA_ACCOUNT | ACME_FAUCET
+            | CREATE_TOKEN | CREATE_KEY_BOOK,
+            2,
+        ) => {
+            put(out, "url", reader.read_string()?);
+        }
+        (CREATE_IDENTITY, 3) => put(out, "keyHash", read_hex(reader)?),
+        (CREATE_IDENTITY, 4) => put(out, "keyBookUrl", reader.read_string()?),
+        (CREATE_IDENTITY | CREATE_KEY_BOOK, 5)
+        | (CREATE_TOKEN_ACCOUNT, 4)
+        | (CREATE_DATA_ACCOUNT, 3)
+        | (CREATE_TOKEN, 9) => push(out, "authorities", reader.read_string()?),
+        (CREATE_TOKEN_ACCOUNT, 3) => put(out, "tokenUrl", reader.read_string()?),
+
+        (SEND_TOKENS | REMOTE, 2) => put(out, "hash", hex::encode(reader.read_hash()?)),
+        (SEND_TOKENS, 3) => {
+            let meta = reader.read_bytes_with_length()?;
+            let meta: Value =
+                serde_json::from_slice(meta).map_err(|_| DecodingError::InvalidUtf8)?;
+            put(out, "meta", meta);
+        }
+        (SEND_TOKENS | ISSUE_TOKENS, 4) => {
+            let recipient = decode_nested(reader, |field, reader, out| {
+                match field {
+                    1 => put(out, "url", reader.read_string()?),
+                    2 => put(out, "amount", read_big_number(reader)?),
+                    other => return Err(DecodingError::UnexpectedField(other)),
+                }
+                Ok(())
+            })?;
+            push(out, "to", recipient);
+        }
+        (ISSUE_TOKENS | ADD_CREDITS | WRITE_DATA_TO, 2) => {
+            put(out, "recipient", reader.read_string()?);
+        }
+        (ISSUE_TOKENS | ADD_CREDITS, 3) | (BURN_TOKENS, 2) => {
+            put(out, "amount", read_big_number(reader)?);
+        }
+        (ADD_CREDITS, 4) => put(out, "oracle", reader.read_uvarint()?),
+
+        (WRITE_DATA | SYSTEM_WRITE_DATA, 2) | (WRITE_DATA_TO, 3) => {
+            put(out, "entry", decode_data_entry(reader)?);
+        }
+        (WRITE_DATA, 3) => put(out, "scratch", reader.read_bool()?),
+        (WRITE_DATA, 4) | (SYSTEM_WRITE_DATA, 3) => put(out, "writeToState", reader.read_bool()?),
+
+        (CREATE_TOKEN, 4) => put(out, "symbol", reader.read_string()?),
+        (CREATE_TOKEN, 5) => put(out, "precision", reader.read_uvarint()?),
+        (CREATE_TOKEN, 6) => put(out, "properties", reader.read_string()?),
+        (CREATE_TOKEN, 7) => put(out, "supplyLimit", read_big_number(reader)?),
+
+        (CREATE_KEY_PAGE, 2) => push(out, "keys", decode_key_spec(reader)?),
+        (CREATE_KEY_BOOK, 3) => put(out, "publicKeyHash", read_hex(reader)?),
+        (UPDATE_KEY_PAGE, 2) => push(out, "operation", decode_key_page_operation(reader)?),
+        (UPDATE_KEY, 2) => put(out, "newKeyHash", read_hex(reader)?),
+
+        (LOCK_ACCOUNT, 2) => put(out, "height", reader.read_uvarint()?),
+        (BURN_CREDITS, 2) => put(out, "amount", reader.read_uvarint()?),
+        (TRANSFER_CREDITS, 2) => {
+            let recipient = decode_nested(reader, |field, reader, out| {
+                match field {
+                    1 => put(out, "url", reader.read_string()?),
+                    2 => put(out, "amount", reader.read_uvarint()?),
+                    other => return Err(DecodingError::UnexpectedField(other)),
+                }
+                Ok(())
+            })?;
+            push(out, "to", recipient);
+        }
+        (UPDATE_ACCOUNT_AUTH, 2) => {
+            let operation = decode_nested(reader, |field, reader, out| {
+                match field {
+                    1 => put(out, "type", account_auth_op_name(reader.read_uvarint()?)),
+                    2 => put(out, "authority", reader.read_string()?),
+                    other => return Err(DecodingError::UnexpectedField(other)),
+                }
+                Ok(())
+            })?;
+            push(out, "operations", operation);
+        }
+        (ACTIVATE_PROTOCOL_VERSION, 2) => {
+            let version = reader.read_uvarint()?;
+            let name =
+                executor_versions::name(version).ok_or(DecodingError::UnknownType(version))?;
+            put(out, "version", name);
+        }
+        (NETWORK_MAINTENANCE, 2) => {
+            let operation = decode_nested(reader, |field, reader, out| {
+                match field {
+                    1 => match reader.read_uvarint()? {
+                        network_maintenance_op_types::PENDING_TRANSACTION_GC => {
+                            put(out, "type", "pendingTransactionGC");
+                        }
+                        other => return Err(DecodingError::UnknownType(other)),
+                    },
+                    2 => put(out, "account", reader.read_string()?),
+                    other => return Err(DecodingError::UnexpectedField(other)),
+                }
+                Ok(())
+            })?;
+            push(out, "operations", operation);
+        }
+        (_, other) => return Err(DecodingError::UnexpectedField(other)),
+    }
+    Ok(())
+}
+
+/// Decode a nested DataEntry (field 1: type, field 2: repeated data)
+fn decode_data_entry(reader: &mut BinaryReader<'_>) -> Result<Value, DecodingError> {
+    decode_nested(reader, |field, reader, out| {
+        match field {
+            1 => {
+                let entry_type = match reader.read_uvarint()? {
+                    2 => "accumulate",
+                    3 => "doublehash",
+                    other => return Err(DecodingError::UnknownType(other)),
+                };
+                put(out, "type", entry_type);
+            }
+            2 => push(out, "data", read_hex(reader)?),
+            other => return Err(DecodingError::UnexpectedField(other)),
+        }
+        Ok(())
+    })
+}
+
+/// Decode a nested KeySpecParams (field 1: keyHash, field 2: delegate)
+fn decode_key_spec(reader: &mut BinaryReader<'_>) -> Result<Value, DecodingError> {
+    decode_nested(reader, |field, reader, out| {
+        match field {
+            1 => put(out, "keyHash", read_hex(reader)?),
+            2 => put(out, "delegate", reader.read_string()?),
+            other => return Err(DecodingError::UnexpectedField(other)),
+        }
+        Ok(())
+    })
+}
+
+/// Decode a nested KeyPageOperation
+///
+/// Field layout per operation type matches [`super::signing::marshal_key_page_operation`].
+fn decode_key_page_operation(reader: &mut BinaryReader<'_>) -> Result<Value, DecodingError> {
+    let mut op_type = key_page_op_types::UNKNOWN;
+    decode_nested(reader, |field, reader, out| {
+        match (op_type, field) {
+            (_, 1) => {
+                op_type = reader.read_uvarint()?;
+                put(out, "type", key_page_op_name(op_type)?);
+            }
+            (key_page_op_types::ADD | key_page_op_types::REMOVE, 2) => {
+                put(out, "entry", decode_key_spec(reader)?);
+            }
+            (key_page_op_types::UPDATE, 2) => put(out, "oldEntry", decode_key_spec(reader)?),
+            (key_page_op_types::UPDATE, 3) => put(out, "newEntry", decode_key_spec(reader)?),
+            (
+                key_page_op_types::SET_THRESHOLD
+                | key_page_op_types::SET_REJECT_THRESHOLD
+                | key_page_op_types::SET_RESPONSE_THRESHOLD,
+                2,
+            ) => put(out, "threshold", reader.read_uvarint()?),
+            (key_page_op_types::UPDATE_ALLOWED, 2) => push(out, "allow", read_tx_type(reader)?),
+            (key_page_op_types::UPDATE_ALLOWED, 3) => push(out, "deny", read_tx_type(reader)?),
+            (_, other) => return Err(DecodingError::UnexpectedField(other)),
+        }
+        Ok(())
+    })
+}
+
+const fn key_page_op_name(op_type: u64) -> Result<&'static str, DecodingError> {
+    Ok(match op_type {
+        key_page_op_types::UPDATE => "update",
+        key_page_op_types::REMOVE => "remove",
+        key_page_op_types::ADD => "add",
+        key_page_op_types::SET_THRESHOLD => "setThreshold",
+        key_page_op_types::UPDATE_ALLOWED => "updateAllowed",
+        key_page_op_types::SET_REJECT_THRESHOLD => "setRejectThreshold",
+        key_page_op_types::SET_RESPONSE_THRESHOLD => "setResponseThreshold",
+        other => return Err(DecodingError::UnknownType(other)),
+    })
+}
+
+const fn account_auth_op_name(op_type: u64) -> &'static str {
+    match op_type {
+        account_auth_op_types::ENABLE => "enable",
+        account_auth_op_types::DISABLE => "disable",
+        account_auth_op_types::ADD_AUTHORITY => "addAuthority",
+        account_auth_op_types::REMOVE_AUTHORITY => "removeAuthority",
+        _ => "unknown",
+    }
+}
+
+/// Read every field of `data` into a JSON object
+fn decode_fields(
+    data: &[u8],
+    mut on_field: impl FnMut(
+        u32,
+        &mut BinaryReader<'_>,
+        &mut Map<String, Value>,
+    ) -> Result<(), DecodingError>,
+) -> Result<Value, DecodingError> {
+    let mut reader = BinaryReader::new(data);
+    let mut out = Map::new();
+    while reader.peek_field_number()?.is_some() {
+        let field = reader.read_field_number()?;
+        on_field(field, &mut reader, &mut out)?;
+    }
+    Ok(Value::Object(out))
+}
+
+/// Read a length-prefixed nested value into a JSON object
+fn decode_nested(
+    reader: &mut BinaryReader<'_>,
+    on_field: impl FnMut(
+        u32,
+        &mut BinaryReader<'_>,
+        &mut Map<String, Value>,
+    ) -> Result<(), DecodingError>,
+) -> Result<Value, DecodingError> {
+    decode_fields(reader.read_bytes_with_length()?, on_field)
+}
+
+fn put(out: &mut Map<String, Value>, key: &str, value: impl Into<Value>) {
+    out.insert(key.to_string(), value.into());
+}
+
+/// Append to the array at `key`, for repeated fields
+fn push(out: &mut Map<String, Value>, key: &str, value: impl Into<Value>) {
+    if let Some(Value::Array(items)) = out.get_mut(key) {
+        items.push(value.into());
+    } else {
+        out.insert(key.to_string(), Value::Array(vec![value.into()]));
+    }
+}
+
+/// Transaction types are named in JSON; unknown numbers are kept as numbers
+fn read_tx_type(reader: &mut BinaryReader<'_>) -> Result<Value, DecodingError> {
+    let tx_type = reader.read_uvarint()?;
+    Ok(tx_types::name(tx_type).map_or_else(|| json!(tx_type), |name| json!(name)))
+}
+
+fn read_hex(reader: &mut BinaryReader<'_>) -> Result<String, DecodingError> {
+    Ok(hex::encode(reader.read_bytes_with_length()?))
+}
+
+/// Big integers are decimal strings in JSON
+fn read_big_number(reader: &mut BinaryReader<'_>) -> Result<String, DecodingError> {
+    Ok(reader.read_big_number()?.to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::codec::signing::*;
+    use crate::helpers::TxBody;
+
+    #[test]
+    fn test_marshalled_bodies_roundtrip() {
+        let key = [5u8; 32];
+        let entries = vec![hex::encode(b"hello"), hex::encode(b"world")];
+        let update_op =
+            marshal_key_page_operation("update", None, None, Some(&key), Some(&[6u8; 32]), None);
+        let threshold_op =
+            marshal_key_page_operation("setRejectThreshold", None, None, None, None, Some(2));
+        let bodies = [
+            marshal_add_credits_body("acc://alice.acme/book/1", 1_000_000, 5000),
+            marshal_send_tokens_body(&[
+                ("acc://bob.acme/tokens".to_string(), 150),
+                ("acc://carol.acme/tokens".to_string(), u64::MAX),
+            ]),
+            marshal_create_identity_body("acc://alice.acme", &key, "acc://alice.acme/book"),
+            marshal_create_data_account_body("acc://alice.acme/data"),
+            marshal_write_data_body(&entries, true, false),
+            marshal_create_token_account_body("acc://alice.acme/tokens", "acc://ACME"),
+            marshal_create_token_body("acc://alice.acme/foo", "FOO", 4, Some(1_000)),
+            marshal_issue_tokens_body(&[("acc://bob.acme/foo", 10)]),
+            marshal_update_key_page_body(&[update_op, threshold_op]),
+            marshal_create_key_page_body(&[key.to_vec()]),
+            marshal_burn_tokens_body(7),
+            marshal_remote_transaction_body(&key),
+            marshal_create_key_book_body("acc://alice.acme/book2", &key),
+            marshal_update_key_body(&key),
+            marshal_burn_credits_body(3),
+            marshal_transfer_credits_body(&[("acc://bob.acme/book/1", 9)]),
+            marshal_write_data_to_body("acc://lite-data", &entries),
+            marshal_lock_account_body(100),
+            marshal_update_account_auth_body(&[("addAuthority", "acc://bob.acme/book")]),
+            marshal_send_tokens_body_full(
+                Some(&key),
+                Some(br#"{"memo":"rent"}"#),
+                &[("acc://bob.acme/tokens".to_string(), 1)],
+            ),
+            marshal_create_identity_body_full(
+                "acc://alice.acme",
+                &key,
+                "acc://alice.acme/book",
+                &["acc://bob.acme/book"],
+            ),
+            marshal_create_token_body_full(
+                "acc://alice.acme/foo",
+                "FOO",
+                4,
+                Some("acc://alice.acme/foo-meta"),
+                None,
+                &["acc://bob.acme/book"],
+            ),
+            marshal_update_key_page_body(&[marshal_update_allowed_operation(
+                &[tx_types::SEND_TOKENS],
+                &[tx_types::WRITE_DATA, tx_types::BURN_TOKENS],
+            )]),
+            marshal_acme_faucet_body("acc://lite"),
+            marshal_create_lite_token_account_body(),
+            marshal_activate_protocol_version_body(executor_versions::V2_VANDENBERG),
+            marshal_network_maintenance_body(&[(
+                network_maintenance_op_types::PENDING_TRANSACTION_GC,
+                "acc://alice.acme/tokens",
+            )]),
+            marshal_system_genesis_body(),
+            marshal_system_write_data_body(&entries, true),
+        ];
+        for bytes in bodies {
+            let body = TransactionBodyCodec::decode(&bytes).unwrap();
+            assert_eq!(
+                TransactionBodyCodec::encode(&body).unwrap(),
+                bytes,
+                "{body}"
+            );
+        }
+
+        let body = TransactionBodyCodec::decode(&marshal_send_tokens_body(&[(
+            "acc://bob.acme/tokens".to_string(),
+            u64::MAX,
+        )]))
+        .unwrap();
+        assert_eq!(body["to"][0]["amount"], u64::MAX.to_string());
+    }
+
+    #[test]
+    fn test_decodes_tx_body_json() {
+        let body = TxBody::update_key_page_add_key(&[9u8; 32]);
+        let bytes = TransactionBodyCodec::encode(&body).unwrap();
+        assert_eq!(TransactionBodyCodec::decode(&bytes).unwrap(), body);
+
+        for body in [
+            TxBody::update_key_page(&json!([
+                {"type": "updateAllowed", "allow": ["sendTokens"], "deny": ["writeData"]}
+            ])),
+            json!({"type": "activateProtocolVersion", "version": "v2Jiuquan"}),
+            json!({"type": "networkMaintenance", "operations": [
+                {"type": "pendingTransactionGC", "account": "acc://alice.acme/tokens"}
+            ]}),
+        ] {
+            let bytes = TransactionBodyCodec::encode(&body).unwrap();
+            assert_eq!(TransactionBodyCodec::decode(&bytes).unwrap(), body);
+        }
+    }
+
+    #[test]
+    fn test_unmarshal_transaction() {
+        let header = marshal_transaction_header_full(
+            "acc://alice.acme/tokens",
+            &[1u8; 32],
+            Some("rent"),
+            Some(&[0xca, 0xfe]),
+            Some(&HeaderBinaryOptions {
+                expire_at_time: Some(1_700_000_000),
+                hold_until_minor_block: Some(42),
+                authorities: Some(vec!["acc://bob.acme/book".to_string()]),
+            }),
+        );
+        let decoded = unmarshal_transaction_header(&header).unwrap();
+        assert_eq!(
+            decoded,
+            json!({
+                "principal": "acc://alice.acme/tokens",
+                "initiator": hex::encode([1u8; 32]),
+                "memo": "rent",
+                "metadata": "cafe",
+                "expire": {"atTime": 1_700_000_000},
+                "holdUntil": {"minorBlock": 42},
+                "authorities": ["acc://bob.acme/book"]
+            })
+        );
+        assert_eq!(
+            crate::helpers::marshal_header_json(&decoded).unwrap(),
+            header
+        );
+
+        let body = marshal_lock_account_body(100);
+        let mut writer = crate::codec::BinaryWriter::new();
+        writer.write_bytes_field(&header, 1).unwrap();
+        writer.write_bytes_field(&body, 2).unwrap();
+        let transaction = unmarshal_transaction(&writer.into_bytes()).unwrap();
+        assert_eq!(
+            transaction["body"],
+            json!({"type": "lockAccount", "height": 100})
+        );
+        assert_eq!(transaction["header"]["memo"], "rent");
+    }
+
+    #[test]
+    fn test_rejects_unknown_fields_and_types() {
+        let mut bytes = marshal_lock_account_body(100);
+        bytes.extend_from_slice(&[3, 1]);
+        assert!(matches!(
+            unmarshal_transaction_body(&bytes),
+            Err(DecodingError::UnexpectedField(3))
+        ));
+        assert!(matches!(
+            unmarshal_transaction_body(&[1, 0x7f]),
+            Err(DecodingError::UnknownType(0x7f))
+        ));
+        assert!(matches!(
+            unmarshal_transaction_body(&marshal_lock_account_body(100)[..3]),
+            Err(DecodingError::UnexpectedEof)
+        ));
+    }
+}
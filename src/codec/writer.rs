@@ -30,6 +30,10 @@ pub enum EncodingError {
     InvalidUtf8,
 }
 
+/// Encoding of a value with no fields set
+/// Matches Go: encoding.EmptyObject
+pub const EMPTY_OBJECT: u8 = 0x80;
+
 /// Binary writer that matches TypeScript SDK encoding exactly
 #[derive(Debug, Clone)]
 pub struct BinaryWriter {
@@ -230,6 +234,79 @@ impl BinaryWriter {
         Ok(())
     }
 
+    /// Encode a signed big integer
+    /// Matches Go: Writer.WriteBigInt, which rejects negative values
+    pub fn write_big_int(&mut self, value: &num_bigint::BigInt) -> Result<(), EncodingError> {
+        let unsigned = value.to_biguint().ok_or(EncodingError::NegativeBigInt)?;
+        self.write_big_number(&unsigned)
+    }
+
+    /// Encode a signed big integer with field number
+    pub fn write_big_int_field(
+        &mut self,
+        value: &num_bigint::BigInt,
+        field: u32,
+    ) -> Result<(), EncodingError> {
+        let mut temp_writer = BinaryWriter::new();
+        temp_writer.write_big_int(value)?;
+        self.write_field(field, temp_writer.bytes())?;
+        Ok(())
+    }
+
+    /// Encode a timestamp as a zigzag varint of whole Unix seconds
+    /// Matches Go: Writer.WriteTime, which drops sub-second precision
+    pub fn write_time(&mut self, value: &chrono::DateTime<chrono::Utc>) -> Result<(), EncodingError> {
+        self.write_varint(value.timestamp())
+    }
+
+    /// Encode a timestamp with field number
+    pub fn write_time_field(
+        &mut self,
+        value: &chrono::DateTime<chrono::Utc>,
+        field: u32,
+    ) -> Result<(), EncodingError> {
+        let mut temp_writer = BinaryWriter::new();
+        temp_writer.write_time(value)?;
+        self.write_field(field, temp_writer.bytes())?;
+        Ok(())
+    }
+
+    /// Encode a duration as two uvarints: whole seconds, then the nanosecond remainder
+    /// Matches Go: Writer.WriteDuration
+    pub fn write_duration(&mut self, value: std::time::Duration) -> Result<(), EncodingError> {
+        self.write_uvarint(value.as_secs())?;
+        self.write_uvarint(u64::from(value.subsec_nanos()))
+    }
+
+    /// Encode a duration with field number
+    pub fn write_duration_field(
+        &mut self,
+        value: std::time::Duration,
+        field: u32,
+    ) -> Result<(), EncodingError> {
+        let mut temp_writer = BinaryWriter::new();
+        temp_writer.write_duration(value)?;
+        self.write_field(field, temp_writer.bytes())?;
+        Ok(())
+    }
+
+    /// Encode a nested value as a length-prefixed field
+    /// Matches Go: Writer.WriteValue
+    ///
+    /// `encode` writes the nested value's fields into a fresh writer. A value
+    /// that writes nothing is framed as [`EMPTY_OBJECT`], as Go does.
+    pub fn write_value_field<F>(&mut self, field: u32, encode: F) -> Result<(), EncodingError>
+    where
+        F: FnOnce(&mut Self) -> Result<(), EncodingError>,
+    {
+        let mut nested = BinaryWriter::new();
+        encode(&mut nested)?;
+        if nested.buffer.is_empty() {
+            nested.buffer.push(EMPTY_OBJECT);
+        }
+        self.write_bytes_field(nested.bytes(), field)
+    }
+
     /// Write an optional value (None = skip, Some = encode)
     pub fn write_optional<T, F>(
         &mut self,
@@ -406,4 +483,43 @@ mod tests {
         let invalid_hash = [0u8; 31];
         assert!(writer.write_hash_bytes(&invalid_hash).is_err());
     }
+
+    #[test]
+    fn test_go_primitive_vectors() {
+        use chrono::TimeZone;
+
+        let mut writer = BinaryWriter::new();
+        let time = chrono::Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        writer.write_time_field(&time, 1).unwrap();
+        writer
+            .write_duration_field(std::time::Duration::from_millis(1500), 2)
+            .unwrap();
+        writer
+            .write_big_int_field(&num_bigint::BigInt::from(1_000_000), 3)
+            .unwrap();
+        writer
+            .write_value_field(4, |w| w.write_uvarint_field(7, 1))
+            .unwrap();
+        writer.write_value_field(5, |_| Ok(())).unwrap();
+
+        assert_eq!(
+            hex::encode(writer.bytes()),
+            concat!(
+                "01808290d90c",   // time: zigzag(1704067200)
+                "020180cab5ee01", // duration: 1s, 500000000ns
+                "03030f4240",     // big int: length-prefixed big-endian
+                "04020107",       // nested value {1: 7}
+                "050180",         // nested value with no fields
+            )
+        );
+    }
+
+    #[test]
+    fn test_negative_big_int_rejected() {
+        let mut writer = BinaryWriter::new();
+        assert!(matches!(
+            writer.write_big_int(&num_bigint::BigInt::from(-1)),
+            Err(EncodingError::NegativeBigInt)
+        ));
+    }
 }
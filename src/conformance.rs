@@ -0,0 +1,860 @@
+//! Cross-SDK conformance harness
+//!
+//! Runs golden fixtures produced by another Accumulate SDK (TypeScript, Go,
+//! Dart) through this SDK and reports every mismatch, so an implementation
+//! can check its output against the Rust SDK from its own CI:
+//!
+//! ```text
+//! let report = accumulate_client::conformance::run_dir("fixtures/")?;
+//! assert!(report.is_success(), "{report}");
+//! ```
+//!
+//! Each `.json` file under the directory is classified by shape; files that
+//! match no shape are counted as skipped. Recognized shapes:
+//!
+//! | Check | Shape |
+//! |---|---|
+//! | canonical JSON | `{"input": …, "canonical_json": "…"}`, or `{"testCases": [{"name", "input", "expectedCanonical"}]}`; a string `input` in test cases is raw JSON text checked with RFC 8785 (JCS) rules |
+//! | hash | `{"input": "<hex>", "hash": "<hex>"}` (SHA-256 of the bytes), or `{"input_header": …, "canonical_json", "hash"}` (SHA-256 of the canonical JSON) |
+//! | signing | `{"vectors": [{"name", "privateKey", "publicKey", "transaction", "canonicalJSON", "txHash", "signature"}]}`; the signature is Ed25519 over the transaction hash, and an all-zero signature is not checked |
+//! | signature | `{"type": "ed25519", "publicKey", "signature", "message"}`, optionally with `"messageHash"` |
+//! | envelope | `{"transaction": [...], "signatures": [...]}`, which must survive a round trip through [`TransactionEnvelope`] |
+//!
+//! [`TsFixtures::import`] reads the directory the TypeScript SDK's fixture
+//! exporter (`tooling/ts-fixture-exporter`) writes, as is, and turns every
+//! vector in it into its own [`FixtureCase`]. New vectors in a fresh export
+//! become new cases without any change on the Rust side:
+//!
+//! ```text
+//! let fixtures = TsFixtures::import("ts-export/")?;
+//! let report = fixtures.run();
+//! assert!(report.is_success(), "{report}");
+//! ```
+//!
+//! On top of the shapes above it understands the exporter's binary vectors
+//! (a JSON file with `hashes`, `transactions` and `envelopes` arrays) and
+//! its random vectors (`.jsonl`, one `{"hexBin", "canonicalJson",
+//! "txHashHex"}` per line, where `hexBin` is an `ACCU`-framed envelope). The
+//! canonical strings in the binary vectors omit nested members, so only
+//! their hashes are compared.
+//!
+//! Available with the `conformance` feature.
+
+use crate::canonjson::{canonicalize, canonicalize_jcs};
+use crate::codec::BinaryWriter;
+use crate::crypto::ed25519::{sha256, verify, Ed25519Signer};
+use crate::protocol::{normalized_envelope, TransactionEnvelope};
+use serde_json::Value;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors that prevent the harness from running
+#[derive(Error, Debug)]
+pub enum ConformanceError {
+    /// The fixture directory or a file in it could not be read
+    #[error("Cannot read {path}: {reason}")]
+    Io {
+        /// Path that failed
+        path: PathBuf,
+        /// Underlying error
+        reason: String,
+    },
+}
+
+/// The kind of parity a check covers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum CheckKind {
+    /// Canonical JSON encoding
+    CanonicalJson,
+    /// SHA-256 hashing
+    Hash,
+    /// Key derivation, transaction hashing and signing
+    Signing,
+    /// Signature verification
+    Signature,
+    /// Envelope serialization
+    Envelope,
+}
+
+impl fmt::Display for CheckKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::CanonicalJson => "canonical-json",
+            Self::Hash => "hash",
+            Self::Signing => "signing",
+            Self::Signature => "signature",
+            Self::Envelope => "envelope",
+        })
+    }
+}
+
+/// Outcome of one check
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CheckResult {
+    /// What was checked
+    pub kind: CheckKind,
+    /// Fixture file and case name
+    pub name: String,
+    /// Why the check failed; `None` when it passed
+    pub failure: Option<String>,
+}
+
+impl CheckResult {
+    fn new(kind: CheckKind, name: &str, result: Result<(), String>) -> Self {
+        Self {
+            kind,
+            name: name.to_string(),
+            failure: result.err(),
+        }
+    }
+
+    /// Whether the check passed
+    #[must_use]
+    pub const fn passed(&self) -> bool {
+        self.failure.is_none()
+    }
+}
+
+/// Results of a harness run
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ConformanceReport {
+    /// Every check that ran
+    pub results: Vec<CheckResult>,
+    /// Fixture files whose shape was not recognized
+    pub skipped: Vec<PathBuf>,
+}
+
+impl ConformanceReport {
+    /// Whether every check passed (and at least one ran)
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        !self.results.is_empty() && self.results.iter().all(CheckResult::passed)
+    }
+
+    /// The checks that failed
+    pub fn failures(&self) -> impl Iterator<Item = &CheckResult> {
+        self.results.iter().filter(|r| !r.passed())
+    }
+
+    /// Number of checks of `kind` that ran
+    #[must_use]
+    pub fn count(&self, kind: CheckKind) -> usize {
+        self.results.iter().filter(|r| r.kind == kind).count()
+    }
+}
+
+impl fmt::Display for ConformanceReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let failed = self.failures().count();
+        writeln!(
+            f,
+            "{} checks, {} passed, {failed} failed, {} files skipped",
+            self.results.len(),
+            self.results.len() - failed,
+            self.skipped.len()
+        )?;
+        for result in self.failures() {
+            let reason = result.failure.as_deref().unwrap_or_default();
+            writeln!(f, "  [{}] {}: {reason}", result.kind, result.name)?;
+        }
+        Ok(())
+    }
+}
+
+/// Run every fixture under `dir`, recursively
+pub fn run_dir(dir: impl AsRef<Path>) -> Result<ConformanceReport, ConformanceError> {
+    let mut files = Vec::new();
+    collect_files(dir.as_ref(), &["json"], &mut files)?;
+    files.sort();
+
+    let mut report = ConformanceReport::default();
+    for path in files {
+        let io = |e: &dyn fmt::Display| ConformanceError::Io {
+            path: path.clone(),
+            reason: e.to_string(),
+        };
+        let text = std::fs::read_to_string(&path).map_err(|e| io(&e))?;
+        let results = serde_json::from_str::<Value>(&text)
+            .map(|fixture| check_fixture(&path.display().to_string(), &fixture))
+            .unwrap_or_default();
+        if results.is_empty() {
+            report.skipped.push(path);
+        } else {
+            report.results.extend(results);
+        }
+    }
+    Ok(report)
+}
+
+/// Classify one fixture and run the checks for its shape
+///
+/// Returns no results when the shape is not recognized.
+#[must_use]
+pub fn check_fixture(name: &str, fixture: &Value) -> Vec<CheckResult> {
+    let str_field = |key: &str| fixture.get(key).and_then(Value::as_str);
+
+    if let Some(cases) = fixture.get("testCases").and_then(Value::as_array) {
+        return cases
+            .iter()
+            .enumerate()
+            .filter_map(|(i, case)| {
+                let expected = case.get("expectedCanonical")?.as_str()?;
+                let input = case.get("input")?;
+                let case_name = case
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .map_or_else(|| i.to_string(), str::to_string);
+                Some(check_canonical_case(
+                    &format!("{name}#{case_name}"),
+                    input,
+                    expected,
+                ))
+            })
+            .collect();
+    }
+    if let Some(vectors) = fixture.get("vectors").and_then(Value::as_array) {
+        return vectors
+            .iter()
+            .enumerate()
+            .flat_map(|(i, vector)| {
+                let case_name = vector
+                    .get("name")
+                    .and_then(Value::as_str)
+                    .map_or_else(|| i.to_string(), str::to_string);
+                check_signing_vector(&format!("{name}#{case_name}"), vector)
+            })
+            .collect();
+    }
+    if fixture.get("transaction").is_some() && fixture.get("signatures").is_some() {
+        return vec![check_envelope(name, fixture)];
+    }
+    if let (Some(header), Some(canonical)) =
+        (fixture.get("input_header"), str_field("canonical_json"))
+    {
+        let mut results = vec![check_canonical_json(name, header, canonical)];
+        if let Some(hash) = str_field("hash") {
+            results.push(check_hash(name, canonicalize(header).as_bytes(), hash));
+        }
+        return results;
+    }
+    if let (Some(input), Some(canonical)) = (fixture.get("input"), str_field("canonical_json")) {
+        return vec![check_canonical_json(name, input, canonical)];
+    }
+    if let (Some(input), Some(hash)) = (str_field("input"), str_field("hash")) {
+        return vec![match hex::decode(input) {
+            Ok(bytes) => check_hash(name, &bytes, hash),
+            Err(e) => {
+                CheckResult::new(CheckKind::Hash, name, Err(format!("input is not hex: {e}")))
+            }
+        }];
+    }
+    if str_field("type") == Some("ed25519") && str_field("message").is_some() {
+        return check_signature(name, fixture);
+    }
+    Vec::new()
+}
+
+/// Check that `input` canonicalizes to `expected`
+#[must_use]
+pub fn check_canonical_json(name: &str, input: &Value, expected: &str) -> CheckResult {
+    CheckResult::new(
+        CheckKind::CanonicalJson,
+        name,
+        compare("canonical JSON", expected, &canonicalize(input)),
+    )
+}
+
+/// Check that SHA-256 of `input` is the hex digest `expected`
+#[must_use]
+pub fn check_hash(name: &str, input: &[u8], expected: &str) -> CheckResult {
+    CheckResult::new(
+        CheckKind::Hash,
+        name,
+        compare_hex("hash", expected, &sha256(input)),
+    )
+}
+
+/// Check an envelope survives a round trip through [`TransactionEnvelope`]
+///
+/// Both sides are normalized first, so `null` members, hex case and
+/// signature order do not count as differences.
+#[must_use]
+pub fn check_envelope(name: &str, envelope: &Value) -> CheckResult {
+    let result = (|| {
+        let parsed: TransactionEnvelope =
+            serde_json::from_value(envelope.clone()).map_err(|e| format!("does not parse: {e}"))?;
+        let expected = normalized_envelope(envelope).map_err(|e| e.to_string())?;
+        let actual = normalized_envelope(&parsed).map_err(|e| e.to_string())?;
+        compare("envelope", &canonicalize(&expected), &canonicalize(&actual))
+    })();
+    CheckResult::new(CheckKind::Envelope, name, result)
+}
+
+/// Check a transaction signing vector
+///
+/// Produces one result each for key derivation, canonical JSON, the
+/// transaction hash and, when present and non-zero, the signature.
+#[must_use]
+pub fn check_signing_vector(name: &str, vector: &Value) -> Vec<CheckResult> {
+    let field = |key: &str| vector.get(key).and_then(Value::as_str);
+    let mut results = Vec::new();
+    let mut push = |what: &str, result: Result<(), String>| {
+        results.push(CheckResult::new(
+            CheckKind::Signing,
+            &format!("{name} ({what})"),
+            result,
+        ));
+    };
+
+    let signer = field("privateKey")
+        .ok_or_else(|| "missing privateKey".to_string())
+        .and_then(decode_array::<32>)
+        .and_then(|seed| Ed25519Signer::from_seed(&seed).map_err(|e| e.to_string()));
+    match (&signer, field("publicKey")) {
+        (Ok(signer), Some(expected)) => push(
+            "public key",
+            compare_hex("public key", expected, &signer.public_key_bytes()),
+        ),
+        (Err(e), _) => push("public key", Err(e.clone())),
+        (Ok(_), None) => push("public key", Err("missing publicKey".to_string())),
+    }
+
+    let Some(transaction) = vector.get("transaction") else {
+        push("transaction", Err("missing transaction".to_string()));
+        return results;
+    };
+    let canonical = canonicalize(transaction);
+    if let Some(expected) = field("canonicalJSON") {
+        push(
+            "canonical JSON",
+            compare("canonical JSON", expected, &canonical),
+        );
+    }
+    let tx_hash = sha256(canonical.as_bytes());
+    if let Some(expected) = field("txHash") {
+        push(
+            "transaction hash",
+            compare_hex("transaction hash", expected, &tx_hash),
+        );
+    }
+
+    if let (Ok(signer), Some(expected)) = (&signer, field("signature")) {
+        if !expected.bytes().all(|b| b == b'0') {
+            push(
+                "signature",
+                compare_hex("signature", expected, &signer.sign(&tx_hash)),
+            );
+        }
+    }
+    results
+}
+
+/// Check a standalone Ed25519 signature over `message`
+#[must_use]
+pub fn check_signature(name: &str, fixture: &Value) -> Vec<CheckResult> {
+    let field = |key: &str| fixture.get(key).and_then(Value::as_str).unwrap_or_default();
+    let message = field("message").as_bytes();
+    let mut results = Vec::new();
+
+    if let Some(expected) = fixture.get("messageHash").and_then(Value::as_str) {
+        results.push(check_hash(
+            &format!("{name} (message hash)"),
+            message,
+            expected,
+        ));
+    }
+    let verified = decode_array::<32>(field("publicKey")).and_then(|key| {
+        let signature = decode_array::<64>(field("signature"))?;
+        if verify(&key, message, &signature) {
+            Ok(())
+        } else {
+            Err("signature does not verify".to_string())
+        }
+    });
+    results.push(CheckResult::new(CheckKind::Signature, name, verified));
+    results
+}
+
+/// Prefix of the framed binary envelopes in TypeScript random vectors
+const TS_FRAME_MAGIC: &[u8] = b"ACCU";
+
+/// How a [`FixtureCase`] is checked
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Source {
+    /// A fixture file of a shape [`check_fixture`] recognizes
+    Fixture,
+    /// One entry of a `testCases` array
+    CanonicalCase,
+    /// One entry of a signing `vectors` array
+    SigningVector,
+    /// One entry of the binary vectors' `hashes`
+    BinaryHash,
+    /// One entry of the binary vectors' `transactions`
+    BinaryTransaction,
+    /// One entry of the binary vectors' `envelopes`
+    BinaryEnvelope,
+    /// One line of a random vectors `.jsonl` file
+    RandomVector,
+}
+
+/// A test case materialized from a TypeScript SDK fixture export
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixtureCase {
+    /// Fixture file and case name
+    pub name: String,
+    /// The vector as exported
+    pub vector: Value,
+    source: Source,
+}
+
+impl FixtureCase {
+    const fn new(name: String, source: Source, vector: Value) -> Self {
+        Self {
+            name,
+            vector,
+            source,
+        }
+    }
+
+    /// Run the case's checks
+    #[must_use]
+    pub fn run(&self) -> Vec<CheckResult> {
+        let (name, vector) = (self.name.as_str(), &self.vector);
+        match self.source {
+            Source::Fixture => check_fixture(name, vector),
+            Source::CanonicalCase => {
+                let expected = vector
+                    .get("expectedCanonical")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default();
+                let input = vector.get("input").unwrap_or(&Value::Null);
+                vec![check_canonical_case(name, input, expected)]
+            }
+            Source::SigningVector => check_signing_vector(name, vector),
+            Source::BinaryHash => vec![check_binary_hash(name, vector)],
+            Source::BinaryTransaction => vec![check_hashed_string(
+                CheckKind::Hash,
+                name,
+                vector,
+                "canonical_json",
+                "hash",
+            )],
+            Source::BinaryEnvelope => vec![check_hashed_string(
+                CheckKind::Envelope,
+                name,
+                vector,
+                "signing_payload",
+                "transaction_hash",
+            )],
+            Source::RandomVector => check_random_vector(name, vector),
+        }
+    }
+}
+
+/// Test cases imported from a TypeScript SDK fixture export
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TsFixtures {
+    /// One case per exported vector
+    pub cases: Vec<FixtureCase>,
+    /// Files that held no recognized vectors
+    pub skipped: Vec<PathBuf>,
+}
+
+impl TsFixtures {
+    /// Import every `.json` and `.jsonl` file under `dir`, recursively
+    pub fn import(dir: impl AsRef<Path>) -> Result<Self, ConformanceError> {
+        let mut files = Vec::new();
+        collect_files(dir.as_ref(), &["json", "jsonl"], &mut files)?;
+        files.sort();
+
+        let mut fixtures = Self::default();
+        for path in files {
+            let text = std::fs::read_to_string(&path).map_err(|e| ConformanceError::Io {
+                path: path.clone(),
+                reason: e.to_string(),
+            })?;
+            let name = path.display().to_string();
+            let cases = if path.extension().is_some_and(|ext| ext == "jsonl") {
+                random_vector_cases(&name, &text)
+            } else {
+                serde_json::from_str::<Value>(&text)
+                    .map(|fixture| fixture_cases(&name, fixture))
+                    .unwrap_or_default()
+            };
+            if cases.is_empty() {
+                fixtures.skipped.push(path);
+            } else {
+                fixtures.cases.extend(cases);
+            }
+        }
+        Ok(fixtures)
+    }
+
+    /// Run every case
+    #[must_use]
+    pub fn run(&self) -> ConformanceReport {
+        ConformanceReport {
+            results: self.cases.iter().flat_map(FixtureCase::run).collect(),
+            skipped: self.skipped.clone(),
+        }
+    }
+}
+
+fn fixture_cases(name: &str, fixture: Value) -> Vec<FixtureCase> {
+    let mut binary = entry_cases(name, &fixture, "hashes", Source::BinaryHash);
+    binary.extend(entry_cases(
+        name,
+        &fixture,
+        "transactions",
+        Source::BinaryTransaction,
+    ));
+    binary.extend(entry_cases(
+        name,
+        &fixture,
+        "envelopes",
+        Source::BinaryEnvelope,
+    ));
+    if !binary.is_empty() {
+        return binary;
+    }
+    if fixture.get("testCases").is_some() {
+        return entry_cases(name, &fixture, "testCases", Source::CanonicalCase);
+    }
+    if fixture.get("vectors").is_some() {
+        return entry_cases(name, &fixture, "vectors", Source::SigningVector);
+    }
+    // Whatever check_fixture recognizes runs as one case
+    if check_fixture(name, &fixture).is_empty() {
+        return Vec::new();
+    }
+    vec![FixtureCase::new(name.to_string(), Source::Fixture, fixture)]
+}
+
+/// One case per entry of the array `fixture[key]`
+fn entry_cases(name: &str, fixture: &Value, key: &str, source: Source) -> Vec<FixtureCase> {
+    fixture
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(i, entry)| {
+            let case_name = entry
+                .get("name")
+                .and_then(Value::as_str)
+                .map_or_else(|| format!("{key}[{i}]"), str::to_string);
+            FixtureCase::new(format!("{name}#{case_name}"), source, entry.clone())
+        })
+        .collect()
+}
+
+fn random_vector_cases(name: &str, text: &str) -> Vec<FixtureCase> {
+    text.lines()
+        .enumerate()
+        .filter_map(|(line, text)| {
+            let vector: Value = serde_json::from_str(text).ok()?;
+            vector.get("txHashHex")?;
+            let index = vector
+                .pointer("/meta/index")
+                .and_then(Value::as_u64)
+                .map_or(line as u64, |i| i);
+            Some(FixtureCase::new(
+                format!("{name}#{index}"),
+                Source::RandomVector,
+                vector,
+            ))
+        })
+        .collect()
+}
+
+/// Check that a 32-byte `input` encodes to `encoded` and `hex`
+fn check_binary_hash(name: &str, vector: &Value) -> CheckResult {
+    let result = (|| {
+        let input: [u8; 32] = byte_array(vector.get("input"))?
+            .try_into()
+            .map_err(|b: Vec<u8>| format!("expected 32 bytes, got {}", b.len()))?;
+        let mut writer = BinaryWriter::new();
+        writer.write_hash(&input).map_err(|e| e.to_string())?;
+        let actual = writer.into_bytes();
+        let expected = byte_array(vector.get("encoded"))?;
+        compare("encoding", &hex::encode(expected), &hex::encode(&actual))?;
+        let expected_hex = vector
+            .get("hex")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        compare_hex("encoding", expected_hex, &actual)
+    })();
+    CheckResult::new(CheckKind::Hash, name, result)
+}
+
+/// Check that SHA-256 of the string `text` is `{hash}_hex` and the byte
+/// array `hash`
+fn check_hashed_string(
+    kind: CheckKind,
+    name: &str,
+    vector: &Value,
+    text: &str,
+    hash: &str,
+) -> CheckResult {
+    let result = (|| {
+        let text = vector
+            .get(text)
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("missing {text}"))?;
+        let actual = sha256(text.as_bytes());
+        let expected = vector
+            .get(format!("{hash}_hex").as_str())
+            .and_then(Value::as_str)
+            .ok_or_else(|| format!("missing {hash}_hex"))?;
+        compare_hex("hash", expected, &actual)?;
+        compare_hex("hash", &hex::encode(byte_array(vector.get(hash))?), &actual)
+    })();
+    CheckResult::new(kind, name, result)
+}
+
+/// Check a random vector: the framed envelope, the canonical JSON of its
+/// transaction, and the transaction hash
+fn check_random_vector(name: &str, vector: &Value) -> Vec<CheckResult> {
+    let field = |key: &str| vector.get(key).and_then(Value::as_str).unwrap_or_default();
+    let (canonical, tx_hash) = (field("canonicalJson"), field("txHashHex"));
+
+    let envelope = decode_frame(field("hexBin"));
+    let transaction = envelope
+        .as_ref()
+        .map_err(Clone::clone)
+        .and_then(|e| {
+            e.pointer("/transaction/0")
+                .ok_or_else(|| "envelope has no transaction".to_string())
+        })
+        .and_then(|tx| compare("canonical JSON", canonical, &canonicalize(tx)));
+    let signatures = envelope.and_then(|e| {
+        e.get("signatures")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .map(|sig| sig.get("transactionHash").and_then(Value::as_str))
+            .try_for_each(|hash| compare("signed transaction hash", tx_hash, hash.unwrap_or("")))
+    });
+
+    vec![
+        CheckResult::new(CheckKind::Envelope, name, signatures),
+        CheckResult::new(CheckKind::CanonicalJson, name, transaction),
+        check_hash(name, canonical.as_bytes(), tx_hash),
+    ]
+}
+
+/// Decode `ACCU`, a big-endian u32 length and that many bytes of JSON
+fn decode_frame(hex_str: &str) -> Result<Value, String> {
+    let bytes = hex::decode(hex_str).map_err(|e| format!("hexBin is not hex: {e}"))?;
+    let rest = bytes
+        .strip_prefix(TS_FRAME_MAGIC)
+        .ok_or_else(|| "hexBin does not start with ACCU".to_string())?;
+    if rest.len() < 4 {
+        return Err("hexBin is truncated".to_string());
+    }
+    let (len, body) = rest.split_at(4);
+    let len = u32::from_be_bytes([len[0], len[1], len[2], len[3]]) as usize;
+    if body.len() != len {
+        return Err(format!(
+            "frame length {len}, but {} bytes follow",
+            body.len()
+        ));
+    }
+    serde_json::from_slice(body).map_err(|e| format!("framed envelope is not JSON: {e}"))
+}
+
+/// A JSON array of bytes
+fn byte_array(value: Option<&Value>) -> Result<Vec<u8>, String> {
+    value
+        .and_then(Value::as_array)
+        .ok_or_else(|| "expected an array of bytes".to_string())?
+        .iter()
+        .map(|b| {
+            b.as_u64()
+                .and_then(|b| u8::try_from(b).ok())
+                .ok_or_else(|| format!("{b} is not a byte"))
+        })
+        .collect()
+}
+
+fn check_canonical_case(name: &str, input: &Value, expected: &str) -> CheckResult {
+    // Raw JSON text: the fixture exercises JCS number and string handling
+    if let Value::String(text) = input {
+        let result = serde_json::from_str::<Value>(text)
+            .map_err(|e| format!("input is not JSON: {e}"))
+            .and_then(|value| canonicalize_jcs(&value).map_err(|e| e.to_string()))
+            .and_then(|actual| compare("JCS", expected, &actual));
+        return CheckResult::new(CheckKind::CanonicalJson, name, result);
+    }
+    check_canonical_json(name, input, expected)
+}
+
+fn compare(what: &str, expected: &str, actual: &str) -> Result<(), String> {
+    if expected == actual {
+        Ok(())
+    } else {
+        Err(format!(
+            "{what} mismatch: expected {expected}, got {actual}"
+        ))
+    }
+}
+
+fn compare_hex(what: &str, expected: &str, actual: &[u8]) -> Result<(), String> {
+    compare(what, &expected.to_ascii_lowercase(), &hex::encode(actual))
+}
+
+fn decode_array<const N: usize>(hex_str: &str) -> Result<[u8; N], String> {
+    let bytes = hex::decode(hex_str).map_err(|e| format!("invalid hex {hex_str:?}: {e}"))?;
+    bytes
+        .try_into()
+        .map_err(|b: Vec<u8>| format!("expected {N} bytes, got {}", b.len()))
+}
+
+fn collect_files(
+    dir: &Path,
+    extensions: &[&str],
+    files: &mut Vec<PathBuf>,
+) -> Result<(), ConformanceError> {
+    let io = |e: std::io::Error| ConformanceError::Io {
+        path: dir.to_path_buf(),
+        reason: e.to_string(),
+    };
+    for entry in std::fs::read_dir(dir).map_err(io)? {
+        let path = entry.map_err(io)?.path();
+        if path.is_dir() {
+            collect_files(&path, extensions, files)?;
+        } else if path
+            .extension()
+            .is_some_and(|ext| extensions.iter().any(|e| ext == *e))
+        {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_repo_golden_fixtures() {
+        let golden = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden");
+        for dir in ["canonical", "hash"] {
+            let report = run_dir(golden.join(dir)).unwrap();
+            assert!(report.is_success(), "{dir}: {report}");
+        }
+        let report = run_dir(golden.join("enums")).unwrap();
+        assert!(report.count(CheckKind::Envelope) > 0);
+        assert!(report.count(CheckKind::Signing) > 0);
+    }
+
+    #[test]
+    fn test_signing_vector_from_sdk() {
+        let seed = [9u8; 32];
+        let signer = Ed25519Signer::from_seed(&seed).unwrap();
+        let transaction = json!({"header": {"principal": "acc://alice.acme/tokens"}, "body": {"type": "burnTokens", "amount": "1"}});
+        let canonical = canonicalize(&transaction);
+        let tx_hash = sha256(canonical.as_bytes());
+        let mut vector = json!({
+            "name": "burn",
+            "privateKey": hex::encode(seed),
+            "publicKey": hex::encode(signer.public_key_bytes()),
+            "transaction": transaction,
+            "canonicalJSON": canonical,
+            "txHash": hex::encode(tx_hash),
+            "signature": hex::encode(signer.sign(&tx_hash))
+        });
+
+        let results = check_fixture("vectors.json", &json!({"vectors": [vector.clone()]}));
+        assert_eq!(results.len(), 4);
+        assert!(results.iter().all(CheckResult::passed), "{results:?}");
+
+        vector["txHash"] = json!(hex::encode([0u8; 32]));
+        let report = ConformanceReport {
+            results: check_signing_vector("burn", &vector),
+            skipped: Vec::new(),
+        };
+        assert!(!report.is_success());
+        assert_eq!(report.failures().count(), 1);
+        assert!(report.to_string().contains("transaction hash mismatch"));
+    }
+
+    #[test]
+    fn test_ts_export_import() {
+        let export = Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/enums");
+        let fixtures = TsFixtures::import(&export).unwrap();
+        assert!(fixtures.cases.iter().any(|c| c
+            .name
+            .ends_with("tx_signing_vectors.json#simple_send_tokens")));
+        assert!(fixtures
+            .cases
+            .iter()
+            .any(|c| c.name.ends_with("ts_rand_vectors.jsonl#0")));
+
+        let report = fixtures.run();
+        for kind in [CheckKind::Hash, CheckKind::Envelope, CheckKind::Signing] {
+            assert!(report.count(kind) > 0, "no {kind} checks");
+        }
+        let binary: Vec<_> = report
+            .results
+            .iter()
+            .filter(|r| r.name.contains("typescript_sdk_vectors.json") || r.name.contains(".jsonl"))
+            .collect();
+        assert_eq!(binary.len(), 12);
+        assert!(binary.iter().all(|r| r.passed()), "{binary:?}");
+
+        // A full export can be checked with ACCUMULATE_TS_VECTORS=<dir>
+        if let Ok(dir) = std::env::var("ACCUMULATE_TS_VECTORS") {
+            let report = TsFixtures::import(dir).unwrap().run();
+            assert!(report.is_success(), "{report}");
+        }
+    }
+
+    #[test]
+    fn test_random_vector_frame() {
+        let transaction =
+            json!({"header": {"principal": "acc://a.acme"}, "body": {"type": "burnTokens"}});
+        let canonical = canonicalize(&transaction);
+        let tx_hash = hex::encode(sha256(canonical.as_bytes()));
+        let envelope = serde_json::to_vec(&json!({
+            "signatures": [{"transactionHash": tx_hash}],
+            "transaction": [transaction]
+        }))
+        .unwrap();
+        let mut frame = b"ACCU".to_vec();
+        frame.extend((envelope.len() as u32).to_be_bytes());
+        frame.extend(&envelope);
+        let line = json!({"hexBin": hex::encode(&frame), "canonicalJson": canonical, "txHashHex": tx_hash});
+
+        let cases = random_vector_cases("r.jsonl", &format!("{line}\n\n"));
+        assert_eq!(cases.len(), 1);
+        assert!(cases[0].run().iter().all(CheckResult::passed));
+
+        frame.pop();
+        let mut truncated = line;
+        truncated["hexBin"] = json!(hex::encode(&frame));
+        let results = FixtureCase::new("r".into(), Source::RandomVector, truncated).run();
+        assert_eq!(
+            results[0].failure.as_deref(),
+            Some(
+                format!(
+                    "frame length {}, but {} bytes follow",
+                    envelope.len(),
+                    envelope.len() - 1
+                )
+                .as_str()
+            )
+        );
+    }
+
+    #[test]
+    fn test_unrecognized_fixture_is_skipped() {
+        assert!(check_fixture("x.json", &json!({"something": "else"})).is_empty());
+        let results = check_fixture("h.json", &json!({"input": "zz", "hash": "00"}));
+        assert!(!results[0].passed());
+    }
+}
@@ -0,0 +1,192 @@
+//! Credits spent per flow
+//!
+//! Give a [`SmartSigner`](crate::helpers::SmartSigner) a [`CreditTracker`]
+//! with [`with_credit_tracker`](crate::helpers::SmartSigner::with_credit_tracker),
+//! or a whole [`QuickStart`](crate::helpers::QuickStart) with
+//! [`QuickStart::with_credit_tracker`](crate::helpers::QuickStart::with_credit_tracker),
+//! and every transaction whose status comes back delivered is recorded with
+//! its credit cost under a flow name. Totals per flow and for the session
+//! help budget the credits a deployment script needs.
+//!
+//! Status responses do not say what was charged, so the cost is the fee the
+//! network's rules give for the transaction ([`NetworkGlobals::estimate_fee`])
+//! plus [`fees::SIGNATURE`] for each signature in the envelope. Transactions
+//! that fail or are not confirmed are not recorded.
+//!
+//! ```
+//! use accumulate_client::credits::CreditTracker;
+//!
+//! let tracker = CreditTracker::new();
+//! // quick.with_credit_tracker(tracker.clone()) ... run the flows ...
+//! for (flow, spent) in tracker.flows() {
+//!     println!("{flow}: {} credits in {} transactions", spent.credits(), spent.transactions);
+//! }
+//! assert_eq!(tracker.total(), 0);
+//! ```
+
+use crate::globals::{fees, NetworkGlobals};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+
+/// One delivered transaction and what it cost
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreditCharge {
+    /// Flow the transaction belongs to, e.g. `"setup_adi"`
+    pub flow: String,
+    /// Transaction ID from the status response
+    pub txid: Option<String>,
+    /// Key page or lite identity that paid
+    pub signer: String,
+    /// Transaction type, e.g. `"createIdentity"`
+    pub transaction_type: String,
+    /// Cost in credit hundredths (100 = 1 credit)
+    pub fee: u64,
+}
+
+/// Credits spent by one flow
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FlowCredits {
+    /// Delivered transactions
+    pub transactions: usize,
+    /// Cost in credit hundredths (100 = 1 credit)
+    pub fee: u64,
+}
+
+impl FlowCredits {
+    /// Cost in credits
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn credits(&self) -> f64 {
+        self.fee as f64 / 100.0
+    }
+}
+
+/// Records the credit cost of delivered transactions
+///
+/// Clones share their records, so one tracker can follow several signers
+/// for a whole session.
+#[derive(Debug, Clone, Default)]
+pub struct CreditTracker {
+    globals: Arc<NetworkGlobals>,
+    charges: Arc<Mutex<Vec<CreditCharge>>>,
+}
+
+impl CreditTracker {
+    /// A tracker pricing transactions with the protocol's default fees
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A tracker pricing transactions with `globals`, e.g. from
+    /// [`AccumulateClient::network_globals`](crate::AccumulateClient::network_globals)
+    #[must_use]
+    pub fn with_globals(globals: NetworkGlobals) -> Self {
+        Self {
+            globals: Arc::new(globals),
+            charges: Arc::default(),
+        }
+    }
+
+    /// Record a delivered envelope under `flow` and return its charge
+    pub fn record(&self, flow: &str, signer: &str, envelope: &Value, txid: Option<&str>) -> CreditCharge {
+        let transaction = &envelope["transaction"][0];
+        let signatures = envelope["signatures"].as_array().map_or(1, Vec::len) as u64;
+        // A body the estimator rejects was still accepted by the network;
+        // count its signatures only
+        let fee = self
+            .globals
+            .estimate_fee(&transaction["header"], &transaction["body"])
+            .unwrap_or(0);
+        let charge = CreditCharge {
+            flow: flow.to_string(),
+            txid: txid.map(str::to_string),
+            signer: signer.to_string(),
+            transaction_type: transaction["body"]["type"].as_str().unwrap_or_default().to_string(),
+            fee: fee + fees::SIGNATURE * signatures,
+        };
+        self.lock().push(charge.clone());
+        charge
+    }
+
+    /// Every charge recorded so far, in order
+    #[must_use]
+    pub fn charges(&self) -> Vec<CreditCharge> {
+        self.lock().clone()
+    }
+
+    /// Credits spent by `flow`
+    #[must_use]
+    pub fn flow(&self, flow: &str) -> FlowCredits {
+        self.flows().remove(flow).unwrap_or_default()
+    }
+
+    /// Credits spent by each flow
+    #[must_use]
+    pub fn flows(&self) -> BTreeMap<String, FlowCredits> {
+        let mut flows = BTreeMap::<String, FlowCredits>::new();
+        for charge in self.lock().iter() {
+            let spent = flows.entry(charge.flow.clone()).or_default();
+            spent.transactions += 1;
+            spent.fee += charge.fee;
+        }
+        flows
+    }
+
+    /// Credit hundredths spent by every flow together
+    #[must_use]
+    pub fn total(&self) -> u64 {
+        self.lock().iter().map(|charge| charge.fee).sum()
+    }
+
+    /// Forget the charges recorded so far
+    pub fn reset(&self) {
+        self.lock().clear();
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Vec<CreditCharge>> {
+        self.charges.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::mock::TestHarness;
+
+    #[tokio::test]
+    async fn test_quickstart_flows_are_tracked() {
+        let harness = TestHarness::new().await.unwrap();
+        let tracker = CreditTracker::new();
+        let quick = harness.quick().clone().with_credit_tracker(tracker.clone());
+        let wallet = harness.funded_wallet(100).unwrap();
+        let adi = quick.setup_adi(&wallet, "budget").await.unwrap();
+        assert!(quick.buy_credits_for_adi(&wallet, &adi, 1_000).await.unwrap().success);
+        assert!(quick.create_data_account(&adi, "data").await.unwrap().success);
+
+        let setup = tracker.flow("setup_adi");
+        assert_eq!(setup.transactions, 2);
+        assert!(setup.fee >= fees::CREATE_IDENTITY);
+        assert_eq!(tracker.flow("buy_credits_for_adi").fee, fees::SIGNATURE);
+        assert_eq!(
+            tracker.flow("create_data_account").fee,
+            fees::CREATE_ACCOUNT + fees::SIGNATURE
+        );
+        assert_eq!(tracker.total(), tracker.flows().values().map(|f| f.fee).sum::<u64>());
+
+        let charges = tracker.charges();
+        assert_eq!(charges[1].transaction_type, "createIdentity");
+        assert_eq!(charges[1].signer, wallet.lite_identity);
+        assert!(charges.iter().all(|charge| charge.txid.is_some()));
+
+        // A failed transaction is not charged
+        assert!(!quick.create_data_account(&adi, "data").await.unwrap().success);
+        assert_eq!(tracker.charges().len(), 4);
+        tracker.reset();
+        assert_eq!(tracker.total(), 0);
+    }
+}
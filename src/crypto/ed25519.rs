@@ -2,7 +2,7 @@
 #![allow(clippy::unwrap_used, clippy::expect_used)]
 
 use ed25519_dalek::{SigningKey, VerifyingKey, Signature, Signer, Verifier};
-use sha2::{Digest, Sha256};
+use sha2::{Digest, Sha256, Sha512};
 
 /// Ed25519 signer that exactly matches TypeScript SDK behavior
 /// Updated for ed25519-dalek v2.x API
@@ -54,11 +54,30 @@ impl Ed25519Signer {
     }
 
     /// Sign a pre-hashed message and return 64-byte signature array
+    ///
+    /// This is pure Ed25519 over the 32-byte hash, which is how the network
+    /// checks ED25519 signatures; for RFC 8032 Ed25519ph use
+    /// [`sign_ed25519ph`](Self::sign_ed25519ph).
     pub fn sign_prehashed(&self, hash: &[u8; 32]) -> [u8; 64] {
         let signature = self.signing_key.sign(hash);
         signature.to_bytes()
     }
 
+    /// Sign `message` with Ed25519ph (RFC 8032): the message is hashed with
+    /// SHA-512 and signed under an optional context of up to 255 bytes
+    ///
+    /// Accumulate has no Ed25519ph signature type, so these signatures are
+    /// for off-chain use (e.g. attestations checked by other Ed25519ph
+    /// verifiers) and are never accepted in a transaction envelope.
+    pub fn sign_ed25519ph(
+        &self,
+        message: &[u8],
+        context: Option<&[u8]>,
+    ) -> Result<[u8; 64], ed25519_dalek::SignatureError> {
+        let prehash = Sha512::new_with_prefix(message);
+        Ok(self.signing_key.sign_prehashed(prehash, context)?.to_bytes())
+    }
+
     /// Get verifying (public) key as reference
     pub fn verifying_key(&self) -> VerifyingKey {
         self.signing_key.verifying_key()
@@ -120,6 +139,22 @@ pub fn verify_prehashed(
     }
 }
 
+/// Verify an Ed25519ph (RFC 8032) signature of `message` under `context`
+/// Returns true if signature is valid, false otherwise
+pub fn verify_ed25519ph(
+    public_key: &[u8; 32],
+    message: &[u8],
+    context: Option<&[u8]>,
+    signature: &[u8; 64],
+) -> bool {
+    VerifyingKey::from_bytes(public_key).is_ok_and(|verifying_key| {
+        let prehash = Sha512::new_with_prefix(message);
+        verifying_key
+            .verify_prehashed(prehash, context, &Signature::from_bytes(signature))
+            .is_ok()
+    })
+}
+
 /// Legacy verify function for backwards compatibility
 pub fn verify_signature(
     public_key: &[u8; 32],
@@ -200,6 +235,36 @@ mod tests {
         assert!(verify(&signer.public_key_bytes(), message, &signature));
     }
 
+    #[test]
+    fn test_ed25519ph_rfc8032_vector() {
+        // RFC 8032 section 7.3, TEST abc
+        let seed: [u8; 32] = hex::decode("833fe62409237b9d62ec77587520911e9a759cec1d19755b7da901b96dca3d42")
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let signer = Ed25519Signer::from_seed(&seed).unwrap();
+        assert_eq!(
+            hex::encode(signer.public_key_bytes()),
+            "ec172b93ad5e563bf4932c70e1245034c35467ef2efd4d64ebf819683467e2bf"
+        );
+
+        let signature = signer.sign_ed25519ph(b"abc", None).unwrap();
+        assert_eq!(
+            hex::encode(signature),
+            "98a70222f0b8121aa9d30f813d683f809e462b469c7ff87639499bb94e6dae41\
+             31f85042463c2a355a2003d062adf5aaa10b8c61e636062aaad11c2a26083406"
+        );
+        let public_key = signer.public_key_bytes();
+        assert!(verify_ed25519ph(&public_key, b"abc", None, &signature));
+
+        // Neither a pure Ed25519 signature nor one under another context
+        assert!(!verify(&public_key, b"abc", &signature));
+        assert!(!verify_ed25519ph(&public_key, b"abc", Some(b"ctx"), &signature));
+        let with_context = signer.sign_ed25519ph(b"abc", Some(b"ctx")).unwrap();
+        assert!(verify_ed25519ph(&public_key, b"abc", Some(b"ctx"), &with_context));
+        assert!(signer.sign_ed25519ph(b"abc", Some(&[0u8; 256])).is_err());
+    }
+
     #[test]
     fn test_verify_functions() {
         let seed = [2u8; 32];
@@ -60,10 +60,10 @@ impl Ed25519Helper {
     /// This uses standard Ed25519 seed-based key generation to match test vectors
     pub fn keypair_from_hex(hex_key: &str) -> Result<Keypair, Error> {
         let bytes = hex::decode(hex_key)
-            .map_err(|_| Error::Signature(SignatureError::InvalidFormat))?;
+            .map_err(|_| Error::Signing(SignatureError::InvalidFormat))?;
 
         if bytes.len() != 32 {
-            return Err(Error::Signature(SignatureError::InvalidFormat));
+            return Err(Error::Signing(SignatureError::InvalidFormat));
         }
 
         let mut seed = [0u8; 32];
@@ -83,7 +83,7 @@ impl Ed25519Helper {
     /// Create a verifying (public) key from bytes
     pub fn public_key_from_bytes(bytes: &[u8; 32]) -> Result<VerifyingKey, Error> {
         VerifyingKey::from_bytes(bytes)
-            .map_err(|_| Error::Signature(SignatureError::InvalidPublicKey))
+            .map_err(|_| Error::Signing(SignatureError::InvalidPublicKey))
     }
 
     /// Create a signature from bytes
@@ -112,13 +112,13 @@ impl Ed25519Helper {
         let message_bytes = canonical.as_bytes();
 
         verify_signature(&public_key.to_bytes(), message_bytes, &signature.to_bytes())
-            .map_err(|_| Error::Signature(SignatureError::VerificationFailed("JSON signature verification failed".to_string())))
+            .map_err(|_| Error::Signing(SignatureError::VerificationFailed("JSON signature verification failed".to_string())))
     }
 
     /// Verify a signature against raw data
     pub fn verify(public_key: &VerifyingKey, message: &[u8], signature: &Signature) -> Result<(), Error> {
         verify_signature(&public_key.to_bytes(), message, &signature.to_bytes())
-            .map_err(|_| Error::Signature(SignatureError::VerificationFailed("Raw signature verification failed".to_string())))
+            .map_err(|_| Error::Signing(SignatureError::VerificationFailed("Raw signature verification failed".to_string())))
     }
 
     /// Sign raw bytes with a keypair
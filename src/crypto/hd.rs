@@ -0,0 +1,464 @@
+//! BIP-39 mnemonics and hierarchical key derivation
+//!
+//! A [`Mnemonic`] encodes 128-256 bits of entropy as 12-24 English words, so
+//! a wallet can be backed up on paper and restored with any BIP-39 wallet.
+//! [`HdWallet`] turns the phrase into keys:
+//!
+//! - Ed25519 keys by [SLIP-0010], hardened indices only, at
+//!   [`lite_identity_path`] (`m/44'/281'/0'/0'/i'`, the path the Ledger app
+//!   uses) for lite identities
+//! - secp256k1 keys by BIP-32, behind the `secp256k1` feature, for `btc` and
+//!   `eth` signers
+//!
+//! ```
+//! use accumulate_client::crypto::hd::{HdWallet, Mnemonic};
+//!
+//! let mnemonic = Mnemonic::generate(24).unwrap();
+//! let restored = Mnemonic::parse(&mnemonic.phrase()).unwrap();
+//!
+//! let wallet = HdWallet::from_mnemonic(&restored, "").lite_wallet(0).unwrap();
+//! let again = HdWallet::from_mnemonic(&mnemonic, "").lite_wallet(0).unwrap();
+//! assert_eq!(wallet.lite_identity, again.lite_identity);
+//! ```
+//!
+//! Phrases are normalized to lowercase words separated by single spaces. The
+//! passphrase is normalized to Unicode NFKD form as BIP-39 requires, so a
+//! non-ASCII passphrase gives the same seed however it was typed.
+//!
+//! [SLIP-0010]: https://github.com/satoshilabs/slips/blob/master/slip-0010.md
+
+use crate::helpers::Wallet;
+use crate::signer::{parse_derivation_path, ACCUMULATE_COIN_TYPE};
+use ed25519_dalek::SigningKey;
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256, Sha512};
+use std::fmt;
+use std::sync::OnceLock;
+use thiserror::Error;
+use unicode_normalization::UnicodeNormalization;
+
+type HmacSha512 = Hmac<Sha512>;
+
+/// The BIP-39 English wordlist, one word per line
+const ENGLISH: &str = include_str!("hd/english.txt");
+
+const HARDENED: u32 = 0x8000_0000;
+
+/// Errors from mnemonic handling and key derivation
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum HdError {
+    /// Mnemonics have 12, 15, 18, 21 or 24 words
+    #[error("Invalid mnemonic length: {0} words (expected 12, 15, 18, 21 or 24)")]
+    InvalidWordCount(usize),
+
+    /// A word is not in the BIP-39 English wordlist
+    #[error("Unknown mnemonic word {0:?}")]
+    UnknownWord(String),
+
+    /// The words do not end in a valid checksum, usually a typo
+    #[error("Invalid mnemonic checksum")]
+    InvalidChecksum,
+
+    /// Entropy is 16, 20, 24, 28 or 32 bytes
+    #[error("Invalid entropy length: {0} bytes")]
+    InvalidEntropyLength(usize),
+
+    /// Seeds are 16 to 64 bytes
+    #[error("Invalid seed length: {0} bytes (expected 16 to 64)")]
+    InvalidSeedLength(usize),
+
+    /// The derivation path does not parse
+    #[error("Invalid derivation path: {0}")]
+    InvalidPath(String),
+
+    /// SLIP-0010 Ed25519 derivation has no public (non-hardened) children
+    #[error("Ed25519 derivation needs hardened indices only: {0}")]
+    NonHardened(String),
+
+    /// BIP-32 produced an invalid key at this path (probability below 2^-127)
+    #[error("No valid key at {0}; use the next index")]
+    InvalidChild(String),
+
+    /// The system random number generator failed
+    #[error("Random number generator failed: {0}")]
+    Random(String),
+}
+
+fn wordlist() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+    WORDS.get_or_init(|| ENGLISH.lines().collect())
+}
+
+/// A BIP-39 mnemonic phrase
+///
+/// `Debug` prints the word count only, so a mnemonic does not end up in logs.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    entropy: Vec<u8>,
+}
+
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mnemonic")
+            .field("words", &self.word_count())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Mnemonic {
+    /// A new random mnemonic of `word_count` words (12, 15, 18, 21 or 24)
+    pub fn generate(word_count: usize) -> Result<Self, HdError> {
+        if !matches!(word_count, 12 | 15 | 18 | 21 | 24) {
+            return Err(HdError::InvalidWordCount(word_count));
+        }
+        let mut entropy = vec![0u8; word_count * 4 / 3];
+        getrandom::getrandom(&mut entropy).map_err(|e| HdError::Random(e.to_string()))?;
+        Self::from_entropy(&entropy)
+    }
+
+    /// The mnemonic encoding `entropy` (16, 20, 24, 28 or 32 bytes)
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, HdError> {
+        if !matches!(entropy.len(), 16 | 20 | 24 | 28 | 32) {
+            return Err(HdError::InvalidEntropyLength(entropy.len()));
+        }
+        Ok(Self {
+            entropy: entropy.to_vec(),
+        })
+    }
+
+    /// Parse and check a phrase
+    pub fn parse(phrase: &str) -> Result<Self, HdError> {
+        let words: Vec<String> = phrase.split_whitespace().map(str::to_lowercase).collect();
+        if !matches!(words.len(), 12 | 15 | 18 | 21 | 24) {
+            return Err(HdError::InvalidWordCount(words.len()));
+        }
+
+        // 11 bits per word: the entropy followed by its checksum
+        let mut bits = Vec::with_capacity(words.len() * 11);
+        for word in &words {
+            let index = wordlist()
+                .binary_search(&word.as_str())
+                .map_err(|_| HdError::UnknownWord(word.clone()))?;
+            bits.extend((0..11).rev().map(|i| (index >> i) & 1 == 1));
+        }
+        let entropy_bits = words.len() * 32 / 3;
+        let entropy: Vec<u8> = bits[..entropy_bits]
+            .chunks(8)
+            .map(|byte| {
+                byte.iter()
+                    .fold(0u8, |acc, &bit| (acc << 1) | u8::from(bit))
+            })
+            .collect();
+
+        let mnemonic = Self { entropy };
+        if mnemonic.bits() == bits {
+            Ok(mnemonic)
+        } else {
+            Err(HdError::InvalidChecksum)
+        }
+    }
+
+    /// Entropy bits followed by the checksum bits
+    fn bits(&self) -> Vec<bool> {
+        let checksum = Sha256::digest(&self.entropy);
+        let checksum_bits = self.entropy.len() / 4;
+        let bit = |bytes: &[u8], i: usize| (bytes[i / 8] >> (7 - i % 8)) & 1 == 1;
+        (0..self.entropy.len() * 8)
+            .map(|i| bit(&self.entropy, i))
+            .chain((0..checksum_bits).map(|i| bit(&checksum, i)))
+            .collect()
+    }
+
+    /// The words of the phrase
+    #[must_use]
+    pub fn words(&self) -> Vec<&'static str> {
+        self.bits()
+            .chunks(11)
+            .map(|chunk| {
+                let index = chunk
+                    .iter()
+                    .fold(0usize, |acc, &bit| (acc << 1) | usize::from(bit));
+                wordlist()[index]
+            })
+            .collect()
+    }
+
+    /// Number of words
+    #[must_use]
+    pub fn word_count(&self) -> usize {
+        self.entropy.len() * 3 / 4
+    }
+
+    /// The phrase, words separated by single spaces
+    #[must_use]
+    pub fn phrase(&self) -> String {
+        self.words().join(" ")
+    }
+
+    /// The entropy the phrase encodes
+    #[must_use]
+    pub fn entropy(&self) -> &[u8] {
+        &self.entropy
+    }
+
+    /// The 64-byte BIP-39 seed for `passphrase` (empty for none)
+    ///
+    /// The passphrase is NFKD-normalized first.
+    #[must_use]
+    pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+        let salt: String = format!("mnemonic{passphrase}").nfkd().collect();
+        let mut seed = [0u8; 64];
+        pbkdf2::pbkdf2_hmac::<Sha512>(self.phrase().as_bytes(), salt.as_bytes(), 2048, &mut seed);
+        seed
+    }
+}
+
+fn hmac_sha512(key: &[u8], parts: &[&[u8]]) -> [u8; 64] {
+    let mut mac = HmacSha512::new_from_slice(key)
+        .unwrap_or_else(|_| unreachable!("HMAC accepts keys of any length"));
+    for part in parts {
+        mac.update(part);
+    }
+    mac.finalize().into_bytes().into()
+}
+
+/// Split an HMAC-SHA512 output into key and chain code
+fn split(i: [u8; 64]) -> ([u8; 32], [u8; 32]) {
+    let mut key = [0u8; 32];
+    let mut chain_code = [0u8; 32];
+    key.copy_from_slice(&i[..32]);
+    chain_code.copy_from_slice(&i[32..]);
+    (key, chain_code)
+}
+
+/// Derivation path of the lite identity key at `index`: `m/44'/281'/0'/0'/{index}'`
+#[must_use]
+pub fn lite_identity_path(index: u32) -> String {
+    format!("m/44'/{ACCUMULATE_COIN_TYPE}'/0'/0'/{index}'")
+}
+
+/// Keys derived from a BIP-39 seed
+///
+/// `Debug` does not print the seed.
+#[derive(Clone)]
+pub struct HdWallet {
+    seed: Vec<u8>,
+}
+
+impl fmt::Debug for HdWallet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HdWallet").finish_non_exhaustive()
+    }
+}
+
+impl HdWallet {
+    /// Keys of `mnemonic` protected by `passphrase` (empty for none)
+    #[must_use]
+    pub fn from_mnemonic(mnemonic: &Mnemonic, passphrase: &str) -> Self {
+        Self {
+            seed: mnemonic.to_seed(passphrase).to_vec(),
+        }
+    }
+
+    /// Keys of a raw seed of 16 to 64 bytes
+    pub fn from_seed(seed: &[u8]) -> Result<Self, HdError> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(HdError::InvalidSeedLength(seed.len()));
+        }
+        Ok(Self {
+            seed: seed.to_vec(),
+        })
+    }
+
+    /// Ed25519 key at `path` by SLIP-0010, e.g. `m/44'/281'/0'/0'/0'`
+    pub fn ed25519_key(&self, path: &str) -> Result<SigningKey, HdError> {
+        let indices = parse_path(path)?;
+        if indices.iter().any(|index| index & HARDENED == 0) {
+            return Err(HdError::NonHardened(path.to_string()));
+        }
+
+        let (mut key, mut chain_code) = split(hmac_sha512(b"ed25519 seed", &[&self.seed]));
+        for index in indices {
+            (key, chain_code) = split(hmac_sha512(
+                &chain_code,
+                &[&[0], &key, &index.to_be_bytes()],
+            ));
+        }
+        Ok(SigningKey::from_bytes(&key))
+    }
+
+    /// Wallet of the lite identity key at `index`
+    pub fn lite_wallet(&self, index: u32) -> Result<Wallet, HdError> {
+        Ok(Wallet::from_keypair(
+            self.ed25519_key(&lite_identity_path(index))?,
+        ))
+    }
+
+    /// secp256k1 private key at `path` by BIP-32, e.g. `m/44'/60'/0'/0/0`
+    #[cfg(feature = "secp256k1")]
+    pub fn secp256k1_key(&self, path: &str) -> Result<[u8; 32], HdError> {
+        use k256::elliptic_curve::sec1::ToEncodedPoint;
+        use k256::elliptic_curve::PrimeField;
+        use k256::{FieldBytes, Scalar, SecretKey};
+
+        let invalid = || HdError::InvalidChild(path.to_string());
+        let scalar = |bytes: &[u8; 32]| -> Option<Scalar> {
+            Option::from(Scalar::from_repr(FieldBytes::from(*bytes)))
+                .filter(|s: &Scalar| !bool::from(s.is_zero()))
+        };
+
+        let (master, mut chain_code) = split(hmac_sha512(b"Bitcoin seed", &[&self.seed]));
+        let mut key = scalar(&master).ok_or_else(invalid)?;
+        for index in parse_path(path)? {
+            let i = if index & HARDENED == 0 {
+                let secret = SecretKey::from_bytes(&key.to_bytes()).map_err(|_| invalid())?;
+                let public = secret.public_key().to_encoded_point(true);
+                hmac_sha512(&chain_code, &[public.as_bytes(), &index.to_be_bytes()])
+            } else {
+                hmac_sha512(&chain_code, &[&[0], &key.to_bytes(), &index.to_be_bytes()])
+            };
+            let (tweak, next_chain_code) = split(i);
+            let tweak =
+                Option::<Scalar>::from(Scalar::from_repr(tweak.into())).ok_or_else(invalid)?;
+            key = Some(tweak + key)
+                .filter(|s| !bool::from(s.is_zero()))
+                .ok_or_else(invalid)?;
+            chain_code = next_chain_code;
+        }
+        Ok(key.to_bytes().into())
+    }
+
+    /// Signer for the secp256k1 key at `path`, signing as `signature_type`
+    #[cfg(feature = "secp256k1")]
+    pub fn secp256k1_signer(
+        &self,
+        path: &str,
+        signature_type: crate::generated::enums::SignatureType,
+    ) -> Result<crate::signer::Secp256k1Signer, HdError> {
+        crate::signer::Secp256k1Signer::new(&self.secp256k1_key(path)?, signature_type)
+            .map_err(|_| HdError::InvalidChild(path.to_string()))
+    }
+}
+
+fn parse_path(path: &str) -> Result<Vec<u32>, HdError> {
+    parse_derivation_path(path).map_err(|_| HdError::InvalidPath(path.to_string()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bip39_vectors() {
+        // From the BIP-39 reference vectors, passphrase "TREZOR"
+        let vectors = [
+            (
+                "00000000000000000000000000000000",
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+            ),
+            (
+                "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+                "dd48c104698c30cfe2b6142103248622fb7bb0ff692eebb00089b32d22484e1613912f0a5b694407be899ffd31ed3992c456cdf60f5d4564b8ba3f05a69890ad",
+            ),
+        ];
+        assert_eq!(wordlist().len(), 2048);
+        for (entropy, phrase, seed) in vectors {
+            let mnemonic = Mnemonic::from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+            assert_eq!(mnemonic.phrase(), phrase);
+            assert_eq!(hex::encode(mnemonic.to_seed("TREZOR")), seed);
+            assert_eq!(Mnemonic::parse(&phrase.to_uppercase()).unwrap(), mnemonic);
+        }
+    }
+
+    #[test]
+    fn test_passphrase_is_nfkd_normalized() {
+        let mnemonic = Mnemonic::from_entropy(&[0u8; 16]).unwrap();
+        // Precomposed and decomposed "é" give the same seed
+        assert_eq!(
+            mnemonic.to_seed("caf\u{e9}"),
+            mnemonic.to_seed("cafe\u{301}")
+        );
+        assert_ne!(mnemonic.to_seed("caf\u{e9}"), mnemonic.to_seed("cafe"));
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_phrases() {
+        assert_eq!(
+            Mnemonic::parse("abandon abandon"),
+            Err(HdError::InvalidWordCount(2))
+        );
+        assert_eq!(
+            Mnemonic::parse(&["abandon"; 12].join(" ")),
+            Err(HdError::InvalidChecksum)
+        );
+        let typo = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abuot";
+        assert_eq!(
+            Mnemonic::parse(typo),
+            Err(HdError::UnknownWord("abuot".to_string()))
+        );
+
+        let generated = Mnemonic::generate(24).unwrap();
+        assert_eq!(generated.word_count(), 24);
+        assert_eq!(Mnemonic::parse(&generated.phrase()).unwrap(), generated);
+        assert!(!format!("{generated:?}").contains(generated.words()[0]));
+    }
+
+    #[test]
+    fn test_slip10_ed25519_vectors() {
+        // SLIP-0010 test vector 1 for ed25519
+        let wallet =
+            HdWallet::from_seed(&hex::decode("000102030405060708090a0b0c0d0e0f").unwrap()).unwrap();
+        assert_eq!(
+            hex::encode(wallet.ed25519_key("m/0'").unwrap().as_bytes()),
+            "68e0fe46dfb67e368c75379acec591dad19df3cde26e63b93a8e704f1dade7a3"
+        );
+        assert_eq!(
+            hex::encode(wallet.ed25519_key("m/0'/1'").unwrap().as_bytes()),
+            "b1d0bad404bf35da785a64ca1ac54b2617211d2777696fbffaf208f746ae84f2"
+        );
+        assert_eq!(
+            wallet.ed25519_key("m/0'/1").unwrap_err(),
+            HdError::NonHardened("m/0'/1".to_string())
+        );
+
+        let lite = wallet.lite_wallet(3).unwrap();
+        let key = wallet.ed25519_key("m/44'/281'/0'/0'/3'").unwrap();
+        assert_eq!(lite.public_key(), key.verifying_key().to_bytes());
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn test_bip32_secp256k1_vectors() {
+        // BIP-32 test vector 1
+        let wallet =
+            HdWallet::from_seed(&hex::decode("000102030405060708090a0b0c0d0e0f").unwrap()).unwrap();
+        for (path, key) in [
+            (
+                "m/0'",
+                "edb2e14f9ee77d26dd93b4ecede8d16ed408ce149b6cd80b0715a2d911a0afea",
+            ),
+            (
+                "m/0'/1",
+                "3c6cb8d0f6a264c91ea8b5030fadaa8e538b020f0a387421a12de9319dc93368",
+            ),
+            (
+                "m/0'/1/2'/2/1000000000",
+                "471b76e389e528d6de6d816857e012c5455051cad6660850e58372a6c3e6e7c8",
+            ),
+        ] {
+            assert_eq!(hex::encode(wallet.secp256k1_key(path).unwrap()), key);
+        }
+        let signer = wallet
+            .secp256k1_signer("m/0'", crate::generated::enums::SignatureType::BTC)
+            .unwrap();
+        assert!(signer.lite_identity().starts_with("acc://"));
+    }
+}
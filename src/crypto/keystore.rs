@@ -0,0 +1,532 @@
+//! Password-encrypted keystore
+//!
+//! A [`Keystore`] keeps Ed25519 keys under named aliases in a [`KvStore`],
+//! so a [`Wallet`] or a [`SmartSigner`](crate::helpers::SmartSigner) key
+//! survives across runs instead of being regenerated:
+//!
+//! ```no_run
+//! use accumulate_client::crypto::keystore::Keystore;
+//! use accumulate_client::QuickStart;
+//!
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! let quick = QuickStart::devnet().await?;
+//! let mut keystore = Keystore::open("./keys")?;
+//! // The same lite account on every run
+//! let wallet = quick.wallet_from_keystore(&mut keystore, "devnet-wallet", "hunter2")?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Each entry stores the public key in the clear and the 32-byte seed
+//! encrypted under the password:
+//!
+//! - Argon2id over the password and a random 16-byte salt gives a 256-bit key
+//! - the seed is encrypted with AES-256-GCM under a random 12-byte nonce
+//! - the GCM tag also covers the alias, key type and public key, so a wrong
+//!   password or a tampered entry is detected before the seed is used
+//!
+//! Entries record the algorithms they use, so other KDFs and ciphers can be
+//! added without breaking existing keystores.
+
+use crate::helpers::Wallet;
+use crate::storage::{get_json, put_json, scan_json, FileKvStore, KvStore, StorageError};
+use aes_gcm::aead::{Aead, KeyInit, Payload};
+use aes_gcm::{Aes256Gcm, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use ed25519_dalek::SigningKey;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+use thiserror::Error;
+
+/// Entry format version
+const VERSION: u32 = 1;
+/// Key derivation function of version 1 entries
+const KDF: &str = "argon2id";
+/// Cipher of version 1 entries
+const CIPHER: &str = "aes-256-gcm";
+/// Default Argon2id memory cost in KiB, per OWASP guidance
+pub const DEFAULT_MEMORY_KIB: u32 = 19_456;
+/// Default Argon2id iteration count, per OWASP guidance
+pub const DEFAULT_ITERATIONS: u32 = 2;
+
+/// Errors from a [`Keystore`]
+#[derive(Error, Debug)]
+pub enum KeystoreError {
+    /// The backing store failed
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+
+    /// No key is stored under the alias
+    #[error("No key named {0:?} in the keystore")]
+    NotFound(String),
+
+    /// A key is already stored under the alias
+    #[error("A key named {0:?} is already in the keystore")]
+    AlreadyExists(String),
+
+    /// Aliases are 1-64 ASCII letters, digits, `.`, `_` or `-`
+    #[error("Invalid key alias {0:?}: use 1-64 letters, digits, '.', '_' or '-'")]
+    InvalidAlias(String),
+
+    /// The password is wrong or the entry was modified
+    #[error("Wrong password for key {0:?}, or the entry is corrupted")]
+    WrongPassword(String),
+
+    /// Imported key material is malformed
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+
+    /// The entry uses a format this SDK does not know
+    #[error("Unsupported keystore entry: {0}")]
+    Unsupported(String),
+
+    /// The system random number generator failed
+    #[error("Random number generator failed: {0}")]
+    Random(String),
+}
+
+/// Public part of a stored key, readable without the password
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyInfo {
+    /// Name the key is stored under
+    pub alias: String,
+    /// Key type, currently always `ed25519`
+    pub key_type: String,
+    /// Public key
+    pub public_key: [u8; 32],
+}
+
+impl KeyInfo {
+    /// Lite identity of the key
+    #[must_use]
+    pub fn lite_identity(&self) -> String {
+        crate::helpers::derive_lite_identity_url(&self.public_key)
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Entry {
+    version: u32,
+    alias: String,
+    key_type: String,
+    #[serde(with = "hex::serde")]
+    public_key: Vec<u8>,
+    kdf: String,
+    memory_kib: u32,
+    iterations: u32,
+    #[serde(with = "hex::serde")]
+    salt: Vec<u8>,
+    cipher: String,
+    #[serde(with = "hex::serde")]
+    nonce: Vec<u8>,
+    #[serde(with = "hex::serde")]
+    ciphertext: Vec<u8>,
+}
+
+impl Entry {
+    fn info(&self) -> Result<KeyInfo, KeystoreError> {
+        let public_key = self.public_key.as_slice().try_into().map_err(|_| {
+            KeystoreError::Unsupported(format!("{}: public key is not 32 bytes", self.alias))
+        })?;
+        Ok(KeyInfo {
+            alias: self.alias.clone(),
+            key_type: self.key_type.clone(),
+            public_key,
+        })
+    }
+
+    /// Associated data authenticated alongside the ciphertext
+    fn associated_data(&self) -> Vec<u8> {
+        let mut data = Vec::new();
+        for part in [
+            self.alias.as_bytes(),
+            self.key_type.as_bytes(),
+            &self.public_key,
+        ] {
+            data.extend_from_slice(&(part.len() as u64).to_be_bytes());
+            data.extend_from_slice(part);
+        }
+        data
+    }
+}
+
+/// Ed25519 keys encrypted under a password, kept in a [`KvStore`] under `key/`
+#[derive(Debug, Clone)]
+pub struct Keystore<S: KvStore> {
+    kv: S,
+    memory_kib: u32,
+    iterations: u32,
+}
+
+impl Keystore<FileKvStore> {
+    /// Keystore in the directory `dir`, creating it if needed
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, KeystoreError> {
+        Ok(Self::new(FileKvStore::open(dir)?))
+    }
+}
+
+impl<S: KvStore> Keystore<S> {
+    const PREFIX: &'static str = "key/";
+
+    /// Keep keys in `kv`
+    pub const fn new(kv: S) -> Self {
+        Self {
+            kv,
+            memory_kib: DEFAULT_MEMORY_KIB,
+            iterations: DEFAULT_ITERATIONS,
+        }
+    }
+
+    /// Argon2id memory cost in KiB and iterations for keys stored from now on
+    ///
+    /// Existing entries keep the cost they were written with. Lower costs
+    /// make brute-forcing the password cheaper; use them for tests only.
+    #[must_use]
+    pub const fn with_cost(mut self, memory_kib: u32, iterations: u32) -> Self {
+        self.memory_kib = memory_kib;
+        self.iterations = iterations;
+        self
+    }
+
+    /// The underlying key-value store
+    pub const fn kv(&self) -> &S {
+        &self.kv
+    }
+
+    /// Generate a new key and store it under `alias`
+    pub fn generate(&mut self, alias: &str, password: &str) -> Result<KeyInfo, KeystoreError> {
+        let seed: [u8; 32] = random()?;
+        self.insert(alias, &SigningKey::from_bytes(&seed), password)
+    }
+
+    /// Store the key with 32-byte `seed` under `alias`
+    pub fn import_seed(
+        &mut self,
+        alias: &str,
+        seed: &[u8; 32],
+        password: &str,
+    ) -> Result<KeyInfo, KeystoreError> {
+        self.insert(alias, &SigningKey::from_bytes(seed), password)
+    }
+
+    /// Store a hex-encoded key under `alias`
+    ///
+    /// Accepts a 32-byte seed or a 64-byte seed followed by its public key,
+    /// the private key format of the Go and JavaScript SDKs.
+    pub fn import_hex(
+        &mut self,
+        alias: &str,
+        key_hex: &str,
+        password: &str,
+    ) -> Result<KeyInfo, KeystoreError> {
+        let bytes = hex::decode(key_hex.trim().trim_start_matches("0x"))
+            .map_err(|e| KeystoreError::InvalidKey(e.to_string()))?;
+        let key = match bytes.len() {
+            32 => SigningKey::from_bytes(&seed_of(&bytes)),
+            64 => {
+                let key = SigningKey::from_bytes(&seed_of(&bytes[..32]));
+                if key.verifying_key().as_bytes() != &bytes[32..] {
+                    return Err(KeystoreError::InvalidKey(
+                        "public key half does not match the seed".to_string(),
+                    ));
+                }
+                key
+            }
+            n => {
+                return Err(KeystoreError::InvalidKey(format!(
+                    "expected 32 or 64 bytes, got {n}"
+                )))
+            }
+        };
+        self.insert(alias, &key, password)
+    }
+
+    /// Encrypt `key` and store it under `alias`
+    ///
+    /// Fails with [`KeystoreError::AlreadyExists`] rather than overwrite a
+    /// key; [`Self::remove`] it first to replace it.
+    pub fn insert(
+        &mut self,
+        alias: &str,
+        key: &SigningKey,
+        password: &str,
+    ) -> Result<KeyInfo, KeystoreError> {
+        check_alias(alias)?;
+        if self.kv.get(&Self::key(alias))?.is_some() {
+            return Err(KeystoreError::AlreadyExists(alias.to_string()));
+        }
+
+        let salt: [u8; 16] = random()?;
+        let nonce: [u8; 12] = random()?;
+        let mut entry = Entry {
+            version: VERSION,
+            alias: alias.to_string(),
+            key_type: "ed25519".to_string(),
+            public_key: key.verifying_key().to_bytes().to_vec(),
+            kdf: KDF.to_string(),
+            memory_kib: self.memory_kib,
+            iterations: self.iterations,
+            salt: salt.to_vec(),
+            cipher: CIPHER.to_string(),
+            nonce: nonce.to_vec(),
+            ciphertext: Vec::new(),
+        };
+        let cipher = cipher_for(password, &entry)?;
+        entry.ciphertext = cipher
+            .encrypt(
+                &Nonce::from(nonce),
+                Payload {
+                    msg: key.as_bytes(),
+                    aad: &entry.associated_data(),
+                },
+            )
+            .unwrap_or_else(|_| unreachable!("AES-GCM encrypts inputs up to 64 GiB"));
+
+        put_json(&mut self.kv, &Self::key(alias), &entry)?;
+        entry.info()
+    }
+
+    /// Decrypt the key stored under `alias`
+    pub fn load(&self, alias: &str, password: &str) -> Result<SigningKey, KeystoreError> {
+        let entry = self.entry(alias)?;
+        if entry.version != VERSION || entry.kdf != KDF || entry.cipher != CIPHER {
+            return Err(KeystoreError::Unsupported(format!(
+                "{alias}: version {} with {}/{}",
+                entry.version, entry.kdf, entry.cipher
+            )));
+        }
+        if entry.key_type != "ed25519" {
+            return Err(KeystoreError::Unsupported(format!(
+                "{alias}: key type {}",
+                entry.key_type
+            )));
+        }
+
+        let nonce: [u8; 12] =
+            entry.nonce.as_slice().try_into().map_err(|_| {
+                KeystoreError::Unsupported(format!("{alias}: nonce is not 12 bytes"))
+            })?;
+        let seed = cipher_for(password, &entry)?
+            .decrypt(
+                &Nonce::from(nonce),
+                Payload {
+                    msg: &entry.ciphertext,
+                    aad: &entry.associated_data(),
+                },
+            )
+            .map_err(|_| KeystoreError::WrongPassword(alias.to_string()))?;
+        if seed.len() != 32 {
+            return Err(KeystoreError::Unsupported(format!(
+                "{alias}: seed is not 32 bytes"
+            )));
+        }
+        let key = SigningKey::from_bytes(&seed_of(&seed));
+        if key.verifying_key().as_bytes() != entry.public_key.as_slice() {
+            return Err(KeystoreError::WrongPassword(alias.to_string()));
+        }
+        Ok(key)
+    }
+
+    /// Hex-encoded 32-byte seed of the key under `alias`
+    pub fn export_hex(&self, alias: &str, password: &str) -> Result<String, KeystoreError> {
+        Ok(hex::encode(self.load(alias, password)?.as_bytes()))
+    }
+
+    /// Public part of the key under `alias`, if there is one
+    pub fn get(&self, alias: &str) -> Result<Option<KeyInfo>, KeystoreError> {
+        get_json::<Entry>(&self.kv, &Self::key(alias))?
+            .map(|entry| entry.info())
+            .transpose()
+    }
+
+    /// Every stored key, ordered by alias
+    pub fn list(&self) -> Result<Vec<KeyInfo>, KeystoreError> {
+        scan_json::<Entry>(&self.kv, Self::PREFIX)?
+            .iter()
+            .map(Entry::info)
+            .collect()
+    }
+
+    /// Remove the key under `alias`; removing a missing one is not an error
+    pub fn remove(&mut self, alias: &str) -> Result<(), KeystoreError> {
+        Ok(self.kv.delete(&Self::key(alias))?)
+    }
+
+    /// Wallet of the key under `alias`
+    pub fn load_wallet(&self, alias: &str, password: &str) -> Result<Wallet, KeystoreError> {
+        Ok(Wallet::from_keypair(self.load(alias, password)?))
+    }
+
+    /// Wallet of the key under `alias`, generating and storing one if needed
+    pub fn load_or_create_wallet(
+        &mut self,
+        alias: &str,
+        password: &str,
+    ) -> Result<Wallet, KeystoreError> {
+        if self.get(alias)?.is_none() {
+            self.generate(alias, password)?;
+        }
+        self.load_wallet(alias, password)
+    }
+
+    fn entry(&self, alias: &str) -> Result<Entry, KeystoreError> {
+        get_json(&self.kv, &Self::key(alias))?
+            .ok_or_else(|| KeystoreError::NotFound(alias.to_string()))
+    }
+
+    fn key(alias: &str) -> String {
+        format!("{}{alias}", Self::PREFIX)
+    }
+}
+
+fn check_alias(alias: &str) -> Result<(), KeystoreError> {
+    let valid = (1..=64).contains(&alias.len())
+        && alias
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || matches!(b, b'.' | b'_' | b'-'));
+    if valid {
+        Ok(())
+    } else {
+        Err(KeystoreError::InvalidAlias(alias.to_string()))
+    }
+}
+
+fn seed_of(bytes: &[u8]) -> [u8; 32] {
+    let mut seed = [0u8; 32];
+    seed.copy_from_slice(&bytes[..32]);
+    seed
+}
+
+fn random<const N: usize>() -> Result<[u8; N], KeystoreError> {
+    let mut bytes = [0u8; N];
+    getrandom::getrandom(&mut bytes).map_err(|e| KeystoreError::Random(e.to_string()))?;
+    Ok(bytes)
+}
+
+/// AES-256-GCM keyed by Argon2id over `password` with the entry's salt and cost
+fn cipher_for(password: &str, entry: &Entry) -> Result<Aes256Gcm, KeystoreError> {
+    let params = Params::new(entry.memory_kib, entry.iterations, 1, Some(32)).map_err(|e| {
+        KeystoreError::Unsupported(format!("{}: Argon2 parameters: {e}", entry.alias))
+    })?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+        .hash_password_into(password.as_bytes(), &entry.salt, &mut key)
+        .map_err(|e| KeystoreError::Unsupported(format!("{}: Argon2: {e}", entry.alias)))?;
+    Ok(Aes256Gcm::new(&key.into()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::storage::MemoryKvStore;
+
+    fn keystore() -> Keystore<MemoryKvStore> {
+        Keystore::new(MemoryKvStore::new()).with_cost(64, 1)
+    }
+
+    #[test]
+    fn test_entries_record_their_algorithms() {
+        let mut keystore = Keystore::new(MemoryKvStore::new());
+        keystore.import_seed("alice", &[7u8; 32], "pw").unwrap();
+        let entry = keystore.entry("alice").unwrap();
+        assert_eq!(
+            (entry.kdf.as_str(), entry.memory_kib, entry.iterations),
+            (KDF, DEFAULT_MEMORY_KIB, DEFAULT_ITERATIONS)
+        );
+        assert_eq!(entry.cipher, CIPHER);
+        // 32-byte seed plus the 16-byte GCM tag
+        assert_eq!(entry.ciphertext.len(), 48);
+        assert_eq!(keystore.load("alice", "pw").unwrap().as_bytes(), &[7u8; 32]);
+    }
+
+    #[test]
+    fn test_import_load_export_round_trip() {
+        let mut keystore = keystore();
+        let seed = [9u8; 32];
+        let info = keystore.import_seed("alice", &seed, "pw").unwrap();
+        let key = keystore.load("alice", "pw").unwrap();
+        assert_eq!(key.as_bytes(), &seed);
+        assert_eq!(info.public_key, key.verifying_key().to_bytes());
+        assert_eq!(
+            keystore.export_hex("alice", "pw").unwrap(),
+            hex::encode(seed)
+        );
+
+        // The seed is not stored in the clear
+        let raw = keystore.kv().get("key/alice").unwrap().unwrap();
+        let raw = String::from_utf8(raw).unwrap();
+        assert!(!raw.contains(&hex::encode(seed)));
+        assert!(raw.contains(&hex::encode(info.public_key)));
+
+        // 64-byte Go/JS private keys import to the same key
+        let keypair = hex::encode(key.to_keypair_bytes());
+        let imported = keystore.import_hex("alice2", &keypair, "other").unwrap();
+        assert_eq!(imported.public_key, info.public_key);
+        let mut bad = key.to_keypair_bytes();
+        bad[63] ^= 1;
+        assert!(matches!(
+            keystore.import_hex("alice3", &hex::encode(bad), "pw"),
+            Err(KeystoreError::InvalidKey(_))
+        ));
+
+        let aliases: Vec<_> = keystore
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|k| k.alias)
+            .collect();
+        assert_eq!(aliases, ["alice", "alice2"]);
+    }
+
+    #[test]
+    fn test_wrong_password_and_tampering_are_detected() {
+        let mut keystore = keystore();
+        keystore.generate("bob", "right").unwrap();
+        assert!(matches!(
+            keystore.load("bob", "wrong"),
+            Err(KeystoreError::WrongPassword(_))
+        ));
+
+        // Swapping the public key of an entry breaks its GCM tag
+        let mut entry = keystore.entry("bob").unwrap();
+        entry.public_key = vec![1; 32];
+        put_json(&mut keystore.kv, "key/bob", &entry).unwrap();
+        assert!(matches!(
+            keystore.load("bob", "right"),
+            Err(KeystoreError::WrongPassword(_))
+        ));
+
+        assert!(matches!(
+            keystore.load("carol", "right"),
+            Err(KeystoreError::NotFound(_))
+        ));
+        assert!(matches!(
+            keystore.generate("bob", "right"),
+            Err(KeystoreError::AlreadyExists(_))
+        ));
+        assert!(matches!(
+            keystore.generate("a/b", "right"),
+            Err(KeystoreError::InvalidAlias(_))
+        ));
+    }
+
+    #[test]
+    fn test_wallet_survives_reopening() {
+        let dir = std::env::temp_dir().join(format!("keystore-{}", std::process::id()));
+        let first = Keystore::open(&dir)
+            .unwrap()
+            .with_cost(64, 1)
+            .load_or_create_wallet("devnet", "pw")
+            .unwrap();
+        let mut reopened = Keystore::open(&dir).unwrap();
+        let second = reopened.load_or_create_wallet("devnet", "pw").unwrap();
+        assert_eq!(first.lite_token_account, second.lite_token_account);
+        assert_eq!(
+            reopened.get("devnet").unwrap().unwrap().lite_identity(),
+            second.lite_identity
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -4,6 +4,8 @@
 
 pub mod ed25519;
 pub mod ed25519_helper;
+pub mod hd;
+pub mod keystore;
 
 pub use ed25519::*;
 pub use ed25519_helper::*;
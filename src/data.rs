@@ -0,0 +1,312 @@
+//! Reading data accounts
+//!
+//! [`AccumulateClient::query_data_entry`] and
+//! [`AccumulateClient::query_data_set`] return the entries written to a data
+//! account decoded: each [`DataEntryRecord`] carries its position, entry hash
+//! and transaction ID, and a [`DataEntry`] whose parts are bytes rather than
+//! hex, with UTF-8 views for entries that hold text.
+//!
+//! ```no_run
+//! # async fn run(client: &accumulate_client::AccumulateClient) -> Result<(), accumulate_client::data::DataError> {
+//! let latest = client.query_data_entry("acc://alice.acme/data", None).await?;
+//! println!("entry {}: {:?}", latest.index, latest.entry.texts());
+//!
+//! let mut start = 0;
+//! loop {
+//!     let page = client.query_data_set("acc://alice.acme/data", start, 50).await?;
+//!     for record in &page.entries {
+//!         println!("{} {}", record.index, record.entry_hash);
+//!     }
+//!     match page.next_start() {
+//!         Some(next) => start = next,
+//!         None => break,
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::AccumulateClient;
+use crate::codec::hex_utils::parse_hex_field;
+use crate::generated::enums::DataEntryType;
+use crate::json_rpc_client::JsonRpcError;
+use crate::query::{ChainEntryRecord, ChainResult};
+use crate::types::{DataQuery, RangeOptions};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Errors from reading a data account
+#[derive(Error, Debug)]
+pub enum DataError {
+    /// The node returned an entry this SDK cannot decode
+    #[error("Malformed data entry: {0}")]
+    Malformed(String),
+
+    /// A query failed
+    #[error("Query failed: {0}")]
+    Query(#[from] JsonRpcError),
+}
+
+/// The content of a data entry
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DataEntry {
+    /// Parts hashed with a single Merkle pass (executor v1 before
+    /// `v1-doubleHashEntries`)
+    Accumulate {
+        /// Data parts
+        data: Vec<Vec<u8>>,
+    },
+    /// Parts whose Merkle root is hashed again (current executors)
+    DoubleHash {
+        /// Data parts
+        data: Vec<Vec<u8>>,
+    },
+    /// An entry migrated from Factom
+    Factom {
+        /// Factom chain ID
+        account_id: Vec<u8>,
+        /// Entry content
+        data: Vec<u8>,
+        /// External IDs
+        ext_ids: Vec<Vec<u8>>,
+    },
+}
+
+impl DataEntry {
+    /// Decode an entry in its JSON form, e.g. the `entry` of a writeData body
+    pub fn from_json(entry: &Value) -> Result<Self, DataError> {
+        let kind = entry.get("type").and_then(Value::as_str).unwrap_or_default();
+        match kind.to_ascii_lowercase().as_str() {
+            "accumulate" => Ok(Self::Accumulate { data: hex_list(entry, "data")? }),
+            "doublehash" => Ok(Self::DoubleHash { data: hex_list(entry, "data")? }),
+            "factom" => Ok(Self::Factom {
+                account_id: hex_field(entry, "accountId")?,
+                data: hex_field(entry, "data")?,
+                ext_ids: hex_list(entry, "extIds")?,
+            }),
+            _ => Err(DataError::Malformed(format!("unknown entry type {kind:?}"))),
+        }
+    }
+
+    /// The entry's type
+    #[must_use]
+    pub const fn entry_type(&self) -> DataEntryType {
+        match self {
+            Self::Accumulate { .. } => DataEntryType::Accumulate,
+            Self::DoubleHash { .. } => DataEntryType::DoubleHash,
+            Self::Factom { .. } => DataEntryType::Factom,
+        }
+    }
+
+    /// The entry's parts in order; a Factom entry's external IDs come
+    /// before its content, as in Go's `GetData`
+    #[must_use]
+    pub fn parts(&self) -> Vec<&[u8]> {
+        match self {
+            Self::Accumulate { data } | Self::DoubleHash { data } => data.iter().map(Vec::as_slice).collect(),
+            Self::Factom { data, ext_ids, .. } => ext_ids
+                .iter()
+                .map(Vec::as_slice)
+                .chain(std::iter::once(data.as_slice()))
+                .collect(),
+        }
+    }
+
+    /// Each part as text, `None` for parts that are not UTF-8
+    #[must_use]
+    pub fn texts(&self) -> Vec<Option<&str>> {
+        self.parts()
+            .into_iter()
+            .map(|part| std::str::from_utf8(part).ok())
+            .collect()
+    }
+}
+
+/// A data entry and where it sits on the account's data chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataEntryRecord {
+    /// Position on the data chain
+    pub index: u64,
+    /// Entry hash (hex)
+    pub entry_hash: String,
+    /// ID of the transaction that wrote the entry, when the node reported it
+    pub txid: Option<String>,
+    /// The decoded entry
+    pub entry: DataEntry,
+}
+
+impl DataEntryRecord {
+    /// Decode a data chain entry whose value is the writing transaction
+    pub fn from_chain_entry(record: &ChainEntryRecord) -> Result<Self, DataError> {
+        let value = record
+            .value
+            .as_ref()
+            .ok_or_else(|| DataError::Malformed(format!("entry {} was not expanded", record.index)))?;
+        let entry = value
+            .pointer("/message/transaction/body/entry")
+            .ok_or_else(|| DataError::Malformed(format!("entry {} holds no data entry", record.index)))?;
+        Ok(Self {
+            index: record.index,
+            entry_hash: record.entry.clone(),
+            txid: value.get("id").and_then(Value::as_str).map(str::to_string),
+            entry: DataEntry::from_json(entry)?,
+        })
+    }
+}
+
+/// A page of entries from [`AccumulateClient::query_data_set`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataEntryPage {
+    /// Entries of this page, in chain order
+    pub entries: Vec<DataEntryRecord>,
+    /// Index of the first entry
+    pub start: u64,
+    /// Number of entries on the account
+    pub total: u64,
+}
+
+impl DataEntryPage {
+    /// Start of the next page, `None` after the last one
+    #[must_use]
+    pub fn next_start(&self) -> Option<u64> {
+        let next = self.start + self.entries.len() as u64;
+        (!self.entries.is_empty() && next < self.total).then_some(next)
+    }
+}
+
+impl AccumulateClient {
+    /// The data entry at `index` of the data account at `url`, or its latest
+    /// entry when `index` is `None`
+    pub async fn query_data_entry(&self, url: &str, index: Option<u64>) -> Result<DataEntryRecord, DataError> {
+        self.data_entry(url, DataQuery { index, ..Default::default() }).await
+    }
+
+    /// The data entry of the data account at `url` with hash `entry_hash` (hex)
+    pub async fn query_data_entry_by_hash(&self, url: &str, entry_hash: &str) -> Result<DataEntryRecord, DataError> {
+        let query = DataQuery {
+            entry: Some(entry_hash.to_string()),
+            ..Default::default()
+        };
+        self.data_entry(url, query).await
+    }
+
+    /// Up to `count` entries of the data account at `url`, from `start`
+    pub async fn query_data_set(&self, url: &str, start: u64, count: u64) -> Result<DataEntryPage, DataError> {
+        let query = DataQuery {
+            range: Some(RangeOptions {
+                start: Some(start),
+                count: Some(count),
+                expand: Some(true),
+                from_end: None,
+            }),
+            ..Default::default()
+        };
+        match self.v3().query_data(url, query).await? {
+            ChainResult::Entries(range) => Ok(DataEntryPage {
+                entries: range
+                    .records
+                    .iter()
+                    .map(DataEntryRecord::from_chain_entry)
+                    .collect::<Result<_, _>>()?,
+                start: range.start,
+                total: range.total,
+            }),
+            other => Err(DataError::Malformed(format!("expected a range, got {other:?}"))),
+        }
+    }
+
+    async fn data_entry(&self, url: &str, query: DataQuery) -> Result<DataEntryRecord, DataError> {
+        match self.v3().query_data(url, query).await? {
+            ChainResult::Entry(record) => DataEntryRecord::from_chain_entry(&record),
+            other => Err(DataError::Malformed(format!("expected an entry, got {other:?}"))),
+        }
+    }
+}
+
+fn hex_field(entry: &Value, key: &str) -> Result<Vec<u8>, DataError> {
+    entry.get(key).map_or_else(|| Ok(Vec::new()), |value| hex_value(value, key))
+}
+
+fn hex_list(entry: &Value, key: &str) -> Result<Vec<Vec<u8>>, DataError> {
+    entry
+        .get(key)
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|part| hex_value(part, key))
+        .collect()
+}
+
+fn hex_value(value: &Value, key: &str) -> Result<Vec<u8>, DataError> {
+    let hex = value
+        .as_str()
+        .ok_or_else(|| DataError::Malformed(format!("{key}: expected a hex string, got {value}")))?;
+    parse_hex_field(key, hex).map_err(|e| DataError::Malformed(e.to_string()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::{SmartSigner, TxBody};
+    use crate::mock::TestHarness;
+    use serde_json::json;
+
+    #[test]
+    fn test_decode_entry_variants() {
+        let entry = DataEntry::from_json(&json!({"type": "doubleHash", "data": ["68656c6c6f", "ff00"]})).unwrap();
+        assert_eq!(entry.entry_type(), DataEntryType::DoubleHash);
+        assert_eq!(entry.texts(), [Some("hello"), None]);
+
+        let entry = DataEntry::from_json(&json!({"type": "accumulate", "data": []})).unwrap();
+        assert_eq!(entry, DataEntry::Accumulate { data: Vec::new() });
+
+        let entry = DataEntry::from_json(&json!({
+            "type": "factom",
+            "accountId": "11".repeat(32),
+            "data": "626f6479",
+            "extIds": ["6964"]
+        }))
+        .unwrap();
+        assert_eq!(entry.entry_type(), DataEntryType::Factom);
+        assert_eq!(entry.parts(), [b"id".as_slice(), b"body".as_slice()]);
+
+        assert!(DataEntry::from_json(&json!({"type": "doubleHash", "data": ["zz"]})).is_err());
+        assert!(DataEntry::from_json(&json!({"type": "doubleHash", "data": [null]})).is_err());
+        assert!(DataEntry::from_json(&json!({"type": "factom", "accountId": "0"})).is_err());
+        assert!(DataEntry::from_json(&json!({"type": "other"})).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_read_entries_and_pages() {
+        let harness = TestHarness::new().await.unwrap();
+        let (_, adi) = harness.adi("reader").await.unwrap();
+        let quick = harness.quick();
+        assert!(quick.create_data_account(&adi, "log").await.unwrap().success);
+        let log = format!("{}/log", adi.url);
+        let mut signer = SmartSigner::new(quick.client(), adi.keypair().clone(), &adi.key_page_url);
+        for line in ["first", "second", "third"] {
+            let result = signer.sign_submit_and_wait(&log, &TxBody::write_data(&[line]), None, 30).await;
+            assert!(result.success, "{:?}", result.error);
+        }
+
+        let client = quick.client();
+        let latest = client.query_data_entry(&log, None).await.unwrap();
+        assert_eq!(latest.index, 2);
+        assert_eq!(latest.entry.texts(), [Some("third")]);
+        assert!(latest.txid.as_deref().unwrap().ends_with("@reader.acme/log"));
+        let first = client.query_data_entry(&log, Some(0)).await.unwrap();
+        assert_eq!(first.entry.texts(), [Some("first")]);
+        let by_hash = client.query_data_entry_by_hash(&log, &first.entry_hash).await.unwrap();
+        assert_eq!(by_hash, first);
+
+        let page = client.query_data_set(&log, 0, 2).await.unwrap();
+        assert_eq!((page.start, page.total, page.entries.len()), (0, 3, 2));
+        assert_eq!(page.entries[1].entry.texts(), [Some("second")]);
+        let last = client.query_data_set(&log, page.next_start().unwrap(), 2).await.unwrap();
+        assert_eq!(last.entries, [latest]);
+        assert_eq!(last.next_start(), None);
+
+        assert!(matches!(client.query_data_entry(&log, Some(9)).await, Err(DataError::Query(_))));
+    }
+}
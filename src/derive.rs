@@ -0,0 +1,494 @@
+//! Lite account derivation and self-test vectors
+//!
+//! A lite identity URL is the first 20 bytes of the public key hash in hex
+//! followed by a 4-byte checksum of that hex string; the lite token account
+//! for ACME is that URL plus `/ACME`. [`lite_identity_for_key`] derives it for
+//! any key type whose key hash the SDK knows how to compute.
+//!
+//! [`verify_against_vectors`] runs a fixture file of expected derivations
+//! through the SDK, and [`verify_builtin_vectors`] does the same for the set
+//! bundled with the crate, so a wallet can check at startup that derivation
+//! matches the network before showing any address:
+//!
+//! ```
+//! let report = accumulate_client::derive::verify_builtin_vectors();
+//! assert!(report.is_ok(), "{:?}", report.failures);
+//! ```
+//!
+//! A fixture is `{"vectors": [...]}` or a bare array of objects with `type`
+//! (a signature type name, `ed25519` when absent), `publicKey` (hex),
+//! `liteIdentity` and optionally `liteTokenAccount`. Vectors for key types
+//! the SDK cannot derive yet are counted as skipped, not failed.
+
+use crate::generated::enums::SignatureType;
+use crate::helpers::sha256_hash;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+use thiserror::Error;
+
+/// Vectors bundled with the crate
+const BUILTIN_VECTORS: &str = include_str!("derive/lite_vectors.json");
+
+/// Derivation errors
+#[derive(Error, Debug)]
+pub enum DeriveError {
+    /// Lite addresses for this key type are not supported yet
+    #[error("Lite derivation is not supported for {0:?} keys")]
+    UnsupportedKeyType(SignatureType),
+    /// The public key is not a valid key of its type
+    #[error("Invalid public key {0}")]
+    InvalidKey(String),
+    /// Not a 20-byte hex Ethereum address, or its EIP-55 checksum is wrong
+    #[error("Invalid Ethereum address {0}")]
+    InvalidAddress(String),
+    /// The fixture file could not be read
+    #[error("Cannot read {path}: {reason}")]
+    Io {
+        /// Path that failed
+        path: String,
+        /// Underlying error
+        reason: String,
+    },
+    /// The fixture is not a list of derivation vectors
+    #[error("Invalid derivation vectors: {0}")]
+    InvalidFixture(String),
+}
+
+/// Lite identity URL for a key hash (at least 20 bytes)
+#[must_use]
+pub fn lite_identity_for_hash(key_hash: &[u8]) -> String {
+    let key_hex = hex::encode(&key_hash[..key_hash.len().min(20)]);
+    let checksum = sha256_hash(key_hex.as_bytes());
+    format!("acc://{}{}", key_hex, hex::encode(&checksum[28..]))
+}
+
+/// Lite identity URL for a public key of the given type
+///
+/// Supports every key type [`key_hash`] does.
+pub fn lite_identity_for_key(
+    key_type: &SignatureType,
+    public_key: &[u8],
+) -> Result<String, DeriveError> {
+    Ok(lite_identity_for_hash(&key_hash(key_type, public_key)?))
+}
+
+/// ACME lite token account URL for a public key of the given type
+pub fn lite_token_account_for_key(
+    key_type: &SignatureType,
+    public_key: &[u8],
+) -> Result<String, DeriveError> {
+    Ok(format!(
+        "{}/ACME",
+        lite_identity_for_key(key_type, public_key)?
+    ))
+}
+
+/// Hash a key page entry or lite identity records for a public key
+///
+/// Ed25519, RCD1, RSA and ECDSA keys hash with SHA-256, Bitcoin keys with
+/// RIPEMD-160 over SHA-256 and Ethereum keys (`eth` and EIP-712 `typeddata`)
+/// to their 20-byte address, the same as the Go core's `PublicKeyHash`.
+/// Ethereum keys need the `secp256k1` feature and may be compressed or
+/// uncompressed. ECDSA keys must be PKIX DER, as the network carries them;
+/// a bare SEC1 point is rejected rather than hashed to the wrong identity.
+pub fn key_hash(key_type: &SignatureType, public_key: &[u8]) -> Result<Vec<u8>, DeriveError> {
+    Ok(match key_type {
+        SignatureType::ED25519 | SignatureType::LegacyED25519 | SignatureType::RsaSha256 => {
+            sha256_hash(public_key).to_vec()
+        }
+        // A PKIX SubjectPublicKeyInfo is a DER SEQUENCE; SEC1 points start 0x02-0x04
+        SignatureType::EcdsaSha256 if public_key.first() == Some(&0x30) => {
+            sha256_hash(public_key).to_vec()
+        }
+        SignatureType::EcdsaSha256 => return Err(DeriveError::InvalidKey(hex::encode(public_key))),
+        SignatureType::RCD1 => {
+            let mut rcd = Vec::with_capacity(public_key.len() + 1);
+            rcd.push(1);
+            rcd.extend_from_slice(public_key);
+            sha256_hash(&sha256_hash(&rcd)).to_vec()
+        }
+        SignatureType::BTC | SignatureType::BTCLegacy => {
+            ripemd160(&sha256_hash(public_key)).to_vec()
+        }
+        #[cfg(feature = "secp256k1")]
+        SignatureType::ETH | SignatureType::TypedData => eth_address(public_key)
+            .ok_or_else(|| DeriveError::InvalidKey(hex::encode(public_key)))?
+            .to_vec(),
+        other => return Err(DeriveError::UnsupportedKeyType(other.clone())),
+    })
+}
+
+/// Parse an Ethereum address, with or without `0x`
+///
+/// The address is the key hash an `eth` key page entry or lite identity
+/// records. A mixed-case address must match its EIP-55 checksum when the
+/// `secp256k1` feature is on; all-lowercase and all-uppercase addresses carry
+/// no checksum.
+pub fn parse_eth_address(address: &str) -> Result<[u8; 20], DeriveError> {
+    let invalid = || DeriveError::InvalidAddress(address.to_string());
+    let digits = address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+        .unwrap_or(address);
+    let bytes: [u8; 20] = hex::decode(digits)
+        .ok()
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or_else(invalid)?;
+    let mixed_case = digits.chars().any(|c| c.is_ascii_lowercase())
+        && digits.chars().any(|c| c.is_ascii_uppercase());
+    #[cfg(feature = "secp256k1")]
+    if mixed_case && eth_checksum_address(&bytes)[2..] != *digits {
+        return Err(invalid());
+    }
+    #[cfg(not(feature = "secp256k1"))]
+    let _ = mixed_case;
+    Ok(bytes)
+}
+
+/// Lite identity URL of the Ethereum account at `address`
+pub fn lite_identity_for_eth_address(address: &str) -> Result<String, DeriveError> {
+    Ok(lite_identity_for_hash(&parse_eth_address(address)?))
+}
+
+/// EIP-55 mixed-case checksum encoding of an Ethereum address, with `0x`
+#[cfg(feature = "secp256k1")]
+#[must_use]
+pub fn eth_checksum_address(address: &[u8; 20]) -> String {
+    use sha3::{Digest, Keccak256};
+
+    let lower = hex::encode(address);
+    let hash = Keccak256::digest(lower.as_bytes());
+    let checksummed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> if i % 2 == 0 { 4 } else { 0 }) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{checksummed}")
+}
+
+/// Ethereum address of a SEC1 public key: the last 20 bytes of the Keccak-256
+/// hash of the uncompressed point without its `0x04` tag
+#[cfg(feature = "secp256k1")]
+fn eth_address(public_key: &[u8]) -> Option<[u8; 20]> {
+    use k256::elliptic_curve::sec1::ToEncodedPoint;
+    use sha3::{Digest, Keccak256};
+
+    let point = k256::PublicKey::from_sec1_bytes(public_key)
+        .ok()?
+        .to_encoded_point(false);
+    let hash = Keccak256::digest(point.as_bytes().get(1..)?);
+    hash.get(12..)?.try_into().ok()
+}
+
+/// RIPEMD-160, for Bitcoin key hashes
+fn ripemd160(data: &[u8]) -> [u8; 20] {
+    use ripemd::{Digest, Ripemd160};
+
+    Ripemd160::digest(data).into()
+}
+
+/// One expected derivation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DerivationVector {
+    /// Label for reports
+    #[serde(default)]
+    pub name: String,
+    /// Key type, `ed25519` when absent
+    #[serde(rename = "type", default = "default_key_type")]
+    pub key_type: SignatureType,
+    /// Hex public key
+    pub public_key: String,
+    /// Expected lite identity URL
+    pub lite_identity: String,
+    /// Expected ACME lite token account URL
+    #[serde(default)]
+    pub lite_token_account: Option<String>,
+}
+
+const fn default_key_type() -> SignatureType {
+    SignatureType::ED25519
+}
+
+/// A vector whose derivation did not match
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DerivationMismatch {
+    /// Name of the vector
+    pub name: String,
+    /// What was expected
+    pub expected: String,
+    /// What the SDK produced, or why it could not
+    pub actual: String,
+}
+
+/// Outcome of a derivation self-test
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DerivationReport {
+    /// Vectors checked
+    pub checked: usize,
+    /// Vectors for key types the SDK cannot derive yet
+    pub skipped: usize,
+    /// Vectors that did not match
+    pub failures: Vec<DerivationMismatch>,
+}
+
+impl DerivationReport {
+    /// Whether every checked vector matched
+    #[must_use]
+    pub fn is_ok(&self) -> bool {
+        self.failures.is_empty()
+    }
+}
+
+/// Check each vector against the SDK's derivation
+#[must_use]
+pub fn verify_vectors(vectors: &[DerivationVector]) -> DerivationReport {
+    let mut report = DerivationReport::default();
+    for vector in vectors {
+        let mismatch = |expected: &str, actual: String| DerivationMismatch {
+            name: vector.name.clone(),
+            expected: expected.to_string(),
+            actual,
+        };
+        let public_key = match hex::decode(&vector.public_key) {
+            Ok(key) => key,
+            Err(e) => {
+                report.checked += 1;
+                report.failures.push(mismatch(
+                    &vector.lite_identity,
+                    format!("invalid public key: {e}"),
+                ));
+                continue;
+            }
+        };
+        let identity = match lite_identity_for_key(&vector.key_type, &public_key) {
+            Ok(identity) => identity,
+            Err(DeriveError::UnsupportedKeyType(_)) => {
+                report.skipped += 1;
+                continue;
+            }
+            Err(e) => {
+                report.checked += 1;
+                report
+                    .failures
+                    .push(mismatch(&vector.lite_identity, e.to_string()));
+                continue;
+            }
+        };
+
+        report.checked += 1;
+        if !identity.eq_ignore_ascii_case(&vector.lite_identity) {
+            report
+                .failures
+                .push(mismatch(&vector.lite_identity, identity));
+        } else if let Some(expected) = &vector.lite_token_account {
+            let account = format!("{identity}/ACME");
+            if !account.eq_ignore_ascii_case(expected) {
+                report.failures.push(mismatch(expected, account));
+            }
+        }
+    }
+    report
+}
+
+/// Parse a fixture into vectors
+pub fn parse_vectors(fixture: &str) -> Result<Vec<DerivationVector>, DeriveError> {
+    let value: Value =
+        serde_json::from_str(fixture).map_err(|e| DeriveError::InvalidFixture(e.to_string()))?;
+    let list = value.get("vectors").cloned().unwrap_or(value);
+    serde_json::from_value(list).map_err(|e| DeriveError::InvalidFixture(e.to_string()))
+}
+
+/// Run the derivation vectors in the fixture file at `path`
+pub fn verify_against_vectors(path: impl AsRef<Path>) -> Result<DerivationReport, DeriveError> {
+    let path = path.as_ref();
+    let fixture = std::fs::read_to_string(path).map_err(|e| DeriveError::Io {
+        path: path.display().to_string(),
+        reason: e.to_string(),
+    })?;
+    Ok(verify_vectors(&parse_vectors(&fixture)?))
+}
+
+/// Run the derivation vectors bundled with the crate
+#[must_use]
+pub fn verify_builtin_vectors() -> DerivationReport {
+    parse_vectors(BUILTIN_VECTORS).map_or_else(
+        |e| DerivationReport {
+            checked: 0,
+            skipped: 0,
+            failures: vec![DerivationMismatch {
+                name: "builtin".to_string(),
+                expected: "valid vectors".to_string(),
+                actual: e.to_string(),
+            }],
+        },
+        |vectors| verify_vectors(&vectors),
+    )
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::derive_lite_identity_url;
+
+    #[test]
+    fn test_builtin_vectors() {
+        let report = verify_builtin_vectors();
+        assert!(report.is_ok(), "{:?}", report.failures);
+        // Ethereum addresses need Keccak-256
+        let skipped = 2 * usize::from(!cfg!(feature = "secp256k1"));
+        assert_eq!((report.checked, report.skipped), (9 - skipped, skipped));
+
+        // The helper used by wallets agrees with the vectors
+        let vectors = parse_vectors(BUILTIN_VECTORS).unwrap();
+        for vector in vectors
+            .iter()
+            .filter(|v| v.key_type == SignatureType::ED25519)
+        {
+            let key: [u8; 32] = hex::decode(&vector.public_key).unwrap().try_into().unwrap();
+            assert_eq!(derive_lite_identity_url(&key), vector.lite_identity);
+        }
+    }
+
+    #[test]
+    fn test_mismatches_and_skips() {
+        let fixture = r#"[
+            {"name": "wrong", "publicKey": "0101010101010101010101010101010101010101010101010101010101010101",
+             "liteIdentity": "acc://72cd6e8422c407fb6d098690f1130b7ded7ec2f76aee7d70",
+             "liteTokenAccount": "acc://72cd6e8422c407fb6d098690f1130b7ded7ec2f76aee7d70/ETH"},
+            {"name": "bad hex", "publicKey": "zz", "liteIdentity": "acc://x"},
+            {"name": "delegated", "type": "delegated", "publicKey": "02", "liteIdentity": "acc://x"}
+        ]"#;
+        let report = verify_vectors(&parse_vectors(fixture).unwrap());
+        assert_eq!((report.checked, report.skipped), (2, 1));
+        assert_eq!(report.failures.len(), 2);
+        assert!(report.failures[0].actual.ends_with("/ACME"));
+        assert_eq!(report.failures[1].name, "bad hex");
+
+        assert!(matches!(
+            verify_against_vectors("does/not/exist.json"),
+            Err(DeriveError::Io { .. })
+        ));
+        assert!(matches!(
+            parse_vectors("{}"),
+            Err(DeriveError::InvalidFixture(_))
+        ));
+    }
+
+    #[test]
+    fn test_ripemd160() {
+        assert_eq!(
+            hex::encode(ripemd160(b"")),
+            "9c1185a5c5e9fc54612808977ee8f548b2258d31"
+        );
+        assert_eq!(
+            hex::encode(ripemd160(b"abc")),
+            "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"
+        );
+        assert_eq!(
+            hex::encode(ripemd160(
+                b"abcdbcdecdefdefgefghfghighijhijkijkljklmklmnlmnomnopnopq"
+            )),
+            "12a053384a9c0c88e405a06c27dcf49ada62eb2b"
+        );
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn test_eth_key_hash_accepts_compressed_keys() {
+        let compressed =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        assert_eq!(
+            hex::encode(key_hash(&SignatureType::ETH, &compressed).unwrap()),
+            "7e5f4552091a69125d5dfcb7b8c2659029395bdf"
+        );
+        assert!(matches!(
+            key_hash(&SignatureType::ETH, &[2u8; 5]),
+            Err(DeriveError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_secp256k1_lite_accounts() {
+        let generator =
+            hex::decode("0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798")
+                .unwrap();
+        assert_eq!(
+            lite_token_account_for_key(&SignatureType::BTC, &generator).unwrap(),
+            "acc://751e76e8199196d454941c45d1b3a323f1433bd632593a0f/ACME"
+        );
+        for key_type in [SignatureType::ETH, SignatureType::TypedData] {
+            let derived = lite_identity_for_key(&key_type, &generator);
+            if cfg!(feature = "secp256k1") {
+                assert_eq!(
+                    derived.unwrap(),
+                    "acc://7e5f4552091a69125d5dfcb7b8c2659029395bdfee94d755"
+                );
+            } else {
+                assert!(matches!(derived, Err(DeriveError::UnsupportedKeyType(_))));
+            }
+        }
+    }
+
+    #[test]
+    fn test_ecdsa_key_hash_needs_pkix() {
+        // PKIX DER of the P-256 key in tests/golden/keys/p256_pkcs8.pem
+        let pkix = hex::decode(
+            "3059301306072a8648ce3d020106082a8648ce3d03010703420004135269ecc63e57d0076d864c2a\
+             d97c16d05faa2df36b7f18a17715d786c4bcfc814b94f2500d64467116671c0a895879091c5c1b6de5e2b\
+             753c9c3a6f4041c5e",
+        )
+        .unwrap();
+        assert_eq!(
+            hex::encode(key_hash(&SignatureType::EcdsaSha256, &pkix).unwrap()),
+            "d188226daba55fdb21b1825e9b78313eb290e5ccbdcb8e1ffcbfbf7db88c9969"
+        );
+        assert!(matches!(
+            key_hash(&SignatureType::EcdsaSha256, &pkix[26..]),
+            Err(DeriveError::InvalidKey(_))
+        ));
+    }
+
+    #[test]
+    fn test_eth_address_lite_identity() {
+        let lite = "acc://7e5f4552091a69125d5dfcb7b8c2659029395bdfee94d755";
+        for address in [
+            "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf",
+            "7E5F4552091A69125D5DFCB7B8C2659029395BDF",
+            "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+        ] {
+            assert_eq!(lite_identity_for_eth_address(address).unwrap(), lite);
+        }
+        for address in ["0x7e5f", "0xzz5f4552091a69125d5dfcb7b8c2659029395bdf"] {
+            assert!(matches!(
+                parse_eth_address(address),
+                Err(DeriveError::InvalidAddress(_))
+            ));
+        }
+    }
+
+    #[cfg(feature = "secp256k1")]
+    #[test]
+    fn test_eth_checksum() {
+        let address = parse_eth_address("0x7e5f4552091a69125d5dfcb7b8c2659029395bdf").unwrap();
+        assert_eq!(
+            eth_checksum_address(&address),
+            "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf"
+        );
+        // One letter's case flipped
+        assert!(matches!(
+            parse_eth_address("0x7E5F4552091A69125d5DfCb7b8C2659029395BDf"),
+            Err(DeriveError::InvalidAddress(_))
+        ));
+    }
+}
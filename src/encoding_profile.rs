@@ -0,0 +1,173 @@
+//! Encoding profiles for networks running older executors
+//!
+//! Executors before `v1-doubleHashEntries` only accept `accumulate` data
+//! entries, whose hash is the Merkle root of their parts; later executors use
+//! `doublehash` entries, which add a final SHA256 and change the writeData
+//! body hash. The SDK builds `doublehash` entries, so a transaction signed
+//! for a legacy network must have its bodies rewritten before hashing.
+//!
+//! [`EncodingProfile`] records which rules a network follows. Read it from
+//! `network-status` with [`AccumulateClient::encoding_profile`] and give it to
+//! [`SmartSigner::with_encoding_profile`](crate::helpers::SmartSigner::with_encoding_profile),
+//! which applies it to every body it signs.
+
+use crate::client::AccumulateClient;
+use crate::generated::enums::ExecutorVersion;
+use crate::json_rpc_client::JsonRpcError;
+use crate::types::{NetworkStatusOptions, V3NetworkStatus};
+use serde_json::Value;
+use std::borrow::Cow;
+
+/// Hashing rules a network's executor expects
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EncodingProfile {
+    /// Executor v1 before `v1-doubleHashEntries`: `accumulate` data entries
+    Legacy,
+    /// `v1-doubleHashEntries` and later: `doublehash` data entries
+    #[default]
+    Current,
+}
+
+impl EncodingProfile {
+    /// Profile for an executor version
+    #[must_use]
+    pub const fn from_executor_version(version: &ExecutorVersion) -> Self {
+        match version {
+            ExecutorVersion::V1 | ExecutorVersion::V1SignatureAnchoring => Self::Legacy,
+            _ => Self::Current,
+        }
+    }
+
+    /// Profile for a network, from its `network-status`
+    ///
+    /// Transactions execute on the BVNs, so the network counts as legacy
+    /// when the directory or any BVN still runs a legacy executor. Missing or
+    /// unrecognized versions are treated as current.
+    #[must_use]
+    pub fn from_network_status(status: &V3NetworkStatus) -> Self {
+        let versions = status.executor_version.iter().map(String::as_str).chain(
+            status
+                .bvn_executor_versions
+                .iter()
+                .map(|p| p.version.as_str()),
+        );
+        for version in versions {
+            let parsed = serde_json::from_value(Value::String(version.to_string()));
+            if matches!(
+                parsed.as_ref().map(Self::from_executor_version),
+                Ok(Self::Legacy)
+            ) {
+                return Self::Legacy;
+            }
+        }
+        Self::Current
+    }
+
+    /// Data entry type bodies must use under this profile
+    #[must_use]
+    pub const fn data_entry_type(self) -> &'static str {
+        match self {
+            Self::Legacy => "accumulate",
+            Self::Current => "doublehash",
+        }
+    }
+
+    /// Adapt a transaction body to this profile
+    ///
+    /// Under [`EncodingProfile::Legacy`], `doublehash` entries in writeData
+    /// and writeDataTo bodies become `accumulate` entries; everything else is
+    /// returned unchanged.
+    #[must_use]
+    pub fn apply(self, body: &Value) -> Cow<'_, Value> {
+        let is_write = matches!(
+            body.get("type").and_then(Value::as_str),
+            Some("writeData" | "writeDataTo")
+        );
+        let entry_type = body
+            .get("entry")
+            .and_then(|e| e.get("type"))
+            .and_then(Value::as_str);
+        if self != Self::Legacy || !is_write || entry_type != Some("doublehash") {
+            return Cow::Borrowed(body);
+        }
+
+        let mut body = body.clone();
+        body["entry"]["type"] = Value::from(self.data_entry_type());
+        Cow::Owned(body)
+    }
+}
+
+impl AccumulateClient {
+    /// Fetch the encoding profile of the connected network
+    pub async fn encoding_profile(&self) -> Result<EncodingProfile, JsonRpcError> {
+        let status = self.network_status(NetworkStatusOptions::default()).await?;
+        Ok(EncodingProfile::from_network_status(&status))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::compute_body_hash;
+    use serde_json::json;
+
+    fn status(executor: &str, bvns: &[&str]) -> V3NetworkStatus {
+        serde_json::from_value(json!({
+            "executorVersion": executor,
+            "bvnExecutorVersions": bvns
+                .iter()
+                .enumerate()
+                .map(|(i, v)| json!({"partition": format!("BVN{i}"), "version": v}))
+                .collect::<Vec<_>>(),
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_profile_from_network_status() {
+        assert_eq!(
+            EncodingProfile::from_network_status(&status("v2Jiuquan", &["v2Jiuquan"])),
+            EncodingProfile::Current
+        );
+        assert_eq!(
+            EncodingProfile::from_network_status(&status("v1DoubleHashEntries", &[])),
+            EncodingProfile::Current
+        );
+        assert_eq!(
+            EncodingProfile::from_network_status(&status("v1SignatureAnchoring", &[])),
+            EncodingProfile::Legacy
+        );
+        assert_eq!(
+            EncodingProfile::from_network_status(&status("v1DoubleHashEntries", &["v1"])),
+            EncodingProfile::Legacy
+        );
+        assert_eq!(
+            EncodingProfile::from_network_status(&status("v9Unknown", &[])),
+            EncodingProfile::Current
+        );
+    }
+
+    #[test]
+    fn test_legacy_profile_rewrites_write_data() {
+        let body =
+            json!({"type": "writeData", "entry": {"type": "doublehash", "data": ["68656c6c6f"]}});
+
+        let current = EncodingProfile::Current.apply(&body);
+        assert!(matches!(current, Cow::Borrowed(_)));
+
+        let legacy = EncodingProfile::Legacy.apply(&body);
+        assert_eq!(legacy["entry"]["type"], "accumulate");
+        assert_eq!(legacy["entry"]["data"], body["entry"]["data"]);
+        assert_ne!(
+            compute_body_hash(&legacy).unwrap(),
+            compute_body_hash(&body).unwrap()
+        );
+
+        let send = json!({"type": "sendTokens", "to": []});
+        assert!(matches!(
+            EncodingProfile::Legacy.apply(&send),
+            Cow::Borrowed(_)
+        ));
+    }
+}
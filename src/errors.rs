@@ -2,32 +2,50 @@
 
 #![allow(missing_docs)]
 
+use crate::json_rpc_client::JsonRpcError;
 use thiserror::Error;
 
 /// Main error type for the Accumulate SDK
+///
+/// Every failure the SDK reports converts into this type, so callers can
+/// branch on the cause: retry [`Transport`](Self::Transport) and
+/// [`Timeout`](Self::Timeout) failures, fix the input on
+/// [`Validation`](Self::Validation), and inspect the node's answer on
+/// [`Rpc`](Self::Rpc).
 #[derive(Error, Debug)]
 pub enum Error {
-    #[error("Signature error: {0}")]
-    Signature(#[from] SignatureError),
+    /// The node could not be reached or the connection failed
+    #[error("Transport error: {0}")]
+    Transport(String),
+
+    /// The node answered with a JSON-RPC error
+    #[error("RPC error: code={code}, message={message}")]
+    Rpc {
+        code: i32,
+        message: String,
+        /// Error details the node attached, if any
+        data: Option<serde_json::Value>,
+    },
+
+    #[error("Validation error: {0}")]
+    Validation(#[from] ValidationError),
+
+    #[error("Signing error: {0}")]
+    Signing(#[from] SignatureError),
+
+    /// A request or a wait for confirmation ran out of time
+    #[error("Timed out: {0}")]
+    Timeout(String),
 
     #[error("Encoding error: {0}")]
     Encoding(String),
 
-    #[error("Network error: {0}")]
-    Network(String),
-
     #[error("JSON error: {0}")]
     Json(#[from] serde_json::Error),
 
     #[error("IO error: {0}")]
     Io(#[from] std::io::Error),
 
-    #[error("RPC error: code={code}, message={message}")]
-    Rpc { code: i32, message: String },
-
-    #[error("Validation error: {0}")]
-    Validation(#[from] ValidationError),
-
     #[error("General error: {0}")]
     General(String),
 }
@@ -100,6 +118,84 @@ impl From<&str> for Error {
 impl Error {
     /// Create an RPC error with code and message
     pub fn rpc(code: i32, message: String) -> Self {
-        Error::Rpc { code, message }
+        Self::Rpc {
+            code,
+            message,
+            data: None,
+        }
     }
-}
\ No newline at end of file
+
+    /// JSON-RPC error code, for [`Rpc`](Self::Rpc) errors
+    #[must_use]
+    pub const fn rpc_code(&self) -> Option<i32> {
+        match self {
+            Self::Rpc { code, .. } => Some(*code),
+            _ => None,
+        }
+    }
+
+    /// Whether repeating the same call may succeed
+    ///
+    /// True for transport failures, timeouts and 5xx answers; a request the
+    /// node rejected will be rejected again.
+    #[must_use]
+    pub const fn is_retryable(&self) -> bool {
+        match self {
+            Self::Transport(_) | Self::Timeout(_) => true,
+            Self::Rpc { code, .. } => *code >= 500 && *code < 600,
+            _ => false,
+        }
+    }
+}
+
+impl From<JsonRpcError> for Error {
+    fn from(e: JsonRpcError) -> Self {
+        match e {
+            JsonRpcError::Http(e) if e.is_timeout() => Self::Timeout(e.to_string()),
+            JsonRpcError::Http(e) => Self::Transport(e.to_string()),
+            JsonRpcError::Json(e) => Self::Json(e),
+            JsonRpcError::Rpc {
+                code,
+                message,
+                data,
+            } => Self::Rpc {
+                code,
+                message,
+                data,
+            },
+            JsonRpcError::InvalidUrl(e) => ValidationError::InvalidUrl(e.to_string()).into(),
+            JsonRpcError::General(message) => Self::General(message),
+        }
+    }
+}
+
+impl From<url::ParseError> for Error {
+    fn from(e: url::ParseError) -> Self {
+        ValidationError::InvalidUrl(e.to_string()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_rpc_errors_convert_by_cause() {
+        let rpc = JsonRpcError::Rpc {
+            code: 503,
+            message: "unavailable".to_string(),
+            data: Some(serde_json::json!({"retry": true})),
+        };
+        let err = Error::from(rpc);
+        assert_eq!(err.rpc_code(), Some(503));
+        assert!(err.is_retryable());
+        assert!(matches!(err, Error::Rpc { data: Some(_), .. }));
+
+        let err = Error::from(JsonRpcError::InvalidUrl(url::ParseError::EmptyHost));
+        assert!(matches!(err, Error::Validation(ValidationError::InvalidUrl(_))));
+        assert!(!err.is_retryable());
+
+        let err = Error::from(JsonRpcError::General("no endpoints configured".to_string()));
+        assert_eq!(err.to_string(), "General error: no endpoints configured");
+    }
+}
@@ -0,0 +1,199 @@
+//! Ethereum address lookup
+//!
+//! An Ethereum account's address is the key hash Accumulate records for its
+//! secp256k1 key, so an ETH-native user can find their Accumulate accounts
+//! from the address alone, without the public key.
+//! [`AccumulateClient::find_eth_account`] derives the address's lite
+//! identity, checks whether it exists, and searches the given key books for
+//! pages that hold the key:
+//!
+//! ```no_run
+//! # async fn run(client: &accumulate_client::AccumulateClient) -> Result<(), Box<dyn std::error::Error>> {
+//! let account = client
+//!     .find_eth_account(
+//!         "0x7E5F4552091A69125d5DfCb7b8C2659029395Bdf",
+//!         &["acc://alice.acme/book"],
+//!     )
+//!     .await?;
+//! println!("lite identity {}", account.lite_identity);
+//! for page in &account.key_pages {
+//!     println!("key {} on {}", page.index, page.signer);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The network has no reverse index from a key hash to the books that hold
+//! it, so only the books passed in are searched.
+
+use crate::accounts::{AccountState, LiteIdentity};
+use crate::client::AccumulateClient;
+use crate::derive::{lite_identity_for_hash, parse_eth_address, DeriveError};
+use crate::json_rpc_client::JsonRpcError;
+use crate::query::KeyRecord;
+use crate::types::PublicKeyHashSearchQuery;
+use thiserror::Error;
+
+/// Why an Ethereum address could not be looked up
+#[derive(Error, Debug)]
+pub enum EthLookupError {
+    /// The address is malformed
+    #[error(transparent)]
+    Address(#[from] DeriveError),
+
+    /// A query failed
+    #[error(transparent)]
+    Network(#[from] JsonRpcError),
+}
+
+/// Accumulate accounts of an Ethereum address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthAccount {
+    /// The address, EIP-55 checksummed when the `secp256k1` feature is on
+    pub address: String,
+    /// Key hash recorded on key pages (hex)
+    pub key_hash: String,
+    /// Lite identity URL
+    pub lite_identity: String,
+    /// ACME lite token account URL
+    pub lite_token_account: String,
+    /// The lite identity's state; `None` until it has received tokens
+    pub lite_identity_state: Option<LiteIdentity>,
+    /// Key page entries holding the key, across the books searched
+    pub key_pages: Vec<KeyRecord>,
+}
+
+impl EthAccount {
+    /// Whether the lite identity exists on the network
+    #[must_use]
+    pub const fn lite_identity_exists(&self) -> bool {
+        self.lite_identity_state.is_some()
+    }
+}
+
+impl AccumulateClient {
+    /// Find the lite identity of the Ethereum `address` and the key pages in
+    /// `books` that hold its key
+    pub async fn find_eth_account(
+        &self,
+        address: &str,
+        books: &[&str],
+    ) -> Result<EthAccount, EthLookupError> {
+        let hash = parse_eth_address(address)?;
+        let key_hash = hex::encode(hash);
+        let lite_identity = lite_identity_for_hash(&hash);
+
+        let lite_identity_state = match self.v3().query_account(&lite_identity).await {
+            Ok(record) => match record.state() {
+                Ok(AccountState::LiteIdentity(state)) => Some(state),
+                _ => None,
+            },
+            Err(e) if e.is_not_found() => None,
+            Err(e) => return Err(e.into()),
+        };
+
+        let mut key_pages = Vec::new();
+        for book in books {
+            let query = PublicKeyHashSearchQuery {
+                public_key_hash: key_hash.clone(),
+            };
+            match self.v3().search_by_public_key_hash(book, query).await {
+                Ok(range) => key_pages.extend(range.records),
+                Err(e) if e.is_not_found() => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        #[cfg(feature = "secp256k1")]
+        let address = crate::derive::eth_checksum_address(&hash);
+        #[cfg(not(feature = "secp256k1"))]
+        let address = format!("0x{key_hash}");
+        Ok(EthAccount {
+            address,
+            lite_token_account: format!("{lite_identity}/ACME"),
+            key_hash,
+            lite_identity,
+            lite_identity_state,
+            key_pages,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::{QuickStart, SmartSigner, TxBody};
+    use crate::simulator::SimulatedNetwork;
+    use crate::ACME_BASE_UNITS;
+    use std::sync::Arc;
+
+    const ADDRESS: &str = "0x7e5f4552091a69125d5dfcb7b8c2659029395bdf";
+
+    #[tokio::test]
+    async fn test_find_eth_account_against_simulator() {
+        let network = SimulatedNetwork::new();
+        let quick = QuickStart::from_client(Arc::new(network.client().await.unwrap()));
+        let client = quick.client();
+
+        // Neither the lite identity nor the book exists yet
+        let account = client
+            .find_eth_account(ADDRESS, &["acc://eth.acme/book"])
+            .await
+            .unwrap();
+        assert_eq!(
+            account.lite_identity,
+            "acc://7e5f4552091a69125d5dfcb7b8c2659029395bdfee94d755"
+        );
+        assert!(!account.lite_identity_exists());
+        assert!(account.key_pages.is_empty());
+
+        let wallet = quick.create_wallet();
+        network
+            .fund(&wallet.lite_token_account, 100 * ACME_BASE_UNITS)
+            .unwrap();
+        let adi = quick.setup_adi(&wallet, "eth").await.unwrap();
+        assert!(
+            quick
+                .buy_credits_for_adi(&wallet, &adi, 100)
+                .await
+                .unwrap()
+                .success
+        );
+        let mut signer = SmartSigner::new(client, adi.keypair().clone(), &adi.key_page_url);
+        let added = signer
+            .sign_submit_and_wait(
+                &adi.key_page_url,
+                &TxBody::update_key_page_add_key(&parse_eth_address(ADDRESS).unwrap()),
+                None,
+                5,
+            )
+            .await;
+        assert!(added.success, "{:?}", added.error);
+        network
+            .fund(&account.lite_token_account, ACME_BASE_UNITS)
+            .unwrap();
+
+        let account = client
+            .find_eth_account(
+                ADDRESS.to_uppercase().trim_start_matches("0X"),
+                &[&adi.key_book_url],
+            )
+            .await
+            .unwrap();
+        assert!(account.lite_identity_exists());
+        assert_eq!(account.key_pages.len(), 1);
+        assert_eq!(account.key_pages[0].signer, adi.key_page_url);
+        assert_eq!(account.key_pages[0].index, 1);
+    }
+
+    #[tokio::test]
+    async fn test_find_eth_account_rejects_bad_address() {
+        let network = SimulatedNetwork::new();
+        let client = network.client().await.unwrap();
+        assert!(matches!(
+            client.find_eth_account("0x1234", &[]).await,
+            Err(EthLookupError::Address(DeriveError::InvalidAddress(_)))
+        ));
+    }
+}
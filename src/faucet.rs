@@ -0,0 +1,429 @@
+//! Typed faucet failures
+//!
+//! Faucets report failures as free-form JSON-RPC errors or as a submission
+//! with `success: false`. [`FaucetError::classify`] sorts them into the cases
+//! a caller acts on differently: back off and retry when rate limited, switch
+//! faucets when this one is drained, and fix the request when the account is
+//! rejected.
+//!
+//! [`AccumulateClient::faucet_with`] sends several requests, retries the
+//! retryable failures and can wait until the balance shows the deposits:
+//!
+//! ```no_run
+//! # async fn run(client: &accumulate_client::AccumulateClient) -> Result<(), accumulate_client::faucet::FaucetError> {
+//! use accumulate_client::faucet::FaucetOptions;
+//!
+//! let account = "acc://0143b52490530b90eef9b1a2405e784a0c2f6bf4ae6b9e48/ACME";
+//! let report = client
+//!     .faucet_with(account, FaucetOptions { times: 3, ..Default::default() })
+//!     .await?;
+//! println!("{:?} -> balance {:?}", report.txids(), report.balance);
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::AccumulateClient;
+use crate::helpers::{confirmation_deadline, query_balance};
+use crate::json_rpc_client::JsonRpcError;
+use crate::progress::{NoProgress, ProgressReporter, ProgressStep};
+use crate::types::{V3FaucetOptions, V3Submission};
+use std::time::Duration;
+use thiserror::Error;
+
+/// JSON-RPC code for a method the node does not serve
+const METHOD_NOT_FOUND: i32 = -32601;
+
+/// Why a faucet request failed
+#[derive(Error, Debug)]
+pub enum FaucetError {
+    /// Too many requests; retry later
+    #[error("Faucet rate limited: {message}")]
+    RateLimited {
+        /// Delay the faucet asked for, if it named one
+        retry_after: Option<Duration>,
+        /// Faucet message
+        message: String,
+    },
+
+    /// The faucet has no tokens left to give
+    #[error("Faucet is empty: {message}")]
+    Empty {
+        /// Faucet message
+        message: String,
+    },
+
+    /// The faucet refused the account (malformed, or not a lite token account)
+    #[error("Faucet rejected account {account}: {message}")]
+    InvalidAccount {
+        /// Account the tokens were requested for
+        account: String,
+        /// Faucet message
+        message: String,
+    },
+
+    /// The node has no faucet or could not be reached
+    #[error("Faucet unavailable: {0}")]
+    Unavailable(String),
+
+    /// Any other failure
+    #[error("Faucet error: code={code}, message={message}")]
+    Other {
+        /// JSON-RPC error code, or 0 for a failed submission
+        code: i32,
+        /// Faucet message
+        message: String,
+    },
+}
+
+impl FaucetError {
+    /// Classify a faucet failure message for `account`
+    #[must_use]
+    pub fn classify(account: &str, code: i32, message: &str) -> Self {
+        let lower = message.to_ascii_lowercase();
+        let has = |needles: &[&str]| needles.iter().any(|n| lower.contains(n));
+        let message = message.to_string();
+
+        if code == 429 || has(&["rate limit", "too many requests", "try again"]) {
+            Self::RateLimited {
+                retry_after: parse_retry_after(&lower),
+                message,
+            }
+        } else if has(&[
+            "insufficient balance",
+            "insufficient funds",
+            "faucet is empty",
+            "drained",
+        ]) {
+            Self::Empty { message }
+        } else if has(&[
+            "invalid lite",
+            "not a lite",
+            "invalid url",
+            "invalid account",
+            "malformed",
+        ]) {
+            Self::InvalidAccount {
+                account: account.to_string(),
+                message,
+            }
+        } else if code == METHOD_NOT_FOUND || has(&["faucet is not enabled", "no faucet"]) {
+            Self::Unavailable(message)
+        } else {
+            Self::Other { code, message }
+        }
+    }
+
+    /// Classify an error returned by a faucet call for `account`
+    #[must_use]
+    pub fn from_rpc_error(account: &str, error: JsonRpcError) -> Self {
+        match error {
+            JsonRpcError::Rpc { code, message, .. } => Self::classify(account, code, &message),
+            JsonRpcError::Http(e) => Self::Unavailable(e.to_string()),
+            other => Self::Other {
+                code: -1,
+                message: other.to_string(),
+            },
+        }
+    }
+
+    /// Whether repeating the same request later may succeed
+    #[must_use]
+    pub const fn is_retryable(&self) -> bool {
+        matches!(self, Self::RateLimited { .. } | Self::Unavailable(_))
+    }
+
+    /// How long to wait before retrying, when the failure is retryable
+    ///
+    /// Uses the delay the faucet named, or `fallback`.
+    #[must_use]
+    pub fn backoff(&self, fallback: Duration) -> Option<Duration> {
+        match self {
+            Self::RateLimited { retry_after, .. } => Some(retry_after.unwrap_or(fallback)),
+            Self::Unavailable(_) => Some(fallback),
+            _ => None,
+        }
+    }
+}
+
+impl From<FaucetError> for JsonRpcError {
+    fn from(error: FaucetError) -> Self {
+        Self::General(error.to_string())
+    }
+}
+
+/// Read a delay such as "retry after 30s" or "try again in 5 seconds"
+fn parse_retry_after(message: &str) -> Option<Duration> {
+    let rest = ["retry after", "try again in", "retry in"]
+        .iter()
+        .find_map(|marker| message.split_once(marker).map(|(_, rest)| rest))?;
+    let digits: String = rest
+        .trim_start()
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok().map(Duration::from_secs)
+}
+
+/// How [`AccumulateClient::faucet_with`] requests tokens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FaucetOptions {
+    /// Requests to send
+    pub times: u32,
+    /// Wait until the balance has grown, within the client's
+    /// `confirmation_timeout`
+    pub wait: bool,
+    /// Pause between requests
+    pub interval: Duration,
+    /// Retries of rate-limited or unavailable requests, over all requests
+    pub max_retries: u32,
+    /// Delay before a retry when the faucet names none
+    pub retry_delay: Duration,
+}
+
+impl Default for FaucetOptions {
+    fn default() -> Self {
+        Self {
+            times: 1,
+            wait: true,
+            interval: Duration::from_secs(2),
+            max_retries: 5,
+            retry_delay: Duration::from_secs(2),
+        }
+    }
+}
+
+/// A request the faucet granted
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaucetResult {
+    /// Transaction ID of the faucet transaction, if the faucet reported one
+    pub txid: Option<String>,
+    /// The faucet's submission
+    pub submission: V3Submission,
+}
+
+/// What [`AccumulateClient::faucet_with`] did
+#[derive(Debug, Clone, PartialEq)]
+pub struct FaucetReport {
+    /// Granted requests, in order
+    pub requests: Vec<FaucetResult>,
+    /// Balance in base units once it grew; `None` when not waited for or
+    /// not confirmed in time
+    pub balance: Option<u64>,
+}
+
+impl FaucetReport {
+    /// Transaction IDs of the faucet transactions
+    #[must_use]
+    pub fn txids(&self) -> Vec<&str> {
+        self.requests
+            .iter()
+            .filter_map(|r| r.txid.as_deref())
+            .collect()
+    }
+}
+
+impl AccumulateClient {
+    /// Request faucet tokens for `account` `options.times` times
+    ///
+    /// Rate-limited and unavailable requests are retried after the delay the
+    /// faucet asks for; an empty faucet or a rejected account fails. With
+    /// `options.wait`, the balance is then polled until it exceeds the
+    /// balance before the first request.
+    pub async fn faucet_with(
+        &self,
+        account: &str,
+        options: FaucetOptions,
+    ) -> Result<FaucetReport, FaucetError> {
+        self.faucet_reporting(account, options, &NoProgress, "faucet")
+            .await
+    }
+
+    /// [`Self::faucet_with`], reporting each request and the balance check
+    /// as steps of `flow`
+    pub(crate) async fn faucet_reporting(
+        &self,
+        account: &str,
+        options: FaucetOptions,
+        progress: &dyn ProgressReporter,
+        flow: &'static str,
+    ) -> Result<FaucetReport, FaucetError> {
+        let total = options.times + u32::from(options.wait);
+        let start_balance = if options.wait {
+            query_balance(self, account).await.unwrap_or(0)
+        } else {
+            0
+        };
+
+        let mut requests = Vec::new();
+        let mut retries = 0;
+        let mut i = 0;
+        while i < options.times {
+            let step = ProgressStep::new(flow, "faucet request")
+                .of(i + 1, total)
+                .on(account);
+            progress.started(&step);
+            match self.request_faucet(account).await {
+                Ok(submission) => {
+                    let txid = submission
+                        .status
+                        .as_ref()
+                        .and_then(|s| s.get("txID"))
+                        .and_then(serde_json::Value::as_str)
+                        .map(str::to_string);
+                    progress.completed(&step, txid.as_deref().unwrap_or("submitted"));
+                    requests.push(FaucetResult { txid, submission });
+                }
+                Err(e) => match e.backoff(options.retry_delay) {
+                    Some(delay) if retries < options.max_retries => {
+                        retries += 1;
+                        progress.failed(&step, &format!("{e} (retrying in {delay:?})"));
+                        tokio::time::sleep(delay).await;
+                        continue;
+                    }
+                    _ => {
+                        progress.failed(&step, &e.to_string());
+                        return Err(e);
+                    }
+                },
+            }
+            i += 1;
+            if i < options.times {
+                tokio::time::sleep(options.interval).await;
+            }
+        }
+
+        let mut balance = None;
+        if options.wait {
+            let step = ProgressStep::new(flow, "confirm balance")
+                .of(total, total)
+                .on(account);
+            progress.started(&step);
+            let deadline = confirmation_deadline(self);
+            loop {
+                let current = query_balance(self, account).await.unwrap_or(0);
+                if current > start_balance {
+                    progress.completed(&step, &format!("balance {current}"));
+                    balance = Some(current);
+                    break;
+                }
+                if tokio::time::Instant::now() >= deadline {
+                    progress.failed(&step, "account balance not confirmed yet");
+                    break;
+                }
+                progress.waiting(&step, &format!("balance {current}, waiting for deposits"));
+                tokio::time::sleep(self.options.poll_interval).await;
+            }
+        }
+
+        Ok(FaucetReport { requests, balance })
+    }
+
+    /// Request faucet tokens for `account` (V3 API), with typed failures
+    ///
+    /// A submission the faucet reports as unsuccessful is an error too.
+    pub async fn request_faucet(&self, account: &str) -> Result<V3Submission, FaucetError> {
+        let submission = self
+            .faucet_v3(account, V3FaucetOptions::default())
+            .await
+            .map_err(|e| FaucetError::from_rpc_error(account, e))?;
+        let failed = submission
+            .status
+            .as_ref()
+            .and_then(|s| s.get("error"))
+            .is_some_and(|e| !e.is_null());
+        if failed || (!submission.success && !submission.message.is_empty()) {
+            let message = if submission.message.is_empty() {
+                submission
+                    .status
+                    .as_ref()
+                    .and_then(|s| s.pointer("/error/message"))
+                    .and_then(serde_json::Value::as_str)
+                    .unwrap_or("submission failed")
+                    .to_string()
+            } else {
+                submission.message
+            };
+            return Err(FaucetError::classify(account, 0, &message));
+        }
+        Ok(submission)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const ACCOUNT: &str = "acc://0143b52490530b90eef9b1a2405e784a0c2f6bf4ae6b9e48/ACME";
+
+    #[test]
+    fn test_classify_faucet_messages() {
+        let err = FaucetError::classify(ACCOUNT, -32000, "Rate limited, retry after 30s");
+        assert!(matches!(
+            err,
+            FaucetError::RateLimited { retry_after: Some(d), .. } if d == Duration::from_secs(30)
+        ));
+        assert!(err.is_retryable());
+
+        let err = FaucetError::classify(ACCOUNT, 429, "slow down");
+        assert_eq!(
+            err.backoff(Duration::from_secs(4)),
+            Some(Duration::from_secs(4))
+        );
+
+        let err = FaucetError::classify(ACCOUNT, -33000, "insufficient balance: faucet has 0");
+        assert!(matches!(err, FaucetError::Empty { .. }));
+        assert!(!err.is_retryable());
+
+        let err = FaucetError::classify(
+            "acc://alice.acme",
+            -32602,
+            "acc://alice.acme is not a lite token account",
+        );
+        assert!(
+            matches!(err, FaucetError::InvalidAccount { ref account, .. } if account == "acc://alice.acme")
+        );
+        assert_eq!(err.backoff(Duration::from_secs(1)), None);
+
+        assert!(matches!(
+            FaucetError::classify(ACCOUNT, METHOD_NOT_FOUND, "Method not found"),
+            FaucetError::Unavailable(_)
+        ));
+        assert!(matches!(
+            FaucetError::classify(ACCOUNT, -32000, "something odd"),
+            FaucetError::Other { code: -32000, .. }
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_faucet_with_waits_for_balance() {
+        use crate::simulator::{SimulatedNetwork, FAUCET_AMOUNT};
+
+        let network = SimulatedNetwork::new();
+        let client = network.client().await.unwrap();
+        let options = FaucetOptions {
+            times: 2,
+            interval: Duration::ZERO,
+            ..Default::default()
+        };
+        let report = client.faucet_with(ACCOUNT, options).await.unwrap();
+        assert_eq!(report.txids().len(), 2);
+        assert_eq!(report.balance, Some(2 * FAUCET_AMOUNT));
+
+        let options = FaucetOptions {
+            wait: false,
+            ..Default::default()
+        };
+        let report = client.faucet_with(ACCOUNT, options).await.unwrap();
+        assert_eq!((report.requests.len(), report.balance), (1, None));
+    }
+
+    #[test]
+    fn test_parse_retry_after() {
+        assert_eq!(
+            parse_retry_after("please try again in 5 seconds"),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(parse_retry_after("rate limited"), None);
+    }
+}
@@ -0,0 +1,189 @@
+//! Credit cost of a transaction before it is submitted
+//!
+//! The protocol charges each transaction a fee by type (with extras for
+//! further recipients, keys, operations and data), a surcharge for each 256
+//! bytes past the first, and a fee per signature. The constants re-exported
+//! here mirror that schedule; [`estimate`] prices a typed
+//! [`TransactionBody`] with a [`NetworkGlobals`] value, and
+//! [`AccumulateClient::estimate_fee`] does the same with the network's
+//! current globals and oracle, so a wallet can show the cost in credits and
+//! in ACME:
+//!
+//! ```no_run
+//! # async fn run(
+//! #     client: &accumulate_client::AccumulateClient,
+//! #     body: &accumulate_client::generated::transactions::TransactionBody,
+//! # ) -> Result<(), accumulate_client::errors::Error> {
+//! let estimate = client.estimate_fee(body).await?;
+//! println!("{} credits", estimate.credits());
+//! if let Some(acme) = estimate.acme() {
+//!     println!("about {} ACME of credits", acme.to_acme());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Estimates are computed locally; what the network charges may differ if
+//! its fee rules change.
+
+use crate::amounts::{Amount, ACME_BASE_UNITS};
+use crate::builders::to_wire;
+use crate::client::AccumulateClient;
+use crate::errors::Error;
+use crate::generated::transactions::TransactionBody;
+use crate::globals::NetworkGlobals;
+use crate::types::NetworkStatusOptions;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+pub use crate::globals::fees::*;
+
+/// Principal assumed by [`AccumulateClient::estimate_fee`] for the size
+/// surcharge; a longer principal can add [`DATA`] per 256 bytes
+pub const NOMINAL_PRINCIPAL: &str = "acc://estimate.acme/book/1";
+
+/// Estimated cost of one transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FeeEstimate {
+    /// Transaction type, e.g. `"sendTokens"`
+    pub transaction_type: String,
+    /// Fee for the transaction type and its contents, in credit hundredths
+    pub base: u64,
+    /// Surcharge for the transaction's size, in credit hundredths
+    pub size_surcharge: u64,
+    /// Signatures paid for
+    pub signatures: u64,
+    /// Oracle price used for [`FeeEstimate::acme`], when known
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub oracle: Option<u64>,
+}
+
+impl FeeEstimate {
+    /// Pay for `count` signatures instead of one, e.g. for a multisig page
+    #[must_use]
+    pub const fn with_signatures(mut self, count: u64) -> Self {
+        self.signatures = count;
+        self
+    }
+
+    /// Total cost in credit hundredths (100 = 1 credit)
+    #[must_use]
+    pub const fn total(&self) -> u64 {
+        self.base + self.size_surcharge + SIGNATURE * self.signatures
+    }
+
+    /// Total cost in credits
+    #[must_use]
+    #[allow(clippy::cast_precision_loss)]
+    pub fn credits(&self) -> f64 {
+        self.total() as f64 / 100.0
+    }
+
+    /// ACME needed to buy the credits at the oracle price, rounded up
+    #[must_use]
+    pub fn acme(&self) -> Option<Amount> {
+        let oracle = u128::from(self.oracle.filter(|&price| price > 0)?);
+        let units = u128::from(self.total()) * u128::from(ACME_BASE_UNITS);
+        Some(Amount::base_units((units + oracle - 1) / oracle))
+    }
+}
+
+/// Price `body` signed once, sent by `principal`, under `globals`
+///
+/// The estimate has no oracle price; set [`FeeEstimate::oracle`] to get
+/// [`FeeEstimate::acme`].
+pub fn estimate(globals: &NetworkGlobals, principal: &str, body: &TransactionBody) -> Result<FeeEstimate, Error> {
+    let body = to_wire(body)?;
+    let header = json!({ "principal": principal });
+    let (base, size_surcharge) = globals.fee_parts(&header, &body)?;
+    Ok(FeeEstimate {
+        transaction_type: body["type"].as_str().unwrap_or_default().to_string(),
+        base,
+        size_surcharge,
+        signatures: 1,
+        oracle: None,
+    })
+}
+
+impl AccumulateClient {
+    /// Estimate the cost of `body` with the network's current fee schedule
+    /// and oracle price
+    ///
+    /// The size surcharge assumes [`NOMINAL_PRINCIPAL`]; use [`estimate`]
+    /// with the real principal when the exact figure matters.
+    pub async fn estimate_fee(&self, body: &TransactionBody) -> Result<FeeEstimate, Error> {
+        let status = self.network_status(NetworkStatusOptions::default()).await?;
+        let globals = NetworkGlobals::from_network_status(&status);
+        let mut estimate = estimate(&globals, NOMINAL_PRINCIPAL, body)?;
+        estimate.oracle = status.oracle.map(|oracle| oracle.price);
+        Ok(estimate)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::generated::transactions::{CreateIdentityBody, SendTokensBody, WriteDataBody};
+    use crate::mock::MockTransport;
+
+    #[test]
+    fn test_estimate_typed_bodies() {
+        let globals = NetworkGlobals::default();
+        let send = TransactionBody::SendTokens(SendTokensBody {
+            hash: None,
+            meta: None,
+            to: vec![
+                json!({"url": "acc://bob.acme/tokens", "amount": "1"}),
+                json!({"url": "acc://carol.acme/tokens", "amount": "2"}),
+            ],
+        });
+        let fee = estimate(&globals, "acc://alice.acme/tokens", &send).unwrap();
+        assert_eq!(fee.transaction_type, "sendTokens");
+        assert_eq!((fee.base, fee.size_surcharge), (TRANSFER_TOKENS + TRANSFER_TOKENS_EXTRA, 0));
+        assert_eq!(fee.total(), 401);
+        assert_eq!(fee.clone().with_signatures(3).total(), 403);
+        assert_eq!(fee.acme(), None);
+
+        let identity = TransactionBody::CreateIdentity(CreateIdentityBody {
+            url: "acc://alice.acme".to_string(),
+            key_hash: Some(vec![7; 32]),
+            key_book_url: Some("acc://alice.acme/book".to_string()),
+            authorities: None,
+        });
+        let fee = estimate(&globals, "acc://lite/1234", &identity).unwrap();
+        assert_eq!(fee.base, CREATE_IDENTITY);
+
+        // 600 bytes of data: three 256-byte chunks plus the size surcharge
+        let write = TransactionBody::WriteData(WriteDataBody {
+            entry: json!({"type": "doubleHash", "data": ["00".repeat(600)]}),
+            scratch: None,
+            write_to_state: None,
+        });
+        let fee = estimate(&globals, "acc://alice.acme/data", &write).unwrap();
+        assert_eq!((fee.base, fee.size_surcharge), (3 * DATA, 2 * DATA));
+    }
+
+    #[tokio::test]
+    async fn test_estimate_with_network_oracle() {
+        let mock = MockTransport::new();
+        let client = mock.client().await.unwrap();
+        mock.on(
+            "network-status",
+            json!({"oracle": {"price": 500}, "globals": {"feeSchedule": {"createIdentitySliding": [1_000_000]}}}),
+        );
+        let identity = TransactionBody::CreateIdentity(CreateIdentityBody {
+            url: "acc://a.acme".to_string(),
+            key_hash: Some(vec![7; 32]),
+            key_book_url: Some("acc://a.acme/book".to_string()),
+            authorities: None,
+        });
+        let fee = client.estimate_fee(&identity).await.unwrap();
+        assert_eq!(fee.base, 1_000_000);
+        assert_eq!(fee.oracle, Some(500));
+        // 10,000.01 credits ($100.0001) at $0.05 per ACME
+        assert_eq!(fee.acme(), Some(Amount::base_units(200_000_200_000)));
+        assert!((fee.credits() - 10_000.01).abs() < 1e-9);
+    }
+}
@@ -1,6 +1,6 @@
 //! GENERATED FILE - DO NOT EDIT
 //! Source: internal/api/v2/methods.yml
-//! Generated: 2025-10-04 03:26:52
+//! Generated: 2026-10-16 10:42:17
 
 #![allow(missing_docs)]
 
@@ -28,26 +28,106 @@ impl<C> AccumulateClient<C> {
     }
 }
 
+// Query option structures
+/// General query options (`QueryOptions`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct QueryOptions {
+    /// Expand chain entries and directory listings
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub expand: Option<bool>,
+    /// Query the state at this height
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub height: Option<u64>,
+    /// Query the scratch chain instead of the main chain
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub scratch: Option<bool>,
+    /// Include a Merkle receipt
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub prove: Option<bool>,
+    /// Forward the query to the partition that owns the account
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub include_remote: Option<bool>,
+}
+
+/// Query options for paged queries (`QueryPagination` + `QueryOptions`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PagedQueryOptions {
+    /// Index of the first result
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub start: Option<u64>,
+    /// Maximum number of results
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub count: Option<u64>,
+    #[serde(flatten)]
+    pub query: QueryOptions,
+}
+
+/// Query options for transaction lookups (`TxnQuery`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TxQueryOptions {
+    /// Wait up to this many milliseconds for the transaction to appear
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub wait: Option<u64>,
+    /// Keep waiting while the transaction is pending
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub ignore_pending: Option<bool>,
+    #[serde(flatten)]
+    pub query: QueryOptions,
+}
+
+/// Query options for a single data entry (`DataEntryQuery`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DataEntryQueryOptions {
+    /// Hex hash of the entry; the latest entry when absent
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub entry_hash: Option<String>,
+    #[serde(flatten)]
+    pub query: QueryOptions,
+}
+
+/// Query options for key lookups (`KeyPageIndexQuery`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct KeyPageIndexQueryOptions {
+    /// Hex public key or key hash
+    pub key: String,
+    #[serde(flatten)]
+    pub query: QueryOptions,
+}
+
 // Parameter structures
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct StatusParams {
     // No parameters
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct VersionParams {
     // No parameters
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct DescribeParams {
     // No parameters
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct MetricsParams {
     pub url: String,
@@ -56,92 +136,104 @@ pub struct MetricsParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct FaucetParams {
     pub url: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryParams {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub options: Option<serde_json::Value>,
+    pub options: Option<QueryOptions>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryDirectoryParams {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub options: Option<serde_json::Value>,
+    pub options: Option<PagedQueryOptions>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryTxParams {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub options: Option<serde_json::Value>,
+    pub options: Option<TxQueryOptions>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryTxLocalParams {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub options: Option<serde_json::Value>,
+    pub options: Option<TxQueryOptions>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryTxHistoryParams {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub options: Option<serde_json::Value>,
+    pub options: Option<PagedQueryOptions>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryDataParams {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub options: Option<serde_json::Value>,
+    pub options: Option<DataEntryQueryOptions>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryDataSetParams {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub options: Option<serde_json::Value>,
+    pub options: Option<PagedQueryOptions>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryKeyPageIndexParams {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub options: Option<serde_json::Value>,
+    pub options: Option<KeyPageIndexQueryOptions>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryMinorBlocksParams {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub options: Option<serde_json::Value>,
+    pub options: Option<PagedQueryOptions>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryMajorBlocksParams {
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub options: Option<serde_json::Value>,
+    pub options: Option<PagedQueryOptions>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QuerySynthParams {
     #[serde(flatten)]
@@ -149,6 +241,7 @@ pub struct QuerySynthParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteParams {
     #[serde(flatten)]
@@ -156,6 +249,7 @@ pub struct ExecuteParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteDirectParams {
     #[serde(flatten)]
@@ -163,6 +257,7 @@ pub struct ExecuteDirectParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteLocalParams {
     #[serde(flatten)]
@@ -170,6 +265,7 @@ pub struct ExecuteLocalParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateAdiParams {
     #[serde(flatten)]
@@ -177,6 +273,7 @@ pub struct ExecuteCreateAdiParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateIdentityParams {
     #[serde(flatten)]
@@ -184,6 +281,7 @@ pub struct ExecuteCreateIdentityParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateDataAccountParams {
     #[serde(flatten)]
@@ -191,6 +289,7 @@ pub struct ExecuteCreateDataAccountParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateKeyBookParams {
     #[serde(flatten)]
@@ -198,6 +297,7 @@ pub struct ExecuteCreateKeyBookParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateKeyPageParams {
     #[serde(flatten)]
@@ -205,6 +305,7 @@ pub struct ExecuteCreateKeyPageParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateTokenParams {
     #[serde(flatten)]
@@ -212,6 +313,7 @@ pub struct ExecuteCreateTokenParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateTokenAccountParams {
     #[serde(flatten)]
@@ -219,6 +321,7 @@ pub struct ExecuteCreateTokenAccountParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteSendTokensParams {
     #[serde(flatten)]
@@ -226,6 +329,7 @@ pub struct ExecuteSendTokensParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteAddCreditsParams {
     #[serde(flatten)]
@@ -233,6 +337,7 @@ pub struct ExecuteAddCreditsParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteUpdateKeyPageParams {
     #[serde(flatten)]
@@ -240,6 +345,7 @@ pub struct ExecuteUpdateKeyPageParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteUpdateKeyParams {
     #[serde(flatten)]
@@ -247,6 +353,7 @@ pub struct ExecuteUpdateKeyParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteWriteDataParams {
     #[serde(flatten)]
@@ -254,6 +361,7 @@ pub struct ExecuteWriteDataParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteIssueTokensParams {
     #[serde(flatten)]
@@ -261,6 +369,7 @@ pub struct ExecuteIssueTokensParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteWriteDataToParams {
     #[serde(flatten)]
@@ -268,6 +377,7 @@ pub struct ExecuteWriteDataToParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteBurnTokensParams {
     #[serde(flatten)]
@@ -275,6 +385,7 @@ pub struct ExecuteBurnTokensParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteUpdateAccountAuthParams {
     #[serde(flatten)]
@@ -283,6 +394,7 @@ pub struct ExecuteUpdateAccountAuthParams {
 
 // Result structures
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct StatusResponse {
     pub ok: bool,
@@ -293,6 +405,7 @@ pub struct StatusResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct VersionResponse {
     #[serde(flatten)]
@@ -300,6 +413,7 @@ pub struct VersionResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct DescribeResponse {
     #[serde(flatten)]
@@ -307,6 +421,7 @@ pub struct DescribeResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct MetricsResponse {
     #[serde(flatten)]
@@ -314,6 +429,7 @@ pub struct MetricsResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct FaucetResponse {
     pub transaction_hash: String,
@@ -326,6 +442,7 @@ pub struct FaucetResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryResponse {
     #[serde(flatten)]
@@ -333,6 +450,7 @@ pub struct QueryResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryDirectoryResponse {
     #[serde(flatten)]
@@ -340,6 +458,7 @@ pub struct QueryDirectoryResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryTxResponse {
     #[serde(flatten)]
@@ -347,6 +466,7 @@ pub struct QueryTxResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryTxLocalResponse {
     #[serde(flatten)]
@@ -354,6 +474,7 @@ pub struct QueryTxLocalResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryTxHistoryResponse {
     #[serde(flatten)]
@@ -361,6 +482,7 @@ pub struct QueryTxHistoryResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryDataResponse {
     #[serde(flatten)]
@@ -368,6 +490,7 @@ pub struct QueryDataResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryDataSetResponse {
     #[serde(flatten)]
@@ -375,6 +498,7 @@ pub struct QueryDataSetResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryKeyPageIndexResponse {
     #[serde(flatten)]
@@ -382,6 +506,7 @@ pub struct QueryKeyPageIndexResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryMinorBlocksResponse {
     #[serde(flatten)]
@@ -389,6 +514,7 @@ pub struct QueryMinorBlocksResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QueryMajorBlocksResponse {
     #[serde(flatten)]
@@ -396,6 +522,7 @@ pub struct QueryMajorBlocksResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct QuerySynthResponse {
     #[serde(flatten)]
@@ -403,6 +530,7 @@ pub struct QuerySynthResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteResponse {
     pub transaction_hash: String,
@@ -415,6 +543,7 @@ pub struct ExecuteResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteDirectResponse {
     pub transaction_hash: String,
@@ -427,6 +556,7 @@ pub struct ExecuteDirectResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteLocalResponse {
     pub transaction_hash: String,
@@ -439,96 +569,112 @@ pub struct ExecuteLocalResponse {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateAdiResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateIdentityResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateDataAccountResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateKeyBookResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateKeyPageResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateTokenResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteCreateTokenAccountResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteSendTokensResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteAddCreditsResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteUpdateKeyPageResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteUpdateKeyResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteWriteDataResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteIssueTokensResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteWriteDataToResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteBurnTokensResponse {
     // No result data
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExecuteUpdateAccountAuthResponse {
     // No result data
@@ -717,4 +863,232 @@ pub fn __minimal_pair_for_test(method_name: &str) -> Option<(serde_json::Value,
         "update-account-auth" => Some((json!({}), json!({"data": {}}))),
         _ => None,
     }
-}
\ No newline at end of file
+}
+
+/// JSON Schemas of an API method's params and result
+#[cfg(feature = "json-schema")]
+#[derive(Debug, Clone)]
+pub struct ApiMethodSchema {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub params: schemars::schema::Schema,
+    pub result: schemars::schema::Schema,
+}
+
+/// Schemas for every API method, in declaration order; referenced types
+/// are added to `gen`'s definitions
+#[cfg(feature = "json-schema")]
+pub fn method_schemas(gen: &mut schemars::gen::SchemaGenerator) -> Vec<ApiMethodSchema> {
+    vec![
+        ApiMethodSchema {
+            name: "status",
+            description: "queries the status of the node",
+            params: gen.subschema_for::<StatusParams>(),
+            result: gen.subschema_for::<StatusResponse>(),
+        },
+        ApiMethodSchema {
+            name: "version",
+            description: "queries the software version of the node",
+            params: gen.subschema_for::<VersionParams>(),
+            result: gen.subschema_for::<VersionResponse>(),
+        },
+        ApiMethodSchema {
+            name: "describe",
+            description: "queries the basic configuration of the node",
+            params: gen.subschema_for::<DescribeParams>(),
+            result: gen.subschema_for::<DescribeResponse>(),
+        },
+        ApiMethodSchema {
+            name: "metrics",
+            description: "queries network metrics, such as transactions per second",
+            params: gen.subschema_for::<MetricsParams>(),
+            result: gen.subschema_for::<MetricsResponse>(),
+        },
+        ApiMethodSchema {
+            name: "faucet",
+            description: "requests tokens from the ACME faucet",
+            params: gen.subschema_for::<FaucetParams>(),
+            result: gen.subschema_for::<FaucetResponse>(),
+        },
+        ApiMethodSchema {
+            name: "query",
+            description: "queries an account or account chain by URL",
+            params: gen.subschema_for::<QueryParams>(),
+            result: gen.subschema_for::<QueryResponse>(),
+        },
+        ApiMethodSchema {
+            name: "query-directory",
+            description: "queries the directory entries of an account",
+            params: gen.subschema_for::<QueryDirectoryParams>(),
+            result: gen.subschema_for::<QueryDirectoryResponse>(),
+        },
+        ApiMethodSchema {
+            name: "query-tx",
+            description: "queries a transaction by ID",
+            params: gen.subschema_for::<QueryTxParams>(),
+            result: gen.subschema_for::<QueryTxResponse>(),
+        },
+        ApiMethodSchema {
+            name: "query-tx-local",
+            description: "queries a transaction by ID",
+            params: gen.subschema_for::<QueryTxLocalParams>(),
+            result: gen.subschema_for::<QueryTxLocalResponse>(),
+        },
+        ApiMethodSchema {
+            name: "query-tx-history",
+            description: "queries an account's transaction history",
+            params: gen.subschema_for::<QueryTxHistoryParams>(),
+            result: gen.subschema_for::<QueryTxHistoryResponse>(),
+        },
+        ApiMethodSchema {
+            name: "query-data",
+            description: "queries an entry on an account's data chain",
+            params: gen.subschema_for::<QueryDataParams>(),
+            result: gen.subschema_for::<QueryDataResponse>(),
+        },
+        ApiMethodSchema {
+            name: "query-data-set",
+            description: "queries a range of entries on an account's data chain",
+            params: gen.subschema_for::<QueryDataSetParams>(),
+            result: gen.subschema_for::<QueryDataSetResponse>(),
+        },
+        ApiMethodSchema {
+            name: "query-key-index",
+            description: "queries the location of a key within an account's key book(s)",
+            params: gen.subschema_for::<QueryKeyPageIndexParams>(),
+            result: gen.subschema_for::<QueryKeyPageIndexResponse>(),
+        },
+        ApiMethodSchema {
+            name: "query-minor-blocks",
+            description: "queries an account's minor blocks",
+            params: gen.subschema_for::<QueryMinorBlocksParams>(),
+            result: gen.subschema_for::<QueryMinorBlocksResponse>(),
+        },
+        ApiMethodSchema {
+            name: "query-major-blocks",
+            description: "queries an account's major blocks",
+            params: gen.subschema_for::<QueryMajorBlocksParams>(),
+            result: gen.subschema_for::<QueryMajorBlocksResponse>(),
+        },
+        ApiMethodSchema {
+            name: "query-synth",
+            description: "",
+            params: gen.subschema_for::<QuerySynthParams>(),
+            result: gen.subschema_for::<QuerySynthResponse>(),
+        },
+        ApiMethodSchema {
+            name: "execute",
+            description: "submits a transaction",
+            params: gen.subschema_for::<ExecuteParams>(),
+            result: gen.subschema_for::<ExecuteResponse>(),
+        },
+        ApiMethodSchema {
+            name: "execute-direct",
+            description: "submits a transaction",
+            params: gen.subschema_for::<ExecuteDirectParams>(),
+            result: gen.subschema_for::<ExecuteDirectResponse>(),
+        },
+        ApiMethodSchema {
+            name: "execute-local",
+            description: "submits a transaction without routing it. INTENDED FOR INTERNAL USE ONLY",
+            params: gen.subschema_for::<ExecuteLocalParams>(),
+            result: gen.subschema_for::<ExecuteLocalResponse>(),
+        },
+        ApiMethodSchema {
+            name: "create-adi",
+            description: "submits a CreateIdentity transaction",
+            params: gen.subschema_for::<ExecuteCreateAdiParams>(),
+            result: gen.subschema_for::<ExecuteCreateAdiResponse>(),
+        },
+        ApiMethodSchema {
+            name: "create-identity",
+            description: "submits a CreateIdentity transaction",
+            params: gen.subschema_for::<ExecuteCreateIdentityParams>(),
+            result: gen.subschema_for::<ExecuteCreateIdentityResponse>(),
+        },
+        ApiMethodSchema {
+            name: "create-data-account",
+            description: "submits a CreateDataAccount transaction",
+            params: gen.subschema_for::<ExecuteCreateDataAccountParams>(),
+            result: gen.subschema_for::<ExecuteCreateDataAccountResponse>(),
+        },
+        ApiMethodSchema {
+            name: "create-key-book",
+            description: "submits a CreateKeyBook transaction",
+            params: gen.subschema_for::<ExecuteCreateKeyBookParams>(),
+            result: gen.subschema_for::<ExecuteCreateKeyBookResponse>(),
+        },
+        ApiMethodSchema {
+            name: "create-key-page",
+            description: "submits a CreateKeyPage transaction",
+            params: gen.subschema_for::<ExecuteCreateKeyPageParams>(),
+            result: gen.subschema_for::<ExecuteCreateKeyPageResponse>(),
+        },
+        ApiMethodSchema {
+            name: "create-token",
+            description: "submits a CreateToken transaction",
+            params: gen.subschema_for::<ExecuteCreateTokenParams>(),
+            result: gen.subschema_for::<ExecuteCreateTokenResponse>(),
+        },
+        ApiMethodSchema {
+            name: "create-token-account",
+            description: "submits a CreateTokenAccount transaction",
+            params: gen.subschema_for::<ExecuteCreateTokenAccountParams>(),
+            result: gen.subschema_for::<ExecuteCreateTokenAccountResponse>(),
+        },
+        ApiMethodSchema {
+            name: "send-tokens",
+            description: "submits a SendTokens transaction",
+            params: gen.subschema_for::<ExecuteSendTokensParams>(),
+            result: gen.subschema_for::<ExecuteSendTokensResponse>(),
+        },
+        ApiMethodSchema {
+            name: "add-credits",
+            description: "submits an AddCredits transaction",
+            params: gen.subschema_for::<ExecuteAddCreditsParams>(),
+            result: gen.subschema_for::<ExecuteAddCreditsResponse>(),
+        },
+        ApiMethodSchema {
+            name: "update-key-page",
+            description: "submits an UpdateKeyPage transaction",
+            params: gen.subschema_for::<ExecuteUpdateKeyPageParams>(),
+            result: gen.subschema_for::<ExecuteUpdateKeyPageResponse>(),
+        },
+        ApiMethodSchema {
+            name: "update-key",
+            description: "submits an UpdateKey transaction",
+            params: gen.subschema_for::<ExecuteUpdateKeyParams>(),
+            result: gen.subschema_for::<ExecuteUpdateKeyResponse>(),
+        },
+        ApiMethodSchema {
+            name: "write-data",
+            description: "submits a WriteData transaction",
+            params: gen.subschema_for::<ExecuteWriteDataParams>(),
+            result: gen.subschema_for::<ExecuteWriteDataResponse>(),
+        },
+        ApiMethodSchema {
+            name: "issue-tokens",
+            description: "submits an IssueTokens transaction",
+            params: gen.subschema_for::<ExecuteIssueTokensParams>(),
+            result: gen.subschema_for::<ExecuteIssueTokensResponse>(),
+        },
+        ApiMethodSchema {
+            name: "write-data-to",
+            description: "submits a WriteDataTo transaction",
+            params: gen.subschema_for::<ExecuteWriteDataToParams>(),
+            result: gen.subschema_for::<ExecuteWriteDataToResponse>(),
+        },
+        ApiMethodSchema {
+            name: "burn-tokens",
+            description: "submits a BurnTokens transaction",
+            params: gen.subschema_for::<ExecuteBurnTokensParams>(),
+            result: gen.subschema_for::<ExecuteBurnTokensResponse>(),
+        },
+        ApiMethodSchema {
+            name: "update-account-auth",
+            description: "submits an UpdateAccountAuth transaction",
+            params: gen.subschema_for::<ExecuteUpdateAccountAuthParams>(),
+            result: gen.subschema_for::<ExecuteUpdateAccountAuthResponse>(),
+        },
+    ]
+}
@@ -4,6 +4,7 @@
 use serde::{Serialize, Deserialize};
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum AccountAuthOperationType {
     #[serde(rename = "unknown")]
     Unknown,
@@ -18,6 +19,7 @@ pub enum AccountAuthOperationType {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum AccountType {
     #[serde(rename = "unknown")]
     Unknown,
@@ -51,7 +53,8 @@ pub enum AccountType {
     SyntheticLedger,
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum AllowedTransactionBit {
     #[serde(rename = "updatekeypage")]
     UpdateKeyPage,
@@ -60,6 +63,7 @@ pub enum AllowedTransactionBit {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum BookType {
     #[serde(rename = "normal")]
     Normal,
@@ -70,6 +74,7 @@ pub enum BookType {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum DataEntryType {
     #[serde(rename = "unknown")]
     Unknown,
@@ -82,6 +87,7 @@ pub enum DataEntryType {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ExecutorVersion {
     #[serde(rename = "v1")]
     V1,
@@ -104,6 +110,7 @@ pub enum ExecutorVersion {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum KeyPageOperationType {
     #[serde(rename = "unknown")]
     Unknown,
@@ -124,6 +131,7 @@ pub enum KeyPageOperationType {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum NetworkMaintenanceOperationType {
     #[serde(rename = "unknown")]
     Unknown,
@@ -132,6 +140,7 @@ pub enum NetworkMaintenanceOperationType {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum ObjectType {
     #[serde(rename = "unknown")]
     Unknown,
@@ -142,6 +151,7 @@ pub enum ObjectType {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum PartitionType {
     #[serde(rename = "directory")]
     Directory,
@@ -154,6 +164,7 @@ pub enum PartitionType {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum SignatureType {
     #[serde(rename = "unknown")]
     Unknown,
@@ -192,6 +203,7 @@ pub enum SignatureType {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum TransactionMax {
     #[serde(rename = "user")]
     User,
@@ -202,6 +214,7 @@ pub enum TransactionMax {
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum TransactionType {
     #[serde(rename = "unknown")]
     Unknown,
@@ -274,6 +287,7 @@ pub enum TransactionType {
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum VoteType {
     #[serde(rename = "accept")]
     Accept,
@@ -608,16 +622,16 @@ pub fn __roundtrip_one(enum_name: &str, tag: &str) -> Result<(), Box<dyn std::er
 pub fn __get_all_enum_variants() -> std::collections::HashMap<String, Vec<String>> {
     let mut map = std::collections::HashMap::new();
     map.insert("AccountAuthOperationType".to_string(), vec!["unknown".to_string(), "enable".to_string(), "disable".to_string(), "addauthority".to_string(), "removeauthority".to_string()]);
-    map.insert("AccountType".to_string(), vec!["unknown".to_string(), "anchorledger".to_string(), "identity".to_string(), "token".to_string(), "tokenaccount".to_string(), "litetokenaccount".to_string(), "blockledger".to_string(), "keypage".to_string(), "keybook".to_string(), "dataaccount".to_string(), "litedataaccount".to_string(), "unknownsigner".to_string(), "systemledger".to_string(), "liteidentity".to_string(), "syntheticledger".to_string()]);
+    map.insert("AccountType".to_string(), vec!["unknown".to_string(), "anchorLedger".to_string(), "identity".to_string(), "tokenIssuer".to_string(), "tokenAccount".to_string(), "liteTokenAccount".to_string(), "blockLedger".to_string(), "keyPage".to_string(), "keyBook".to_string(), "dataAccount".to_string(), "liteDataAccount".to_string(), "unknownSigner".to_string(), "systemLedger".to_string(), "liteIdentity".to_string(), "syntheticLedger".to_string()]);
     map.insert("AllowedTransactionBit".to_string(), vec!["updatekeypage".to_string(), "updateaccountauth".to_string()]);
     map.insert("BookType".to_string(), vec!["normal".to_string(), "validator".to_string(), "operator".to_string()]);
     map.insert("DataEntryType".to_string(), vec!["unknown".to_string(), "factom".to_string(), "accumulate".to_string(), "doublehash".to_string()]);
-    map.insert("ExecutorVersion".to_string(), vec!["v1".to_string(), "v1-signatureAnchoring".to_string(), "v1-doubleHashEntries".to_string(), "v1-halt".to_string(), "v2".to_string(), "v2-baikonur".to_string(), "v2-vandenberg".to_string(), "v2-jiuquan".to_string(), "vnext".to_string()]);
+    map.insert("ExecutorVersion".to_string(), vec!["v1".to_string(), "v1SignatureAnchoring".to_string(), "v1DoubleHashEntries".to_string(), "v1Halt".to_string(), "v2".to_string(), "v2Baikonur".to_string(), "v2Vandenberg".to_string(), "v2Jiuquan".to_string(), "vNext".to_string()]);
     map.insert("KeyPageOperationType".to_string(), vec!["unknown".to_string(), "update".to_string(), "remove".to_string(), "add".to_string(), "setthreshold".to_string(), "updateallowed".to_string(), "setrejectthreshold".to_string(), "setresponsethreshold".to_string()]);
     map.insert("NetworkMaintenanceOperationType".to_string(), vec!["unknown".to_string(), "pendingtransactiongc".to_string()]);
     map.insert("ObjectType".to_string(), vec!["unknown".to_string(), "account".to_string(), "transaction".to_string()]);
-    map.insert("PartitionType".to_string(), vec!["directory".to_string(), "block-validator".to_string(), "block-summary".to_string(), "bootstrap".to_string()]);
-    map.insert("SignatureType".to_string(), vec!["unknown".to_string(), "legacyed25519".to_string(), "ed25519".to_string(), "rcd1".to_string(), "receipt".to_string(), "synthetic".to_string(), "set".to_string(), "remote".to_string(), "btc".to_string(), "btclegacy".to_string(), "eth".to_string(), "delegated".to_string(), "internal".to_string(), "authority".to_string(), "rsasha256".to_string(), "ecdsasha256".to_string(), "typeddata".to_string()]);
+    map.insert("PartitionType".to_string(), vec!["directory".to_string(), "blockValidator".to_string(), "blockSummary".to_string(), "bootstrap".to_string()]);
+    map.insert("SignatureType".to_string(), vec!["unknown".to_string(), "legacyED25519".to_string(), "ed25519".to_string(), "rcd1".to_string(), "receipt".to_string(), "synthetic".to_string(), "set".to_string(), "remote".to_string(), "btc".to_string(), "btclegacy".to_string(), "eth".to_string(), "delegated".to_string(), "internal".to_string(), "authority".to_string(), "rsasha256".to_string(), "ecdsasha256".to_string(), "typeddata".to_string()]);
     map.insert("TransactionMax".to_string(), vec!["user".to_string(), "synthetic".to_string(), "system".to_string()]);
     map.insert("TransactionType".to_string(), vec!["unknown".to_string(), "createIdentity".to_string(), "createTokenAccount".to_string(), "sendTokens".to_string(), "createDataAccount".to_string(), "writeData".to_string(), "writeDataTo".to_string(), "acmeFaucet".to_string(), "createToken".to_string(), "issueTokens".to_string(), "burnTokens".to_string(), "createLiteTokenAccount".to_string(), "createKeyPage".to_string(), "createKeyBook".to_string(), "addCredits".to_string(), "updateKeyPage".to_string(), "lockAccount".to_string(), "burnCredits".to_string(), "transferCredits".to_string(), "updateAccountAuth".to_string(), "updateKey".to_string(), "networkMaintenance".to_string(), "activateProtocolVersion".to_string(), "signPending".to_string(), "syntheticCreateIdentity".to_string(), "syntheticWriteData".to_string(), "syntheticDepositTokens".to_string(), "syntheticDepositCredits".to_string(), "syntheticBurnTokens".to_string(), "syntheticForwardTransaction".to_string(), "systemGenesis".to_string(), "directoryAnchor".to_string(), "blockValidatorAnchor".to_string(), "systemWriteData".to_string()]);
     map.insert("VoteType".to_string(), vec!["accept".to_string(), "reject".to_string(), "abstain".to_string(), "suggest".to_string()]);
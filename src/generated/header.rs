@@ -35,6 +35,7 @@ mod hex_option_vec {
     }
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ExpireOptions {
     pub at_time: Option<u64>,
@@ -73,6 +74,7 @@ impl ExpireOptions {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct HoldUntilOptions {
     pub minor_block: Option<u64>,
@@ -94,15 +96,18 @@ impl HoldUntilOptions {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionHeader {
     pub principal: String,
     #[serde(with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub initiator: Vec<u8>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub memo: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     #[serde(with = "hex_option_vec")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub metadata: Option<Vec<u8>>,
     #[serde(skip_serializing_if = "Option::is_none", default)]
     pub expire: Option<ExpireOptions>,
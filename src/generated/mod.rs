@@ -5,9 +5,26 @@
 
 #![allow(missing_docs)]
 
+// With the `codegen` feature and ACCUMULATE_PROTOCOL_DIR set, build.rs
+// regenerates these three modules into OUT_DIR from the given protocol YAML
+#[cfg(not(accumulate_codegen))]
 pub mod enums;
+#[cfg(accumulate_codegen)]
+pub mod enums {
+    include!(concat!(env!("OUT_DIR"), "/generated/enums.rs"));
+}
+#[cfg(not(accumulate_codegen))]
 pub mod signatures;
+#[cfg(accumulate_codegen)]
+pub mod signatures {
+    include!(concat!(env!("OUT_DIR"), "/generated/signatures.rs"));
+}
 pub mod header;
+#[cfg(not(accumulate_codegen))]
 pub mod transactions;
+#[cfg(accumulate_codegen)]
+pub mod transactions {
+    include!(concat!(env!("OUT_DIR"), "/generated/transactions.rs"));
+}
 pub mod api_methods;
 pub mod types;  // Phase 3 generated protocol types
\ No newline at end of file
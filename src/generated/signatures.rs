@@ -1,8 +1,6 @@
 //! GENERATED FILE - DO NOT EDIT
 //! Source: protocol/signatures.yml | Generated: 2025-10-03 20:47:50
 
-#![allow(missing_docs)]
-
 use serde::{Serialize, Deserialize};
 use hex;
 
@@ -139,6 +137,15 @@ pub enum SignatureError {
     UnsupportedType,
 }
 
+/// Error for signature types whose verifier is compiled out
+#[cfg(not(all(feature = "secp256k1", feature = "p256", feature = "rsa")))]
+fn unsupported_signature(sig_type: &str, feature: &str) -> crate::errors::Error {
+    crate::errors::SignatureError::UnsupportedType(format!(
+        "{sig_type} (verification requires the `{feature}` feature)"
+    ))
+    .into()
+}
+
 /// Main signature trait for verification
 pub trait AccSignature {
     fn verify(&self, message: &[u8]) -> Result<bool, crate::errors::Error>;
@@ -147,13 +154,16 @@ pub trait AccSignature {
 
 /// LegacyED25519Signature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct LegacyED25519Signature {
     #[serde(rename = "Timestamp")]
     pub timestamp: u64,
     #[serde(rename = "PublicKey", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub public_key: Vec<u8>,
     #[serde(rename = "Signature", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub signature: Vec<u8>,
     #[serde(rename = "Signer")]
     pub signer: String,
@@ -162,16 +172,20 @@ pub struct LegacyED25519Signature {
     #[serde(rename = "Vote")]
     pub vote: Option<crate::generated::enums::VoteType>,
     #[serde(rename = "TransactionHash", with = "hex_option")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub transaction_hash: Option<[u8; 32]>,
 }
 
 /// RCD1Signature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct RCD1Signature {
     #[serde(rename = "PublicKey", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub public_key: Vec<u8>,
     #[serde(rename = "Signature", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub signature: Vec<u8>,
     #[serde(rename = "Signer")]
     pub signer: String,
@@ -182,20 +196,25 @@ pub struct RCD1Signature {
     #[serde(rename = "Vote")]
     pub vote: Option<crate::generated::enums::VoteType>,
     #[serde(rename = "TransactionHash", with = "hex_option")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub transaction_hash: Option<[u8; 32]>,
     #[serde(rename = "Memo")]
     pub memo: Option<String>,
     #[serde(rename = "Data", with = "hex_option_vec")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub data: Option<Vec<u8>>,
 }
 
 /// ED25519Signature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ED25519Signature {
     #[serde(rename = "PublicKey", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub public_key: Vec<u8>,
     #[serde(rename = "Signature", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub signature: Vec<u8>,
     #[serde(rename = "Signer")]
     pub signer: String,
@@ -206,20 +225,25 @@ pub struct ED25519Signature {
     #[serde(rename = "Vote")]
     pub vote: Option<crate::generated::enums::VoteType>,
     #[serde(rename = "TransactionHash", with = "hex_option")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub transaction_hash: Option<[u8; 32]>,
     #[serde(rename = "Memo")]
     pub memo: Option<String>,
     #[serde(rename = "Data", with = "hex_option_vec")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub data: Option<Vec<u8>>,
 }
 
 /// BTCSignature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BTCSignature {
     #[serde(rename = "PublicKey", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub public_key: Vec<u8>,
     #[serde(rename = "Signature", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub signature: Vec<u8>,
     #[serde(rename = "Signer")]
     pub signer: String,
@@ -230,20 +254,25 @@ pub struct BTCSignature {
     #[serde(rename = "Vote")]
     pub vote: Option<crate::generated::enums::VoteType>,
     #[serde(rename = "TransactionHash", with = "hex_option")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub transaction_hash: Option<[u8; 32]>,
     #[serde(rename = "Memo")]
     pub memo: Option<String>,
     #[serde(rename = "Data", with = "hex_option_vec")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub data: Option<Vec<u8>>,
 }
 
 /// BTCLegacySignature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BTCLegacySignature {
     #[serde(rename = "PublicKey", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub public_key: Vec<u8>,
     #[serde(rename = "Signature", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub signature: Vec<u8>,
     #[serde(rename = "Signer")]
     pub signer: String,
@@ -254,20 +283,25 @@ pub struct BTCLegacySignature {
     #[serde(rename = "Vote")]
     pub vote: Option<crate::generated::enums::VoteType>,
     #[serde(rename = "TransactionHash", with = "hex_option")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub transaction_hash: Option<[u8; 32]>,
     #[serde(rename = "Memo")]
     pub memo: Option<String>,
     #[serde(rename = "Data", with = "hex_option_vec")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub data: Option<Vec<u8>>,
 }
 
 /// ETHSignature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ETHSignature {
     #[serde(rename = "PublicKey", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub public_key: Vec<u8>,
     #[serde(rename = "Signature", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub signature: Vec<u8>,
     #[serde(rename = "Signer")]
     pub signer: String,
@@ -278,20 +312,25 @@ pub struct ETHSignature {
     #[serde(rename = "Vote")]
     pub vote: Option<crate::generated::enums::VoteType>,
     #[serde(rename = "TransactionHash", with = "hex_option")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub transaction_hash: Option<[u8; 32]>,
     #[serde(rename = "Memo")]
     pub memo: Option<String>,
     #[serde(rename = "Data", with = "hex_option_vec")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub data: Option<Vec<u8>>,
 }
 
 /// RsaSha256Signature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct RsaSha256Signature {
     #[serde(rename = "PublicKey", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub public_key: Vec<u8>,
     #[serde(rename = "Signature", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub signature: Vec<u8>,
     #[serde(rename = "Signer")]
     pub signer: String,
@@ -302,20 +341,25 @@ pub struct RsaSha256Signature {
     #[serde(rename = "Vote")]
     pub vote: Option<crate::generated::enums::VoteType>,
     #[serde(rename = "TransactionHash", with = "hex_option")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub transaction_hash: Option<[u8; 32]>,
     #[serde(rename = "Memo")]
     pub memo: Option<String>,
     #[serde(rename = "Data", with = "hex_option_vec")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub data: Option<Vec<u8>>,
 }
 
 /// EcdsaSha256Signature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct EcdsaSha256Signature {
     #[serde(rename = "PublicKey", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub public_key: Vec<u8>,
     #[serde(rename = "Signature", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub signature: Vec<u8>,
     #[serde(rename = "Signer")]
     pub signer: String,
@@ -326,20 +370,25 @@ pub struct EcdsaSha256Signature {
     #[serde(rename = "Vote")]
     pub vote: Option<crate::generated::enums::VoteType>,
     #[serde(rename = "TransactionHash", with = "hex_option")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub transaction_hash: Option<[u8; 32]>,
     #[serde(rename = "Memo")]
     pub memo: Option<String>,
     #[serde(rename = "Data", with = "hex_option_vec")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub data: Option<Vec<u8>>,
 }
 
 /// TypedDataSignature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TypedDataSignature {
     #[serde(rename = "PublicKey", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub public_key: Vec<u8>,
     #[serde(rename = "Signature", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub signature: Vec<u8>,
     #[serde(rename = "Signer")]
     pub signer: String,
@@ -350,10 +399,12 @@ pub struct TypedDataSignature {
     #[serde(rename = "Vote")]
     pub vote: Option<crate::generated::enums::VoteType>,
     #[serde(rename = "TransactionHash", with = "hex_option")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub transaction_hash: Option<[u8; 32]>,
     #[serde(rename = "Memo")]
     pub memo: Option<String>,
     #[serde(rename = "Data", with = "hex_option_vec")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub data: Option<Vec<u8>>,
     #[serde(rename = "ChainID")]
     pub chain_i_d: String,
@@ -361,6 +412,7 @@ pub struct TypedDataSignature {
 
 /// ReceiptSignature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ReceiptSignature {
     #[serde(rename = "SourceNetwork")]
@@ -368,11 +420,13 @@ pub struct ReceiptSignature {
     #[serde(rename = "Proof")]
     pub proof: crate::types::MerkleReceipt,
     #[serde(rename = "TransactionHash", with = "hex_option")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub transaction_hash: Option<[u8; 32]>,
 }
 
 /// PartitionSignature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct PartitionSignature {
     #[serde(rename = "SourceNetwork")]
@@ -382,11 +436,13 @@ pub struct PartitionSignature {
     #[serde(rename = "SequenceNumber")]
     pub sequence_number: u64,
     #[serde(rename = "TransactionHash", with = "hex_option")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub transaction_hash: Option<[u8; 32]>,
 }
 
 /// SignatureSet signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct SignatureSet {
     #[serde(rename = "Vote")]
@@ -394,6 +450,7 @@ pub struct SignatureSet {
     #[serde(rename = "Signer")]
     pub signer: String,
     #[serde(rename = "TransactionHash", with = "hex_option")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub transaction_hash: Option<[u8; 32]>,
     #[serde(rename = "Signatures")]
     pub signatures: Vec<Box<crate::generated::signatures::Signature>>,
@@ -403,6 +460,7 @@ pub struct SignatureSet {
 
 /// RemoteSignature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct RemoteSignature {
     #[serde(rename = "Destination")]
@@ -410,11 +468,13 @@ pub struct RemoteSignature {
     #[serde(rename = "Signature")]
     pub signature: Box<crate::generated::signatures::Signature>,
     #[serde(rename = "Cause", with = "hex_vec_hash")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Vec<String>"))]
     pub cause: Vec<[u8; 32]>,
 }
 
 /// DelegatedSignature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct DelegatedSignature {
     #[serde(rename = "Signature")]
@@ -425,16 +485,20 @@ pub struct DelegatedSignature {
 
 /// InternalSignature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct InternalSignature {
     #[serde(rename = "Cause", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub cause: [u8; 32],
     #[serde(rename = "TransactionHash", with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub transaction_hash: [u8; 32],
 }
 
 /// AuthoritySignature signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AuthoritySignature {
     #[serde(rename = "Origin")]
@@ -455,6 +519,7 @@ pub struct AuthoritySignature {
 
 /// Main signature dispatch enum
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum Signature {
     #[serde(rename = "legacyED25519")]
@@ -682,20 +747,15 @@ impl AccSignature for ED25519Signature {
 }
 
 impl AccSignature for BTCSignature {
+    #[cfg(feature = "secp256k1")]
     fn verify(&self, message: &[u8]) -> Result<bool, crate::errors::Error> {
         // BTC uses secp256k1 ECDSA with DER-encoded signatures
-        use k256::ecdsa::{Signature as K256Signature, VerifyingKey, signature::Verifier};
-        use sha2::{Sha256, Digest};
+        use k256::ecdsa::{Signature as K256Signature, VerifyingKey, signature::hazmat::PrehashVerifier};
 
         if self.public_key.is_empty() || self.signature.is_empty() {
             return Ok(false);
         }
 
-        // Hash the message with SHA256 (Bitcoin double-SHA256 for transaction signing)
-        let mut hasher = Sha256::new();
-        hasher.update(message);
-        let hash = hasher.finalize();
-
         // Parse the public key (compressed or uncompressed SEC1 format)
         let verifying_key = match VerifyingKey::from_sec1_bytes(&self.public_key) {
             Ok(key) => key,
@@ -715,7 +775,13 @@ impl AccSignature for BTCSignature {
             return Ok(false);
         };
 
-        Ok(verifying_key.verify(&hash, &signature).is_ok())
+        // The message is the signing digest, signed as-is like the Go core does
+        Ok(verifying_key.verify_prehash(message, &signature).is_ok())
+    }
+
+    #[cfg(not(feature = "secp256k1"))]
+    fn verify(&self, _message: &[u8]) -> Result<bool, crate::errors::Error> {
+        Err(unsupported_signature("btc", "secp256k1"))
     }
 
     fn sig_type(&self) -> &'static str {
@@ -724,20 +790,15 @@ impl AccSignature for BTCSignature {
 }
 
 impl AccSignature for BTCLegacySignature {
+    #[cfg(feature = "secp256k1")]
     fn verify(&self, message: &[u8]) -> Result<bool, crate::errors::Error> {
         // BTCLegacy uses same secp256k1 ECDSA as BTC
-        use k256::ecdsa::{Signature as K256Signature, VerifyingKey, signature::Verifier};
-        use sha2::{Sha256, Digest};
+        use k256::ecdsa::{Signature as K256Signature, VerifyingKey, signature::hazmat::PrehashVerifier};
 
         if self.public_key.is_empty() || self.signature.is_empty() {
             return Ok(false);
         }
 
-        // Hash the message with SHA256
-        let mut hasher = Sha256::new();
-        hasher.update(message);
-        let hash = hasher.finalize();
-
         // Parse the public key (compressed or uncompressed SEC1 format)
         let verifying_key = match VerifyingKey::from_sec1_bytes(&self.public_key) {
             Ok(key) => key,
@@ -756,7 +817,13 @@ impl AccSignature for BTCLegacySignature {
             return Ok(false);
         };
 
-        Ok(verifying_key.verify(&hash, &signature).is_ok())
+        // The message is the signing digest, signed as-is like the Go core does
+        Ok(verifying_key.verify_prehash(message, &signature).is_ok())
+    }
+
+    #[cfg(not(feature = "secp256k1"))]
+    fn verify(&self, _message: &[u8]) -> Result<bool, crate::errors::Error> {
+        Err(unsupported_signature("btcLegacy", "secp256k1"))
     }
 
     fn sig_type(&self) -> &'static str {
@@ -765,21 +832,16 @@ impl AccSignature for BTCLegacySignature {
 }
 
 impl AccSignature for ETHSignature {
+    #[cfg(feature = "secp256k1")]
     fn verify(&self, message: &[u8]) -> Result<bool, crate::errors::Error> {
-        // Ethereum uses secp256k1 ECDSA with keccak256 hash
+        // Ethereum-style secp256k1 ECDSA over the signing digest, signed as-is like the Go core does
         // Signature format: r (32 bytes) || s (32 bytes) || v (1 byte recovery id)
-        use k256::ecdsa::{Signature as K256Signature, VerifyingKey, RecoveryId, signature::Verifier};
-        use sha3::{Keccak256, Digest};
+        use k256::ecdsa::{Signature as K256Signature, VerifyingKey, RecoveryId, signature::hazmat::PrehashVerifier};
 
         if self.public_key.is_empty() || self.signature.is_empty() {
             return Ok(false);
         }
 
-        // Hash the message with Keccak256 (Ethereum style)
-        let mut hasher = Keccak256::new();
-        hasher.update(message);
-        let hash = hasher.finalize();
-
         // Parse the public key (compressed or uncompressed SEC1 format)
         let expected_key = match VerifyingKey::from_sec1_bytes(&self.public_key) {
             Ok(key) => key,
@@ -800,7 +862,7 @@ impl AccSignature for ETHSignature {
             };
 
             // Recover the public key and compare
-            match VerifyingKey::recover_from_prehash(&hash, &sig, recovery_id) {
+            match VerifyingKey::recover_from_prehash(message, &sig, recovery_id) {
                 Ok(recovered_key) => {
                     Ok(recovered_key == expected_key)
                 },
@@ -812,22 +874,28 @@ impl AccSignature for ETHSignature {
                 Ok(sig) => sig,
                 Err(_) => return Ok(false),
             };
-            Ok(expected_key.verify(&hash, &signature).is_ok())
+            Ok(expected_key.verify_prehash(message, &signature).is_ok())
         } else {
             Ok(false)
         }
     }
 
+    #[cfg(not(feature = "secp256k1"))]
+    fn verify(&self, _message: &[u8]) -> Result<bool, crate::errors::Error> {
+        Err(unsupported_signature("eth", "secp256k1"))
+    }
+
     fn sig_type(&self) -> &'static str {
         "eth"
     }
 }
 
 impl AccSignature for RsaSha256Signature {
+    #[cfg(feature = "rsa")]
     fn verify(&self, message: &[u8]) -> Result<bool, crate::errors::Error> {
         // RSA PKCS#1 v1.5 with SHA-256
         use rsa::{RsaPublicKey, pkcs1::DecodeRsaPublicKey, pkcs8::DecodePublicKey};
-        use rsa::signature::Verifier;
+        use rsa::signature::hazmat::PrehashVerifier;
         use rsa::pkcs1v15::{Signature, VerifyingKey};
         use sha2::Sha256;
 
@@ -854,8 +922,13 @@ impl AccSignature for RsaSha256Signature {
             Err(_) => return Ok(false),
         };
 
-        // Verify the signature
-        Ok(verifying_key.verify(message, &signature).is_ok())
+        // The message is the signing digest, signed as-is like the Go core does
+        Ok(verifying_key.verify_prehash(message, &signature).is_ok())
+    }
+
+    #[cfg(not(feature = "rsa"))]
+    fn verify(&self, _message: &[u8]) -> Result<bool, crate::errors::Error> {
+        Err(unsupported_signature("rsaSha256", "rsa"))
     }
 
     fn sig_type(&self) -> &'static str {
@@ -864,39 +937,35 @@ impl AccSignature for RsaSha256Signature {
 }
 
 impl AccSignature for EcdsaSha256Signature {
+    #[cfg(feature = "p256")]
     fn verify(&self, message: &[u8]) -> Result<bool, crate::errors::Error> {
-        // ECDSA secp256k1 with SHA-256 hash
-        use k256::ecdsa::{Signature as K256Signature, VerifyingKey, signature::Verifier};
-        use sha2::{Sha256, Digest};
+        // ECDSA over NIST P-256 with SHA-256 hash
+        use p256::ecdsa::{Signature as P256Signature, VerifyingKey, signature::hazmat::PrehashVerifier};
+        use p256::pkcs8::DecodePublicKey;
 
         if self.signature.is_empty() || self.public_key.is_empty() {
             return Ok(false);
         }
 
-        // Hash the message with SHA256
-        let mut hasher = Sha256::new();
-        hasher.update(message);
-        let hash = hasher.finalize();
-
-        // Parse the public key (compressed or uncompressed SEC1 format)
-        let verifying_key = match VerifyingKey::from_sec1_bytes(&self.public_key) {
+        // The public key is PKIX DER, as the Go core's x509.MarshalPKIXPublicKey writes it
+        let verifying_key = match VerifyingKey::from_public_key_der(&self.public_key) {
             Ok(key) => key,
             Err(_) => return Ok(false),
         };
 
-        // Try to parse signature as DER format first, then as raw r||s format
-        let signature = if let Ok(sig) = K256Signature::from_der(&self.signature) {
-            sig
-        } else if self.signature.len() == 64 {
-            match K256Signature::from_slice(&self.signature) {
-                Ok(sig) => sig,
-                Err(_) => return Ok(false),
-            }
-        } else {
-            return Ok(false);
+        // The Go core signs with ecdsa.SignASN1, so the signature is DER
+        let signature = match P256Signature::from_der(&self.signature) {
+            Ok(sig) => sig,
+            Err(_) => return Ok(false),
         };
 
-        Ok(verifying_key.verify(&hash, &signature).is_ok())
+        // The message is the signing digest, signed as-is like the Go core does
+        Ok(verifying_key.verify_prehash(message, &signature).is_ok())
+    }
+
+    #[cfg(not(feature = "p256"))]
+    fn verify(&self, _message: &[u8]) -> Result<bool, crate::errors::Error> {
+        Err(unsupported_signature("ecdsaSha256", "p256"))
     }
 
     fn sig_type(&self) -> &'static str {
@@ -905,6 +974,7 @@ impl AccSignature for EcdsaSha256Signature {
 }
 
 impl AccSignature for TypedDataSignature {
+    #[cfg(feature = "secp256k1")]
     fn verify(&self, message: &[u8]) -> Result<bool, crate::errors::Error> {
         // EIP-712 TypedData signature - uses keccak256 and secp256k1 with recovery
         // Similar to ETHSignature but the message is already the EIP-712 typed data hash
@@ -954,6 +1024,11 @@ impl AccSignature for TypedDataSignature {
         }
     }
 
+    #[cfg(not(feature = "secp256k1"))]
+    fn verify(&self, _message: &[u8]) -> Result<bool, crate::errors::Error> {
+        Err(unsupported_signature("typedData", "secp256k1"))
+    }
+
     fn sig_type(&self) -> &'static str {
         "typedData"
     }
@@ -2,11 +2,37 @@
 //! Sources: protocol/transaction.yml, user_transactions.yml, system.yml, synthetic_transactions.yml
 //! Generated: 2025-10-03 21:53:38
 
-#![allow(missing_docs)]
-
 use serde::{Serialize, Deserialize};
 use crate::errors::{Error, ValidationError};
 
+mod hex_option_vec {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(bytes) => serializer.serialize_str(&hex::encode(bytes)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        let opt: Option<String> = Option::deserialize(deserializer)?;
+        match opt {
+            Some(hex_str) => {
+                hex::decode(&hex_str).map(Some).map_err(D::Error::custom)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
 /// Validates that a string is a valid Accumulate URL
 /// Accumulate URLs must:
 /// - Start with "acc://"
@@ -81,9 +107,10 @@ fn validate_authorities(authorities: &Option<Vec<String>>) -> Result<(), Error>
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AcmeFaucetBody {
-    #[serde(rename = "Url")]
+    #[serde(rename = "url", alias = "Url")]
     pub url: String,
 }
 
@@ -96,9 +123,10 @@ impl AcmeFaucetBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct ActivateProtocolVersionBody {
-    #[serde(rename = "Version")]
+    #[serde(rename = "version", alias = "Version")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub version: Option<String>,
 }
@@ -119,13 +147,14 @@ impl ActivateProtocolVersionBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct AddCreditsBody {
-    #[serde(rename = "Recipient")]
+    #[serde(rename = "recipient", alias = "Recipient")]
     pub recipient: String,
-    #[serde(rename = "Amount")]
+    #[serde(rename = "amount", alias = "Amount")]
     pub amount: String,
-    #[serde(rename = "Oracle")]
+    #[serde(rename = "oracle", alias = "Oracle")]
     pub oracle: u64,
 }
 
@@ -150,9 +179,10 @@ impl AddCreditsBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BlockValidatorAnchorBody {
-    #[serde(rename = "AcmeBurnt")]
+    #[serde(rename = "acmeBurnt", alias = "AcmeBurnt")]
     pub acme_burnt: String,
 }
 
@@ -165,9 +195,10 @@ impl BlockValidatorAnchorBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BurnCreditsBody {
-    #[serde(rename = "Amount")]
+    #[serde(rename = "amount", alias = "Amount")]
     pub amount: u64,
 }
 
@@ -184,9 +215,10 @@ impl BurnCreditsBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct BurnTokensBody {
-    #[serde(rename = "Amount")]
+    #[serde(rename = "amount", alias = "Amount")]
     pub amount: String,
 }
 
@@ -206,11 +238,12 @@ impl BurnTokensBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CreateDataAccountBody {
-    #[serde(rename = "Url")]
+    #[serde(rename = "url", alias = "Url")]
     pub url: String,
-    #[serde(rename = "Authorities")]
+    #[serde(rename = "authorities", alias = "Authorities")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub authorities: Option<Vec<String>>,
 }
@@ -228,17 +261,20 @@ impl CreateDataAccountBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CreateIdentityBody {
-    #[serde(rename = "Url")]
+    #[serde(rename = "url", alias = "Url")]
     pub url: String,
-    #[serde(rename = "KeyHash")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "keyHash", alias = "KeyHash")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    #[serde(with = "hex_option_vec")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub key_hash: Option<Vec<u8>>,
-    #[serde(rename = "KeyBookUrl")]
+    #[serde(rename = "keyBookUrl", alias = "KeyBookUrl")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub key_book_url: Option<String>,
-    #[serde(rename = "Authorities")]
+    #[serde(rename = "authorities", alias = "Authorities")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub authorities: Option<Vec<String>>,
 }
@@ -271,14 +307,16 @@ impl CreateIdentityBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CreateKeyBookBody {
-    #[serde(rename = "Url")]
+    #[serde(rename = "url", alias = "Url")]
     pub url: String,
-    #[serde(rename = "PublicKeyHash")]
+    #[serde(rename = "publicKeyHash", alias = "PublicKeyHash")]
     #[serde(with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub public_key_hash: Vec<u8>,
-    #[serde(rename = "Authorities")]
+    #[serde(rename = "authorities", alias = "Authorities")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub authorities: Option<Vec<String>>,
 }
@@ -304,9 +342,10 @@ impl CreateKeyBookBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CreateKeyPageBody {
-    #[serde(rename = "Keys")]
+    #[serde(rename = "keys", alias = "Keys")]
     pub keys: Vec<serde_json::Value>,
 }
 
@@ -334,6 +373,7 @@ impl CreateKeyPageBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CreateLiteTokenAccountBody {
     // No fields defined
@@ -347,21 +387,22 @@ impl CreateLiteTokenAccountBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTokenBody {
-    #[serde(rename = "Url")]
+    #[serde(rename = "url", alias = "Url")]
     pub url: String,
-    #[serde(rename = "Symbol")]
+    #[serde(rename = "symbol", alias = "Symbol")]
     pub symbol: String,
-    #[serde(rename = "Precision")]
+    #[serde(rename = "precision", alias = "Precision")]
     pub precision: u64,
-    #[serde(rename = "Properties")]
+    #[serde(rename = "properties", alias = "Properties")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub properties: Option<String>,
-    #[serde(rename = "SupplyLimit")]
+    #[serde(rename = "supplyLimit", alias = "SupplyLimit")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub supply_limit: Option<String>,
-    #[serde(rename = "Authorities")]
+    #[serde(rename = "authorities", alias = "Authorities")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub authorities: Option<Vec<String>>,
 }
@@ -408,16 +449,17 @@ impl CreateTokenBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct CreateTokenAccountBody {
-    #[serde(rename = "Url")]
+    #[serde(rename = "url", alias = "Url")]
     pub url: String,
-    #[serde(rename = "TokenUrl")]
+    #[serde(rename = "tokenUrl", alias = "TokenUrl")]
     pub token_url: String,
-    #[serde(rename = "Authorities")]
+    #[serde(rename = "authorities", alias = "Authorities")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub authorities: Option<Vec<String>>,
-    #[serde(rename = "Proof")]
+    #[serde(rename = "proof", alias = "Proof")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub proof: Option<serde_json::Value>,
 }
@@ -438,15 +480,16 @@ impl CreateTokenAccountBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct DirectoryAnchorBody {
-    #[serde(rename = "Updates")]
+    #[serde(rename = "updates", alias = "Updates")]
     pub updates: Vec<serde_json::Value>,
-    #[serde(rename = "Receipts")]
+    #[serde(rename = "receipts", alias = "Receipts")]
     pub receipts: Vec<serde_json::Value>,
-    #[serde(rename = "MakeMajorBlock")]
+    #[serde(rename = "makeMajorBlock", alias = "MakeMajorBlock")]
     pub make_major_block: u64,
-    #[serde(rename = "MakeMajorBlockTime")]
+    #[serde(rename = "makeMajorBlockTime", alias = "MakeMajorBlockTime")]
     pub make_major_block_time: u64,
 }
 
@@ -460,29 +503,49 @@ impl DirectoryAnchorBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct IssueTokensBody {
-    #[serde(rename = "Recipient")]
-    pub recipient: String,
-    #[serde(rename = "Amount")]
-    pub amount: String,
-    #[serde(rename = "To")]
+    #[serde(rename = "recipient", alias = "Recipient")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub recipient: Option<String>,
+    #[serde(rename = "amount", alias = "Amount")]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<String>,
+    #[serde(rename = "to", alias = "To")]
     pub to: Vec<serde_json::Value>,
 }
 
 impl IssueTokensBody {
     pub fn validate(&self) -> Result<(), Error> {
-        // Recipient must be a valid Accumulate URL
-        validate_accumulate_url(&self.recipient, "recipient")?;
-
-        // Amount must be a valid positive integer string
-        validate_amount_string(&self.amount, "amount")?;
-
-        // Amount must be positive for issuance
-        if self.amount == "0" {
-            return Err(ValidationError::InvalidAmount(
-                "amount: must be greater than zero to issue tokens".to_string()
-            ).into());
+        // Recipients are listed in `to`; the superseded single recipient needs
+        // both of its fields
+        match (&self.recipient, &self.amount) {
+            (Some(recipient), Some(amount)) => {
+                // Recipient must be a valid Accumulate URL
+                validate_accumulate_url(recipient, "recipient")?;
+
+                // Amount must be a valid positive integer string
+                validate_amount_string(amount, "amount")?;
+
+                // Amount must be positive for issuance
+                if amount == "0" {
+                    return Err(ValidationError::InvalidAmount(
+                        "amount: must be greater than zero to issue tokens".to_string()
+                    ).into());
+                }
+            }
+            (None, None) if !self.to.is_empty() => {}
+            (None, None) => {
+                return Err(ValidationError::EmptyCollection(
+                    "to: at least one recipient is required".to_string()
+                ).into());
+            }
+            _ => {
+                return Err(ValidationError::RequiredFieldMissing(
+                    "recipient and amount must be set together".to_string()
+                ).into());
+            }
         }
 
         Ok(())
@@ -490,9 +553,10 @@ impl IssueTokensBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct LockAccountBody {
-    #[serde(rename = "Height")]
+    #[serde(rename = "height", alias = "Height")]
     pub height: u64,
 }
 
@@ -510,9 +574,10 @@ impl LockAccountBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct NetworkMaintenanceBody {
-    #[serde(rename = "Operations")]
+    #[serde(rename = "operations", alias = "Operations")]
     pub operations: Vec<serde_json::Value>,
 }
 
@@ -525,10 +590,13 @@ impl NetworkMaintenanceBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct RemoteTransactionBody {
-    #[serde(rename = "Hash")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "hash", alias = "Hash")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    #[serde(with = "hex_option_vec")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub hash: Option<Vec<u8>>,
 }
 
@@ -548,15 +616,18 @@ impl RemoteTransactionBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct SendTokensBody {
-    #[serde(rename = "Hash")]
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[serde(rename = "hash", alias = "Hash")]
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    #[serde(with = "hex_option_vec")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]
     pub hash: Option<Vec<u8>>,
-    #[serde(rename = "Meta")]
+    #[serde(rename = "meta", alias = "Meta")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub meta: Option<serde_json::Value>,
-    #[serde(rename = "To")]
+    #[serde(rename = "to", alias = "To")]
     pub to: Vec<serde_json::Value>,
 }
 
@@ -604,6 +675,7 @@ impl SendTokensBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct SystemGenesisBody {
     // No fields defined
@@ -617,11 +689,12 @@ impl SystemGenesisBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct SystemWriteDataBody {
-    #[serde(rename = "Entry")]
+    #[serde(rename = "entry", alias = "Entry")]
     pub entry: serde_json::Value,
-    #[serde(rename = "WriteToState")]
+    #[serde(rename = "writeToState", alias = "WriteToState")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub write_to_state: Option<bool>,
 }
@@ -640,9 +713,10 @@ impl SystemWriteDataBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TransferCreditsBody {
-    #[serde(rename = "To")]
+    #[serde(rename = "to", alias = "To")]
     pub to: Vec<serde_json::Value>,
 }
 
@@ -675,9 +749,10 @@ impl TransferCreditsBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateAccountAuthBody {
-    #[serde(rename = "Operations")]
+    #[serde(rename = "operations", alias = "Operations")]
     pub operations: Vec<serde_json::Value>,
 }
 
@@ -705,10 +780,12 @@ impl UpdateAccountAuthBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateKeyBody {
-    #[serde(rename = "NewKeyHash")]
+    #[serde(rename = "newKeyHash", alias = "NewKeyHash")]
     #[serde(with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub new_key_hash: Vec<u8>,
 }
 
@@ -726,9 +803,10 @@ impl UpdateKeyBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct UpdateKeyPageBody {
-    #[serde(rename = "Operation")]
+    #[serde(rename = "operation", alias = "Operation")]
     pub operation: Vec<serde_json::Value>,
 }
 
@@ -756,14 +834,15 @@ impl UpdateKeyPageBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct WriteDataBody {
-    #[serde(rename = "Entry")]
+    #[serde(rename = "entry", alias = "Entry")]
     pub entry: serde_json::Value,
-    #[serde(rename = "Scratch")]
+    #[serde(rename = "scratch", alias = "Scratch")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub scratch: Option<bool>,
-    #[serde(rename = "WriteToState")]
+    #[serde(rename = "writeToState", alias = "WriteToState")]
     #[serde(skip_serializing_if = "Option::is_none")]
     pub write_to_state: Option<bool>,
 }
@@ -782,11 +861,12 @@ impl WriteDataBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct WriteDataToBody {
-    #[serde(rename = "Recipient")]
+    #[serde(rename = "recipient", alias = "Recipient")]
     pub recipient: String,
-    #[serde(rename = "Entry")]
+    #[serde(rename = "entry", alias = "Entry")]
     pub entry: serde_json::Value,
 }
 
@@ -808,6 +888,7 @@ impl WriteDataToBody {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum TransactionBody {
     #[serde(rename = "acmeFaucet")]
@@ -844,7 +925,7 @@ pub enum TransactionBody {
     LockAccount(LockAccountBody),
     #[serde(rename = "networkMaintenance")]
     NetworkMaintenance(NetworkMaintenanceBody),
-    #[serde(rename = "remoteTransaction")]
+    #[serde(rename = "remoteTransaction", alias = "signPending")]
     RemoteTransaction(RemoteTransactionBody),
     #[serde(rename = "sendTokens")]
     SendTokens(SendTokensBody),
@@ -905,119 +986,119 @@ pub fn __minimal_tx_body_json(wire_tag: &str) -> serde_json::Value {
     match wire_tag {
         "acmeFaucet" => serde_json::json!({
             "type": "acmeFaucet",
-            "Url": ""
+            "url": ""
         }),
         "activateProtocolVersion" => serde_json::json!({
             "type": "activateProtocolVersion"
         }),
         "addCredits" => serde_json::json!({
             "type": "addCredits",
-            "Recipient": "",
-            "Amount": "0",
-            "Oracle": 0
+            "recipient": "",
+            "amount": "0",
+            "oracle": 0
         }),
         "blockValidatorAnchor" => serde_json::json!({
             "type": "blockValidatorAnchor",
-            "AcmeBurnt": "0"
+            "acmeBurnt": "0"
         }),
         "burnCredits" => serde_json::json!({
             "type": "burnCredits",
-            "Amount": 0
+            "amount": 0
         }),
         "burnTokens" => serde_json::json!({
             "type": "burnTokens",
-            "Amount": "0"
+            "amount": "0"
         }),
         "createDataAccount" => serde_json::json!({
             "type": "createDataAccount",
-            "Url": ""
+            "url": ""
         }),
         "createIdentity" => serde_json::json!({
             "type": "createIdentity",
-            "Url": ""
+            "url": ""
         }),
         "createKeyBook" => serde_json::json!({
             "type": "createKeyBook",
-            "Url": "",
-            "PublicKeyHash": "00"
+            "url": "",
+            "publicKeyHash": "00"
         }),
         "createKeyPage" => serde_json::json!({
             "type": "createKeyPage",
-            "Keys": []
+            "keys": []
         }),
         "createLiteTokenAccount" => serde_json::json!({
             "type": "createLiteTokenAccount"
         }),
         "createToken" => serde_json::json!({
             "type": "createToken",
-            "Url": "",
-            "Symbol": "",
-            "Precision": 0
+            "url": "",
+            "symbol": "",
+            "precision": 0
         }),
         "createTokenAccount" => serde_json::json!({
             "type": "createTokenAccount",
-            "Url": "",
-            "TokenUrl": ""
+            "url": "",
+            "tokenUrl": ""
         }),
         "directoryAnchor" => serde_json::json!({
             "type": "directoryAnchor",
-            "Updates": [],
-            "Receipts": [],
-            "MakeMajorBlock": 0,
-            "MakeMajorBlockTime": {}
+            "updates": [],
+            "receipts": [],
+            "makeMajorBlock": 0,
+            "makeMajorBlockTime": {}
         }),
         "issueTokens" => serde_json::json!({
             "type": "issueTokens",
-            "Recipient": "",
-            "Amount": "0",
-            "To": []
+            "recipient": "",
+            "amount": "0",
+            "to": []
         }),
         "lockAccount" => serde_json::json!({
             "type": "lockAccount",
-            "Height": 0
+            "height": 0
         }),
         "networkMaintenance" => serde_json::json!({
             "type": "networkMaintenance",
-            "Operations": []
+            "operations": []
         }),
         "remoteTransaction" => serde_json::json!({
             "type": "remoteTransaction"
         }),
         "sendTokens" => serde_json::json!({
             "type": "sendTokens",
-            "To": []
+            "to": []
         }),
         "systemGenesis" => serde_json::json!({
             "type": "systemGenesis"
         }),
         "systemWriteData" => serde_json::json!({
             "type": "systemWriteData",
-            "Entry": {}
+            "entry": {}
         }),
         "transferCredits" => serde_json::json!({
             "type": "transferCredits",
-            "To": []
+            "to": []
         }),
         "updateAccountAuth" => serde_json::json!({
             "type": "updateAccountAuth",
-            "Operations": []
+            "operations": []
         }),
         "updateKey" => serde_json::json!({
             "type": "updateKey",
-            "NewKeyHash": "00"
+            "newKeyHash": "00"
         }),
         "updateKeyPage" => serde_json::json!({
             "type": "updateKeyPage",
-            "Operation": []
+            "operation": []
         }),
         "writeData" => serde_json::json!({
             "type": "writeData",
-            "Entry": {}
+            "entry": {}
         }),
         "writeDataTo" => serde_json::json!({
             "type": "writeDataTo",
-            "Recipient": "",
-            "Entry": {}
+            "recipient": "",
+            "entry": {}
         }),
         _ => serde_json::json!({"type": wire_tag}),
     }
@@ -20,12 +20,14 @@ pub use serde_json::Value as JsonValue;
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ADI {
     #[serde(rename = "Url")]
     pub url: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AccountAuth {
     #[serde(rename = "Authorities")]
     pub authorities: AuthorityEntry,
@@ -38,36 +40,42 @@ pub struct AccountAuth {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AccumulateDataEntry {
     #[serde(rename = "Data")]
     pub data: Vec<u8>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AcmeFaucet {
     #[serde(rename = "Url")]
     pub url: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AcmeOracle {
     #[serde(rename = "Price")]
     pub price: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ActivateProtocolVersion {
     #[serde(rename = "Version")]
     pub version: ExecutorVersion,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AddAccountAuthorityOperation {
     #[serde(rename = "Authority")]
     pub authority: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AddCredits {
     #[serde(rename = "Recipient")]
     pub recipient: String,
@@ -78,6 +86,7 @@ pub struct AddCredits {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AddCreditsResult {
     #[serde(rename = "Amount")]
     pub amount: String,
@@ -88,6 +97,7 @@ pub struct AddCreditsResult {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AddKeyOperation {
     #[serde(rename = "Entry")]
     pub entry: KeySpecParams,
@@ -97,6 +107,7 @@ pub struct AddKeyOperation {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AnchorLedger {
     #[serde(rename = "Url")]
     pub url: String,
@@ -113,6 +124,7 @@ pub struct AnchorLedger {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AnchorMetadata {
     #[serde(rename = "Account")]
     pub account: String,
@@ -127,6 +139,7 @@ pub struct AnchorMetadata {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AnnotatedReceipt {
     #[serde(rename = "Receipt")]
     pub receipt: Vec<u8>,
@@ -135,6 +148,7 @@ pub struct AnnotatedReceipt {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AuthorityEntry {
     #[serde(rename = "Url")]
     pub url: String,
@@ -143,6 +157,7 @@ pub struct AuthorityEntry {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct AuthoritySignature {
     #[serde(rename = "Origin")]
     pub origin: String,
@@ -161,6 +176,7 @@ pub struct AuthoritySignature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct BTCLegacySignature {
     #[serde(rename = "PublicKey")]
     pub public_key: Vec<u8>,
@@ -183,6 +199,7 @@ pub struct BTCLegacySignature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct BTCSignature {
     #[serde(rename = "PublicKey")]
     pub public_key: Vec<u8>,
@@ -205,6 +222,7 @@ pub struct BTCSignature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct BlockEntry {
     #[serde(rename = "Account")]
     pub account: String,
@@ -215,6 +233,7 @@ pub struct BlockEntry {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct BlockLedger {
     #[serde(rename = "Url")]
     pub url: String,
@@ -227,6 +246,7 @@ pub struct BlockLedger {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct BlockValidatorAnchor {
     #[serde(rename = "AcmeBurnt")]
     pub acme_burnt: String,
@@ -236,18 +256,21 @@ pub struct BlockValidatorAnchor {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct BurnCredits {
     #[serde(rename = "Amount")]
     pub amount: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct BurnTokens {
     #[serde(rename = "Amount")]
     pub amount: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ChainMetadata {
     #[serde(rename = "Name")]
     pub name: String,
@@ -256,6 +279,7 @@ pub struct ChainMetadata {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ChainParams {
     #[serde(rename = "Data")]
     pub data: Vec<u8>,
@@ -267,6 +291,7 @@ pub struct ChainParams {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CreateDataAccount {
     #[serde(rename = "Url")]
     pub url: String,
@@ -275,6 +300,7 @@ pub struct CreateDataAccount {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CreateIdentity {
     #[serde(rename = "Url")]
     pub url: String,
@@ -287,6 +313,7 @@ pub struct CreateIdentity {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CreateKeyBook {
     #[serde(rename = "Url")]
     pub url: String,
@@ -297,17 +324,20 @@ pub struct CreateKeyBook {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CreateKeyPage {
     #[serde(rename = "Keys")]
     pub keys: KeySpecParams,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CreateLiteTokenAccount {
 
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CreateToken {
     #[serde(rename = "Url")]
     pub url: String,
@@ -324,6 +354,7 @@ pub struct CreateToken {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CreateTokenAccount {
     #[serde(rename = "Url")]
     pub url: String,
@@ -336,6 +367,7 @@ pub struct CreateTokenAccount {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct CreditRecipient {
     #[serde(rename = "Url")]
     pub url: String,
@@ -344,6 +376,7 @@ pub struct CreditRecipient {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct DataAccount {
     #[serde(rename = "Url")]
     pub url: String,
@@ -355,6 +388,7 @@ pub struct DataAccount {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct DelegatedSignature {
     #[serde(rename = "Signature")]
     pub signature: serde_json::Value,
@@ -363,6 +397,7 @@ pub struct DelegatedSignature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct DirectoryAnchor {
     #[serde(rename = "Updates")]
     pub updates: NetworkAccountUpdate,
@@ -375,18 +410,21 @@ pub struct DirectoryAnchor {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct DisableAccountAuthOperation {
     #[serde(rename = "Authority")]
     pub authority: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct DoubleHashDataEntry {
     #[serde(rename = "Data")]
     pub data: Vec<u8>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ED25519Signature {
     #[serde(rename = "PublicKey")]
     pub public_key: Vec<u8>,
@@ -409,6 +447,7 @@ pub struct ED25519Signature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ETHSignature {
     #[serde(rename = "PublicKey")]
     pub public_key: Vec<u8>,
@@ -431,6 +470,7 @@ pub struct ETHSignature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct EcdsaSha256Signature {
     #[serde(rename = "PublicKey")]
     pub public_key: Vec<u8>,
@@ -453,11 +493,13 @@ pub struct EcdsaSha256Signature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct EmptyResult {
 
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct EnableAccountAuthOperation {
     #[serde(rename = "Authority")]
     pub authority: String,
@@ -470,12 +512,14 @@ pub struct EnableAccountAuthOperation {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ExpireOptions {
     #[serde(rename = "AtTime")]
     pub at_time: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct FactomDataEntry {
     #[serde(rename = "AccountId")]
     pub account_id: [u8; 32],
@@ -486,11 +530,13 @@ pub struct FactomDataEntry {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct FactomDataEntryWrapper {
 
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct FeeSchedule {
     #[serde(rename = "CreateIdentitySliding")]
     pub create_identity_sliding: String,
@@ -501,12 +547,14 @@ pub struct FeeSchedule {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct HoldUntilOptions {
     #[serde(rename = "MinorBlock")]
     pub minor_block: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct IndexEntry {
     #[serde(rename = "Source")]
     pub source: u64,
@@ -521,6 +569,7 @@ pub struct IndexEntry {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct InternalSignature {
     #[serde(rename = "Cause")]
     pub cause: [u8; 32],
@@ -529,6 +578,7 @@ pub struct InternalSignature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct IssueTokens {
     #[serde(rename = "Recipient")]
     pub recipient: String,
@@ -539,6 +589,7 @@ pub struct IssueTokens {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct KeyBook {
     #[serde(rename = "Url")]
     pub url: String,
@@ -549,6 +600,7 @@ pub struct KeyBook {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct KeyPage {
     #[serde(rename = "KeyBook")]
     pub key_book: String,
@@ -576,6 +628,7 @@ pub struct KeyPage {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct KeySpec {
     #[serde(rename = "PublicKeyHash")]
     pub public_key_hash: Vec<u8>,
@@ -586,6 +639,7 @@ pub struct KeySpec {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct KeySpecParams {
     #[serde(rename = "KeyHash")]
     pub key_hash: Vec<u8>,
@@ -594,6 +648,7 @@ pub struct KeySpecParams {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct LegacyED25519Signature {
     #[serde(rename = "Timestamp")]
     pub timestamp: u64,
@@ -612,12 +667,14 @@ pub struct LegacyED25519Signature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct LiteDataAccount {
     #[serde(rename = "Url")]
     pub url: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct LiteIdentity {
     #[serde(rename = "Url")]
     pub url: String,
@@ -628,6 +685,7 @@ pub struct LiteIdentity {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct LiteTokenAccount {
     #[serde(rename = "Url")]
     pub url: String,
@@ -640,12 +698,14 @@ pub struct LiteTokenAccount {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct LockAccount {
     #[serde(rename = "Height")]
     pub height: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct MetricsRequest {
     #[serde(rename = "Metric")]
     pub metric: String,
@@ -654,12 +714,14 @@ pub struct MetricsRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct MetricsResponse {
     #[serde(rename = "Value")]
     pub value: serde_json::Value,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NetworkAccountUpdate {
     #[serde(rename = "Name")]
     pub name: String,
@@ -668,6 +730,7 @@ pub struct NetworkAccountUpdate {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NetworkDefinition {
     #[serde(rename = "NetworkName")]
     pub network_name: String,
@@ -680,6 +743,7 @@ pub struct NetworkDefinition {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NetworkGlobals {
     #[serde(rename = "OperatorAcceptThreshold")]
     pub operator_accept_threshold: Rational,
@@ -696,6 +760,7 @@ pub struct NetworkGlobals {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NetworkLimits {
     #[serde(rename = "DataEntryParts")]
     pub data_entry_parts: u64,
@@ -714,6 +779,7 @@ pub struct NetworkLimits {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct NetworkMaintenance {
     #[serde(rename = "Operations")]
     pub operations: serde_json::Value,
@@ -723,6 +789,7 @@ pub struct NetworkMaintenance {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Object {
     #[serde(rename = "Type")]
     pub r#type: ObjectType,
@@ -736,6 +803,7 @@ pub struct Object {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PartitionAnchor {
     #[serde(rename = "Source")]
     pub source: String,
@@ -752,6 +820,7 @@ pub struct PartitionAnchor {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PartitionAnchorReceipt {
     #[serde(rename = "Anchor")]
     pub anchor: PartitionAnchor,
@@ -760,6 +829,7 @@ pub struct PartitionAnchorReceipt {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PartitionExecutorVersion {
     #[serde(rename = "Partition")]
     pub partition: String,
@@ -768,6 +838,7 @@ pub struct PartitionExecutorVersion {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PartitionInfo {
     #[serde(rename = "ID")]
     pub id: String,
@@ -776,6 +847,7 @@ pub struct PartitionInfo {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PartitionSignature {
     #[serde(rename = "SourceNetwork")]
     pub source_network: String,
@@ -788,6 +860,7 @@ pub struct PartitionSignature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PartitionSyntheticLedger {
     #[serde(rename = "Url")]
     pub url: String,
@@ -805,12 +878,14 @@ pub struct PartitionSyntheticLedger {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct PendingTransactionGCOperation {
     #[serde(rename = "Account")]
     pub account: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RCD1Signature {
     #[serde(rename = "PublicKey")]
     pub public_key: Vec<u8>,
@@ -833,6 +908,7 @@ pub struct RCD1Signature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Rational {
     #[serde(rename = "Numerator")]
     pub numerator: u64,
@@ -841,6 +917,7 @@ pub struct Rational {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ReceiptSignature {
     #[serde(rename = "SourceNetwork")]
     pub source_network: String,
@@ -851,6 +928,7 @@ pub struct ReceiptSignature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RemoteSignature {
     #[serde(rename = "Destination")]
     pub destination: String,
@@ -861,6 +939,7 @@ pub struct RemoteSignature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RemoteTransaction {
     #[serde(rename = "Hash")]
     pub hash: [u8; 32],
@@ -870,18 +949,21 @@ pub struct RemoteTransaction {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RemoveAccountAuthorityOperation {
     #[serde(rename = "Authority")]
     pub authority: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RemoveKeyOperation {
     #[serde(rename = "Entry")]
     pub entry: KeySpecParams,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Route {
     #[serde(rename = "Length")]
     pub length: u64,
@@ -892,6 +974,7 @@ pub struct Route {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RouteOverride {
     #[serde(rename = "Account")]
     pub account: String,
@@ -900,6 +983,7 @@ pub struct RouteOverride {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RoutingTable {
     #[serde(rename = "Overrides")]
     pub overrides: RouteOverride,
@@ -908,6 +992,7 @@ pub struct RoutingTable {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct RsaSha256Signature {
     #[serde(rename = "PublicKey")]
     pub public_key: Vec<u8>,
@@ -930,6 +1015,7 @@ pub struct RsaSha256Signature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SendTokens {
     #[serde(rename = "Hash")]
     pub hash: [u8; 32],
@@ -940,24 +1026,28 @@ pub struct SendTokens {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SetRejectThresholdKeyPageOperation {
     #[serde(rename = "Threshold")]
     pub threshold: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SetResponseThresholdKeyPageOperation {
     #[serde(rename = "Threshold")]
     pub threshold: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SetThresholdKeyPageOperation {
     #[serde(rename = "Threshold")]
     pub threshold: u64,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SignatureSet {
     #[serde(rename = "Vote")]
     pub vote: VoteType,
@@ -975,6 +1065,7 @@ pub struct SignatureSet {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SyntheticBurnTokens {
     #[serde(rename = "Amount")]
     pub amount: String,
@@ -983,12 +1074,14 @@ pub struct SyntheticBurnTokens {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SyntheticCreateIdentity {
     #[serde(rename = "Accounts")]
     pub accounts: serde_json::Value,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SyntheticDepositCredits {
     #[serde(rename = "Amount")]
     pub amount: u64,
@@ -999,6 +1092,7 @@ pub struct SyntheticDepositCredits {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SyntheticDepositTokens {
     #[serde(rename = "Token")]
     pub token: String,
@@ -1011,6 +1105,7 @@ pub struct SyntheticDepositTokens {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SyntheticForwardTransaction {
     #[serde(rename = "Signatures")]
     pub signatures: RemoteSignature,
@@ -1019,6 +1114,7 @@ pub struct SyntheticForwardTransaction {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SyntheticLedger {
     #[serde(rename = "Url")]
     pub url: String,
@@ -1027,6 +1123,7 @@ pub struct SyntheticLedger {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SyntheticOrigin {
     #[serde(rename = "Cause")]
     pub cause: [u8; 32],
@@ -1041,18 +1138,21 @@ pub struct SyntheticOrigin {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SyntheticWriteData {
     #[serde(rename = "Entry")]
     pub entry: serde_json::Value,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum SystemGenesis {
     // Union variants would be populated based on the union definition
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SystemLedger {
     #[serde(rename = "Url")]
     pub url: String,
@@ -1073,6 +1173,7 @@ pub struct SystemLedger {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct SystemWriteData {
     #[serde(rename = "Entry")]
     pub entry: serde_json::Value,
@@ -1081,6 +1182,7 @@ pub struct SystemWriteData {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TokenAccount {
     #[serde(rename = "Url")]
     pub url: String,
@@ -1091,6 +1193,7 @@ pub struct TokenAccount {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TokenIssuer {
     #[serde(rename = "Url")]
     pub url: String,
@@ -1107,6 +1210,7 @@ pub struct TokenIssuer {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TokenIssuerProof {
     #[serde(rename = "Transaction")]
     pub transaction: CreateToken,
@@ -1115,6 +1219,7 @@ pub struct TokenIssuerProof {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TokenRecipient {
     #[serde(rename = "Url")]
     pub url: String,
@@ -1123,6 +1228,7 @@ pub struct TokenRecipient {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct Transaction {
     #[serde(rename = "Header")]
     pub header: TransactionHeader,
@@ -1134,6 +1240,7 @@ pub struct Transaction {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TransactionHeader {
     #[serde(rename = "Principal")]
     pub principal: String,
@@ -1155,12 +1262,14 @@ pub struct TransactionHeader {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TransactionResultSet {
     #[serde(rename = "Results")]
     pub results: TransactionStatus,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TransactionStatus {
     #[serde(rename = "TxID")]
     pub tx_id: [u8; 32],
@@ -1204,18 +1313,21 @@ pub struct TransactionStatus {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TransferCredits {
     #[serde(rename = "To")]
     pub to: CreditRecipient,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TxIdSet {
     #[serde(rename = "Entries")]
     pub entries: [u8; 32],
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct TypedDataSignature {
     #[serde(rename = "PublicKey")]
     pub public_key: Vec<u8>,
@@ -1240,12 +1352,14 @@ pub struct TypedDataSignature {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct UnknownAccount {
     #[serde(rename = "Url")]
     pub url: String,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct UnknownSigner {
     #[serde(rename = "Url")]
     pub url: String,
@@ -1254,12 +1368,14 @@ pub struct UnknownSigner {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct UpdateAccountAuth {
     #[serde(rename = "Operations")]
     pub operations: serde_json::Value,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct UpdateAllowedKeyPageOperation {
     #[serde(rename = "Allow")]
     pub allow: TransactionType,
@@ -1268,12 +1384,14 @@ pub struct UpdateAllowedKeyPageOperation {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct UpdateKey {
     #[serde(rename = "NewKeyHash")]
     pub new_key_hash: Vec<u8>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct UpdateKeyOperation {
     #[serde(rename = "OldEntry")]
     pub old_entry: KeySpecParams,
@@ -1282,12 +1400,14 @@ pub struct UpdateKeyOperation {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct UpdateKeyPage {
     #[serde(rename = "Operation")]
     pub operation: serde_json::Value,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ValidatorInfo {
     #[serde(rename = "PublicKey")]
     pub public_key: Vec<u8>,
@@ -1300,6 +1420,7 @@ pub struct ValidatorInfo {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct ValidatorPartitionInfo {
     #[serde(rename = "ID")]
     pub id: String,
@@ -1311,6 +1432,7 @@ pub struct ValidatorPartitionInfo {
 
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct WriteData {
     #[serde(rename = "Entry")]
     pub entry: serde_json::Value,
@@ -1321,6 +1443,7 @@ pub struct WriteData {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct WriteDataResult {
     #[serde(rename = "EntryHash")]
     pub entry_hash: [u8; 32],
@@ -1331,6 +1454,7 @@ pub struct WriteDataResult {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct WriteDataTo {
     #[serde(rename = "Recipient")]
     pub recipient: String,
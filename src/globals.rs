@@ -0,0 +1,405 @@
+//! Network globals: fee schedule, limits and acceptance thresholds
+//!
+//! The directory network publishes its tunable parameters as the `globals`
+//! section of `network-status`. [`NetworkGlobals`] is the typed form of that
+//! section; fields a node leaves out keep the protocol defaults, so
+//! [`NetworkGlobals::default`] is usable offline. Transaction limits and fee
+//! estimates are computed from a `NetworkGlobals` value rather than from
+//! constants, so refreshing it with [`AccumulateClient::network_globals`]
+//! keeps them in step with the network.
+
+use crate::client::AccumulateClient;
+use crate::errors::{Error, ValidationError};
+use crate::helpers::{marshal_body_to_binary, marshal_header_json};
+use crate::json_rpc_client::JsonRpcError;
+use crate::types::{NetworkStatusOptions, V3NetworkStatus};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Maximum size of a binary-encoded transaction (header plus body), in bytes
+pub const TRANSACTION_SIZE_MAX: usize = 20 * 1024;
+
+/// Fixed fees in credit hundredths (100 = 1 credit), as in the Go protocol
+pub mod fees {
+    /// Signature fee
+    pub const SIGNATURE: u64 = 1;
+    /// Creating a root identity when no sliding fee applies
+    pub const CREATE_IDENTITY: u64 = 50_000;
+    /// Creating a token, data or lite token account
+    pub const CREATE_ACCOUNT: u64 = 2_500;
+    /// sendTokens / issueTokens, first recipient
+    pub const TRANSFER_TOKENS: u64 = 300;
+    /// sendTokens / issueTokens, each further recipient
+    pub const TRANSFER_TOKENS_EXTRA: u64 = 100;
+    /// Creating a token issuer
+    pub const CREATE_TOKEN: u64 = 500_000;
+    /// Small general operations (burns, locks, credit transfers)
+    pub const GENERAL_SMALL: u64 = 10;
+    /// Creating a key page or key book
+    pub const CREATE_KEY_PAGE: u64 = 10_000;
+    /// Each key beyond the first on a new key page
+    pub const CREATE_KEY_PAGE_EXTRA: u64 = 100;
+    /// Each 256 bytes of data, and each 256 bytes of transaction past the first
+    pub const DATA: u64 = 10;
+    /// Each 256 bytes of scratch data
+    pub const SCRATCH_DATA: u64 = 1;
+    /// Authority updates, first operation
+    pub const UPDATE_AUTH: u64 = 300;
+    /// Authority updates, each further operation
+    pub const UPDATE_AUTH_EXTRA: u64 = 100;
+}
+
+/// A threshold expressed as a fraction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Ratio {
+    /// Numerator
+    pub numerator: u64,
+    /// Denominator
+    pub denominator: u64,
+}
+
+impl Ratio {
+    /// Number of approvals out of `count` needed to reach the ratio (rounded up)
+    #[must_use]
+    pub const fn threshold(&self, count: u64) -> u64 {
+        if self.denominator == 0 {
+            return count;
+        }
+        (count * self.numerator + self.denominator - 1) / self.denominator
+    }
+}
+
+impl Default for Ratio {
+    fn default() -> Self {
+        Self {
+            numerator: 2,
+            denominator: 3,
+        }
+    }
+}
+
+/// Identity creation fees set by the network
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FeeSchedule {
+    /// Fee for a root identity by name length: entry `n - 1` applies to names
+    /// of `n` characters; longer names pay [`fees::CREATE_IDENTITY`]
+    pub create_identity_sliding: Vec<u64>,
+    /// Fee for a sub-identity; 0 means the root identity fee applies
+    pub create_sub_identity: u64,
+    /// Discount for an identity created without a key book
+    pub bare_identity_discount: u64,
+}
+
+/// Size and count limits enforced by the network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NetworkLimits {
+    /// Maximum number of parts in a data entry
+    pub data_entry_parts: u64,
+    /// Maximum number of authorities on an account
+    pub account_authorities: u64,
+    /// Maximum number of pages in a key book
+    pub book_pages: u64,
+    /// Maximum number of entries on a key page
+    pub page_entries: u64,
+    /// Maximum number of accounts under an identity
+    pub identity_accounts: u64,
+    /// Maximum number of major blocks a transaction may stay pending
+    pub pending_major_blocks: u64,
+    /// Maximum number of events per block
+    pub events_per_block: u64,
+}
+
+impl Default for NetworkLimits {
+    fn default() -> Self {
+        Self {
+            data_entry_parts: 100,
+            account_authorities: 20,
+            book_pages: 20,
+            page_entries: 100,
+            identity_accounts: 1000,
+            pending_major_blocks: 14,
+            events_per_block: 100,
+        }
+    }
+}
+
+/// Typed network globals, as published in `network-status`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct NetworkGlobals {
+    /// Fraction of operators that must sign operator transactions
+    pub operator_accept_threshold: Ratio,
+    /// Fraction of validators that must agree on blocks
+    pub validator_accept_threshold: Ratio,
+    /// Cron schedule of major blocks
+    pub major_block_schedule: String,
+    /// Whether partitions anchor empty blocks
+    pub anchor_empty_blocks: bool,
+    /// Identity creation fees
+    pub fee_schedule: FeeSchedule,
+    /// Size and count limits
+    pub limits: NetworkLimits,
+}
+
+impl Default for NetworkGlobals {
+    fn default() -> Self {
+        Self {
+            operator_accept_threshold: Ratio::default(),
+            validator_accept_threshold: Ratio::default(),
+            major_block_schedule: "0 */12 * * *".to_string(),
+            anchor_empty_blocks: false,
+            fee_schedule: FeeSchedule::default(),
+            limits: NetworkLimits::default(),
+        }
+    }
+}
+
+impl NetworkGlobals {
+    /// Read the globals out of a network status response
+    ///
+    /// Missing or malformed sections fall back to the protocol defaults.
+    #[must_use]
+    pub fn from_network_status(status: &V3NetworkStatus) -> Self {
+        status
+            .globals
+            .as_ref()
+            .and_then(|g| serde_json::from_value(g.clone()).ok())
+            .unwrap_or_default()
+    }
+
+    /// Check a transaction against the network's size and count limits
+    ///
+    /// `header` and `body` are the JSON forms used in envelopes.
+    pub fn validate_transaction(&self, header: &Value, body: &Value) -> Result<(), ValidationError> {
+        let limits = &self.limits;
+
+        let authorities = header
+            .get("authorities")
+            .and_then(Value::as_array)
+            .map_or(0, Vec::len);
+        check_count("header.authorities", authorities, limits.account_authorities)?;
+
+        match body_type(body) {
+            "writeData" | "writeDataTo" | "syntheticWriteData" => {
+                let parts = body
+                    .get("entry")
+                    .and_then(|e| e.get("data"))
+                    .and_then(Value::as_array)
+                    .map_or(0, Vec::len);
+                check_count("entry.data", parts, limits.data_entry_parts)?;
+            }
+            "createKeyPage" => {
+                let keys = body.get("keys").and_then(Value::as_array).map_or(0, Vec::len);
+                check_count("keys", keys, limits.page_entries)?;
+            }
+            "createIdentity" | "createTokenAccount" | "createDataAccount" | "createToken" | "createKeyBook" => {
+                let authorities = body
+                    .get("authorities")
+                    .and_then(Value::as_array)
+                    .map_or(0, Vec::len);
+                check_count("authorities", authorities, limits.account_authorities)?;
+            }
+            _ => {}
+        }
+
+        let size = transaction_size(header, body).map_err(|e| ValidationError::InvalidFieldValue {
+            field: "body".to_string(),
+            reason: e.to_string(),
+        })?;
+        if size > TRANSACTION_SIZE_MAX {
+            return Err(ValidationError::OutOfRange {
+                field: "transaction size".to_string(),
+                min: "0".to_string(),
+                max: TRANSACTION_SIZE_MAX.to_string(),
+            });
+        }
+        Ok(())
+    }
+
+    /// Estimate the fee of a transaction in credit hundredths (100 = 1 credit)
+    ///
+    /// Follows the network's fee rules: a base fee per transaction type, extras
+    /// for additional recipients, keys or operations, data fees per 256 bytes,
+    /// and a surcharge for each 256 bytes of transaction past the first.
+    /// Signature fees are not included.
+    pub fn estimate_fee(&self, header: &Value, body: &Value) -> Result<u64, Error> {
+        let (fee, surcharge) = self.fee_parts(header, body)?;
+        Ok(fee + surcharge)
+    }
+
+    /// Fee for the transaction type and the size surcharge, separately
+    pub(crate) fn fee_parts(&self, header: &Value, body: &Value) -> Result<(u64, u64), Error> {
+        self.validate_transaction(header, body)?;
+        let size = transaction_size(header, body).map_err(|e| Error::Encoding(e.to_string()))? as u64;
+        let count = |key: &str| body.get(key).and_then(Value::as_array).map_or(0, Vec::len) as u64;
+        let extra = |n: u64, fee: u64| n.saturating_sub(1) * fee;
+
+        let fee = match body_type(body) {
+            "createIdentity" => self.identity_fee(body),
+            "createTokenAccount" | "createDataAccount" | "createLiteTokenAccount" => fees::CREATE_ACCOUNT,
+            "createToken" => fees::CREATE_TOKEN,
+            "createKeyPage" => fees::CREATE_KEY_PAGE + extra(count("keys"), fees::CREATE_KEY_PAGE_EXTRA),
+            "createKeyBook" => fees::CREATE_KEY_PAGE,
+            "sendTokens" | "issueTokens" => {
+                fees::TRANSFER_TOKENS + extra(count("to"), fees::TRANSFER_TOKENS_EXTRA)
+            }
+            "updateKeyPage" | "updateAccountAuth" => {
+                fees::UPDATE_AUTH + extra(count("operations"), fees::UPDATE_AUTH_EXTRA)
+            }
+            "updateKey" => fees::UPDATE_AUTH,
+            "writeData" | "writeDataTo" => {
+                let scratch = body
+                    .get("scratch")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                let per_chunk = if scratch { fees::SCRATCH_DATA } else { fees::DATA };
+                per_chunk * ((data_size(body) + 255) / 256).max(1)
+            }
+            "acmeFaucet" | "addCredits" | "remoteTransaction" => 0,
+            _ => fees::GENERAL_SMALL,
+        };
+
+        Ok((fee, fees::DATA * (size.saturating_sub(1) / 256)))
+    }
+
+    fn identity_fee(&self, body: &Value) -> u64 {
+        let schedule = &self.fee_schedule;
+        let url = body.get("url").and_then(Value::as_str).unwrap_or("");
+        let path = url.trim_start_matches("acc://").trim_end_matches('/');
+        let (authority, sub_path) = path.split_once('/').unwrap_or((path, ""));
+
+        let mut fee = if !sub_path.is_empty() && schedule.create_sub_identity != 0 {
+            schedule.create_sub_identity
+        } else if sub_path.is_empty() {
+            let name = authority.strip_suffix(".acme").unwrap_or(authority);
+            name.chars()
+                .count()
+                .checked_sub(1)
+                .and_then(|i| schedule.create_identity_sliding.get(i).copied())
+                .unwrap_or(fees::CREATE_IDENTITY)
+        } else {
+            fees::CREATE_IDENTITY
+        };
+        if body.get("keyBookUrl").and_then(Value::as_str).is_none() {
+            fee = fee.saturating_sub(schedule.bare_identity_discount);
+        }
+        fee
+    }
+}
+
+impl AccumulateClient {
+    /// Fetch the current network globals from `network-status`
+    pub async fn network_globals(&self) -> Result<NetworkGlobals, JsonRpcError> {
+        let status = self.network_status(NetworkStatusOptions::default()).await?;
+        Ok(NetworkGlobals::from_network_status(&status))
+    }
+}
+
+fn body_type(body: &Value) -> &str {
+    body.get("type").and_then(Value::as_str).unwrap_or("")
+}
+
+fn check_count(field: &str, count: usize, max: u64) -> Result<(), ValidationError> {
+    if count as u64 > max {
+        return Err(ValidationError::OutOfRange {
+            field: field.to_string(),
+            min: "0".to_string(),
+            max: max.to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Binary size of header plus body, with a placeholder initiator
+fn transaction_size(header: &Value, body: &Value) -> Result<usize, JsonRpcError> {
+    let mut header = header.clone();
+    if header.get("initiator").is_none() {
+        header["initiator"] = json!(hex::encode([0u8; 32]));
+    }
+    Ok(marshal_header_json(&header)?.len() + marshal_body_to_binary(body)?.len())
+}
+
+/// Total decoded size of a data entry's parts
+fn data_size(body: &Value) -> u64 {
+    body.get("entry")
+        .and_then(|e| e.get("data"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .map(|part| (part.len() / 2) as u64)
+        .sum()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::TxBody;
+
+    fn header() -> Value {
+        json!({"principal": "acc://alice.acme/tokens"})
+    }
+
+    #[test]
+    fn test_parse_with_defaults_for_missing_fields() {
+        let status: V3NetworkStatus = serde_json::from_value(json!({
+            "globals": {
+                "operatorAcceptThreshold": {"numerator": 3, "denominator": 4},
+                "feeSchedule": {"createIdentitySliding": [100, 50, 25]},
+                "limits": {"dataEntryParts": 2}
+            }
+        }))
+        .unwrap();
+        let globals = NetworkGlobals::from_network_status(&status);
+        assert_eq!(globals.operator_accept_threshold.threshold(7), 6);
+        assert_eq!(globals.validator_accept_threshold, Ratio::default());
+        assert_eq!(globals.limits.data_entry_parts, 2);
+        assert_eq!(globals.limits.page_entries, 100);
+        assert_eq!(globals.fee_schedule.create_identity_sliding, vec![100, 50, 25]);
+    }
+
+    #[test]
+    fn test_limits_come_from_globals() {
+        let body = TxBody::write_data(&["a", "b", "c"]);
+        assert!(NetworkGlobals::default().validate_transaction(&header(), &body).is_ok());
+
+        let mut strict = NetworkGlobals::default();
+        strict.limits.data_entry_parts = 2;
+        let err = strict.validate_transaction(&header(), &body).unwrap_err();
+        assert!(err.to_string().contains("entry.data"), "{}", err);
+
+        let huge = "ab".repeat(TRANSACTION_SIZE_MAX);
+        let body = TxBody::write_data_hex(&[huge.as_str()]);
+        assert!(NetworkGlobals::default().validate_transaction(&header(), &body).is_err());
+    }
+
+    #[test]
+    fn test_fee_estimates() {
+        let globals = NetworkGlobals {
+            fee_schedule: FeeSchedule {
+                create_identity_sliding: vec![480_000, 240_000, 120_000],
+                create_sub_identity: 0,
+                bare_identity_discount: 1_000,
+            },
+            ..Default::default()
+        };
+
+        let send = TxBody::send_tokens_multi(&[("acc://bob.acme/tokens", "1"), ("acc://carol.acme/tokens", "2")]);
+        assert_eq!(globals.estimate_fee(&header(), &send).unwrap(), 400);
+
+        let short = TxBody::create_identity("acc://ab.acme", "acc://ab.acme/book", &"00".repeat(32));
+        assert_eq!(globals.estimate_fee(&header(), &short).unwrap(), 240_000);
+        let long = TxBody::create_identity("acc://abcdefgh.acme", "acc://abcdefgh.acme/book", &"00".repeat(32));
+        assert_eq!(globals.estimate_fee(&header(), &long).unwrap(), fees::CREATE_IDENTITY);
+
+        // 600 bytes of data: three 256-byte data chunks plus the size surcharge
+        let data = "00".repeat(600);
+        let write = TxBody::write_data_hex(&[data.as_str()]);
+        let fee = globals.estimate_fee(&header(), &write).unwrap();
+        assert_eq!(fee, 3 * fees::DATA + 2 * fees::DATA);
+    }
+}
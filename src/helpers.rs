@@ -9,13 +9,26 @@
 
 #![allow(clippy::unwrap_used, clippy::expect_used)]
 
+use crate::acc_url::AccUrl;
+use crate::amounts::{Amount, ACME_BASE_UNITS};
+use crate::audit::{AuditRecord, AuditSink, FinalStatusRecord, SignedRecord};
+use crate::auth::{AccountAuth, AuthError};
 use crate::client::AccumulateClient;
+use crate::codec::hex_utils::{parse_hex_field, parse_hex_hash};
+use crate::credits::CreditTracker;
+use crate::encoding_profile::EncodingProfile;
+use crate::errors::{Error, ValidationError};
+use crate::faucet::FaucetOptions;
+use crate::generated::transactions::TransactionBody;
 use crate::json_rpc_client::JsonRpcError;
+use crate::progress::{NoProgress, ProgressReporter, ProgressStep};
+use crate::tx_status::{message_scope, ConfirmationPolicy, TxStatus};
 use crate::AccOptions;
-use ed25519_dalek::{SigningKey, Signer};
+use ed25519_dalek::SigningKey;
 use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use sha2::{Digest, Sha256};
+use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use url::Url;
 
@@ -38,11 +51,14 @@ pub const DEVNET_V3: &str = "http://127.0.0.1:26660/v3";
 // =============================================================================
 
 /// Result of a transaction submission with wait
-#[derive(Debug, Clone)]
+///
+/// Serializable so a caller can persist it and, when the wait timed out,
+/// continue with [`AccumulateClient::resume_wait`] after a restart.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TxResult {
     /// Whether the transaction succeeded
     pub success: bool,
-    /// Transaction ID (if successful)
+    /// Transaction ID (if submitted)
     pub txid: Option<String>,
     /// Error message (if failed)
     pub error: Option<String>,
@@ -70,6 +86,118 @@ impl TxResult {
             response: None,
         }
     }
+
+    /// The transaction was submitted but not confirmed before the wait gave
+    /// up; `txid` can be passed to [`AccumulateClient::resume_wait`]
+    pub fn timed_out(txid: String, response: Value) -> Self {
+        Self {
+            success: false,
+            error: Some(format!("{TIMEOUT_PREFIX}{txid}")),
+            txid: Some(txid),
+            response: Some(response),
+        }
+    }
+
+    /// Whether the wait gave up before the transaction's outcome was known
+    #[must_use]
+    pub fn is_timeout(&self) -> bool {
+        !self.success
+            && self.txid.is_some()
+            && self.error.as_deref().is_some_and(|e| e.starts_with(TIMEOUT_PREFIX))
+    }
+}
+
+const TIMEOUT_PREFIX: &str = "Timeout waiting for delivery: ";
+
+/// Whether a transaction error says an addCredits oracle no longer matches
+/// the network's
+#[must_use]
+pub fn is_stale_oracle_error(error: &str) -> bool {
+    let error = error.to_ascii_lowercase();
+    error.contains("oracle") && ["match", "stale", "invalid"].iter().any(|word| error.contains(word))
+}
+
+/// Slippage [`QuickStart`] accepts when it re-prices a credit purchase
+pub const DEFAULT_ORACLE_SLIPPAGE_BPS: u32 = 100;
+
+/// A credit purchase priced at a quoted oracle, for
+/// [`SmartSigner::buy_credits_and_wait`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CreditPurchase {
+    /// Key page or lite identity receiving the credits
+    pub recipient: String,
+    /// Credits to buy
+    pub credits: u64,
+    /// Oracle price the purchase was quoted at
+    pub oracle: u64,
+    /// How much more ACME, in basis points of the quoted amount, a re-priced
+    /// purchase may cost
+    pub max_slippage_bps: u32,
+}
+
+impl CreditPurchase {
+    /// Buy `credits` for `recipient` at `oracle`, without re-pricing at a
+    /// higher cost
+    #[must_use]
+    pub fn new(recipient: &str, credits: u64, oracle: u64) -> Self {
+        Self {
+            recipient: recipient.to_string(),
+            credits,
+            oracle,
+            max_slippage_bps: 0,
+        }
+    }
+
+    /// Accept up to `bps` basis points more ACME when re-priced
+    #[must_use]
+    pub const fn with_max_slippage_bps(mut self, bps: u32) -> Self {
+        self.max_slippage_bps = bps;
+        self
+    }
+
+    /// ACME base units the purchase costs at `oracle`
+    #[must_use]
+    pub fn amount_at(&self, oracle: u64) -> u64 {
+        QuickStart::calculate_credits_amount(self.credits, oracle)
+    }
+
+    /// Whether buying at `oracle` stays within the slippage bound
+    #[must_use]
+    pub fn within_slippage(&self, oracle: u64) -> bool {
+        let quoted = u128::from(self.amount_at(self.oracle));
+        u128::from(self.amount_at(oracle)) * 10_000 <= quoted * (10_000 + u128::from(self.max_slippage_bps))
+    }
+}
+
+/// Current oracle price from network status
+async fn fetch_oracle_price(client: &AccumulateClient) -> Result<u64, JsonRpcError> {
+    let result: Value = client.v3_client.call_v3("network-status", json!({})).await?;
+
+    result.get("oracle")
+        .and_then(|o| o.get("price"))
+        .and_then(Value::as_u64)
+        .ok_or_else(|| JsonRpcError::General("Oracle price not found".to_string()))
+}
+
+/// How [`AccumulateClient::resume_wait`] polls for a transaction's outcome
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PollOptions {
+    /// Most status queries to send
+    pub max_attempts: u32,
+    /// Pause between queries; the client's `poll_interval` when `None`
+    pub interval: Option<Duration>,
+    /// Give up after this long; the client's `confirmation_timeout` when `None`
+    pub timeout: Option<Duration>,
+}
+
+impl Default for PollOptions {
+    fn default() -> Self {
+        Self {
+            max_attempts: 30,
+            interval: None,
+            timeout: None,
+        }
+    }
 }
 
 // =============================================================================
@@ -91,6 +219,11 @@ impl TxBody {
         })
     }
 
+    /// Create an AddCredits transaction body spending `amount` of ACME
+    pub fn add_credits_acme(recipient: &str, amount: Amount, oracle: u64) -> Value {
+        Self::add_credits(recipient, &amount.to_wire(), oracle)
+    }
+
     /// Create a CreateIdentity transaction body
     pub fn create_identity(url: &str, key_book_url: &str, public_key_hash: &str) -> Value {
         json!({
@@ -155,6 +288,22 @@ impl TxBody {
         })
     }
 
+    /// Create a SendTokens transaction body sending `amount` to one recipient
+    pub fn send_tokens(to_url: &str, amount: Amount) -> Value {
+        Self::send_tokens_single(to_url, &amount.to_wire())
+    }
+
+    /// Create a SendTokens transaction body from recipient amounts
+    pub fn send_tokens_to(recipients: &[(&str, Amount)]) -> Value {
+        let amounts: Vec<String> = recipients.iter().map(|(_, a)| a.to_wire()).collect();
+        let recipients: Vec<(&str, &str)> = recipients
+            .iter()
+            .zip(&amounts)
+            .map(|((url, _), amount)| (*url, amount.as_str()))
+            .collect();
+        Self::send_tokens_multi(&recipients)
+    }
+
     /// Create an IssueTokens transaction body for a single recipient
     pub fn issue_tokens_single(to_url: &str, amount: &str) -> Value {
         json!({
@@ -273,6 +422,44 @@ impl TxBody {
         })
     }
 
+    /// Create an UpdateKeyPage transaction body to replace one key with another
+    pub fn update_key_page_update_key(old_key_hash: &[u8], new_key_hash: &[u8]) -> Value {
+        json!({
+            "type": "updateKeyPage",
+            "operation": [{
+                "type": "update",
+                "oldEntry": {
+                    "keyHash": hex::encode(old_key_hash)
+                },
+                "newEntry": {
+                    "keyHash": hex::encode(new_key_hash)
+                }
+            }]
+        })
+    }
+
+    /// Create an UpdateKeyPage transaction body to set the reject threshold
+    pub fn update_key_page_set_reject_threshold(threshold: u64) -> Value {
+        json!({
+            "type": "updateKeyPage",
+            "operation": [{
+                "type": "setRejectThreshold",
+                "threshold": threshold
+            }]
+        })
+    }
+
+    /// Create an UpdateKeyPage transaction body to set the response threshold
+    pub fn update_key_page_set_response_threshold(threshold: u64) -> Value {
+        json!({
+            "type": "updateKeyPage",
+            "operation": [{
+                "type": "setResponseThreshold",
+                "threshold": threshold
+            }]
+        })
+    }
+
     /// Create a BurnTokens transaction body
     pub fn burn_tokens(amount: &str) -> Value {
         json!({
@@ -340,6 +527,50 @@ impl TxBody {
         })
     }
 
+    /// Create a RemoteTransaction body that references an existing transaction by hash
+    ///
+    /// Signing this body produces a signature-only envelope for the referenced
+    /// transaction, e.g. to add a second signature to a pending multi-sig transaction.
+    pub fn remote_transaction(tx_hash: &str) -> Value {
+        json!({
+            "type": "signPending",
+            "hash": tx_hash
+        })
+    }
+
+    /// Strictly check a body against the generated [`TransactionBody`] schema
+    ///
+    /// Opt-in guard for hand-built or `TxBody` bodies: the body must deserialize
+    /// into the generated type for its `type` tag, and every non-null key, at
+    /// any depth, must survive the round trip. Ordinary decoding ignores
+    /// unknown fields; here a misspelled key is reported, and so is a
+    /// legacy-cased key (`KeyBookUrl`) that only decodes through an alias
+    /// instead of the camelCase `TxBody` emits.
+    ///
+    /// Returns the body unchanged on success so it can wrap a builder call:
+    /// `let body = TxBody::strict(TxBody::lock_account(1000))?;`
+    pub fn strict(body: Value) -> Result<Value, Error> {
+        let tx_type = body.get("type").and_then(|t| t.as_str()).unwrap_or("").to_string();
+
+        let typed: TransactionBody = serde_json::from_value(body.clone()).map_err(|e| {
+            ValidationError::InvalidFieldValue {
+                field: "body".to_string(),
+                reason: format!("does not match the {tx_type} schema: {e}"),
+            }
+        })?;
+        let roundtrip = serde_json::to_value(&typed)?;
+
+        if let Some(field) = dropped_key(&body, &roundtrip) {
+            return Err(ValidationError::InvalidFieldValue {
+                field,
+                reason: format!("unknown field for {tx_type} body"),
+            }
+            .into());
+        }
+
+        Ok(body)
+    }
+
     /// Create a generic UpdateKeyPage transaction body with raw operations
     ///
     /// For simple cases, prefer update_key_page_add_key, update_key_page_remove_key,
@@ -352,6 +583,27 @@ impl TxBody {
     }
 }
 
+/// Path of the first non-null key in `input` that `output` lacks
+fn dropped_key(input: &Value, output: &Value) -> Option<String> {
+    match (input, output) {
+        (Value::Object(input), Value::Object(output)) => input.iter().find_map(|(key, value)| {
+            if value.is_null() {
+                return None;
+            }
+            output.get(key).map_or_else(
+                || Some(key.clone()),
+                |out| dropped_key(value, out).map(|path| format!("{key}.{path}")),
+            )
+        }),
+        (Value::Array(input), Value::Array(output)) => input
+            .iter()
+            .zip(output)
+            .enumerate()
+            .find_map(|(i, (value, out))| dropped_key(value, out).map(|path| format!("{i}.{path}"))),
+        _ => None,
+    }
+}
+
 // =============================================================================
 // KEY PAGE STATE
 // =============================================================================
@@ -371,6 +623,67 @@ pub struct KeyPageState {
     pub keys: Vec<KeyEntry>,
 }
 
+impl KeyPageState {
+    /// Build the state from a key page account record returned by `query`
+    ///
+    /// `default_url` is used when the record carries no URL.
+    #[must_use]
+    pub fn from_account(account: &Value, default_url: &str) -> Self {
+        let url = account.get("url")
+            .and_then(|v| v.as_str())
+            .unwrap_or(default_url)
+            .to_string();
+
+        let version = account.get("version")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(1);
+
+        let credit_balance = account.get("creditBalance")
+            .and_then(|v| v.as_u64())
+            .unwrap_or(0);
+
+        let accept_threshold = account.get("acceptThreshold")
+            .or_else(|| account.get("threshold"))
+            .and_then(|v| v.as_u64())
+            .unwrap_or(1);
+
+        let keys: Vec<KeyEntry> = if let Some(keys_arr) = account.get("keys").and_then(|k| k.as_array()) {
+            keys_arr.iter().map(|k| {
+                let key_hash = k.get("publicKeyHash")
+                    .or_else(|| k.get("publicKey"))
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("")
+                    .to_string();
+                let delegate = k.get("delegate").and_then(|v| v.as_str()).map(String::from);
+                KeyEntry { key_hash, delegate }
+            }).collect()
+        } else {
+            vec![]
+        };
+
+        Self {
+            url,
+            version,
+            credit_balance,
+            accept_threshold,
+            keys,
+        }
+    }
+
+    /// Index of `public_key` among the page's key entries
+    ///
+    /// Entries are matched on the SHA-256 hash of the key, or on the key
+    /// itself for pages that report raw public keys.
+    #[must_use]
+    pub fn find_key_index(&self, public_key: &[u8]) -> Option<usize> {
+        let key_hash = hex::encode(sha256_hash(public_key));
+        let key = hex::encode(public_key);
+        self.keys.iter().position(|entry| {
+            entry.key_hash.eq_ignore_ascii_case(&key_hash) || entry.key_hash.eq_ignore_ascii_case(&key)
+        })
+    }
+}
+
 /// Key entry in a key page
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyEntry {
@@ -380,6 +693,47 @@ pub struct KeyEntry {
     pub delegate: Option<String>,
 }
 
+/// URL of page `index` of the key book at `book_url`
+///
+/// Pages are numbered from 1, the highest priority page.
+#[must_use]
+pub fn key_page_url(book_url: &str, index: u64) -> String {
+    format!("{}/{}", book_url.trim_end_matches('/'), index)
+}
+
+/// A page of a key book, as listed by [`AccumulateClient::key_book_pages`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyBookPage {
+    /// Page number; 1 is the highest priority
+    pub index: u64,
+    /// Version, accept threshold, credits and keys of the page
+    pub state: KeyPageState,
+}
+
+impl AccumulateClient {
+    /// The pages of the key book at `book_url`, highest priority first
+    ///
+    /// Each page is queried for its current version, accept threshold and
+    /// keys, e.g. to pick the page a [`SmartSigner`] should sign with.
+    pub async fn key_book_pages(&self, book_url: &str) -> Result<Vec<KeyBookPage>, JsonRpcError> {
+        let book = self.v3().query_account(book_url).await?;
+        if book.account_type() != Some("keyBook") {
+            return Err(JsonRpcError::General(format!("{book_url} is not a key book")));
+        }
+        let count = book.account.get("pageCount").and_then(Value::as_u64).unwrap_or(0);
+        let mut pages = Vec::new();
+        for index in 1..=count {
+            let url = key_page_url(book_url, index);
+            let record = self.v3().query_account(&url).await?;
+            pages.push(KeyBookPage {
+                index,
+                state: KeyPageState::from_account(&record.account, &url),
+            });
+        }
+        Ok(pages)
+    }
+}
+
 // =============================================================================
 // HEADER OPTIONS
 // =============================================================================
@@ -406,6 +760,84 @@ pub struct HeaderOptions {
     pub authorities: Option<Vec<String>>,
 }
 
+/// Optional signature fields, covered by the signature metadata hash.
+///
+/// Unlike [`HeaderOptions`], these belong to one signature rather than the
+/// transaction, so each signer of a multisig transaction can set its own.
+/// - `memo`: Memo text attached to the signature
+/// - `data`: Binary data attached to the signature (hex-encoded in the envelope)
+/// - `vote`: How the signer votes; the network treats a missing vote as accept
+#[derive(Debug, Clone, Default)]
+pub struct SignatureOptions {
+    /// Signature memo text
+    pub memo: Option<String>,
+    /// Signature data bytes
+    pub data: Option<Vec<u8>>,
+    /// Vote cast by this signature
+    pub vote: Option<crate::generated::enums::VoteType>,
+}
+
+impl SignatureOptions {
+    /// Metadata hash of a signature of `signature_type` carrying these fields,
+    /// wrapped once per delegator
+    fn metadata_hash(
+        &self,
+        signature_type: &crate::generated::enums::SignatureType,
+        public_key: &[u8],
+        signer: &str,
+        signer_version: u64,
+        timestamp: u64,
+        delegators: &[String],
+    ) -> [u8; 32] {
+        sha256_hash(&self.metadata(signature_type, public_key, signer, signer_version, timestamp, delegators))
+    }
+
+    /// Binary metadata of a signature of `signature_type` carrying these
+    /// fields, wrapped once per delegator
+    fn metadata(
+        &self,
+        signature_type: &crate::generated::enums::SignatureType,
+        public_key: &[u8],
+        signer: &str,
+        signer_version: u64,
+        timestamp: u64,
+        delegators: &[String],
+    ) -> Vec<u8> {
+        let metadata = crate::codec::signing::marshal_signature_metadata(
+            signature_type.value(),
+            public_key,
+            signer,
+            signer_version,
+            timestamp,
+            self.vote.as_ref().map_or(0, crate::generated::enums::VoteType::value),
+            self.memo.as_deref(),
+            self.data.as_deref(),
+        );
+        delegators.iter().fold(metadata, |inner, delegator| {
+            crate::codec::signing::marshal_delegated_signature_metadata(&inner, delegator)
+        })
+    }
+
+    /// Add these fields to a signature object
+    fn apply_to(&self, signature: &mut Value) {
+        if let Some(ref vote) = self.vote {
+            if vote.value() != 0 {
+                signature["vote"] = json!(vote);
+            }
+        }
+        if let Some(ref memo) = self.memo {
+            if !memo.is_empty() {
+                signature["memo"] = json!(memo);
+            }
+        }
+        if let Some(ref data) = self.data {
+            if !data.is_empty() {
+                signature["data"] = json!(hex::encode(data));
+            }
+        }
+    }
+}
+
 // =============================================================================
 // SMART SIGNER
 // =============================================================================
@@ -415,49 +847,240 @@ pub struct HeaderOptions {
 pub struct SmartSigner<'a> {
     /// Reference to the client
     client: &'a AccumulateClient,
-    /// Signing key
-    keypair: SigningKey,
+    /// Signing key, in memory or on a device
+    key: Arc<dyn crate::signer::Signer>,
     /// Signer URL (key page URL)
     signer_url: String,
     /// Cached version (updated automatically)
     cached_version: u64,
+    /// Hashing rules of the target network
+    profile: EncodingProfile,
+    /// Verify every signature locally before returning it
+    preflight: bool,
+    /// Where signed envelopes and final statuses are recorded
+    audit: Option<Arc<dyn AuditSink>>,
+    /// Records the cost of delivered transactions, with the flow name
+    credits: Option<(CreditTracker, String)>,
+    /// Pages this signer signs for as a delegate, innermost first
+    delegators: Vec<String>,
+    /// How final a transaction must be before a wait returns success
+    confirmation: ConfirmationPolicy,
 }
 
 impl<'a> SmartSigner<'a> {
     /// Create a new SmartSigner
     pub fn new(client: &'a AccumulateClient, keypair: SigningKey, signer_url: &str) -> Self {
+        Self::from_signer(client, Arc::new(keypair), signer_url)
+    }
+
+    /// Create a signer for page `index` of the key book at `book_url`
+    ///
+    /// The page version is read on the first
+    /// [`sign_submit_and_wait`](Self::sign_submit_and_wait); call
+    /// [`refresh_version`](Self::refresh_version) before signing with
+    /// [`sign`](Self::sign) directly.
+    #[must_use]
+    pub fn for_page(client: &'a AccumulateClient, keypair: SigningKey, book_url: &str, index: u64) -> Self {
+        Self::new(client, keypair, &key_page_url(book_url, index))
+    }
+
+    /// Create a SmartSigner over any signing backend, e.g. a hardware wallet
+    ///
+    /// The backend signs each 32-byte preimage; the key never has to be
+    /// loaded into this process.
+    pub fn from_signer(
+        client: &'a AccumulateClient,
+        key: Arc<dyn crate::signer::Signer>,
+        signer_url: &str,
+    ) -> Self {
         Self {
             client,
-            keypair,
+            key,
             signer_url: signer_url.to_string(),
             cached_version: 1,
+            profile: EncodingProfile::default(),
+            preflight: false,
+            audit: None,
+            credits: None,
+            delegators: Vec::new(),
+            confirmation: ConfirmationPolicy::default(),
         }
     }
 
+    /// Verify each signature locally right after signing
+    ///
+    /// The envelope is re-hashed from its JSON and the signature checked with
+    /// the signer's public key, so a key or encoding mismatch fails here with
+    /// a specific error instead of as an opaque rejection from the network.
+    pub fn with_preflight(mut self, enabled: bool) -> Self {
+        self.preflight = enabled;
+        self
+    }
+
+    /// Whether signatures are verified locally before they are returned
+    pub fn preflight(&self) -> bool {
+        self.preflight
+    }
+
+    /// Record every signed envelope and final status in `sink`
+    ///
+    /// Envelopes are recorded before they are returned; if the sink fails,
+    /// signing fails and nothing is submitted.
+    pub fn with_audit_sink(mut self, sink: Arc<dyn AuditSink>) -> Self {
+        self.audit = Some(sink);
+        self
+    }
+
+    /// The audit sink, if one is set
+    pub fn audit_sink(&self) -> Option<&Arc<dyn AuditSink>> {
+        self.audit.as_ref()
+    }
+
+    /// Record the credit cost of each delivered transaction in `tracker`,
+    /// under `flow`
+    #[must_use]
+    pub fn with_credit_tracker(mut self, tracker: CreditTracker, flow: &str) -> Self {
+        self.credits = Some((tracker, flow.to_string()));
+        self
+    }
+
+    /// The credit tracker and flow name, if a tracker is set
+    #[must_use]
+    pub fn credit_tracker(&self) -> Option<(&CreditTracker, &str)> {
+        self.credits.as_ref().map(|(tracker, flow)| (tracker, flow.as_str()))
+    }
+
+    /// Wait for `policy` before a submission counts as successful
+    ///
+    /// Applies to [`sign_submit_and_wait`](Self::sign_submit_and_wait) and
+    /// the other waiting submissions. The default is
+    /// [`ConfirmationPolicy::Delivered`]; with
+    /// [`Submitted`](ConfirmationPolicy::Submitted) they return as soon as
+    /// the node accepts the envelope, and the credit tracker records nothing.
+    #[must_use]
+    pub const fn with_confirmation(mut self, policy: ConfirmationPolicy) -> Self {
+        self.confirmation = policy;
+        self
+    }
+
+    /// The confirmation policy waits apply
+    #[must_use]
+    pub const fn confirmation(&self) -> ConfirmationPolicy {
+        self.confirmation
+    }
+
+    /// Sign as a delegate of the key pages in `delegators`
+    ///
+    /// Use this when this signer's key book is a delegate entry on another
+    /// key page: `delegators[0]` is that page, and each further page lists
+    /// the book of the page before it as a delegate. Every signature is
+    /// wrapped in one delegated signature per page, innermost first, and the
+    /// key signs the metadata hash of the outermost wrapper, which is also
+    /// the initiator of transactions this signer initiates. The signature
+    /// counts toward the last page's threshold.
+    #[must_use]
+    pub fn with_delegators(mut self, delegators: &[&str]) -> Self {
+        self.delegators = delegators.iter().map(|url| (*url).to_string()).collect();
+        self
+    }
+
+    /// The pages this signer signs for as a delegate, innermost first
+    #[must_use]
+    pub fn delegators(&self) -> &[String] {
+        &self.delegators
+    }
+
+    /// Wrap a key signature in one delegated signature per delegator
+    fn delegate(&self, signature: Value) -> Value {
+        self.delegators.iter().fold(signature, |inner, delegator| {
+            json!({"type": "delegated", "signature": inner, "delegator": delegator})
+        })
+    }
+
+    /// Sign for a network with the given hashing rules
+    pub fn with_encoding_profile(mut self, profile: EncodingProfile) -> Self {
+        self.profile = profile;
+        self
+    }
+
+    /// The encoding profile applied to signed bodies
+    pub fn encoding_profile(&self) -> EncodingProfile {
+        self.profile
+    }
+
+    /// Read the network's encoding profile and use it for later signatures
+    pub async fn detect_encoding_profile(&mut self) -> Result<EncodingProfile, JsonRpcError> {
+        self.profile = self.client.encoding_profile().await?;
+        Ok(self.profile)
+    }
+
     /// Query and update the cached version
     pub async fn refresh_version(&mut self) -> Result<u64, JsonRpcError> {
-        let params = json!({
-            "scope": &self.signer_url,
-            "query": {"queryType": "default"}
-        });
+        let record = self.client.v3().query_account(&self.signer_url).await?;
 
-        let result: Value = self.client.v3_client.call_v3("query", params).await?;
-
-        if let Some(account) = result.get("account") {
-            if let Some(version) = account.get("version").and_then(|v| v.as_u64()) {
-                self.cached_version = version;
-                return Ok(version);
-            }
+        if let Some(version) = record.version() {
+            self.cached_version = version;
+            return Ok(version);
         }
 
         Ok(self.cached_version)
     }
 
+    /// Refresh the cached version and check the signing key is on the signer page
+    ///
+    /// Returns the key's index on the page; a lite identity signer has a
+    /// single implicit key at index 0. Fails when the page does not hold the
+    /// key, since the network would reject the signature anyway.
+    pub async fn verify_key_on_page(&mut self) -> Result<usize, JsonRpcError> {
+        let record = self.client.v3().query_account(&self.signer_url).await?;
+        if record.account_type() == Some("liteIdentity") {
+            return Ok(0);
+        }
+
+        let state = KeyPageState::from_account(&record.account, &self.signer_url);
+        self.cached_version = state.version;
+        // Pages record secp256k1 keys by their type-specific hash
+        let key_hash = hex::encode(self.public_key_hash());
+        let on_page = state.find_key_index(&self.key.public_key()).or_else(|| {
+            state.keys.iter().position(|entry| entry.key_hash.eq_ignore_ascii_case(&key_hash))
+        });
+        on_page.ok_or_else(|| {
+            JsonRpcError::General(format!(
+                "Signing key {} is not on signer page {} (version {}, {} keys)",
+                key_hash,
+                state.url,
+                state.version,
+                state.keys.len()
+            ))
+        })
+    }
+
     /// Get the current cached version
     pub fn version(&self) -> u64 {
         self.cached_version
     }
 
+    /// The key page or lite identity signatures are made for
+    #[must_use]
+    pub fn signer_url(&self) -> &str {
+        &self.signer_url
+    }
+
+    /// Sign for `page_url` from now on, e.g. another page of the same book
+    ///
+    /// The cached version belonged to the previous signer, so it is read
+    /// again from the new page and returned.
+    pub async fn switch_page(&mut self, page_url: &str) -> Result<u64, JsonRpcError> {
+        self.signer_url = page_url.to_string();
+        self.cached_version = 1;
+        self.refresh_version().await
+    }
+
+    /// The client this signer submits through
+    pub fn client(&self) -> &'a AccumulateClient {
+        self.client
+    }
+
     /// Sign a transaction and return the envelope
     ///
     /// This uses proper binary encoding matching the Go core implementation:
@@ -468,30 +1091,39 @@ impl<'a> SmartSigner<'a> {
     /// 5. Sign the preimage
     pub fn sign(&self, principal: &str, body: &Value, memo: Option<&str>) -> Result<Value, JsonRpcError> {
         use crate::codec::signing::{
-            compute_ed25519_signature_metadata_hash,
-            compute_transaction_hash,
-            compute_write_data_body_hash,
-            compute_write_data_to_body_hash,
             create_signing_preimage,
+            marshal_signature_metadata,
             marshal_transaction_header,
-            sha256_bytes,
         };
 
+        if !self.delegators.is_empty() {
+            let options = HeaderOptions {
+                memo: memo.map(str::to_string),
+                ..HeaderOptions::default()
+            };
+            return self.sign_with_signature_options(principal, body, &options, &SignatureOptions::default());
+        }
+
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
-            .map_err(|e| JsonRpcError::General(anyhow::anyhow!("Time error: {}", e)))?
+            .map_err(|e| JsonRpcError::General(format!("Time error: {}", e)))?
             .as_micros() as u64;
 
-        let public_key = self.keypair.verifying_key().to_bytes();
+        let public_key = self.key.public_key();
 
         // Step 1: Compute signature metadata hash
         // This is used as BOTH the transaction initiator AND for signing
-        let sig_metadata_hash = compute_ed25519_signature_metadata_hash(
+        let sig_metadata = marshal_signature_metadata(
+            self.key.signature_type().value(),
             &public_key,
             &self.signer_url,
             self.cached_version,
             timestamp,
+            0, // vote
+            None, // memo
+            None, // data
         );
+        let sig_metadata_hash = sha256_hash(&sig_metadata);
         let initiator_hex = hex::encode(&sig_metadata_hash);
 
         // Step 2: Marshal header with initiator
@@ -503,56 +1135,12 @@ impl<'a> SmartSigner<'a> {
         );
 
         // Step 3 & 4: Compute transaction hash
-        // WriteData/WriteDataTo use special Merkle hash algorithm
-        let tx_type = body.get("type").and_then(|t| t.as_str()).unwrap_or("");
-
-        // Helper: extract entries from body.entry.data
-        let extract_entries = |body: &Value| -> Vec<String> {
-            let mut entries_hex = Vec::new();
-            if let Some(entry) = body.get("entry") {
-                if let Some(data) = entry.get("data") {
-                    if let Some(arr) = data.as_array() {
-                        for item in arr {
-                            if let Some(s) = item.as_str() {
-                                entries_hex.push(s.to_string());
-                            }
-                        }
-                    }
-                }
-            }
-            entries_hex
-        };
-
-        let tx_hash = if tx_type == "writeData" {
-            let header_hash = sha256_bytes(&header_bytes);
-            let entries_hex = extract_entries(body);
-            let scratch = body.get("scratch").and_then(|s| s.as_bool()).unwrap_or(false);
-            let write_to_state = body.get("writeToState").and_then(|w| w.as_bool()).unwrap_or(false);
-            let body_hash = compute_write_data_body_hash(&entries_hex, scratch, write_to_state);
-            // txHash = SHA256(SHA256(header) + bodyHash)
-            let mut combined = Vec::with_capacity(64);
-            combined.extend_from_slice(&header_hash);
-            combined.extend_from_slice(&body_hash);
-            sha256_bytes(&combined)
-        } else if tx_type == "writeDataTo" {
-            let header_hash = sha256_bytes(&header_bytes);
-            let entries_hex = extract_entries(body);
-            let recipient = body.get("recipient").and_then(|r| r.as_str()).unwrap_or("");
-            let body_hash = compute_write_data_to_body_hash(recipient, &entries_hex);
-            // txHash = SHA256(SHA256(header) + bodyHash)
-            let mut combined = Vec::with_capacity(64);
-            combined.extend_from_slice(&header_hash);
-            combined.extend_from_slice(&body_hash);
-            sha256_bytes(&combined)
-        } else {
-            // Standard: SHA256(SHA256(header) + SHA256(body))
-            let body_bytes = marshal_body_to_binary(body)?;
-            compute_transaction_hash(&header_bytes, &body_bytes)
-        };
+        let body = &*self.profile.apply(body);
+        let tx_hash = compute_tx_hash(&header_bytes, body)?;
 
         // Step 5: Create signing preimage and sign
         let preimage = create_signing_preimage(&sig_metadata_hash, &tx_hash);
-        let signature = self.keypair.sign(&preimage);
+        let signature = self.sign_transaction(&header_bytes, body, &sig_metadata, &preimage)?;
 
         // Build transaction JSON (for submission)
         let mut tx = json!({
@@ -569,33 +1157,68 @@ impl<'a> SmartSigner<'a> {
 
         // Build envelope with proper signature document
         // V3 API expects: envelope.signatures[].transactionHash
-        let envelope = json!({
+        let mut envelope = json!({
             "transaction": [tx],
             "signatures": [{
-                "type": "ed25519",
+                "type": self.signature_type_name(),
                 "publicKey": hex::encode(&public_key),
-                "signature": hex::encode(signature.to_bytes()),
+                "signature": hex::encode(&signature),
                 "signer": &self.signer_url,
                 "signerVersion": self.cached_version,
                 "timestamp": timestamp,
                 "transactionHash": hex::encode(&tx_hash)
             }]
         });
+        crate::protocol::normalize_envelope(&mut envelope);
+
+        if self.preflight {
+            self.preflight_check(&envelope, &sig_metadata_hash, &tx_hash, &signature)?;
+        }
+        self.audit_signed(SignedRecord::from_envelope(&self.signer_url, &envelope))?;
 
         Ok(envelope)
     }
 
-    /// Sign, submit, and wait for transaction confirmation
-    pub async fn sign_submit_and_wait(
-        &mut self,
-        principal: &str,
-        body: &Value,
+    /// Sign an existing transaction by hash and return a signature-only envelope
+    ///
+    /// The envelope carries a RemoteTransaction body instead of the original body,
+    /// so the network attaches the signature to the already-pending transaction.
+    pub fn sign_remote(&self, principal: &str, tx_hash: &[u8; 32]) -> Result<Value, JsonRpcError> {
+        self.sign(principal, &TxBody::remote_transaction(&hex::encode(tx_hash)), None)
+    }
+
+    /// Sign an existing transaction by hash with signature options
+    ///
+    /// Like [`sign_remote`], but the signature carries the memo, data and
+    /// vote of `signature`, e.g. to vote against a pending transaction.
+    pub fn sign_remote_with_options(
+        &self,
+        principal: &str,
+        tx_hash: &[u8; 32],
+        signature: &SignatureOptions,
+    ) -> Result<Value, JsonRpcError> {
+        self.sign_with_signature_options(
+            principal,
+            &TxBody::remote_transaction(&hex::encode(tx_hash)),
+            &HeaderOptions::default(),
+            signature,
+        )
+    }
+
+    /// Sign, submit, and wait for transaction confirmation
+    ///
+    /// Gives up after `max_attempts` polls or the client's confirmation
+    /// timeout, whichever comes first.
+    pub async fn sign_submit_and_wait(
+        &mut self,
+        principal: &str,
+        body: &Value,
         memo: Option<&str>,
         max_attempts: u32,
     ) -> TxResult {
-        // Refresh version before signing
-        if let Err(e) = self.refresh_version().await {
-            return TxResult::err(format!("Failed to refresh version: {}", e));
+        // Refresh version and make sure our key can sign for the page
+        if let Err(e) = self.verify_key_on_page().await {
+            return TxResult::err(format!("Failed to verify signer: {}", e));
         }
 
         // Sign the transaction
@@ -604,86 +1227,51 @@ impl<'a> SmartSigner<'a> {
             Err(e) => return TxResult::err(format!("Failed to sign: {}", e)),
         };
 
-        // Submit
-        let submit_result: Result<Value, _> = self.client.v3_client.call_v3("submit", json!({
-            "envelope": envelope
-        })).await;
-
-        let response = match submit_result {
-            Ok(resp) => resp,
-            Err(e) => return TxResult::err(format!("Submit failed: {}", e)),
-        };
+        let result = self.submit_and_wait(&envelope, max_attempts).await;
+        let result = self.explain_auth_failure(principal, body, result).await;
+        self.audit_outcome(&envelope, result)
+    }
 
-        // Extract transaction ID
-        let txid = extract_txid(&response);
-        if txid.is_none() {
-            return TxResult::err("No transaction ID in response".to_string());
+    /// Buy credits with ACME from `principal`, re-pricing once if the
+    /// oracle moved
+    ///
+    /// When the network rejects the purchase because its oracle no longer
+    /// matches the quote, the oracle is read again and the ACME amount
+    /// recomputed for the same credits. The purchase is retried once if that
+    /// amount is within [`CreditPurchase::max_slippage_bps`] of the quoted
+    /// one; otherwise the result is an error naming both prices.
+    pub async fn buy_credits_and_wait(
+        &mut self,
+        principal: &str,
+        purchase: &CreditPurchase,
+        memo: Option<&str>,
+        max_attempts: u32,
+    ) -> TxResult {
+        let body = TxBody::add_credits(
+            &purchase.recipient,
+            &purchase.amount_at(purchase.oracle).to_string(),
+            purchase.oracle,
+        );
+        let result = self.sign_submit_and_wait(principal, &body, memo, max_attempts).await;
+        if result.success || !result.error.as_deref().is_some_and(is_stale_oracle_error) {
+            return result;
         }
-        let txid = txid.unwrap();
 
-        // Wait for confirmation
-        // Extract just the hash for querying - format: acc://hash@unknown
-        let tx_hash = if txid.starts_with("acc://") && txid.contains('@') {
-            txid.split('@').next().unwrap_or(&txid).replace("acc://", "")
-        } else {
-            txid.clone()
+        let oracle = match fetch_oracle_price(self.client).await {
+            Ok(oracle) => oracle,
+            Err(e) => return TxResult::err(format!("Oracle moved, and reading it failed: {e}")),
         };
-        let query_scope = format!("acc://{}@unknown", tx_hash);
-
-        for _attempt in 0..max_attempts {
-            tokio::time::sleep(Duration::from_secs(2)).await;
-
-            // Query transaction status
-            let query_result: Result<Value, _> = self.client.v3_client.call_v3("query", json!({
-                "scope": &query_scope,
-                "query": {"queryType": "default"}
-            })).await;
-
-            if let Ok(result) = query_result {
-                // Check status - can be a String or a Map (matching Dart SDK)
-                if let Some(status_value) = result.get("status") {
-                    // Case 1: Status is a simple string like "delivered" or "pending"
-                    if let Some(status_str) = status_value.as_str() {
-                        if status_str == "delivered" {
-                            return TxResult::ok(txid, response);
-                        }
-                        // "pending" - continue waiting
-                        continue;
-                    }
-
-                    // Case 2: Status is a map with delivered/failed fields
-                    if status_value.is_object() {
-                        let delivered = status_value.get("delivered")
-                            .and_then(|d| d.as_bool())
-                            .unwrap_or(false);
-
-                        if delivered {
-                            // Check for errors
-                            let failed = status_value.get("failed")
-                                .and_then(|f| f.as_bool())
-                                .unwrap_or(false);
-
-                            if failed {
-                                let error_msg = status_value.get("error")
-                                    .and_then(|e| {
-                                        if let Some(msg) = e.get("message").and_then(|m| m.as_str()) {
-                                            Some(msg.to_string())
-                                        } else {
-                                            e.as_str().map(String::from)
-                                        }
-                                    })
-                                    .unwrap_or_else(|| "Unknown error".to_string());
-                                return TxResult::err(error_msg);
-                            }
-
-                            return TxResult::ok(txid, response);
-                        }
-                    }
-                }
-            }
+        if !purchase.within_slippage(oracle) {
+            return TxResult::err(format!(
+                "Oracle moved from {} to {oracle}: {} ACME base units exceeds the quoted {} by more than {} bps",
+                purchase.oracle,
+                purchase.amount_at(oracle),
+                purchase.amount_at(purchase.oracle),
+                purchase.max_slippage_bps
+            ));
         }
-
-        TxResult::err(format!("Timeout waiting for delivery: {}", txid))
+        let body = TxBody::add_credits(&purchase.recipient, &purchase.amount_at(oracle).to_string(), oracle);
+        self.sign_submit_and_wait(principal, &body, memo, max_attempts).await
     }
 
     /// Add a key to the key page using SmartSigner
@@ -705,10 +1293,22 @@ impl<'a> SmartSigner<'a> {
         self.sign_submit_and_wait(&self.signer_url.clone(), &body, Some("Set threshold"), 30).await
     }
 
-    /// Get public key hash
-    #[allow(dead_code)]
-    fn public_key_hash(&self) -> [u8; 32] {
-        sha256_hash(&self.keypair.verifying_key().to_bytes())
+    /// Public key of the signing key
+    pub fn public_key(&self) -> Vec<u8> {
+        self.key.public_key()
+    }
+
+    /// Get public key hash, as the signer's key page records it
+    fn public_key_hash(&self) -> Vec<u8> {
+        let public_key = self.key.public_key();
+        crate::derive::key_hash(&self.key.signature_type(), &public_key)
+            .unwrap_or_else(|_| sha256_hash(&public_key).to_vec())
+    }
+
+    /// JSON name of the signature type this signer produces
+    fn signature_type_name(&self) -> &'static str {
+        crate::codec::signing::signature_types::name(self.key.signature_type().value())
+            .unwrap_or("unknown")
     }
 
     /// Sign a transaction with full header options and return the envelope.
@@ -720,32 +1320,44 @@ impl<'a> SmartSigner<'a> {
         principal: &str,
         body: &Value,
         options: &HeaderOptions,
+    ) -> Result<Value, JsonRpcError> {
+        self.sign_with_signature_options(principal, body, options, &SignatureOptions::default())
+    }
+
+    /// Sign a transaction with header and signature options and return the envelope.
+    ///
+    /// Like [`sign_with_options`], but the signature also carries the memo,
+    /// data and vote of `signature`, which are part of the signed metadata.
+    pub fn sign_with_signature_options(
+        &self,
+        principal: &str,
+        body: &Value,
+        options: &HeaderOptions,
+        signature: &SignatureOptions,
     ) -> Result<Value, JsonRpcError> {
         use crate::codec::signing::{
-            compute_ed25519_signature_metadata_hash,
-            compute_transaction_hash,
-            compute_write_data_body_hash,
-            compute_write_data_to_body_hash,
             create_signing_preimage,
             marshal_transaction_header_full,
             HeaderBinaryOptions,
-            sha256_bytes,
         };
 
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
-            .map_err(|e| JsonRpcError::General(anyhow::anyhow!("Time error: {}", e)))?
+            .map_err(|e| JsonRpcError::General(format!("Time error: {}", e)))?
             .as_micros() as u64;
 
-        let public_key = self.keypair.verifying_key().to_bytes();
+        let public_key = self.key.public_key();
 
         // Step 1: Compute signature metadata hash
-        let sig_metadata_hash = compute_ed25519_signature_metadata_hash(
+        let sig_metadata = signature.metadata(
+            &self.key.signature_type(),
             &public_key,
             &self.signer_url,
             self.cached_version,
             timestamp,
+            &self.delegators,
         );
+        let sig_metadata_hash = sha256_hash(&sig_metadata);
         let initiator_hex = hex::encode(&sig_metadata_hash);
 
         // Step 2: Marshal header with initiator, memo, metadata, and extended options
@@ -776,52 +1388,12 @@ impl<'a> SmartSigner<'a> {
         );
 
         // Step 3 & 4: Compute transaction hash
-        let tx_type = body.get("type").and_then(|t| t.as_str()).unwrap_or("");
-
-        // Helper: extract entries from body.entry.data
-        let extract_entries = |body: &Value| -> Vec<String> {
-            let mut entries_hex = Vec::new();
-            if let Some(entry) = body.get("entry") {
-                if let Some(data) = entry.get("data") {
-                    if let Some(arr) = data.as_array() {
-                        for item in arr {
-                            if let Some(s) = item.as_str() {
-                                entries_hex.push(s.to_string());
-                            }
-                        }
-                    }
-                }
-            }
-            entries_hex
-        };
-
-        let tx_hash = if tx_type == "writeData" {
-            let header_hash = sha256_bytes(&header_bytes);
-            let entries_hex = extract_entries(body);
-            let scratch = body.get("scratch").and_then(|s| s.as_bool()).unwrap_or(false);
-            let write_to_state = body.get("writeToState").and_then(|w| w.as_bool()).unwrap_or(false);
-            let body_hash = compute_write_data_body_hash(&entries_hex, scratch, write_to_state);
-            let mut combined = Vec::with_capacity(64);
-            combined.extend_from_slice(&header_hash);
-            combined.extend_from_slice(&body_hash);
-            sha256_bytes(&combined)
-        } else if tx_type == "writeDataTo" {
-            let header_hash = sha256_bytes(&header_bytes);
-            let entries_hex = extract_entries(body);
-            let recipient = body.get("recipient").and_then(|r| r.as_str()).unwrap_or("");
-            let body_hash = compute_write_data_to_body_hash(recipient, &entries_hex);
-            let mut combined = Vec::with_capacity(64);
-            combined.extend_from_slice(&header_hash);
-            combined.extend_from_slice(&body_hash);
-            sha256_bytes(&combined)
-        } else {
-            let body_bytes = marshal_body_to_binary(body)?;
-            compute_transaction_hash(&header_bytes, &body_bytes)
-        };
+        let body = &*self.profile.apply(body);
+        let tx_hash = compute_tx_hash(&header_bytes, body)?;
 
         // Step 5: Create signing preimage and sign
         let preimage = create_signing_preimage(&sig_metadata_hash, &tx_hash);
-        let signature = self.keypair.sign(&preimage);
+        let sig_bytes = self.sign_transaction(&header_bytes, body, &sig_metadata, &preimage)?;
 
         // Build transaction JSON (for submission)
         let mut tx = json!({
@@ -857,18 +1429,205 @@ impl<'a> SmartSigner<'a> {
         }
 
         // Build envelope
-        let envelope = json!({
+        let mut sig = json!({
+            "type": self.signature_type_name(),
+            "publicKey": hex::encode(&public_key),
+            "signature": hex::encode(&sig_bytes),
+            "signer": &self.signer_url,
+            "signerVersion": self.cached_version,
+            "timestamp": timestamp,
+            "transactionHash": hex::encode(&tx_hash)
+        });
+        signature.apply_to(&mut sig);
+        let mut envelope = json!({
             "transaction": [tx],
-            "signatures": [{
-                "type": "ed25519",
-                "publicKey": hex::encode(&public_key),
-                "signature": hex::encode(signature.to_bytes()),
-                "signer": &self.signer_url,
-                "signerVersion": self.cached_version,
-                "timestamp": timestamp,
-                "transactionHash": hex::encode(&tx_hash)
-            }]
+            "signatures": [self.delegate(sig)]
+        });
+        crate::protocol::normalize_envelope(&mut envelope);
+
+        if self.preflight {
+            self.preflight_check(&envelope, &sig_metadata_hash, &tx_hash, &sig_bytes)?;
+        }
+        self.audit_signed(SignedRecord::from_envelope(&self.signer_url, &envelope))?;
+
+        Ok(envelope)
+    }
+
+    /// Re-check a freshly signed envelope: the transaction JSON must hash to
+    /// what was signed, the initiator must be this signature's metadata hash,
+    /// and the signature must verify with this signer's public key.
+    fn preflight_check(
+        &self,
+        envelope: &Value,
+        sig_metadata_hash: &[u8; 32],
+        tx_hash: &[u8; 32],
+        signature: &[u8],
+    ) -> Result<(), JsonRpcError> {
+        let tx = &envelope["transaction"][0];
+        let header_bytes = marshal_header_json(&tx["header"])?;
+        let envelope_hash = compute_tx_hash(&header_bytes, &tx["body"])?;
+        if envelope_hash != *tx_hash {
+            return Err(JsonRpcError::General(format!(
+                "Preflight: envelope hashes to {} but {} was signed",
+                hex::encode(envelope_hash),
+                hex::encode(tx_hash)
+            )));
+        }
+
+        let initiator = tx["header"]["initiator"].as_str().unwrap_or_default();
+        if initiator != hex::encode(sig_metadata_hash) {
+            return Err(JsonRpcError::General(format!(
+                "Preflight: initiator {} is not the signature metadata hash {}",
+                initiator,
+                hex::encode(sig_metadata_hash)
+            )));
+        }
+
+        let preimage = crate::codec::signing::create_signing_preimage(sig_metadata_hash, tx_hash);
+        self.verify_own_signature(&preimage, signature)
+    }
+
+    /// Sign a preimage with the signing backend
+    fn sign_preimage(&self, preimage: &[u8; 32]) -> Result<Vec<u8>, JsonRpcError> {
+        self.key
+            .sign_digest(preimage)
+            .map_err(|e| JsonRpcError::General(format!("Signer: {}", e)))
+    }
+
+    /// Sign a transaction with the signing backend, handing it the binary
+    /// transaction and signature metadata as well as the preimage
+    fn sign_transaction(
+        &self,
+        header_bytes: &[u8],
+        body: &Value,
+        sig_metadata: &[u8],
+        preimage: &[u8; 32],
+    ) -> Result<Vec<u8>, JsonRpcError> {
+        let mut transaction = crate::codec::BinaryWriter::new();
+        let _ = transaction.write_bytes_field(header_bytes, 1);
+        let _ = transaction.write_bytes_field(&marshal_body_to_binary(body)?, 2);
+        let request = crate::signer::SigningRequest {
+            transaction: transaction.bytes(),
+            metadata: sig_metadata,
+            digest: preimage,
+        };
+        self.key
+            .sign_transaction(&request)
+            .map_err(|e| JsonRpcError::General(format!("Signer: {}", e)))
+    }
+
+    /// Verify a signature over `preimage` with this signer's public key
+    fn verify_own_signature(&self, preimage: &[u8; 32], signature: &[u8]) -> Result<(), JsonRpcError> {
+        let public_key = self.key.public_key();
+        crate::signer::verify_digest(&self.key.signature_type(), &public_key, preimage, signature).map_err(|e| {
+            JsonRpcError::General(format!(
+                "Preflight: signature by {} does not verify: {}",
+                hex::encode(&public_key),
+                e
+            ))
+        })
+    }
+
+    /// The key book this signer's key page belongs to
+    ///
+    /// This is the URL to list in a transaction's `authorities` header field
+    /// when this signer has to approve a transaction for another identity.
+    pub fn authority(&self) -> String {
+        AccUrl::parse(&self.signer_url)
+            .ok()
+            .and_then(|url| url.parent())
+            .map_or_else(|| self.signer_url.clone(), String::from)
+    }
+
+    /// Sign an already-computed transaction hash and return the signature object
+    ///
+    /// The result is a single entry for an envelope's `signatures` array, used to
+    /// add a second signer to an envelope produced by [`sign`] or
+    /// [`sign_with_options`].
+    pub fn sign_hash(&self, tx_hash: &[u8; 32]) -> Result<Value, JsonRpcError> {
+        self.sign_hash_with_options(tx_hash, &SignatureOptions::default())
+    }
+
+    /// Sign an already-computed transaction hash with signature options
+    ///
+    /// Like [`sign_hash`], but the signature carries the memo, data and vote
+    /// of `options`; use it to reject or abstain on a pending transaction.
+    pub fn sign_hash_with_options(
+        &self,
+        tx_hash: &[u8; 32],
+        options: &SignatureOptions,
+    ) -> Result<Value, JsonRpcError> {
+        use crate::codec::signing::create_signing_preimage;
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_err(|e| JsonRpcError::General(format!("Time error: {}", e)))?
+            .as_micros() as u64;
+
+        let public_key = self.key.public_key();
+        let sig_metadata_hash = options.metadata_hash(
+            &self.key.signature_type(),
+            &public_key,
+            &self.signer_url,
+            self.cached_version,
+            timestamp,
+            &self.delegators,
+        );
+        let preimage = create_signing_preimage(&sig_metadata_hash, tx_hash);
+        let signature = self.sign_preimage(&preimage)?;
+        if self.preflight {
+            self.verify_own_signature(&preimage, &signature)?;
+        }
+
+        let mut sig = json!({
+            "type": self.signature_type_name(),
+            "publicKey": hex::encode(&public_key),
+            "signature": hex::encode(&signature),
+            "signer": &self.signer_url,
+            "signerVersion": self.cached_version,
+            "timestamp": timestamp,
+            "transactionHash": hex::encode(tx_hash)
         });
+        options.apply_to(&mut sig);
+        let sig = self.delegate(sig);
+        self.audit_signed(SignedRecord::from_signature(&self.signer_url, &sig))?;
+        Ok(sig)
+    }
+
+    /// Sign a transaction on behalf of another identity, paying its fees.
+    ///
+    /// `self` is the sponsor: its key page initiates the transaction and is
+    /// charged the credits, and its key book is added to the header's
+    /// `authorities` so the sponsor signature counts as an approval. `owner`
+    /// signs for the identity that owns `principal`. The returned envelope
+    /// carries both signatures over the same transaction hash and can be
+    /// submitted as-is.
+    pub fn sign_sponsored(
+        &self,
+        owner: &SmartSigner<'_>,
+        principal: &str,
+        body: &Value,
+        options: &HeaderOptions,
+    ) -> Result<Value, JsonRpcError> {
+        let mut options = options.clone();
+        let sponsor_authority = self.authority();
+        let authorities = options.authorities.get_or_insert_with(Vec::new);
+        if !authorities.contains(&sponsor_authority) {
+            authorities.push(sponsor_authority);
+        }
+
+        let mut envelope = self.sign_with_options(principal, body, &options)?;
+
+        let tx_hash = envelope["signatures"][0]["transactionHash"]
+            .as_str()
+            .ok_or_else(|| JsonRpcError::General("Sponsor signature has no transaction hash".to_string()))?;
+        let tx_hash = parse_hex_hash("transactionHash", tx_hash).map_err(|e| JsonRpcError::General(e.to_string()))?;
+        let owner_signature = owner.sign_hash(&tx_hash)?;
+
+        if let Some(signatures) = envelope["signatures"].as_array_mut() {
+            signatures.push(owner_signature);
+        }
+        crate::protocol::normalize_envelope(&mut envelope);
 
         Ok(envelope)
     }
@@ -883,9 +1642,9 @@ impl<'a> SmartSigner<'a> {
         options: &HeaderOptions,
         max_attempts: u32,
     ) -> TxResult {
-        // Refresh version before signing
-        if let Err(e) = self.refresh_version().await {
-            return TxResult::err(format!("Failed to refresh version: {}", e));
+        // Refresh version and make sure our key can sign for the page
+        if let Err(e) = self.verify_key_on_page().await {
+            return TxResult::err(format!("Failed to verify signer: {}", e));
         }
 
         // Sign the transaction with options
@@ -894,103 +1653,304 @@ impl<'a> SmartSigner<'a> {
             Err(e) => return TxResult::err(format!("Failed to sign: {}", e)),
         };
 
-        // Submit
-        let submit_result: Result<Value, _> = self.client.v3_client.call_v3("submit", json!({
-            "envelope": envelope
-        })).await;
+        let result = self.submit_and_wait(&envelope, max_attempts).await;
+        let result = self.explain_auth_failure(principal, body, result).await;
+        self.audit_outcome(&envelope, result)
+    }
+
+    /// Authority set of `principal`, read from its account record
+    pub async fn account_auth(&self, principal: &str) -> Result<AccountAuth, JsonRpcError> {
+        let record = self.client.v3().query_account(principal).await?;
+        Ok(AccountAuth::from_account(principal, &record.account))
+    }
 
-        let response = match submit_result {
-            Ok(resp) => resp,
-            Err(e) => return TxResult::err(format!("Submit failed: {}", e)),
+    /// Check that the signer page can authorize `body` for `principal`
+    ///
+    /// Fails with an [`AuthError`](crate::auth::AuthError) naming the
+    /// authorities that must sign when the signer's book is not one of them,
+    /// including when auth is disabled but the transaction type ignores that.
+    pub async fn check_authority(&self, principal: &str, body: &Value) -> Result<(), JsonRpcError> {
+        let tx_type = body.get("type").and_then(|t| t.as_str()).unwrap_or("");
+        Ok(self.account_auth(principal).await?.check(&self.signer_url, tx_type)?)
+    }
+
+    /// Replace an authorization failure with guidance from the principal's
+    /// authority set
+    async fn explain_auth_failure(&self, principal: &str, body: &Value, result: TxResult) -> TxResult {
+        let message = match &result.error {
+            Some(message) if !result.success && AuthError::is_auth_failure(0, message) => message,
+            _ => return result,
         };
+        let tx_type = body.get("type").and_then(|t| t.as_str()).unwrap_or("");
+        match self.account_auth(principal).await {
+            Ok(auth) => match AuthError::diagnose(&auth, &self.signer_url, tx_type, 0, message) {
+                Some(error) => TxResult { error: Some(error.to_string()), ..result },
+                None => result,
+            },
+            Err(_) => result,
+        }
+    }
+
+    /// Submit a signed envelope and poll until it meets the confirmation
+    /// policy, fails, or `max_attempts` polls or the confirmation timeout
+    /// run out
+    pub(crate) async fn submit_and_wait(&self, envelope: &Value, max_attempts: u32) -> TxResult {
+        submit_envelope_and_confirm(self.client, envelope, max_attempts, self.confirmation).await
+    }
 
-        // Extract transaction ID
-        let txid = extract_txid(&response);
-        if txid.is_none() {
-            return TxResult::err("No transaction ID in response".to_string());
+    /// Pass a signed envelope or signature to the audit sink
+    fn audit_signed(&self, record: SignedRecord) -> Result<(), JsonRpcError> {
+        match &self.audit {
+            Some(sink) => sink
+                .record(&AuditRecord::Signed(record))
+                .map_err(|e| JsonRpcError::General(format!("Audit: {}", e))),
+            None => Ok(()),
         }
-        let txid = txid.unwrap();
+    }
 
-        // Wait for confirmation
-        let tx_hash = if txid.starts_with("acc://") && txid.contains('@') {
-            txid.split('@').next().unwrap_or(&txid).replace("acc://", "")
-        } else {
-            txid.clone()
+    /// Record the final status of a submitted envelope, and its cost when
+    /// it was delivered
+    ///
+    /// The transaction has already executed, so a sink failure does not
+    /// change `success`; it is reported in `error` instead.
+    pub(crate) fn audit_outcome(&self, envelope: &Value, mut result: TxResult) -> TxResult {
+        let delivered = result.success && self.confirmation >= ConfirmationPolicy::Delivered;
+        if let (Some((tracker, flow)), true) = (&self.credits, delivered) {
+            tracker.record(flow, &self.signer_url, envelope, result.txid.as_deref());
+        }
+        let Some(sink) = &self.audit else {
+            return result;
         };
-        let query_scope = format!("acc://{}@unknown", tx_hash);
+        let signed = SignedRecord::from_envelope(&self.signer_url, envelope);
+        let record = AuditRecord::Finalized(FinalStatusRecord {
+            signer: self.signer_url.clone(),
+            transaction_hash: signed.transaction_hash,
+            envelope_hash: signed.envelope_hash,
+            txid: result.txid.clone(),
+            delivered: result.success,
+            error: result.error.clone(),
+        });
+        if let Err(e) = sink.record(&record) {
+            let message = format!("Audit: {}", e);
+            result.error = Some(match result.error.take() {
+                Some(error) => format!("{}; {}", error, message),
+                None => message,
+            });
+        }
+        result
+    }
+}
 
-        for _attempt in 0..max_attempts {
-            tokio::time::sleep(Duration::from_secs(2)).await;
+/// Whether a body type string names a RemoteTransaction body
+fn is_remote_body_type(tx_type: &str) -> bool {
+    matches!(tx_type, "signPending" | "remoteTransaction")
+}
 
-            let query_result: Result<Value, _> = self.client.v3_client.call_v3("query", json!({
-                "scope": &query_scope,
-                "query": {"queryType": "default"}
-            })).await;
+/// Extract the referenced transaction hash from a RemoteTransaction body
+fn remote_body_hash(body: &Value) -> Result<[u8; 32], JsonRpcError> {
+    let hash_hex = body.get("hash").and_then(|h| h.as_str()).unwrap_or("");
+    parse_hex_hash("hash", hash_hex).map_err(|e| JsonRpcError::General(e.to_string()))
+}
 
-            if let Ok(result) = query_result {
-                if let Some(status_value) = result.get("status") {
-                    if let Some(status_str) = status_value.as_str() {
-                        if status_str == "delivered" {
-                            return TxResult::ok(txid, response);
-                        }
-                        continue;
-                    }
-                    if status_value.is_object() {
-                        let delivered = status_value.get("delivered")
-                            .and_then(|d| d.as_bool())
-                            .unwrap_or(false);
-                        if delivered {
-                            let failed = status_value.get("failed")
-                                .and_then(|f| f.as_bool())
-                                .unwrap_or(false);
-                            if failed {
-                                let error_msg = status_value.get("error")
-                                    .and_then(|e| {
-                                        if let Some(msg) = e.get("message").and_then(|m| m.as_str()) {
-                                            Some(msg.to_string())
-                                        } else {
-                                            e.as_str().map(String::from)
-                                        }
-                                    })
-                                    .unwrap_or_else(|| "Unknown error".to_string());
-                                return TxResult::err(error_msg);
-                            }
-                            return TxResult::ok(txid, response);
-                        }
-                    }
-                }
+/// Decode a hex body field, surfacing bad hex as an error instead of empty bytes
+fn body_hex(name: &str, hex_str: &str) -> Result<Vec<u8>, JsonRpcError> {
+    parse_hex_field(name, hex_str).map_err(|e| JsonRpcError::General(e.to_string()))
+}
+
+/// Extract and validate the hex entries from body.entry.data
+fn extract_data_entries(body: &Value) -> Result<Vec<String>, JsonRpcError> {
+    let mut entries_hex = Vec::new();
+    if let Some(arr) = body.get("entry").and_then(|e| e.get("data")).and_then(|d| d.as_array()) {
+        for (i, item) in arr.iter().enumerate() {
+            if let Some(s) = item.as_str() {
+                body_hex(&format!("entry.data[{i}]"), s)?;
+                entries_hex.push(s.to_string());
             }
         }
+    }
+    Ok(entries_hex)
+}
+
+/// Compute the body hash that feeds into the transaction hash
+///
+/// WriteData and WriteDataTo use the special Merkle body hash; every other
+/// type hashes its binary encoding. `accumulate` data entries (executor v1)
+/// skip the final SHA256 that `doublehash` entries apply.
+pub(crate) fn compute_body_hash(body: &Value) -> Result<[u8; 32], JsonRpcError> {
+    use crate::codec::signing::{
+        compute_legacy_write_data_body_hash, compute_legacy_write_data_to_body_hash,
+        compute_write_data_body_hash, compute_write_data_to_body_hash, sha256_bytes,
+    };
 
-        TxResult::err(format!("Timeout waiting for delivery: {}", txid))
+    let tx_type = body.get("type").and_then(|t| t.as_str()).unwrap_or("");
+    let single_hash = body.get("entry").and_then(|e| e.get("type")).and_then(|t| t.as_str()) == Some("accumulate");
+    match tx_type {
+        "writeData" => {
+            let entries_hex = extract_data_entries(body)?;
+            let scratch = body.get("scratch").and_then(|s| s.as_bool()).unwrap_or(false);
+            let write_to_state = body.get("writeToState").and_then(|w| w.as_bool()).unwrap_or(false);
+            if single_hash {
+                return Ok(compute_legacy_write_data_body_hash(&entries_hex, scratch, write_to_state));
+            }
+            Ok(compute_write_data_body_hash(&entries_hex, scratch, write_to_state))
+        }
+        "writeDataTo" => {
+            let entries_hex = extract_data_entries(body)?;
+            let recipient = body.get("recipient").and_then(|r| r.as_str()).unwrap_or("");
+            if single_hash {
+                return Ok(compute_legacy_write_data_to_body_hash(recipient, &entries_hex));
+            }
+            Ok(compute_write_data_to_body_hash(recipient, &entries_hex))
+        }
+        _ => Ok(sha256_bytes(&marshal_body_to_binary(body)?)),
     }
 }
 
+/// Compute the transaction hash from a marshaled header and a JSON body
+///
+/// txHash = SHA256(SHA256(header) + bodyHash), except for remote bodies, which
+/// stand in for the transaction they reference and carry its hash directly.
+pub(crate) fn compute_tx_hash(header_bytes: &[u8], body: &Value) -> Result<[u8; 32], JsonRpcError> {
+    use crate::codec::signing::sha256_bytes;
+
+    let tx_type = body.get("type").and_then(|t| t.as_str()).unwrap_or("");
+    if is_remote_body_type(tx_type) {
+        return remote_body_hash(body);
+    }
+
+    let header_hash = sha256_bytes(header_bytes);
+    let body_hash = compute_body_hash(body)?;
+    let mut combined = Vec::with_capacity(64);
+    combined.extend_from_slice(&header_hash);
+    combined.extend_from_slice(&body_hash);
+    Ok(sha256_bytes(&combined))
+}
+
+/// Marshal a transaction header from its envelope JSON form
+///
+/// Accepts the header shape produced by [`SmartSigner::sign_with_options`]:
+/// hex `initiator` and `metadata`, RFC 3339 `expire.atTime`, `holdUntil.minorBlock`
+/// and an `authorities` array.
+pub(crate) fn marshal_header_json(header: &Value) -> Result<Vec<u8>, JsonRpcError> {
+    use crate::codec::signing::{marshal_transaction_header_full, HeaderBinaryOptions};
+
+    let principal = header.get("principal").and_then(|p| p.as_str()).unwrap_or("");
+    let initiator = match header.get("initiator").and_then(|i| i.as_str()) {
+        Some(hex_str) => parse_hex_hash("initiator", hex_str).map_err(|e| JsonRpcError::General(e.to_string()))?,
+        None => [0u8; 32],
+    };
+    let memo = header.get("memo").and_then(|m| m.as_str());
+    let metadata = match header.get("metadata").and_then(|m| m.as_str()) {
+        Some(hex_str) => Some(body_hex("metadata", hex_str)?),
+        None => None,
+    };
+
+    let expire_at_time = match header.get("expire").and_then(|e| e.get("atTime")) {
+        Some(Value::String(t)) => Some(
+            chrono::DateTime::parse_from_rfc3339(t)
+                .map_err(|e| JsonRpcError::General(format!("Invalid expire.atTime: {}", e)))?
+                .timestamp(),
+        ),
+        Some(t) => t.as_i64(),
+        None => None,
+    };
+    let hold_until_minor_block = header
+        .get("holdUntil")
+        .and_then(|h| h.get("minorBlock"))
+        .and_then(|b| b.as_u64());
+    let authorities: Option<Vec<String>> = header.get("authorities").and_then(|a| a.as_array()).map(|arr| {
+        arr.iter().filter_map(|v| v.as_str().map(str::to_string)).collect()
+    });
+
+    let extended = if expire_at_time.is_some() || hold_until_minor_block.is_some() || authorities.is_some() {
+        Some(HeaderBinaryOptions {
+            expire_at_time,
+            hold_until_minor_block,
+            authorities,
+        })
+    } else {
+        None
+    };
+
+    Ok(marshal_transaction_header_full(
+        principal,
+        &initiator,
+        memo,
+        metadata.as_deref(),
+        extended.as_ref(),
+    ))
+}
+
+/// Parse a body amount given as a decimal string or a JSON number
+///
+/// A missing amount is zero; anything else that is not a `u64` is an error,
+/// since silently encoding zero would sign a different transaction.
+fn body_amount(name: &str, value: Option<&Value>) -> Result<u64, JsonRpcError> {
+    let parsed = match value {
+        None | Some(Value::Null) => return Ok(0),
+        Some(Value::String(s)) => s.parse::<u64>().ok(),
+        Some(Value::Number(n)) => n.as_u64(),
+        Some(_) => None,
+    };
+    parsed.ok_or_else(|| {
+        JsonRpcError::General(format!(
+            "{name}: expected an unsigned 64-bit amount, got {}",
+            value.map_or_else(String::new, Value::to_string)
+        ))
+    })
+}
+
+/// Extract the body's `authorities` URLs
+fn body_authorities(body: &Value) -> Vec<&str> {
+    body.get("authorities")
+        .and_then(|a| a.as_array())
+        .map(|a| a.iter().filter_map(|u| u.as_str()).collect())
+        .unwrap_or_default()
+}
+
+/// Parse a list of transaction types given by name or number
+fn body_tx_types(name: &str, value: Option<&Value>) -> Result<Vec<u64>, JsonRpcError> {
+    use crate::codec::signing::tx_types;
+
+    let mut types = Vec::new();
+    for (i, item) in value.and_then(|v| v.as_array()).into_iter().flatten().enumerate() {
+        let tx_type = item.as_u64().or_else(|| item.as_str().and_then(tx_types::from_name));
+        types.push(tx_type.ok_or_else(|| {
+            JsonRpcError::General(format!("{name}[{i}]: unknown transaction type {item}"))
+        })?);
+    }
+    Ok(types)
+}
+
 /// Marshal a JSON transaction body to binary format
 ///
-/// This handles different transaction types and converts them to proper binary encoding.
-fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
+/// Every user transaction type plus systemGenesis and systemWriteData is
+/// encoded field by field as the Go core does. Anchor bodies are produced by
+/// validators and types the SDK does not know are rejected rather than
+/// encoded partially, since a partial encoding signs the wrong hash.
+pub(crate) fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
     use crate::codec::signing::{
-        marshal_add_credits_body, marshal_send_tokens_body, marshal_create_identity_body,
-        marshal_create_token_account_body, marshal_create_data_account_body,
-        marshal_write_data_body, marshal_create_token_body, marshal_issue_tokens_body,
-        marshal_key_page_operation, marshal_update_key_page_body,
-        marshal_create_key_book_body, marshal_create_key_page_body,
+        marshal_add_credits_body, marshal_send_tokens_body_full, marshal_create_identity_body_full,
+        marshal_create_token_account_body_full, marshal_create_data_account_body_full,
+        marshal_write_data_body, marshal_create_token_body_full, marshal_issue_tokens_body,
+        marshal_key_page_operation, marshal_update_allowed_operation, marshal_update_key_page_body,
+        marshal_create_key_book_body_full, marshal_create_key_page_body,
         marshal_burn_tokens_body, marshal_update_key_body,
         marshal_burn_credits_body, marshal_transfer_credits_body,
         marshal_write_data_to_body, marshal_lock_account_body,
-        marshal_update_account_auth_body,
-        tx_types
+        marshal_update_account_auth_body, marshal_remote_transaction_body,
+        marshal_acme_faucet_body, marshal_create_lite_token_account_body,
+        marshal_activate_protocol_version_body, marshal_network_maintenance_body,
+        marshal_system_genesis_body, marshal_system_write_data_body,
+        executor_versions, network_maintenance_op_types,
     };
-    use crate::codec::writer::BinaryWriter;
 
     let tx_type = body.get("type").and_then(|t| t.as_str()).unwrap_or("");
 
     match tx_type {
         "addCredits" => {
             let recipient = body.get("recipient").and_then(|r| r.as_str()).unwrap_or("");
-            let amount_str = body.get("amount").and_then(|a| a.as_str()).unwrap_or("0");
-            let amount: u64 = amount_str.parse().unwrap_or(0);
+            let amount = body_amount("amount", body.get("amount"))?;
             let oracle = body.get("oracle").and_then(|o| o.as_u64()).unwrap_or(0);
             Ok(marshal_add_credits_body(recipient, amount, oracle))
         }
@@ -998,14 +1958,21 @@ fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
             let to_array = body.get("to").and_then(|t| t.as_array());
             let mut recipients = Vec::new();
             if let Some(to) = to_array {
-                for recipient in to {
+                for (i, recipient) in to.iter().enumerate() {
                     let url = recipient.get("url").and_then(|u| u.as_str()).unwrap_or("");
-                    let amount_str = recipient.get("amount").and_then(|a| a.as_str()).unwrap_or("0");
-                    let amount: u64 = amount_str.parse().unwrap_or(0);
+                    let amount = body_amount(&format!("to[{i}].amount"), recipient.get("amount"))?;
                     recipients.push((url.to_string(), amount));
                 }
             }
-            Ok(marshal_send_tokens_body(&recipients))
+            let hash = body.get("hash")
+                .and_then(|h| h.as_str())
+                .map(|hex_str| parse_hex_hash("hash", hex_str).map_err(|e| JsonRpcError::General(e.to_string())))
+                .transpose()?;
+            let meta = body.get("meta")
+                .filter(|m| !m.is_null())
+                .map(serde_json::to_vec)
+                .transpose()?;
+            Ok(marshal_send_tokens_body_full(hash.as_ref(), meta.as_deref(), &recipients))
         }
         "createIdentity" => {
             let url = body.get("url").and_then(|u| u.as_str()).unwrap_or("");
@@ -1015,32 +1982,21 @@ fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
                 .or_else(|| body.get("publicKeyHash"))
                 .and_then(|k| k.as_str())
                 .unwrap_or("");
-            let key_hash = hex::decode(key_hash_hex).unwrap_or_default();
-            Ok(marshal_create_identity_body(url, &key_hash, key_book_url))
+            let key_hash = body_hex("keyHash", key_hash_hex)?;
+            Ok(marshal_create_identity_body_full(url, &key_hash, key_book_url, &body_authorities(body)))
         }
         "createTokenAccount" => {
             let url = body.get("url").and_then(|u| u.as_str()).unwrap_or("");
             let token_url = body.get("tokenUrl").and_then(|t| t.as_str()).unwrap_or("");
-            Ok(marshal_create_token_account_body(url, token_url))
+            Ok(marshal_create_token_account_body_full(url, token_url, &body_authorities(body)))
         }
         "createDataAccount" => {
             let url = body.get("url").and_then(|u| u.as_str()).unwrap_or("");
-            Ok(marshal_create_data_account_body(url))
+            Ok(marshal_create_data_account_body_full(url, &body_authorities(body)))
         }
         "writeData" => {
             // Extract entries from nested entry.data structure
-            let mut entries_hex = Vec::new();
-            if let Some(entry) = body.get("entry") {
-                if let Some(data) = entry.get("data") {
-                    if let Some(arr) = data.as_array() {
-                        for item in arr {
-                            if let Some(s) = item.as_str() {
-                                entries_hex.push(s.to_string());
-                            }
-                        }
-                    }
-                }
-            }
+            let entries_hex = extract_data_entries(body)?;
             let scratch = body.get("scratch").and_then(|s| s.as_bool()).unwrap_or(false);
             let write_to_state = body.get("writeToState").and_then(|w| w.as_bool()).unwrap_or(false);
             Ok(marshal_write_data_body(&entries_hex, scratch, write_to_state))
@@ -1049,27 +2005,34 @@ fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
             let url = body.get("url").and_then(|u| u.as_str()).unwrap_or("");
             let symbol = body.get("symbol").and_then(|s| s.as_str()).unwrap_or("");
             let precision = body.get("precision").and_then(|p| p.as_u64()).unwrap_or(0);
-            let supply_limit = body.get("supplyLimit")
-                .and_then(|s| s.as_str())
-                .and_then(|s| s.parse::<u64>().ok());
-            Ok(marshal_create_token_body(url, symbol, precision, supply_limit))
+            let properties = body.get("properties").and_then(|p| p.as_str());
+            let supply_limit = match body.get("supplyLimit") {
+                None | Some(Value::Null) => None,
+                limit => Some(body_amount("supplyLimit", limit)?),
+            };
+            Ok(marshal_create_token_body_full(
+                url,
+                symbol,
+                precision,
+                properties,
+                supply_limit,
+                &body_authorities(body),
+            ))
         }
         "issueTokens" => {
             let to_array = body.get("to").and_then(|t| t.as_array());
             let mut recipients: Vec<(&str, u64)> = Vec::new();
             if let Some(to) = to_array {
-                for recipient in to {
+                for (i, recipient) in to.iter().enumerate() {
                     let url = recipient.get("url").and_then(|u| u.as_str()).unwrap_or("");
-                    let amount_str = recipient.get("amount").and_then(|a| a.as_str()).unwrap_or("0");
-                    let amount: u64 = amount_str.parse().unwrap_or(0);
+                    let amount = body_amount(&format!("to[{i}].amount"), recipient.get("amount"))?;
                     recipients.push((url, amount));
                 }
             }
             Ok(marshal_issue_tokens_body(&recipients))
         }
         "burnTokens" => {
-            let amount_str = body.get("amount").and_then(|a| a.as_str()).unwrap_or("0");
-            let amount: u64 = amount_str.parse().unwrap_or(0);
+            let amount = body_amount("amount", body.get("amount"))?;
             Ok(marshal_burn_tokens_body(amount))
         }
         "createKeyBook" => {
@@ -1078,19 +2041,19 @@ fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
                 .or_else(|| body.get("keyHash"))
                 .and_then(|k| k.as_str())
                 .unwrap_or("");
-            let key_hash = hex::decode(key_hash_hex).unwrap_or_default();
-            Ok(marshal_create_key_book_body(url, &key_hash))
+            let key_hash = body_hex("publicKeyHash", key_hash_hex)?;
+            Ok(marshal_create_key_book_body_full(url, &key_hash, &body_authorities(body)))
         }
         "createKeyPage" => {
             let keys_array = body.get("keys").and_then(|k| k.as_array());
             let mut key_hashes: Vec<Vec<u8>> = Vec::new();
             if let Some(keys) = keys_array {
-                for key in keys {
+                for (i, key) in keys.iter().enumerate() {
                     let key_hash_hex = key.get("keyHash")
                         .or_else(|| key.get("publicKeyHash"))
                         .and_then(|k| k.as_str())
                         .unwrap_or("");
-                    let key_hash = hex::decode(key_hash_hex).unwrap_or_default();
+                    let key_hash = body_hex(&format!("keys[{i}].keyHash"), key_hash_hex)?;
                     key_hashes.push(key_hash);
                 }
             }
@@ -1101,7 +2064,7 @@ fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
                 .or_else(|| body.get("newKey"))
                 .and_then(|k| k.as_str())
                 .unwrap_or("");
-            let new_key_hash = hex::decode(new_key_hash_hex).unwrap_or_default();
+            let new_key_hash = body_hex("newKeyHash", new_key_hash_hex)?;
             Ok(marshal_update_key_body(&new_key_hash))
         }
         "updateKeyPage" => {
@@ -1110,7 +2073,7 @@ fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
             let mut operations: Vec<Vec<u8>> = Vec::new();
 
             if let Some(ops) = op_array {
-                for op in ops {
+                for (i, op) in ops.iter().enumerate() {
                     let op_type = op.get("type").and_then(|t| t.as_str()).unwrap_or("");
 
                     // Extract key hash from entry.keyHash (for add/remove operations)
@@ -1118,10 +2081,12 @@ fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
                     let key_hash: Option<Vec<u8>> = op.get("entry")
                         .and_then(|e| e.get("keyHash"))
                         .and_then(|h| h.as_str())
-                        .and_then(|hex_str| hex::decode(hex_str).ok());
+                        .map(|hex_str| body_hex(&format!("operation[{i}].entry.keyHash"), hex_str))
+                        .transpose()?;
 
-                    // Extract delegate URL if present
+                    // Extract delegate URL if present; update carries it in newEntry
                     let delegate: Option<&str> = op.get("entry")
+                        .or_else(|| op.get("newEntry"))
                         .and_then(|e| e.get("delegate"))
                         .and_then(|d| d.as_str());
 
@@ -1129,16 +2094,34 @@ fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
                     let old_key_hash: Option<Vec<u8>> = op.get("oldEntry")
                         .and_then(|e| e.get("keyHash"))
                         .and_then(|h| h.as_str())
-                        .and_then(|hex_str| hex::decode(hex_str).ok());
+                        .map(|hex_str| body_hex(&format!("operation[{i}].oldEntry.keyHash"), hex_str))
+                        .transpose()?;
 
                     let new_key_hash: Option<Vec<u8>> = op.get("newEntry")
                         .and_then(|e| e.get("keyHash"))
                         .and_then(|h| h.as_str())
-                        .and_then(|hex_str| hex::decode(hex_str).ok());
+                        .map(|hex_str| body_hex(&format!("operation[{i}].newEntry.keyHash"), hex_str))
+                        .transpose()?;
 
                     // Extract threshold for setThreshold operation
                     let threshold: Option<u64> = op.get("threshold").and_then(|t| t.as_u64());
 
+                    if !matches!(op_type, "add" | "remove" | "update" | "setThreshold"
+                        | "setRejectThreshold" | "setResponseThreshold" | "updateAllowed")
+                    {
+                        return Err(JsonRpcError::General(format!(
+                            "operation[{i}]: unknown key page operation {op_type:?}"
+                        )));
+                    }
+
+                    // Allow/Deny lists are only carried by updateAllowed
+                    if op_type == "updateAllowed" {
+                        let allow = body_tx_types(&format!("operation[{i}].allow"), op.get("allow"))?;
+                        let deny = body_tx_types(&format!("operation[{i}].deny"), op.get("deny"))?;
+                        operations.push(marshal_update_allowed_operation(&allow, &deny));
+                        continue;
+                    }
+
                     // Marshal the operation
                     let op_bytes = marshal_key_page_operation(
                         op_type,
@@ -1155,16 +2138,16 @@ fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
             Ok(marshal_update_key_page_body(&operations))
         }
         "burnCredits" => {
-            let amount = body.get("amount").and_then(|a| a.as_u64()).unwrap_or(0);
+            let amount = body_amount("amount", body.get("amount"))?;
             Ok(marshal_burn_credits_body(amount))
         }
         "transferCredits" => {
             let to_array = body.get("to").and_then(|t| t.as_array());
             let mut recipients: Vec<(&str, u64)> = Vec::new();
             if let Some(to) = to_array {
-                for recipient in to {
+                for (i, recipient) in to.iter().enumerate() {
                     let url = recipient.get("url").and_then(|u| u.as_str()).unwrap_or("");
-                    let amount = recipient.get("amount").and_then(|a| a.as_u64()).unwrap_or(0);
+                    let amount = body_amount(&format!("to[{i}].amount"), recipient.get("amount"))?;
                     recipients.push((url, amount));
                 }
             }
@@ -1172,24 +2155,17 @@ fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
         }
         "writeDataTo" => {
             let recipient = body.get("recipient").and_then(|r| r.as_str()).unwrap_or("");
-            let mut entries_hex = Vec::new();
-            if let Some(entry) = body.get("entry") {
-                if let Some(data) = entry.get("data") {
-                    if let Some(arr) = data.as_array() {
-                        for item in arr {
-                            if let Some(s) = item.as_str() {
-                                entries_hex.push(s.to_string());
-                            }
-                        }
-                    }
-                }
-            }
+            let entries_hex = extract_data_entries(body)?;
             Ok(marshal_write_data_to_body(recipient, &entries_hex))
         }
         "lockAccount" => {
             let height = body.get("height").and_then(|h| h.as_u64()).unwrap_or(0);
             Ok(marshal_lock_account_body(height))
         }
+        "signPending" | "remoteTransaction" => {
+            let hash = remote_body_hash(body)?;
+            Ok(marshal_remote_transaction_body(&hash))
+        }
         "updateAccountAuth" => {
             let ops_array = body.get("operations").and_then(|o| o.as_array());
             let mut operations: Vec<(&str, &str)> = Vec::new();
@@ -1202,44 +2178,50 @@ fn marshal_body_to_binary(body: &Value) -> Result<Vec<u8>, JsonRpcError> {
             }
             Ok(marshal_update_account_auth_body(&operations))
         }
-        // For other transaction types, fall back to JSON encoding
-        // This won't produce correct signatures but allows compilation
-        _ => {
-            // Create a minimal binary encoding with just the type
-            let mut writer = BinaryWriter::new();
-
-            // Map type string to numeric type
-            let type_num = match tx_type {
-                "createIdentity" => tx_types::CREATE_IDENTITY,
-                "createTokenAccount" => tx_types::CREATE_TOKEN_ACCOUNT,
-                "createDataAccount" => tx_types::CREATE_DATA_ACCOUNT,
-                "writeData" => tx_types::WRITE_DATA,
-                "writeDataTo" => tx_types::WRITE_DATA_TO,
-                "acmeFaucet" => tx_types::ACME_FAUCET,
-                "createToken" => tx_types::CREATE_TOKEN,
-                "issueTokens" => tx_types::ISSUE_TOKENS,
-                "burnTokens" => tx_types::BURN_TOKENS,
-                "createLiteTokenAccount" => tx_types::CREATE_LITE_TOKEN_ACCOUNT,
-                "createKeyPage" => tx_types::CREATE_KEY_PAGE,
-                "createKeyBook" => tx_types::CREATE_KEY_BOOK,
-                "updateKeyPage" => tx_types::UPDATE_KEY_PAGE,
-                "updateAccountAuth" => tx_types::UPDATE_ACCOUNT_AUTH,
-                "updateKey" => tx_types::UPDATE_KEY,
-                "lockAccount" => tx_types::LOCK_ACCOUNT,
-                "transferCredits" => tx_types::TRANSFER_CREDITS,
-                "burnCredits" => tx_types::BURN_CREDITS,
-                _ => 0,
+        "acmeFaucet" => {
+            let url = body.get("url").and_then(|u| u.as_str()).unwrap_or("");
+            Ok(marshal_acme_faucet_body(url))
+        }
+        "createLiteTokenAccount" => Ok(marshal_create_lite_token_account_body()),
+        "activateProtocolVersion" => {
+            let version = match body.get("version") {
+                Some(Value::String(name)) => executor_versions::from_name(name).ok_or_else(|| {
+                    JsonRpcError::General(format!("version: unknown executor version {name:?}"))
+                })?,
+                Some(v) => v.as_u64().unwrap_or(0),
+                None => 0,
             };
-
-            // Write field 1: Type
-            let _ = writer.write_uvarint(1);
-            let _ = writer.write_uvarint(type_num);
-
-            // Just return the type encoding - proper implementation needed
-            // for each transaction type
-            // TODO: Implement full binary encoding for all transaction types
-            Ok(writer.into_bytes())
+            Ok(marshal_activate_protocol_version_body(version))
+        }
+        "networkMaintenance" => {
+            let ops_array = body.get("operations").and_then(|o| o.as_array());
+            let mut operations: Vec<(u64, &str)> = Vec::new();
+            if let Some(ops) = ops_array {
+                for (i, op) in ops.iter().enumerate() {
+                    let op_type = op.get("type").and_then(|t| t.as_str()).unwrap_or("");
+                    if !op_type.eq_ignore_ascii_case("pendingTransactionGC") {
+                        return Err(JsonRpcError::General(format!(
+                            "operations[{i}]: unknown network maintenance operation {op_type:?}"
+                        )));
+                    }
+                    let account = op.get("account").and_then(|a| a.as_str()).unwrap_or("");
+                    operations.push((network_maintenance_op_types::PENDING_TRANSACTION_GC, account));
+                }
+            }
+            Ok(marshal_network_maintenance_body(&operations))
+        }
+        "systemGenesis" => Ok(marshal_system_genesis_body()),
+        "systemWriteData" => {
+            let entries_hex = extract_data_entries(body)?;
+            let write_to_state = body.get("writeToState").and_then(|w| w.as_bool()).unwrap_or(false);
+            Ok(marshal_system_write_data_body(&entries_hex, write_to_state))
         }
+        "directoryAnchor" | "blockValidatorAnchor" => Err(JsonRpcError::General(format!(
+            "{tx_type} bodies are produced by validators and cannot be marshalled by the SDK"
+        ))),
+        _ => Err(JsonRpcError::General(format!(
+            "cannot marshal transaction body of type {tx_type:?}"
+        ))),
     }
 }
 
@@ -1267,56 +2249,199 @@ impl<'a> KeyManager<'a> {
 
     /// Get the current key page state
     pub async fn get_key_page_state(&self) -> Result<KeyPageState, JsonRpcError> {
-        let params = json!({
-            "scope": &self.key_page_url,
-            "query": {"queryType": "default"}
-        });
+        let record = self.client.v3().query_account(&self.key_page_url).await?;
+        Ok(KeyPageState::from_account(&record.account, &self.key_page_url))
+    }
 
-        let result: Value = self.client.v3_client.call_v3("query", params).await?;
+    /// Find the index of `public_key` among the entries of the key page
+    ///
+    /// Returns `None` when the key is not on the page.
+    pub async fn find_key_index(&self, public_key: &[u8]) -> Result<Option<usize>, JsonRpcError> {
+        Ok(self.get_key_page_state().await?.find_key_index(public_key))
+    }
 
-        let account = result.get("account")
-            .ok_or_else(|| JsonRpcError::General(anyhow::anyhow!("No account in response")))?;
+    /// Add `public_key` to the page, signed by `signer`
+    ///
+    /// `signer` must be able to sign for the page, usually as a key on it or on
+    /// a page of the same book.
+    pub async fn add_key(&self, signer: &mut SmartSigner<'_>, public_key: &[u8]) -> TxResult {
+        let body = TxBody::update_key_page_add_key(&sha256_hash(public_key));
+        signer.sign_submit_and_wait(&self.key_page_url, &body, Some("Add key"), 30).await
+    }
 
-        let url = account.get("url")
-            .and_then(|v| v.as_str())
-            .unwrap_or(&self.key_page_url)
-            .to_string();
+    /// Remove the key with hash `public_key_hash` from the page, signed by `signer`
+    pub async fn remove_key(&self, signer: &mut SmartSigner<'_>, public_key_hash: &[u8]) -> TxResult {
+        let body = TxBody::update_key_page_remove_key(public_key_hash);
+        signer.sign_submit_and_wait(&self.key_page_url, &body, Some("Remove key"), 30).await
+    }
 
-        let version = account.get("version")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(1);
+    /// Replace the key with hash `old_key_hash` by `new_public_key`, signed by `signer`
+    pub async fn update_key(
+        &self,
+        signer: &mut SmartSigner<'_>,
+        old_key_hash: &[u8],
+        new_public_key: &[u8],
+    ) -> TxResult {
+        let body = TxBody::update_key_page_update_key(old_key_hash, &sha256_hash(new_public_key));
+        signer.sign_submit_and_wait(&self.key_page_url, &body, Some("Update key"), 30).await
+    }
 
-        let credit_balance = account.get("creditBalance")
-            .and_then(|v| v.as_u64())
-            .unwrap_or(0);
+    /// Set the page's accept threshold, signed by `signer`
+    pub async fn set_threshold(&self, signer: &mut SmartSigner<'_>, threshold: u64) -> TxResult {
+        let body = TxBody::update_key_page_set_threshold(threshold);
+        signer.sign_submit_and_wait(&self.key_page_url, &body, Some("Set threshold"), 30).await
+    }
 
-        let accept_threshold = account.get("acceptThreshold")
-            .or_else(|| account.get("threshold"))
-            .and_then(|v| v.as_u64())
-            .unwrap_or(1);
+    /// Set the page's reject threshold, signed by `signer`
+    pub async fn set_reject_threshold(&self, signer: &mut SmartSigner<'_>, threshold: u64) -> TxResult {
+        let body = TxBody::update_key_page_set_reject_threshold(threshold);
+        signer.sign_submit_and_wait(&self.key_page_url, &body, Some("Set reject threshold"), 30).await
+    }
 
-        let keys: Vec<KeyEntry> = if let Some(keys_arr) = account.get("keys").and_then(|k| k.as_array()) {
-            keys_arr.iter().map(|k| {
-                let key_hash = k.get("publicKeyHash")
-                    .or_else(|| k.get("publicKey"))
-                    .and_then(|v| v.as_str())
-                    .unwrap_or("")
-                    .to_string();
-                let delegate = k.get("delegate").and_then(|v| v.as_str()).map(String::from);
-                KeyEntry { key_hash, delegate }
-            }).collect()
-        } else {
-            vec![]
+    /// Set the page's response threshold, signed by `signer`
+    pub async fn set_response_threshold(&self, signer: &mut SmartSigner<'_>, threshold: u64) -> TxResult {
+        let body = TxBody::update_key_page_set_response_threshold(threshold);
+        signer.sign_submit_and_wait(&self.key_page_url, &body, Some("Set response threshold"), 30).await
+    }
+
+    /// Apply every operation of `operations` in one transaction, signed by `signer`
+    ///
+    /// Batching keeps the page version from changing once per operation, so
+    /// other signers collecting signatures on the page are disturbed once.
+    pub async fn apply(
+        &self,
+        signer: &mut SmartSigner<'_>,
+        operations: crate::builders::UpdateKeyPageBuilder,
+    ) -> TxResult {
+        match operations.build() {
+            Ok(body) => {
+                signer.sign_submit_body_and_wait(&self.key_page_url, &body, Some("Update key page"), 30).await
+            }
+            Err(e) => TxResult::err(format!("Invalid transaction body: {e}")),
+        }
+    }
+
+    /// Every version of the page, oldest first, rebuilt from its main chain
+    ///
+    /// Each transaction that created or changed the page yields one
+    /// [`KeyPageVersion`] with the keys and threshold it left behind, so the
+    /// page's membership at any earlier version can be audited.
+    pub async fn page_history(&self) -> Result<Vec<KeyPageVersion>, JsonRpcError> {
+        let range = crate::types::RangeOptions {
+            start: Some(0),
+            expand: Some(true),
+            ..Default::default()
         };
+        let history: Value = self
+            .client
+            .v3_client
+            .call_v3(
+                "query",
+                json!({
+                    "url": &self.key_page_url,
+                    "query": {"queryType": "chain", "name": "main", "range": range}
+                }),
+            )
+            .await?;
+        Ok(replay_page_history(&crate::activity::history_transactions(&history)))
+    }
+}
 
-        Ok(KeyPageState {
-            url,
-            version,
-            credit_balance,
+/// One version of a key page, from [`KeyManager::page_history`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeyPageVersion {
+    /// Page version after the transaction
+    pub version: u64,
+    /// Hash of the transaction that produced this version
+    pub transaction_hash: String,
+    /// Type of that transaction
+    pub transaction_type: String,
+    /// Accept threshold after the transaction
+    pub accept_threshold: u64,
+    /// Keys on the page after the transaction
+    pub keys: Vec<KeyEntry>,
+}
+
+/// Replay the page-changing transactions of a key page's main chain
+///
+/// `updateKey` does not name the key it replaces; the main chain entry
+/// lacks the signature too, so the replaced key is only known when the page
+/// held a single key. Otherwise the new key is added alongside the others.
+fn replay_page_history(transactions: &[(String, Value)]) -> Vec<KeyPageVersion> {
+    let mut versions: Vec<KeyPageVersion> = Vec::new();
+    let mut keys: Vec<KeyEntry> = Vec::new();
+    let mut accept_threshold = 1;
+    let entry_of = |value: &Value| KeyEntry {
+        key_hash: value.get("keyHash")
+            .or_else(|| value.get("publicKeyHash"))
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .to_ascii_lowercase(),
+        delegate: value.get("delegate").and_then(Value::as_str).map(String::from),
+    };
+    let same_entry = |a: &KeyEntry, b: &KeyEntry| {
+        (!a.key_hash.is_empty() && a.key_hash == b.key_hash)
+            || (a.key_hash.is_empty() && a.delegate.is_some() && a.delegate == b.delegate)
+    };
+
+    for (hash, tx) in transactions {
+        let body = &tx["body"];
+        let kind = body["type"].as_str().unwrap_or("");
+        match kind {
+            "createIdentity" | "createKeyBook" => {
+                let field = if kind == "createIdentity" { "keyHash" } else { "publicKeyHash" };
+                keys = body[field].as_str()
+                    .map(|h| vec![KeyEntry { key_hash: h.to_ascii_lowercase(), delegate: None }])
+                    .unwrap_or_default();
+                accept_threshold = 1;
+            }
+            "createKeyPage" => {
+                keys = body["keys"].as_array().into_iter().flatten().map(entry_of).collect();
+                accept_threshold = 1;
+            }
+            "updateKey" => {
+                let new_entry = KeyEntry {
+                    key_hash: body["newKeyHash"].as_str().unwrap_or("").to_ascii_lowercase(),
+                    delegate: None,
+                };
+                if keys.len() == 1 {
+                    keys[0] = new_entry;
+                } else {
+                    keys.push(new_entry);
+                }
+            }
+            "updateKeyPage" => {
+                for op in body["operation"].as_array().into_iter().flatten() {
+                    match op["type"].as_str().unwrap_or("").to_ascii_lowercase().as_str() {
+                        "add" => keys.push(entry_of(&op["entry"])),
+                        "remove" => {
+                            let entry = entry_of(&op["entry"]);
+                            keys.retain(|k| !same_entry(&entry, k));
+                        }
+                        "update" => {
+                            let old = entry_of(&op["oldEntry"]);
+                            if let Some(k) = keys.iter_mut().find(|k| same_entry(&old, k)) {
+                                *k = entry_of(&op["newEntry"]);
+                            }
+                        }
+                        "setthreshold" => {
+                            accept_threshold = op["threshold"].as_u64().unwrap_or(accept_threshold);
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            _ => continue,
+        }
+        versions.push(KeyPageVersion {
+            version: versions.last().map_or(1, |v| v.version + 1),
+            transaction_hash: hash.clone(),
+            transaction_type: kind.to_string(),
             accept_threshold,
-            keys,
-        })
+            keys: keys.clone(),
+        });
     }
+    versions
 }
 
 // =============================================================================
@@ -1329,99 +2454,291 @@ pub async fn poll_for_balance(
     account_url: &str,
     max_attempts: u32,
 ) -> Option<u64> {
-    for i in 0..max_attempts {
-        let params = json!({
-            "scope": account_url,
-            "query": {"queryType": "default"}
-        });
+    let step = ProgressStep::new("poll_for_balance", "confirm balance").on(account_url);
+    poll_for_balance_reporting(client, account_url, max_attempts, &NoProgress, &step).await
+}
 
-        match client.v3_client.call_v3::<Value>("query", params).await {
-            Ok(result) => {
-                if let Some(account) = result.get("account") {
-                    // Try balance as string first
-                    if let Some(balance) = account.get("balance").and_then(|b| b.as_str()) {
-                        if let Ok(bal) = balance.parse::<u64>() {
-                            if bal > 0 {
-                                return Some(bal);
-                            }
-                        }
-                    }
-                    // Try balance as number
-                    if let Some(bal) = account.get("balance").and_then(|b| b.as_u64()) {
-                        if bal > 0 {
-                            return Some(bal);
-                        }
-                    }
+/// Like [`poll_for_balance`], reporting each unsuccessful attempt as `step` waiting
+pub(crate) async fn poll_for_balance_reporting(
+    client: &AccumulateClient,
+    account_url: &str,
+    max_attempts: u32,
+    progress: &dyn ProgressReporter,
+    step: &ProgressStep,
+) -> Option<u64> {
+    let deadline = confirmation_deadline(client);
+    for i in 0..max_attempts {
+        match client.v3().query_account(account_url).await {
+            Ok(record) => {
+                if let Some(bal) = record.balance().filter(|&bal| bal > 0) {
+                    return Some(bal);
                 }
-                println!("  Waiting for balance... (attempt {}/{})", i + 1, max_attempts);
+                progress.waiting(step, &format!("waiting for balance (attempt {}/{})", i + 1, max_attempts));
             }
             Err(_) => {
                 // Account may not exist yet
-                println!("  Account not found yet... (attempt {}/{})", i + 1, max_attempts);
+                progress.waiting(step, &format!("account not found yet (attempt {}/{})", i + 1, max_attempts));
+            }
+        }
+
+        if i < max_attempts - 1 && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(client.options.poll_interval).await;
+        } else {
+            break;
+        }
+    }
+    None
+}
+
+/// Poll for key page credits (matching Dart SDK pollForKeyPageCredits)
+pub async fn poll_for_credits(
+    client: &AccumulateClient,
+    key_page_url: &str,
+    max_attempts: u32,
+) -> Option<u64> {
+    let deadline = confirmation_deadline(client);
+    for i in 0..max_attempts {
+        if let Ok(record) = client.v3().query_account(key_page_url).await {
+            if let Some(credits) = record.credit_balance().filter(|&credits| credits > 0) {
+                return Some(credits);
+            }
+        }
+
+        if i < max_attempts - 1 && tokio::time::Instant::now() < deadline {
+            tokio::time::sleep(client.options.poll_interval).await;
+        } else {
+            break;
+        }
+    }
+    None
+}
+
+/// First delay between [`ensure_funded`] rounds
+const FUNDING_INITIAL_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Longest delay between [`ensure_funded`] rounds
+const FUNDING_MAX_BACKOFF: Duration = Duration::from_secs(16);
+
+/// Request faucet tokens until `token_account` holds at least `min_balance`
+///
+/// Each round reads the balance and, if it is still short and has not moved
+/// since the previous round, asks the faucet for more. The delay between
+/// rounds doubles (1s up to 16s) and resets whenever the balance grows.
+/// Returns the balance in base units, or an error once `timeout` elapses.
+pub async fn ensure_funded(
+    client: &AccumulateClient,
+    token_account: &str,
+    min_balance: u64,
+    timeout: Duration,
+) -> Result<u64, JsonRpcError> {
+    let deadline = tokio::time::Instant::now() + timeout;
+    let mut backoff = FUNDING_INITIAL_BACKOFF;
+    let mut last_balance = None;
+
+    loop {
+        let balance = query_balance(client, token_account).await.unwrap_or(0);
+        if balance >= min_balance {
+            return Ok(balance);
+        }
+
+        if last_balance.is_some_and(|last| balance > last) {
+            backoff = FUNDING_INITIAL_BACKOFF;
+        } else {
+            // Faucet failures are retried on the next round
+            let params = json!({"account": token_account});
+            let _ = client.v3_client.call_v3::<Value>("faucet", params).await;
+        }
+        last_balance = Some(balance);
+
+        let now = tokio::time::Instant::now();
+        if now >= deadline {
+            return Err(JsonRpcError::General(format!(
+                "{token_account} holds {balance} base units, wanted {min_balance} (gave up after {timeout:?})"
+            )));
+        }
+        tokio::time::sleep(backoff.min(deadline - now)).await;
+        backoff = (backoff * 2).min(FUNDING_MAX_BACKOFF);
+    }
+}
+
+/// Read a token account balance once; `None` if the account cannot be queried
+pub(crate) async fn query_balance(client: &AccumulateClient, account_url: &str) -> Option<u64> {
+    client.v3().query_account(account_url).await.ok()?.balance()
+}
+
+/// Wait for transaction confirmation
+///
+/// Polls up to `max_attempts` times, stopping early once the client's
+/// confirmation timeout has elapsed. Returns `true` once the transaction has
+/// been executed, successfully or not; use a
+/// [`TxTracker`](crate::tx_status::TxTracker) to tell which.
+pub async fn wait_for_tx(
+    client: &AccumulateClient,
+    txid: &str,
+    max_attempts: u32,
+) -> bool {
+    let scope = message_scope(txid);
+    let deadline = confirmation_deadline(client);
+
+    for _ in 0..max_attempts {
+        if let Ok(record) = client.v3().query_transaction(&scope).await {
+            if TxStatus::from_record(&record).is_final() {
+                return true;
             }
         }
 
-        if i < max_attempts - 1 {
-            tokio::time::sleep(Duration::from_secs(2)).await;
+        if tokio::time::Instant::now() >= deadline {
+            break;
         }
+        tokio::time::sleep(client.options.poll_interval).await;
     }
-    None
+    false
 }
 
-/// Poll for key page credits (matching Dart SDK pollForKeyPageCredits)
-pub async fn poll_for_credits(
+/// When a wait helper gives up: now plus the client's
+/// [`AccOptions::confirmation_timeout`](crate::AccOptions::confirmation_timeout)
+/// Submit a signed envelope and poll until it is delivered, fails, or
+/// `max_attempts` polls or the client's confirmation timeout run out
+pub(crate) async fn submit_envelope_and_wait(
     client: &AccumulateClient,
-    key_page_url: &str,
+    envelope: &Value,
     max_attempts: u32,
-) -> Option<u64> {
-    for i in 0..max_attempts {
-        let params = json!({
-            "scope": key_page_url,
-            "query": {"queryType": "default"}
-        });
+) -> TxResult {
+    submit_envelope_and_confirm(client, envelope, max_attempts, ConfirmationPolicy::Delivered).await
+}
 
-        if let Ok(result) = client.v3_client.call_v3::<Value>("query", params).await {
-            if let Some(account) = result.get("account") {
-                if let Some(credits) = account.get("creditBalance").and_then(|c| c.as_u64()) {
-                    if credits > 0 {
-                        return Some(credits);
-                    }
-                }
-            }
+/// Submit a signed envelope and poll until it meets `policy`, fails, or
+/// `max_attempts` polls or the client's confirmation timeout run out
+///
+/// Delivery is polled for first; levels past it are then tracked with a
+/// [`TxTracker`](crate::tx_status::TxTracker) until the same deadline.
+async fn submit_envelope_and_confirm(
+    client: &AccumulateClient,
+    envelope: &Value,
+    max_attempts: u32,
+    policy: ConfirmationPolicy,
+) -> TxResult {
+    // Submit
+    let submit_result: Result<Value, _> = client.v3_client.call_v3("submit", json!({
+        "envelope": envelope
+    })).await;
+
+    let response = match submit_result {
+        Ok(resp) => resp,
+        Err(e) => return TxResult::err(format!("Submit failed: {}", e)),
+    };
+
+    // Extract transaction ID
+    let txid = extract_txid(&response);
+    if txid.is_none() {
+        return TxResult::err("No transaction ID in response".to_string());
+    }
+    let txid = txid.unwrap();
+    if policy == ConfirmationPolicy::Submitted {
+        return TxResult::ok(txid, response);
+    }
+
+    // Wait for confirmation, querying the hash under acc://<hash>@unknown
+    let query_scope = message_scope(&txid);
+
+    let deadline = confirmation_deadline(client);
+    for _attempt in 0..max_attempts {
+        if tokio::time::Instant::now() >= deadline {
+            break;
         }
+        tokio::time::sleep(client.options.poll_interval).await;
 
-        if i < max_attempts - 1 {
-            tokio::time::sleep(Duration::from_secs(2)).await;
+        if let Some(outcome) = delivery_outcome(client, &query_scope).await {
+            return match outcome {
+                Ok(_) if policy > ConfirmationPolicy::Delivered => {
+                    confirm_after_delivery(client, txid, response, policy, deadline).await
+                }
+                Ok(_) => TxResult::ok(txid, response),
+                Err(error) => TxResult::err(error),
+            };
         }
     }
-    None
+
+    TxResult::timed_out(txid, response)
 }
 
-/// Wait for transaction confirmation
-pub async fn wait_for_tx(
+/// Track a delivered transaction until it meets `policy` or `deadline`
+async fn confirm_after_delivery(
     client: &AccumulateClient,
-    txid: &str,
-    max_attempts: u32,
-) -> bool {
-    let tx_hash = txid.split('@').next().unwrap_or(txid).replace("acc://", "");
+    txid: String,
+    response: Value,
+    policy: ConfirmationPolicy,
+    deadline: tokio::time::Instant,
+) -> TxResult {
+    let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+    let outcome = client.tx_tracker().timeout(remaining).confirm(&txid, policy).await;
+    if outcome.confirmed {
+        return TxResult::ok(txid, response);
+    }
+    TxResult {
+        txid: Some(txid),
+        response: Some(response),
+        ..TxResult::err(format!("Delivered but not {policy} before the confirmation timeout"))
+    }
+}
 
-    for _ in 0..max_attempts {
-        let params = json!({
-            "scope": format!("acc://{}@unknown", tx_hash),
-            "query": {"queryType": "default"}
-        });
+/// Query a transaction's status once; `None` while it is pending or cannot
+/// be queried, else the delivered record or the failure message
+async fn delivery_outcome(
+    client: &AccumulateClient,
+    query_scope: &str,
+) -> Option<Result<crate::query::MessageRecord, String>> {
+    let record = client.v3().query_transaction(query_scope).await.ok()?;
+    match TxStatus::from_record(&record) {
+        TxStatus::Delivered => Some(Ok(record)),
+        TxStatus::Failed { message, .. } => Some(Err(message)),
+        TxStatus::Pending | TxStatus::Unknown => None,
+    }
+}
 
-        if let Ok(result) = client.v3_client.call_v3::<Value>("query", params).await {
-            if let Some(status) = result.get("status") {
-                if status.get("delivered").and_then(|d| d.as_bool()).unwrap_or(false) {
-                    return true;
+impl AccumulateClient {
+    /// Wait for a transaction submitted earlier, possibly by another process
+    ///
+    /// Picks up where [`SmartSigner::sign_submit_and_wait`] left off when it
+    /// timed out or the process died before confirmation. Checks the status
+    /// right away, then every `options.interval`; the result carries the
+    /// message record as `response` once delivered.
+    pub async fn resume_wait(&self, txid: &str, options: PollOptions) -> TxResult {
+        let query_scope = message_scope(txid);
+        let interval = options.interval.unwrap_or(self.options.poll_interval);
+        let deadline = tokio::time::Instant::now()
+            + options.timeout.unwrap_or(self.options.confirmation_timeout);
+
+        for attempt in 0..options.max_attempts {
+            if attempt > 0 {
+                if tokio::time::Instant::now() >= deadline {
+                    break;
                 }
+                tokio::time::sleep(interval).await;
+            }
+            if let Some(outcome) = delivery_outcome(self, &query_scope).await {
+                return match outcome {
+                    Ok(record) => TxResult::ok(
+                        txid.to_string(),
+                        serde_json::to_value(record).unwrap_or(Value::Null),
+                    ),
+                    Err(error) => TxResult {
+                        txid: Some(txid.to_string()),
+                        ..TxResult::err(error)
+                    },
+                };
             }
         }
 
-        tokio::time::sleep(Duration::from_secs(2)).await;
+        TxResult {
+            response: None,
+            ..TxResult::timed_out(txid.to_string(), Value::Null)
+        }
     }
-    false
+}
+
+pub(crate) fn confirmation_deadline(client: &AccumulateClient) -> tokio::time::Instant {
+    tokio::time::Instant::now() + client.options.confirmation_timeout
 }
 
 // =============================================================================
@@ -1440,6 +2757,35 @@ pub struct Wallet {
 }
 
 impl Wallet {
+    /// Generate a new key and derive its lite identity and ACME token account
+    pub fn generate() -> Self {
+        let keypair = AccumulateClient::generate_keypair();
+        let public_key = keypair.verifying_key().to_bytes();
+
+        // Derive lite identity URL
+        let lite_identity = derive_lite_identity_url(&public_key);
+        let lite_token_account = format!("{}/ACME", lite_identity);
+
+        Self {
+            lite_identity,
+            lite_token_account,
+            keypair,
+        }
+    }
+
+    /// Wallet of an existing key, e.g. one loaded from a
+    /// [`Keystore`](crate::crypto::keystore::Keystore)
+    pub fn from_keypair(keypair: SigningKey) -> Self {
+        let lite_identity = derive_lite_identity_url(&keypair.verifying_key().to_bytes());
+        let lite_token_account = format!("{}/ACME", lite_identity);
+
+        Self {
+            lite_identity,
+            lite_token_account,
+            keypair,
+        }
+    }
+
     /// Get the signing key
     pub fn keypair(&self) -> &SigningKey {
         &self.keypair
@@ -1507,50 +2853,122 @@ pub struct KeyPageInfo {
 // =============================================================================
 
 /// Ultra-simple API for rapid development (matching Dart SDK QuickStart)
-#[derive(Debug)]
+///
+/// Every method takes `&self` and keeps no state between calls, so one
+/// `QuickStart` (or clones of it, which share the client) can run several
+/// flows concurrently over the same connections.
+#[derive(Debug, Clone)]
 pub struct QuickStart {
-    /// The underlying client
-    client: AccumulateClient,
+    /// The underlying client, shared with clones
+    client: Arc<AccumulateClient>,
+    /// Receives the steps of long-running flows
+    progress: Arc<dyn ProgressReporter>,
+    /// Records the cost of the transactions each flow submits
+    credits: Option<CreditTracker>,
+    /// Slippage accepted when a credit purchase is re-priced
+    oracle_slippage_bps: u32,
 }
 
 impl QuickStart {
     /// Connect to local DevNet
     pub async fn devnet() -> Result<Self, JsonRpcError> {
         let v2_url = Url::parse(DEVNET_V2).map_err(|e| {
-            JsonRpcError::General(anyhow::anyhow!("Invalid URL: {}", e))
+            JsonRpcError::General(format!("Invalid URL: {}", e))
         })?;
         let v3_url = Url::parse(DEVNET_V3).map_err(|e| {
-            JsonRpcError::General(anyhow::anyhow!("Invalid URL: {}", e))
+            JsonRpcError::General(format!("Invalid URL: {}", e))
         })?;
 
         let client = AccumulateClient::new_with_options(v2_url, v3_url, AccOptions::default()).await?;
-        Ok(Self { client })
+        Ok(Self::from_client(Arc::new(client)))
     }
 
     /// Connect to Kermit testnet
     pub async fn kermit() -> Result<Self, JsonRpcError> {
         let v2_url = Url::parse(KERMIT_V2).map_err(|e| {
-            JsonRpcError::General(anyhow::anyhow!("Invalid URL: {}", e))
+            JsonRpcError::General(format!("Invalid URL: {}", e))
         })?;
         let v3_url = Url::parse(KERMIT_V3).map_err(|e| {
-            JsonRpcError::General(anyhow::anyhow!("Invalid URL: {}", e))
+            JsonRpcError::General(format!("Invalid URL: {}", e))
         })?;
 
         let client = AccumulateClient::new_with_options(v2_url, v3_url, AccOptions::default()).await?;
-        Ok(Self { client })
+        Ok(Self::from_client(Arc::new(client)))
     }
 
     /// Connect to custom endpoints
     pub async fn custom(v2_endpoint: &str, v3_endpoint: &str) -> Result<Self, JsonRpcError> {
         let v2_url = Url::parse(v2_endpoint).map_err(|e| {
-            JsonRpcError::General(anyhow::anyhow!("Invalid V2 URL: {}", e))
+            JsonRpcError::General(format!("Invalid V2 URL: {}", e))
         })?;
         let v3_url = Url::parse(v3_endpoint).map_err(|e| {
-            JsonRpcError::General(anyhow::anyhow!("Invalid V3 URL: {}", e))
+            JsonRpcError::General(format!("Invalid V3 URL: {}", e))
         })?;
 
         let client = AccumulateClient::new_with_options(v2_url, v3_url, AccOptions::default()).await?;
-        Ok(Self { client })
+        Ok(Self::from_client(Arc::new(client)))
+    }
+
+    /// Run flows over an existing client, sharing its connections
+    pub fn from_client(client: Arc<AccumulateClient>) -> Self {
+        Self {
+            client,
+            progress: Arc::new(NoProgress),
+            credits: None,
+            oracle_slippage_bps: DEFAULT_ORACLE_SLIPPAGE_BPS,
+        }
+    }
+
+    /// Report the steps of `fund_wallet` and `setup_adi` to `reporter`
+    ///
+    /// Without a reporter the flows run silently.
+    #[must_use]
+    pub fn with_progress(mut self, reporter: Arc<dyn ProgressReporter>) -> Self {
+        self.progress = reporter;
+        self
+    }
+
+    /// The progress reporter
+    pub fn progress(&self) -> &Arc<dyn ProgressReporter> {
+        &self.progress
+    }
+
+    /// Record the credit cost of every transaction the flows submit in
+    /// `tracker`, each under the name of the method that submitted it
+    #[must_use]
+    pub fn with_credit_tracker(mut self, tracker: CreditTracker) -> Self {
+        self.credits = Some(tracker);
+        self
+    }
+
+    /// The credit tracker, if one is set
+    #[must_use]
+    pub const fn credit_tracker(&self) -> Option<&CreditTracker> {
+        self.credits.as_ref()
+    }
+
+    /// Accept up to `bps` basis points more ACME when a credit purchase is
+    /// re-priced because the oracle moved (default
+    /// [`DEFAULT_ORACLE_SLIPPAGE_BPS`])
+    #[must_use]
+    pub const fn with_oracle_slippage_bps(mut self, bps: u32) -> Self {
+        self.oracle_slippage_bps = bps;
+        self
+    }
+
+    /// A purchase of `credits` for `recipient` at `oracle`, with this
+    /// instance's slippage bound
+    fn credit_purchase(&self, recipient: &str, credits: u64, oracle: u64) -> CreditPurchase {
+        CreditPurchase::new(recipient, credits, oracle).with_max_slippage_bps(self.oracle_slippage_bps)
+    }
+
+    /// A signer for `flow`, recording costs in the credit tracker if set
+    fn signer(&self, keypair: &SigningKey, signer_url: &str, flow: &str) -> SmartSigner<'_> {
+        let signer = SmartSigner::new(&self.client, keypair.clone(), signer_url);
+        match &self.credits {
+            Some(tracker) => signer.with_credit_tracker(tracker.clone(), flow),
+            None => signer,
+        }
     }
 
     /// Get the underlying client
@@ -1558,54 +2976,67 @@ impl QuickStart {
         &self.client
     }
 
+    /// Get a shared handle to the underlying client
+    pub fn shared_client(&self) -> Arc<AccumulateClient> {
+        Arc::clone(&self.client)
+    }
+
     /// Create a new wallet with lite identity and token account
     pub fn create_wallet(&self) -> Wallet {
-        let keypair = AccumulateClient::generate_keypair();
-        let public_key = keypair.verifying_key().to_bytes();
-
-        // Derive lite identity URL
-        let lite_identity = derive_lite_identity_url(&public_key);
-        let lite_token_account = format!("{}/ACME", lite_identity);
+        Wallet::generate()
+    }
 
-        Wallet {
-            lite_identity,
-            lite_token_account,
-            keypair,
-        }
+    /// Wallet stored under `alias` in `keystore`, created on first use
+    ///
+    /// Unlike [`QuickStart::create_wallet`], later runs get the same lite
+    /// account back, so funding and ADI setup need not start over.
+    pub fn wallet_from_keystore<S: crate::storage::KvStore>(
+        &self,
+        keystore: &mut crate::crypto::keystore::Keystore<S>,
+        alias: &str,
+        password: &str,
+    ) -> Result<Wallet, crate::crypto::keystore::KeystoreError> {
+        keystore.load_or_create_wallet(alias, password)
     }
 
     /// Fund wallet from faucet (multiple requests) using V3 API
+    ///
+    /// Rate-limited or unreachable faucet requests are retried after the
+    /// delay the faucet asks for (2s otherwise); an empty faucet or a rejected
+    /// account stops funding with the typed [`FaucetError`](crate::faucet::FaucetError).
+    ///
+    /// Each faucet request is a step of the `fund_wallet` flow, followed by
+    /// a `confirm balance` step; see [`QuickStart::with_progress`]. An
+    /// unconfirmed balance fails that step but not the flow. Use
+    /// [`AccumulateClient::faucet_with`] to fund accounts outside `QuickStart`.
     pub async fn fund_wallet(&self, wallet: &Wallet, times: u32) -> Result<(), JsonRpcError> {
-        for i in 0..times {
-            let params = json!({"account": &wallet.lite_token_account});
-            match self.client.v3_client.call_v3::<Value>("faucet", params).await {
-                Ok(response) => {
-                    let txid = response.get("transactionHash")
-                        .or_else(|| response.get("txid"))
-                        .and_then(|v| v.as_str())
-                        .unwrap_or("submitted");
-                    println!("  Faucet {}/{}: {}", i + 1, times, txid);
-                }
-                Err(e) => {
-                    println!("  Faucet {}/{} failed: {}", i + 1, times, e);
-                }
-            }
-            if i < times - 1 {
-                tokio::time::sleep(Duration::from_secs(2)).await;
-            }
-        }
-
-        // Wait for faucet transactions to process
-        println!("  Waiting for faucet to process...");
-        tokio::time::sleep(Duration::from_secs(10)).await;
+        let options = FaucetOptions {
+            times,
+            ..FaucetOptions::default()
+        };
+        self.client
+            .faucet_reporting(&wallet.lite_token_account, options, self.progress.as_ref(), "fund_wallet")
+            .await?;
+        Ok(())
+    }
 
-        // Poll for balance to confirm account is available
-        let balance = poll_for_balance(&self.client, &wallet.lite_token_account, 30).await;
-        if balance.is_none() || balance == Some(0) {
-            println!("  Warning: Account balance not confirmed yet");
-        }
+    /// Fund wallet from faucet until it holds at least `min_acme` ACME
+    ///
+    /// Gives up after two minutes; see [`ensure_funded`] for the retry
+    /// schedule. Returns the balance in base units.
+    pub async fn ensure_funded(&self, wallet: &Wallet, min_acme: u64) -> Result<u64, JsonRpcError> {
+        self.ensure_funded_within(wallet, min_acme, Duration::from_secs(120)).await
+    }
 
-        Ok(())
+    /// Like [`QuickStart::ensure_funded`], with a custom deadline
+    pub async fn ensure_funded_within(
+        &self,
+        wallet: &Wallet,
+        min_acme: u64,
+        timeout: Duration,
+    ) -> Result<u64, JsonRpcError> {
+        let min_balance = min_acme.saturating_mul(ACME_BASE_UNITS);
+        ensure_funded(&self.client, &wallet.lite_token_account, min_balance, timeout).await
     }
 
     /// Get account balance (polls up to 30 times)
@@ -1615,12 +3046,7 @@ impl QuickStart {
 
     /// Get oracle price from network status
     pub async fn get_oracle_price(&self) -> Result<u64, JsonRpcError> {
-        let result: Value = self.client.v3_client.call_v3("network-status", json!({})).await?;
-
-        result.get("oracle")
-            .and_then(|o| o.get("price"))
-            .and_then(|p| p.as_u64())
-            .ok_or_else(|| JsonRpcError::General(anyhow::anyhow!("Oracle price not found")))
+        fetch_oracle_price(&self.client).await
     }
 
     /// Calculate ACME amount for desired credits
@@ -1630,6 +3056,9 @@ impl QuickStart {
     }
 
     /// Set up an ADI (handles all the complexity)
+    ///
+    /// Reports three steps of the `setup_adi` flow: reading the oracle price,
+    /// adding credits to the lite identity and creating the identity.
     pub async fn setup_adi(&self, wallet: &Wallet, adi_name: &str) -> Result<AdiInfo, JsonRpcError> {
         let adi_keypair = AccumulateClient::generate_keypair();
         let adi_public_key = adi_keypair.verifying_key().to_bytes();
@@ -1640,27 +3069,31 @@ impl QuickStart {
         let key_page_url = format!("{}/1", book_url);
 
         // First, add credits to lite identity
-        let oracle = self.get_oracle_price().await?;
-        let credits_amount = Self::calculate_credits_amount(1000, oracle);
+        let step = ProgressStep::new("setup_adi", "read oracle price").of(1, 3);
+        self.progress.started(&step);
+        let oracle = self.get_oracle_price().await.map_err(|e| {
+            self.progress.failed(&step, &e.to_string());
+            e
+        })?;
+        self.progress.completed(&step, &format!("oracle {}", oracle));
 
-        let mut signer = SmartSigner::new(&self.client, wallet.keypair.clone(), &wallet.lite_identity);
+        let mut signer = self.signer(&wallet.keypair, &wallet.lite_identity, "setup_adi");
 
         // Add credits to lite identity
-        let add_credits_body = TxBody::add_credits(
-            &wallet.lite_identity,
-            &credits_amount.to_string(),
-            oracle,
-        );
+        let purchase = self.credit_purchase(&wallet.lite_identity, 1000, oracle);
 
-        let result = signer.sign_submit_and_wait(
+        let step = ProgressStep::new("setup_adi", "add credits").of(2, 3).on(&wallet.lite_identity);
+        self.progress.started(&step);
+        let result = signer.buy_credits_and_wait(
             &wallet.lite_token_account,
-            &add_credits_body,
+            &purchase,
             Some("Add credits to lite identity"),
             30,
         ).await;
+        self.report_tx(&step, &result);
 
         if !result.success {
-            return Err(JsonRpcError::General(anyhow::anyhow!(
+            return Err(JsonRpcError::General(format!(
                 "Failed to add credits: {:?}", result.error
             )));
         }
@@ -1672,15 +3105,18 @@ impl QuickStart {
             &hex::encode(adi_key_hash),
         );
 
+        let step = ProgressStep::new("setup_adi", "create identity").of(3, 3).on(&identity_url);
+        self.progress.started(&step);
         let result = signer.sign_submit_and_wait(
             &wallet.lite_token_account,
             &create_adi_body,
             Some("Create ADI"),
             30,
         ).await;
+        self.report_tx(&step, &result);
 
         if !result.success {
-            return Err(JsonRpcError::General(anyhow::anyhow!(
+            return Err(JsonRpcError::General(format!(
                 "Failed to create ADI: {:?}", result.error
             )));
         }
@@ -1693,23 +3129,57 @@ impl QuickStart {
         })
     }
 
+    /// Report the outcome of a transaction step
+    fn report_tx(&self, step: &ProgressStep, result: &TxResult) {
+        if result.success {
+            self.progress.completed(step, result.txid.as_deref().unwrap_or_default());
+        } else {
+            self.progress.failed(step, result.error.as_deref().unwrap_or("transaction failed"));
+        }
+    }
+
     /// Buy credits for ADI key page (auto-fetches oracle)
     pub async fn buy_credits_for_adi(&self, wallet: &Wallet, adi: &AdiInfo, credits: u64) -> Result<TxResult, JsonRpcError> {
         let oracle = self.get_oracle_price().await?;
-        let amount = Self::calculate_credits_amount(credits, oracle);
 
-        let mut signer = SmartSigner::new(&self.client, wallet.keypair.clone(), &wallet.lite_identity);
+        let mut signer = self.signer(&wallet.keypair, &wallet.lite_identity, "buy_credits_for_adi");
 
-        let body = TxBody::add_credits(&adi.key_page_url, &amount.to_string(), oracle);
+        let purchase = self.credit_purchase(&adi.key_page_url, credits, oracle);
 
-        Ok(signer.sign_submit_and_wait(
+        Ok(signer.buy_credits_and_wait(
             &wallet.lite_token_account,
-            &body,
+            &purchase,
             Some("Buy credits for ADI"),
             30,
         ).await)
     }
 
+    /// Buy credits for an ADI key page, paid from one of the ADI's token accounts
+    ///
+    /// The token account `{adi}/{account_name}` funds the purchase and the key
+    /// page signs for it, so no lite account is involved. The page needs
+    /// enough credits to pay for this transaction itself.
+    pub async fn buy_credits_from_adi_account(
+        &self,
+        adi: &AdiInfo,
+        account_name: &str,
+        credits: u64,
+    ) -> Result<TxResult, JsonRpcError> {
+        let oracle = self.get_oracle_price().await?;
+        let token_account = format!("{}/{}", adi.url, account_name);
+
+        let mut signer = self.signer(&adi.keypair, &adi.key_page_url, "buy_credits_from_adi_account");
+
+        let purchase = self.credit_purchase(&adi.key_page_url, credits, oracle);
+
+        Ok(signer.buy_credits_and_wait(
+            &token_account,
+            &purchase,
+            Some("Buy credits from ADI token account"),
+            30,
+        ).await)
+    }
+
     /// Get key page information
     pub async fn get_key_page_info(&self, key_page_url: &str) -> Option<KeyPageInfo> {
         let manager = KeyManager::new(&self.client, key_page_url);
@@ -1727,7 +3197,7 @@ impl QuickStart {
     /// Create a token account under an ADI
     pub async fn create_token_account(&self, adi: &AdiInfo, account_name: &str) -> Result<TxResult, JsonRpcError> {
         let account_url = format!("{}/{}", adi.url, account_name);
-        let mut signer = SmartSigner::new(&self.client, adi.keypair.clone(), &adi.key_page_url);
+        let mut signer = self.signer(&adi.keypair, &adi.key_page_url, "create_token_account");
 
         let body = TxBody::create_token_account(&account_url, "acc://ACME");
 
@@ -1742,7 +3212,7 @@ impl QuickStart {
     /// Create a data account under an ADI
     pub async fn create_data_account(&self, adi: &AdiInfo, account_name: &str) -> Result<TxResult, JsonRpcError> {
         let account_url = format!("{}/{}", adi.url, account_name);
-        let mut signer = SmartSigner::new(&self.client, adi.keypair.clone(), &adi.key_page_url);
+        let mut signer = self.signer(&adi.keypair, &adi.key_page_url, "create_data_account");
 
         let body = TxBody::create_data_account(&account_url);
 
@@ -1757,7 +3227,7 @@ impl QuickStart {
     /// Write data to a data account
     pub async fn write_data(&self, adi: &AdiInfo, account_name: &str, entries: &[&str]) -> Result<TxResult, JsonRpcError> {
         let account_url = format!("{}/{}", adi.url, account_name);
-        let mut signer = SmartSigner::new(&self.client, adi.keypair.clone(), &adi.key_page_url);
+        let mut signer = self.signer(&adi.keypair, &adi.key_page_url, "write_data");
 
         let body = TxBody::write_data(entries);
 
@@ -1771,13 +3241,30 @@ impl QuickStart {
 
     /// Add a key to the ADI's key page
     pub async fn add_key_to_adi(&self, adi: &AdiInfo, new_keypair: &SigningKey) -> Result<TxResult, JsonRpcError> {
-        let mut signer = SmartSigner::new(&self.client, adi.keypair.clone(), &adi.key_page_url);
+        let mut signer = self.signer(&adi.keypair, &adi.key_page_url, "add_key_to_adi");
         Ok(signer.add_key(&new_keypair.verifying_key().to_bytes()).await)
     }
 
+    /// Add a page holding `keypair` to the ADI's key book, signed with its
+    /// first page
+    ///
+    /// The new page is numbered after the book's existing pages; it needs
+    /// credits before it can pay for a transaction it initiates.
+    pub async fn add_key_page_to_adi(&self, adi: &AdiInfo, keypair: &SigningKey) -> Result<TxResult, JsonRpcError> {
+        let mut signer = self.signer(&adi.keypair, &adi.key_page_url, "add_key_page_to_adi");
+        let key_hash = sha256_hash(&keypair.verifying_key().to_bytes());
+        let body = TxBody::create_key_page(&[&key_hash]);
+        Ok(signer.sign_submit_and_wait(
+            &adi.key_book_url,
+            &body,
+            Some("Create key page"),
+            30,
+        ).await)
+    }
+
     /// Set multi-sig threshold for the ADI's key page
     pub async fn set_multi_sig_threshold(&self, adi: &AdiInfo, threshold: u64) -> Result<TxResult, JsonRpcError> {
-        let mut signer = SmartSigner::new(&self.client, adi.keypair.clone(), &adi.key_page_url);
+        let mut signer = self.signer(&adi.keypair, &adi.key_page_url, "set_multi_sig_threshold");
         Ok(signer.set_threshold(threshold).await)
     }
 
@@ -1798,6 +3285,9 @@ impl QuickStart {
 /// - Checksum: SHA256(keyHashHex)[28..32] as hex (8 chars)
 ///
 /// Note: Lite addresses do NOT have .acme suffix!
+///
+/// This is the Ed25519 derivation; for BTC, ETH and other key types use
+/// [`crate::derive::lite_identity_for_key`].
 pub fn derive_lite_identity_url(public_key: &[u8; 32]) -> String {
     // Get first 20 bytes of SHA256(publicKey)
     let hash = sha256_hash(public_key);
@@ -1836,7 +3326,7 @@ pub fn sha256_hash(data: &[u8]) -> [u8; 32] {
 /// - [1] = signature result with txID like acc://hash@account
 ///
 /// We prefer the second entry (signature tx) which doesn't have path suffix.
-fn extract_txid(response: &Value) -> Option<String> {
+pub(crate) fn extract_txid(response: &Value) -> Option<String> {
     // Try array format first - this is the V3 format
     if let Some(arr) = response.as_array() {
         // Prefer second entry (signature tx) if available
@@ -1867,6 +3357,7 @@ fn extract_txid(response: &Value) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use ed25519_dalek::Signer;
 
     #[test]
     fn test_derive_lite_identity_url() {
@@ -1904,6 +3395,52 @@ mod tests {
         assert_eq!(body["url"], "acc://test.acme");
     }
 
+    #[test]
+    fn test_key_page_find_key_index() {
+        let first = [1u8; 32];
+        let second = [2u8; 32];
+        let state = KeyPageState::from_account(
+            &json!({
+                "type": "keyPage",
+                "version": 3,
+                "keys": [
+                    {"publicKeyHash": hex::encode(sha256_hash(&first))},
+                    {"publicKey": hex::encode(second).to_uppercase()}
+                ]
+            }),
+            "acc://alice.acme/book/1",
+        );
+        assert_eq!(state.url, "acc://alice.acme/book/1");
+        assert_eq!(state.version, 3);
+        assert_eq!(state.find_key_index(&first), Some(0));
+        assert_eq!(state.find_key_index(&second), Some(1));
+        assert_eq!(state.find_key_index(&[3u8; 32]), None);
+    }
+
+    #[test]
+    fn test_replay_page_history() {
+        let (a, b, c) = ([1u8; 32], [2u8; 32], [3u8; 32]);
+        let tx = |body: Value| json!({"header": {"principal": "acc://alice.acme/book/1"}, "body": body});
+        let transactions = vec![
+            ("01".to_string(), tx(TxBody::create_identity("acc://alice.acme", "acc://alice.acme/book", &hex::encode(a)))),
+            ("02".to_string(), tx(TxBody::add_credits("acc://alice.acme/book/1", "100", 5000))),
+            ("03".to_string(), tx(TxBody::update_key_page_add_key(&b))),
+            ("04".to_string(), tx(TxBody::update_key_page_set_threshold(2))),
+            ("05".to_string(), tx(TxBody::update_key_page_update_key(&a, &c))),
+            ("06".to_string(), tx(TxBody::update_key_page_remove_key(&b))),
+        ];
+        let history = replay_page_history(&transactions);
+        let hashes = |v: &KeyPageVersion| v.keys.iter().map(|k| k.key_hash.clone()).collect::<Vec<_>>();
+
+        assert_eq!(history.len(), 5);
+        assert_eq!((history[0].version, hashes(&history[0])), (1, vec![hex::encode(a)]));
+        assert_eq!(history[1].transaction_hash, "03");
+        assert_eq!(hashes(&history[1]), vec![hex::encode(a), hex::encode(b)]);
+        assert_eq!(history[2].accept_threshold, 2);
+        assert_eq!(hashes(&history[3]), vec![hex::encode(c), hex::encode(b)]);
+        assert_eq!((history[4].version, hashes(&history[4])), (5, vec![hex::encode(c)]));
+    }
+
     #[test]
     fn test_wallet_creation() {
         let keypair = AccumulateClient::generate_keypair();
@@ -1914,6 +3451,586 @@ mod tests {
         assert!(lite_identity.starts_with("acc://"));
         assert!(lite_token_account.contains("/ACME"));
     }
+
+    #[test]
+    fn test_strict_body_check() {
+        let body = TxBody::lock_account(1000);
+        assert_eq!(TxBody::strict(body.clone()).unwrap(), body);
+
+        // Legacy casing decodes through the alias, but is not what TxBody emits
+        let err = TxBody::strict(json!({"type": "lockAccount", "Height": 1000}))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Height"), "{}", err);
+
+        // Misspelled key
+        let err = TxBody::strict(json!({"type": "lockAccount", "height": 1000, "heigth": 1}))
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("heigth"), "{}", err);
+
+        // Ordinary decoding ignores fields added by newer nodes; strict does not
+        let newer = json!({"type": "lockAccount", "height": 1000, "newField": 1});
+        assert!(serde_json::from_value::<TransactionBody>(newer.clone()).is_ok());
+        assert!(TxBody::strict(newer).is_err());
+
+        // Both casings: the field is given twice
+        assert!(TxBody::strict(json!({"type": "lockAccount", "Height": 1000, "height": 1000})).is_err());
+
+        // Unknown type tag
+        assert!(TxBody::strict(json!({"type": "notATransaction"})).is_err());
+    }
+
+    #[test]
+    fn test_strict_accepts_every_builder() {
+        let hash = [7u8; 32];
+        let hash_hex = hex::encode(hash);
+        let bodies = [
+            TxBody::add_credits("acc://alice.acme/tokens", "100", 5),
+            TxBody::create_identity("acc://alice.acme", "acc://alice.acme/book", &hash_hex),
+            TxBody::create_token_account("acc://alice.acme/tokens", "acc://ACME"),
+            TxBody::create_data_account("acc://alice.acme/data"),
+            TxBody::create_token("acc://alice.acme/token", "ALC", 8, Some("1000")),
+            TxBody::create_token("acc://alice.acme/token", "ALC", 8, None),
+            TxBody::send_tokens_single("acc://bob.acme/tokens", "100"),
+            TxBody::send_tokens_multi(&[("acc://bob.acme/tokens", "1"), ("acc://carol.acme/tokens", "2")]),
+            TxBody::issue_tokens_single("acc://bob.acme/tokens", "100"),
+            TxBody::write_data(&["hello"]),
+            TxBody::write_data_hex(&["0102"]),
+            TxBody::write_data_to_hex("acc://bob.acme/data", &["0102"]),
+            TxBody::create_key_page(&[&hash]),
+            TxBody::create_key_book("acc://alice.acme/book2", &hash_hex),
+            TxBody::update_key_page_add_key(&hash),
+            TxBody::update_key_page_remove_key(&hash),
+            TxBody::update_key_page_set_threshold(2),
+            TxBody::update_key_page_update_key(&hash, &[8u8; 32]),
+            TxBody::update_key_page_set_reject_threshold(1),
+            TxBody::update_key_page_set_response_threshold(1),
+            TxBody::burn_tokens("100"),
+            TxBody::transfer_credits("acc://bob.acme/book/1", 100),
+            TxBody::burn_credits(100),
+            TxBody::update_key(&hash_hex),
+            TxBody::lock_account(1000),
+            TxBody::update_account_auth(&json!([{"type": "enable", "authority": "acc://alice.acme/book"}])),
+            TxBody::write_data_to("acc://bob.acme/data", &["hello"]),
+            TxBody::remote_transaction(&hash_hex),
+        ];
+        let mut fails = Vec::new();
+        for body in bodies {
+            if let Err(err) = TxBody::strict(body.clone()) {
+                fails.push(format!("{body}: {err}"));
+            }
+        }
+        assert!(fails.is_empty(), "{}", fails.join("\n"));
+    }
+
+    #[test]
+    fn test_marshal_rejects_malformed_hex() {
+        let body = TxBody::create_key_book("acc://alice.acme/book2", "not-hex");
+        let err = marshal_body_to_binary(&body).unwrap_err().to_string();
+        assert!(err.contains("publicKeyHash"), "{}", err);
+
+        let body = TxBody::write_data_hex(&["48656c6c6f", "zz"]);
+        let err = extract_data_entries(&body).unwrap_err().to_string();
+        assert!(err.contains("entry.data[1]"), "{}", err);
+    }
+
+    #[test]
+    fn test_amount_bodies_match_string_bodies() {
+        let amount = Amount::from_acme("1.5").unwrap();
+        assert_eq!(
+            TxBody::send_tokens("acc://bob.acme/tokens", amount),
+            TxBody::send_tokens_single("acc://bob.acme/tokens", "150000000")
+        );
+        assert_eq!(
+            TxBody::send_tokens_to(&[("acc://bob.acme/tokens", amount), ("acc://carol.acme/tokens", Amount::acme(2))]),
+            TxBody::send_tokens_multi(&[("acc://bob.acme/tokens", "150000000"), ("acc://carol.acme/tokens", "200000000")])
+        );
+        assert_eq!(
+            TxBody::add_credits_acme("acc://alice.acme/book/1", amount, 5000),
+            TxBody::add_credits("acc://alice.acme/book/1", "150000000", 5000)
+        );
+    }
+
+    #[test]
+    fn test_marshal_rejects_unencodable_bodies() {
+        // Amounts that do not fit are errors, not zero
+        let err = marshal_body_to_binary(&TxBody::burn_tokens("12abc")).unwrap_err().to_string();
+        assert!(err.contains("amount"), "{}", err);
+        let body = TxBody::send_tokens_multi(&[("acc://bob.acme/tokens", "1"), ("acc://carol.acme/tokens", "-1")]);
+        let err = marshal_body_to_binary(&body).unwrap_err().to_string();
+        assert!(err.contains("to[1].amount"), "{}", err);
+
+        // Numeric amounts encode like their string form
+        assert_eq!(
+            marshal_body_to_binary(&json!({"type": "burnTokens", "amount": 100_000_000})).unwrap(),
+            marshal_body_to_binary(&TxBody::burn_tokens("100000000")).unwrap()
+        );
+
+        // No more type-only fallbacks
+        assert!(marshal_body_to_binary(&json!({"type": "directoryAnchor"})).is_err());
+        assert!(marshal_body_to_binary(&json!({"type": "notATransaction"})).is_err());
+        let body = TxBody::update_key_page(&json!([{"type": "updateAllowed", "allow": ["notATransaction"]}]));
+        assert!(marshal_body_to_binary(&body).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sign_remote_uses_referenced_hash() {
+        let client = AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap();
+        let signer = SmartSigner::new(&client, SigningKey::from_bytes(&[7u8; 32]), "acc://alice.acme/book/1");
+
+        let tx_hash = [0x5au8; 32];
+        let envelope = signer.sign_remote("acc://alice.acme/tokens", &tx_hash).unwrap();
+
+        assert_eq!(envelope["transaction"][0]["body"]["type"], "signPending");
+        assert_eq!(envelope["signatures"][0]["transactionHash"], hex::encode(tx_hash));
+
+        let bad = TxBody::remote_transaction("abcd");
+        assert!(signer.sign("acc://alice.acme/tokens", &bad, None).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_signature_options_are_signed() {
+        let client = AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap();
+        let signer = SmartSigner::new(&client, SigningKey::from_bytes(&[3u8; 32]), "acc://alice.acme/book/1");
+        let signature = SignatureOptions {
+            memo: Some("not this one".to_string()),
+            data: Some(vec![0xca, 0xfe]),
+            vote: Some(crate::generated::enums::VoteType::Reject),
+        };
+
+        let envelope = signer
+            .sign_remote_with_options("acc://alice.acme/tokens", &[0x11u8; 32], &signature)
+            .unwrap();
+        let sig = &envelope["signatures"][0];
+        assert_eq!(sig["vote"], "reject");
+        assert_eq!(sig["memo"], "not this one");
+        assert_eq!(sig["data"], "cafe");
+
+        // The initiator is the metadata hash including memo, data and vote
+        let report = crate::protocol::explain_envelope(&envelope);
+        assert!(report.contains("matches initiator"), "{}", report);
+        assert!(report.contains(" valid\n"), "{}", report);
+
+        // Accept is the default vote and is left out
+        let accept = SignatureOptions {
+            vote: Some(crate::generated::enums::VoteType::Accept),
+            ..Default::default()
+        };
+        let sig = signer.sign_hash_with_options(&[0x11u8; 32], &accept).unwrap();
+        assert!(sig.get("vote").is_none());
+        assert_eq!(sig, {
+            let mut plain = signer.sign_hash(&[0x11u8; 32]).unwrap();
+            plain["timestamp"] = sig["timestamp"].clone();
+            plain["signature"] = sig["signature"].clone();
+            plain
+        });
+    }
+
+    #[tokio::test]
+    async fn test_preflight_verifies_signed_envelopes() {
+        let client = AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap();
+        let signer = SmartSigner::new(&client, SigningKey::from_bytes(&[4u8; 32]), "acc://alice.acme/book/1")
+            .with_preflight(true);
+        assert!(signer.preflight());
+
+        let send = TxBody::send_tokens_single("acc://bob.acme/tokens", "100");
+        signer.sign("acc://alice.acme/tokens", &send, Some("rent")).unwrap();
+        signer.sign("acc://alice.acme/data", &TxBody::write_data(&["hello"]), None).unwrap();
+
+        let options = HeaderOptions {
+            memo: Some("later".to_string()),
+            metadata: Some(vec![1, 2, 3]),
+            expire: Some(crate::generated::header::ExpireOptions { at_time: Some(1_900_000_000) }),
+            authorities: Some(vec!["acc://bob.acme/book".to_string()]),
+            ..Default::default()
+        };
+        let signature = SignatureOptions {
+            memo: Some("ok".to_string()),
+            ..Default::default()
+        };
+        signer
+            .sign_with_signature_options("acc://alice.acme/tokens", &send, &options, &signature)
+            .unwrap();
+        signer.sign_remote("acc://alice.acme/tokens", &[0x22u8; 32]).unwrap();
+        signer.sign_hash(&[0x22u8; 32]).unwrap();
+
+        // A signature from another key is caught
+        let other = SigningKey::from_bytes(&[5u8; 32]).sign(&[7u8; 32]);
+        let err = signer.verify_own_signature(&[7u8; 32], &other.to_bytes()).unwrap_err();
+        assert!(err.to_string().contains("does not verify"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_delegated_signatures_nest() {
+        use crate::codec::signing::create_signing_preimage;
+        use crate::protocol::hash_debug::{diff_transaction_hash, ReferenceHashes};
+
+        let client = AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap();
+        let key = SigningKey::from_bytes(&[4u8; 32]);
+        let signer = SmartSigner::new(&client, key.clone(), "acc://bob.acme/book/1")
+            .with_delegators(&["acc://alice.acme/book/1", "acc://corp.acme/book/1"]);
+        let envelope = signer
+            .sign("acc://corp.acme/tokens", &TxBody::burn_credits(100), Some("delegated"))
+            .unwrap();
+
+        let outer = &envelope["signatures"][0];
+        assert_eq!(outer["type"], "delegated");
+        assert_eq!(outer["delegator"], "acc://corp.acme/book/1");
+        assert_eq!(outer["signature"]["delegator"], "acc://alice.acme/book/1");
+        let inner = &outer["signature"]["signature"];
+        assert_eq!(inner["type"], "ed25519");
+        assert_eq!(inner["signer"], "acc://bob.acme/book/1");
+
+        // The initiator is the outermost metadata hash, and the key signs it
+        let transaction = &envelope["transaction"][0];
+        let report = diff_transaction_hash(
+            transaction,
+            Some(outer),
+            &ReferenceHashes {
+                signature_metadata: transaction["header"]["initiator"].as_str().map(str::to_string),
+                ..Default::default()
+            },
+        );
+        assert!(report.is_match(), "{report}");
+        let md_hash: [u8; 32] = hex::decode(transaction["header"]["initiator"].as_str().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let tx_hash: [u8; 32] = hex::decode(inner["transactionHash"].as_str().unwrap())
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let signature = hex::decode(inner["signature"].as_str().unwrap()).unwrap();
+        let signature = ed25519_dalek::Signature::from_slice(&signature).unwrap();
+        key.verifying_key()
+            .verify_strict(&create_signing_preimage(&md_hash, &tx_hash), &signature)
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_audit_sink_records_signed_envelopes() {
+        use crate::audit::{AuditError, KvAuditSink};
+        use crate::storage::MemoryKvStore;
+
+        #[derive(Debug)]
+        struct Refusing;
+        impl AuditSink for Refusing {
+            fn record(&self, _: &AuditRecord) -> Result<(), AuditError> {
+                Err(AuditError::Sink("disk full".to_string()))
+            }
+        }
+
+        let client = AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap();
+        let sink = Arc::new(KvAuditSink::open(MemoryKvStore::new()).unwrap());
+        let signer = SmartSigner::new(&client, SigningKey::from_bytes(&[4u8; 32]), "acc://alice.acme/book/1")
+            .with_audit_sink(sink.clone());
+
+        let envelope = signer
+            .sign("acc://alice.acme/tokens", &TxBody::burn_credits(100), None)
+            .unwrap();
+        signer.sign_hash(&[0x22u8; 32]).unwrap();
+
+        let entries = sink.entries().unwrap();
+        assert_eq!(sink.verify().unwrap(), 2);
+        let AuditRecord::Signed(record) = &entries[0].record else {
+            unreachable!("expected a signed record")
+        };
+        assert_eq!(record.principal.as_deref(), Some("acc://alice.acme/tokens"));
+        assert_eq!(
+            Some(record.transaction_hash.as_str()),
+            envelope["signatures"][0]["transactionHash"].as_str()
+        );
+        assert_eq!(record.canonical, crate::canonjson::canonicalize(&envelope).into_bytes());
+
+        let result = signer.audit_outcome(&envelope, TxResult::ok("acc://aa@alice.acme".to_string(), json!({})));
+        assert!(result.success && result.error.is_none());
+        assert!(matches!(
+            &sink.entries().unwrap()[2].record,
+            AuditRecord::Finalized(status) if status.delivered && status.envelope_hash == record.envelope_hash
+        ));
+
+        let refusing = SmartSigner::new(&client, SigningKey::from_bytes(&[4u8; 32]), "acc://alice.acme/book/1")
+            .with_audit_sink(Arc::new(Refusing));
+        let err = refusing.sign_hash(&[0x22u8; 32]).unwrap_err();
+        assert!(err.to_string().contains("disk full"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_sign_sponsored_assembles_both_signatures() {
+        let client = AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap();
+        let sponsor = SmartSigner::new(&client, SigningKey::from_bytes(&[1u8; 32]), "acc://sponsor.acme/book/1");
+        let owner = SmartSigner::new(&client, SigningKey::from_bytes(&[2u8; 32]), "acc://alice.acme/book/1");
+        assert_eq!(sponsor.authority(), "acc://sponsor.acme/book");
+
+        let body = TxBody::send_tokens_single("acc://bob.acme/tokens", "100");
+        let options = HeaderOptions {
+            memo: Some("sponsored".to_string()),
+            ..Default::default()
+        };
+        let envelope = sponsor
+            .sign_sponsored(&owner, "acc://alice.acme/tokens", &body, &options)
+            .unwrap();
+
+        let header = &envelope["transaction"][0]["header"];
+        assert_eq!(header["authorities"], json!(["acc://sponsor.acme/book"]));
+        assert_eq!(header["principal"], "acc://alice.acme/tokens");
+
+        let signatures = envelope["signatures"].as_array().unwrap();
+        assert_eq!(signatures.len(), 2);
+        // Normalized envelopes order signatures by signer
+        assert_eq!(signatures[0]["signer"], "acc://alice.acme/book/1");
+        assert_eq!(signatures[1]["signer"], "acc://sponsor.acme/book/1");
+        assert_eq!(signatures[0]["transactionHash"], signatures[1]["transactionHash"]);
+
+        // The sponsor is the initiator, and both signatures verify
+        let report = crate::protocol::explain_envelope(&envelope);
+        assert!(report.contains("matches initiator"), "{}", report);
+        assert_eq!(report.matches(" valid\n").count(), 2, "{}", report);
+        assert!(!report.contains("MISMATCH"), "{}", report);
+    }
+
+    #[tokio::test]
+    async fn test_quickstart_shares_client() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<QuickStart>();
+
+        let client = Arc::new(
+            AccumulateClient::new_with_options(
+                url::Url::parse("http://localhost:26660/v2").unwrap(),
+                url::Url::parse("http://localhost:26661/v3").unwrap(),
+                crate::AccOptions::default(),
+            )
+            .await
+            .unwrap(),
+        );
+        let first = QuickStart::from_client(Arc::clone(&client));
+        let second = first.clone();
+        assert!(Arc::ptr_eq(&first.shared_client(), &client));
+        assert!(std::ptr::eq(first.client(), second.client()));
+
+        // Flows borrow the QuickStart immutably, so they can run side by side
+        let (a, b) = tokio::join!(
+            first.get_key_page_info("acc://alice.acme/book/1"),
+            first.get_key_page_info("acc://bob.acme/book/1")
+        );
+        assert!(a.is_none() && b.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_resume_wait_after_restart() {
+        let network = crate::simulator::SimulatedNetwork::new();
+        let client = network.client().await.unwrap();
+        let account = format!("{}/ACME", derive_lite_identity_url(&[4u8; 32]));
+        let txid = network.fund(&account, ACME_BASE_UNITS).unwrap();
+        let poll = PollOptions {
+            max_attempts: 3,
+            interval: Some(Duration::from_millis(1)),
+            timeout: None,
+        };
+
+        // A persisted result survives the restart
+        let persisted = serde_json::to_string(&TxResult::timed_out(txid.clone(), json!({}))).unwrap();
+        let restored: TxResult = serde_json::from_str(&persisted).unwrap();
+        assert!(restored.is_timeout());
+
+        let result = client.resume_wait(restored.txid.as_deref().unwrap(), poll).await;
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(result.txid.as_deref(), Some(txid.as_str()));
+        assert!(result.response.unwrap().get("status").is_some());
+        assert!(!TxResult::err("Submit failed".to_string()).is_timeout());
+
+        // Never submitted: gives up, and can be resumed again later
+        let unknown = format!("acc://{}@{}", "00".repeat(32), account);
+        let result = client.resume_wait(&unknown, poll).await;
+        assert!(result.is_timeout());
+        assert_eq!(result.txid, Some(unknown));
+    }
+
+    #[tokio::test]
+    async fn test_sign_with_secondary_key_page() {
+        let harness = crate::mock::TestHarness::new().await.unwrap();
+        let quick = harness.quick();
+        let (wallet, adi) = harness.adi("pages").await.unwrap();
+        let second = AccumulateClient::generate_keypair();
+        let result = quick.add_key_page_to_adi(&adi, &second).await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+
+        let pages = quick.client().key_book_pages(&adi.key_book_url).await.unwrap();
+        assert_eq!(pages.iter().map(|page| page.index).collect::<Vec<_>>(), [1, 2]);
+        let page_url = key_page_url(&adi.key_book_url, 2);
+        assert_eq!(pages[1].state.url, page_url);
+        assert_eq!(pages[1].state.accept_threshold, 1);
+        assert!(pages[1].state.find_key_index(&second.verifying_key().to_bytes()).is_some());
+
+        let oracle = quick.get_oracle_price().await.unwrap();
+        let amount = QuickStart::calculate_credits_amount(1_000, oracle);
+        let mut lite = SmartSigner::new(quick.client(), wallet.keypair().clone(), &wallet.lite_identity);
+        let body = TxBody::add_credits(&page_url, &amount.to_string(), oracle);
+        assert!(lite.sign_submit_and_wait(&wallet.lite_token_account, &body, None, 30).await.success);
+
+        // Page 2 signs for the identity and updates itself
+        let mut signer = SmartSigner::for_page(quick.client(), second, &adi.key_book_url, 2);
+        assert_eq!(signer.signer_url(), page_url);
+        let body = TxBody::create_data_account(&format!("{}/data", adi.url));
+        let result = signer.sign_submit_and_wait(&adi.url, &body, None, 30).await;
+        assert!(result.success, "{:?}", result.error);
+        let result = signer.add_key(&AccumulateClient::generate_keypair().verifying_key().to_bytes()).await;
+        assert!(result.success, "{:?}", result.error);
+
+        // A signer moved to page 2 picks up its version
+        let mut first = SmartSigner::new(quick.client(), adi.keypair().clone(), &adi.key_page_url);
+        assert_eq!(first.refresh_version().await.unwrap(), 1);
+        assert_eq!(first.switch_page(&page_url).await.unwrap(), 2);
+        assert_eq!(first.signer_url(), page_url);
+    }
+
+    #[tokio::test]
+    async fn test_credit_purchase_reprices_once_when_oracle_moves() {
+        let harness = crate::mock::TestHarness::new().await.unwrap();
+        let quick = harness.quick();
+        let (wallet, adi) = harness.adi("oracle").await.unwrap();
+        let quote = quick.get_oracle_price().await.unwrap();
+        let mut signer = SmartSigner::new(quick.client(), wallet.keypair().clone(), &wallet.lite_identity);
+        let credits = |url: &str| {
+            let url = url.to_string();
+            async move { quick.get_key_page_info(&url).await.unwrap().credits }
+        };
+        let before = credits(&adi.key_page_url).await;
+
+        // A higher oracle makes credits cheaper, so the retry goes through
+        harness.network().set_oracle(quote * 2);
+        let purchase = CreditPurchase::new(&adi.key_page_url, 500, quote);
+        let result = signer.buy_credits_and_wait(&wallet.lite_token_account, &purchase, None, 30).await;
+        assert!(result.success, "{:?}", result.error);
+        assert_eq!(credits(&adi.key_page_url).await - before, 500 * 100);
+
+        // A lower one costs more than the bound allows
+        harness.network().set_oracle(quote / 2);
+        let purchase = purchase.with_max_slippage_bps(500);
+        assert!(!purchase.within_slippage(quote / 2));
+        let result = signer.buy_credits_and_wait(&wallet.lite_token_account, &purchase, None, 30).await;
+        assert!(result.error.unwrap().contains("Oracle moved"));
+
+        assert!(is_stale_oracle_error("oracle price 1 does not match the network's 2"));
+        assert!(!is_stale_oracle_error("insufficient balance"));
+    }
+
+    #[tokio::test]
+    async fn test_sign_submit_and_wait_honors_confirmation() {
+        let harness = crate::mock::TestHarness::new().await.unwrap();
+        let (_, adi) = harness.adi("finality").await.unwrap();
+        let tracker = CreditTracker::new();
+        let mut signer = harness
+            .signer(&adi)
+            .with_credit_tracker(tracker.clone(), "writes")
+            .with_confirmation(ConfirmationPolicy::Submitted);
+        assert_eq!(signer.confirmation(), ConfirmationPolicy::Submitted);
+
+        // Accepted is enough, and nothing is charged for an unconfirmed write
+        let body = TxBody::create_data_account(&format!("{}/data", adi.url));
+        let result = signer.sign_submit_and_wait(&adi.url, &body, None, 30).await;
+        assert!(result.success, "{:?}", result.error);
+        assert!(tracker.charges().is_empty());
+        let outcome = harness.client().tx_tracker().track(result.txid.as_deref().unwrap()).await;
+        assert!(outcome.is_delivered());
+
+        let mut signer = signer.with_confirmation(ConfirmationPolicy::Delivered);
+        let body = TxBody::create_data_account(&format!("{}/more", adi.url));
+        assert!(signer.sign_submit_and_wait(&adi.url, &body, None, 30).await.success);
+        assert_eq!(tracker.charges().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_ensure_funded_honors_deadline() {
+        let client = AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap();
+        let account = "acc://unreachable.acme/ACME";
+
+        // Nothing to wait for
+        assert_eq!(ensure_funded(&client, account, 0, Duration::ZERO).await.unwrap(), 0);
+
+        // Unreachable node: one round, then give up at the deadline
+        let err = ensure_funded(&client, account, 1, Duration::ZERO).await.unwrap_err();
+        assert!(err.to_string().contains("wanted 1"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_setup_adi_reports_progress() {
+        use std::sync::Mutex;
+
+        #[derive(Debug, Default)]
+        struct Recorder(Mutex<Vec<String>>);
+        impl ProgressReporter for Recorder {
+            fn started(&self, step: &ProgressStep) {
+                self.0.lock().unwrap().push(format!("start {}", step));
+            }
+            fn failed(&self, step: &ProgressStep, _error: &str) {
+                self.0.lock().unwrap().push(format!("fail {}", step));
+            }
+        }
+
+        let client = AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap();
+        let recorder = Arc::new(Recorder::default());
+        let quick = QuickStart::from_client(Arc::new(client)).with_progress(recorder.clone());
+
+        // Unreachable node: the first step fails and ends the flow
+        assert!(quick.setup_adi(&Wallet::generate(), "progress").await.is_err());
+        assert_eq!(
+            *recorder.0.lock().unwrap(),
+            vec![
+                "start [setup_adi] read oracle price (1/3)".to_string(),
+                "fail [setup_adi] read oracle price (1/3)".to_string(),
+            ]
+        );
+    }
 }
 
 // =============================================================================
@@ -1959,6 +4076,7 @@ mod golden_marshal {
             ("transfer_credits", hex_bytes(TxBody::transfer_credits("acc://bob.acme", 100))),
             ("burn_credits", hex_bytes(TxBody::burn_credits(100))),
             ("lock_account", hex_bytes(TxBody::lock_account(1000))),
+            ("remote_transaction", hex_bytes(TxBody::remote_transaction(KH_HEX))),
         ]
     }
 
@@ -1983,6 +4101,56 @@ mod golden_marshal {
         }
     }
 
+    const A: &str = "6163633a2f2f612e61636d65"; // acc://a.acme
+    const B: &str = "6163633a2f2f622e61636d65"; // acc://b.acme
+
+    /// Field layouts with no `TxBody` builder, written out by hand from the
+    /// Go `MarshalBinary` field numbers rather than captured from this encoder.
+    #[test]
+    fn golden_go_layouts() {
+        let cases = [
+            (json!({"type": "acmeFaucet", "url": "acc://a.acme"}), format!("0107020c{A}")),
+            (json!({"type": "createLiteTokenAccount"}), "010b".to_string()),
+            (json!({"type": "activateProtocolVersion", "version": "v2Baikonur"}), "012f0206".to_string()),
+            (
+                json!({"type": "networkMaintenance", "operations": [{"type": "pendingTransactionGC", "account": "acc://a.acme"}]}),
+                format!("012e02100101020c{A}"),
+            ),
+            (json!({"type": "systemGenesis"}), "0160".to_string()),
+            (
+                json!({"type": "systemWriteData", "entry": {"type": "doublehash", "data": ["68656c6c6f"]}, "writeToState": true}),
+                "016302090103020568656c6c6f0301".to_string(),
+            ),
+            (
+                json!({"type": "sendTokens", "hash": "11".repeat(32), "meta": {"memo": "x"}, "to": [{"url": "acc://b.acme", "amount": "5"}]}),
+                format!("010302{}030c7b226d656d6f223a2278227d0411010c{B}020105", "11".repeat(32)),
+            ),
+            (
+                json!({"type": "createToken", "url": "acc://a.acme", "symbol": "T", "precision": 2, "properties": "acc://b.acme", "supplyLimit": "256", "authorities": ["acc://b.acme"]}),
+                format!("0108020c{A}0401540502060c{B}07020100090c{B}"),
+            ),
+            (
+                json!({"type": "createTokenAccount", "url": "acc://a.acme", "tokenUrl": "acc://b.acme", "authorities": ["acc://b.acme"]}),
+                format!("0102020c{A}030c{B}040c{B}"),
+            ),
+            (
+                json!({"type": "createDataAccount", "url": "acc://a.acme", "authorities": ["acc://b.acme"]}),
+                format!("0104020c{A}030c{B}"),
+            ),
+            (
+                json!({"type": "createKeyBook", "url": "acc://a.acme", "publicKeyHash": KH_HEX, "authorities": ["acc://b.acme"]}),
+                format!("010d020c{A}0320{KH_HEX}050c{B}"),
+            ),
+            (
+                json!({"type": "updateKeyPage", "operation": [{"type": "updateAllowed", "allow": ["sendTokens"], "deny": ["writeData"]}]}),
+                "010f0206010502030305".to_string(),
+            ),
+        ];
+        for (body, want) in cases {
+            assert_eq!(hex_bytes(body.clone()), want, "{body}");
+        }
+    }
+
     // Filled from `golden_capture` output (the pinned reference).
     const GOLDEN: &[(&str, &str)] = &[
         ("add_credits", "010e02106163633a2f2f616c6963652e61636d6503030f424004f403"),
@@ -2006,5 +4174,6 @@ mod golden_marshal {
         ("transfer_credits", "01120212010e6163633a2f2f626f622e61636d650264"),
         ("burn_credits", "01110264"),
         ("lock_account", "011002e807"),
+        ("remote_transaction", "01300200112233445566778899aabbccddeeff00112233445566778899aabbccddeeff"),
     ];
 }
@@ -1,10 +1,14 @@
-//! GENERATED BY Accumulate gen-sdk. DO NOT EDIT.
+//! JSON-RPC client for the V2 and V3 APIs
+//!
+//! Retries and endpoint failover live in [`crate::retry`].
 
 #![allow(missing_docs)]
 
 use reqwest::Client;
 use serde::de::DeserializeOwned;
 use serde_json::Value;
+use crate::retry::{check_status, is_idempotent, retry_after, EndpointHealth, Failure, RetryPolicy};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use thiserror::Error;
 use url::Url;
@@ -18,20 +22,49 @@ pub enum JsonRpcError {
     Json(#[from] serde_json::Error),
 
     #[error("JSON-RPC error: code={code}, message={message}")]
-    Rpc { code: i32, message: String },
+    Rpc {
+        code: i32,
+        message: String,
+        data: Option<Value>,
+    },
 
     #[error("Invalid URL: {0}")]
     InvalidUrl(#[from] url::ParseError),
 
     #[error("General error: {0}")]
-    General(#[from] anyhow::Error),
+    General(String),
+}
+
+impl JsonRpcError {
+    /// Whether the node answered that the account or record does not exist
+    pub(crate) fn is_not_found(&self) -> bool {
+        match self {
+            Self::Rpc { code, message, .. } => {
+                matches!(code, 404 | -33404) || message.to_ascii_lowercase().contains("not found")
+            }
+            _ => false,
+        }
+    }
 }
 
 /// JSON-RPC client for Accumulate API calls
+///
+/// Calls go to `base_url` while it is healthy. An endpoint that cannot be
+/// reached, times out or answers with a 5xx status is skipped for
+/// `cooldown` in favor of the next of `fallbacks`; when every endpoint is
+/// down, the one that failed longest ago is tried first. Clones share
+/// endpoint health.
 #[derive(Debug, Clone)]
 pub struct JsonRpcClient {
     pub base_url: Url,
     pub http: Client,
+    /// Retry policy applied to every call
+    pub retry: RetryPolicy,
+    /// Endpoints to fail over to, in order of preference
+    pub fallbacks: Vec<Url>,
+    /// How long an endpoint that failed is passed over
+    pub cooldown: Duration,
+    pub(crate) health: Arc<Mutex<Vec<EndpointHealth>>>,
 }
 
 impl JsonRpcClient {
@@ -39,12 +72,19 @@ impl JsonRpcClient {
     pub fn new(base_url: Url) -> Result<Self, JsonRpcError> {
         let http = Client::builder().timeout(Duration::from_secs(30)).build()?;
 
-        Ok(Self { base_url, http })
+        Self::with_client(base_url, http)
     }
 
     /// Create a new JSON-RPC client with custom HTTP client
     pub fn with_client(base_url: Url, http: Client) -> Result<Self, JsonRpcError> {
-        Ok(Self { base_url, http })
+        Ok(Self {
+            base_url,
+            http,
+            retry: RetryPolicy::default(),
+            fallbacks: Vec::new(),
+            cooldown: Duration::from_secs(30),
+            health: Arc::default(),
+        })
     }
 
     /// Make a generic JSON-RPC call
@@ -60,27 +100,39 @@ impl JsonRpcClient {
             "params": params
         });
 
-        let response = self
-            .http
-            .post(self.base_url.clone())
-            .json(&request_body)
-            .send()
+        let idempotent = is_idempotent(method);
+        let request_body = &request_body;
+        let result = self
+            .retrying(idempotent, || {
+                self.failover(idempotent, |url| async move {
+                    let response = self.http.post(url).json(request_body).send().await?;
+
+                    let status = response.status();
+                    let retry_after = retry_after(&response);
+                    let response_text = response.text().await?;
+                    let response_json = check_status(status, &response_text, retry_after)?;
+
+                    if let Some(error) = response_json.get("error") {
+                        let code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(-1) as i32;
+                        let message = error
+                            .get("message")
+                            .and_then(|m| m.as_str())
+                            .unwrap_or("Unknown error")
+                            .to_string();
+                        return Err(Failure {
+                            error: JsonRpcError::Rpc {
+                                code,
+                                message,
+                                data: error.get("data").cloned(),
+                            },
+                            retry_after,
+                        });
+                    }
+
+                    Ok(response_json.get("result").cloned().unwrap_or(Value::Null))
+                })
+            })
             .await?;
-
-        let response_text = response.text().await?;
-        let response_json: Value = serde_json::from_str(&response_text)?;
-
-        if let Some(error) = response_json.get("error") {
-            let code = error.get("code").and_then(|c| c.as_i64()).unwrap_or(-1) as i32;
-            let message = error
-                .get("message")
-                .and_then(|m| m.as_str())
-                .unwrap_or("Unknown error")
-                .to_string();
-            return Err(JsonRpcError::Rpc { code, message });
-        }
-
-        let result = response_json.get("result").cloned().unwrap_or(Value::Null);
         Ok(serde_json::from_value(result)?)
     }
 
@@ -90,17 +142,29 @@ impl JsonRpcClient {
         method_path: &str,
         payload: Option<Value>,
     ) -> Result<T, JsonRpcError> {
-        let url = self.base_url.join(method_path)?;
-
-        let request = match payload {
-            Some(body) => self.http.post(url).json(&body),
-            None => self.http.get(url),
-        };
-
-        let response = request.send().await?;
-        let response_text = response.text().await?;
-
-        Ok(serde_json::from_str(&response_text)?)
+        // Reject a bad path before it counts against an endpoint
+        self.base_url.join(method_path)?;
+        let idempotent = payload.is_none() || is_idempotent(method_path);
+        let payload = &payload;
+
+        let result = self
+            .retrying(idempotent, || {
+                self.failover(idempotent, |base| async move {
+                    let url = base.join(method_path)?;
+                    let request = match payload {
+                        Some(body) => self.http.post(url).json(body),
+                        None => self.http.get(url),
+                    };
+
+                    let response = request.send().await?;
+                    let status = response.status();
+                    let retry_after = retry_after(&response);
+                    let response_text = response.text().await?;
+                    check_status(status, &response_text, retry_after)
+                })
+            })
+            .await?;
+        Ok(serde_json::from_value(result)?)
     }
 
     /// Make a V3 API call
@@ -119,6 +183,7 @@ pub fn canonical_json(value: &Value) -> String {
 }
 
 #[cfg(test)]
+#[allow(clippy::unwrap_used)]
 mod tests {
     use super::*;
     use serde_json::json;
@@ -9,22 +9,32 @@
 
 // Import the main V2/V3 client as the primary AccumulateClient
 pub use crate::client::AccumulateClient;
+pub use crate::retry::RetryPolicy;
+pub use crate::acc_url::{AccUrl, AccUrlError};
 
 // ACME amount helpers
-pub use crate::amounts::{Amount, ACME_BASE_UNITS, ACME_PRECISION};
+pub use crate::amounts::{format_base_units, parse_base_units, Amount, TokenAmount, ACME_BASE_UNITS, ACME_PRECISION};
 
 /// Type alias for convenient access to AccumulateClient methods
 pub type Accumulate = AccumulateClient;
 pub use crate::codec::{
     TransactionCodec, TransactionEnvelope, TransactionSignature,
     TransactionBodyBuilder, TokenRecipient, KeySpec, BinaryReader, BinaryWriter,
-    AccumulateHash, UrlHash, canonical_json, sha256_bytes, to_canonical_string
+    AccumulateHash, UrlHash, canonical_json, sha256_bytes, to_canonical_string,
+    parse_hex_field, parse_hex_hash
+};
+/// Protocol hashes (`hashes::header_hash`, `hashes::body_hash`, `hashes::tx_hash`)
+pub use crate::codec::hashes;
+pub use crate::canonjson::{
+    canonicalize, canonicalize_jcs, dumps_canonical, escape_json_string, escape_json_utf16, CanonicalMode, JcsError,
 };
-pub use crate::canonjson::{dumps_canonical, canonicalize};
 pub use crate::crypto::ed25519::{Ed25519Signer, verify, verify_prehashed, verify_signature, sha256};
 pub use crate::crypto::ed25519_helper::Ed25519Helper;
 pub use crate::codec::hash_helper::HashHelper;
-pub use crate::protocol::{EnvelopeBuilder, helpers as protocol_helpers};
+pub use crate::protocol::{
+    AllowedTransactions, EnvelopeBuilder, explain_envelope, diff_transaction_hash, ReferenceHashes,
+    helpers as protocol_helpers,
+};
 pub use crate::generated::enums::{
     AccountAuthOperationType, AccountType, AllowedTransactionBit, BookType,
     DataEntryType, ExecutorVersion, KeyPageOperationType, NetworkMaintenanceOperationType,
@@ -43,6 +53,8 @@ pub use crate::generated::api_methods::{
     StatusParams, QueryParams, ExecuteParams, QueryDirectoryParams, QueryTxParams,
     QueryTxLocalParams, ExecuteCreateIdentityParams, ExecuteSendTokensParams,
     FaucetParams, VersionParams, DescribeParams,
+    // Typed query options
+    QueryOptions, PagedQueryOptions, TxQueryOptions,
     // Response types used in tests
     StatusResponse, QueryResponse, ExecuteResponse, QueryDirectoryResponse, QueryTxResponse,
     QueryTxLocalResponse, ExecuteCreateIdentityResponse, ExecuteSendTokensResponse
@@ -54,32 +66,137 @@ pub use crate::runtime::rpc::*;
 #[cfg(test)]
 pub use crate::runtime::signing_test_shims;
 
+/// Parsed and validated `acc://` URLs
+pub mod acc_url;
+/// Typed account states decoded from query results
+pub mod accounts;
+/// Wallet activity reports (per-token inflow/outflow summaries)
+pub mod activity;
+/// Anchor ledger queries (anchor pool, per-partition anchoring lag)
+pub mod anchors;
+/// Structured memo annotations (namespaced JSON or key=value memos)
+pub mod annotations;
 /// ACME amount helpers (1 ACME = 1e8 base units)
 pub mod amounts;
+/// Tamper-evident audit log of signed envelopes and final statuses
+pub mod audit;
+/// Account authority checks and guidance for unauthorized submissions
+pub mod auth;
+/// Typed, validated builders for transaction bodies
+pub mod builders;
+/// Bulk wallet generation with batched faucet funding for load tests
+pub mod bulk_wallets;
+/// Block cadence estimation from recent minor block times
+pub mod cadence;
 /// Canonical JSON encoding utilities
 pub mod canonjson;
+/// Block explorer-style traversal of minor and major blocks
+pub mod chain;
+/// Stored indexing checkpoints to resume block and history walks
+pub mod checkpoint;
 /// Main Accumulate client implementation
 pub mod client;
 /// Binary encoding/decoding utilities
 pub mod codec;
-/// Cryptographic utilities (Ed25519, hashing)
+/// Cross-SDK conformance harness over golden fixtures
+#[cfg(feature = "conformance")]
+pub mod conformance;
+/// Credits spent per flow, for budgeting deployment scripts
+pub mod credits;
+/// Cryptographic utilities (Ed25519, hashing, HD derivation, encrypted keystore)
 pub mod crypto;
+/// Decoded data account entries (accumulate, doubleHash, factom) with paging
+pub mod data;
+/// Lite account derivation and self-test vectors
+pub mod derive;
+/// Deposit detection for exchange backends
+pub mod deposits;
+/// Encoding profiles for networks on older executors
+pub mod encoding_profile;
 /// Error types and handling
 pub mod errors;
+/// Ethereum address lookup of lite identities and key pages
+pub mod eth;
+/// Typed faucet failures (rate limited, empty, rejected account)
+pub mod faucet;
+/// Transaction fee schedule and credit cost estimates
+pub mod fees;
 /// Auto-generated protocol types
 pub mod generated;
+/// Network globals (fee schedule, limits, thresholds)
+pub mod globals;
 /// Helper utilities (SmartSigner, TxBody, KeyManager, QuickStart)
 pub mod helpers;
 /// JSON-RPC client implementation
 pub mod json_rpc_client;
+/// Transaction lifecycle state machine with resumable, persisted records
+pub mod lifecycle;
+/// Load-test driver for sustained transaction throughput
+#[cfg(feature = "loadgen")]
+pub mod loadgen;
+/// Mock JSON-RPC transport and offline test harness
+pub mod mock;
+/// Multi-signature coordination (partial envelopes, co-signing, pending transactions)
+pub mod multisig;
+/// Document notarization (hash as data entry, verify with inclusion proof)
+pub mod notary;
+/// Historical ACME oracle lookups
+pub mod oracle;
+/// Paged iteration over V3 range queries, as async iterators and streams
+pub mod paging;
+/// Payment request URIs (account, amount, memo) for QR codes and links
+pub mod payment_uri;
+/// Multi-recipient token payouts with validation and batching
+pub mod payout;
+/// Structured progress events for long-running flows (funding, ADI setup)
+pub mod progress;
 /// Protocol envelope and transaction building
 pub mod protocol;
+/// Compact QR payloads of unsigned transactions for air-gapped signers
+pub mod qr;
+/// Typed V3 queries returning decoded records
+pub mod query;
 /// Runtime utilities (RPC, signing)
 pub mod runtime;
+/// Merkle receipt verification for query results
+pub mod receipts;
+/// Compact, log-safe views of envelopes and signatures
+pub mod redact;
+/// Retries and endpoint failover for JSON-RPC calls
+pub mod retry;
+/// Partition routing and node-pinned read-your-writes queries
+pub mod routing;
+/// Recurring transactions signed and submitted on a schedule
+pub mod scheduler;
+/// JSON Schema and OpenRPC export of the generated protocol types
+#[cfg(feature = "json-schema")]
+pub mod schema;
+/// Pluggable signing backends (in-memory keys, Ledger hardware wallets)
+pub mod signer;
+/// In-memory network for testing flows without a node
+pub mod simulator;
+/// Snapshot file decoding
+pub mod snapshot;
+/// Pluggable key-value storage (memory, file, sled) for persisted SDK state
+pub mod storage;
+/// Transaction templates with placeholders for recurring operations
+pub mod templates;
+/// Token symbol and issuer lookup
+pub mod tokens;
+/// Typed, paged transaction history from the V2 API
+pub mod tx_history;
+/// Transaction status tracking with typed outcomes
+pub mod tx_status;
 /// V3 API type definitions
 pub mod types;
 /// Type matrix for testing
 pub mod types_matrix;
+/// Account watchers and transaction type filters
+pub mod watch;
+/// Minimal and maximal wire-format samples for every generated type
+pub mod wire_matrix;
+/// Withdrawal pipeline with replay protection
+pub mod withdrawals;
 
 // Re-export helper utilities for convenience
 pub use crate::helpers::{
@@ -88,11 +205,12 @@ pub use crate::helpers::{
     // Transaction builders
     TxBody, TxResult,
     // Smart signing
-    SmartSigner, HeaderOptions, KeyManager, KeyPageState, KeyEntry,
+    SmartSigner, HeaderOptions, SignatureOptions, KeyManager, KeyPageState, KeyEntry,
+    KeyBookPage, key_page_url, CreditPurchase, is_stale_oracle_error,
     // QuickStart API
     QuickStart, Wallet, AdiInfo, KeyPageInfo,
     // Polling utilities
-    poll_for_balance, poll_for_credits, wait_for_tx,
+    poll_for_balance, poll_for_credits, wait_for_tx, ensure_funded, PollOptions,
     // URL derivation
     derive_lite_identity_url, derive_lite_token_account_url, sha256_hash,
 };
@@ -112,36 +230,65 @@ pub use crate::types::{
     ChainQuery, DataQuery, DirectoryQuery, PendingQuery, BlockQuery,
     AnchorSearchQuery, PublicKeySearchQuery, PublicKeyHashSearchQuery,
     DelegateSearchQuery, MessageHashSearchQuery, V3Query,
+    // Account State Types
+    AccountAuthority, TokenAccountState,
 };
 
-use anyhow::Result;
+use crate::errors::Error;
 use std::time::Duration;
 use url::Url;
 
 /// Configuration options for the Accumulate client
 #[derive(Debug, Clone)]
 pub struct AccOptions {
-    /// Request timeout duration
-    pub timeout: Duration,
+    /// Time allowed to establish a connection to a node
+    pub connect_timeout: Duration,
+    /// Time allowed for a single RPC call, connection included
+    pub request_timeout: Duration,
+    /// Overall deadline for the wait helpers that poll until a transaction
+    /// or balance is confirmed (e.g. [`helpers::wait_for_tx`])
+    pub confirmation_timeout: Duration,
+    /// Delay between the polls of those wait helpers
+    pub poll_interval: Duration,
     /// Default headers to include with requests
     pub headers: std::collections::HashMap<String, String>,
+    /// Verify every Merkle receipt returned by V3 queries and mark each
+    /// record `verified` (see [`receipts`])
+    pub verify_receipts: bool,
+    /// Retry policy for V2 and V3 calls; no retries by default
+    pub retry: RetryPolicy,
+    /// V2 endpoints to fail over to when the primary one is down
+    pub v2_fallbacks: Vec<Url>,
+    /// V3 endpoints to fail over to when the primary one is down
+    pub v3_fallbacks: Vec<Url>,
+    /// How long an endpoint that went down is passed over
+    pub endpoint_cooldown: Duration,
+    /// Idle connections kept open per node for reuse
+    pub pool_max_idle_per_host: usize,
 }
 
 impl Default for AccOptions {
     fn default() -> Self {
         Self {
-            timeout: Duration::from_secs(30),
+            connect_timeout: Duration::from_secs(10),
+            request_timeout: Duration::from_secs(30),
+            confirmation_timeout: Duration::from_secs(120),
+            poll_interval: Duration::from_secs(2),
             headers: std::collections::HashMap::new(),
+            verify_receipts: false,
+            retry: RetryPolicy::default(),
+            v2_fallbacks: Vec::new(),
+            v3_fallbacks: Vec::new(),
+            endpoint_cooldown: Duration::from_secs(30),
+            pool_max_idle_per_host: usize::MAX,
         }
     }
 }
 
 impl AccumulateClient {
     /// Create a new client from explicit V2 and V3 endpoints
-    pub async fn from_endpoints(v2: Url, v3: Url, opts: AccOptions) -> Result<Self> {
-        Self::new_with_options(v2, v3, opts)
-            .await
-            .map_err(|e| anyhow::anyhow!(e))
+    pub async fn from_endpoints(v2: Url, v3: Url, opts: AccOptions) -> Result<Self, Error> {
+        Ok(Self::new_with_options(v2, v3, opts).await?)
     }
 
     /// Create a new client from environment variables
@@ -149,25 +296,60 @@ impl AccumulateClient {
     /// Reads the following environment variables:
     /// - `ACCUMULATE_V2_URL`: V2 endpoint URL
     /// - `ACCUMULATE_V3_URL`: V3 endpoint URL
-    /// - `ACCUMULATE_TIMEOUT_MS`: Request timeout in milliseconds (optional, defaults to 30000)
-    pub async fn from_env() -> Result<Self> {
+    /// - `ACCUMULATE_TIMEOUT_MS`: Per-RPC timeout in milliseconds (optional, defaults to 30000)
+    /// - `ACCUMULATE_CONNECT_TIMEOUT_MS`: Connect timeout in milliseconds (optional, defaults to 10000)
+    /// - `ACCUMULATE_CONFIRMATION_TIMEOUT_MS`: Confirmation deadline in milliseconds (optional, defaults to 120000)
+    /// - `ACCUMULATE_RETRY_ATTEMPTS`: Attempts per RPC call with exponential backoff (optional, defaults to 1, no retries)
+    /// - `ACCUMULATE_V2_FALLBACK_URLS`, `ACCUMULATE_V3_FALLBACK_URLS`: Comma-separated endpoints to fail over to (optional)
+    ///
+    /// A missing or malformed URL is an [`Error::Validation`].
+    pub async fn from_env() -> Result<Self, Error> {
         dotenvy::dotenv().ok(); // Load .env file if present, ignore errors
 
-        let v2_url = std::env::var("ACCUMULATE_V2_URL")
-            .map_err(|_| anyhow::anyhow!("ACCUMULATE_V2_URL environment variable not set"))?;
-        let v3_url = std::env::var("ACCUMULATE_V3_URL")
-            .map_err(|_| anyhow::anyhow!("ACCUMULATE_V3_URL environment variable not set"))?;
+        let required = |name: &str| {
+            std::env::var(name).map_err(|_| {
+                errors::ValidationError::RequiredFieldMissing(format!(
+                    "{name} environment variable not set"
+                ))
+            })
+        };
+        let v2_url = required("ACCUMULATE_V2_URL")?;
+        let v3_url = required("ACCUMULATE_V3_URL")?;
 
         let v2 = Url::parse(&v2_url)?;
         let v3 = Url::parse(&v3_url)?;
 
-        let timeout_ms = std::env::var("ACCUMULATE_TIMEOUT_MS")
-            .ok()
-            .and_then(|s| s.parse().ok())
-            .unwrap_or(30_000);
+        let millis = |name: &str, default: u64| {
+            let ms = std::env::var(name)
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .unwrap_or(default);
+            Duration::from_millis(ms)
+        };
+
+        let urls = |name: &str| -> Result<Vec<Url>, Error> {
+            let list = std::env::var(name).unwrap_or_default();
+            list.split(',')
+                .map(str::trim)
+                .filter(|s| !s.is_empty())
+                .map(|s| {
+                    Url::parse(s).map_err(|e| {
+                        errors::ValidationError::InvalidUrl(format!("{name}: {e}")).into()
+                    })
+                })
+                .collect()
+        };
 
         let opts = AccOptions {
-            timeout: Duration::from_millis(timeout_ms),
+            connect_timeout: millis("ACCUMULATE_CONNECT_TIMEOUT_MS", 10_000),
+            request_timeout: millis("ACCUMULATE_TIMEOUT_MS", 30_000),
+            confirmation_timeout: millis("ACCUMULATE_CONFIRMATION_TIMEOUT_MS", 120_000),
+            retry: std::env::var("ACCUMULATE_RETRY_ATTEMPTS")
+                .ok()
+                .and_then(|s| s.parse().ok())
+                .map_or_else(RetryPolicy::default, RetryPolicy::exponential),
+            v2_fallbacks: urls("ACCUMULATE_V2_FALLBACK_URLS")?,
+            v3_fallbacks: urls("ACCUMULATE_V3_FALLBACK_URLS")?,
             ..Default::default()
         };
 
@@ -175,28 +357,28 @@ impl AccumulateClient {
     }
 
     /// Create a client configured for DevNet
-    pub async fn devnet(opts: AccOptions) -> Result<Self> {
+    pub async fn devnet(opts: AccOptions) -> Result<Self, Error> {
         let v2 = Url::parse("http://localhost:26660/v2")?;
         let v3 = Url::parse("http://localhost:26661/v3")?;
         Self::from_endpoints(v2, v3, opts).await
     }
 
     /// Create a client configured for TestNet
-    pub async fn testnet(opts: AccOptions) -> Result<Self> {
+    pub async fn testnet(opts: AccOptions) -> Result<Self, Error> {
         let v2 = Url::parse("https://testnet.accumulatenetwork.io/v2")?;
         let v3 = Url::parse("https://testnet.accumulatenetwork.io/v3")?;
         Self::from_endpoints(v2, v3, opts).await
     }
 
     /// Create a client configured for MainNet
-    pub async fn mainnet(opts: AccOptions) -> Result<Self> {
+    pub async fn mainnet(opts: AccOptions) -> Result<Self, Error> {
         let v2 = Url::parse("https://mainnet.accumulatenetwork.io/v2")?;
         let v3 = Url::parse("https://mainnet.accumulatenetwork.io/v3")?;
         Self::from_endpoints(v2, v3, opts).await
     }
 
     /// Create a client configured for a custom endpoint
-    pub async fn custom(base_url: &str, opts: AccOptions) -> Result<Self> {
+    pub async fn custom(base_url: &str, opts: AccOptions) -> Result<Self, Error> {
         let base = Url::parse(base_url)?;
         let v2 = base.join("/v2")?;
         let v3 = base.join("/v3")?;
@@ -0,0 +1,770 @@
+//! Transaction lifecycle as a resumable state machine
+//!
+//! A [`TxLifecycle`] follows one transaction through
+//!
+//! ```text
+//! Built → Signed → Submitted → Pending → Delivered → Anchored
+//!            │         │          │
+//!            └─────────┴──────────┴────→ Failed
+//! ```
+//!
+//! where `Submitted` may skip `Pending` when the network executes the
+//! transaction straight away. [`TxLifecycle::transition`] only accepts these
+//! moves, so a record never returns to a state it has left, and
+//! [`TxLifecycle::advance`] takes the next step against the network.
+//!
+//! A record is plain serde data and can be snapshotted in any state.
+//! [`TxPipeline`] writes every transition to a [`KvStore`] before acting on
+//! it: the signed envelope is stored before it is submitted, so resuming
+//! after a crash resubmits the same transaction, which the network
+//! deduplicates by hash, instead of signing a second one. Records are keyed
+//! by a caller-chosen ID, and a known ID is resumed rather than started again,
+//! which gives exactly-once processing over at-least-once inputs.
+
+use crate::client::AccumulateClient;
+use crate::helpers::SmartSigner;
+use crate::json_rpc_client::JsonRpcError;
+use crate::query::ChainResult;
+use crate::storage::{get_json, put_json, scan_json, KvStore, StorageError};
+use crate::types::{ChainQuery, ReceiptOptions};
+use crate::withdrawals::{is_duplicate, submission_error};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::fmt;
+use thiserror::Error;
+
+/// The states of [`TxState`] without their data
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum TxPhase {
+    /// Body and principal chosen, not signed
+    Built,
+    /// Signed, not yet accepted by the network
+    Signed,
+    /// Accepted by the network, status not yet known
+    Submitted,
+    /// Waiting on the network, e.g. for more signatures
+    Pending,
+    /// Executed successfully
+    Delivered,
+    /// Rejected or failed during execution
+    Failed,
+    /// Delivered, and provably part of a block anchored to the directory
+    Anchored,
+}
+
+impl TxPhase {
+    /// Whether `next` may follow this phase
+    #[must_use]
+    pub const fn can_move_to(self, next: Self) -> bool {
+        matches!(
+            (self, next),
+            (Self::Built, Self::Signed)
+                | (Self::Signed, Self::Submitted | Self::Failed)
+                | (
+                    Self::Submitted | Self::Pending,
+                    Self::Pending | Self::Delivered | Self::Failed
+                )
+                | (Self::Delivered, Self::Anchored)
+        )
+    }
+
+    /// Whether no phase can follow this one
+    #[must_use]
+    pub const fn is_terminal(self) -> bool {
+        matches!(self, Self::Failed | Self::Anchored)
+    }
+
+    /// Whether the transaction has executed successfully
+    #[must_use]
+    pub const fn is_executed(self) -> bool {
+        matches!(self, Self::Delivered | Self::Anchored)
+    }
+
+    /// Name used in snapshots
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Built => "built",
+            Self::Signed => "signed",
+            Self::Submitted => "submitted",
+            Self::Pending => "pending",
+            Self::Delivered => "delivered",
+            Self::Failed => "failed",
+            Self::Anchored => "anchored",
+        }
+    }
+}
+
+impl fmt::Display for TxPhase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Where a transaction is in its lifecycle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum TxState {
+    /// Body and principal chosen, not signed
+    Built,
+    /// Signed; the envelope is on the record
+    Signed,
+    /// Accepted by the network
+    #[serde(rename_all = "camelCase")]
+    Submitted {
+        /// Transaction ID
+        txid: String,
+    },
+    /// Known to the network but not executed
+    #[serde(rename_all = "camelCase")]
+    Pending {
+        /// Transaction ID
+        txid: String,
+    },
+    /// Executed successfully
+    #[serde(rename_all = "camelCase")]
+    Delivered {
+        /// Transaction ID
+        txid: String,
+    },
+    /// Rejected on submission or failed during execution
+    #[serde(rename_all = "camelCase")]
+    Failed {
+        /// Failure message from the network
+        reason: String,
+    },
+    /// Delivered and covered by a verified receipt
+    #[serde(rename_all = "camelCase")]
+    Anchored {
+        /// Transaction ID
+        txid: String,
+        /// Root the receipt proves the transaction into (hex)
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        anchor: Option<String>,
+        /// Block the transaction was recorded in
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        local_block: Option<u64>,
+    },
+}
+
+impl TxState {
+    /// The state without its data
+    #[must_use]
+    pub const fn phase(&self) -> TxPhase {
+        match self {
+            Self::Built => TxPhase::Built,
+            Self::Signed => TxPhase::Signed,
+            Self::Submitted { .. } => TxPhase::Submitted,
+            Self::Pending { .. } => TxPhase::Pending,
+            Self::Delivered { .. } => TxPhase::Delivered,
+            Self::Failed { .. } => TxPhase::Failed,
+            Self::Anchored { .. } => TxPhase::Anchored,
+        }
+    }
+}
+
+/// Errors from driving a transaction lifecycle
+#[derive(Error, Debug)]
+pub enum LifecycleError {
+    /// The requested move is not allowed from the current state
+    #[error("Transaction {id} cannot move from {from} to {to}")]
+    InvalidTransition {
+        /// Record ID
+        id: String,
+        /// Current phase
+        from: TxPhase,
+        /// Requested phase
+        to: TxPhase,
+    },
+
+    /// The ID is already used for a different transaction
+    #[error("Transaction {id} already exists with a different principal, body or memo")]
+    Conflict {
+        /// The reused ID
+        id: String,
+    },
+
+    /// Reading or writing the store failed
+    #[error("Lifecycle store error: {0}")]
+    Store(String),
+
+    /// Signing or talking to the network failed; the record is left as-is
+    #[error("Network error: {0}")]
+    Network(#[from] JsonRpcError),
+}
+
+impl From<StorageError> for LifecycleError {
+    fn from(e: StorageError) -> Self {
+        Self::Store(e.to_string())
+    }
+}
+
+/// One transaction and the state it has reached
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxLifecycle {
+    id: String,
+    principal: String,
+    body: Value,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    memo: Option<String>,
+    /// Signed envelope, fixed once signed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    envelope: Option<Value>,
+    /// Transaction hash (hex), fixed once signed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tx_hash: Option<String>,
+    #[serde(flatten)]
+    state: TxState,
+}
+
+impl TxLifecycle {
+    /// A built transaction `body` for `principal`, identified by `id`
+    #[must_use]
+    pub fn new(id: &str, principal: &str, body: Value) -> Self {
+        Self {
+            id: id.to_string(),
+            principal: principal.to_string(),
+            body,
+            memo: None,
+            envelope: None,
+            tx_hash: None,
+            state: TxState::Built,
+        }
+    }
+
+    /// Attach a memo to the transaction header
+    #[must_use]
+    pub fn with_memo(mut self, memo: &str) -> Self {
+        self.memo = Some(memo.to_string());
+        self
+    }
+
+    /// Caller-chosen ID
+    #[must_use]
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// Principal account
+    #[must_use]
+    pub fn principal(&self) -> &str {
+        &self.principal
+    }
+
+    /// Transaction body
+    #[must_use]
+    pub const fn body(&self) -> &Value {
+        &self.body
+    }
+
+    /// Header memo
+    #[must_use]
+    pub fn memo(&self) -> Option<&str> {
+        self.memo.as_deref()
+    }
+
+    /// Signed envelope, once signed
+    #[must_use]
+    pub const fn envelope(&self) -> Option<&Value> {
+        self.envelope.as_ref()
+    }
+
+    /// Transaction hash (hex), once signed
+    #[must_use]
+    pub fn tx_hash(&self) -> Option<&str> {
+        self.tx_hash.as_deref()
+    }
+
+    /// Transaction ID (`acc://<hash>@<principal>`), once signed
+    #[must_use]
+    pub fn txid(&self) -> Option<String> {
+        let principal = self.principal.trim_start_matches("acc://");
+        self.tx_hash
+            .as_ref()
+            .map(|hash| format!("acc://{hash}@{principal}"))
+    }
+
+    /// Current state
+    #[must_use]
+    pub const fn state(&self) -> &TxState {
+        &self.state
+    }
+
+    /// Current phase
+    #[must_use]
+    pub const fn phase(&self) -> TxPhase {
+        self.state.phase()
+    }
+
+    /// Move to `next`, if the current phase allows it
+    ///
+    /// Lets callers that sign or submit by other means record what happened.
+    /// `Signed` is set by [`sign`](Self::sign), which also stores the
+    /// envelope.
+    pub fn transition(&mut self, next: TxState) -> Result<(), LifecycleError> {
+        let (from, to) = (self.phase(), next.phase());
+        if !from.can_move_to(to) || (to == TxPhase::Signed && self.envelope.is_none()) {
+            return Err(LifecycleError::InvalidTransition {
+                id: self.id.clone(),
+                from,
+                to,
+            });
+        }
+        self.state = next;
+        Ok(())
+    }
+
+    /// Sign a built transaction with `signer`, using its cached version
+    pub fn sign(&mut self, signer: &SmartSigner<'_>) -> Result<(), LifecycleError> {
+        self.expect(TxPhase::Signed)?;
+        let envelope = signer.sign(&self.principal, &self.body, self.memo.as_deref())?;
+        self.tx_hash = envelope["signatures"][0]["transactionHash"]
+            .as_str()
+            .map(str::to_string);
+        self.envelope = Some(envelope);
+        self.transition(TxState::Signed)
+    }
+
+    /// Submit the signed envelope
+    ///
+    /// A rejected submission moves the record to `Failed`. If the network
+    /// already holds the transaction, because an earlier run submitted it
+    /// without recording that, the record moves to `Submitted` as if this
+    /// submission had been accepted.
+    pub async fn submit(&mut self, client: &AccumulateClient) -> Result<(), LifecycleError> {
+        self.expect(TxPhase::Submitted)?;
+        let envelope = self.envelope.clone().unwrap_or(Value::Null);
+        let txid = self.txid().unwrap_or_default();
+        let submitted: Result<Value, JsonRpcError> = client
+            .v3_client
+            .call_v3("submit", json!({ "envelope": envelope }))
+            .await;
+        let next = match submitted {
+            Ok(response) => match submission_error(&response) {
+                Some(message) if is_duplicate(&message) => TxState::Submitted { txid },
+                Some(reason) => TxState::Failed { reason },
+                None => TxState::Submitted { txid },
+            },
+            Err(e) => {
+                if client.v3().query_transaction(&txid).await.is_err() {
+                    return Err(e.into());
+                }
+                TxState::Submitted { txid }
+            }
+        };
+        self.transition(next)
+    }
+
+    /// Check a submitted transaction once and record any progress
+    ///
+    /// Moves `Submitted` or `Pending` on to `Pending`, `Delivered` or
+    /// `Failed`, and `Delivered` to `Anchored` once the node returns a
+    /// verified receipt for the transaction's entry on the principal's main
+    /// chain. Returns whether the phase changed; other phases, and failed
+    /// queries, are left as they are.
+    pub async fn poll(&mut self, client: &AccumulateClient) -> Result<bool, LifecycleError> {
+        let before = self.phase();
+        let txid = self.txid().unwrap_or_default();
+        let next = match before {
+            TxPhase::Submitted | TxPhase::Pending => {
+                let Ok(record) = client.v3().query_transaction(&txid).await else {
+                    return Ok(false);
+                };
+                if let Some(reason) = record.error_message() {
+                    TxState::Failed { reason }
+                } else if record.is_delivered() {
+                    TxState::Delivered { txid }
+                } else if record.status_code() == Some("pending") {
+                    TxState::Pending { txid }
+                } else {
+                    return Ok(false);
+                }
+            }
+            TxPhase::Delivered => {
+                let query = ChainQuery {
+                    name: Some("main".to_string()),
+                    entry: self.tx_hash.clone(),
+                    include_receipt: Some(ReceiptOptions {
+                        for_any: Some(true),
+                        for_height: None,
+                    }),
+                    ..Default::default()
+                };
+                match client.v3().query_chain(&self.principal, query).await {
+                    Ok(ChainResult::Entry(entry)) => match anchored_state(
+                        txid,
+                        self.tx_hash.as_deref().unwrap_or_default(),
+                        entry.receipt.as_ref(),
+                    ) {
+                        Some(state) => state,
+                        None => return Ok(false),
+                    },
+                    _ => return Ok(false),
+                }
+            }
+            _ => return Ok(false),
+        };
+        if next == self.state {
+            return Ok(false);
+        }
+        self.transition(next)?;
+        Ok(self.phase() != before)
+    }
+
+    /// Take the next step: sign, submit or poll, depending on the phase
+    ///
+    /// Returns whether the state changed.
+    pub async fn advance(&mut self, signer: &SmartSigner<'_>) -> Result<bool, LifecycleError> {
+        match self.phase() {
+            TxPhase::Built => self.sign(signer).map(|()| true),
+            TxPhase::Signed => self.submit(signer.client()).await.map(|()| true),
+            _ => self.poll(signer.client()).await,
+        }
+    }
+
+    /// Fail with [`LifecycleError::InvalidTransition`] unless `to` may follow
+    fn expect(&self, to: TxPhase) -> Result<(), LifecycleError> {
+        if self.phase().can_move_to(to) {
+            Ok(())
+        } else {
+            Err(LifecycleError::InvalidTransition {
+                id: self.id.clone(),
+                from: self.phase(),
+                to,
+            })
+        }
+    }
+
+    /// Whether `other` describes the same transaction
+    fn same_transaction(&self, other: &Self) -> bool {
+        self.principal == other.principal && self.body == other.body && self.memo == other.memo
+    }
+}
+
+/// The `Anchored` state for the receipt of a chain entry, if it verifies
+/// and proves `tx_hash`
+fn anchored_state(txid: String, tx_hash: &str, receipt: Option<&Value>) -> Option<TxState> {
+    let receipt = receipt?;
+    crate::receipts::verify_receipt(receipt).ok()?;
+    let start = receipt.get("start").and_then(Value::as_str)?;
+    if !start.eq_ignore_ascii_case(tx_hash) {
+        return None;
+    }
+    Some(TxState::Anchored {
+        txid,
+        anchor: receipt
+            .get("anchor")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        local_block: receipt.get("localBlock").and_then(Value::as_u64),
+    })
+}
+
+/// Drives transactions through their lifecycle, persisting every transition
+#[derive(Debug)]
+pub struct TxPipeline<'a, S: KvStore> {
+    signer: SmartSigner<'a>,
+    store: S,
+    max_attempts: u32,
+    wait_for_anchor: bool,
+}
+
+impl<'a, S: KvStore> TxPipeline<'a, S> {
+    const PREFIX: &'static str = "lifecycle/";
+
+    /// Sign with `signer` and keep records in `store`
+    pub const fn new(signer: SmartSigner<'a>, store: S) -> Self {
+        Self {
+            signer,
+            store,
+            max_attempts: 30,
+            wait_for_anchor: false,
+        }
+    }
+
+    /// Number of status polls before leaving a record where it is
+    #[must_use]
+    pub const fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// Keep polling delivered transactions until they are anchored
+    #[must_use]
+    pub const fn wait_for_anchor(mut self, wait: bool) -> Self {
+        self.wait_for_anchor = wait;
+        self
+    }
+
+    /// The underlying store
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// The persisted record with `id`
+    pub fn load(&self, id: &str) -> Result<Option<TxLifecycle>, LifecycleError> {
+        Ok(get_json(&self.store, &Self::key(id))?)
+    }
+
+    /// Sign a new transaction and persist it without touching the network
+    ///
+    /// Returns the stored record if the ID is already known for the same
+    /// transaction, or [`LifecycleError::Conflict`] if it is known for a
+    /// different one. Uses the signer's cached version.
+    pub fn prepare(&mut self, tx: TxLifecycle) -> Result<TxLifecycle, LifecycleError> {
+        if let Some(existing) = self.load(tx.id())? {
+            if !existing.same_transaction(&tx) {
+                return Err(LifecycleError::Conflict { id: tx.id });
+            }
+            return Ok(existing);
+        }
+        let mut tx = tx;
+        if tx.phase() == TxPhase::Built {
+            tx.sign(&self.signer)?;
+        }
+        self.save(&tx)?;
+        Ok(tx)
+    }
+
+    /// Process a transaction as far as it will go
+    ///
+    /// Safe to call again with the same ID at any time: a known record is
+    /// resumed from its persisted state and never signed again.
+    pub async fn run(&mut self, tx: TxLifecycle) -> Result<TxLifecycle, LifecycleError> {
+        if self.load(tx.id())?.is_none() {
+            self.signer.refresh_version().await?;
+        }
+        let tx = self.prepare(tx)?;
+        self.drive(tx).await
+    }
+
+    /// Drive every unfinished record forward, e.g. after a restart
+    ///
+    /// Records that fail with a network error are reported with their
+    /// current state and left for the next call.
+    pub async fn resume(&mut self) -> Result<Vec<TxLifecycle>, LifecycleError> {
+        let mut records: Vec<TxLifecycle> = scan_json(&self.store, Self::PREFIX)?;
+        records.retain(|tx| !self.is_finished(tx));
+        let mut results = Vec::new();
+        for tx in records {
+            match self.drive(tx.clone()).await {
+                Ok(driven) => results.push(driven),
+                Err(LifecycleError::Network(_)) => results.push(tx),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(results)
+    }
+
+    async fn drive(&mut self, mut tx: TxLifecycle) -> Result<TxLifecycle, LifecycleError> {
+        let mut polls = 0;
+        while !self.is_finished(&tx) && polls < self.max_attempts {
+            let polling = tx.phase() > TxPhase::Signed;
+            if polling {
+                if polls > 0 {
+                    tokio::time::sleep(self.signer.client().options.poll_interval).await;
+                }
+                polls += 1;
+            }
+            if tx.advance(&self.signer).await? {
+                self.save(&tx)?;
+            }
+        }
+        Ok(tx)
+    }
+
+    fn is_finished(&self, tx: &TxLifecycle) -> bool {
+        tx.phase().is_terminal() || (!self.wait_for_anchor && tx.phase() == TxPhase::Delivered)
+    }
+
+    fn save(&mut self, tx: &TxLifecycle) -> Result<(), LifecycleError> {
+        Ok(put_json(&mut self.store, &Self::key(tx.id()), tx)?)
+    }
+
+    fn key(id: &str) -> String {
+        format!("{}{id}", Self::PREFIX)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::{QuickStart, TxBody};
+    use crate::simulator::{SimulatedNetwork, DEFAULT_ORACLE};
+    use crate::storage::MemoryKvStore;
+    use crate::ACME_BASE_UNITS;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_transitions() {
+        let mut tx = TxLifecycle::new(
+            "t-1",
+            "acc://alice.acme/tokens",
+            json!({"type": "burnTokens"}),
+        );
+        let err = tx
+            .transition(TxState::Delivered {
+                txid: "x".to_string(),
+            })
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            LifecycleError::InvalidTransition {
+                from: TxPhase::Built,
+                to: TxPhase::Delivered,
+                ..
+            }
+        ));
+        // Signed needs an envelope, which only sign() provides
+        assert!(tx.transition(TxState::Signed).is_err());
+
+        assert!(TxPhase::Submitted.can_move_to(TxPhase::Delivered));
+        assert!(TxPhase::Pending.can_move_to(TxPhase::Pending));
+        assert!(!TxPhase::Delivered.can_move_to(TxPhase::Failed));
+        assert!(!TxPhase::Anchored.can_move_to(TxPhase::Delivered));
+        assert!(TxPhase::Failed.is_terminal() && !TxPhase::Delivered.is_terminal());
+    }
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut tx = TxLifecycle::new(
+            "t-2",
+            "acc://alice.acme/tokens",
+            json!({"type": "burnTokens"}),
+        )
+        .with_memo("payout");
+        tx.envelope = Some(json!({"signatures": []}));
+        tx.tx_hash = Some("ab".repeat(32));
+        tx.state = TxState::Anchored {
+            txid: tx.txid().unwrap(),
+            anchor: Some("cd".repeat(32)),
+            local_block: Some(12),
+        };
+        let snapshot = serde_json::to_value(&tx).unwrap();
+        assert_eq!(snapshot["state"], "anchored");
+        assert_eq!(snapshot["localBlock"], 12);
+        assert_eq!(serde_json::from_value::<TxLifecycle>(snapshot).unwrap(), tx);
+
+        let built =
+            serde_json::to_value(TxLifecycle::new("t-3", "acc://a.acme", json!({}))).unwrap();
+        assert_eq!(built["state"], "built");
+        assert_eq!(
+            serde_json::from_value::<TxLifecycle>(built)
+                .unwrap()
+                .phase(),
+            TxPhase::Built
+        );
+    }
+
+    #[test]
+    fn test_anchored_state_needs_a_valid_receipt() {
+        let hash = "11".repeat(32);
+        let receipt = json!({
+            "start": &hash,
+            "end": &hash,
+            "anchor": &hash,
+            "entries": [],
+            "localBlock": 40
+        });
+        let state = anchored_state("acc://x@y".to_string(), &hash, Some(&receipt)).unwrap();
+        assert_eq!(
+            state,
+            TxState::Anchored {
+                txid: "acc://x@y".to_string(),
+                anchor: Some(hash.clone()),
+                local_block: Some(40),
+            }
+        );
+        let forged =
+            json!({"start": &hash, "end": &hash, "anchor": "22".repeat(32), "entries": []});
+        assert!(anchored_state("acc://x@y".to_string(), &hash, Some(&forged)).is_none());
+        // A valid proof of some other entry
+        assert!(
+            anchored_state("acc://x@y".to_string(), &"33".repeat(32), Some(&receipt)).is_none()
+        );
+        assert!(anchored_state("acc://x@y".to_string(), &hash, None).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_against_simulator() {
+        let network = SimulatedNetwork::new();
+        let quick = QuickStart::from_client(Arc::new(network.client().await.unwrap()));
+        let wallet = quick.create_wallet();
+        network
+            .fund(&wallet.lite_token_account, 100 * ACME_BASE_UNITS)
+            .unwrap();
+        let signer = SmartSigner::new(
+            quick.client(),
+            wallet.keypair().clone(),
+            &wallet.lite_identity,
+        );
+        let mut pipeline = TxPipeline::new(signer, MemoryKvStore::new()).max_attempts(5);
+
+        let credits = TxLifecycle::new(
+            "credits-1",
+            &wallet.lite_token_account,
+            TxBody::add_credits(
+                &wallet.lite_identity,
+                &(10 * ACME_BASE_UNITS).to_string(),
+                DEFAULT_ORACLE,
+            ),
+        );
+        let done = pipeline.run(credits.clone()).await.unwrap();
+        assert_eq!(done.phase(), TxPhase::Delivered);
+        assert_eq!(pipeline.load("credits-1").unwrap(), Some(done.clone()));
+
+        // The same ID again is the same record, not a second purchase
+        let again = pipeline.run(credits).await.unwrap();
+        assert_eq!(again, done);
+        assert_eq!(
+            network.account(&wallet.lite_token_account).unwrap()["balance"],
+            (90 * ACME_BASE_UNITS).to_string()
+        );
+        let other = TxLifecycle::new(
+            "credits-1",
+            &wallet.lite_token_account,
+            json!({"type": "burnTokens"}),
+        );
+        assert!(matches!(
+            pipeline.run(other).await.unwrap_err(),
+            LifecycleError::Conflict { .. }
+        ));
+
+        // A crash after submitting but before recording it: resume finds
+        // the transaction on the network instead of signing it again
+        let recipient = format!(
+            "{}/ACME",
+            crate::helpers::derive_lite_identity_url(&[9u8; 32])
+        );
+        let send = TxLifecycle::new(
+            "send-1",
+            &wallet.lite_token_account,
+            TxBody::send_tokens_single(&recipient, &ACME_BASE_UNITS.to_string()),
+        );
+        let prepared = pipeline.prepare(send).unwrap();
+        assert_eq!(prepared.phase(), TxPhase::Signed);
+        network
+            .call("submit", &json!({"envelope": prepared.envelope().unwrap()}))
+            .unwrap();
+        let resumed = pipeline.resume().await.unwrap();
+        assert_eq!(resumed.len(), 1);
+        assert_eq!(resumed[0].phase(), TxPhase::Delivered);
+        assert_eq!(
+            network.account(&recipient).unwrap()["balance"],
+            ACME_BASE_UNITS.to_string()
+        );
+
+        // A rejected transaction fails for good
+        let broke = TxLifecycle::new(
+            "send-2",
+            &wallet.lite_token_account,
+            TxBody::send_tokens_single(&recipient, &(1000 * ACME_BASE_UNITS).to_string()),
+        );
+        let failed = pipeline.run(broke).await.unwrap();
+        assert_eq!(failed.phase(), TxPhase::Failed);
+        assert!(pipeline.resume().await.unwrap().is_empty());
+    }
+}
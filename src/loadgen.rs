@@ -0,0 +1,665 @@
+//! Load-test driver for sustained transaction throughput
+//!
+//! [`LoadTest`] signs simple transactions — self-sends or data writes — at a
+//! target rate and pushes them through `submitMulti` in batches, the same
+//! path [`PayoutBatch`](crate::payout::PayoutBatch) uses. Batches are
+//! submitted concurrently up to [`LoadTest::max_in_flight`], so a slow node
+//! shows up as submit latency and backpressure rather than as a lower send
+//! rate hidden in the driver.
+//!
+//! The [`LoadReport`] holds separate histograms for signing (the SDK's hot
+//! path: hashing, marshalling and ED25519) and for the `submitMulti` round
+//! trip (the node), plus counts of every distinct rejection message. Its
+//! `Display` output is a plain-text summary.
+//!
+//! ```no_run
+//! # async fn example(client: std::sync::Arc<accumulate_client::AccumulateClient>) {
+//! use accumulate_client::loadgen::{LoadTest, Workload};
+//! use ed25519_dalek::SigningKey;
+//! use std::sync::Arc;
+//! use std::time::Duration;
+//!
+//! let account = "acc://0123456789abcdef0123456789abcdef0123456789abcdef/ACME";
+//! let report = LoadTest::new(Workload::self_send(account, 1))
+//!     .tps(200.0)
+//!     .duration(Duration::from_secs(30))
+//!     .run(client, Arc::new(SigningKey::from_bytes(&[1u8; 32])), account)
+//!     .await
+//!     .unwrap();
+//! let summary = report.to_string();
+//! # }
+//! ```
+
+use crate::client::AccumulateClient;
+use crate::helpers::{SmartSigner, TxBody};
+use crate::json_rpc_client::JsonRpcError;
+use crate::payout::submission_outcome;
+use crate::signer::Signer;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::sync::Arc;
+use std::time::Duration;
+use thiserror::Error;
+use tokio::task::JoinSet;
+use tokio::time::{Instant, MissedTickBehavior};
+
+/// Transactions per second when no rate is set
+pub const DEFAULT_TPS: f64 = 10.0;
+
+/// Length of a run when none is set
+pub const DEFAULT_DURATION: Duration = Duration::from_secs(60);
+
+/// Envelopes per `submitMulti` call when no batch size is set
+pub const DEFAULT_BATCH_SIZE: usize = 10;
+
+/// Concurrent `submitMulti` calls when no limit is set
+pub const DEFAULT_MAX_IN_FLIGHT: usize = 8;
+
+/// Error recorded for an envelope the response has no entry for
+const NO_RESPONSE: &str = "no response for this transaction";
+
+/// Why a load test could not run
+#[derive(Error, Debug)]
+pub enum LoadError {
+    /// The signer could not be verified or could not sign
+    #[error("Signing failed: {0}")]
+    Signing(JsonRpcError),
+}
+
+/// Transactions a load test sends
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Workload {
+    /// sendTokens from a token account back to itself
+    SelfSend {
+        /// Token account, both principal and recipient
+        account: String,
+        /// Base units sent in each transaction
+        amount: u64,
+    },
+    /// writeData of one entry to a data account
+    WriteData {
+        /// Data account
+        account: String,
+        /// Entry size in bytes
+        entry_size: usize,
+    },
+}
+
+impl Workload {
+    /// Send `amount` base units from `account` to itself
+    #[must_use]
+    pub fn self_send(account: &str, amount: u64) -> Self {
+        Self::SelfSend {
+            account: account.to_string(),
+            amount,
+        }
+    }
+
+    /// Write an `entry_size`-byte entry to `account`
+    #[must_use]
+    pub fn write_data(account: &str, entry_size: usize) -> Self {
+        Self::WriteData {
+            account: account.to_string(),
+            entry_size,
+        }
+    }
+
+    /// Principal of every transaction
+    #[must_use]
+    pub fn principal(&self) -> &str {
+        match self {
+            Self::SelfSend { account, .. } | Self::WriteData { account, .. } => account,
+        }
+    }
+
+    /// Body of the `seq`th transaction
+    ///
+    /// Data entries start with the sequence number so no two are equal.
+    #[must_use]
+    pub fn body(&self, seq: u64) -> Value {
+        match self {
+            Self::SelfSend { account, amount } => {
+                TxBody::send_tokens_single(account, &amount.to_string())
+            }
+            Self::WriteData { entry_size, .. } => {
+                let mut entry = seq.to_be_bytes().to_vec();
+                entry.resize((*entry_size).max(entry.len()), 0);
+                TxBody::write_data_hex(&[&hex::encode(entry)])
+            }
+        }
+    }
+}
+
+/// Latency histogram with log-linear buckets
+///
+/// Each power-of-two range of microseconds is split into 8 buckets, so a
+/// reported percentile is at most 12.5% above the true value.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct LatencyHistogram {
+    /// Sample count per bucket, keyed by the bucket's upper bound in µs
+    buckets: BTreeMap<u64, u64>,
+    count: u64,
+    sum_micros: u128,
+    min_micros: u64,
+    max_micros: u64,
+}
+
+impl LatencyHistogram {
+    /// An empty histogram
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add one sample
+    pub fn record(&mut self, latency: Duration) {
+        let micros = u64::try_from(latency.as_micros()).unwrap_or(u64::MAX);
+        *self.buckets.entry(bucket_bound(micros)).or_default() += 1;
+        self.min_micros = if self.count == 0 {
+            micros
+        } else {
+            self.min_micros.min(micros)
+        };
+        self.max_micros = self.max_micros.max(micros);
+        self.sum_micros += u128::from(micros);
+        self.count += 1;
+    }
+
+    /// Add every sample of `other`
+    pub fn merge(&mut self, other: &Self) {
+        if other.count == 0 {
+            return;
+        }
+        for (bound, count) in &other.buckets {
+            *self.buckets.entry(*bound).or_default() += count;
+        }
+        self.min_micros = if self.count == 0 {
+            other.min_micros
+        } else {
+            self.min_micros.min(other.min_micros)
+        };
+        self.max_micros = self.max_micros.max(other.max_micros);
+        self.sum_micros += other.sum_micros;
+        self.count += other.count;
+    }
+
+    /// Number of samples
+    #[must_use]
+    pub const fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// Smallest sample
+    #[must_use]
+    pub const fn min(&self) -> Duration {
+        Duration::from_micros(self.min_micros)
+    }
+
+    /// Largest sample
+    #[must_use]
+    pub const fn max(&self) -> Duration {
+        Duration::from_micros(self.max_micros)
+    }
+
+    /// Mean of the samples
+    #[must_use]
+    pub fn mean(&self) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        let mean = self.sum_micros / u128::from(self.count);
+        Duration::from_micros(u64::try_from(mean).unwrap_or(u64::MAX))
+    }
+
+    /// Latency at or below which `percentile` percent of samples fall
+    ///
+    /// Reported as the upper bound of the bucket holding that sample, capped
+    /// at the largest sample.
+    #[must_use]
+    pub fn percentile(&self, percentile: f64) -> Duration {
+        if self.count == 0 {
+            return Duration::ZERO;
+        }
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            clippy::cast_precision_loss
+        )]
+        let rank = ((percentile.clamp(0.0, 100.0) / 100.0) * self.count as f64).ceil() as u64;
+        let rank = rank.max(1);
+        let mut seen = 0;
+        for (bound, count) in &self.buckets {
+            seen += count;
+            if seen >= rank {
+                return Duration::from_micros((*bound).min(self.max_micros));
+            }
+        }
+        self.max()
+    }
+
+    /// Non-empty buckets as `(upper bound, samples)`, fastest first
+    pub fn buckets(&self) -> impl Iterator<Item = (Duration, u64)> + '_ {
+        self.buckets
+            .iter()
+            .map(|(bound, count)| (Duration::from_micros(*bound), *count))
+    }
+}
+
+impl fmt::Display for LatencyHistogram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.count == 0 {
+            return f.write_str("no samples");
+        }
+        write!(
+            f,
+            "n={} mean={:?} p50={:?} p90={:?} p99={:?} max={:?}",
+            self.count,
+            self.mean(),
+            self.percentile(50.0),
+            self.percentile(90.0),
+            self.percentile(99.0),
+            self.max()
+        )
+    }
+}
+
+/// Inclusive upper bound of the bucket holding `micros`
+fn bucket_bound(micros: u64) -> u64 {
+    if micros < 8 {
+        return micros;
+    }
+    let shift = micros.ilog2() - 3;
+    let bound = ((u128::from(micros >> shift) + 1) << shift) - 1;
+    u64::try_from(bound).unwrap_or(u64::MAX)
+}
+
+/// Results of a [`LoadTest`] run
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LoadReport {
+    /// Rate the run aimed for
+    pub target_tps: f64,
+    /// Wall-clock time from the first batch until every response arrived
+    pub elapsed: Duration,
+    /// Envelopes signed and sent
+    pub sent: u64,
+    /// Envelopes the network accepted
+    pub accepted: u64,
+    /// Envelopes the network rejected
+    pub rejected: u64,
+    /// Envelopes whose `submitMulti` call failed outright
+    pub failed: u64,
+    /// Ticks that waited for an in-flight batch before sending
+    pub throttled: u64,
+    /// Time to build and sign one envelope
+    pub sign_latency: LatencyHistogram,
+    /// Round trip of one `submitMulti` call
+    pub submit_latency: LatencyHistogram,
+    /// Occurrences of each distinct error, hashes masked
+    pub errors: BTreeMap<String, u64>,
+}
+
+impl LoadReport {
+    /// Accepted envelopes per second over the run
+    #[must_use]
+    pub fn achieved_tps(&self) -> f64 {
+        let secs = self.elapsed.as_secs_f64();
+        if secs == 0.0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let accepted = self.accepted as f64;
+        accepted / secs
+    }
+
+    /// Fraction of sent envelopes that were not accepted
+    #[must_use]
+    pub fn error_rate(&self) -> f64 {
+        if self.sent == 0 {
+            return 0.0;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let rate = (self.rejected + self.failed) as f64 / self.sent as f64;
+        rate
+    }
+
+    fn record_error(&mut self, message: &str, count: u64) {
+        *self.errors.entry(error_class(message)).or_default() += count;
+    }
+
+    /// Count the outcome of one `submitMulti` call carrying `sent` envelopes
+    fn absorb(&mut self, sent: u64, latency: Duration, response: Result<Value, JsonRpcError>) {
+        let response = match response {
+            Ok(response) => response,
+            Err(e) => {
+                self.failed += sent;
+                self.record_error(&format!("request failed: {e}"), sent);
+                return;
+            }
+        };
+        self.submit_latency.record(latency);
+        let entries = response.as_array().map(Vec::as_slice).unwrap_or_default();
+        for i in 0..usize::try_from(sent).unwrap_or(usize::MAX) {
+            match entries.get(i).map(submission_outcome) {
+                Some((_, None)) => self.accepted += 1,
+                Some((_, Some(error))) => {
+                    self.rejected += 1;
+                    self.record_error(&error, 1);
+                }
+                None => {
+                    self.rejected += 1;
+                    self.record_error(NO_RESPONSE, 1);
+                }
+            }
+        }
+    }
+}
+
+impl fmt::Display for LoadReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "target {:.1} tps, achieved {:.1} tps over {:.1}s",
+            self.target_tps,
+            self.achieved_tps(),
+            self.elapsed.as_secs_f64()
+        )?;
+        writeln!(
+            f,
+            "sent {}: {} accepted, {} rejected, {} failed ({:.2}% errors), {} throttled ticks",
+            self.sent,
+            self.accepted,
+            self.rejected,
+            self.failed,
+            self.error_rate() * 100.0,
+            self.throttled
+        )?;
+        writeln!(f, "sign:   {}", self.sign_latency)?;
+        writeln!(f, "submit: {}", self.submit_latency)?;
+        for (error, count) in &self.errors {
+            writeln!(f, "  {count:>8}  {error}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Mask hashes and other long hex runs so equal errors share one entry
+fn error_class(message: &str) -> String {
+    let mut out = String::with_capacity(message.len());
+    let mut run = String::new();
+    for c in message.chars().chain(std::iter::once(' ')) {
+        if c.is_ascii_hexdigit() {
+            run.push(c);
+            continue;
+        }
+        if run.len() >= 16 {
+            out.push_str("<hash>");
+        } else {
+            out.push_str(&run);
+        }
+        run.clear();
+        out.push(c);
+    }
+    out.pop();
+    out
+}
+
+/// Configuration of a sustained-throughput run
+#[derive(Debug, Clone)]
+pub struct LoadTest {
+    workload: Workload,
+    tps: f64,
+    duration: Duration,
+    batch_size: usize,
+    max_in_flight: usize,
+}
+
+impl LoadTest {
+    /// Send `workload` with the default rate, duration and batching
+    #[must_use]
+    pub const fn new(workload: Workload) -> Self {
+        Self {
+            workload,
+            tps: DEFAULT_TPS,
+            duration: DEFAULT_DURATION,
+            batch_size: DEFAULT_BATCH_SIZE,
+            max_in_flight: DEFAULT_MAX_IN_FLIGHT,
+        }
+    }
+
+    /// Target transactions per second
+    #[must_use]
+    pub fn tps(mut self, tps: f64) -> Self {
+        if tps.is_finite() && tps > 0.0 {
+            self.tps = tps;
+        }
+        self
+    }
+
+    /// How long to keep sending
+    #[must_use]
+    pub const fn duration(mut self, duration: Duration) -> Self {
+        self.duration = duration;
+        self
+    }
+
+    /// Envelopes per `submitMulti` call (at least 1)
+    #[must_use]
+    pub fn batch_size(mut self, batch_size: usize) -> Self {
+        self.batch_size = batch_size.max(1);
+        self
+    }
+
+    /// Concurrent `submitMulti` calls (at least 1)
+    #[must_use]
+    pub fn max_in_flight(mut self, max_in_flight: usize) -> Self {
+        self.max_in_flight = max_in_flight.max(1);
+        self
+    }
+
+    /// Time between batches that gives the target rate
+    #[must_use]
+    pub fn batch_interval(&self) -> Duration {
+        #[allow(clippy::cast_precision_loss)]
+        let batch = self.batch_size as f64;
+        Duration::from_secs_f64(batch / self.tps)
+    }
+
+    /// Run the test, signing with `key` for `signer_url`
+    ///
+    /// The signer version is read once up front. Rejections and failed
+    /// requests are counted in the report; only a signer that cannot be
+    /// verified or cannot sign stops the run.
+    pub async fn run(
+        &self,
+        client: Arc<AccumulateClient>,
+        key: Arc<dyn Signer>,
+        signer_url: &str,
+    ) -> Result<LoadReport, LoadError> {
+        let mut signer = SmartSigner::from_signer(&client, key, signer_url);
+        signer
+            .verify_key_on_page()
+            .await
+            .map_err(LoadError::Signing)?;
+
+        let mut report = LoadReport {
+            target_tps: self.tps,
+            ..LoadReport::default()
+        };
+        let mut ticker = tokio::time::interval(self.batch_interval());
+        ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+        let mut in_flight = JoinSet::new();
+        let mut seq = 0;
+        let mut last_timestamp = 0;
+        let start = Instant::now();
+        let deadline = start + self.duration;
+
+        loop {
+            ticker.tick().await;
+            if Instant::now() >= deadline {
+                break;
+            }
+            while let Some(done) = in_flight.try_join_next() {
+                absorb_task(&mut report, done);
+            }
+            if in_flight.len() >= self.max_in_flight {
+                report.throttled += 1;
+                while in_flight.len() >= self.max_in_flight {
+                    let Some(done) = in_flight.join_next().await else {
+                        break;
+                    };
+                    absorb_task(&mut report, done);
+                }
+            }
+
+            let mut requests = Vec::with_capacity(self.batch_size);
+            for _ in 0..self.batch_size {
+                let started = Instant::now();
+                let envelope = self
+                    .sign_next(&signer, seq, &mut last_timestamp)
+                    .await
+                    .map_err(LoadError::Signing)?;
+                report.sign_latency.record(started.elapsed());
+                requests.push(json!({ "envelope": envelope }));
+                seq += 1;
+            }
+
+            let sent = requests.len() as u64;
+            report.sent += sent;
+            let client = Arc::clone(&client);
+            in_flight.spawn(async move {
+                let started = Instant::now();
+                let response = client
+                    .v3_client
+                    .call_v3::<Value>("submitMulti", json!(requests))
+                    .await;
+                (sent, started.elapsed(), response)
+            });
+        }
+
+        while let Some(done) = in_flight.join_next().await {
+            absorb_task(&mut report, done);
+        }
+        report.elapsed = start.elapsed();
+        Ok(report)
+    }
+
+    /// Sign the `seq`th transaction with a timestamp above `last_timestamp`
+    ///
+    /// The network rejects a signature whose timestamp is not greater than
+    /// the signer's previous one, and signing is fast enough to repeat a
+    /// microsecond.
+    async fn sign_next(
+        &self,
+        signer: &SmartSigner<'_>,
+        seq: u64,
+        last_timestamp: &mut u64,
+    ) -> Result<Value, JsonRpcError> {
+        let body = self.workload.body(seq);
+        loop {
+            let envelope = signer.sign(self.workload.principal(), &body, None)?;
+            let timestamp = envelope["signatures"][0]["timestamp"].as_u64().unwrap_or(0);
+            if timestamp > *last_timestamp {
+                *last_timestamp = timestamp;
+                return Ok(envelope);
+            }
+            tokio::task::yield_now().await;
+        }
+    }
+}
+
+type SubmitOutcome = (u64, Duration, Result<Value, JsonRpcError>);
+
+fn absorb_task(report: &mut LoadReport, done: Result<SubmitOutcome, tokio::task::JoinError>) {
+    match done {
+        Ok((sent, latency, response)) => report.absorb(sent, latency, response),
+        Err(e) => report.record_error(&format!("submit task failed: {e}"), 1),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_histogram_percentiles() {
+        let mut histogram = LatencyHistogram::new();
+        for ms in 1..=100 {
+            histogram.record(Duration::from_millis(ms));
+        }
+        assert_eq!(histogram.count(), 100);
+        assert_eq!(histogram.min(), Duration::from_millis(1));
+        assert_eq!(histogram.max(), Duration::from_millis(100));
+        assert_eq!(histogram.mean(), Duration::from_micros(50_500));
+
+        let p50 = histogram.percentile(50.0);
+        assert!(p50 >= Duration::from_millis(50) && p50 <= Duration::from_micros(56_250));
+        assert_eq!(histogram.percentile(100.0), Duration::from_millis(100));
+        assert_eq!(histogram.buckets().map(|(_, n)| n).sum::<u64>(), 100);
+
+        let mut merged = LatencyHistogram::new();
+        merged.merge(&histogram);
+        merged.merge(&histogram);
+        assert_eq!(merged.count(), 200);
+        assert_eq!(merged.percentile(50.0), p50);
+    }
+
+    #[test]
+    fn test_bucket_bounds() {
+        assert_eq!(bucket_bound(0), 0);
+        assert_eq!(bucket_bound(7), 7);
+        assert_eq!(bucket_bound(8), 8);
+        assert_eq!(bucket_bound(17), 17);
+        assert_eq!(bucket_bound(1000), 1023);
+        assert_eq!(bucket_bound(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_absorb_counts_outcomes() {
+        let mut report = LoadReport::default();
+        let hash = "ab".repeat(32);
+        let response = json!([
+            {"success": true, "status": {"txID": format!("acc://{hash}@a.acme")}},
+            [{"success": false, "message": format!("transaction {hash} insufficient balance")}],
+            [{"success": false, "message": format!("transaction {} insufficient balance", "cd".repeat(32))}]
+        ]);
+        report.absorb(4, Duration::from_millis(20), Ok(response));
+        assert_eq!(report.accepted, 1);
+        assert_eq!(report.rejected, 3);
+        assert_eq!(
+            report.errors.get("transaction <hash> insufficient balance"),
+            Some(&2)
+        );
+        assert_eq!(report.errors.get(NO_RESPONSE), Some(&1));
+
+        report.absorb(
+            2,
+            Duration::from_millis(5),
+            Err(JsonRpcError::General("connection refused".to_string())),
+        );
+        report.sent = 6;
+        assert_eq!(report.failed, 2);
+        assert_eq!(report.submit_latency.count(), 1);
+        assert!((report.error_rate() - 5.0 / 6.0).abs() < 1e-9);
+        assert!(report.to_string().contains("insufficient balance"));
+    }
+
+    #[test]
+    fn test_workload_bodies() {
+        let send = Workload::self_send("acc://a.acme/tokens", 5);
+        assert_eq!(send.body(0)["to"][0]["url"], "acc://a.acme/tokens");
+
+        let write = Workload::write_data("acc://a.acme/data", 32);
+        let first = write.body(0);
+        assert_eq!(first["type"], "writeData");
+        assert_ne!(first, write.body(1));
+        assert_eq!(
+            LoadTest::new(write)
+                .tps(50.0)
+                .batch_size(5)
+                .batch_interval(),
+            Duration::from_millis(100)
+        );
+    }
+}
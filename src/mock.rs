@@ -0,0 +1,587 @@
+//! Mock JSON-RPC transport and test harness for offline tests
+//!
+//! [`MockTransport`] answers calls from canned responses, one-shot responses
+//! queued per method, and optionally a fallback (a [`SimulatedNetwork`] or a
+//! live node). It records every call with its answer, so tests can assert on
+//! what was sent, and a recording saved from a live node can be replayed
+//! later without one.
+//!
+//! Like the simulator it works in process as an [`AccumulateRpc`] transport,
+//! or over HTTP on a loopback port for [`AccumulateClient`],
+//! [`SmartSigner`] and [`QuickStart`]:
+//!
+//! ```
+//! use accumulate_client::mock::MockTransport;
+//! use serde_json::json;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let mock = MockTransport::new();
+//! mock.on("network-status", json!({"oracle": {"price": 5_000_000}}));
+//! let client = mock.client().await?;
+//!
+//! let status = client.v3_client.call::<serde_json::Value>("network-status", json!({})).await?;
+//! assert_eq!(status["oracle"]["price"], 5_000_000);
+//! mock.assert_called_times("network-status", 1);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`TestHarness`] puts a mock in front of a fresh simulated network, so
+//! flows run against the simulator while single calls can be overridden,
+//! e.g. to make the faucet fail.
+
+use crate::amounts::ACME_BASE_UNITS;
+use crate::client::AccumulateClient;
+use crate::generated::api_methods::AccumulateRpc;
+use crate::helpers::{AdiInfo, QuickStart, SmartSigner, Wallet};
+use crate::json_rpc_client::JsonRpcError;
+use crate::simulator::{loopback_client, serve_json_rpc, RpcHandler, SimulatedNetwork};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::{BTreeMap, VecDeque};
+use std::path::Path;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use url::Url;
+
+/// JSON-RPC code of a call the mock has no answer for
+pub const UNEXPECTED_CALL: i32 = -32601;
+
+/// The answer to a mocked call
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum MockResponse {
+    /// The JSON-RPC `result`
+    Result(Value),
+    /// A JSON-RPC error
+    Error {
+        /// Error code
+        code: i32,
+        /// Error message
+        message: String,
+    },
+}
+
+impl MockResponse {
+    fn into_result(self) -> Result<Value, (i32, String)> {
+        match self {
+            Self::Result(value) => Ok(value),
+            Self::Error { code, message } => Err((code, message)),
+        }
+    }
+}
+
+/// A call the mock answered, as recorded and replayed
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MockCall {
+    /// JSON-RPC method
+    pub method: String,
+    /// Call parameters
+    pub params: Value,
+    /// What the mock answered
+    pub response: MockResponse,
+}
+
+/// Where calls without a canned or queued response go
+#[derive(Debug, Clone)]
+enum Fallback {
+    None,
+    Simulator(SimulatedNetwork),
+    Proxy { base: Url, http: reqwest::Client },
+}
+
+#[derive(Debug)]
+struct State {
+    /// One-shot responses, used before the canned ones
+    queued: BTreeMap<String, VecDeque<MockResponse>>,
+    /// Responses to every call of a method
+    canned: BTreeMap<String, MockResponse>,
+    fallback: Fallback,
+    calls: Vec<MockCall>,
+}
+
+/// A JSON-RPC transport answering from canned responses
+///
+/// A call is answered by the first of: the next response queued with
+/// [`once`](Self::once) for its method, the response set with
+/// [`on`](Self::on), the fallback, or an [`UNEXPECTED_CALL`] error. Clones
+/// share their responses and recorded calls.
+#[derive(Debug, Clone)]
+pub struct MockTransport {
+    state: Arc<Mutex<State>>,
+}
+
+impl Default for MockTransport {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MockTransport {
+    /// A mock with no responses
+    #[must_use]
+    pub fn new() -> Self {
+        Self::with_fallback(Fallback::None)
+    }
+
+    /// A mock answering calls it has no response for from `network`
+    #[must_use]
+    pub fn over(network: SimulatedNetwork) -> Self {
+        Self::with_fallback(Fallback::Simulator(network))
+    }
+
+    /// A mock forwarding calls it has no response for to the node at `base`
+    ///
+    /// Calls keep their path, so a client's `/v2` and `/v3` endpoints map
+    /// to `base/v2` and `base/v3`. Record a session this way and
+    /// [`save`](Self::save) it to replay it offline.
+    #[must_use]
+    pub fn proxy(base: Url) -> Self {
+        Self::with_fallback(Fallback::Proxy {
+            base,
+            http: reqwest::Client::new(),
+        })
+    }
+
+    /// A mock answering each method with its recorded responses, in order
+    ///
+    /// Calls are matched by method only, since parameters such as signature
+    /// timestamps differ between runs.
+    #[must_use]
+    pub fn replay(calls: impl IntoIterator<Item = MockCall>) -> Self {
+        let mock = Self::new();
+        for call in calls {
+            mock.queue(&call.method, call.response);
+        }
+        mock
+    }
+
+    /// [`replay`](Self::replay) a recording written by [`save`](Self::save)
+    pub fn load(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        let calls: Vec<MockCall> = serde_json::from_slice(&std::fs::read(path)?)?;
+        Ok(Self::replay(calls))
+    }
+
+    /// Write the calls answered so far as JSON
+    pub fn save(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        std::fs::write(path, serde_json::to_vec_pretty(&self.calls())?)
+    }
+
+    fn with_fallback(fallback: Fallback) -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                queued: BTreeMap::new(),
+                canned: BTreeMap::new(),
+                fallback,
+                calls: Vec::new(),
+            })),
+        }
+    }
+
+    /// Answer every call of `method` with `result`
+    pub fn on(&self, method: &str, result: Value) {
+        self.lock()
+            .canned
+            .insert(method.to_string(), MockResponse::Result(result));
+    }
+
+    /// Fail every call of `method` with a JSON-RPC error
+    pub fn on_error(&self, method: &str, code: i32, message: &str) {
+        self.lock().canned.insert(
+            method.to_string(),
+            MockResponse::Error {
+                code,
+                message: message.to_string(),
+            },
+        );
+    }
+
+    /// Answer the next call of `method` with `result`
+    pub fn once(&self, method: &str, result: Value) {
+        self.queue(method, MockResponse::Result(result));
+    }
+
+    /// Fail the next call of `method` with a JSON-RPC error
+    pub fn once_error(&self, method: &str, code: i32, message: &str) {
+        self.queue(
+            method,
+            MockResponse::Error {
+                code,
+                message: message.to_string(),
+            },
+        );
+    }
+
+    fn queue(&self, method: &str, response: MockResponse) {
+        self.lock()
+            .queued
+            .entry(method.to_string())
+            .or_default()
+            .push_back(response);
+    }
+
+    /// Forget the canned and queued responses of `method`
+    pub fn clear(&self, method: &str) {
+        let mut state = self.lock();
+        state.canned.remove(method);
+        state.queued.remove(method);
+    }
+
+    /// Every call answered so far, in order
+    #[must_use]
+    pub fn calls(&self) -> Vec<MockCall> {
+        self.lock().calls.clone()
+    }
+
+    /// The calls of `method` answered so far, in order
+    #[must_use]
+    pub fn calls_to(&self, method: &str) -> Vec<MockCall> {
+        self.lock()
+            .calls
+            .iter()
+            .filter(|call| call.method == method)
+            .cloned()
+            .collect()
+    }
+
+    /// How often `method` was called
+    #[must_use]
+    pub fn call_count(&self, method: &str) -> usize {
+        self.lock()
+            .calls
+            .iter()
+            .filter(|call| call.method == method)
+            .count()
+    }
+
+    /// Forget the calls recorded so far
+    pub fn reset_calls(&self) {
+        self.lock().calls.clear();
+    }
+
+    /// Assert that `method` was called at least once
+    ///
+    /// # Panics
+    ///
+    /// When it was not, listing the methods that were called.
+    pub fn assert_called(&self, method: &str) {
+        assert!(
+            self.call_count(method) > 0,
+            "expected a call to {method}, got {:?}",
+            self.methods()
+        );
+    }
+
+    /// Assert that `method` was called exactly `times` times
+    ///
+    /// # Panics
+    ///
+    /// When the count differs.
+    pub fn assert_called_times(&self, method: &str, times: usize) {
+        let count = self.call_count(method);
+        assert!(
+            count == times,
+            "expected {times} calls to {method}, got {count}"
+        );
+    }
+
+    /// Assert that `method` was never called
+    ///
+    /// # Panics
+    ///
+    /// When it was.
+    pub fn assert_not_called(&self, method: &str) {
+        self.assert_called_times(method, 0);
+    }
+
+    fn methods(&self) -> Vec<String> {
+        self.lock()
+            .calls
+            .iter()
+            .map(|call| call.method.clone())
+            .collect()
+    }
+
+    /// Answer `method` and record the call
+    ///
+    /// `path` is the request path the fallback proxy forwards to.
+    pub async fn answer(&self, path: &str, method: &str, params: Value) -> MockResponse {
+        let (prepared, fallback) = {
+            let mut state = self.lock();
+            let queued = state.queued.get_mut(method).and_then(VecDeque::pop_front);
+            let prepared = queued.or_else(|| state.canned.get(method).cloned());
+            (prepared, state.fallback.clone())
+        };
+        let response = match prepared {
+            Some(response) => response,
+            None => fallback.answer(path, method, &params).await,
+        };
+        self.lock().calls.push(MockCall {
+            method: method.to_string(),
+            params,
+            response: response.clone(),
+        });
+        response
+    }
+
+    /// Answer JSON-RPC over HTTP on a loopback port until the runtime shuts
+    /// down; returns the base URL
+    pub async fn serve(&self) -> std::io::Result<Url> {
+        serve_json_rpc(self.clone()).await
+    }
+
+    /// [`serve`](Self::serve) and connect a client polling every millisecond
+    pub async fn client(&self) -> Result<AccumulateClient, JsonRpcError> {
+        loopback_client(self.serve().await).await
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl Fallback {
+    async fn answer(&self, path: &str, method: &str, params: &Value) -> MockResponse {
+        let error = |code, message: String| MockResponse::Error { code, message };
+        match self {
+            Self::None => error(UNEXPECTED_CALL, format!("unexpected call to {method}")),
+            Self::Simulator(network) => match network.call(method, params) {
+                Ok(result) => MockResponse::Result(result),
+                Err(e) => error(e.code(), e.to_string()),
+            },
+            Self::Proxy { base, http } => {
+                let url = match base.join(path.trim_start_matches('/')) {
+                    Ok(url) => url,
+                    Err(e) => return error(-32600, e.to_string()),
+                };
+                let request =
+                    json!({"jsonrpc": "2.0", "id": 1, "method": method, "params": params});
+                let reply: Value = match http.post(url).json(&request).send().await {
+                    Ok(response) => match response.json().await {
+                        Ok(reply) => reply,
+                        Err(e) => return error(-32700, e.to_string()),
+                    },
+                    Err(e) => return error(503, e.to_string()),
+                };
+                reply.get("error").map_or_else(
+                    || MockResponse::Result(reply.get("result").cloned().unwrap_or(Value::Null)),
+                    |e| {
+                        let code = e.get("code").and_then(Value::as_i64);
+                        let message = e.get("message").and_then(Value::as_str);
+                        error(
+                            code.and_then(|code| i32::try_from(code).ok()).unwrap_or(-1),
+                            message.unwrap_or_default().to_string(),
+                        )
+                    },
+                )
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl RpcHandler for MockTransport {
+    async fn handle(
+        &self,
+        path: &str,
+        method: &str,
+        params: Value,
+    ) -> Result<Value, (i32, String)> {
+        self.answer(path, method, params).await.into_result()
+    }
+}
+
+#[async_trait]
+impl AccumulateRpc for MockTransport {
+    async fn rpc_call<TParams: Serialize + Send + Sync, TResult: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        params: &TParams,
+    ) -> Result<TResult, crate::errors::Error> {
+        let params = serde_json::to_value(params)?;
+        let result = self
+            .answer("/v3", method, params)
+            .await
+            .into_result()
+            .map_err(|(code, message)| crate::errors::Error::rpc(code, message))?;
+        Ok(serde_json::from_value(result)?)
+    }
+}
+
+/// A simulated network behind a [`MockTransport`], with a [`QuickStart`]
+/// connected to it
+///
+/// Flows run against the simulator; responses set on [`mock`](Self::mock)
+/// take precedence, and every call is recorded there.
+#[derive(Debug)]
+pub struct TestHarness {
+    network: SimulatedNetwork,
+    mock: MockTransport,
+    quick: QuickStart,
+}
+
+impl TestHarness {
+    /// A harness over a fresh [`SimulatedNetwork`]
+    pub async fn new() -> Result<Self, JsonRpcError> {
+        Self::over(SimulatedNetwork::new()).await
+    }
+
+    /// A harness over `network`, e.g. one with a custom oracle price
+    pub async fn over(network: SimulatedNetwork) -> Result<Self, JsonRpcError> {
+        let mock = MockTransport::over(network.clone());
+        let quick = QuickStart::from_client(Arc::new(mock.client().await?));
+        Ok(Self {
+            network,
+            mock,
+            quick,
+        })
+    }
+
+    /// The simulated network, to fund accounts or inspect state
+    #[must_use]
+    pub const fn network(&self) -> &SimulatedNetwork {
+        &self.network
+    }
+
+    /// The mock in front of the network, to override calls and assert on them
+    #[must_use]
+    pub const fn mock(&self) -> &MockTransport {
+        &self.mock
+    }
+
+    /// A [`QuickStart`] connected to the network
+    #[must_use]
+    pub const fn quick(&self) -> &QuickStart {
+        &self.quick
+    }
+
+    /// The client connected to the network
+    #[must_use]
+    pub fn client(&self) -> &AccumulateClient {
+        self.quick.client()
+    }
+
+    /// A new wallet whose lite token account holds `acme` ACME
+    pub fn funded_wallet(&self, acme: u64) -> Result<Wallet, JsonRpcError> {
+        let wallet = self.quick.create_wallet();
+        self.network
+            .fund(
+                &wallet.lite_token_account,
+                acme.saturating_mul(ACME_BASE_UNITS),
+            )
+            .map_err(|e| JsonRpcError::General(e.to_string()))?;
+        Ok(wallet)
+    }
+
+    /// An ADI `name` with credits on its key page, ready to sign, and the
+    /// funded wallet that created it
+    pub async fn adi(&self, name: &str) -> Result<(Wallet, AdiInfo), JsonRpcError> {
+        let wallet = self.funded_wallet(100)?;
+        let adi = self.quick.setup_adi(&wallet, name).await?;
+        let result = self.quick.buy_credits_for_adi(&wallet, &adi, 1_000).await?;
+        if !result.success {
+            return Err(JsonRpcError::General(result.error.unwrap_or_default()));
+        }
+        Ok((wallet, adi))
+    }
+
+    /// A signer for the key page of `adi`
+    #[must_use]
+    pub fn signer(&self, adi: &AdiInfo) -> SmartSigner<'_> {
+        SmartSigner::new(self.client(), adi.keypair().clone(), &adi.key_page_url)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::TxBody;
+
+    #[tokio::test]
+    async fn test_canned_queued_and_unexpected() {
+        let mock = MockTransport::new();
+        mock.on("network-status", json!({"oracle": {"price": 7}}));
+        mock.once("network-status", json!({"oracle": {"price": 1}}));
+        mock.once_error("faucet", 429, "slow down");
+
+        let price = |v: Value| v["oracle"]["price"].as_u64().unwrap();
+        let status: Value = mock.rpc_call("network-status", &json!({})).await.unwrap();
+        assert_eq!(price(status), 1);
+        let status: Value = mock.rpc_call("network-status", &json!({})).await.unwrap();
+        assert_eq!(price(status), 7);
+
+        let err = mock
+            .rpc_call::<_, Value>("faucet", &json!({}))
+            .await
+            .unwrap_err();
+        assert_eq!(err.rpc_code(), Some(429));
+        let err = mock
+            .rpc_call::<_, Value>("faucet", &json!({}))
+            .await
+            .unwrap_err();
+        assert_eq!(err.rpc_code(), Some(UNEXPECTED_CALL));
+
+        mock.assert_called_times("network-status", 2);
+        mock.assert_not_called("submit");
+        assert_eq!(mock.calls().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_record_and_replay() {
+        let network = SimulatedNetwork::new();
+        let recorder = MockTransport::over(network);
+        let client = recorder.client().await.unwrap();
+        let wallet = Wallet::generate();
+        client
+            .request_faucet(&wallet.lite_token_account)
+            .await
+            .unwrap();
+
+        let path = std::env::temp_dir().join(format!("mock-{}.json", std::process::id()));
+        recorder.save(&path).unwrap();
+        let replay = MockTransport::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let client = replay.client().await.unwrap();
+        let submission = client
+            .request_faucet(&wallet.lite_token_account)
+            .await
+            .unwrap();
+        assert_eq!(
+            replay.calls_to("faucet")[0].response,
+            recorder.calls()[0].response
+        );
+        assert!(submission.status.is_some());
+        assert!(client
+            .request_faucet(&wallet.lite_token_account)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_harness_runs_signer_flows() {
+        let harness = TestHarness::new().await.unwrap();
+        let (_, adi) = harness.adi("harness").await.unwrap();
+        let mut signer = harness.signer(&adi);
+        let result = signer
+            .sign_submit_and_wait(
+                &adi.url,
+                &TxBody::create_data_account(&format!("{}/data", adi.url)),
+                None,
+                30,
+            )
+            .await;
+        assert!(result.success, "{:?}", result.error);
+        assert!(harness
+            .network()
+            .account(&format!("{}/data", adi.url))
+            .is_some());
+        harness.mock().assert_called("submit");
+
+        harness.mock().on_error("faucet", 503, "faucet down");
+        let wallet = harness.quick().create_wallet();
+        assert!(harness.quick().fund_wallet(&wallet, 1).await.is_err());
+    }
+}
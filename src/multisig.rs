@@ -0,0 +1,1031 @@
+//! Multi-signature coordination for key pages with an accept threshold
+//!
+//! A key page whose accept threshold is above one needs signatures from
+//! several keys before the network executes a transaction. The parties rarely
+//! share a process, so the envelope travels between them:
+//!
+//! 1. the initiator signs with [`PartialEnvelope::initiate`] and exports the
+//!    result with [`PartialEnvelope::to_json`] or [`PartialEnvelope::to_binary`];
+//! 2. each co-signer imports it, refreshes its signer version and adds its
+//!    signature with [`PartialEnvelope::co_sign_verified`];
+//! 3. whoever holds it last calls [`PartialEnvelope::submit_when_ready`], which
+//!    refuses to submit until every signing page has met its threshold.
+//!
+//! Imported signatures are verified against the transaction hash before they
+//! are accepted, so a corrupted or mismatched export fails on import rather
+//! than on submission.
+//!
+//! A transaction that was submitted with too few signatures waits in its
+//! principal's pending set instead. [`pending_transactions`] lists that set
+//! and [`SmartSigner::sign_pending_and_wait`] adds a signature to one of its
+//! transactions by hash.
+//!
+//! A signer whose key page lives on another partition than the principal is
+//! signing across partitions. [`route_remote_signatures`] wraps each such
+//! signature in a remote signature addressed to the principal's partition,
+//! citing the signature's hash as its cause, the way the Go CLI forwards
+//! them.
+//!
+//! The binary export uses the Go `messaging.Envelope` layout (signatures in
+//! field 1, transactions in field 3) and carries ED25519 signatures only.
+
+use crate::client::AccumulateClient;
+use crate::codec::hex_utils::{parse_hex_field, parse_hex_hash};
+use crate::codec::reader::{BinaryReader, DecodingError};
+use crate::codec::signing::{
+    compute_signature_metadata_hash, create_signing_preimage, signature_types,
+};
+use crate::codec::unmarshal::unmarshal_transaction;
+use crate::codec::writer::BinaryWriter;
+use crate::helpers::{
+    marshal_body_to_binary, marshal_header_json, submit_envelope_and_wait, HeaderOptions,
+    KeyPageState, SignatureOptions, SmartSigner, TxResult,
+};
+use crate::json_rpc_client::JsonRpcError;
+use crate::routing::{partition_url, RoutingTable};
+use crate::types::{PendingQuery, RangeOptions};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde_json::{json, Value};
+use std::collections::BTreeSet;
+use thiserror::Error;
+
+/// Why a partially-signed envelope could not be built, extended or submitted
+#[derive(Error, Debug)]
+pub enum MultisigError {
+    /// The envelope does not hold exactly one well-formed transaction
+    #[error("Malformed envelope: {0}")]
+    Malformed(String),
+
+    /// A signature covers a different transaction
+    #[error("Signature is for transaction {found}, expected {expected}")]
+    HashMismatch {
+        /// Hash of the envelope's transaction (hex)
+        expected: String,
+        /// Hash the signature claims (hex)
+        found: String,
+    },
+
+    /// A signature does not verify over the transaction
+    #[error("Invalid signature by {signer}: {reason}")]
+    InvalidSignature {
+        /// Signer URL of the offending signature
+        signer: String,
+        /// What failed
+        reason: String,
+    },
+
+    /// The key has already signed this envelope
+    #[error("Key {0} has already signed this transaction")]
+    AlreadySigned(String),
+
+    /// The signature cannot be carried in the binary export
+    #[error("Unsupported signature type for binary export: {0}")]
+    UnsupportedSignature(String),
+
+    /// The binary export could not be decoded
+    #[error("Decoding failed: {0}")]
+    Decode(#[from] DecodingError),
+
+    /// A signing page has fewer accepting signatures than its threshold
+    #[error("Signer {signer} has {signatures} of {threshold} required signatures")]
+    ThresholdNotMet {
+        /// Key page (or lite identity) URL
+        signer: String,
+        /// Accepting signatures from distinct keys on the page
+        signatures: usize,
+        /// The page's accept threshold
+        threshold: u64,
+    },
+
+    /// The routing table has no partition for an account
+    #[error("No partition routes {0}")]
+    NoRoute(String),
+
+    /// Signing or querying the network failed
+    #[error(transparent)]
+    Rpc(#[from] JsonRpcError),
+}
+
+/// How far a signing page is from its accept threshold
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ThresholdStatus {
+    /// Key page (or lite identity) URL
+    pub signer: String,
+    /// Accepting signatures from distinct keys on the page
+    pub signatures: usize,
+    /// The page's accept threshold
+    pub threshold: u64,
+}
+
+impl ThresholdStatus {
+    /// Whether the page has enough signatures
+    #[must_use]
+    pub const fn is_met(&self) -> bool {
+        self.signatures as u64 >= self.threshold
+    }
+}
+
+/// A transaction and the signatures gathered for it so far
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PartialEnvelope {
+    transaction: Value,
+    hash: [u8; 32],
+    signatures: Vec<Value>,
+}
+
+impl PartialEnvelope {
+    /// Sign a new transaction as its initiator
+    ///
+    /// Uses the signer's cached version; call
+    /// [`SmartSigner::verify_key_on_page`] first if it may be stale.
+    pub fn initiate(
+        signer: &SmartSigner<'_>,
+        principal: &str,
+        body: &Value,
+        options: &HeaderOptions,
+    ) -> Result<Self, MultisigError> {
+        Self::from_envelope(&signer.sign_with_options(principal, body, options)?)
+    }
+
+    /// Wrap an envelope in its JSON form, as returned by [`SmartSigner::sign`]
+    ///
+    /// The envelope must hold exactly one transaction, and every signature
+    /// must verify over that transaction's hash.
+    pub fn from_envelope(envelope: &Value) -> Result<Self, MultisigError> {
+        let transaction = match envelope.get("transaction") {
+            Some(Value::Array(transactions)) if transactions.len() == 1 => transactions[0].clone(),
+            Some(Value::Array(transactions)) => {
+                return Err(MultisigError::Malformed(format!(
+                    "expected one transaction, found {}",
+                    transactions.len()
+                )))
+            }
+            Some(transaction @ Value::Object(_)) => transaction.clone(),
+            _ => return Err(MultisigError::Malformed("no transaction".to_string())),
+        };
+        let mut partial = Self::new(transaction)?;
+        for signature in envelope
+            .get("signatures")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            partial.add_signature(signature.clone())?;
+        }
+        Ok(partial)
+    }
+
+    /// An envelope for `transaction` with no signatures yet
+    pub fn new(transaction: Value) -> Result<Self, MultisigError> {
+        let hash = crate::codec::hashes::tx_hash(&transaction)?;
+        Ok(Self {
+            transaction,
+            hash,
+            signatures: Vec::new(),
+        })
+    }
+
+    /// The transaction being signed (`{header, body}`)
+    #[must_use]
+    pub const fn transaction(&self) -> &Value {
+        &self.transaction
+    }
+
+    /// Hash every signature covers
+    #[must_use]
+    pub const fn transaction_hash(&self) -> [u8; 32] {
+        self.hash
+    }
+
+    /// Signatures gathered so far
+    #[must_use]
+    pub fn signatures(&self) -> &[Value] {
+        &self.signatures
+    }
+
+    /// Whether `public_key` has already signed
+    #[must_use]
+    pub fn is_signed_by(&self, public_key: &[u8]) -> bool {
+        let key = hex::encode(public_key);
+        self.signatures.iter().any(|sig| {
+            sig.get("publicKey")
+                .and_then(Value::as_str)
+                .is_some_and(|k| k.eq_ignore_ascii_case(&key))
+        })
+    }
+
+    /// Verify a signature produced elsewhere and add it
+    ///
+    /// The signature must be an ED25519 signature over this transaction, from
+    /// a key that has not signed yet.
+    pub fn add_signature(&mut self, signature: Value) -> Result<(), MultisigError> {
+        let signer = str_field(&signature, "signer")
+            .unwrap_or_default()
+            .to_string();
+        let expected = hex::encode(self.hash);
+        match str_field(&signature, "transactionHash") {
+            Some(found) if found.eq_ignore_ascii_case(&expected) => {}
+            found => {
+                return Err(MultisigError::HashMismatch {
+                    expected,
+                    found: found.unwrap_or_default().to_string(),
+                })
+            }
+        }
+        let public_key = verify_ed25519(&signature, &self.hash)
+            .map_err(|reason| MultisigError::InvalidSignature { signer, reason })?;
+        if self.is_signed_by(&public_key) {
+            return Err(MultisigError::AlreadySigned(hex::encode(public_key)));
+        }
+        self.signatures.push(signature);
+        Ok(())
+    }
+
+    /// Add `signer`'s signature using its cached version
+    pub fn co_sign(&mut self, signer: &SmartSigner<'_>) -> Result<(), MultisigError> {
+        self.co_sign_with_options(signer, &SignatureOptions::default())
+    }
+
+    /// Add `signer`'s signature carrying a memo, data or vote
+    pub fn co_sign_with_options(
+        &mut self,
+        signer: &SmartSigner<'_>,
+        options: &SignatureOptions,
+    ) -> Result<(), MultisigError> {
+        if self.is_signed_by(&signer.public_key()) {
+            return Err(MultisigError::AlreadySigned(hex::encode(
+                signer.public_key(),
+            )));
+        }
+        let signature = signer.sign_hash_with_options(&self.hash, options)?;
+        self.add_signature(signature)
+    }
+
+    /// Refresh `signer`'s version from its key page, then add its signature
+    ///
+    /// The network rejects a signature whose signer version does not match
+    /// the page, and the page version changes whenever a key is added or
+    /// removed, so co-signers should prefer this over [`co_sign`](Self::co_sign).
+    pub async fn co_sign_verified(
+        &mut self,
+        signer: &mut SmartSigner<'_>,
+    ) -> Result<(), MultisigError> {
+        signer.verify_key_on_page().await?;
+        self.co_sign(signer)
+    }
+
+    /// Take the signatures of another copy of this envelope
+    ///
+    /// Lets an initiator collect copies signed independently by each
+    /// co-signer. Keys that already signed are skipped.
+    pub fn merge(&mut self, other: &Self) -> Result<(), MultisigError> {
+        if other.hash != self.hash {
+            return Err(MultisigError::HashMismatch {
+                expected: hex::encode(self.hash),
+                found: hex::encode(other.hash),
+            });
+        }
+        for signature in &other.signatures {
+            match self.add_signature(signature.clone()) {
+                Ok(()) | Err(MultisigError::AlreadySigned(_)) => {}
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(())
+    }
+
+    /// Accepting signatures from distinct keys on `page`
+    ///
+    /// Signatures voting to reject or abstain do not count. Fails if a
+    /// counted signature's public key is not valid hex.
+    pub fn signature_count(&self, page: &KeyPageState) -> Result<usize, MultisigError> {
+        let mut indices = BTreeSet::new();
+        for sig in self.signatures.iter().filter(|sig| {
+            str_field(sig, "signer").is_some_and(|s| s.eq_ignore_ascii_case(&page.url))
+                && str_field(sig, "vote").map_or(true, |v| v == "accept")
+        }) {
+            let key = hex_field(sig, "publicKey")
+                .map_err(|reason| MultisigError::Malformed(format!("signature {reason}")))?;
+            indices.extend(page.find_key_index(&key));
+        }
+        Ok(indices.len())
+    }
+
+    /// Whether `page` has met its accept threshold
+    pub fn threshold_met(&self, page: &KeyPageState) -> Result<bool, MultisigError> {
+        Ok(self.signature_count(page)? as u64 >= page.accept_threshold)
+    }
+
+    /// Threshold status of every page that has signed
+    ///
+    /// Queries each signer; a lite identity has a single key and a threshold
+    /// of one.
+    pub async fn threshold_status(
+        &self,
+        client: &AccumulateClient,
+    ) -> Result<Vec<ThresholdStatus>, MultisigError> {
+        let signers: BTreeSet<String> = self
+            .signatures
+            .iter()
+            .filter_map(|sig| str_field(sig, "signer"))
+            .map(str::to_string)
+            .collect();
+
+        let mut statuses = Vec::with_capacity(signers.len());
+        for signer in signers {
+            let record = client.v3().query_account(&signer).await?;
+            let account = &record.account;
+            let page = if account.get("type").and_then(Value::as_str) == Some("liteIdentity") {
+                KeyPageState {
+                    url: signer.clone(),
+                    version: 1,
+                    credit_balance: 0,
+                    accept_threshold: 1,
+                    keys: Vec::new(),
+                }
+            } else {
+                KeyPageState::from_account(account, &signer)
+            };
+            let signatures = if page.keys.is_empty() {
+                self.signatures
+                    .iter()
+                    .filter(|sig| str_field(sig, "signer") == Some(signer.as_str()))
+                    .count()
+            } else {
+                self.signature_count(&page)?
+            };
+            statuses.push(ThresholdStatus {
+                signer,
+                signatures,
+                threshold: page.accept_threshold,
+            });
+        }
+        Ok(statuses)
+    }
+
+    /// Submit once every signing page has met its threshold, and wait
+    ///
+    /// Fails with [`MultisigError::ThresholdNotMet`] without submitting when
+    /// a page is short of signatures; otherwise polls as
+    /// [`SmartSigner::sign_submit_and_wait`] does.
+    pub async fn submit_when_ready(
+        &self,
+        client: &AccumulateClient,
+        max_attempts: u32,
+    ) -> Result<TxResult, MultisigError> {
+        if let Some(status) = self
+            .threshold_status(client)
+            .await?
+            .into_iter()
+            .find(|status| !status.is_met())
+        {
+            return Err(MultisigError::ThresholdNotMet {
+                signer: status.signer,
+                signatures: status.signatures,
+                threshold: status.threshold,
+            });
+        }
+        Ok(submit_envelope_and_wait(client, &self.to_envelope(), max_attempts).await)
+    }
+
+    /// The envelope in its JSON form, ready for `submit`
+    #[must_use]
+    pub fn to_envelope(&self) -> Value {
+        let mut envelope = json!({
+            "transaction": [&self.transaction],
+            "signatures": &self.signatures,
+        });
+        crate::protocol::normalize_envelope(&mut envelope);
+        envelope
+    }
+
+    /// Export as JSON for another party
+    #[must_use]
+    pub fn to_json(&self) -> String {
+        self.to_envelope().to_string()
+    }
+
+    /// Import a JSON export
+    pub fn from_json(json: &str) -> Result<Self, MultisigError> {
+        let envelope: Value = serde_json::from_str(json)
+            .map_err(|e| MultisigError::Malformed(format!("invalid JSON: {e}")))?;
+        Self::from_envelope(&envelope)
+    }
+
+    /// Export in the Go binary envelope layout
+    pub fn to_binary(&self) -> Result<Vec<u8>, MultisigError> {
+        let header = self.transaction.get("header").unwrap_or(&Value::Null);
+        let body = self.transaction.get("body").unwrap_or(&Value::Null);
+        let mut transaction = BinaryWriter::new();
+        let _ = transaction.write_bytes_field(&marshal_header_json(header)?, 1);
+        let _ = transaction.write_bytes_field(&marshal_body_to_binary(body)?, 2);
+
+        let mut writer = BinaryWriter::new();
+        for signature in &self.signatures {
+            let _ = writer.write_bytes_field(&marshal_ed25519_signature(signature)?, 1);
+        }
+        let _ = writer.write_bytes_field(transaction.bytes(), 3);
+        Ok(writer.into_bytes())
+    }
+
+    /// Import a binary export
+    ///
+    /// The decoded signatures are verified like [`add_signature`](Self::add_signature)
+    /// does, which also catches a transaction that did not decode to the
+    /// bytes it was signed as.
+    pub fn from_binary(data: &[u8]) -> Result<Self, MultisigError> {
+        let mut reader = BinaryReader::new(data);
+        let mut signatures = Vec::new();
+        let mut transaction = None;
+        while reader.has_remaining() {
+            match reader.read_field_number()? {
+                1 => signatures.push(unmarshal_ed25519_signature(
+                    reader.read_bytes_with_length()?,
+                )?),
+                3 if transaction.is_none() => {
+                    transaction = Some(unmarshal_transaction(reader.read_bytes_with_length()?)?);
+                }
+                3 => {
+                    return Err(MultisigError::Malformed(
+                        "more than one transaction".to_string(),
+                    ))
+                }
+                other => return Err(DecodingError::UnexpectedField(other).into()),
+            }
+        }
+        let transaction =
+            transaction.ok_or_else(|| MultisigError::Malformed("no transaction".to_string()))?;
+        let mut partial = Self::new(transaction)?;
+        for signature in signatures {
+            partial.add_signature(signature)?;
+        }
+        Ok(partial)
+    }
+}
+
+/// Hash of a signature, as Go's `Signature.Hash` computes it
+///
+/// This is the SHA-256 of the signature's binary encoding, signature bytes
+/// and transaction hash included; remote signatures cite it as their cause.
+/// Only ED25519 signatures are supported.
+pub fn signature_hash(signature: &Value) -> Result<[u8; 32], MultisigError> {
+    Ok(crate::codec::sha256_bytes(&marshal_ed25519_signature(signature)?))
+}
+
+/// Wrap `signature` in a remote signature bound for `destination`
+///
+/// `destination` is the URL of the partition the signature is forwarded
+/// to, see [`partition_url`], and `cause` the hashes of the signatures that
+/// caused the forwarding.
+#[must_use]
+pub fn remote_signature(signature: &Value, destination: &str, cause: &[[u8; 32]]) -> Value {
+    json!({
+        "type": "remote",
+        "destination": destination,
+        "signature": signature,
+        "cause": cause.iter().map(hex::encode).collect::<Vec<_>>(),
+    })
+}
+
+/// Wrap the signatures of `envelope` that come from another partition than
+/// its principal in remote signatures
+///
+/// A signature's partition is the one its key signer routes to, the
+/// innermost signer of a delegated signature. Signatures on the principal's
+/// partition and signatures that are already remote are kept as they are.
+pub fn route_remote_signatures(envelope: &Value, routing: &RoutingTable) -> Result<Value, MultisigError> {
+    let mut routed = envelope.clone();
+    let transaction = match routed.get("transaction") {
+        Some(Value::Array(transactions)) => transactions.first(),
+        transaction => transaction,
+    };
+    let principal = transaction
+        .and_then(|transaction| transaction.pointer("/header/principal"))
+        .and_then(Value::as_str)
+        .ok_or_else(|| MultisigError::Malformed("transaction has no principal".to_string()))?;
+    let route = |url: &str| routing.route(url).ok_or_else(|| MultisigError::NoRoute(url.to_string()));
+    let destination = route(principal)?.to_string();
+
+    for signature in routed
+        .get_mut("signatures")
+        .and_then(Value::as_array_mut)
+        .into_iter()
+        .flatten()
+    {
+        if str_field(signature, "type") == Some("remote") {
+            continue;
+        }
+        let mut key_signature = &*signature;
+        while str_field(key_signature, "type") == Some("delegated") {
+            key_signature = &key_signature["signature"];
+        }
+        let signer = str_field(key_signature, "signer").unwrap_or_default();
+        if route(signer)?.eq_ignore_ascii_case(&destination) {
+            continue;
+        }
+        let cause = signature_hash(key_signature)?;
+        *signature = remote_signature(signature, &partition_url(&destination), &[cause]);
+    }
+    Ok(routed)
+}
+
+/// A transaction waiting in an account's pending set
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PendingTransaction {
+    /// Transaction ID (`acc://<hash>@<principal>`)
+    pub txid: String,
+    /// Transaction hash
+    pub hash: [u8; 32],
+    /// The transaction, when the node expanded the record
+    pub transaction: Option<Value>,
+}
+
+/// Transactions pending on `account`, waiting for more signatures
+///
+/// Asks the node to expand the records so each transaction can be inspected
+/// before signing; nodes that only return IDs leave
+/// [`PendingTransaction::transaction`] empty.
+pub async fn pending_transactions(
+    client: &AccumulateClient,
+    account: &str,
+) -> Result<Vec<PendingTransaction>, MultisigError> {
+    let range = RangeOptions {
+        expand: Some(true),
+        ..Default::default()
+    };
+    let pending = client
+        .v3()
+        .query_pending(account, PendingQuery { range: Some(range) })
+        .await?;
+    Ok(pending.records)
+}
+
+/// A pending transaction from a pending-query record, a transaction ID or
+/// an expanded message
+///
+/// Records without a transaction ID yield `None`; an ID that is not
+/// `acc://<hash>@<account>` is an error.
+pub(crate) fn parse_pending_record(
+    record: &Value,
+) -> Result<Option<PendingTransaction>, JsonRpcError> {
+    let Some(txid) = record
+        .get("value")
+        .and_then(Value::as_str)
+        .or_else(|| record.get("id").and_then(Value::as_str))
+    else {
+        return Ok(None);
+    };
+    let (hash, _) = txid
+        .strip_prefix("acc://")
+        .and_then(|rest| rest.split_once('@'))
+        .ok_or_else(|| JsonRpcError::General(format!("Malformed transaction ID {txid}")))?;
+    let hash =
+        parse_hex_hash("transaction ID", hash).map_err(|e| JsonRpcError::General(e.to_string()))?;
+    let transaction = record
+        .get("message")
+        .and_then(|message| message.get("transaction"))
+        .cloned();
+    Ok(Some(PendingTransaction {
+        txid: txid.to_string(),
+        hash,
+        transaction,
+    }))
+}
+
+impl SmartSigner<'_> {
+    /// Sign a pending transaction by hash, submit the signature and wait
+    ///
+    /// Refreshes the signer version first. `principal` is the account the
+    /// transaction is pending on; see [`pending_transactions`].
+    pub async fn sign_pending_and_wait(
+        &mut self,
+        principal: &str,
+        tx_hash: &[u8; 32],
+        max_attempts: u32,
+    ) -> TxResult {
+        if let Err(e) = self.verify_key_on_page().await {
+            return TxResult::err(format!("Failed to verify signer: {e}"));
+        }
+        let envelope = match self.sign_remote(principal, tx_hash) {
+            Ok(envelope) => envelope,
+            Err(e) => return TxResult::err(format!("Failed to sign: {e}")),
+        };
+        let result = self.submit_and_wait(&envelope, max_attempts).await;
+        self.audit_outcome(&envelope, result)
+    }
+}
+
+fn str_field<'v>(value: &'v Value, key: &str) -> Option<&'v str> {
+    value.get(key).and_then(Value::as_str)
+}
+
+fn hex_field(value: &Value, key: &str) -> Result<Vec<u8>, String> {
+    let hex = str_field(value, key).ok_or_else(|| format!("missing {key}"))?;
+    parse_hex_field(key, hex).map_err(|e| e.to_string())
+}
+
+/// Like [`hex_field`], but a missing field decodes to no bytes
+fn optional_hex_field(value: &Value, key: &str) -> Result<Vec<u8>, String> {
+    str_field(value, key).map_or_else(
+        || Ok(Vec::new()),
+        |hex| parse_hex_field(key, hex).map_err(|e| e.to_string()),
+    )
+}
+
+fn vote_value(vote: Option<&str>) -> Result<u64, String> {
+    match vote {
+        None | Some("accept") => Ok(0),
+        Some("reject") => Ok(1),
+        Some("abstain") => Ok(2),
+        Some("suggest") => Ok(3),
+        Some(other) => Err(format!("unknown vote {other}")),
+    }
+}
+
+const fn vote_name(vote: u64) -> Result<&'static str, DecodingError> {
+    match vote {
+        0 => Ok("accept"),
+        1 => Ok("reject"),
+        2 => Ok("abstain"),
+        3 => Ok("suggest"),
+        other => Err(DecodingError::UnknownType(other)),
+    }
+}
+
+/// Check an ED25519 signature over `tx_hash`, returning its public key
+fn verify_ed25519(signature: &Value, tx_hash: &[u8; 32]) -> Result<Vec<u8>, String> {
+    match str_field(signature, "type") {
+        Some("ed25519") => {}
+        other => {
+            return Err(format!(
+                "unsupported signature type {}",
+                other.unwrap_or("(none)")
+            ))
+        }
+    }
+    let public_key = hex_field(signature, "publicKey")?;
+    let key = <[u8; 32]>::try_from(public_key.as_slice())
+        .map_err(|_| "public key must be 32 bytes".to_string())
+        .and_then(|key| VerifyingKey::from_bytes(&key).map_err(|e| e.to_string()))?;
+    let sig = <[u8; 64]>::try_from(hex_field(signature, "signature")?.as_slice())
+        .map(|sig| Signature::from_bytes(&sig))
+        .map_err(|_| "signature must be 64 bytes".to_string())?;
+    let data = optional_hex_field(signature, "data")?;
+
+    let metadata_hash = compute_signature_metadata_hash(
+        signature_types::ED25519,
+        &public_key,
+        str_field(signature, "signer").unwrap_or_default(),
+        signature
+            .get("signerVersion")
+            .and_then(Value::as_u64)
+            .unwrap_or(0),
+        signature
+            .get("timestamp")
+            .and_then(Value::as_u64)
+            .unwrap_or(0),
+        vote_value(str_field(signature, "vote"))?,
+        str_field(signature, "memo"),
+        (!data.is_empty()).then_some(data.as_slice()),
+    );
+    key.verify(&create_signing_preimage(&metadata_hash, tx_hash), &sig)
+        .map_err(|e| e.to_string())?;
+    Ok(public_key)
+}
+
+/// Go `protocol.ED25519Signature` binary layout
+fn marshal_ed25519_signature(signature: &Value) -> Result<Vec<u8>, MultisigError> {
+    let kind = str_field(signature, "type").unwrap_or_default();
+    if kind != "ed25519" {
+        return Err(MultisigError::UnsupportedSignature(kind.to_string()));
+    }
+    let malformed = |reason: String| MultisigError::Malformed(format!("signature {reason}"));
+    let transaction_hash =
+        <[u8; 32]>::try_from(hex_field(signature, "transactionHash").map_err(malformed)?)
+            .map_err(|_| malformed("transactionHash must be 32 bytes".to_string()))?;
+
+    let mut writer = BinaryWriter::new();
+    let _ = writer.write_uvarint_field(signature_types::ED25519, 1);
+    let _ = writer.write_bytes_field(&hex_field(signature, "publicKey").map_err(malformed)?, 2);
+    let _ = writer.write_bytes_field(&hex_field(signature, "signature").map_err(malformed)?, 3);
+    if let Some(signer) = str_field(signature, "signer") {
+        let _ = writer.write_string_field(signer, 4);
+    }
+    if let Some(version) = signature
+        .get("signerVersion")
+        .and_then(Value::as_u64)
+        .filter(|v| *v != 0)
+    {
+        let _ = writer.write_uvarint_field(version, 5);
+    }
+    if let Some(timestamp) = signature
+        .get("timestamp")
+        .and_then(Value::as_u64)
+        .filter(|t| *t != 0)
+    {
+        let _ = writer.write_uvarint_field(timestamp, 6);
+    }
+    let vote = vote_value(str_field(signature, "vote")).map_err(malformed)?;
+    if vote != 0 {
+        let _ = writer.write_uvarint_field(vote, 7);
+    }
+    let _ = writer.write_hash_field(&transaction_hash, 8);
+    if let Some(memo) = str_field(signature, "memo").filter(|m| !m.is_empty()) {
+        let _ = writer.write_string_field(memo, 9);
+    }
+    let data = optional_hex_field(signature, "data").map_err(malformed)?;
+    if !data.is_empty() {
+        let _ = writer.write_bytes_field(&data, 10);
+    }
+    Ok(writer.into_bytes())
+}
+
+/// Decode [`marshal_ed25519_signature`] output into the signature's JSON form
+fn unmarshal_ed25519_signature(data: &[u8]) -> Result<Value, MultisigError> {
+    let mut reader = BinaryReader::new(data);
+    let mut signature = serde_json::Map::new();
+    while reader.has_remaining() {
+        match reader.read_field_number()? {
+            1 => {
+                let kind = reader.read_uvarint()?;
+                if kind != signature_types::ED25519 {
+                    return Err(MultisigError::UnsupportedSignature(kind.to_string()));
+                }
+                signature.insert("type".to_string(), json!("ed25519"));
+            }
+            2 => {
+                signature.insert(
+                    "publicKey".to_string(),
+                    json!(hex::encode(reader.read_bytes_with_length()?)),
+                );
+            }
+            3 => {
+                signature.insert(
+                    "signature".to_string(),
+                    json!(hex::encode(reader.read_bytes_with_length()?)),
+                );
+            }
+            4 => {
+                signature.insert("signer".to_string(), json!(reader.read_string()?));
+            }
+            5 => {
+                signature.insert("signerVersion".to_string(), json!(reader.read_uvarint()?));
+            }
+            6 => {
+                signature.insert("timestamp".to_string(), json!(reader.read_uvarint()?));
+            }
+            7 => {
+                signature.insert(
+                    "vote".to_string(),
+                    json!(vote_name(reader.read_uvarint()?)?),
+                );
+            }
+            8 => {
+                signature.insert(
+                    "transactionHash".to_string(),
+                    json!(hex::encode(reader.read_hash()?)),
+                );
+            }
+            9 => {
+                signature.insert("memo".to_string(), json!(reader.read_string()?));
+            }
+            10 => {
+                signature.insert(
+                    "data".to_string(),
+                    json!(hex::encode(reader.read_bytes_with_length()?)),
+                );
+            }
+            other => return Err(DecodingError::UnexpectedField(other).into()),
+        }
+    }
+    Ok(Value::Object(signature))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::generated::enums::VoteType;
+    use crate::helpers::KeyEntry;
+    use crate::AccOptions;
+    use ed25519_dalek::SigningKey;
+
+    const PAGE: &str = "acc://multi.acme/book/1";
+
+    async fn client() -> AccumulateClient {
+        AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            AccOptions::default(),
+        )
+        .await
+        .unwrap()
+    }
+
+    fn key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+
+    fn body() -> Value {
+        json!({"type": "sendTokens", "to": [{"url": "acc://bob.acme/tokens", "amount": "100"}]})
+    }
+
+    fn page(threshold: u64, seeds: &[u8]) -> KeyPageState {
+        KeyPageState {
+            url: PAGE.to_string(),
+            version: 3,
+            credit_balance: 0,
+            accept_threshold: threshold,
+            keys: seeds
+                .iter()
+                .map(|seed| KeyEntry {
+                    key_hash: hex::encode(crate::helpers::sha256_hash(
+                        &key(*seed).verifying_key().to_bytes(),
+                    )),
+                    delegate: None,
+                })
+                .collect(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_co_sign_and_threshold() {
+        let client = client().await;
+        let alice = SmartSigner::new(&client, key(1), PAGE);
+        let bob = SmartSigner::new(&client, key(2), PAGE);
+
+        let mut partial = PartialEnvelope::initiate(
+            &alice,
+            "acc://multi.acme/tokens",
+            &body(),
+            &HeaderOptions::default(),
+        )
+        .unwrap();
+        let page = page(2, &[1, 2, 3]);
+        assert_eq!(partial.signature_count(&page).unwrap(), 1);
+        assert!(!partial.threshold_met(&page).unwrap());
+
+        partial.co_sign(&bob).unwrap();
+        assert!(partial.threshold_met(&page).unwrap());
+        assert!(matches!(
+            partial.co_sign(&bob),
+            Err(MultisigError::AlreadySigned(_))
+        ));
+
+        let carol = SmartSigner::new(&client, key(3), PAGE);
+        let options = SignatureOptions {
+            vote: Some(VoteType::Reject),
+            ..SignatureOptions::default()
+        };
+        partial.co_sign_with_options(&carol, &options).unwrap();
+        assert_eq!(partial.signature_count(&page).unwrap(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_json_and_binary_export() {
+        let client = client().await;
+        let alice = SmartSigner::new(&client, key(1), PAGE);
+        let bob = SmartSigner::new(&client, key(2), PAGE);
+        let options = HeaderOptions {
+            memo: Some("payroll".to_string()),
+            ..HeaderOptions::default()
+        };
+        let partial =
+            PartialEnvelope::initiate(&alice, "acc://multi.acme/tokens", &body(), &options)
+                .unwrap();
+
+        let mut imported = PartialEnvelope::from_json(&partial.to_json()).unwrap();
+        imported
+            .co_sign_with_options(
+                &bob,
+                &SignatureOptions {
+                    memo: Some("ok".to_string()),
+                    ..SignatureOptions::default()
+                },
+            )
+            .unwrap();
+        assert_eq!(imported.transaction_hash(), partial.transaction_hash());
+
+        let binary = imported.to_binary().unwrap();
+        let decoded = PartialEnvelope::from_binary(&binary).unwrap();
+        assert_eq!(decoded.to_envelope(), imported.to_envelope());
+        assert_eq!(decoded.to_binary().unwrap(), binary);
+
+        let mut merged = partial.clone();
+        merged.merge(&decoded).unwrap();
+        assert_eq!(merged.signatures().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_rejects_foreign_and_tampered_signatures() {
+        let client = client().await;
+        let alice = SmartSigner::new(&client, key(1), PAGE);
+        let mut partial = PartialEnvelope::initiate(
+            &alice,
+            "acc://multi.acme/tokens",
+            &body(),
+            &HeaderOptions::default(),
+        )
+        .unwrap();
+
+        let other = alice.sign_hash(&[9u8; 32]).unwrap();
+        assert!(matches!(
+            partial.add_signature(other),
+            Err(MultisigError::HashMismatch { .. })
+        ));
+
+        let bob = SmartSigner::new(&client, key(2), PAGE);
+        let mut tampered = bob.sign_hash(&partial.transaction_hash()).unwrap();
+        tampered["signerVersion"] = json!(7);
+        assert!(matches!(
+            partial.add_signature(tampered),
+            Err(MultisigError::InvalidSignature { .. })
+        ));
+
+        let mut envelope = partial.to_envelope();
+        envelope["transaction"][0]["header"]["memo"] = json!("changed");
+        assert!(matches!(
+            PartialEnvelope::from_envelope(&envelope),
+            Err(MultisigError::HashMismatch { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_route_remote_signatures() {
+        let client = client().await;
+        let routing: RoutingTable = serde_json::from_value(json!({
+            "overrides": [
+                {"account": "acc://multi.acme", "partition": "Apollo"},
+                {"account": "acc://bob.acme", "partition": "Yutu"}
+            ],
+            "routes": []
+        }))
+        .unwrap();
+        let alice = SmartSigner::new(&client, key(1), PAGE);
+        let partial =
+            PartialEnvelope::initiate(&alice, "acc://multi.acme/tokens", &body(), &HeaderOptions::default())
+                .unwrap();
+        let local = partial.signatures()[0].clone();
+        let bob = SmartSigner::new(&client, key(2), "acc://bob.acme/book/1");
+        let delegated = SmartSigner::new(&client, key(3), "acc://bob.acme/book/1")
+            .with_delegators(&[PAGE])
+            .sign_hash(&partial.transaction_hash())
+            .unwrap();
+        let mut envelope = partial.to_envelope();
+        let signatures = envelope["signatures"].as_array_mut().unwrap();
+        signatures.push(bob.sign_hash(&partial.transaction_hash()).unwrap());
+        signatures.push(delegated.clone());
+
+        let routed = route_remote_signatures(&envelope, &routing).unwrap();
+        let signatures = routed["signatures"].as_array().unwrap();
+        assert_eq!(signatures[0], local);
+        assert_eq!(signatures[1]["type"], "remote");
+        assert_eq!(signatures[1]["destination"], "acc://bvn-Apollo.acme");
+        assert_eq!(signatures[1]["signature"], envelope["signatures"][1]);
+        assert_eq!(
+            signatures[1]["cause"][0],
+            hex::encode(signature_hash(&envelope["signatures"][1]).unwrap())
+        );
+        assert_eq!(signatures[2]["signature"], delegated);
+        assert_eq!(
+            signatures[2]["cause"][0],
+            hex::encode(signature_hash(&delegated["signature"]).unwrap())
+        );
+
+        // Routing again leaves remote signatures alone
+        assert_eq!(route_remote_signatures(&routed, &routing).unwrap(), routed);
+        let unknown = RoutingTable::default();
+        assert!(matches!(route_remote_signatures(&envelope, &unknown), Err(MultisigError::NoRoute(_))));
+    }
+
+    #[test]
+    fn test_parse_pending() {
+        let hash = "ab".repeat(32);
+        let result = json!({
+            "recordType": "range",
+            "records": [
+                {"recordType": "txID", "value": format!("acc://{hash}@multi.acme/tokens")},
+                {
+                    "recordType": "message",
+                    "id": format!("acc://{hash}@multi.acme/tokens"),
+                    "message": {"type": "transaction", "transaction": {"header": {}, "body": {}}}
+                },
+                {"recordType": "txID", "value": "acc://not-a-hash@multi.acme/tokens"}
+            ],
+            "total": 3
+        });
+        let records = result["records"].as_array().unwrap();
+        let pending: Vec<PendingTransaction> = records[..2]
+            .iter()
+            .filter_map(|record| parse_pending_record(record).unwrap())
+            .collect();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].hash, [0xab; 32]);
+        assert!(pending[0].transaction.is_none());
+        assert!(pending[1].transaction.is_some());
+        assert!(parse_pending_record(&records[2]).is_err());
+        assert!(parse_pending_record(&json!({"recordType": "chainEntry"}))
+            .unwrap()
+            .is_none());
+    }
+}
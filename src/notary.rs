@@ -0,0 +1,264 @@
+//! Document notarization on data accounts
+//!
+//! [`SmartSigner::notarize`] writes the SHA-256 hash of a document, never
+//! the document itself, as a data entry of a data account.
+//! [`AccumulateClient::verify_notarized`] later hashes the same bytes, finds
+//! that entry and fetches a receipt proving the writeData transaction is on
+//! the account's main chain.
+//!
+//! ```no_run
+//! # async fn run(
+//! #     client: &accumulate_client::AccumulateClient,
+//! #     signer: &mut accumulate_client::helpers::SmartSigner<'_>,
+//! # ) -> Result<(), accumulate_client::notary::NotaryError> {
+//! let contract = std::fs::read("contract.pdf").unwrap_or_default();
+//! let notarization = signer.notarize("acc://acme-corp.acme/notary", &contract).await?;
+//! println!("notarized in {}", notarization.txid);
+//!
+//! let entry = client.verify_notarized("acc://acme-corp.acme/notary", &contract).await?;
+//! println!("entry {} anchored: {}", entry.index, entry.is_anchored());
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::client::AccumulateClient;
+use crate::codec::signing::compute_data_entry_hash;
+use crate::crypto::ed25519::sha256;
+use crate::helpers::{SmartSigner, TxBody};
+use crate::json_rpc_client::JsonRpcError;
+use crate::query::ChainResult;
+use crate::receipts::{verify_receipt, ReceiptError};
+use crate::tx_history::txid_hash;
+use crate::types::{ChainQuery, DataQuery, ReceiptOptions};
+use serde_json::Value;
+use thiserror::Error;
+
+/// Errors from notarizing a document or verifying a notarization
+#[derive(Error, Debug)]
+pub enum NotaryError {
+    /// The writeData transaction failed or was not confirmed
+    #[error("Notarization failed: {0}")]
+    Submit(String),
+
+    /// The data account holds no entry for the document
+    #[error("Document {document_hash} is not notarized on {data_account}")]
+    NotFound {
+        /// Data account searched
+        data_account: String,
+        /// SHA-256 of the document (hex)
+        document_hash: String,
+    },
+
+    /// The node returned a receipt that does not prove the entry
+    #[error("Invalid inclusion proof: {0}")]
+    Receipt(#[from] ReceiptError),
+
+    /// A query failed
+    #[error("Query failed: {0}")]
+    Query(#[from] JsonRpcError),
+}
+
+/// A document hash written by [`SmartSigner::notarize`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Notarization {
+    /// SHA-256 of the document (hex)
+    pub document_hash: String,
+    /// Hash of the data entry holding the document hash (hex)
+    pub entry_hash: String,
+    /// ID the submission returned, as in [`TxResult::txid`](crate::helpers::TxResult::txid)
+    pub txid: String,
+}
+
+/// A notarization found by [`AccumulateClient::verify_notarized`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotarizedEntry {
+    /// SHA-256 of the document (hex)
+    pub document_hash: String,
+    /// Hash of the data entry (hex)
+    pub entry_hash: String,
+    /// Position of the entry among the account's data entries
+    pub index: u64,
+    /// ID of the writeData transaction, when the node reported it
+    pub txid: Option<String>,
+    /// Verified receipt proving the transaction is on the account's main
+    /// chain, when the node returned one
+    pub receipt: Option<Value>,
+}
+
+impl NotarizedEntry {
+    /// Whether the entry comes with a verified inclusion proof
+    #[must_use]
+    pub const fn is_anchored(&self) -> bool {
+        self.receipt.is_some()
+    }
+
+    /// Anchor root the receipt leads to (hex)
+    #[must_use]
+    pub fn anchor(&self) -> Option<&str> {
+        self.receipt.as_ref()?.get("anchor")?.as_str()
+    }
+
+    /// Local block the receipt was anchored in
+    #[must_use]
+    pub fn local_block(&self) -> Option<u64> {
+        self.receipt.as_ref()?.get("localBlock")?.as_u64()
+    }
+}
+
+/// SHA-256 of `document`, the value a notarization writes
+#[must_use]
+pub fn document_hash(document: &[u8]) -> [u8; 32] {
+    sha256(document)
+}
+
+/// Hash of the data entry that notarizes a document with `document_hash`
+#[must_use]
+pub fn notarized_entry_hash(document_hash: &[u8; 32]) -> [u8; 32] {
+    compute_data_entry_hash(&[hex::encode(document_hash)], true)
+}
+
+impl SmartSigner<'_> {
+    /// Write the SHA-256 hash of `document` as a data entry of `data_account`
+    /// and wait for the transaction to be delivered
+    pub async fn notarize(
+        &mut self,
+        data_account: &str,
+        document: &[u8],
+    ) -> Result<Notarization, NotaryError> {
+        let hash = document_hash(document);
+        let body = TxBody::write_data_hex(&[&hex::encode(hash)]);
+        let result = self
+            .sign_submit_and_wait(data_account, &body, Some("Notarize document"), 30)
+            .await;
+        if !result.success {
+            return Err(NotaryError::Submit(
+                result.error.unwrap_or_else(|| "not confirmed".to_string()),
+            ));
+        }
+        Ok(Notarization {
+            document_hash: hex::encode(hash),
+            entry_hash: hex::encode(notarized_entry_hash(&hash)),
+            txid: result.txid.unwrap_or_default(),
+        })
+    }
+}
+
+impl AccumulateClient {
+    /// Find the notarization of `document` on `data_account`, with an
+    /// inclusion proof of its transaction when the node provides one
+    ///
+    /// A receipt that fails verification is an error rather than a missing
+    /// proof.
+    pub async fn verify_notarized(
+        &self,
+        data_account: &str,
+        document: &[u8],
+    ) -> Result<NotarizedEntry, NotaryError> {
+        let hash = document_hash(document);
+        let entry_hash = hex::encode(notarized_entry_hash(&hash));
+        let not_found = || NotaryError::NotFound {
+            data_account: data_account.to_string(),
+            document_hash: hex::encode(hash),
+        };
+
+        let query = DataQuery {
+            entry: Some(entry_hash.clone()),
+            ..Default::default()
+        };
+        let record = match self.v3().query_data(data_account, query).await {
+            Ok(ChainResult::Entry(record)) if record.entry.eq_ignore_ascii_case(&entry_hash) => {
+                record
+            }
+            Ok(_) => return Err(not_found()),
+            Err(e) if e.is_not_found() => return Err(not_found()),
+            Err(e) => return Err(e.into()),
+        };
+        let txid = record
+            .value
+            .as_ref()
+            .and_then(|value| value.get("id"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+
+        let mut receipt = None;
+        if let Some(tx_hash) = txid.as_deref().and_then(txid_hash) {
+            let query = ChainQuery {
+                name: Some("main".to_string()),
+                entry: Some(tx_hash.clone()),
+                include_receipt: Some(ReceiptOptions {
+                    for_any: Some(true),
+                    for_height: None,
+                }),
+                ..Default::default()
+            };
+            if let ChainResult::Entry(entry) = self.v3().query_chain(data_account, query).await? {
+                receipt = entry.receipt;
+            }
+            if let Some(proof) = &receipt {
+                verify_receipt(proof)?;
+                let start = proof.get("start").and_then(Value::as_str).unwrap_or_default();
+                if !start.eq_ignore_ascii_case(&tx_hash) {
+                    return Err(ReceiptError::WrongEntry {
+                        start: start.to_string(),
+                        entry: tx_hash,
+                    }
+                    .into());
+                }
+            }
+        }
+
+        Ok(NotarizedEntry {
+            document_hash: hex::encode(hash),
+            entry_hash,
+            index: record.index,
+            txid,
+            receipt,
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::QuickStart;
+    use crate::simulator::SimulatedNetwork;
+    use crate::ACME_BASE_UNITS;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_notarize_and_verify() {
+        let network = SimulatedNetwork::new();
+        let quick = QuickStart::from_client(Arc::new(network.client().await.unwrap()));
+        let wallet = quick.create_wallet();
+        network
+            .fund(&wallet.lite_token_account, 100 * ACME_BASE_UNITS)
+            .unwrap();
+        let adi = quick.setup_adi(&wallet, "notary").await.unwrap();
+        quick.buy_credits_for_adi(&wallet, &adi, 1_000).await.unwrap();
+        let result = quick.create_data_account(&adi, "documents").await.unwrap();
+        assert!(result.success, "{:?}", result.error);
+        let data_account = format!("{}/documents", adi.url);
+        let mut signer = SmartSigner::new(quick.client(), adi.keypair().clone(), &adi.key_page_url);
+
+        let notarization = signer.notarize(&data_account, b"contract v1").await.unwrap();
+        assert_eq!(notarization.document_hash, hex::encode(sha256(b"contract v1")));
+
+        let entry = quick
+            .client()
+            .verify_notarized(&data_account, b"contract v1")
+            .await
+            .unwrap();
+        assert_eq!(entry.entry_hash, notarization.entry_hash);
+        assert_eq!(entry.index, 0);
+        assert!(entry.txid.as_deref().unwrap().ends_with("@notary.acme/documents"));
+        assert!(!entry.is_anchored());
+
+        let err = quick
+            .client()
+            .verify_notarized(&data_account, b"contract v2")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, NotaryError::NotFound { .. }), "{err}");
+    }
+}
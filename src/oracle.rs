@@ -0,0 +1,329 @@
+//! Historical ACME oracle lookups
+//!
+//! The credit oracle is published by the directory network as `writeData`
+//! entries on [`DN_ORACLE_URL`]. [`OracleUpdates`] walks that account's main
+//! chain and yields each update with the block and time it took effect;
+//! [`OracleHistory`] answers "what was the oracle at block N / time T" for
+//! retroactive fee accounting.
+
+use crate::client::AccumulateClient;
+use crate::codec::hex_utils::parse_hex_field;
+use crate::codec::BinaryReader;
+use crate::json_rpc_client::JsonRpcError;
+use crate::storage::{get_json, put_json, KvStore};
+use crate::types::RangeOptions;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+
+/// Directory network data account holding the ACME oracle
+pub const DN_ORACLE_URL: &str = "acc://dn.acme/oracle";
+
+/// Key under which [`AccumulateClient::oracle_history_cached`] keeps the history
+pub const ORACLE_HISTORY_KEY: &str = "oracle/history";
+
+/// Main chain entries requested per query
+const PAGE_SIZE: u64 = 100;
+
+/// One published oracle value
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct OracleUpdate {
+    /// Position of the update on the oracle account's main chain
+    pub chain_index: u64,
+    /// Directory block in which the update was executed
+    pub block: u64,
+    /// Time of that block, when the node reports it
+    pub time: Option<DateTime<Utc>>,
+    /// Oracle price, in the same units as `AcmeOracle::price`
+    pub price: u64,
+    /// Receipt verification outcome, when the client verifies receipts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verified: Option<bool>,
+}
+
+/// Paging iterator over oracle updates, oldest first
+#[derive(Debug)]
+pub struct OracleUpdates<'a> {
+    client: &'a AccumulateClient,
+    url: String,
+    next_index: u64,
+    buffer: VecDeque<OracleUpdate>,
+    exhausted: bool,
+}
+
+impl OracleUpdates<'_> {
+    /// Start at main chain entry `index` instead of the beginning
+    #[must_use]
+    pub const fn start_at(mut self, index: u64) -> Self {
+        self.next_index = index;
+        self
+    }
+
+    /// The next update, or `None` once the chain is exhausted
+    pub async fn next(&mut self) -> Result<Option<OracleUpdate>, JsonRpcError> {
+        while self.buffer.is_empty() && !self.exhausted {
+            let range = RangeOptions {
+                start: Some(self.next_index),
+                count: Some(PAGE_SIZE),
+                expand: Some(true),
+                from_end: None,
+            };
+            let response: Value = self
+                .client
+                .call_query(json!({
+                    "url": &self.url,
+                    "query": {
+                        "queryType": "chain",
+                        "name": "main",
+                        "range": range,
+                        "includeReceipt": {"forAny": true}
+                    }
+                }))
+                .await?;
+
+            let records = response.get("records").and_then(Value::as_array);
+            let count = records.map_or(0, Vec::len) as u64;
+            self.buffer.extend(parse_oracle_updates(&response)?);
+            self.next_index += count;
+            self.exhausted = count < PAGE_SIZE;
+        }
+        Ok(self.buffer.pop_front())
+    }
+
+    /// Collect every remaining update into an [`OracleHistory`]
+    pub async fn collect(mut self) -> Result<OracleHistory, JsonRpcError> {
+        let mut updates = Vec::new();
+        while let Some(update) = self.next().await? {
+            updates.push(update);
+        }
+        Ok(OracleHistory::new(updates))
+    }
+}
+
+/// Oracle updates ordered by block, with point-in-time lookups
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct OracleHistory {
+    updates: Vec<OracleUpdate>,
+}
+
+impl OracleHistory {
+    /// Build a history from updates in any order
+    #[must_use]
+    pub fn new(mut updates: Vec<OracleUpdate>) -> Self {
+        updates.sort_by_key(|u| (u.block, u.chain_index));
+        Self { updates }
+    }
+
+    /// All updates, oldest first
+    #[must_use]
+    pub fn updates(&self) -> &[OracleUpdate] {
+        &self.updates
+    }
+
+    /// The update in effect at directory block `block`
+    ///
+    /// That is the last update executed at or before `block`; `None` if the
+    /// block predates the first known update.
+    #[must_use]
+    pub fn at_block(&self, block: u64) -> Option<&OracleUpdate> {
+        let n = self.updates.partition_point(|u| u.block <= block);
+        n.checked_sub(1).map(|i| &self.updates[i])
+    }
+
+    /// The update in effect at time `time`
+    ///
+    /// Updates without a block time are skipped.
+    #[must_use]
+    pub fn at_time(&self, time: DateTime<Utc>) -> Option<&OracleUpdate> {
+        self.updates
+            .iter()
+            .rev()
+            .find(|u| u.time.is_some_and(|t| t <= time))
+    }
+}
+
+impl AccumulateClient {
+    /// Iterate over the directory network's oracle updates, oldest first
+    #[must_use]
+    pub fn oracle_updates(&self) -> OracleUpdates<'_> {
+        OracleUpdates {
+            client: self,
+            url: DN_ORACLE_URL.to_string(),
+            next_index: 0,
+            buffer: VecDeque::new(),
+            exhausted: false,
+        }
+    }
+
+    /// Load the complete oracle history
+    pub async fn oracle_history(&self) -> Result<OracleHistory, JsonRpcError> {
+        self.oracle_updates().collect().await
+    }
+
+    /// Load the oracle history, fetching only updates newer than the copy
+    /// cached in `store`
+    ///
+    /// The merged history is written back, so repeated calls and restarts
+    /// only page through the tail of the oracle chain.
+    pub async fn oracle_history_cached(
+        &self,
+        store: &mut (impl KvStore + ?Sized),
+    ) -> Result<OracleHistory, JsonRpcError> {
+        let cached: OracleHistory = get_json(store, ORACLE_HISTORY_KEY)
+            .map_err(|e| JsonRpcError::General(e.to_string()))?
+            .unwrap_or_default();
+        let start = cached.updates.iter().map(|u| u.chain_index + 1).max().unwrap_or(0);
+        let fresh = self.oracle_updates().start_at(start).collect().await?;
+
+        let mut updates = cached.updates;
+        updates.extend(fresh.updates);
+        let history = OracleHistory::new(updates);
+        put_json(store, ORACLE_HISTORY_KEY, &history).map_err(|e| JsonRpcError::General(e.to_string()))?;
+        Ok(history)
+    }
+}
+
+/// Extract oracle updates from an expanded main chain query response
+///
+/// Records that are not successful `writeData` transactions carrying an
+/// oracle value are skipped; an entry that is not valid hex is an error.
+pub fn parse_oracle_updates(response: &Value) -> Result<Vec<OracleUpdate>, JsonRpcError> {
+    let start = response.get("start").and_then(Value::as_u64).unwrap_or(0);
+    response
+        .get("records")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .filter_map(|(offset, record)| parse_oracle_record(record, start + offset as u64).transpose())
+        .collect()
+}
+
+fn parse_oracle_record(record: &Value, default_index: u64) -> Result<Option<OracleUpdate>, JsonRpcError> {
+    let value = record.get("value").unwrap_or(record);
+    if value.get("status").and_then(Value::as_str).is_some_and(|s| s != "delivered") {
+        return Ok(None);
+    }
+    let message = value.get("message").unwrap_or(value);
+    let Some(body) = message.get("transaction").and_then(|tx| tx.get("body")) else {
+        return Ok(None);
+    };
+    if body.get("type").and_then(Value::as_str) != Some("writeData") {
+        return Ok(None);
+    }
+    let Some(data) = body
+        .get("entry")
+        .and_then(|entry| entry.get("data"))
+        .and_then(Value::as_array)
+    else {
+        return Ok(None);
+    };
+    let mut price = None;
+    for part in data.iter().filter_map(Value::as_str) {
+        price = decode_oracle(part)?;
+        if price.is_some() {
+            break;
+        }
+    }
+    let Some(price) = price else {
+        return Ok(None);
+    };
+
+    let receipt = record.get("receipt").or_else(|| value.get("receipt"));
+    let block = receipt
+        .and_then(|r| r.get("localBlock"))
+        .or_else(|| value.get("received"))
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    let time = receipt
+        .and_then(|r| r.get("localBlockTime"))
+        .and_then(Value::as_str)
+        .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+        .map(|t| t.with_timezone(&Utc));
+
+    Ok(Some(OracleUpdate {
+        chain_index: record.get("index").and_then(Value::as_u64).unwrap_or(default_index),
+        block,
+        time,
+        price,
+        verified: record.get("verified").and_then(Value::as_bool),
+    }))
+}
+
+/// Decode a hex data entry holding an `AcmeOracle`
+///
+/// The directory writes the binary encoding (field 1: price); JSON
+/// `{"price": N}` entries are accepted as well. Entries that hold neither
+/// yield `None`.
+fn decode_oracle(entry_hex: &str) -> Result<Option<u64>, JsonRpcError> {
+    let bytes = parse_hex_field("data", entry_hex).map_err(|e| JsonRpcError::General(e.to_string()))?;
+    if let Ok(value) = serde_json::from_slice::<Value>(&bytes) {
+        return Ok(value.get("price").and_then(Value::as_u64));
+    }
+    let mut reader = BinaryReader::new(&bytes);
+    if !reader.read_field(1).unwrap_or(false) {
+        return Ok(None);
+    }
+    Ok(reader.read_uvarint().ok())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::codec::BinaryWriter;
+
+    fn binary_oracle(price: u64) -> String {
+        let mut writer = BinaryWriter::new();
+        writer.write_uvarint_field(price, 1).unwrap();
+        hex::encode(writer.bytes())
+    }
+
+    fn record(index: u64, block: u64, time: &str, data: &[String]) -> Value {
+        json!({
+            "index": index,
+            "receipt": {"localBlock": block, "localBlockTime": time},
+            "value": {
+                "status": "delivered",
+                "message": {"transaction": {
+                    "header": {"principal": DN_ORACLE_URL},
+                    "body": {"type": "writeData", "entry": {"type": "doubleHash", "data": data}}
+                }}
+            }
+        })
+    }
+
+    #[test]
+    fn test_parse_and_lookup() {
+        let response = json!({
+            "start": 0,
+            "records": [
+                record(0, 100, "2024-01-01T00:00:00Z", &[binary_oracle(5_000)]),
+                json!({"index": 1, "value": {"status": "delivered", "message": {"transaction": {"body": {"type": "updateKeyPage"}}}}}),
+                record(2, 250, "2024-01-02T00:00:00Z", &[hex::encode(br#"{"price":7500}"#)]),
+                record(3, 400, "2024-01-03T00:00:00Z", &[binary_oracle(6_000)]),
+            ]
+        });
+        let history = OracleHistory::new(parse_oracle_updates(&response).unwrap());
+        assert_eq!(history.updates().len(), 3);
+        assert_eq!(history.updates()[1].chain_index, 2);
+
+        assert!(history.at_block(99).is_none());
+        assert_eq!(history.at_block(100).unwrap().price, 5_000);
+        assert_eq!(history.at_block(399).unwrap().price, 7_500);
+        assert_eq!(history.at_block(u64::MAX).unwrap().price, 6_000);
+
+        let noon = DateTime::parse_from_rfc3339("2024-01-02T12:00:00Z").unwrap().with_timezone(&Utc);
+        assert_eq!(history.at_time(noon).unwrap().block, 250);
+        let before = DateTime::parse_from_rfc3339("2023-12-31T00:00:00Z").unwrap().with_timezone(&Utc);
+        assert!(history.at_time(before).is_none());
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_hex() {
+        let response = json!({"records": [record(0, 100, "2024-01-01T00:00:00Z", &["0xzz".to_string()])]});
+        assert!(parse_oracle_updates(&response).is_err());
+    }
+}
@@ -0,0 +1,341 @@
+//! Paged iteration over V3 range queries
+//!
+//! Directory, chain and block queries return one page of a range at a time,
+//! selected by `start` and `count`. A [`RangePager`] requests the following
+//! pages as its records are consumed, either one at a time with
+//! [`RangePager::next`] or as a [`Stream`] for use with stream combinators:
+//!
+//! ```no_run
+//! # async fn run(client: &accumulate_client::AccumulateClient) -> Result<(), accumulate_client::json_rpc_client::JsonRpcError> {
+//! let mut accounts = client.directory_pager("acc://alice.acme");
+//! while let Some(entry) = accounts.next().await? {
+//!     println!("{}", entry.url);
+//! }
+//!
+//! // Every transaction on an account's main chain, 50 per request
+//! let history = client.chain_pager("acc://alice.acme/tokens", "main").page_size(50);
+//! for entry in history.collect().await? {
+//!     println!("{} {}", entry.index, entry.entry);
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! A pager stops once it has fetched as many records as the range's `total`,
+//! or when a page comes back empty. A failed request is returned as an error
+//! and leaves the pager where it was, so the next call retries the same page.
+
+use crate::client::AccumulateClient;
+use crate::json_rpc_client::JsonRpcError;
+use crate::query::{
+    BlockResult, ChainEntryRecord, ChainResult, DirectoryEntry, MajorBlockRecord, MinorBlockRecord, RecordRange,
+};
+use crate::types::{BlockQuery, ChainQuery, DirectoryQuery, RangeOptions};
+use futures_core::Stream;
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{ready, Context, Poll};
+
+/// Records requested per page when no page size is set
+pub const DEFAULT_PAGE_SIZE: u64 = 100;
+
+type PageFuture<'a, T> = Pin<Box<dyn Future<Output = Result<RecordRange<T>, JsonRpcError>> + Send + 'a>>;
+type FetchPage<'a, T> = Box<dyn Fn(u64, u64) -> PageFuture<'a, T> + Send + Sync + 'a>;
+
+/// Iterator over the records of a range query, fetching pages as needed
+pub struct RangePager<'a, T> {
+    fetch: FetchPage<'a, T>,
+    page_size: u64,
+    next_start: u64,
+    total: Option<u64>,
+    buffer: VecDeque<T>,
+    pending: Option<PageFuture<'a, T>>,
+    exhausted: bool,
+}
+
+impl<T> fmt::Debug for RangePager<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RangePager")
+            .field("page_size", &self.page_size)
+            .field("next_start", &self.next_start)
+            .field("total", &self.total)
+            .field("buffered", &self.buffer.len())
+            .field("exhausted", &self.exhausted)
+            .finish_non_exhaustive()
+    }
+}
+
+// The page future is boxed and nothing is pinned in place
+impl<T> Unpin for RangePager<'_, T> {}
+
+impl<'a, T> RangePager<'a, T> {
+    /// Pager over the range `fetch` returns for a `start` and `count`
+    ///
+    /// Use this for range queries without a pager of their own, e.g. an
+    /// expanded directory or a pending query.
+    pub fn new<F, Fut>(fetch: F) -> Self
+    where
+        F: Fn(u64, u64) -> Fut + Send + Sync + 'a,
+        Fut: Future<Output = Result<RecordRange<T>, JsonRpcError>> + Send + 'a,
+    {
+        Self {
+            fetch: Box::new(move |start, count| Box::pin(fetch(start, count))),
+            page_size: DEFAULT_PAGE_SIZE,
+            next_start: 0,
+            total: None,
+            buffer: VecDeque::new(),
+            pending: None,
+            exhausted: false,
+        }
+    }
+
+    /// Request `count` records per page instead of [`DEFAULT_PAGE_SIZE`]
+    #[must_use]
+    pub fn page_size(mut self, count: u64) -> Self {
+        self.page_size = count.max(1);
+        self
+    }
+
+    /// Start at record `start` instead of the beginning
+    #[must_use]
+    pub const fn start_at(mut self, start: u64) -> Self {
+        self.next_start = start;
+        self
+    }
+
+    /// Number of records in the whole range, once a page has been fetched
+    #[must_use]
+    pub const fn total(&self) -> Option<u64> {
+        self.total
+    }
+
+    /// The next page, or `None` once the range is exhausted
+    ///
+    /// Records already buffered by [`RangePager::next`] are returned first.
+    pub async fn next_page(&mut self) -> Result<Option<RecordRange<T>>, JsonRpcError> {
+        if !self.buffer.is_empty() {
+            return Ok(Some(RecordRange {
+                start: self.next_start - self.buffer.len() as u64,
+                total: self.total.unwrap_or_default(),
+                records: self.buffer.drain(..).collect(),
+            }));
+        }
+        while !self.exhausted {
+            let page = match self.pending.take() {
+                Some(pending) => pending.await?,
+                None => (self.fetch)(self.next_start, self.page_size).await?,
+            };
+            self.advance(&page);
+            if !page.records.is_empty() {
+                return Ok(Some(page));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The next record, or `None` once the range is exhausted
+    pub async fn next(&mut self) -> Result<Option<T>, JsonRpcError> {
+        std::future::poll_fn(|cx| Pin::new(&mut *self).poll_next(cx))
+            .await
+            .transpose()
+    }
+
+    /// Collect every remaining record
+    pub async fn collect(mut self) -> Result<Vec<T>, JsonRpcError> {
+        let mut records = Vec::new();
+        while let Some(record) = self.next().await? {
+            records.push(record);
+        }
+        Ok(records)
+    }
+
+    /// Move the cursor past a fetched page
+    fn advance(&mut self, page: &RecordRange<T>) {
+        let fetched = page.records.len() as u64;
+        self.next_start += fetched;
+        self.total = Some(page.total);
+        self.exhausted = fetched == 0 || self.next_start >= page.total;
+    }
+}
+
+impl<T> Stream for RangePager<'_, T> {
+    type Item = Result<T, JsonRpcError>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        loop {
+            if let Some(record) = this.buffer.pop_front() {
+                return Poll::Ready(Some(Ok(record)));
+            }
+            if this.exhausted {
+                return Poll::Ready(None);
+            }
+            let pending = this
+                .pending
+                .get_or_insert_with(|| (this.fetch)(this.next_start, this.page_size));
+            let result = ready!(pending.as_mut().poll(cx));
+            this.pending = None;
+            match result {
+                Ok(page) => {
+                    this.advance(&page);
+                    this.buffer.extend(page.records);
+                }
+                Err(e) => return Poll::Ready(Some(Err(e))),
+            }
+        }
+    }
+}
+
+fn range(start: u64, count: u64, expand: bool) -> RangeOptions {
+    RangeOptions {
+        start: Some(start),
+        count: Some(count),
+        expand: expand.then_some(true),
+        from_end: None,
+    }
+}
+
+impl AccumulateClient {
+    /// Pager over the accounts directly under the identity at `url`
+    ///
+    /// Entries carry URLs only; for account states, page an expanded
+    /// [`query_directory`](crate::query::V3Queries::query_directory) with
+    /// [`RangePager::new`].
+    #[must_use]
+    pub fn directory_pager(&self, url: &str) -> RangePager<'_, DirectoryEntry> {
+        let url = url.to_string();
+        RangePager::new(move |start, count| {
+            let url = url.clone();
+            async move {
+                let query = DirectoryQuery {
+                    range: Some(range(start, count, false)),
+                };
+                self.v3().query_directory(&url, query).await
+            }
+        })
+    }
+
+    /// Pager over the entries of chain `chain` (e.g. `main`) of the account
+    /// at `url`, each expanded with the message it records
+    #[must_use]
+    pub fn chain_pager(&self, url: &str, chain: &str) -> RangePager<'_, ChainEntryRecord> {
+        let (url, chain) = (url.to_string(), chain.to_string());
+        RangePager::new(move |start, count| {
+            let (url, chain) = (url.clone(), chain.clone());
+            async move {
+                let query = ChainQuery {
+                    name: Some(chain),
+                    range: Some(range(start, count, true)),
+                    ..Default::default()
+                };
+                match self.v3().query_chain(&url, query).await? {
+                    ChainResult::Entries(page) => Ok(page),
+                    other => Err(JsonRpcError::General(format!("Expected a range of chain entries, got {other:?}"))),
+                }
+            }
+        })
+    }
+
+    /// Pager over the minor blocks of the partition at `url` (e.g.
+    /// `acc://bvn-Apollo.acme`)
+    #[must_use]
+    pub fn minor_block_pager(&self, url: &str) -> RangePager<'_, MinorBlockRecord> {
+        let url = url.to_string();
+        RangePager::new(move |start, count| {
+            let url = url.clone();
+            async move {
+                let query = BlockQuery {
+                    minor_range: Some(range(start, count, false)),
+                    ..Default::default()
+                };
+                match self.v3().query_block(&url, query).await? {
+                    BlockResult::MinorBlocks(page) => Ok(page),
+                    other => Err(JsonRpcError::General(format!("Expected a range of minor blocks, got {other:?}"))),
+                }
+            }
+        })
+    }
+
+    /// Pager over the major blocks of the partition at `url`
+    #[must_use]
+    pub fn major_block_pager(&self, url: &str) -> RangePager<'_, MajorBlockRecord> {
+        let url = url.to_string();
+        RangePager::new(move |start, count| {
+            let url = url.clone();
+            async move {
+                let query = BlockQuery {
+                    major_range: Some(range(start, count, false)),
+                    ..Default::default()
+                };
+                match self.v3().query_block(&url, query).await? {
+                    BlockResult::MajorBlocks(page) => Ok(page),
+                    other => Err(JsonRpcError::General(format!("Expected a range of major blocks, got {other:?}"))),
+                }
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::mock::{MockTransport, TestHarness};
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_directory_pages_against_simulator() {
+        let harness = TestHarness::new().await.unwrap();
+        let (_, adi) = harness.adi("lister").await.unwrap();
+        let quick = harness.quick();
+        for name in ["a", "b", "c", "d"] {
+            assert!(quick.create_data_account(&adi, name).await.unwrap().success);
+        }
+        let client = quick.client();
+        let all = client.directory_pager(&adi.url).collect().await.unwrap();
+        let mut pager = client.directory_pager(&adi.url).page_size(2);
+        let first = pager.next_page().await.unwrap().unwrap();
+        assert_eq!((first.start, first.records.len()), (0, 2));
+        assert_eq!(pager.total(), Some(all.len() as u64));
+        let mut rest = Vec::new();
+        while let Some(entry) = pager.next().await.unwrap() {
+            rest.push(entry);
+        }
+        assert_eq!([first.records, rest].concat(), all);
+        assert!(all.iter().any(|entry| entry.url.ends_with("lister.acme/d")));
+        assert!(pager.next_page().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_stream_retries_a_failed_page() {
+        let mock = MockTransport::new();
+        let client = mock.client().await.unwrap();
+        let page = |start: u64, urls: &[&str]| {
+            let records: Vec<_> = urls.iter().map(|url| json!({"recordType": "url", "value": url})).collect();
+            json!({"recordType": "range", "start": start, "total": 3, "records": records})
+        };
+        mock.once("query", page(0, &["acc://x.acme/a", "acc://x.acme/b"]));
+        mock.once_error("query", -32000, "busy");
+        mock.once("query", page(2, &["acc://x.acme/c"]));
+
+        let mut pager = client.directory_pager("acc://x.acme").page_size(2);
+        let mut seen = Vec::new();
+        let mut errors = 0;
+        while let Some(item) = std::future::poll_fn(|cx| Pin::new(&mut pager).poll_next(cx)).await {
+            match item {
+                Ok(entry) => seen.push(entry.url),
+                Err(_) => errors += 1,
+            }
+        }
+        assert_eq!(seen, ["acc://x.acme/a", "acc://x.acme/b", "acc://x.acme/c"]);
+        assert_eq!(errors, 1);
+        let starts: Vec<_> = mock
+            .calls_to("query")
+            .iter()
+            .map(|call| call.params["query"]["range"]["start"].clone())
+            .collect();
+        assert_eq!(starts, [json!(0), json!(2), json!(2)]);
+    }
+}
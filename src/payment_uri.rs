@@ -0,0 +1,320 @@
+//! Payment request URIs
+//!
+//! A [`PaymentRequest`] encodes "pay this account, optionally this amount of
+//! this token, with this memo" as a single string suitable for a QR code or a
+//! link, for example:
+//!
+//! ```text
+//! accumulate:alice.acme/tokens?amount=1.5&token=acc://ACME&memo=invoice+42
+//! ```
+//!
+//! Amounts are decimal token units, not base units, so the payload stays
+//! readable; use [`PaymentRequest::amount_base_units`] with the token's
+//! precision to convert. Rendering the QR image is left to the application.
+
+use crate::acc_url::AccUrl;
+use crate::amounts::{format_base_units, parse_base_units};
+use std::fmt;
+use std::str::FromStr;
+use thiserror::Error;
+use url::form_urlencoded;
+
+/// URI scheme used for payment requests
+pub const PAYMENT_URI_SCHEME: &str = "accumulate";
+
+/// Errors parsing a payment request URI
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum PaymentUriError {
+    /// The string does not start with `accumulate:`
+    #[error("not an {PAYMENT_URI_SCHEME}: URI")]
+    Scheme,
+    /// No account URL before the query string
+    #[error("payment URI has no account")]
+    MissingAccount,
+    /// The account URL is not a valid Accumulate URL
+    #[error("invalid account URL: {0}")]
+    InvalidAccount(String),
+    /// The amount is not a positive decimal number
+    #[error("invalid amount: {0}")]
+    InvalidAmount(String),
+    /// The token URL is not a valid Accumulate URL
+    #[error("invalid token URL: {0}")]
+    InvalidToken(String),
+    /// A parameter appears more than once
+    #[error("duplicate parameter: {0}")]
+    Duplicate(String),
+    /// A `req-` parameter this implementation does not understand
+    #[error("unsupported required parameter: {0}")]
+    UnsupportedRequired(String),
+}
+
+/// A payment request: recipient plus optional amount, token, memo and label
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PaymentRequest {
+    /// Recipient account, normalized to `acc://...`
+    pub account: String,
+    /// Requested amount in decimal token units (e.g. `"1.5"`)
+    pub amount: Option<String>,
+    /// Token issuer URL; wallets should assume ACME when absent
+    pub token: Option<String>,
+    /// Memo to attach to the transaction
+    pub memo: Option<String>,
+    /// Human-readable name of the recipient
+    pub label: Option<String>,
+}
+
+impl PaymentRequest {
+    /// Request a payment to `account`
+    pub fn new(account: impl AsRef<str>) -> Result<Self, PaymentUriError> {
+        Ok(Self {
+            account: normalize_url(account.as_ref())
+                .ok_or_else(|| PaymentUriError::InvalidAccount(account.as_ref().to_string()))?,
+            ..Self::default()
+        })
+    }
+
+    /// Set the amount from a decimal string such as `"1.5"`
+    pub fn with_amount(mut self, amount: impl Into<String>) -> Result<Self, PaymentUriError> {
+        let amount = amount.into();
+        if !is_valid_amount(&amount) {
+            return Err(PaymentUriError::InvalidAmount(amount));
+        }
+        self.amount = Some(amount);
+        Ok(self)
+    }
+
+    /// Set the amount from base units at the given token precision
+    #[must_use]
+    pub fn with_base_units(mut self, units: u128, precision: u32) -> Self {
+        self.amount = Some(format_base_units(units, precision));
+        self
+    }
+
+    /// Set the token issuer URL
+    pub fn with_token(mut self, token: impl AsRef<str>) -> Result<Self, PaymentUriError> {
+        self.token = Some(
+            normalize_url(token.as_ref())
+                .ok_or_else(|| PaymentUriError::InvalidToken(token.as_ref().to_string()))?,
+        );
+        Ok(self)
+    }
+
+    /// Set the memo
+    #[must_use]
+    pub fn with_memo(mut self, memo: impl Into<String>) -> Self {
+        self.memo = Some(memo.into());
+        self
+    }
+
+    /// Set the recipient label
+    #[must_use]
+    pub fn with_label(mut self, label: impl Into<String>) -> Self {
+        self.label = Some(label.into());
+        self
+    }
+
+    /// The amount in base units at `precision`, if an amount was requested
+    ///
+    /// Returns `Err` when the amount has more fractional digits than the
+    /// token supports.
+    pub fn amount_base_units(&self, precision: u32) -> Result<Option<u128>, PaymentUriError> {
+        self.amount
+            .as_deref()
+            .map(|a| {
+                parse_base_units(a, precision)
+                    .ok_or_else(|| PaymentUriError::InvalidAmount(a.to_string()))
+            })
+            .transpose()
+    }
+
+    /// Encode as an `accumulate:` URI
+    #[must_use]
+    pub fn to_uri(&self) -> String {
+        self.to_string()
+    }
+
+    /// Parse an `accumulate:` URI
+    ///
+    /// The scheme is case-insensitive and the account may be written with or
+    /// without its `acc://` prefix. Unknown parameters are ignored unless
+    /// they are prefixed with `req-`, in which case the request is rejected.
+    pub fn parse(uri: &str) -> Result<Self, PaymentUriError> {
+        let uri = uri.trim();
+        let rest = uri
+            .split_once(':')
+            .filter(|(scheme, _)| scheme.eq_ignore_ascii_case(PAYMENT_URI_SCHEME))
+            .map(|(_, rest)| rest)
+            .ok_or(PaymentUriError::Scheme)?;
+        let (account, query) = rest.split_once('?').unwrap_or((rest, ""));
+        if account.trim_start_matches("//").is_empty() {
+            return Err(PaymentUriError::MissingAccount);
+        }
+        let mut request = Self::new(account)?;
+
+        for (key, value) in form_urlencoded::parse(query.as_bytes()) {
+            let slot = match key.as_ref() {
+                "amount" => &mut request.amount,
+                "token" => &mut request.token,
+                "memo" => &mut request.memo,
+                "label" => &mut request.label,
+                other if other.starts_with("req-") => {
+                    return Err(PaymentUriError::UnsupportedRequired(other.to_string()))
+                }
+                _ => continue,
+            };
+            if slot.is_some() {
+                return Err(PaymentUriError::Duplicate(key.into_owned()));
+            }
+            *slot = Some(value.into_owned());
+        }
+
+        if let Some(amount) = request.amount.take() {
+            request = request.with_amount(amount)?;
+        }
+        if let Some(token) = request.token.take() {
+            request = request.with_token(token)?;
+        }
+        Ok(request)
+    }
+}
+
+impl fmt::Display for PaymentRequest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let account = self.account.strip_prefix("acc://").unwrap_or(&self.account);
+        write!(f, "{PAYMENT_URI_SCHEME}:{account}")?;
+
+        let mut query = form_urlencoded::Serializer::new(String::new());
+        let params = [
+            ("amount", &self.amount),
+            ("token", &self.token),
+            ("memo", &self.memo),
+            ("label", &self.label),
+        ];
+        let mut any = false;
+        for (key, value) in params {
+            if let Some(value) = value {
+                query.append_pair(key, value);
+                any = true;
+            }
+        }
+        if any {
+            // Keep `acc://` and `/` readable in token URLs
+            let query = query.finish().replace("%3A", ":").replace("%2F", "/");
+            write!(f, "?{query}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for PaymentRequest {
+    type Err = PaymentUriError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+/// Normalize an account URL to `acc://authority/path`
+fn normalize_url(url: &str) -> Option<String> {
+    let url = url.trim();
+    let path = url
+        .strip_prefix("acc://")
+        .or_else(|| url.strip_prefix("//"))
+        .unwrap_or(url);
+    AccUrl::parse(&format!("acc://{path}"))
+        .ok()
+        .filter(|url| url.user_info().is_none())
+        .map(String::from)
+}
+
+/// A positive decimal number with no sign or exponent
+fn is_valid_amount(amount: &str) -> bool {
+    let (whole, fraction) = amount.split_once('.').unwrap_or((amount, ""));
+    let digits = |s: &str| s.bytes().all(|b| b.is_ascii_digit());
+    !whole.is_empty()
+        && digits(whole)
+        && digits(fraction)
+        && !amount.ends_with('.')
+        && amount.bytes().any(|b| matches!(b, b'1'..=b'9'))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_roundtrip() {
+        let request = PaymentRequest::new("acc://alice.acme/tokens")
+            .unwrap()
+            .with_amount("1.5")
+            .unwrap()
+            .with_token("acc://ACME")
+            .unwrap()
+            .with_memo("invoice #42 & tip")
+            .with_label("Alice");
+
+        let uri = request.to_uri();
+        assert_eq!(
+            uri,
+            "accumulate:alice.acme/tokens?amount=1.5&token=acc://ACME&memo=invoice+%2342+%26+tip&label=Alice"
+        );
+        assert_eq!(PaymentRequest::parse(&uri).unwrap(), request);
+        assert_eq!(request.amount_base_units(8).unwrap(), Some(150_000_000));
+
+        let bare = PaymentRequest::new("bob.acme/tokens").unwrap();
+        assert_eq!(bare.to_uri(), "accumulate:bob.acme/tokens");
+        assert_eq!(bare.to_uri().parse::<PaymentRequest>().unwrap(), bare);
+    }
+
+    #[test]
+    fn test_parse_variants() {
+        let request =
+            PaymentRequest::parse("ACCUMULATE://alice.acme/tokens?memo=hi%20there&foo=bar")
+                .unwrap();
+        assert_eq!(request.account, "acc://alice.acme/tokens");
+        assert_eq!(request.memo.as_deref(), Some("hi there"));
+        assert!(request.amount.is_none());
+
+        let request =
+            PaymentRequest::parse("accumulate:acc://alice.acme/tokens?token=ACME").unwrap();
+        assert_eq!(request.account, "acc://alice.acme/tokens");
+        assert_eq!(request.token.as_deref(), Some("acc://ACME"));
+
+        let request = PaymentRequest::new("alice.acme")
+            .unwrap()
+            .with_base_units(1, 8);
+        assert_eq!(request.amount.as_deref(), Some("0.00000001"));
+    }
+
+    #[test]
+    fn test_parse_rejects() {
+        assert_eq!(
+            PaymentRequest::parse("bitcoin:abc"),
+            Err(PaymentUriError::Scheme)
+        );
+        assert_eq!(
+            PaymentRequest::parse("accumulate:?amount=1"),
+            Err(PaymentUriError::MissingAccount)
+        );
+        assert!(matches!(
+            PaymentRequest::parse("accumulate:alice.acme?amount=-1"),
+            Err(PaymentUriError::InvalidAmount(_))
+        ));
+        assert!(matches!(
+            PaymentRequest::parse("accumulate:alice.acme?amount=0"),
+            Err(PaymentUriError::InvalidAmount(_))
+        ));
+        assert_eq!(
+            PaymentRequest::parse("accumulate:alice.acme?memo=a&memo=b"),
+            Err(PaymentUriError::Duplicate("memo".into()))
+        );
+        assert_eq!(
+            PaymentRequest::parse("accumulate:alice.acme?req-expiry=10"),
+            Err(PaymentUriError::UnsupportedRequired("req-expiry".into()))
+        );
+
+        let request = PaymentRequest::parse("accumulate:alice.acme?amount=0.000000001").unwrap();
+        assert!(request.amount_base_units(8).is_err());
+    }
+}
@@ -0,0 +1,484 @@
+//! Multi-recipient token payouts
+//!
+//! [`PayoutBatch`] collects `(recipient, amount)` pairs, validates every one of
+//! them before anything is signed, and packs them into as few sendTokens
+//! transactions as the per-transaction recipient limit allows.
+//! [`PayoutBatch::submit`] signs those transactions with a single
+//! [`SmartSigner`] and sends them in one `submitMulti` call, returning a
+//! [`PayoutReport`] that says which recipients went out in which transaction.
+
+use crate::acc_url::AccUrl;
+use crate::helpers::{extract_txid, HeaderOptions, SmartSigner, TxBody};
+use crate::json_rpc_client::JsonRpcError;
+use serde_json::{json, Value};
+use std::fmt::Write as _;
+use std::time::Duration;
+use thiserror::Error;
+
+/// Default number of recipients packed into one sendTokens transaction
+pub const DEFAULT_MAX_RECIPIENTS_PER_TX: usize = 100;
+
+/// A validated payment to a single recipient
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Payout {
+    /// Recipient token account URL
+    pub url: String,
+    /// Amount in token base units
+    pub amount: u64,
+}
+
+/// A payout entry that failed validation
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayoutIssue {
+    /// Position of the entry in the order it was added
+    pub index: usize,
+    /// Recipient URL as given
+    pub url: String,
+    /// Amount as given
+    pub amount: String,
+    /// Why the entry was rejected
+    pub reason: String,
+}
+
+/// Errors from building or submitting a payout batch
+#[derive(Error, Debug)]
+pub enum PayoutError {
+    /// No recipients were added
+    #[error("Payout batch has no recipients")]
+    Empty,
+
+    /// One or more entries failed validation
+    #[error("{}", describe_issues(.0))]
+    Invalid(Vec<PayoutIssue>),
+
+    /// The signer could not be refreshed or could not sign
+    #[error("Signing failed: {0}")]
+    Signing(JsonRpcError),
+
+    /// The `submitMulti` request itself failed
+    #[error("Submission failed: {0}")]
+    Submit(JsonRpcError),
+}
+
+fn describe_issues(issues: &[PayoutIssue]) -> String {
+    let mut out = format!("{} invalid payout(s)", issues.len());
+    for issue in issues {
+        let _ = write!(out, "; #{} {} ({}): {}", issue.index, issue.url, issue.amount, issue.reason);
+    }
+    out
+}
+
+/// Builder for a payout from one token account to many recipients
+///
+/// Amounts are strings of token base units, the same form sendTokens bodies
+/// carry. Use [`Amount::to_wire`](crate::Amount::to_wire) to convert from ACME.
+#[derive(Debug, Clone)]
+pub struct PayoutBatch {
+    source: String,
+    entries: Vec<(String, String)>,
+    max_recipients: usize,
+    memo: Option<String>,
+}
+
+impl PayoutBatch {
+    /// Start a payout from the `source` token account
+    #[must_use]
+    pub fn new(source: &str) -> Self {
+        Self {
+            source: source.to_string(),
+            entries: Vec::new(),
+            max_recipients: DEFAULT_MAX_RECIPIENTS_PER_TX,
+            memo: None,
+        }
+    }
+
+    /// Add a recipient
+    pub fn add(&mut self, url: &str, amount: &str) -> &mut Self {
+        self.entries.push((url.to_string(), amount.to_string()));
+        self
+    }
+
+    /// Add every recipient from an iterator of `(url, amount)` pairs
+    pub fn add_all<I, U, A>(&mut self, recipients: I) -> &mut Self
+    where
+        I: IntoIterator<Item = (U, A)>,
+        U: AsRef<str>,
+        A: AsRef<str>,
+    {
+        for (url, amount) in recipients {
+            self.add(url.as_ref(), amount.as_ref());
+        }
+        self
+    }
+
+    /// Limit the number of recipients per transaction (at least 1)
+    pub fn max_recipients_per_tx(&mut self, limit: usize) -> &mut Self {
+        self.max_recipients = limit.max(1);
+        self
+    }
+
+    /// Memo attached to every transaction in the batch
+    pub fn memo(&mut self, memo: &str) -> &mut Self {
+        self.memo = Some(memo.to_string());
+        self
+    }
+
+    /// Number of recipients added so far
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Whether no recipients have been added
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Validate every entry, reporting all problems at once
+    pub fn validate(&self) -> Result<Vec<Payout>, PayoutError> {
+        if self.entries.is_empty() {
+            return Err(PayoutError::Empty);
+        }
+
+        let source = normalize(&self.source);
+        let mut payouts = Vec::with_capacity(self.entries.len());
+        let mut issues = Vec::new();
+        for (index, (url, amount)) in self.entries.iter().enumerate() {
+            let checked = check_url(url)
+                .and_then(|()| {
+                    if normalize(url) == source {
+                        Err("recipient is the source account".to_string())
+                    } else {
+                        Ok(())
+                    }
+                })
+                .and_then(|()| check_amount(amount));
+            match checked {
+                Ok(amount) => payouts.push(Payout { url: url.clone(), amount }),
+                Err(reason) => issues.push(PayoutIssue {
+                    index,
+                    url: url.clone(),
+                    amount: amount.clone(),
+                    reason,
+                }),
+            }
+        }
+
+        if issues.is_empty() {
+            Ok(payouts)
+        } else {
+            Err(PayoutError::Invalid(issues))
+        }
+    }
+
+    /// Validate and split the recipients into per-transaction groups
+    pub fn plan(&self) -> Result<Vec<Vec<Payout>>, PayoutError> {
+        Ok(self
+            .validate()?
+            .chunks(self.max_recipients)
+            .map(<[Payout]>::to_vec)
+            .collect())
+    }
+
+    /// The sendTokens bodies this batch would submit
+    pub fn bodies(&self) -> Result<Vec<Value>, PayoutError> {
+        Ok(self.plan()?.iter().map(|group| send_tokens_body(group)).collect())
+    }
+
+    /// Sign every transaction in the batch and return the envelopes
+    ///
+    /// Each envelope gets its own signature timestamp; the signer's cached
+    /// version is used as-is, so call [`SmartSigner::refresh_version`] first
+    /// or use [`submit`](Self::submit).
+    pub async fn sign(&self, signer: &SmartSigner<'_>) -> Result<Vec<Value>, PayoutError> {
+        let options = HeaderOptions {
+            memo: self.memo.clone(),
+            ..Default::default()
+        };
+
+        let mut envelopes = Vec::new();
+        let mut last_timestamp = 0;
+        for group in self.plan()? {
+            let body = send_tokens_body(&group);
+            loop {
+                let envelope = signer
+                    .sign_with_options(&self.source, &body, &options)
+                    .map_err(PayoutError::Signing)?;
+                let timestamp = envelope["signatures"][0]["timestamp"].as_u64().unwrap_or(0);
+                // The network rejects a signature whose timestamp is not
+                // greater than the signer's previous one.
+                if timestamp > last_timestamp {
+                    last_timestamp = timestamp;
+                    envelopes.push(envelope);
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        }
+        Ok(envelopes)
+    }
+
+    /// Sign and submit the whole batch in one `submitMulti` call
+    ///
+    /// Validation failures are returned before anything is signed. Once the
+    /// request reaches the network, per-transaction failures are reported in
+    /// the [`PayoutReport`] rather than as an error.
+    pub async fn submit(&self, signer: &mut SmartSigner<'_>) -> Result<PayoutReport, PayoutError> {
+        let plan = self.plan()?;
+        signer.refresh_version().await.map_err(PayoutError::Signing)?;
+        let envelopes = self.sign(signer).await?;
+
+        let requests: Vec<Value> = envelopes.into_iter().map(|e| json!({ "envelope": e })).collect();
+        let response: Value = signer
+            .client()
+            .v3_client
+            .call_v3("submitMulti", json!(requests))
+            .await
+            .map_err(PayoutError::Submit)?;
+
+        Ok(build_payout_report(plan, &response))
+    }
+}
+
+/// Outcome of one sendTokens transaction in a payout
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayoutTxResult {
+    /// Recipients paid by this transaction
+    pub recipients: Vec<Payout>,
+    /// Transaction ID reported by the network
+    pub txid: Option<String>,
+    /// Failure message, if the network rejected the transaction
+    pub error: Option<String>,
+}
+
+impl PayoutTxResult {
+    /// Whether the transaction was accepted
+    #[must_use]
+    pub const fn is_success(&self) -> bool {
+        self.error.is_none()
+    }
+
+    /// Sum of the amounts in this transaction
+    #[must_use]
+    pub fn total(&self) -> u128 {
+        self.recipients.iter().map(|p| u128::from(p.amount)).sum()
+    }
+}
+
+/// Consolidated result of [`PayoutBatch::submit`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PayoutReport {
+    /// One entry per transaction, in submission order
+    pub transactions: Vec<PayoutTxResult>,
+}
+
+impl PayoutReport {
+    /// Whether every transaction was accepted
+    #[must_use]
+    pub fn is_success(&self) -> bool {
+        self.transactions.iter().all(PayoutTxResult::is_success)
+    }
+
+    /// Total amount in accepted transactions
+    #[must_use]
+    pub fn total_submitted(&self) -> u128 {
+        self.transactions
+            .iter()
+            .filter(|t| t.is_success())
+            .map(PayoutTxResult::total)
+            .sum()
+    }
+
+    /// Transactions the network rejected
+    pub fn failed(&self) -> impl Iterator<Item = &PayoutTxResult> {
+        self.transactions.iter().filter(|t| !t.is_success())
+    }
+
+    /// Recipients whose transaction was rejected, for a retry batch
+    #[must_use]
+    pub fn unpaid(&self) -> Vec<&Payout> {
+        self.failed().flat_map(|t| &t.recipients).collect()
+    }
+}
+
+/// Match a `submitMulti` response to the planned transactions
+///
+/// The response holds one entry per envelope, each either a single submission
+/// or the list of submissions for the envelope's messages.
+#[must_use]
+pub fn build_payout_report(plan: Vec<Vec<Payout>>, response: &Value) -> PayoutReport {
+    let entries = response.as_array().map(Vec::as_slice).unwrap_or_default();
+    let transactions = plan
+        .into_iter()
+        .enumerate()
+        .map(|(i, recipients)| {
+            let Some(entry) = entries.get(i) else {
+                return PayoutTxResult {
+                    recipients,
+                    txid: None,
+                    error: Some("no response for this transaction".to_string()),
+                };
+            };
+            let (txid, error) = submission_outcome(entry);
+            PayoutTxResult { recipients, txid, error }
+        })
+        .collect();
+    PayoutReport { transactions }
+}
+
+/// Transaction ID and failure message of one `submitMulti` response entry
+///
+/// An entry is either a single submission or the list of submissions for the
+/// envelope's messages; it failed if any of them did.
+pub(crate) fn submission_outcome(entry: &Value) -> (Option<String>, Option<String>) {
+    let submissions = if entry.is_array() { entry.clone() } else { json!([entry]) };
+    let error = submissions.as_array().and_then(|subs| {
+        subs.iter()
+            .find(|s| s.get("success").and_then(Value::as_bool) == Some(false))
+            .map(|s| {
+                s.get("message")
+                    .and_then(Value::as_str)
+                    .filter(|m| !m.is_empty())
+                    .unwrap_or("rejected")
+                    .to_string()
+            })
+    });
+    (extract_txid(&submissions), error)
+}
+
+fn send_tokens_body(group: &[Payout]) -> Value {
+    let amounts: Vec<String> = group.iter().map(|p| p.amount.to_string()).collect();
+    let recipients: Vec<(&str, &str)> = group
+        .iter()
+        .zip(&amounts)
+        .map(|(p, a)| (p.url.as_str(), a.as_str()))
+        .collect();
+    TxBody::send_tokens_multi(&recipients)
+}
+
+fn normalize(url: &str) -> String {
+    url.trim_end_matches('/').to_ascii_lowercase()
+}
+
+fn check_url(url: &str) -> Result<(), String> {
+    AccUrl::parse(url).map(drop).map_err(|e| e.to_string())
+}
+
+fn check_amount(amount: &str) -> Result<u64, String> {
+    if amount.is_empty() || !amount.bytes().all(|b| b.is_ascii_digit()) {
+        return Err("amount must be an integer number of base units".to_string());
+    }
+    match amount.parse::<u64>() {
+        Ok(0) => Err("amount must be positive".to_string()),
+        Ok(value) => Ok(value),
+        Err(_) => Err("amount exceeds the maximum of 2^64-1 base units".to_string()),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::client::AccumulateClient;
+    use ed25519_dalek::SigningKey;
+
+    fn batch(count: usize) -> PayoutBatch {
+        let mut batch = PayoutBatch::new("acc://payer.acme/tokens");
+        batch.add_all((0..count).map(|i| (format!("acc://user{i}.acme/tokens"), (i + 1).to_string())));
+        batch
+    }
+
+    #[test]
+    fn test_validation_reports_every_issue() {
+        let mut batch = PayoutBatch::new("acc://payer.acme/tokens");
+        batch
+            .add("acc://bob.acme/tokens", "100")
+            .add("bob.acme/tokens", "100")
+            .add("acc://", "100")
+            .add("acc://bob.acme/tok ens", "100")
+            .add("acc://carol.acme/tokens", "0")
+            .add("acc://carol.acme/tokens", "1.5")
+            .add("acc://carol.acme/tokens", "18446744073709551616")
+            .add("ACC://Payer.acme/tokens/", "5");
+
+        let Err(PayoutError::Invalid(issues)) = batch.validate() else {
+            unreachable!("batch should be invalid");
+        };
+        let indexes: Vec<usize> = issues.iter().map(|i| i.index).collect();
+        assert_eq!(indexes, vec![1, 2, 3, 4, 5, 6, 7]);
+        assert!(issues[6].reason.contains("source account"));
+        assert!(PayoutError::Invalid(issues).to_string().starts_with("7 invalid payout(s)"));
+
+        assert!(matches!(PayoutBatch::new("acc://payer.acme/tokens").validate(), Err(PayoutError::Empty)));
+    }
+
+    #[test]
+    fn test_plan_splits_under_recipient_limit() {
+        let plan = batch(250).plan().unwrap();
+        assert_eq!(plan.iter().map(Vec::len).collect::<Vec<_>>(), vec![100, 100, 50]);
+        assert_eq!(plan[2][49].url, "acc://user249.acme/tokens");
+
+        let mut small = batch(5);
+        small.max_recipients_per_tx(2);
+        let bodies = small.bodies().unwrap();
+        assert_eq!(bodies.len(), 3);
+        assert_eq!(bodies[0]["type"], "sendTokens");
+        assert_eq!(bodies[0]["to"][1], json!({"url": "acc://user1.acme/tokens", "amount": "2"}));
+    }
+
+    #[tokio::test]
+    async fn test_sign_uses_increasing_timestamps() {
+        let client = AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap();
+        let signer = SmartSigner::new(&client, SigningKey::from_bytes(&[4u8; 32]), "acc://payer.acme/book/1");
+
+        let mut payout = batch(30);
+        payout.max_recipients_per_tx(10).memo("march payroll");
+        let envelopes = payout.sign(&signer).await.unwrap();
+        assert_eq!(envelopes.len(), 3);
+
+        let timestamps: Vec<u64> = envelopes
+            .iter()
+            .map(|e| e["signatures"][0]["timestamp"].as_u64().unwrap())
+            .collect();
+        assert!(timestamps.windows(2).all(|w| w[0] < w[1]));
+        for envelope in &envelopes {
+            let tx = &envelope["transaction"][0];
+            assert_eq!(tx["header"]["principal"], "acc://payer.acme/tokens");
+            assert_eq!(tx["header"]["memo"], "march payroll");
+            assert_eq!(tx["body"]["to"].as_array().unwrap().len(), 10);
+        }
+    }
+
+    #[test]
+    fn test_report_matches_responses_to_transactions() {
+        let mut payout = batch(5);
+        payout.max_recipients_per_tx(2);
+        let plan = payout.plan().unwrap();
+
+        let response = json!([
+            [{"status": {"txID": "acc://aa@payer.acme/tokens"}, "success": true, "message": ""}],
+            {"status": {"txID": "acc://bb@payer.acme/tokens"}, "success": false, "message": "insufficient balance"}
+        ]);
+        let report = build_payout_report(plan, &response);
+
+        assert_eq!(report.transactions.len(), 3);
+        assert_eq!(report.transactions[0].txid.as_deref(), Some("acc://aa@payer.acme/tokens"));
+        assert!(report.transactions[0].is_success());
+        assert_eq!(report.transactions[1].error.as_deref(), Some("insufficient balance"));
+        assert_eq!(report.transactions[2].error.as_deref(), Some("no response for this transaction"));
+        assert!(!report.is_success());
+        assert_eq!(report.total_submitted(), 1 + 2);
+        let unpaid: Vec<&str> = report.unpaid().iter().map(|p| p.url.as_str()).collect();
+        assert_eq!(
+            unpaid,
+            vec!["acc://user2.acme/tokens", "acc://user3.acme/tokens", "acc://user4.acme/tokens"]
+        );
+    }
+}
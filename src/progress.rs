@@ -0,0 +1,213 @@
+//! Progress reporting for long-running flows
+//!
+//! [`QuickStart`](crate::helpers::QuickStart) flows such as `fund_wallet` and
+//! `setup_adi` take tens of seconds and several transactions. They report each
+//! step to a [`ProgressReporter`] instead of printing, so a GUI or TUI can
+//! render progress from structured [`ProgressStep`]s. Nothing is reported
+//! unless a reporter is set; [`ProgressLog`] writes one line per event for
+//! command-line tools.
+//!
+//! ```
+//! use accumulate_client::progress::{ProgressReporter, ProgressStep};
+//!
+//! #[derive(Debug)]
+//! struct Bar;
+//!
+//! impl ProgressReporter for Bar {
+//!     fn completed(&self, step: &ProgressStep, _detail: &str) {
+//!         if let (Some(index), Some(total)) = (step.index, step.total) {
+//!             let _percent = 100 * index / total;
+//!         }
+//!     }
+//! }
+//! ```
+
+use std::fmt;
+use std::io::Write;
+use std::sync::Mutex;
+
+/// A step of a long-running flow
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressStep {
+    /// Flow the step belongs to, e.g. `fund_wallet`
+    pub flow: &'static str,
+    /// What the step does
+    pub name: String,
+    /// 1-based position among the flow's steps, when the flow knows it
+    pub index: Option<u32>,
+    /// Number of steps in the flow, when known
+    pub total: Option<u32>,
+    /// Account the step acts on
+    pub target: Option<String>,
+}
+
+impl ProgressStep {
+    /// A step called `name` of `flow`
+    pub fn new(flow: &'static str, name: impl Into<String>) -> Self {
+        Self {
+            flow,
+            name: name.into(),
+            index: None,
+            total: None,
+            target: None,
+        }
+    }
+
+    /// Mark this as step `index` of `total`
+    #[must_use]
+    pub const fn of(mut self, index: u32, total: u32) -> Self {
+        self.index = Some(index);
+        self.total = Some(total);
+        self
+    }
+
+    /// Set the account the step acts on
+    #[must_use]
+    pub fn on(mut self, target: impl Into<String>) -> Self {
+        self.target = Some(target.into());
+        self
+    }
+}
+
+impl fmt::Display for ProgressStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}] {}", self.flow, self.name)?;
+        if let (Some(index), Some(total)) = (self.index, self.total) {
+            write!(f, " ({index}/{total})")?;
+        }
+        if let Some(target) = &self.target {
+            write!(f, " {target}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Receives progress events from long-running flows
+///
+/// Every method has an empty default, so a reporter only implements what it
+/// renders. A failed step does not always end its flow: a retried faucet
+/// request fails and then starts again.
+pub trait ProgressReporter: Send + Sync + fmt::Debug {
+    /// `step` has started
+    fn started(&self, step: &ProgressStep) {
+        let _ = step;
+    }
+
+    /// `step` is still running; `message` says what it is waiting for
+    fn waiting(&self, step: &ProgressStep, message: &str) {
+        let _ = (step, message);
+    }
+
+    /// `step` finished; `detail` is its result, such as a transaction ID
+    fn completed(&self, step: &ProgressStep, detail: &str) {
+        let _ = (step, detail);
+    }
+
+    /// `step` failed with `error`
+    fn failed(&self, step: &ProgressStep, error: &str) {
+        let _ = (step, error);
+    }
+}
+
+/// Reporter that ignores every event
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoProgress;
+
+impl ProgressReporter for NoProgress {}
+
+/// Reporter writing one line per event, e.g. to stdout
+pub struct ProgressLog<W> {
+    out: Mutex<W>,
+}
+
+impl<W> fmt::Debug for ProgressLog<W> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ProgressLog").finish_non_exhaustive()
+    }
+}
+
+impl ProgressLog<std::io::Stdout> {
+    /// Log to stdout
+    #[must_use]
+    pub fn stdout() -> Self {
+        Self::new(std::io::stdout())
+    }
+}
+
+impl<W: Write + Send> ProgressLog<W> {
+    /// Log to `out`
+    pub const fn new(out: W) -> Self {
+        Self {
+            out: Mutex::new(out),
+        }
+    }
+
+    /// The writer, once no more events will be logged
+    pub fn into_inner(self) -> W {
+        self.out
+            .into_inner()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn line(&self, step: &ProgressStep, event: &str) {
+        // Progress output is best-effort
+        let _ = writeln!(
+            self.out
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner),
+            "{step}: {event}"
+        );
+    }
+}
+
+impl<W: Write + Send> ProgressReporter for ProgressLog<W> {
+    fn started(&self, step: &ProgressStep) {
+        self.line(step, "started");
+    }
+
+    fn waiting(&self, step: &ProgressStep, message: &str) {
+        self.line(step, message);
+    }
+
+    fn completed(&self, step: &ProgressStep, detail: &str) {
+        if detail.is_empty() {
+            self.line(step, "done");
+        } else {
+            self.line(step, &format!("done, {detail}"));
+        }
+    }
+
+    fn failed(&self, step: &ProgressStep, error: &str) {
+        self.line(step, &format!("failed, {error}"));
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_progress_log_lines() {
+        let log = ProgressLog::new(Vec::new());
+        let step = ProgressStep::new("fund_wallet", "faucet request")
+            .of(2, 3)
+            .on("acc://abc/ACME");
+        log.started(&step);
+        log.waiting(&step, "attempt 1/30");
+        log.completed(&step, "txid acc://ff@abc");
+        log.failed(
+            &ProgressStep::new("setup_adi", "create identity"),
+            "out of credits",
+        );
+        NoProgress.started(&step);
+
+        assert_eq!(
+            String::from_utf8(log.into_inner()).unwrap(),
+            "[fund_wallet] faucet request (2/3) acc://abc/ACME: started\n\
+             [fund_wallet] faucet request (2/3) acc://abc/ACME: attempt 1/30\n\
+             [fund_wallet] faucet request (2/3) acc://abc/ACME: done, txid acc://ff@abc\n\
+             [setup_adi] create identity: failed, out of credits\n"
+        );
+    }
+}
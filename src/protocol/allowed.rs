@@ -0,0 +1,237 @@
+//! Key page transaction blacklists
+//!
+//! A key page can be barred from signing some transaction types with an
+//! `updateAllowed` operation. The page records the barred types as a bit
+//! mask, one [`AllowedTransactionBit`] per bit; only `updateKeyPage` and
+//! `updateAccountAuth` can be barred.
+//!
+//! ```
+//! use accumulate_client::{AllowedTransactionBit, AllowedTransactions};
+//!
+//! let mut denied = AllowedTransactions::empty();
+//! denied.set(AllowedTransactionBit::UpdateKeyPage);
+//! assert!(denied.contains(AllowedTransactionBit::UpdateKeyPage));
+//! assert_eq!(denied.bits(), 0b10);
+//! assert_eq!(serde_json::to_string(&denied).unwrap(), "2");
+//! ```
+
+use crate::generated::enums::AllowedTransactionBit;
+use serde::de::{self, SeqAccess, Visitor};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::ops::{BitOr, BitOrAssign};
+
+impl AllowedTransactionBit {
+    /// Every bit, in bit order
+    pub const ALL: [Self; 2] = [Self::UpdateKeyPage, Self::UpdateAccountAuth];
+
+    /// Bit position in the mask (matches Go protocol)
+    #[must_use]
+    pub const fn value(self) -> u64 {
+        match self {
+            Self::UpdateKeyPage => 1,
+            Self::UpdateAccountAuth => 2,
+        }
+    }
+
+    /// The bit at position `value`
+    #[must_use]
+    pub const fn from_u64(value: u64) -> Option<Self> {
+        match value {
+            1 => Some(Self::UpdateKeyPage),
+            2 => Some(Self::UpdateAccountAuth),
+            _ => None,
+        }
+    }
+
+    /// The bit for a transaction type name, ignoring case; `None` for types
+    /// a page cannot be barred from
+    #[must_use]
+    pub fn for_transaction(tx_type: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|bit| bit.transaction_type().eq_ignore_ascii_case(tx_type))
+    }
+
+    /// Name of the transaction type this bit bars, e.g. `"updateKeyPage"`
+    #[must_use]
+    pub const fn transaction_type(self) -> &'static str {
+        match self {
+            Self::UpdateKeyPage => "updateKeyPage",
+            Self::UpdateAccountAuth => "updateAccountAuth",
+        }
+    }
+}
+
+/// A set of [`AllowedTransactionBit`]s, stored as the protocol's `u64` mask
+///
+/// Serializes as the number; deserializes from the number or from a list of
+/// transaction type names, the form nodes report a page's blacklist in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct AllowedTransactions(u64);
+
+impl AllowedTransactions {
+    /// No bits set
+    #[must_use]
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    /// The mask `bits` as is, unknown bits included
+    #[must_use]
+    pub const fn from_bits(bits: u64) -> Self {
+        Self(bits)
+    }
+
+    /// The raw mask
+    #[must_use]
+    pub const fn bits(self) -> u64 {
+        self.0
+    }
+
+    /// Whether no bit is set
+    #[must_use]
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Set `bit`
+    pub fn set(&mut self, bit: AllowedTransactionBit) {
+        self.0 |= 1 << bit.value();
+    }
+
+    /// Clear `bit`
+    pub fn clear(&mut self, bit: AllowedTransactionBit) {
+        self.0 &= !(1 << bit.value());
+    }
+
+    /// Whether `bit` is set
+    #[must_use]
+    pub const fn contains(self, bit: AllowedTransactionBit) -> bool {
+        self.0 & (1 << bit.value()) != 0
+    }
+
+    /// Copy with `bit` set
+    #[must_use]
+    pub fn with(mut self, bit: AllowedTransactionBit) -> Self {
+        self.set(bit);
+        self
+    }
+
+    /// The known bits that are set, in bit order
+    pub fn iter(self) -> impl Iterator<Item = AllowedTransactionBit> {
+        AllowedTransactionBit::ALL
+            .into_iter()
+            .filter(move |bit| self.contains(*bit))
+    }
+
+    /// Names of the transaction types set, e.g. for
+    /// [`UpdateKeyPageBuilder::update_allowed`](crate::builders::UpdateKeyPageBuilder::update_allowed)
+    #[must_use]
+    pub fn transaction_types(self) -> Vec<&'static str> {
+        self.iter().map(AllowedTransactionBit::transaction_type).collect()
+    }
+}
+
+impl From<AllowedTransactionBit> for AllowedTransactions {
+    fn from(bit: AllowedTransactionBit) -> Self {
+        Self::empty().with(bit)
+    }
+}
+
+impl FromIterator<AllowedTransactionBit> for AllowedTransactions {
+    fn from_iter<I: IntoIterator<Item = AllowedTransactionBit>>(bits: I) -> Self {
+        bits.into_iter().fold(Self::empty(), Self::with)
+    }
+}
+
+impl BitOr for AllowedTransactions {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+impl BitOr<AllowedTransactionBit> for AllowedTransactions {
+    type Output = Self;
+
+    fn bitor(self, bit: AllowedTransactionBit) -> Self {
+        self.with(bit)
+    }
+}
+
+impl BitOrAssign for AllowedTransactions {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+}
+
+impl Serialize for AllowedTransactions {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_u64(self.0)
+    }
+}
+
+impl<'de> Deserialize<'de> for AllowedTransactions {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct MaskVisitor;
+
+        impl<'de> Visitor<'de> for MaskVisitor {
+            type Value = AllowedTransactions;
+
+            fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str("a bit mask or a list of transaction types")
+            }
+
+            fn visit_u64<E: de::Error>(self, bits: u64) -> Result<Self::Value, E> {
+                Ok(AllowedTransactions(bits))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+                let mut mask = AllowedTransactions::empty();
+                while let Some(name) = seq.next_element::<String>()? {
+                    let bit = AllowedTransactionBit::for_transaction(&name)
+                        .ok_or_else(|| de::Error::custom(format!("{name} cannot be disallowed")))?;
+                    mask.set(bit);
+                }
+                Ok(mask)
+            }
+        }
+
+        deserializer.deserialize_any(MaskVisitor)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_clear_contains() {
+        let mut mask = AllowedTransactions::empty();
+        assert!(mask.is_empty());
+        mask.set(AllowedTransactionBit::UpdateAccountAuth);
+        mask.set(AllowedTransactionBit::UpdateKeyPage);
+        assert_eq!(mask.bits(), 0b110);
+        mask.clear(AllowedTransactionBit::UpdateKeyPage);
+        assert!(!mask.contains(AllowedTransactionBit::UpdateKeyPage));
+        assert!(mask.contains(AllowedTransactionBit::UpdateAccountAuth));
+        assert_eq!(mask.transaction_types(), ["updateAccountAuth"]);
+
+        let all: AllowedTransactions = AllowedTransactionBit::ALL.into_iter().collect();
+        assert_eq!(all, AllowedTransactions::from(AllowedTransactionBit::UpdateKeyPage) | mask);
+        assert_eq!(all.iter().count(), 2);
+    }
+
+    #[test]
+    fn test_serde() {
+        let mask = AllowedTransactions::from_bits(0b100);
+        assert_eq!(serde_json::to_value(mask).unwrap(), 4);
+        assert_eq!(serde_json::from_value::<AllowedTransactions>(4.into()).unwrap(), mask);
+        let names = serde_json::json!(["updateAccountAuth", "UPDATEKEYPAGE"]);
+        assert_eq!(serde_json::from_value::<AllowedTransactions>(names).unwrap().bits(), 0b110);
+        assert!(serde_json::from_value::<AllowedTransactions>(serde_json::json!(["sendTokens"])).is_err());
+    }
+}
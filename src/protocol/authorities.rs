@@ -0,0 +1,292 @@
+//! Outstanding authority approvals for an envelope
+//!
+//! A transaction executes once every enabled authority of its principal, and
+//! every additional authority named in the header's `authorities` field, has
+//! approved it. [`required_authorities`] lists those authorities next to the
+//! envelope signatures that speak for each, so a caller can see which
+//! approvals still have to be gathered after `sign_with_options`.
+//!
+//! A signature counts for the key book its signer page belongs to (or for the
+//! lite identity that signed). Page thresholds are not checked: one signature
+//! from a page marks its book as signed.
+
+use super::TransactionEnvelope;
+use crate::acc_url::AccUrl;
+use crate::client::AccumulateClient;
+use crate::json_rpc_client::JsonRpcError;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Why an authority has to approve the transaction
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum AuthoritySource {
+    /// Listed in the principal account's authority set
+    Account,
+    /// Listed in the transaction header's `authorities` field
+    Header,
+}
+
+/// An authority whose approval the transaction needs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequiredAuthority {
+    /// Authority URL (key book or lite identity)
+    pub url: String,
+    /// Where the requirement comes from
+    pub source: AuthoritySource,
+    /// Signers in the envelope that approve on behalf of this authority
+    pub signers: Vec<String>,
+}
+
+impl RequiredAuthority {
+    /// Whether the envelope already carries a signature for this authority
+    #[must_use]
+    pub fn is_satisfied(&self) -> bool {
+        !self.signers.is_empty()
+    }
+}
+
+impl TransactionEnvelope {
+    /// Authorities that must approve this envelope's transaction
+    ///
+    /// See [`required_authorities`].
+    #[must_use]
+    pub fn required_authorities(&self, account_authorities: &[String]) -> Vec<RequiredAuthority> {
+        serde_json::to_value(self)
+            .map(|value| required_authorities(&value, account_authorities))
+            .unwrap_or_default()
+    }
+}
+
+/// List the authorities that must approve an envelope in its JSON form
+///
+/// `account_authorities` are the enabled authorities of the principal; the
+/// header's additional `authorities` are read from the envelope's first
+/// transaction. An authority listed in both places is reported once, as
+/// [`AuthoritySource::Account`].
+#[must_use]
+pub fn required_authorities(
+    envelope: &Value,
+    account_authorities: &[String],
+) -> Vec<RequiredAuthority> {
+    let header = first_transaction(envelope).and_then(|tx| tx.get("header"));
+    let header_authorities = header
+        .and_then(|h| h.get("authorities"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str);
+
+    let mut required: Vec<RequiredAuthority> = Vec::new();
+    let sources = account_authorities
+        .iter()
+        .map(|a| (a.as_str(), AuthoritySource::Account))
+        .chain(header_authorities.map(|a| (a, AuthoritySource::Header)));
+    for (url, source) in sources {
+        if required.iter().any(|r| same_url(&r.url, url)) {
+            continue;
+        }
+        required.push(RequiredAuthority {
+            url: url.to_string(),
+            source,
+            signers: Vec::new(),
+        });
+    }
+
+    let signatures = envelope
+        .get("signatures")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten();
+    for signer in signatures.filter_map(approving_signer) {
+        let book = parent_url(signer);
+        for authority in &mut required {
+            let approves = same_url(&authority.url, signer)
+                || book.as_ref().is_some_and(|b| same_url(&authority.url, b));
+            if approves && !authority.signers.iter().any(|s| same_url(s, signer)) {
+                authority.signers.push(signer.to_string());
+            }
+        }
+    }
+
+    required
+}
+
+/// Authorities from [`required_authorities`] that have not signed yet
+#[must_use]
+pub fn outstanding_authorities(envelope: &Value, account_authorities: &[String]) -> Vec<String> {
+    required_authorities(envelope, account_authorities)
+        .into_iter()
+        .filter(|a| !a.is_satisfied())
+        .map(|a| a.url)
+        .collect()
+}
+
+/// Enabled authorities from an account record returned by a `query`
+///
+/// Lite accounts carry no authority set; their lite identity is the
+/// authority.
+#[must_use]
+pub fn account_authorities(account: &Value) -> Vec<String> {
+    if let Some(entries) = account.get("authorities").and_then(Value::as_array) {
+        return entries
+            .iter()
+            .filter(|e| !e.get("disabled").and_then(Value::as_bool).unwrap_or(false))
+            .filter_map(|e| e.get("url").and_then(Value::as_str))
+            .map(str::to_string)
+            .collect();
+    }
+
+    let is_lite = account
+        .get("type")
+        .and_then(Value::as_str)
+        .is_some_and(|t| t.starts_with("lite"));
+    match account.get("url").and_then(Value::as_str) {
+        Some(url) if is_lite => {
+            let path = url.strip_prefix("acc://").unwrap_or(url);
+            let identity = path.split('/').next().unwrap_or(path);
+            vec![format!("acc://{identity}")]
+        }
+        _ => Vec::new(),
+    }
+}
+
+impl AccumulateClient {
+    /// Authorities that must approve `envelope`, using the principal's
+    /// current authority set
+    pub async fn required_authorities(
+        &self,
+        envelope: &Value,
+    ) -> Result<Vec<RequiredAuthority>, JsonRpcError> {
+        let principal = first_transaction(envelope)
+            .and_then(|tx| tx.get("header"))
+            .and_then(|h| h.get("principal"))
+            .and_then(Value::as_str)
+            .ok_or_else(|| JsonRpcError::General("envelope has no principal".to_string()))?;
+
+        let result: Value = self
+            .v3_client
+            .call_v3(
+                "query",
+                json!({"scope": principal, "query": {"queryType": "default"}}),
+            )
+            .await?;
+        let authorities = result
+            .get("account")
+            .map(account_authorities)
+            .unwrap_or_default();
+        Ok(required_authorities(envelope, &authorities))
+    }
+}
+
+fn first_transaction(envelope: &Value) -> Option<&Value> {
+    match envelope.get("transaction")? {
+        Value::Array(items) => items.first(),
+        single => Some(single),
+    }
+}
+
+/// The signer a signature approves as, unwrapping remote and delegated
+/// signatures
+///
+/// A delegated signature approves for its outermost delegator.
+fn approving_signer(signature: &Value) -> Option<&str> {
+    match signature.get("type").and_then(Value::as_str) {
+        Some("remote") => approving_signer(signature.get("signature")?),
+        Some("delegated") => signature.get("delegator").and_then(Value::as_str),
+        _ => signature.get("signer").and_then(Value::as_str),
+    }
+}
+
+fn parent_url(url: &str) -> Option<AccUrl> {
+    AccUrl::parse(url).ok()?.parent()
+}
+
+fn same_url(a: &str, b: &str) -> bool {
+    let parse = |u: &str| AccUrl::parse(u).or_else(|_| AccUrl::parse(&format!("acc://{u}")));
+    match (parse(a), parse(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a.eq_ignore_ascii_case(b),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn envelope(header_authorities: &[&str], signers: &[Value]) -> Value {
+        json!({
+            "transaction": [{
+                "header": {"principal": "acc://alice.acme/tokens", "authorities": header_authorities},
+                "body": {"type": "sendTokens", "to": []}
+            }],
+            "signatures": signers
+        })
+    }
+
+    #[test]
+    fn test_required_authorities() {
+        let envelope = envelope(
+            &["acc://sponsor.acme/book", "acc://ALICE.acme/book"],
+            &[json!({"type": "ed25519", "signer": "acc://sponsor.acme/book/1"})],
+        );
+        let account = vec!["acc://alice.acme/book".to_string()];
+
+        let required = required_authorities(&envelope, &account);
+        assert_eq!(required.len(), 2);
+        assert_eq!(required[0].url, "acc://alice.acme/book");
+        assert_eq!(required[0].source, AuthoritySource::Account);
+        assert!(!required[0].is_satisfied());
+        assert_eq!(required[1].source, AuthoritySource::Header);
+        assert_eq!(required[1].signers, vec!["acc://sponsor.acme/book/1"]);
+
+        assert_eq!(
+            outstanding_authorities(&envelope, &account),
+            vec!["acc://alice.acme/book"]
+        );
+    }
+
+    #[test]
+    fn test_delegated_and_remote_signatures() {
+        let envelope = envelope(
+            &["acc://bob.acme/book"],
+            &[json!({
+                "type": "remote",
+                "destination": "acc://alice.acme",
+                "signature": {
+                    "type": "delegated",
+                    "delegator": "acc://alice.acme/book/1",
+                    "signature": {"type": "ed25519", "signer": "acc://bob.acme/book/1"}
+                }
+            })],
+        );
+        let required = required_authorities(&envelope, &["acc://alice.acme/book".to_string()]);
+        assert!(required[0].is_satisfied());
+        assert!(!required[1].is_satisfied());
+    }
+
+    #[test]
+    fn test_account_authorities() {
+        let account = json!({
+            "type": "tokenAccount",
+            "url": "acc://alice.acme/tokens",
+            "authorities": [
+                {"url": "acc://alice.acme/book"},
+                {"url": "acc://ops.acme/book", "disabled": true}
+            ]
+        });
+        assert_eq!(account_authorities(&account), vec!["acc://alice.acme/book"]);
+
+        let lite = json!({"type": "liteTokenAccount", "url": "acc://0123abcd/ACME"});
+        assert_eq!(account_authorities(&lite), vec!["acc://0123abcd"]);
+
+        // The lite identity signs as itself
+        let envelope = envelope(
+            &[],
+            &[json!({"type": "ed25519", "signer": "acc://0123abcd"})],
+        );
+        assert!(required_authorities(&envelope, &account_authorities(&lite))[0].is_satisfied());
+    }
+}
@@ -1,7 +1,147 @@
 //! Transaction envelope utilities
 //!
-//! Additional envelope-specific functionality
+//! Envelopes built by different code paths can carry the same transaction and
+//! signatures yet differ in signature order, `null` placeholders or hex case.
+//! [`normalize_envelope`] removes those differences so equal envelopes
+//! serialize, hash and compare identically; the signing helpers and the
+//! client's submit methods apply it before an envelope leaves the SDK.
 
 pub use super::{EnvelopeBuilder, EnvelopeError, TransactionEnvelope, TransactionSignature};
 
-// Additional envelope utilities can be added here
\ No newline at end of file
+use crate::codec::canonical_json;
+use serde::Serialize;
+use serde_json::Value;
+
+/// Fields holding hex-encoded bytes, lower-cased by [`normalize_envelope`]
+const HEX_FIELDS: &[&str] = &[
+    "publicKey",
+    "signature",
+    "transactionHash",
+    "initiator",
+    "keyHash",
+    "publicKeyHash",
+    "entryHash",
+    "hash",
+    "cause",
+];
+
+impl TransactionEnvelope {
+    /// Normalize in place; see [`normalize_envelope`]
+    pub fn normalize(&mut self) -> Result<(), serde_json::Error> {
+        let mut value = serde_json::to_value(&*self)?;
+        normalize_envelope(&mut value);
+        *self = serde_json::from_value(value)?;
+        Ok(())
+    }
+}
+
+/// Bring an envelope in its JSON form to a canonical shape
+///
+/// - object members whose value is `null` are removed
+/// - hex fields (public keys, signatures, hashes) are lower-cased
+/// - signatures are sorted by signer, then timestamp, then content
+///
+/// Signature order carries no meaning to the network and none of these
+/// changes alter the bytes that are signed, so a normalized envelope is
+/// accepted exactly as the original.
+pub fn normalize_envelope(envelope: &mut Value) {
+    normalize_value(envelope);
+    if let Some(signatures) = envelope.get_mut("signatures").and_then(Value::as_array_mut) {
+        signatures.sort_by_cached_key(|sig| {
+            (
+                sig.get("signer")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_ascii_lowercase(),
+                sig.get("timestamp").and_then(Value::as_u64).unwrap_or(0),
+                canonical_json(sig),
+            )
+        });
+    }
+}
+
+/// Serialize `envelope` and normalize the result
+pub fn normalized_envelope<T: Serialize>(envelope: &T) -> Result<Value, serde_json::Error> {
+    let mut value = serde_json::to_value(envelope)?;
+    normalize_envelope(&mut value);
+    Ok(value)
+}
+
+fn normalize_value(value: &mut Value) {
+    match value {
+        Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            for (key, v) in map.iter_mut() {
+                match v {
+                    Value::String(s) if HEX_FIELDS.contains(&key.as_str()) && is_hex(s) => {
+                        s.make_ascii_lowercase();
+                    }
+                    _ => normalize_value(v),
+                }
+            }
+        }
+        Value::Array(items) => items.iter_mut().for_each(normalize_value),
+        _ => {}
+    }
+}
+
+fn is_hex(s: &str) -> bool {
+    s.len() % 2 == 0 && s.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_normalize_envelope() {
+        let mut a = json!({
+            "transaction": [{
+                "header": {"principal": "acc://alice.acme/tokens", "initiator": "ABCDEF", "memo": null},
+                "body": {"type": "sendTokens", "to": [{"url": "acc://bob.acme/tokens", "amount": "1"}]}
+            }],
+            "signatures": [
+                {"type": "ed25519", "signer": "acc://bob.acme/book/1", "timestamp": 2, "publicKey": "AA", "signature": "BB"},
+                {"type": "ed25519", "signer": "acc://alice.acme/book/1", "timestamp": 5, "publicKey": "cc", "signature": "dd", "vote": null}
+            ]
+        });
+        let b = json!({
+            "signatures": [
+                {"type": "ed25519", "signer": "acc://alice.acme/book/1", "timestamp": 5, "publicKey": "CC", "signature": "DD"},
+                {"type": "ed25519", "signer": "acc://bob.acme/book/1", "timestamp": 2, "publicKey": "aa", "signature": "bb"}
+            ],
+            "transaction": [{
+                "header": {"principal": "acc://alice.acme/tokens", "initiator": "abcdef"},
+                "body": {"type": "sendTokens", "to": [{"url": "acc://bob.acme/tokens", "amount": "1"}]}
+            }]
+        });
+
+        normalize_envelope(&mut a);
+        assert_eq!(a, normalized_envelope(&b).unwrap());
+        assert_eq!(a["signatures"][0]["signer"], "acc://alice.acme/book/1");
+        assert_eq!(a["signatures"][1]["publicKey"], "aa");
+        assert!(a["transaction"][0]["header"].get("memo").is_none());
+        assert_eq!(
+            canonical_json(&a),
+            canonical_json(&normalized_envelope(&b).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_normalize_leaves_non_hex_alone() {
+        let mut envelope = json!({
+            "transaction": [{"header": {"memo": "ABC"}, "body": {"type": "remoteTransaction", "hash": "not hex"}}],
+            "signatures": [{"type": "delegated", "delegator": "acc://Alice.acme/book/1", "signature": {"publicKey": "AB"}}]
+        });
+        normalize_envelope(&mut envelope);
+        assert_eq!(envelope["transaction"][0]["header"]["memo"], "ABC");
+        assert_eq!(envelope["transaction"][0]["body"]["hash"], "not hex");
+        assert_eq!(
+            envelope["signatures"][0]["delegator"],
+            "acc://Alice.acme/book/1"
+        );
+        assert_eq!(envelope["signatures"][0]["signature"]["publicKey"], "ab");
+    }
+}
@@ -0,0 +1,298 @@
+//! Human-readable envelope breakdown for debugging rejected submissions
+//!
+//! When a node answers "signature invalid" the envelope is usually fine on the
+//! surface and wrong in one of the hashes. [`explain_envelope`] recomputes the
+//! header, body and transaction hashes, each signature's metadata hash and the
+//! signature itself, and prints them next to what the envelope claims.
+
+use super::hash_debug::signature_metadata_hash;
+use super::TransactionEnvelope;
+use crate::codec::hex_utils::{parse_hex_field, parse_hex_hash};
+use crate::codec::signing::{create_signing_preimage, sha256_bytes};
+use crate::helpers::{compute_body_hash, compute_tx_hash, marshal_header_json};
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde_json::Value;
+use std::fmt::Write as _;
+
+impl TransactionEnvelope {
+    /// Render a human-readable breakdown of the envelope and its computed hashes
+    #[must_use]
+    pub fn explain(&self) -> String {
+        match serde_json::to_value(self) {
+            Ok(value) => explain_envelope(&value),
+            Err(e) => format!("envelope could not be serialized: {e}\n"),
+        }
+    }
+
+    /// Write [`explain`](Self::explain) output to stderr
+    pub fn dump(&self) {
+        dump_envelope(&serde_json::to_value(self).unwrap_or(Value::Null));
+    }
+}
+
+/// Write [`explain_envelope`] output to stderr
+pub fn dump_envelope(envelope: &Value) {
+    use std::io::Write;
+    let _ = std::io::stderr().write_all(explain_envelope(envelope).as_bytes());
+}
+
+/// Render a human-readable breakdown of an envelope in its JSON form
+///
+/// Works on the `Value` returned by `SmartSigner::sign` as well as on any
+/// serialized [`TransactionEnvelope`]. Hashes that cannot be computed are shown
+/// with the reason instead of aborting the whole report.
+pub fn explain_envelope(envelope: &Value) -> String {
+    let transactions = as_list(envelope.get("transaction"));
+    let signatures = as_list(envelope.get("signatures"));
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "Envelope: {} transaction(s), {} signature(s)",
+        transactions.len(),
+        signatures.len()
+    );
+
+    let mut tx_hashes: Vec<Option<[u8; 32]>> = Vec::with_capacity(transactions.len());
+    let mut initiators: Vec<Option<String>> = Vec::with_capacity(transactions.len());
+    for (i, tx) in transactions.iter().enumerate() {
+        let header = tx.get("header").unwrap_or(&Value::Null);
+        let body = tx.get("body").unwrap_or(&Value::Null);
+        let initiator = header.get("initiator").and_then(|v| v.as_str()).map(str::to_string);
+
+        let _ = writeln!(out, "Transaction {i}");
+        field(&mut out, "principal", str_or_missing(header.get("principal")));
+        field(&mut out, "type", str_or_missing(body.get("type")));
+        field(&mut out, "initiator", initiator.as_deref().unwrap_or("<none>"));
+        if let Some(memo) = header.get("memo").and_then(|v| v.as_str()) {
+            field(&mut out, "memo", memo);
+        }
+
+        let header_bytes = marshal_header_json(header);
+        match &header_bytes {
+            Ok(bytes) => field(&mut out, "header hash", &hex::encode(sha256_bytes(bytes))),
+            Err(e) => field(&mut out, "header hash", &format!("<error: {e}>")),
+        }
+        match compute_body_hash(body) {
+            Ok(hash) => field(&mut out, "body hash", &hex::encode(hash)),
+            Err(e) => field(&mut out, "body hash", &format!("<error: {e}>")),
+        }
+        let tx_hash = header_bytes.ok().and_then(|bytes| compute_tx_hash(&bytes, body).ok());
+        field(
+            &mut out,
+            "tx hash",
+            &tx_hash.map_or_else(|| "<unavailable>".to_string(), hex::encode),
+        );
+
+        tx_hashes.push(tx_hash);
+        initiators.push(initiator);
+    }
+
+    for (i, sig) in signatures.iter().enumerate() {
+        let sig_type = str_or_missing(sig.get("type"));
+        let signer = sig.get("signer").and_then(|v| v.as_str()).unwrap_or("");
+        let version = sig.get("signerVersion").and_then(Value::as_u64).unwrap_or(0);
+        let timestamp = sig.get("timestamp").and_then(Value::as_u64).unwrap_or(0);
+        let claimed_hash = sig.get("transactionHash").and_then(|v| v.as_str()).unwrap_or("");
+
+        let _ = writeln!(out, "Signature {i}");
+        field(&mut out, "type", sig_type);
+        field(&mut out, "signer", signer);
+        field(&mut out, "version", &version.to_string());
+        field(&mut out, "timestamp", &describe_timestamp(timestamp));
+        field(&mut out, "public key", str_or_missing(sig.get("publicKey")));
+
+        // Signatures are matched to the transaction whose hash they claim,
+        // falling back to the transaction at the same position.
+        let tx_index = tx_hashes
+            .iter()
+            .position(|h| h.is_some_and(|h| hex::encode(h) == claimed_hash))
+            .or_else(|| (i < tx_hashes.len()).then_some(i));
+        let computed = tx_index.and_then(|idx| tx_hashes[idx]);
+        let tx_status = match (tx_index, computed) {
+            (Some(idx), Some(h)) if hex::encode(h) == claimed_hash => format!("matches transaction {idx}"),
+            (Some(idx), Some(h)) => format!("MISMATCH: transaction {idx} hashes to {}", hex::encode(h)),
+            _ => "no transaction hash to compare".to_string(),
+        };
+        field(&mut out, "tx hash", &format!("{claimed_hash} ({tx_status})"));
+
+        if sig_type != "ed25519" {
+            field(&mut out, "signature", "<not checked for this signature type>");
+            continue;
+        }
+
+        let public_key = match parse_hex_field(
+            "publicKey",
+            sig.get("publicKey").and_then(|v| v.as_str()).unwrap_or_default(),
+        ) {
+            Ok(key) => key,
+            Err(e) => {
+                field(&mut out, "signature", &format!("cannot verify: {e}"));
+                continue;
+            }
+        };
+        // Memo, data and vote are part of the metadata
+        let md_hash = match signature_metadata_hash(sig) {
+            Ok(hash) => hash,
+            Err(e) => {
+                field(&mut out, "metadata hash", &format!("cannot compute: {e}"));
+                continue;
+            }
+        };
+        let md_hex = hex::encode(md_hash);
+        let initiator_status = match tx_index.and_then(|idx| initiators[idx].as_deref()) {
+            Some(init) if init == md_hex => "matches initiator",
+            Some(_) => "does not match initiator (not the initiating signature?)",
+            None => "no initiator to compare",
+        };
+        field(&mut out, "metadata hash", &format!("{md_hex} ({initiator_status})"));
+
+        let verdict = parse_hex_hash("transactionHash", claimed_hash).map_or_else(
+            |e| format!("cannot verify: {e}"),
+            |tx_hash| verify_ed25519(sig, &public_key, &md_hash, &tx_hash),
+        );
+        field(&mut out, "signature", &verdict);
+    }
+
+    out
+}
+
+fn verify_ed25519(sig: &Value, public_key: &[u8], md_hash: &[u8; 32], tx_hash: &[u8; 32]) -> String {
+    let Ok(key_bytes) = <[u8; 32]>::try_from(public_key) else {
+        return format!("cannot verify: public key is {} bytes, expected 32", public_key.len());
+    };
+    let Ok(key) = VerifyingKey::from_bytes(&key_bytes) else {
+        return "cannot verify: invalid ed25519 public key".to_string();
+    };
+    let sig_bytes = match parse_hex_field(
+        "signature",
+        sig.get("signature").and_then(|v| v.as_str()).unwrap_or_default(),
+    ) {
+        Ok(bytes) => bytes,
+        Err(e) => return format!("cannot verify: {e}"),
+    };
+    let Ok(sig_bytes) = <[u8; 64]>::try_from(sig_bytes.as_slice()) else {
+        return format!("cannot verify: signature is {} bytes, expected 64", sig_bytes.len());
+    };
+
+    let preimage = create_signing_preimage(md_hash, tx_hash);
+    if key.verify(&preimage, &Signature::from_bytes(&sig_bytes)).is_ok() {
+        "valid".to_string()
+    } else if crate::crypto::ed25519::verify_ed25519ph(&key_bytes, &preimage, None, &sig_bytes) {
+        "INVALID: signed with Ed25519ph; the network only accepts pure Ed25519".to_string()
+    } else {
+        "INVALID over SHA256(metadataHash + transactionHash)".to_string()
+    }
+}
+
+fn as_list(value: Option<&Value>) -> Vec<&Value> {
+    match value {
+        Some(Value::Array(items)) => items.iter().collect(),
+        Some(Value::Null) | None => Vec::new(),
+        Some(single) => vec![single],
+    }
+}
+
+fn str_or_missing(value: Option<&Value>) -> &str {
+    value.and_then(|v| v.as_str()).unwrap_or("<missing>")
+}
+
+fn describe_timestamp(micros: u64) -> String {
+    let secs = i64::try_from(micros / 1_000_000).unwrap_or(i64::MAX);
+    chrono::DateTime::from_timestamp(secs, 0)
+        .map_or_else(|| micros.to_string(), |dt| format!("{micros} ({})", dt.to_rfc3339()))
+}
+
+fn field(out: &mut String, name: &str, value: &str) {
+    let _ = writeln!(out, "  {:<14} {}", format!("{name}:"), value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn signed_envelope() -> Value {
+        use crate::codec::signing::{compute_ed25519_signature_metadata_hash, marshal_transaction_header};
+        use ed25519_dalek::{Signer, SigningKey};
+
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let public_key = key.verifying_key().to_bytes();
+        let signer = "acc://alice.acme/book/1";
+        let timestamp = 1_700_000_000_000_000u64;
+        let body = json!({"type": "burnCredits", "amount": 100});
+
+        let md_hash = compute_ed25519_signature_metadata_hash(&public_key, signer, 1, timestamp);
+        let header_bytes = marshal_transaction_header("acc://alice.acme/tokens", &md_hash, None, None);
+        let tx_hash = compute_tx_hash(&header_bytes, &body).unwrap_or([0u8; 32]);
+        let signature = key.sign(&create_signing_preimage(&md_hash, &tx_hash));
+
+        json!({
+            "transaction": [{
+                "header": {"principal": "acc://alice.acme/tokens", "initiator": hex::encode(md_hash)},
+                "body": body
+            }],
+            "signatures": [{
+                "type": "ed25519",
+                "publicKey": hex::encode(public_key),
+                "signature": hex::encode(signature.to_bytes()),
+                "signer": signer,
+                "signerVersion": 1,
+                "timestamp": timestamp,
+                "transactionHash": hex::encode(tx_hash)
+            }]
+        })
+    }
+
+    #[test]
+    fn test_explain_valid_envelope() {
+        let report = explain_envelope(&signed_envelope());
+        assert!(report.contains("acc://alice.acme/tokens"));
+        assert!(report.contains("burnCredits"));
+        assert!(report.contains("matches transaction 0"));
+        assert!(report.contains("matches initiator"));
+        assert!(report.contains(" valid\n"));
+    }
+
+    #[test]
+    fn test_explain_flags_tampered_body() {
+        let mut envelope = signed_envelope();
+        envelope["transaction"][0]["body"]["amount"] = json!(101);
+        let report = explain_envelope(&envelope);
+        assert!(report.contains("MISMATCH"));
+    }
+
+    #[test]
+    fn test_explain_flags_ed25519ph_signature() {
+        let mut envelope = signed_envelope();
+        let hash = |value: &Value| -> [u8; 32] {
+            parse_hex_hash("hash", value.as_str().unwrap_or_default()).unwrap_or_default()
+        };
+        let md_hash = hash(&envelope["transaction"][0]["header"]["initiator"]);
+        let tx_hash = hash(&envelope["signatures"][0]["transactionHash"]);
+        let key = crate::crypto::ed25519::Ed25519Signer::from_seed(&[9u8; 32]).ok();
+        let prehashed = key
+            .and_then(|key| key.sign_ed25519ph(&create_signing_preimage(&md_hash, &tx_hash), None).ok())
+            .unwrap_or([0u8; 64]);
+        envelope["signatures"][0]["signature"] = json!(hex::encode(prehashed));
+        let report = explain_envelope(&envelope);
+        assert!(report.contains("signed with Ed25519ph"), "{report}");
+    }
+
+    #[test]
+    fn test_explain_reports_malformed_public_key() {
+        let mut envelope = signed_envelope();
+        envelope["signatures"][0]["publicKey"] = json!("zz");
+        let report = explain_envelope(&envelope);
+        assert!(report.contains("cannot verify: Invalid field value: publicKey"), "{report}");
+    }
+
+    #[test]
+    fn test_explain_flags_wrong_signer_version() {
+        let mut envelope = signed_envelope();
+        envelope["signatures"][0]["signerVersion"] = json!(2);
+        let report = explain_envelope(&envelope);
+        assert!(report.contains("does not match initiator"));
+        assert!(report.contains("INVALID"));
+    }
+}
@@ -0,0 +1,348 @@
+//! Stage-by-stage transaction hash comparison against reference values
+//!
+//! A hash mismatch between this SDK and the Go implementation only says that
+//! *something* in the encoding differs. [`diff_transaction_hash`] recomputes
+//! each intermediate value (header, body, transaction, signature metadata),
+//! compares it with whatever reference values are available, and reports the
+//! first stage that diverges together with the encoded bytes that fed it.
+
+use crate::codec::signing::{
+    marshal_delegated_signature_metadata, marshal_signature_metadata, sha256_bytes, signature_types,
+};
+use crate::helpers::{compute_body_hash, compute_tx_hash, marshal_body_to_binary, marshal_header_json};
+use serde_json::Value;
+use std::fmt;
+
+/// Reference hashes to compare against, hex-encoded
+///
+/// Any subset may be provided, e.g. only the transaction hash reported by the
+/// Go CLI. Stages without a reference are computed and shown but not checked.
+#[derive(Debug, Clone, Default)]
+pub struct ReferenceHashes {
+    /// SHA-256 of the binary-encoded header
+    pub header: Option<String>,
+    /// Body hash as it enters the transaction hash
+    pub body: Option<String>,
+    /// Final transaction hash
+    pub transaction: Option<String>,
+    /// Signature metadata hash (the initiator for the initiating signature)
+    pub signature_metadata: Option<String>,
+}
+
+/// Outcome of a single comparison stage
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StageStatus {
+    /// Computed value equals the reference
+    Match,
+    /// Computed value differs from the reference
+    Mismatch,
+    /// No reference value was available
+    Unchecked,
+    /// The value could not be computed
+    Error(String),
+}
+
+/// One intermediate value in the hash computation
+#[derive(Debug, Clone)]
+pub struct HashStage {
+    /// Stage name (`header`, `body`, `transaction`, `signature metadata`)
+    pub name: &'static str,
+    /// Hex of the binary encoding that was hashed, when there is one
+    pub encoded: Option<String>,
+    /// Hex of the computed hash
+    pub computed: Option<String>,
+    /// Hex of the reference hash, if one was given
+    pub expected: Option<String>,
+    /// Comparison result
+    pub status: StageStatus,
+}
+
+/// Result of [`diff_transaction_hash`]
+#[derive(Debug, Clone)]
+pub struct HashDiffReport {
+    /// Stages in computation order
+    pub stages: Vec<HashStage>,
+}
+
+impl HashDiffReport {
+    /// The first stage that failed to compute or differs from its reference
+    #[must_use]
+    pub fn first_divergence(&self) -> Option<&HashStage> {
+        self.stages
+            .iter()
+            .find(|s| matches!(s.status, StageStatus::Mismatch | StageStatus::Error(_)))
+    }
+
+    /// Whether every checked stage matched
+    #[must_use]
+    pub fn is_match(&self) -> bool {
+        self.first_divergence().is_none()
+    }
+
+    /// Look up a stage by name
+    #[must_use]
+    pub fn stage(&self, name: &str) -> Option<&HashStage> {
+        self.stages.iter().find(|s| s.name == name)
+    }
+}
+
+impl fmt::Display for HashDiffReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for stage in &self.stages {
+            let status = match &stage.status {
+                StageStatus::Match => "match".to_string(),
+                StageStatus::Mismatch => "MISMATCH".to_string(),
+                StageStatus::Unchecked => "unchecked".to_string(),
+                StageStatus::Error(e) => format!("ERROR: {e}"),
+            };
+            writeln!(f, "[{status}] {}", stage.name)?;
+            if let Some(encoded) = &stage.encoded {
+                writeln!(f, "    encoded:  {encoded}")?;
+            }
+            if let Some(computed) = &stage.computed {
+                writeln!(f, "    computed: {computed}")?;
+            }
+            if let Some(expected) = &stage.expected {
+                writeln!(f, "    expected: {expected}")?;
+            }
+        }
+
+        match self.first_divergence() {
+            Some(stage) => writeln!(f, "First divergence: {}", divergence_hint(stage.name)),
+            None => writeln!(f, "No divergence among checked stages"),
+        }
+    }
+}
+
+fn divergence_hint(stage: &str) -> String {
+    match stage {
+        "header" => "header — check principal, initiator, memo, metadata and expire/holdUntil/authorities".to_string(),
+        "body" => "body — the body encoding differs; compare the encoded bytes field by field".to_string(),
+        "transaction" => {
+            "transaction — supply header/body reference hashes to narrow this down".to_string()
+        }
+        "signature metadata" => {
+            "signature metadata — check signer URL, signer version, timestamp and public key".to_string()
+        }
+        other => other.to_string(),
+    }
+}
+
+/// Recompute a transaction's hashes step by step and compare with references
+///
+/// `transaction` is the `{header, body}` object from an envelope. When a
+/// `signature` is given its metadata hash is computed as well, and its
+/// `transactionHash` serves as the transaction reference if
+/// [`ReferenceHashes::transaction`] is not set.
+#[must_use]
+pub fn diff_transaction_hash(
+    transaction: &Value,
+    signature: Option<&Value>,
+    expected: &ReferenceHashes,
+) -> HashDiffReport {
+    let header = transaction.get("header").unwrap_or(&Value::Null);
+    let body = transaction.get("body").unwrap_or(&Value::Null);
+    let mut stages = Vec::with_capacity(4);
+
+    let header_bytes = marshal_header_json(header);
+    stages.push(match &header_bytes {
+        Ok(bytes) => stage("header", Some(bytes), Ok(sha256_bytes(bytes)), expected.header.as_deref()),
+        Err(e) => stage("header", None, Err(e.to_string()), expected.header.as_deref()),
+    });
+
+    // WriteData bodies hash a Merkle root rather than their encoding, but the
+    // encoding is still the thing to compare against Go's MarshalBinary.
+    let body_bytes = marshal_body_to_binary(body).ok();
+    stages.push(stage(
+        "body",
+        body_bytes.as_deref(),
+        compute_body_hash(body).map_err(|e| e.to_string()),
+        expected.body.as_deref(),
+    ));
+
+    let expected_tx = expected.transaction.as_deref().or_else(|| {
+        signature
+            .map(key_signature)
+            .and_then(|s| s.get("transactionHash"))
+            .and_then(|h| h.as_str())
+    });
+    let tx_hash = match &header_bytes {
+        Ok(bytes) => compute_tx_hash(bytes, body).map_err(|e| e.to_string()),
+        Err(e) => Err(format!("header did not encode: {e}")),
+    };
+    stages.push(stage("transaction", None, tx_hash, expected_tx));
+
+    if let Some(sig) = signature {
+        stages.push(stage(
+            "signature metadata",
+            None,
+            signature_metadata_hash(sig),
+            expected.signature_metadata.as_deref(),
+        ));
+    }
+
+    HashDiffReport { stages }
+}
+
+fn stage(
+    name: &'static str,
+    encoded: Option<&[u8]>,
+    computed: Result<[u8; 32], String>,
+    expected: Option<&str>,
+) -> HashStage {
+    let expected = expected.map(str::to_ascii_lowercase);
+    let (computed, status) = match computed {
+        Ok(hash) => {
+            let computed = hex::encode(hash);
+            let status = match &expected {
+                Some(e) if *e == computed => StageStatus::Match,
+                Some(_) => StageStatus::Mismatch,
+                None => StageStatus::Unchecked,
+            };
+            (Some(computed), status)
+        }
+        Err(e) => (None, StageStatus::Error(e)),
+    };
+
+    HashStage {
+        name,
+        encoded: encoded.map(hex::encode),
+        computed,
+        expected,
+        status,
+    }
+}
+
+/// The key signature a delegated or remote signature wraps, or `sig` itself
+fn key_signature(mut sig: &Value) -> &Value {
+    while let Some(inner) = sig
+        .get("signature")
+        .filter(|_| matches!(sig["type"].as_str(), Some("delegated" | "remote")))
+    {
+        sig = inner;
+    }
+    sig
+}
+
+pub(super) fn signature_metadata_hash(sig: &Value) -> Result<[u8; 32], String> {
+    signature_metadata(sig).map(|metadata| sha256_bytes(&metadata))
+}
+
+/// Binary metadata of a key signature, or of a delegated signature and the
+/// signatures it wraps; a remote signature forwards the metadata of the one
+/// it carries
+fn signature_metadata(sig: &Value) -> Result<Vec<u8>, String> {
+    if sig.get("type").and_then(Value::as_str) == Some("remote") {
+        return signature_metadata(sig.get("signature").ok_or("remote signature has no inner signature")?);
+    }
+    if sig.get("type").and_then(Value::as_str) == Some("delegated") {
+        let inner = sig.get("signature").ok_or("delegated signature has no inner signature")?;
+        let delegator = sig.get("delegator").and_then(Value::as_str).unwrap_or("");
+        return Ok(marshal_delegated_signature_metadata(&signature_metadata(inner)?, delegator));
+    }
+    let sig_type = match sig.get("type").and_then(|t| t.as_str()).unwrap_or("") {
+        "legacyED25519" | "legacyed25519" => signature_types::LEGACY_ED25519,
+        "ed25519" => signature_types::ED25519,
+        "rcd1" => signature_types::RCD1,
+        "btc" => signature_types::BTC,
+        "btcLegacy" | "btclegacy" => signature_types::BTC_LEGACY,
+        "eth" => signature_types::ETH,
+        "rsaSha256" | "rsasha256" => signature_types::RSA_SHA256,
+        "ecdsaSha256" | "ecdsasha256" => signature_types::ECDSA_SHA256,
+        other => return Err(format!("unsupported signature type '{other}'")),
+    };
+    let public_key = match sig.get("publicKey").and_then(|k| k.as_str()) {
+        Some(h) => hex::decode(h).map_err(|e| format!("publicKey: {e}"))?,
+        None => Vec::new(),
+    };
+    let data = match sig.get("data").and_then(|d| d.as_str()) {
+        Some(h) => Some(hex::decode(h).map_err(|e| format!("data: {e}"))?),
+        None => None,
+    };
+    let vote = match sig.get("vote").and_then(|v| v.as_str()) {
+        None | Some("accept") => 0,
+        Some("reject") => 1,
+        Some("abstain") => 2,
+        Some("suggest") => 3,
+        Some(other) => return Err(format!("unknown vote '{other}'")),
+    };
+
+    Ok(marshal_signature_metadata(
+        sig_type,
+        &public_key,
+        sig.get("signer").and_then(|s| s.as_str()).unwrap_or(""),
+        sig.get("signerVersion").and_then(Value::as_u64).unwrap_or(0),
+        sig.get("timestamp").and_then(Value::as_u64).unwrap_or(0),
+        vote,
+        sig.get("memo").and_then(|m| m.as_str()),
+        data.as_deref(),
+    ))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::codec::signing::{compute_ed25519_signature_metadata_hash, marshal_transaction_header};
+    use serde_json::json;
+
+    fn fixture() -> (Value, Value, [u8; 32], [u8; 32]) {
+        let public_key = [3u8; 32];
+        let md_hash = compute_ed25519_signature_metadata_hash(&public_key, "acc://alice.acme/book/1", 4, 42);
+        let body = json!({"type": "lockAccount", "height": 1000});
+        let header_bytes = marshal_transaction_header("acc://alice.acme/data", &md_hash, Some("memo"), None);
+        let tx_hash = compute_tx_hash(&header_bytes, &body).unwrap();
+
+        let tx = json!({
+            "header": {"principal": "acc://alice.acme/data", "initiator": hex::encode(md_hash), "memo": "memo"},
+            "body": body
+        });
+        let sig = json!({
+            "type": "ed25519",
+            "publicKey": hex::encode(public_key),
+            "signer": "acc://alice.acme/book/1",
+            "signerVersion": 4,
+            "timestamp": 42,
+            "transactionHash": hex::encode(tx_hash)
+        });
+        (tx, sig, md_hash, sha256_bytes(&header_bytes))
+    }
+
+    #[test]
+    fn test_all_stages_match() {
+        let (tx, sig, md_hash, header_hash) = fixture();
+        let expected = ReferenceHashes {
+            header: Some(hex::encode(header_hash)),
+            signature_metadata: Some(hex::encode(md_hash)),
+            ..Default::default()
+        };
+        let report = diff_transaction_hash(&tx, Some(&sig), &expected);
+        assert!(report.is_match(), "{}", report);
+        assert_eq!(report.stage("transaction").unwrap().status, StageStatus::Match);
+        assert_eq!(report.stage("body").unwrap().status, StageStatus::Unchecked);
+    }
+
+    #[test]
+    fn test_reports_header_divergence_first() {
+        let (mut tx, sig, _, header_hash) = fixture();
+        tx["header"]["memo"] = json!("different memo");
+        let expected = ReferenceHashes {
+            header: Some(hex::encode(header_hash)),
+            ..Default::default()
+        };
+        let report = diff_transaction_hash(&tx, Some(&sig), &expected);
+        assert_eq!(report.first_divergence().unwrap().name, "header");
+        assert_eq!(report.stage("transaction").unwrap().status, StageStatus::Mismatch);
+        assert!(report.to_string().contains("First divergence: header"));
+    }
+
+    #[test]
+    fn test_reports_body_encoding_errors() {
+        let (mut tx, _, _, _) = fixture();
+        tx["body"] = json!({"type": "createKeyBook", "url": "acc://alice.acme/book2", "publicKeyHash": "xyz"});
+        let report = diff_transaction_hash(&tx, None, &ReferenceHashes::default());
+        let divergence = report.first_divergence().unwrap();
+        assert_eq!(divergence.name, "body");
+        assert!(matches!(divergence.status, StageStatus::Error(_)));
+    }
+}
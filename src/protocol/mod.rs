@@ -7,13 +7,26 @@
 
 use crate::codec::{canonical_json, sha256_bytes};
 use crate::crypto::ed25519_helper::{Ed25519Helper, Keypair};
+use crate::signer::{Signer, SignerError};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+pub mod allowed;
+pub mod authorities;
 pub mod envelope;
+pub mod explain;
+pub mod hash_debug;
 pub mod transaction;
 
+pub use allowed::AllowedTransactions;
+pub use authorities::{
+    account_authorities, outstanding_authorities, required_authorities, AuthoritySource, RequiredAuthority,
+};
+pub use envelope::{normalize_envelope, normalized_envelope};
+pub use explain::{dump_envelope, explain_envelope};
+pub use hash_debug::{diff_transaction_hash, HashDiffReport, HashStage, ReferenceHashes, StageStatus};
+
 // Re-export envelope and transaction modules (currently empty)
 // pub use envelope::*;
 // pub use transaction::*;
@@ -69,6 +82,16 @@ impl EnvelopeBuilder {
         keypair: &Keypair,
         signer_url: &str,
         signer_version: u64,
+    ) -> Result<TransactionEnvelope, EnvelopeError> {
+        Self::create_envelope_with_signer(transaction, keypair, signer_url, signer_version)
+    }
+
+    /// Create a new transaction envelope signed by any signing backend
+    pub fn create_envelope_with_signer(
+        transaction: Transaction,
+        signer: &dyn Signer,
+        signer_url: &str,
+        signer_version: u64,
     ) -> Result<TransactionEnvelope, EnvelopeError> {
         // Serialize transaction to canonical JSON
         let tx_value = serde_json::to_value(&transaction)?;
@@ -79,7 +102,7 @@ impl EnvelopeBuilder {
         let tx_hash_hex = hex::encode(tx_hash);
 
         // Sign the transaction hash
-        let signature = Ed25519Helper::sign_bytes(keypair, &tx_hash);
+        let signature = signer.sign_digest(&tx_hash)?;
 
         // Get current timestamp in microseconds
         let timestamp = SystemTime::now()
@@ -89,9 +112,11 @@ impl EnvelopeBuilder {
 
         // Create signature object
         let tx_signature = TransactionSignature {
-            signature_type: "ed25519".to_string(),
-            public_key: hex::encode(Ed25519Helper::public_key_bytes(keypair)),
-            signature: hex::encode(signature.to_bytes()),
+            signature_type: crate::codec::signing::signature_types::name(signer.signature_type().value())
+                .unwrap_or("unknown")
+                .to_string(),
+            public_key: hex::encode(signer.public_key()),
+            signature: hex::encode(signature),
             signer: signer_url.to_string(),
             signer_version,
             timestamp,
@@ -225,6 +250,9 @@ pub enum EnvelopeError {
 
     #[error("Signature verification failed: {0}")]
     VerificationFailed(String),
+
+    #[error("Signing failed: {0}")]
+    Signer(#[from] SignerError),
 }
 
 /// Helper functions for common transaction types
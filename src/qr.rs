@@ -0,0 +1,365 @@
+//! Compact QR payloads for air-gapped signers
+//!
+//! An offline signing device with a camera reads the transaction it should
+//! sign from QR codes. [`to_compact`] turns a [`PartialEnvelope`], usually
+//! one built with [`PartialEnvelope::new`] and not signed yet, into its Go
+//! binary envelope encoding written as unpadded base64url, which is far
+//! smaller than the JSON export. [`to_qr_chunks`] splits that text into
+//! chunks of bounded length, one per QR code:
+//!
+//! ```text
+//! acctx:1/3:5f0c2a91:<base64url>
+//! ```
+//!
+//! The payload is the binary envelope rather than CBOR. It is the encoding
+//! the network hashes and signs, and Accumulate signers already decode it, so
+//! the device signs exactly the bytes it displays without a second format
+//! that could disagree with the node. Its numbered fields also make it
+//! smaller than a CBOR map keyed by field name.
+//!
+//! Each chunk carries its position, the chunk count and the first four bytes
+//! of the SHA-256 of the whole payload, so a [`QrAssembler`] can take chunks
+//! in any order, ignore rescans of the same code, refuse chunks of another
+//! transaction and check the reassembled payload before decoding it. The
+//! signed envelope can travel back the same way.
+//!
+//! ```
+//! use accumulate_client::multisig::PartialEnvelope;
+//! use accumulate_client::qr::{to_qr_chunks, QrAssembler};
+//! use serde_json::json;
+//!
+//! let unsigned = PartialEnvelope::new(json!({
+//!     "header": {"principal": "acc://alice.acme/tokens", "initiator": "11".repeat(32)},
+//!     "body": {"type": "burnTokens", "amount": "100"}
+//! }))?;
+//! let chunks = to_qr_chunks(&unsigned, 48)?;
+//! assert!(chunks.len() > 1 && chunks.iter().all(|chunk| chunk.len() <= 48));
+//!
+//! let mut assembler = QrAssembler::new();
+//! let mut scanned = None;
+//! for chunk in chunks.iter().rev() {
+//!     scanned = assembler.add(chunk)?;
+//! }
+//! assert_eq!(scanned, Some(unsigned));
+//! # Ok::<(), accumulate_client::qr::QrError>(())
+//! ```
+
+use crate::crypto::ed25519::sha256;
+use crate::globals::TRANSACTION_SIZE_MAX;
+use crate::multisig::{MultisigError, PartialEnvelope};
+use base64::engine::general_purpose::URL_SAFE_NO_PAD;
+use base64::Engine as _;
+use thiserror::Error;
+
+/// Prefix of every chunk
+pub const QR_PREFIX: &str = "acctx";
+
+/// Chunk length that stays easy to scan with a phone camera
+pub const DEFAULT_CHUNK_LEN: usize = 500;
+
+/// Fewest payload characters a chunk must hold besides its header
+const MIN_CHUNK_DATA: usize = 16;
+
+/// Most chunks a payload may span: a maximum-size transaction and its
+/// signatures, written as base64url, at the smallest chunk length
+pub const MAX_CHUNKS: usize = 2 * TRANSACTION_SIZE_MAX / MIN_CHUNK_DATA;
+
+/// Why a compact payload could not be built or read back
+#[derive(Error, Debug)]
+pub enum QrError {
+    /// The text is not a chunk or not valid base64url
+    #[error("Malformed QR payload: {0}")]
+    Malformed(String),
+
+    /// The chunk length leaves too little room for data, or would split the
+    /// payload into more than [`MAX_CHUNKS`] chunks
+    #[error("Chunk length {0} is too small for a QR chunk")]
+    ChunkTooSmall(usize),
+
+    /// A chunk belongs to another payload than the ones scanned before
+    #[error("Chunk {found} belongs to another payload, expected {expected}")]
+    OtherPayload {
+        /// Payload ID and chunk count of the first chunk (`id/total`)
+        expected: String,
+        /// Payload ID and chunk count of this chunk (`id/total`)
+        found: String,
+    },
+
+    /// Chunks are still missing
+    #[error("Missing QR chunks: {0:?}")]
+    Incomplete(Vec<usize>),
+
+    /// The reassembled payload does not hash to its ID
+    #[error("Reassembled payload does not match its checksum")]
+    Checksum,
+
+    /// The payload did not decode to an envelope
+    #[error("Invalid envelope: {0}")]
+    Envelope(#[from] MultisigError),
+}
+
+/// The envelope in its binary encoding, as unpadded base64url
+pub fn to_compact(envelope: &PartialEnvelope) -> Result<String, QrError> {
+    Ok(encode_base64url(&envelope.to_binary()?))
+}
+
+/// Decode a payload written by [`to_compact`]
+pub fn from_compact(compact: &str) -> Result<PartialEnvelope, QrError> {
+    Ok(PartialEnvelope::from_binary(&decode_base64url(compact.trim())?)?)
+}
+
+/// Split the compact payload of `envelope` into chunks of at most
+/// `max_len` characters, header included
+pub fn to_qr_chunks(envelope: &PartialEnvelope, max_len: usize) -> Result<Vec<String>, QrError> {
+    let payload = envelope.to_binary()?;
+    let id = payload_id(&payload);
+    let data = encode_base64url(&payload);
+
+    // The header grows with the chunk count, so settle the count first
+    let mut total = 1;
+    let capacity = loop {
+        let capacity = max_len.saturating_sub(format!("{QR_PREFIX}:{total}/{total}:{id}:").len());
+        if capacity < MIN_CHUNK_DATA {
+            return Err(QrError::ChunkTooSmall(max_len));
+        }
+        let needed = ((data.len() + capacity - 1) / capacity).max(1);
+        if needed > MAX_CHUNKS {
+            return Err(QrError::ChunkTooSmall(max_len));
+        }
+        if needed <= total {
+            break capacity;
+        }
+        total = needed;
+    };
+
+    // base64url is ASCII, so byte chunks are character chunks
+    let pieces: Vec<&[u8]> = data.as_bytes().chunks(capacity).collect();
+    let total = pieces.len().max(1);
+    Ok((0..total)
+        .map(|index| {
+            let piece = pieces.get(index).map_or("", |piece| std::str::from_utf8(piece).unwrap_or_default());
+            format!("{QR_PREFIX}:{}/{total}:{id}:{piece}", index + 1)
+        })
+        .collect())
+}
+
+/// Reassemble an envelope from all of its chunks, in any order
+pub fn from_qr_chunks<S: AsRef<str>>(chunks: &[S]) -> Result<PartialEnvelope, QrError> {
+    let mut assembler = QrAssembler::new();
+    for chunk in chunks {
+        if let Some(envelope) = assembler.add(chunk.as_ref())? {
+            return Ok(envelope);
+        }
+    }
+    Err(QrError::Incomplete(assembler.missing()))
+}
+
+/// Collects scanned chunks until the payload is complete
+#[derive(Debug, Clone, Default)]
+pub struct QrAssembler {
+    id: Option<String>,
+    chunks: Vec<Option<String>>,
+}
+
+impl QrAssembler {
+    /// An assembler that has seen no chunks
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a scanned chunk; returns the envelope once every chunk is in
+    ///
+    /// Scanning a chunk again is harmless. A chunk from another payload is
+    /// an error and leaves the chunks collected so far untouched, as does a
+    /// chunk claiming more than [`MAX_CHUNKS`] chunks.
+    pub fn add(&mut self, chunk: &str) -> Result<Option<PartialEnvelope>, QrError> {
+        let (index, total, id, data) = parse_chunk(chunk.trim())?;
+        if total > MAX_CHUNKS {
+            return Err(QrError::Malformed(format!("{total} chunks, at most {MAX_CHUNKS} allowed")));
+        }
+        match &self.id {
+            Some(expected) if *expected != id || self.chunks.len() != total => {
+                return Err(QrError::OtherPayload {
+                    expected: format!("{expected}/{}", self.chunks.len()),
+                    found: format!("{id}/{total}"),
+                });
+            }
+            Some(_) => {}
+            None => {
+                self.id = Some(id.to_string());
+                self.chunks = vec![None; total];
+            }
+        }
+        self.chunks[index - 1] = Some(data.to_string());
+        if !self.is_complete() {
+            return Ok(None);
+        }
+
+        let data: String = self.chunks.iter().flatten().map(String::as_str).collect();
+        let payload = decode_base64url(&data)?;
+        if payload_id(&payload) != id {
+            return Err(QrError::Checksum);
+        }
+        Ok(Some(PartialEnvelope::from_binary(&payload)?))
+    }
+
+    /// Chunks scanned so far and the chunk count, once the first chunk is in
+    #[must_use]
+    pub fn progress(&self) -> (usize, usize) {
+        (self.chunks.iter().flatten().count(), self.chunks.len())
+    }
+
+    /// Whether every chunk has been scanned
+    #[must_use]
+    pub fn is_complete(&self) -> bool {
+        !self.chunks.is_empty() && self.chunks.iter().all(Option::is_some)
+    }
+
+    /// Positions (1-based) of the chunks not scanned yet
+    #[must_use]
+    pub fn missing(&self) -> Vec<usize> {
+        self.chunks
+            .iter()
+            .enumerate()
+            .filter(|(_, chunk)| chunk.is_none())
+            .map(|(index, _)| index + 1)
+            .collect()
+    }
+}
+
+/// Split `acctx:<index>/<total>:<id>:<data>` into its parts
+fn parse_chunk(chunk: &str) -> Result<(usize, usize, &str, &str), QrError> {
+    let malformed = || QrError::Malformed(format!("not a {QR_PREFIX} chunk"));
+    let mut parts = chunk.splitn(4, ':');
+    if !parts.next().is_some_and(|prefix| prefix.eq_ignore_ascii_case(QR_PREFIX)) {
+        return Err(malformed());
+    }
+    let (index, total) = parts.next().and_then(|position| position.split_once('/')).ok_or_else(malformed)?;
+    let id = parts.next().filter(|id| id.len() == 8).ok_or_else(malformed)?;
+    let data = parts.next().ok_or_else(malformed)?;
+    let index: usize = index.parse().map_err(|_| malformed())?;
+    let total: usize = total.parse().map_err(|_| malformed())?;
+    if index == 0 || index > total {
+        return Err(QrError::Malformed(format!("chunk {index} of {total}")));
+    }
+    Ok((index, total, id, data))
+}
+
+/// First four bytes of the payload's SHA-256 (hex)
+fn payload_id(payload: &[u8]) -> String {
+    hex::encode(&sha256(payload)[..4])
+}
+
+fn encode_base64url(bytes: &[u8]) -> String {
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+fn decode_base64url(text: &str) -> Result<Vec<u8>, QrError> {
+    URL_SAFE_NO_PAD
+        .decode(text.trim_end_matches('='))
+        .map_err(|e| QrError::Malformed(format!("invalid base64url: {e}")))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::{SmartSigner, TxBody};
+    use crate::AccumulateClient;
+    use ed25519_dalek::SigningKey;
+    use serde_json::json;
+
+    fn unsigned() -> PartialEnvelope {
+        PartialEnvelope::new(json!({
+            "header": {
+                "principal": "acc://alice.acme/tokens",
+                "initiator": "11".repeat(32),
+                "memo": "rent for march"
+            },
+            "body": TxBody::send_tokens_single("acc://bob.acme/tokens", "150000000")
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_base64url_roundtrip() {
+        for len in 0..8 {
+            let bytes: Vec<u8> = (0..len).map(|i| 0xf0 ^ i).collect();
+            let text = encode_base64url(&bytes);
+            assert!(!text.contains('='));
+            assert_eq!(decode_base64url(&text).unwrap(), bytes);
+        }
+        assert_eq!(encode_base64url(b"\xfb\xff"), "-_8");
+        assert!(decode_base64url("ab+c").is_err());
+        assert!(decode_base64url("abcde").is_err());
+    }
+
+    #[test]
+    fn test_chunks_reassemble_in_any_order() {
+        let envelope = unsigned();
+        let compact = to_compact(&envelope).unwrap();
+        assert!(compact.len() < envelope.to_json().len());
+        assert_eq!(from_compact(&compact).unwrap(), envelope);
+
+        let chunks = to_qr_chunks(&envelope, 40).unwrap();
+        assert!(chunks.len() > 2);
+        assert!(chunks.iter().all(|chunk| chunk.len() <= 40));
+        assert!(chunks[0].starts_with(&format!("acctx:1/{}:", chunks.len())));
+        assert_eq!(to_qr_chunks(&envelope, DEFAULT_CHUNK_LEN).unwrap().len(), 1);
+        assert!(matches!(to_qr_chunks(&envelope, 20), Err(QrError::ChunkTooSmall(20))));
+
+        let mut assembler = QrAssembler::new();
+        assert_eq!(assembler.add(&chunks[1]).unwrap(), None);
+        assert_eq!(assembler.add(&chunks[1]).unwrap(), None);
+        assert_eq!(assembler.progress(), (1, chunks.len()));
+        assert_eq!(assembler.missing()[0], 1);
+        let mut reversed = chunks.clone();
+        reversed.reverse();
+        assert_eq!(from_qr_chunks(&reversed).unwrap(), envelope);
+        assert!(matches!(from_qr_chunks(&chunks[1..]), Err(QrError::Incomplete(missing)) if missing == [1]));
+    }
+
+    #[test]
+    fn test_foreign_and_corrupt_chunks_are_refused() {
+        let chunks = to_qr_chunks(&unsigned(), 40).unwrap();
+        let mut assembler = QrAssembler::new();
+        assembler.add(&chunks[0]).unwrap();
+
+        let other = PartialEnvelope::new(json!({
+            "header": {"principal": "acc://alice.acme/tokens", "initiator": "22".repeat(32)},
+            "body": TxBody::burn_credits(100)
+        }))
+        .unwrap();
+        let foreign = to_qr_chunks(&other, 40).unwrap();
+        assert!(matches!(assembler.add(&foreign[1]), Err(QrError::OtherPayload { .. })));
+        assert!(matches!(assembler.add("hello"), Err(QrError::Malformed(_))));
+        let oversized = format!("acctx:1/{}:{}:AAAA", MAX_CHUNKS + 1, payload_id(b""));
+        assert!(matches!(assembler.add(&oversized), Err(QrError::Malformed(_))));
+        assert_eq!(assembler.progress(), (1, chunks.len()));
+
+        let mut corrupt = chunks.clone();
+        let (header, data) = corrupt[0].rsplit_once(':').unwrap();
+        let flipped = if data.starts_with('A') { 'B' } else { 'A' };
+        corrupt[0] = format!("{header}:{flipped}{}", &data[1..]);
+        assert!(matches!(from_qr_chunks(&corrupt), Err(QrError::Checksum)));
+    }
+
+    #[tokio::test]
+    async fn test_signed_envelope_travels_back() {
+        let client = AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap();
+        let signer = SmartSigner::new(&client, SigningKey::from_bytes(&[4u8; 32]), "acc://alice.acme/book/1");
+        let envelope = signer
+            .sign("acc://alice.acme/tokens", &TxBody::burn_credits(100), None)
+            .unwrap();
+        let signed = PartialEnvelope::from_envelope(&envelope).unwrap();
+        let chunks = to_qr_chunks(&signed, 120).unwrap();
+        assert_eq!(from_qr_chunks(&chunks).unwrap().signatures(), signed.signatures());
+    }
+}
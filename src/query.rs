@@ -0,0 +1,740 @@
+//! Typed V3 queries
+//!
+//! [`AccumulateClient::v3`] returns a [`V3Queries`] handle whose methods send
+//! a V3 `query` with the right scope and one of the [`V3Query`] shapes, and
+//! decode the record the node answers with. The record type depends on the
+//! query: a chain query by name returns the chain, by index or entry a single
+//! entry, and by range a page of entries, so [`V3Queries::query_chain`] picks
+//! the matching [`ChainResult`] variant from the query it was given.
+//!
+//! ```no_run
+//! use accumulate_client::types::{PendingQuery, RangeOptions};
+//! # async fn example(client: &accumulate_client::AccumulateClient) -> Result<(), accumulate_client::json_rpc_client::JsonRpcError> {
+//! let record = client.v3().query_account("acc://alice.acme/tokens").await?;
+//! println!("balance {:?}", record.balance());
+//!
+//! let range = RangeOptions { expand: Some(true), ..Default::default() };
+//! let pending = client
+//!     .v3()
+//!     .query_pending("acc://alice.acme/tokens", PendingQuery { range: Some(range) })
+//!     .await?;
+//! println!("{} of {} pending", pending.records.len(), pending.total);
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Results go through the same receipt checks as the other V3 queries when
+//! [`AccOptions::verify_receipts`](crate::AccOptions::verify_receipts) is set.
+
+use crate::accounts::{AccountDecodeError, AccountState};
+use crate::client::AccumulateClient;
+use crate::json_rpc_client::JsonRpcError;
+use crate::multisig::PendingTransaction;
+use crate::types::{
+    AnchorSearchQuery, BlockQuery, ChainQuery, DataQuery, DefaultQuery, DelegateSearchQuery,
+    DirectoryQuery, MessageHashSearchQuery, PendingQuery, PublicKeyHashSearchQuery,
+    PublicKeySearchQuery, TokenAccountState, V3Query,
+};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Scope of searches that are not tied to an account
+const UNKNOWN_SCOPE: &str = "acc://unknown";
+
+/// A page of records from a range query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RecordRange<T> {
+    /// Records of this page
+    #[serde(default = "Vec::new")]
+    pub records: Vec<T>,
+    /// Index of the first record
+    #[serde(default)]
+    pub start: u64,
+    /// Number of records in the whole range
+    #[serde(default)]
+    pub total: u64,
+}
+
+/// An account as returned by a default query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountRecord {
+    /// The account state, shaped by its `type`
+    pub account: Value,
+    /// Sub-accounts, for identities
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub directory: Option<RecordRange<UrlRecord>>,
+    /// Transactions pending on the account
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending: Option<RecordRange<TxIdRecord>>,
+    /// Proof of the account state, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<Value>,
+}
+
+impl AccountRecord {
+    /// Account type (`tokenAccount`, `keyPage`, ...)
+    #[must_use]
+    pub fn account_type(&self) -> Option<&str> {
+        self.account.get("type").and_then(Value::as_str)
+    }
+
+    /// Account URL
+    #[must_use]
+    pub fn url(&self) -> Option<&str> {
+        self.account.get("url").and_then(Value::as_str)
+    }
+
+    /// Token balance in base units, sent as a string or a number
+    #[must_use]
+    pub fn balance(&self) -> Option<u64> {
+        let balance = self.account.get("balance")?;
+        balance
+            .as_str()
+            .and_then(|b| b.parse().ok())
+            .or_else(|| balance.as_u64())
+    }
+
+    /// Credit balance of a key page or lite identity
+    #[must_use]
+    pub fn credit_balance(&self) -> Option<u64> {
+        self.account.get("creditBalance").and_then(Value::as_u64)
+    }
+
+    /// Version of a key page
+    #[must_use]
+    pub fn version(&self) -> Option<u64> {
+        self.account.get("version").and_then(Value::as_u64)
+    }
+
+    /// The account decoded as a token account, if it is one
+    #[must_use]
+    pub fn token_account(&self) -> Option<TokenAccountState> {
+        match self.account_type() {
+            Some("tokenAccount" | "liteTokenAccount") => {
+                serde_json::from_value(self.account.clone()).ok()
+            }
+            _ => None,
+        }
+    }
+
+    /// The account decoded by its type
+    pub fn state(&self) -> Result<AccountState, AccountDecodeError> {
+        AccountState::from_account(&self.account)
+    }
+}
+
+/// A transaction or signature as returned by a default query on its ID
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MessageRecord {
+    /// Message ID (`acc://<hash>@<principal>`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
+    /// The message, a transaction or a signature
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<Value>,
+    /// Status, either a code such as `delivered` or an object with
+    /// `delivered`, `failed` and `error`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status: Option<Value>,
+    /// Why the message failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<Value>,
+    /// Result of executing the transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub result: Option<Value>,
+    /// Block the message was received in
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub received: Option<u64>,
+    /// Messages produced by this one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub produced: Option<Value>,
+    /// Signatures collected for the transaction
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub signatures: Option<Value>,
+}
+
+impl MessageRecord {
+    /// Status code, such as `delivered` or `pending`
+    #[must_use]
+    pub fn status_code(&self) -> Option<&str> {
+        let status = self.status.as_ref()?;
+        status
+            .as_str()
+            .or_else(|| status.get("code").and_then(Value::as_str))
+    }
+
+    /// Whether the message has been executed, successfully or not
+    #[must_use]
+    pub fn is_delivered(&self) -> bool {
+        match &self.status {
+            Some(Value::String(code)) => code == "delivered",
+            Some(status) => status
+                .get("delivered")
+                .and_then(Value::as_bool)
+                .unwrap_or(false),
+            None => false,
+        }
+    }
+
+    /// Why the message failed; `None` while it is pending or if it succeeded
+    #[must_use]
+    pub fn error_message(&self) -> Option<String> {
+        if let Some(status) = &self.status {
+            if status
+                .get("failed")
+                .and_then(Value::as_bool)
+                .unwrap_or(false)
+            {
+                let error = status.get("error").and_then(error_text);
+                return Some(error.unwrap_or_else(|| "Unknown error".to_string()));
+            }
+        }
+        self.error.as_ref().and_then(error_text)
+    }
+
+    /// The transaction, when the message is one
+    #[must_use]
+    pub fn transaction(&self) -> Option<&Value> {
+        self.message.as_ref()?.get("transaction")
+    }
+
+    /// IDs of the messages this one produced, such as synthetic deposits
+    #[must_use]
+    pub fn produced_ids(&self) -> Vec<String> {
+        let Some(produced) = &self.produced else {
+            return Vec::new();
+        };
+        let records = produced.get("records").unwrap_or(produced);
+        records
+            .as_array()
+            .into_iter()
+            .flatten()
+            .filter_map(|record| record.get("value").unwrap_or(record).as_str())
+            .map(str::to_string)
+            .collect()
+    }
+}
+
+fn error_text(error: &Value) -> Option<String> {
+    error
+        .get("message")
+        .and_then(Value::as_str)
+        .or_else(|| error.as_str())
+        .map(str::to_string)
+}
+
+/// A chain of an account
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainRecord {
+    /// Chain name (`main`, `signature`, ...)
+    pub name: String,
+    /// Chain type (`transaction`, `anchor`, `index`)
+    #[serde(rename = "type", default)]
+    pub chain_type: String,
+    /// Number of entries
+    #[serde(default)]
+    pub count: u64,
+    /// Merkle state (hex hashes)
+    #[serde(default)]
+    pub state: Vec<String>,
+}
+
+/// An entry of a chain
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChainEntryRecord {
+    /// Account the chain belongs to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    /// Chain name
+    #[serde(default)]
+    pub name: String,
+    /// Chain type
+    #[serde(rename = "type", default)]
+    pub chain_type: String,
+    /// Position on the chain
+    #[serde(default)]
+    pub index: u64,
+    /// Entry hash (hex)
+    #[serde(default)]
+    pub entry: String,
+    /// The record the entry refers to, when expanded
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+    /// Proof of the entry, when requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub receipt: Option<Value>,
+}
+
+/// A URL, as listed by directory queries
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UrlRecord {
+    /// The URL
+    pub value: String,
+}
+
+/// A transaction ID, as listed by pending queries and hash searches
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TxIdRecord {
+    /// The ID (`acc://<hash>@<principal>`)
+    pub value: String,
+}
+
+/// A key entry found by a key or delegate search
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRecord {
+    /// Key book the page belongs to
+    #[serde(default)]
+    pub authority: String,
+    /// Key page holding the key
+    #[serde(default)]
+    pub signer: String,
+    /// Version of that page
+    #[serde(default)]
+    pub version: u64,
+    /// Position of the key on the page
+    #[serde(default)]
+    pub index: u64,
+    /// The key entry
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entry: Option<Value>,
+}
+
+/// A minor block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MinorBlockRecord {
+    /// Block index
+    pub index: u64,
+    /// Block time (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    /// Partition that produced the block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub source: Option<String>,
+    /// Chain entries recorded in the block
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub entries: Option<RecordRange<ChainEntryRecord>>,
+    /// Blocks of other partitions anchored in this one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub anchored: Option<Value>,
+}
+
+/// A major block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MajorBlockRecord {
+    /// Block index
+    pub index: u64,
+    /// Block time (RFC 3339)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub time: Option<String>,
+    /// Minor blocks the major block spans
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub minor_blocks: Option<RecordRange<MinorBlockRecord>>,
+}
+
+/// A sub-account listed by a directory query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DirectoryEntry {
+    /// Account URL
+    pub url: String,
+    /// Account state, when the range was expanded
+    pub account: Option<Value>,
+}
+
+/// Result of a chain or data query, shaped by the query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChainResult {
+    /// Every chain of the account (chain query without a name)
+    Chains(RecordRange<ChainRecord>),
+    /// One chain (chain query with only a name)
+    Chain(ChainRecord),
+    /// One entry (query by index or entry, or the latest data entry)
+    Entry(ChainEntryRecord),
+    /// A page of entries (query by range)
+    Entries(RecordRange<ChainEntryRecord>),
+}
+
+/// Result of a block query, shaped by the query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlockResult {
+    /// One minor block
+    Minor(MinorBlockRecord),
+    /// One major block
+    Major(MajorBlockRecord),
+    /// A page of minor blocks
+    MinorBlocks(RecordRange<MinorBlockRecord>),
+    /// A page of major blocks
+    MajorBlocks(RecordRange<MajorBlockRecord>),
+}
+
+/// Typed V3 queries, from [`AccumulateClient::v3`]
+#[derive(Debug, Clone, Copy)]
+pub struct V3Queries<'a> {
+    client: &'a AccumulateClient,
+}
+
+impl AccumulateClient {
+    /// Typed V3 queries through this client
+    #[must_use]
+    pub const fn v3(&self) -> V3Queries<'_> {
+        V3Queries { client: self }
+    }
+}
+
+impl V3Queries<'_> {
+    /// Validate `query` and send it for `scope`
+    async fn query<T: DeserializeOwned>(
+        &self,
+        scope: &str,
+        query: V3Query,
+    ) -> Result<T, JsonRpcError> {
+        query
+            .validate()
+            .map_err(|e| JsonRpcError::General(format!("Query validation failed: {e}")))?;
+        self.client
+            .call_query(json!({"scope": scope, "query": query}))
+            .await
+    }
+
+    /// Current state of the account at `url`
+    pub async fn query_account(&self, url: &str) -> Result<AccountRecord, JsonRpcError> {
+        self.query(url, V3Query::Default(DefaultQuery::default()))
+            .await
+    }
+
+    /// A transaction or signature by ID
+    ///
+    /// `txid` is `acc://<hash>@<principal>`; a principal of `unknown` lets
+    /// the node find the message by hash alone.
+    pub async fn query_transaction(&self, txid: &str) -> Result<MessageRecord, JsonRpcError> {
+        self.query(txid, V3Query::Default(DefaultQuery::default()))
+            .await
+    }
+
+    /// Chains of the account at `url`, or entries of one of them
+    pub async fn query_chain(
+        &self,
+        url: &str,
+        query: ChainQuery,
+    ) -> Result<ChainResult, JsonRpcError> {
+        let named = query.name.is_some();
+        let single = query.index.is_some() || query.entry.is_some();
+        let ranged = query.range.is_some();
+        let record: Value = self.query(url, V3Query::Chain(query)).await?;
+        Ok(if !named {
+            ChainResult::Chains(serde_json::from_value(record)?)
+        } else if ranged {
+            ChainResult::Entries(serde_json::from_value(record)?)
+        } else if single {
+            ChainResult::Entry(serde_json::from_value(record)?)
+        } else {
+            ChainResult::Chain(serde_json::from_value(record)?)
+        })
+    }
+
+    /// Entries of the data account at `url`; the latest one without an
+    /// index, entry or range
+    pub async fn query_data(
+        &self,
+        url: &str,
+        query: DataQuery,
+    ) -> Result<ChainResult, JsonRpcError> {
+        let ranged = query.range.is_some();
+        let record: Value = self.query(url, V3Query::Data(query)).await?;
+        Ok(if ranged {
+            ChainResult::Entries(serde_json::from_value(record)?)
+        } else {
+            ChainResult::Entry(serde_json::from_value(record)?)
+        })
+    }
+
+    /// Sub-accounts of the identity at `url`
+    ///
+    /// Entries carry the account state when the range is expanded.
+    pub async fn query_directory(
+        &self,
+        url: &str,
+        query: DirectoryQuery,
+    ) -> Result<RecordRange<DirectoryEntry>, JsonRpcError> {
+        let range: RecordRange<Value> = self.query(url, V3Query::Directory(query)).await?;
+        Ok(RecordRange {
+            records: range
+                .records
+                .into_iter()
+                .filter_map(|record| {
+                    let account = record.get("account").cloned();
+                    let url = record
+                        .get("value")
+                        .or_else(|| account.as_ref().and_then(|a| a.get("url")))
+                        .and_then(Value::as_str)?
+                        .to_string();
+                    Some(DirectoryEntry { url, account })
+                })
+                .collect(),
+            start: range.start,
+            total: range.total,
+        })
+    }
+
+    /// Transactions pending on the account at `url`
+    ///
+    /// With an expanded range each entry carries its transaction.
+    pub async fn query_pending(
+        &self,
+        url: &str,
+        query: PendingQuery,
+    ) -> Result<RecordRange<PendingTransaction>, JsonRpcError> {
+        let range: RecordRange<Value> = self.query(url, V3Query::Pending(query)).await?;
+        Ok(RecordRange {
+            records: range
+                .records
+                .iter()
+                .filter_map(|record| crate::multisig::parse_pending_record(record).transpose())
+                .collect::<Result<_, _>>()?,
+            start: range.start,
+            total: range.total,
+        })
+    }
+
+    /// Blocks of the partition at `url` (e.g. `acc://bvn-Apollo.acme`)
+    pub async fn query_block(
+        &self,
+        url: &str,
+        query: BlockQuery,
+    ) -> Result<BlockResult, JsonRpcError> {
+        let minor = query.minor.is_some();
+        let major = query.major.is_some();
+        let minor_range = query.minor_range.is_some();
+        let record: Value = self.query(url, V3Query::Block(query)).await?;
+        Ok(if minor {
+            BlockResult::Minor(serde_json::from_value(record)?)
+        } else if major {
+            BlockResult::Major(serde_json::from_value(record)?)
+        } else if minor_range {
+            BlockResult::MinorBlocks(serde_json::from_value(record)?)
+        } else {
+            BlockResult::MajorBlocks(serde_json::from_value(record)?)
+        })
+    }
+
+    /// Key pages under the authority at `url` holding a public key
+    pub async fn search_by_public_key(
+        &self,
+        url: &str,
+        query: PublicKeySearchQuery,
+    ) -> Result<RecordRange<KeyRecord>, JsonRpcError> {
+        self.query(url, V3Query::PublicKeySearch(query)).await
+    }
+
+    /// Key pages under the authority at `url` holding a public key hash
+    pub async fn search_by_public_key_hash(
+        &self,
+        url: &str,
+        query: PublicKeyHashSearchQuery,
+    ) -> Result<RecordRange<KeyRecord>, JsonRpcError> {
+        self.query(url, V3Query::PublicKeyHashSearch(query)).await
+    }
+
+    /// Key pages under the authority at `url` naming a delegate
+    pub async fn search_by_delegate(
+        &self,
+        url: &str,
+        query: DelegateSearchQuery,
+    ) -> Result<RecordRange<KeyRecord>, JsonRpcError> {
+        self.query(url, V3Query::DelegateSearch(query)).await
+    }
+
+    /// Anchor chain entries of the partition at `url` recording an anchor
+    pub async fn search_by_anchor(
+        &self,
+        url: &str,
+        query: AnchorSearchQuery,
+    ) -> Result<RecordRange<ChainEntryRecord>, JsonRpcError> {
+        self.query(url, V3Query::AnchorSearch(query)).await
+    }
+
+    /// IDs of the messages with a hash, on any account
+    pub async fn search_by_message_hash(
+        &self,
+        query: MessageHashSearchQuery,
+    ) -> Result<RecordRange<TxIdRecord>, JsonRpcError> {
+        self.query(UNKNOWN_SCOPE, V3Query::MessageHashSearch(query))
+            .await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::derive_lite_identity_url;
+    use crate::simulator::{SimulatedNetwork, FAUCET_AMOUNT};
+    use crate::types::RangeOptions;
+
+    #[tokio::test]
+    async fn test_typed_queries_against_simulator() {
+        let network = SimulatedNetwork::new();
+        let client = network.client().await.unwrap();
+        let identity = derive_lite_identity_url(&[3u8; 32]);
+        let account = format!("{identity}/ACME");
+        let txid = network.fund(&account, FAUCET_AMOUNT).unwrap();
+
+        let record = client.v3().query_account(&account).await.unwrap();
+        assert_eq!(record.account_type(), Some("liteTokenAccount"));
+        assert_eq!(record.balance(), Some(FAUCET_AMOUNT));
+        let token = record.token_account().unwrap();
+        assert!(token.is_lite());
+        assert_eq!(token.enabled_authorities(), vec![identity.clone()]);
+        let crate::accounts::AccountState::LiteTokenAccount(lite) = record.state().unwrap() else {
+            unreachable!("not a lite token account");
+        };
+        assert_eq!(lite.balance_units(), Some(u128::from(FAUCET_AMOUNT)));
+
+        let message = client.v3().query_transaction(&txid).await.unwrap();
+        assert!(message.is_delivered());
+        assert_eq!(message.status_code(), Some("delivered"));
+        assert_eq!(message.error_message(), None);
+        assert!(message.transaction().is_some());
+
+        let directory = client
+            .v3()
+            .query_directory(&identity, DirectoryQuery::default())
+            .await
+            .unwrap();
+        assert_eq!(directory.total, 1);
+        assert_eq!(
+            directory.records[0].url.to_lowercase(),
+            account.to_lowercase()
+        );
+        assert!(directory.records[0].account.is_none());
+        let range = RangeOptions {
+            expand: Some(true),
+            ..Default::default()
+        };
+        let expanded = client
+            .v3()
+            .query_directory(&identity, DirectoryQuery { range: Some(range) })
+            .await
+            .unwrap();
+        assert!(expanded.records[0].account.is_some());
+
+        let pending = client
+            .v3()
+            .query_pending(&account, PendingQuery::default())
+            .await
+            .unwrap();
+        assert!(pending.records.is_empty());
+
+        assert!(client
+            .v3()
+            .query_account("acc://nobody.acme")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_invalid_queries_are_not_sent() {
+        let network = SimulatedNetwork::new();
+        let client = network.client().await.unwrap();
+        let query = ChainQuery {
+            index: Some(3),
+            ..Default::default()
+        };
+        let err = client
+            .v3()
+            .query_chain("acc://alice.acme", query)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Query validation failed"), "{err}");
+        let err = client
+            .v3()
+            .query_block("acc://bvn-Apollo.acme", BlockQuery::default())
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("Query validation failed"), "{err}");
+    }
+
+    #[test]
+    fn test_message_status_shapes() {
+        let record = |value: Value| -> MessageRecord { serde_json::from_value(value).unwrap() };
+
+        let pending = record(json!({"recordType": "message", "status": "pending"}));
+        assert!(!pending.is_delivered());
+        assert_eq!(pending.error_message(), None);
+
+        let failed = record(json!({
+            "status": {"code": "failed", "delivered": true, "failed": true, "error": {"message": "no credits"}}
+        }));
+        assert!(failed.is_delivered());
+        assert_eq!(failed.error_message().as_deref(), Some("no credits"));
+
+        let rejected = record(json!({
+            "status": "insufficientBalance",
+            "error": {"code": "insufficientBalance", "message": "balance too low"}
+        }));
+        assert_eq!(rejected.status_code(), Some("insufficientBalance"));
+        assert_eq!(rejected.error_message().as_deref(), Some("balance too low"));
+
+        let produced = record(json!({
+            "status": "delivered",
+            "produced": {"recordType": "range", "records": [
+                {"recordType": "txID", "value": "acc://aa@bob.acme/tokens"}
+            ]}
+        }));
+        assert_eq!(produced.produced_ids(), vec!["acc://aa@bob.acme/tokens"]);
+        assert!(pending.produced_ids().is_empty());
+    }
+
+    #[test]
+    fn test_block_and_chain_records_decode() {
+        let minor: MinorBlockRecord = serde_json::from_value(json!({
+            "recordType": "minorBlock",
+            "index": 42,
+            "time": "2024-01-01T00:00:00Z",
+            "source": "acc://bvn-Apollo.acme",
+            "entries": {"recordType": "range", "total": 1, "records": [{
+                "recordType": "chainEntry",
+                "account": "acc://alice.acme/tokens",
+                "name": "main",
+                "type": "transaction",
+                "index": 7,
+                "entry": "ab"
+            }]}
+        }))
+        .unwrap();
+        let entries = minor.entries.unwrap();
+        assert_eq!(entries.total, 1);
+        assert_eq!(entries.records[0].index, 7);
+        assert_eq!(entries.records[0].chain_type, "transaction");
+
+        let chains: RecordRange<ChainRecord> = serde_json::from_value(json!({
+            "recordType": "range",
+            "records": [{"recordType": "chain", "name": "main", "type": "transaction", "count": 3}],
+            "start": 0,
+            "total": 1
+        }))
+        .unwrap();
+        assert_eq!(chains.records[0].count, 3);
+
+        let keys: RecordRange<KeyRecord> = serde_json::from_value(json!({
+            "records": [{
+                "authority": "acc://alice.acme/book",
+                "signer": "acc://alice.acme/book/1",
+                "version": 2,
+                "index": 0,
+                "entry": {"keyHash": "cd"}
+            }],
+            "total": 1
+        }))
+        .unwrap();
+        assert_eq!(keys.records[0].signer, "acc://alice.acme/book/1");
+    }
+}
@@ -0,0 +1,180 @@
+//! Receipt verification for query results
+//!
+//! Queries that ask for receipts (`includeReceipt`) get a Merkle proof with
+//! every record, and trusting the record means checking that proof. When
+//! [`AccOptions::verify_receipts`](crate::AccOptions::verify_receipts) is set
+//! the client runs [`annotate_receipts`] over every V3 query response: each
+//! record that carries a `receipt` gets `"verified": true`, or `false` plus a
+//! `"verificationError"` explaining why.
+//!
+//! A receipt proves its start hash is included under its anchor; where the
+//! record also carries the chain `entry` it claims to prove, the two must
+//! match.
+
+use crate::types::MerkleReceipt;
+use serde_json::Value;
+use thiserror::Error;
+
+/// Why a receipt failed verification
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ReceiptError {
+    /// The receipt could not be decoded or has invalid hash lengths
+    #[error("malformed receipt: {0}")]
+    Malformed(String),
+    /// Applying the proof entries to the start hash does not give the anchor
+    #[error("proof does not lead from start to anchor")]
+    Mismatch,
+    /// The receipt proves a different hash than the record's entry
+    #[error("receipt starts at {start}, record entry is {entry}")]
+    WrongEntry {
+        /// Receipt start hash (hex)
+        start: String,
+        /// Record entry hash (hex)
+        entry: String,
+    },
+}
+
+/// Verify a single receipt in its JSON form
+pub fn verify_receipt(receipt: &Value) -> Result<(), ReceiptError> {
+    let receipt: MerkleReceipt = serde_json::from_value(receipt.clone())
+        .map_err(|e| ReceiptError::Malformed(e.to_string()))?;
+    match receipt.verify() {
+        Ok(true) => Ok(()),
+        Ok(false) => Err(ReceiptError::Mismatch),
+        Err(e) => Err(ReceiptError::Malformed(e.to_string())),
+    }
+}
+
+/// Verify every receipt in a query response and annotate its record
+///
+/// Walks the whole response, so single records, record ranges and nested
+/// records are all covered. Returns how many receipts were checked.
+pub fn annotate_receipts(response: &mut Value) -> usize {
+    match response {
+        Value::Array(items) => items.iter_mut().map(annotate_receipts).sum(),
+        Value::Object(map) => {
+            let mut checked = 0;
+            if let Some(receipt) = map.get("receipt").filter(|r| r.is_object()) {
+                let result =
+                    verify_receipt(receipt).and_then(|()| check_entry(map.get("entry"), receipt));
+                map.insert("verified".to_string(), Value::Bool(result.is_ok()));
+                match result {
+                    Ok(()) => map.remove("verificationError"),
+                    Err(e) => map.insert(
+                        "verificationError".to_string(),
+                        Value::String(e.to_string()),
+                    ),
+                };
+                checked += 1;
+            }
+            checked
+                + map
+                    .iter_mut()
+                    .filter(|(key, _)| key.as_str() != "receipt")
+                    .map(|(_, value)| annotate_receipts(value))
+                    .sum::<usize>()
+        }
+        _ => 0,
+    }
+}
+
+/// The verification outcome [`annotate_receipts`] attached to a record
+///
+/// `None` when the record was not checked.
+#[must_use]
+pub fn receipt_status(record: &Value) -> Option<Result<(), String>> {
+    let verified = record.get("verified")?.as_bool()?;
+    Some(if verified {
+        Ok(())
+    } else {
+        Err(record
+            .get("verificationError")
+            .and_then(Value::as_str)
+            .unwrap_or("receipt not verified")
+            .to_string())
+    })
+}
+
+fn check_entry(entry: Option<&Value>, receipt: &Value) -> Result<(), ReceiptError> {
+    let (Some(entry), Some(start)) = (
+        entry.and_then(Value::as_str),
+        receipt.get("start").and_then(Value::as_str),
+    ) else {
+        return Ok(());
+    };
+    if entry.eq_ignore_ascii_case(start) {
+        Ok(())
+    } else {
+        Err(ReceiptError::WrongEntry {
+            start: start.to_string(),
+            entry: entry.to_string(),
+        })
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use sha2::{Digest, Sha256};
+
+    fn receipt() -> (String, Value) {
+        let start = [1u8; 32];
+        let sibling = [2u8; 32];
+        let anchor: [u8; 32] = Sha256::new()
+            .chain_update(start)
+            .chain_update(sibling)
+            .finalize()
+            .into();
+        let start = hex::encode(start);
+        let receipt = json!({
+            "start": start,
+            "startIndex": 0,
+            "end": start,
+            "endIndex": 0,
+            "anchor": hex::encode(anchor),
+            "entries": [{"right": true, "hash": hex::encode(sibling)}],
+            "localBlock": 10
+        });
+        (start, receipt)
+    }
+
+    #[test]
+    fn test_annotate_receipts() {
+        let (start, good) = receipt();
+        let mut bad = good.clone();
+        bad["anchor"] = json!(hex::encode([0u8; 32]));
+
+        let mut response = json!({
+            "recordType": "range",
+            "records": [
+                {"recordType": "chainEntry", "entry": start, "receipt": good},
+                {"recordType": "chainEntry", "entry": start, "receipt": bad},
+                {"recordType": "chainEntry", "entry": hex::encode([9u8; 32]), "receipt": good},
+                {"recordType": "chainEntry", "entry": start}
+            ]
+        });
+        assert_eq!(annotate_receipts(&mut response), 3);
+
+        let records = response["records"].as_array().unwrap();
+        assert_eq!(receipt_status(&records[0]), Some(Ok(())));
+        assert_eq!(
+            receipt_status(&records[1]),
+            Some(Err(ReceiptError::Mismatch.to_string()))
+        );
+        assert!(receipt_status(&records[2])
+            .unwrap()
+            .unwrap_err()
+            .contains("record entry"));
+        assert_eq!(receipt_status(&records[3]), None);
+    }
+
+    #[test]
+    fn test_verify_receipt_malformed() {
+        assert!(matches!(
+            verify_receipt(&json!({"start": "zz", "end": "", "anchor": ""})),
+            Err(ReceiptError::Malformed(_))
+        ));
+    }
+}
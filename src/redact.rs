@@ -0,0 +1,318 @@
+//! Compact, log-safe views of envelopes and signatures
+//!
+//! The derived `Debug` of the signature and envelope types prints every byte
+//! of every public key and signature, which buries the useful part of a log
+//! line. The [`fmt::Display`] impls here print one line instead: the signature
+//! type, signer and version and a truncated transaction hash, and for an
+//! envelope the body type and principal followed by its signatures. The same
+//! summaries are available for JSON envelopes through [`envelope_summary`] and
+//! [`signature_summary`].
+//!
+//! [`Redact::redacted`] keeps the full structure but shortens every hash, key
+//! and signature; it works on anything serializable, JSON envelopes included:
+//!
+//! ```
+//! use accumulate_client::redact::Redact;
+//! use serde_json::json;
+//!
+//! let signature = json!({"type": "ed25519", "publicKey": "ab".repeat(32)});
+//! assert_eq!(
+//!     signature.redacted().to_string(),
+//!     r#"{"publicKey":"abababab…abab","type":"ed25519"}"#
+//! );
+//! ```
+
+use serde::Serialize;
+use serde_json::Value;
+use std::fmt;
+
+/// Hex strings at least this long are truncated
+const MIN_REDACTED_HEX: usize = 32;
+
+/// Byte arrays at least this long are replaced by their length
+const MIN_REDACTED_BYTES: usize = 16;
+
+/// Shorten a long hex string to its first 8 and last 4 digits
+///
+/// Anything that is not a long hex string is returned unchanged.
+#[must_use]
+pub fn short_hex(value: &str) -> String {
+    if value.len() < MIN_REDACTED_HEX || !value.bytes().all(|b| b.is_ascii_hexdigit()) {
+        return value.to_string();
+    }
+    let value = value.to_ascii_lowercase();
+    format!("{}…{}", &value[..8], &value[value.len() - 4..])
+}
+
+/// Copy of `value` with long hex strings shortened and byte arrays replaced
+/// by their length
+#[must_use]
+pub fn redact_json(value: &Value) -> Value {
+    match value {
+        Value::String(s) => Value::String(short_hex(s)),
+        Value::Array(items) if items.len() >= MIN_REDACTED_BYTES && items.iter().all(is_byte) => {
+            Value::String(format!("<{} bytes>", items.len()))
+        }
+        Value::Array(items) => Value::Array(items.iter().map(redact_json).collect()),
+        Value::Object(object) => Value::Object(
+            object
+                .iter()
+                .map(|(k, v)| (k.clone(), redact_json(v)))
+                .collect(),
+        ),
+        other => other.clone(),
+    }
+}
+
+fn is_byte(value: &Value) -> bool {
+    value.as_u64().is_some_and(|n| u8::try_from(n).is_ok())
+}
+
+/// A view of a value whose `Debug` and `Display` print it as redacted JSON
+pub struct Redacted<'a, T: ?Sized>(&'a T);
+
+/// Redacted formatting for any serializable value
+pub trait Redact {
+    /// View of `self` with hashes, keys and signatures shortened
+    fn redacted(&self) -> Redacted<'_, Self>;
+}
+
+impl<T: Serialize + ?Sized> Redact for T {
+    fn redacted(&self) -> Redacted<'_, Self> {
+        Redacted(self)
+    }
+}
+
+impl<T: Serialize + ?Sized> fmt::Display for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let value = serde_json::to_value(self.0).map_err(|_| fmt::Error)?;
+        write!(f, "{}", redact_json(&value))
+    }
+}
+
+impl<T: Serialize + ?Sized> fmt::Debug for Redacted<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(self, f)
+    }
+}
+
+/// One-line summary of a JSON signature
+#[must_use]
+pub const fn signature_summary(signature: &Value) -> SignatureSummary<'_> {
+    SignatureSummary(signature)
+}
+
+/// One-line summary of a JSON envelope
+#[must_use]
+pub const fn envelope_summary(envelope: &Value) -> EnvelopeSummary<'_> {
+    EnvelopeSummary(envelope)
+}
+
+/// One-line summary of a signature, from [`signature_summary`]
+#[derive(Debug, Clone, Copy)]
+pub struct SignatureSummary<'a>(&'a Value);
+
+/// One-line summary of an envelope, from [`envelope_summary`]
+#[derive(Debug, Clone, Copy)]
+pub struct EnvelopeSummary<'a>(&'a Value);
+
+/// Field by its camelCase or `PascalCase` name; the generated types use the
+/// latter
+fn field<'v>(value: &'v Value, name: &str) -> Option<&'v Value> {
+    value.get(name).or_else(|| {
+        let mut chars = name.chars();
+        let pascal: String = chars
+            .next()
+            .map(|c| c.to_ascii_uppercase().to_string() + chars.as_str())
+            .unwrap_or_default();
+        value.get(pascal)
+    })
+}
+
+fn field_str<'v>(value: &'v Value, name: &str) -> Option<&'v str> {
+    field(value, name).and_then(Value::as_str)
+}
+
+impl fmt::Display for SignatureSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let signature = self.0;
+        f.write_str(field_str(signature, "type").unwrap_or("signature"))?;
+        if let Some(signer) = field_str(signature, "signer") {
+            write!(f, " {signer}")?;
+        }
+        if let Some(version) = field(signature, "signerVersion").and_then(Value::as_u64) {
+            write!(f, " v{version}")?;
+        }
+        if let Some(delegator) = field_str(signature, "delegator") {
+            write!(f, " for {delegator}")?;
+        }
+        if let Some(hash) = field_str(signature, "transactionHash") {
+            write!(f, " tx {}", short_hex(hash))?;
+        }
+        if let Some(inner) = field(signature, "signature").filter(|s| s.is_object()) {
+            write!(f, " ({})", SignatureSummary(inner))?;
+        }
+        if let Some(inner) = field(signature, "signatures").and_then(Value::as_array) {
+            write!(f, " ({} signatures)", inner.len())?;
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for EnvelopeSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let envelope = self.0;
+        let transaction = match envelope.get("transaction") {
+            Some(Value::Array(transactions)) => transactions.first(),
+            Some(transaction) => Some(transaction),
+            None => Some(envelope),
+        };
+        let body_type = transaction
+            .and_then(|t| t.get("body"))
+            .and_then(|b| field_str(b, "type"))
+            .unwrap_or("transaction");
+        f.write_str(body_type)?;
+        if let Some(principal) = transaction
+            .and_then(|t| t.get("header"))
+            .and_then(|h| field_str(h, "principal"))
+        {
+            write!(f, " for {principal}")?;
+        }
+
+        let signatures = envelope
+            .get("signatures")
+            .and_then(Value::as_array)
+            .map_or(&[][..], Vec::as_slice);
+        f.write_str(" [")?;
+        for (i, signature) in signatures.iter().enumerate() {
+            if i > 0 {
+                f.write_str("; ")?;
+            }
+            write!(f, "{}", SignatureSummary(signature))?;
+        }
+        f.write_str("]")
+    }
+}
+
+/// Display through the JSON summary of a serializable value
+fn display_as<T: Serialize>(
+    value: &T,
+    f: &mut fmt::Formatter<'_>,
+    summary: fn(&Value, &mut fmt::Formatter<'_>) -> fmt::Result,
+) -> fmt::Result {
+    let value = serde_json::to_value(value).map_err(|_| fmt::Error)?;
+    summary(&value, f)
+}
+
+fn write_signature(value: &Value, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", SignatureSummary(value))
+}
+
+fn write_envelope(value: &Value, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", EnvelopeSummary(value))
+}
+
+macro_rules! display_summary {
+    ($writer:ident: $($ty:ty),* $(,)?) => {
+        $(
+            impl fmt::Display for $ty {
+                fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                    display_as(self, f, $writer)
+                }
+            }
+        )*
+    };
+}
+
+display_summary!(write_signature:
+    crate::generated::signatures::Signature,
+    crate::protocol::TransactionSignature,
+    crate::codec::TransactionSignature,
+);
+
+display_summary!(write_envelope:
+    crate::protocol::TransactionEnvelope,
+    crate::codec::TransactionEnvelope,
+);
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_short_hex_and_redact_json() {
+        assert_eq!(short_hex(&"AB".repeat(32)), "abababab…abab");
+        assert_eq!(short_hex("acc://alice.acme"), "acc://alice.acme");
+        assert_eq!(short_hex("deadbeef"), "deadbeef");
+
+        let redacted = redact_json(&json!({
+            "signer": "acc://alice.acme/book/1",
+            "key": vec![7u8; 32],
+            "nested": [{"hash": "01".repeat(32)}],
+            "small": [1, 2, 3]
+        }));
+        assert_eq!(
+            redacted,
+            json!({
+                "signer": "acc://alice.acme/book/1",
+                "key": "<32 bytes>",
+                "nested": [{"hash": "01010101…0101"}],
+                "small": [1, 2, 3]
+            })
+        );
+    }
+
+    #[test]
+    fn test_signature_and_envelope_display() {
+        let signature: crate::generated::signatures::Signature = serde_json::from_value(json!({
+            "type": "ed25519",
+            "PublicKey": "11".repeat(32),
+            "Signature": "22".repeat(64),
+            "Signer": "acc://alice.acme/book/1",
+            "SignerVersion": 3,
+            "Timestamp": 1,
+            "TransactionHash": "ab".repeat(32),
+            "Data": null,
+        }))
+        .unwrap();
+        assert_eq!(
+            signature.to_string(),
+            "ed25519 acc://alice.acme/book/1 v3 tx abababab…abab"
+        );
+        assert!(!format!("{:?}", signature.redacted()).contains(&"22".repeat(64)));
+
+        let envelope = json!({
+            "transaction": [{
+                "header": {"principal": "acc://alice.acme/tokens", "initiator": "33".repeat(32)},
+                "body": {"type": "sendTokens", "to": []}
+            }],
+            "signatures": [
+                {"type": "ed25519", "signer": "acc://alice.acme/book/1", "signerVersion": 1,
+                 "transactionHash": "ab".repeat(32)},
+                {"type": "delegated", "delegator": "acc://bob.acme/book/1",
+                 "signature": {"type": "ed25519", "signer": "acc://carol.acme/book/1"}}
+            ]
+        });
+        assert_eq!(
+            envelope_summary(&envelope).to_string(),
+            "sendTokens for acc://alice.acme/tokens [ed25519 acc://alice.acme/book/1 v1 tx \
+             abababab…abab; delegated for acc://bob.acme/book/1 (ed25519 acc://carol.acme/book/1)]"
+        );
+
+        let typed: crate::protocol::TransactionEnvelope = serde_json::from_value(json!({
+            "transaction": [{"header": {"principal": "acc://alice.acme/tokens"}, "body": {"type": "burnTokens"}}],
+            "signatures": [{
+                "type": "ed25519", "publicKey": "11".repeat(32), "signature": "22".repeat(64),
+                "signer": "acc://alice.acme/book/1", "signerVersion": 2, "timestamp": 1,
+                "transactionHash": "cd".repeat(32)
+            }]
+        }))
+        .unwrap();
+        assert_eq!(
+            typed.to_string(),
+            "burnTokens for acc://alice.acme/tokens [ed25519 acc://alice.acme/book/1 v2 tx cdcdcdcd…cdcd]"
+        );
+    }
+}
@@ -0,0 +1,539 @@
+//! Retries and endpoint failover for [`JsonRpcClient`] calls
+//!
+//! A [`RetryPolicy`] decides whether and when a failed call is repeated, and
+//! [`JsonRpcClient::with_fallbacks`] adds endpoints a call moves on to while
+//! the preferred one is down. Only read-only methods are repeated freely; see
+//! [`is_idempotent`].
+
+use crate::json_rpc_client::{JsonRpcClient, JsonRpcError};
+use serde_json::Value;
+use std::sync::{Arc, PoisonError};
+use std::time::{Duration, Instant};
+use url::Url;
+
+/// When and how often a failed call is repeated
+///
+/// A call is retried when the node could not be reached, timed out, or
+/// answered with one of [`retry_on_codes`](Self::retry_on_codes) (HTTP
+/// statuses such as 503 and JSON-RPC error codes alike). Calls that may
+/// change state, which is every call [`is_idempotent`] does not list as
+/// read-only, are only retried when the request provably never reached the
+/// node, since a timed-out submission may still have been accepted; set
+/// [`retry_submits`](Self::retry_submits) to retry them like queries.
+///
+/// The delay before retry `n` is `initial_backoff * multiplier^(n-1)`, capped
+/// at `max_backoff`, with up to half of it removed at random when `jitter` is
+/// set. A `Retry-After` header on the response takes precedence, within the
+/// same cap.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// Attempts per call, the first included; 1 disables retries
+    pub max_attempts: u32,
+    /// Delay before the first retry
+    pub initial_backoff: Duration,
+    /// Longest delay between attempts
+    pub max_backoff: Duration,
+    /// Factor the delay grows by after each retry
+    pub multiplier: u32,
+    /// Randomize delays so clients that failed together do not retry together
+    pub jitter: bool,
+    /// HTTP statuses and JSON-RPC error codes worth retrying
+    pub retry_on_codes: Vec<i32>,
+    /// Retry state-changing calls after timeouts and retryable codes too
+    pub retry_submits: bool,
+}
+
+impl Default for RetryPolicy {
+    /// No retries
+    fn default() -> Self {
+        Self {
+            max_attempts: 1,
+            ..Self::exponential(1)
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Up to `max_attempts` attempts with exponential backoff and jitter,
+    /// starting at 250ms and capped at 5s
+    #[must_use]
+    pub fn exponential(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            initial_backoff: Duration::from_millis(250),
+            max_backoff: Duration::from_secs(5),
+            multiplier: 2,
+            jitter: true,
+            retry_on_codes: vec![429, 502, 503, 504],
+            retry_submits: false,
+        }
+    }
+
+    /// Whether `error` from a call is worth repeating
+    ///
+    /// `idempotent` is false for calls that change state; see
+    /// [`is_idempotent`].
+    #[must_use]
+    pub fn is_retryable(&self, error: &JsonRpcError, idempotent: bool) -> bool {
+        let repeatable = idempotent || self.retry_submits;
+        match error {
+            // The request never left, so even a submission is safe to repeat
+            JsonRpcError::Http(e) if e.is_connect() => true,
+            JsonRpcError::Http(e) => repeatable && (e.is_timeout() || e.is_request()),
+            JsonRpcError::Rpc { code, .. } => repeatable && self.retry_on_codes.contains(code),
+            _ => false,
+        }
+    }
+
+    /// Delay before attempt `attempt + 1` after attempt `attempt` failed with
+    /// `error`, or `None` to give up
+    #[must_use]
+    pub fn next_delay(
+        &self,
+        attempt: u32,
+        error: &JsonRpcError,
+        idempotent: bool,
+        retry_after: Option<Duration>,
+    ) -> Option<Duration> {
+        if attempt >= self.max_attempts || !self.is_retryable(error, idempotent) {
+            return None;
+        }
+        if let Some(delay) = retry_after {
+            return Some(delay.min(self.max_backoff));
+        }
+        let factor = self.multiplier.saturating_pow(attempt.saturating_sub(1));
+        let delay = self
+            .initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff);
+        Some(if self.jitter { jittered(delay) } else { delay })
+    }
+}
+
+/// `delay` minus a random part of up to half of it
+fn jittered(delay: Duration) -> Duration {
+    let mut bytes = [0u8; 4];
+    if getrandom::getrandom(&mut bytes).is_err() {
+        return delay;
+    }
+    let half = delay / 2;
+    half + half.mul_f64(f64::from(u32::from_le_bytes(bytes)) / f64::from(u32::MAX))
+}
+
+/// Whether repeating `method` cannot apply a change twice
+///
+/// `method` is a V3 method name or a V2 path. Only the read-only query,
+/// describe, status and metrics methods are idempotent; every other method,
+/// including the V2 write methods such as `send-tokens` and any method this
+/// SDK does not know, is treated as a submission.
+#[must_use]
+pub fn is_idempotent(method: &str) -> bool {
+    method.starts_with("query")
+        || matches!(
+            method,
+            "describe"
+                | "status"
+                | "version"
+                | "metrics"
+                | "network-status"
+                | "node-info"
+                | "consensus-status"
+                | "find-service"
+                | "list-snapshots"
+                | "validate"
+        )
+}
+
+/// A failed attempt and the delay the node asked for, if any
+pub(crate) struct Failure {
+    pub(crate) error: JsonRpcError,
+    pub(crate) retry_after: Option<Duration>,
+}
+
+impl From<JsonRpcError> for Failure {
+    fn from(error: JsonRpcError) -> Self {
+        Self {
+            error,
+            retry_after: None,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Failure {
+    fn from(error: reqwest::Error) -> Self {
+        JsonRpcError::from(error).into()
+    }
+}
+
+impl From<url::ParseError> for Failure {
+    fn from(error: url::ParseError) -> Self {
+        JsonRpcError::from(error).into()
+    }
+}
+
+/// `Retry-After` in seconds; HTTP dates are ignored
+pub(crate) fn retry_after(response: &reqwest::Response) -> Option<Duration> {
+    response
+        .headers()
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+/// Reject an unsuccessful HTTP status whose body is not JSON
+///
+/// JSON bodies are passed on, since nodes report JSON-RPC errors with
+/// non-2xx statuses too.
+pub(crate) fn check_status(
+    status: reqwest::StatusCode,
+    body: &str,
+    retry_after: Option<Duration>,
+) -> Result<Value, Failure> {
+    match serde_json::from_str(body) {
+        Ok(value) => Ok(value),
+        Err(_) if !status.is_success() => Err(Failure {
+            error: JsonRpcError::Rpc {
+                code: i32::from(status.as_u16()),
+                message: format!("HTTP {status}"),
+                data: None,
+            },
+            retry_after,
+        }),
+        Err(e) => Err(JsonRpcError::from(e).into()),
+    }
+}
+
+/// Whether `error` means the endpoint is down rather than that it refused
+/// the request
+///
+/// `repeatable` is false for state-changing calls, which only move on when
+/// the request never reached the node.
+fn is_endpoint_failure(error: &JsonRpcError, repeatable: bool) -> bool {
+    match error {
+        JsonRpcError::Http(e) if e.is_connect() => true,
+        JsonRpcError::Http(e) => repeatable && (e.is_timeout() || e.is_request()),
+        JsonRpcError::Rpc { code, .. } => repeatable && (500..600).contains(code),
+        _ => false,
+    }
+}
+
+/// Health of one endpoint as tracked by a [`JsonRpcClient`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EndpointStatus {
+    /// Endpoint URL
+    pub url: Url,
+    /// Whether the endpoint is tried before those that failed recently
+    pub healthy: bool,
+    /// Failures since the endpoint last answered
+    pub consecutive_failures: u32,
+    /// Calls the endpoint answered
+    pub successes: u64,
+    /// Calls that failed because the endpoint was down
+    pub failures: u64,
+}
+
+#[derive(Debug, Clone, Default)]
+pub(crate) struct EndpointHealth {
+    consecutive_failures: u32,
+    successes: u64,
+    failures: u64,
+    down_until: Option<Instant>,
+}
+
+impl EndpointHealth {
+    fn is_healthy(&self, now: Instant) -> bool {
+        self.down_until.map_or(true, |until| now >= until)
+    }
+}
+
+impl JsonRpcClient {
+    /// Use `retry` for every call
+    #[must_use]
+    pub fn with_retry(mut self, retry: RetryPolicy) -> Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Fail over to `fallbacks`, in order, when `base_url` is down, passing
+    /// over a failed endpoint for `cooldown`
+    #[must_use]
+    pub fn with_fallbacks(mut self, fallbacks: Vec<Url>, cooldown: Duration) -> Self {
+        self.fallbacks = fallbacks;
+        self.cooldown = cooldown;
+        self.health = Arc::default();
+        self
+    }
+
+    /// `base_url` followed by the fallbacks
+    pub fn endpoints(&self) -> impl Iterator<Item = &Url> {
+        std::iter::once(&self.base_url).chain(&self.fallbacks)
+    }
+
+    /// Health of every endpoint, `base_url` first
+    #[must_use]
+    pub fn endpoint_status(&self) -> Vec<EndpointStatus> {
+        let now = Instant::now();
+        let health = self.lock_health().clone();
+        self.endpoints()
+            .enumerate()
+            .map(|(i, url)| {
+                let health = health.get(i).cloned().unwrap_or_default();
+                EndpointStatus {
+                    url: url.clone(),
+                    healthy: health.is_healthy(now),
+                    consecutive_failures: health.consecutive_failures,
+                    successes: health.successes,
+                    failures: health.failures,
+                }
+            })
+            .collect()
+    }
+
+    fn lock_health(&self) -> std::sync::MutexGuard<'_, Vec<EndpointHealth>> {
+        let mut health = self.health.lock().unwrap_or_else(PoisonError::into_inner);
+        health.resize_with(1 + self.fallbacks.len(), EndpointHealth::default);
+        health
+    }
+
+    /// Endpoint indices in the order to try them: healthy endpoints as
+    /// configured, then the rest by how long ago they failed
+    fn endpoint_order(&self) -> Vec<usize> {
+        let now = Instant::now();
+        let health = self.lock_health().clone();
+        let (mut order, mut down): (Vec<_>, Vec<_>) =
+            (0..health.len()).partition(|&i| health[i].is_healthy(now));
+        down.sort_by_key(|&i| health[i].down_until);
+        order.extend(down);
+        order
+    }
+
+    fn record(&self, index: usize, up: bool) {
+        let mut health = self.lock_health();
+        let Some(health) = health.get_mut(index) else {
+            return;
+        };
+        if up {
+            health.successes += 1;
+            health.consecutive_failures = 0;
+            health.down_until = None;
+        } else {
+            health.failures += 1;
+            health.consecutive_failures += 1;
+            health.down_until = Some(Instant::now() + self.cooldown);
+        }
+    }
+
+    /// Run `attempt` against each endpoint in turn until one is up
+    pub(crate) async fn failover<T, F, Fut>(&self, idempotent: bool, attempt: F) -> Result<T, Failure>
+    where
+        F: Fn(Url) -> Fut,
+        Fut: std::future::Future<Output = Result<T, Failure>>,
+    {
+        let repeatable = idempotent || self.retry.retry_submits;
+        let mut last = None;
+        for index in self.endpoint_order() {
+            let Some(url) = self.endpoints().nth(index).cloned() else {
+                continue;
+            };
+            match attempt(url).await {
+                Err(failure) if is_endpoint_failure(&failure.error, repeatable) => {
+                    self.record(index, false);
+                    last = Some(failure);
+                }
+                result => {
+                    self.record(index, true);
+                    return result;
+                }
+            }
+        }
+        // endpoint_order always yields base_url
+        Err(last.unwrap_or_else(|| JsonRpcError::General("no endpoints configured".to_string()).into()))
+    }
+
+    /// Run `attempt` until it succeeds or the retry policy gives up
+    pub(crate) async fn retrying<T, F, Fut>(&self, idempotent: bool, mut attempt: F) -> Result<T, JsonRpcError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, Failure>>,
+    {
+        let mut attempts = 1;
+        loop {
+            match attempt().await {
+                Ok(value) => return Ok(value),
+                Err(failure) => {
+                    let delay = self.retry.next_delay(
+                        attempts,
+                        &failure.error,
+                        idempotent,
+                        failure.retry_after,
+                    );
+                    match delay {
+                        Some(delay) => tokio::time::sleep(delay).await,
+                        None => return Err(failure.error),
+                    }
+                }
+            }
+            attempts += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn rpc(code: i32) -> JsonRpcError {
+        JsonRpcError::Rpc {
+            code,
+            message: String::new(),
+            data: None,
+        }
+    }
+
+    #[test]
+    fn test_retry_backoff_and_classification() {
+        let policy = RetryPolicy {
+            jitter: false,
+            ..RetryPolicy::exponential(5)
+        };
+        let delays: Vec<_> = (1..=5)
+            .map(|n| policy.next_delay(n, &rpc(503), true, None))
+            .collect();
+        assert_eq!(
+            delays,
+            vec![
+                Some(Duration::from_millis(250)),
+                Some(Duration::from_millis(500)),
+                Some(Duration::from_secs(1)),
+                Some(Duration::from_secs(2)),
+                None,
+            ]
+        );
+        assert_eq!(
+            policy.next_delay(1, &rpc(429), true, Some(Duration::from_secs(60))),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(policy.next_delay(1, &rpc(-32602), true, None), None);
+
+        // Submissions are only repeated when asked to
+        assert!(!is_idempotent("submit") && !is_idempotent("execute-direct"));
+        assert!(!is_idempotent("send-tokens") && !is_idempotent("create-adi"));
+        assert!(!is_idempotent("faucet") && !is_idempotent("some-new-method"));
+        assert!(is_idempotent("query") && is_idempotent("network-status"));
+        assert!(is_idempotent("query-tx-history") && is_idempotent("version"));
+        assert_eq!(policy.next_delay(1, &rpc(503), false, None), None);
+        let submits = RetryPolicy {
+            retry_submits: true,
+            ..policy
+        };
+        assert!(submits.next_delay(1, &rpc(503), false, None).is_some());
+
+        assert_eq!(
+            RetryPolicy::default().next_delay(1, &rpc(503), true, None),
+            None
+        );
+        let jittered = RetryPolicy::exponential(3)
+            .next_delay(2, &rpc(503), true, None)
+            .unwrap();
+        assert!(jittered >= Duration::from_millis(250) && jittered <= Duration::from_millis(500));
+    }
+
+    /// Serve `responses` in order, one per connection
+    fn serve(responses: Vec<&'static str>) -> Url {
+        use std::io::{Read, Write};
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = Url::parse(&format!("http://{}/v3", listener.local_addr().unwrap())).unwrap();
+        std::thread::spawn(move || {
+            for body in responses {
+                let (mut stream, _) = listener.accept().unwrap();
+                let mut buf = [0u8; 4096];
+                let _ = stream.read(&mut buf);
+                let _ = stream.write_all(body.as_bytes());
+            }
+        });
+        url
+    }
+
+    #[tokio::test]
+    async fn test_call_retries_unavailable_node() {
+        const UNAVAILABLE: &str =
+            "HTTP/1.1 503 Service Unavailable\r\nRetry-After: 0\r\nContent-Length: 4\r\nConnection: close\r\n\r\nbusy";
+        const OK: &str = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 35\r\nConnection: close\r\n\r\n{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":7}";
+
+        let client = JsonRpcClient::new(serve(vec![UNAVAILABLE, OK]))
+            .unwrap()
+            .with_retry(RetryPolicy::exponential(3));
+        let result: u64 = client.call("query", json!({})).await.unwrap();
+        assert_eq!(result, 7);
+
+        // Without retries the 503 surfaces as an error code
+        let client = JsonRpcClient::new(serve(vec![UNAVAILABLE])).unwrap();
+        let err = client.call::<u64>("query", json!({})).await.unwrap_err();
+        assert!(matches!(err, JsonRpcError::Rpc { code: 503, .. }));
+
+        // A submission is not repeated after a retryable response
+        let client = JsonRpcClient::new(serve(vec![UNAVAILABLE, OK]))
+            .unwrap()
+            .with_retry(RetryPolicy::exponential(3));
+        assert!(client.call::<u64>("submit", json!({})).await.is_err());
+    }
+
+    /// A URL nothing listens on
+    fn closed() -> Url {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        Url::parse(&format!("http://{}/v3", listener.local_addr().unwrap())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_call_fails_over_to_healthy_endpoint() {
+        const OK: &str = "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 35\r\nConnection: close\r\n\r\n{\"jsonrpc\":\"2.0\",\"id\":1,\"result\":7}";
+        const UNAVAILABLE: &str =
+            "HTTP/1.1 503 Service Unavailable\r\nContent-Length: 4\r\nConnection: close\r\n\r\nbusy";
+
+        let down = closed();
+        let client = JsonRpcClient::new(down.clone())
+            .unwrap()
+            .with_fallbacks(vec![serve(vec![OK, OK])], Duration::from_secs(60));
+        let result: u64 = client.call("query", json!({})).await.unwrap();
+        assert_eq!(result, 7);
+
+        let status = client.endpoint_status();
+        assert_eq!(status[0].url, down);
+        assert!(!status[0].healthy);
+        assert_eq!((status[0].failures, status[1].successes), (1, 1));
+
+        // The failed endpoint is passed over until its cooldown ends
+        let result: u64 = client.clone().call("query", json!({})).await.unwrap();
+        assert_eq!(result, 7);
+        let status = client.endpoint_status();
+        assert_eq!((status[0].failures, status[1].successes), (1, 2));
+
+        // A submission that reached a node is not sent to another one
+        let client = JsonRpcClient::new(serve(vec![UNAVAILABLE]))
+            .unwrap()
+            .with_fallbacks(vec![serve(vec![OK])], Duration::from_secs(60));
+        let err = client.call::<u64>("submit", json!({})).await.unwrap_err();
+        assert!(matches!(err, JsonRpcError::Rpc { code: 503, .. }));
+        assert!(client.endpoint_status()[0].healthy);
+    }
+
+    #[test]
+    fn test_endpoint_order_prefers_least_recently_failed() {
+        let client = JsonRpcClient::new(closed())
+            .unwrap()
+            .with_fallbacks(vec![closed(), closed()], Duration::from_secs(60));
+        assert_eq!(client.endpoint_order(), vec![0, 1, 2]);
+        client.record(1, false);
+        std::thread::sleep(Duration::from_millis(1));
+        client.record(0, false);
+        assert_eq!(client.endpoint_order(), vec![2, 1, 0]);
+        client.record(0, true);
+        assert_eq!(client.endpoint_order(), vec![0, 2, 1]);
+    }
+}
@@ -0,0 +1,458 @@
+//! Partition routing and node-pinned queries
+//!
+//! Accounts live on the BVN their root identity routes to. The routing number
+//! is the first eight bytes (big-endian) of the SHA-256 of the lowercased
+//! authority; the network's [`RoutingTable`] maps number prefixes to
+//! partitions, with per-identity overrides taking precedence.
+//!
+//! Right after a submit, a node of another partition (or a lagging node of
+//! the same one) may not have seen the transaction yet. [`PinnedQueries`]
+//! gives read-your-writes consistency: it pins the submitted transaction and
+//! its principal to the partition they route to, sends their queries to a
+//! node of that partition, and releases the pin once the transaction is
+//! delivered. Queries for anything unpinned go through the regular client.
+
+use crate::client::AccumulateClient;
+use crate::json_rpc_client::JsonRpcError;
+use crate::types::{FindServiceOptions, NetworkStatusOptions, ServiceAddress};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::sync::{Mutex, PoisonError};
+use url::Url;
+
+/// Routing number of an account: the first eight bytes of its identity hash
+#[must_use]
+pub fn routing_number(url: &str) -> u64 {
+    let hash = Sha256::digest(identity_authority(url).as_bytes());
+    let mut prefix = [0u8; 8];
+    prefix.copy_from_slice(&hash[..8]);
+    u64::from_be_bytes(prefix)
+}
+
+/// Lowercased authority of an account or transaction URL, without the
+/// `acc://` scheme, user info or path
+fn identity_authority(url: &str) -> String {
+    let rest = url
+        .trim()
+        .strip_prefix("acc://")
+        .unwrap_or_else(|| url.trim());
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or_default();
+    let authority = authority.rsplit('@').next().unwrap_or(authority);
+    authority.to_ascii_lowercase()
+}
+
+/// URL of a partition: `acc://dn.acme` for the directory, `acc://bvn-<id>.acme`
+/// for a BVN
+#[must_use]
+pub fn partition_url(partition: &str) -> String {
+    if partition.eq_ignore_ascii_case("directory") || partition.eq_ignore_ascii_case("dn") {
+        "acc://dn.acme".to_string()
+    } else {
+        format!("acc://bvn-{partition}.acme")
+    }
+}
+
+/// A prefix of routing numbers assigned to a partition
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Route {
+    /// Number of leading bits matched
+    #[serde(default)]
+    pub length: u64,
+    /// Value of those bits
+    #[serde(default)]
+    pub value: u64,
+    /// Partition ID
+    #[serde(default)]
+    pub partition: String,
+}
+
+impl Route {
+    /// Whether `number` starts with this route's prefix
+    #[must_use]
+    pub const fn matches(&self, number: u64) -> bool {
+        match self.length {
+            0 => true,
+            1..=63 => number >> (64 - self.length) == self.value,
+            _ => number == self.value,
+        }
+    }
+}
+
+/// A root identity routed to a partition regardless of its routing number
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RouteOverride {
+    /// Root identity URL
+    #[serde(default)]
+    pub account: String,
+    /// Partition ID
+    #[serde(default)]
+    pub partition: String,
+}
+
+/// The network's routing table, as published in `network-status`
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RoutingTable {
+    /// Identity overrides
+    #[serde(default)]
+    pub overrides: Vec<RouteOverride>,
+    /// Prefix routes
+    #[serde(default)]
+    pub routes: Vec<Route>,
+}
+
+impl RoutingTable {
+    /// Partition an account or transaction URL routes to
+    ///
+    /// Overrides are checked first; otherwise the longest matching route
+    /// wins. Returns `None` when no route matches.
+    #[must_use]
+    pub fn route(&self, url: &str) -> Option<&str> {
+        let authority = identity_authority(url);
+        if let Some(o) = self
+            .overrides
+            .iter()
+            .find(|o| identity_authority(&o.account) == authority)
+        {
+            return Some(&o.partition);
+        }
+
+        let number = routing_number(url);
+        self.routes
+            .iter()
+            .filter(|r| r.matches(number))
+            .max_by_key(|r| r.length)
+            .map(|r| r.partition.as_str())
+    }
+
+    /// Every partition named by the table, in first-seen order
+    #[must_use]
+    pub fn partitions(&self) -> Vec<&str> {
+        let mut partitions: Vec<&str> = Vec::new();
+        let names = self
+            .routes
+            .iter()
+            .map(|r| r.partition.as_str())
+            .chain(self.overrides.iter().map(|o| o.partition.as_str()));
+        for name in names {
+            if !partitions.iter().any(|p| p.eq_ignore_ascii_case(name)) {
+                partitions.push(name);
+            }
+        }
+        partitions
+    }
+}
+
+/// HTTP base URL of a node, from one of its multiaddrs
+///
+/// Takes the `ip4`, `ip6` or `dns*` host of `addr` and serves the API on
+/// `api_port`, since a multiaddr only carries the peer-to-peer port.
+#[must_use]
+pub fn node_endpoint(addr: &str, api_port: u16) -> Option<String> {
+    let mut parts = addr.split('/').skip(1);
+    while let Some(protocol) = parts.next() {
+        let value = parts.next()?;
+        match protocol {
+            "ip4" | "dns" | "dns4" | "dns6" => return Some(format!("http://{value}:{api_port}")),
+            "ip6" => return Some(format!("http://[{value}]:{api_port}")),
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Key a query scope is pinned under: the transaction hash of a txid, the
+/// lowercased URL of an account
+fn pin_key(scope: &str) -> String {
+    let scope = scope.trim();
+    let rest = scope.strip_prefix("acc://").unwrap_or(scope);
+    match rest.split_once('@') {
+        Some((hash, _)) => hash.to_ascii_lowercase(),
+        None => rest.trim_end_matches('/').to_ascii_lowercase(),
+    }
+}
+
+/// A pinned account or transaction
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Pin {
+    partition: String,
+    txid: String,
+}
+
+/// Query handle that keeps follow-up reads on the partition a transaction
+/// was submitted to until it is delivered
+///
+/// Build it with [`AccumulateClient::pinned_queries`], register nodes with
+/// [`PinnedQueries::with_node`] or [`PinnedQueries::discover_node`], and call
+/// [`PinnedQueries::pin`] with each submitted txid.
+#[derive(Debug)]
+pub struct PinnedQueries<'a> {
+    client: &'a AccumulateClient,
+    routing: RoutingTable,
+    nodes: HashMap<String, AccumulateClient>,
+    pins: Mutex<HashMap<String, Pin>>,
+}
+
+impl<'a> PinnedQueries<'a> {
+    /// Pin queries made through `client` using `routing`
+    #[must_use]
+    pub fn new(client: &'a AccumulateClient, routing: RoutingTable) -> Self {
+        Self {
+            client,
+            routing,
+            nodes: HashMap::new(),
+            pins: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Routing table in use
+    #[must_use]
+    pub const fn routing(&self) -> &RoutingTable {
+        &self.routing
+    }
+
+    /// Send pinned queries for `partition` to `node`
+    #[must_use]
+    pub fn with_node(mut self, partition: &str, node: AccumulateClient) -> Self {
+        self.nodes.insert(partition.to_ascii_lowercase(), node);
+        self
+    }
+
+    /// Find a node serving queries for `partition` and pin to it
+    ///
+    /// Asks `find-service` for the partition's query service and connects to
+    /// the first peer with a usable address, on `api_port`. Returns the
+    /// node's base URL, or `None` when no peer could be reached that way.
+    pub async fn discover_node(
+        &mut self,
+        partition: &str,
+        api_port: u16,
+    ) -> Result<Option<String>, JsonRpcError> {
+        let peers = self
+            .client
+            .find_service(FindServiceOptions {
+                network: None,
+                service: Some(ServiceAddress {
+                    service_type: "query".to_string(),
+                    argument: Some(partition.to_string()),
+                }),
+                known: Some(true),
+                timeout: None,
+            })
+            .await?;
+        let Some(base) = peers
+            .iter()
+            .flat_map(|peer| peer.addresses.iter())
+            .find_map(|addr| node_endpoint(addr, api_port))
+        else {
+            return Ok(None);
+        };
+
+        let v2 = Url::parse(&format!("{base}/v2"))?;
+        let v3 = Url::parse(&format!("{base}/v3"))?;
+        let node = AccumulateClient::new_with_options(v2, v3, self.client.options.clone()).await?;
+        self.nodes.insert(partition.to_ascii_lowercase(), node);
+        Ok(Some(base))
+    }
+
+    /// Pin a submitted transaction and its principal to the partition the
+    /// principal routes to
+    ///
+    /// Returns that partition, or `None` when the routing table has no route
+    /// for it (nothing is pinned then).
+    pub fn pin(&self, txid: &str) -> Option<String> {
+        let partition = self.routing.route(txid)?.to_string();
+        let principal = txid
+            .rsplit_once('@')
+            .map_or_else(|| txid.to_string(), |(_, p)| format!("acc://{p}"));
+        let pin = Pin {
+            partition: partition.clone(),
+            txid: pin_key(txid),
+        };
+        {
+            let mut pins = self.lock();
+            pins.insert(pin_key(txid), pin.clone());
+            pins.insert(pin_key(&principal), pin);
+        }
+        Some(partition)
+    }
+
+    /// Partition `scope` is pinned to, if any
+    #[must_use]
+    pub fn pinned_partition(&self, scope: &str) -> Option<String> {
+        self.lock()
+            .get(&pin_key(scope))
+            .map(|p| p.partition.clone())
+    }
+
+    /// Drop every pin held for `txid`
+    pub fn release(&self, txid: &str) {
+        let txid = pin_key(txid);
+        self.lock().retain(|_, pin| pin.txid != txid);
+    }
+
+    /// Number of pinned accounts and transactions
+    #[must_use]
+    pub fn pinned_count(&self) -> usize {
+        self.lock().len()
+    }
+
+    /// Client queries for `scope` go to: the pinned partition's node when
+    /// there is one, the regular client otherwise
+    #[must_use]
+    pub fn client_for(&self, scope: &str) -> &AccumulateClient {
+        self.pinned_partition(scope)
+            .and_then(|p| self.nodes.get(&p.to_ascii_lowercase()))
+            .unwrap_or(self.client)
+    }
+
+    /// Run a V3 `query` for `scope`, on the pinned node if `scope` is pinned
+    pub async fn query(&self, scope: &str, query: Value) -> Result<Value, JsonRpcError> {
+        self.client_for(scope)
+            .call_query(json!({"scope": scope, "query": query}))
+            .await
+    }
+
+    /// Check whether `txid` has been delivered, releasing its pins once it
+    /// has
+    pub async fn confirm_delivery(&self, txid: &str) -> Result<bool, JsonRpcError> {
+        let result = self.query(txid, json!({"queryType": "default"})).await?;
+        let delivered = result
+            .get("status")
+            .and_then(|s| s.get("delivered"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        if delivered {
+            self.release(txid);
+        }
+        Ok(delivered)
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<String, Pin>> {
+        self.pins.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl AccumulateClient {
+    /// The network's routing table, from `network-status`
+    pub async fn routing_table(&self) -> Result<RoutingTable, JsonRpcError> {
+        let status = self.network_status(NetworkStatusOptions::default()).await?;
+        match status.routing {
+            Some(routing) => Ok(serde_json::from_value(routing)?),
+            None => Err(JsonRpcError::General("network status has no routing table".to_string())),
+        }
+    }
+
+    /// Query handle that pins reads of submitted transactions to their
+    /// partition (see [`PinnedQueries`])
+    pub async fn pinned_queries(&self) -> Result<PinnedQueries<'_>, JsonRpcError> {
+        Ok(PinnedQueries::new(self, self.routing_table().await?))
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn table() -> RoutingTable {
+        serde_json::from_value(json!({
+            "overrides": [{"account": "acc://dn.acme", "partition": "Directory"}],
+            "routes": [
+                {"length": 1, "value": 0, "partition": "Apollo"},
+                {"length": 2, "value": 2, "partition": "Chandrayaan"},
+                {"length": 2, "value": 3, "partition": "Yutu"}
+            ]
+        }))
+        .unwrap()
+    }
+
+    async fn offline_client() -> AccumulateClient {
+        AccumulateClient::new_with_options(
+            Url::parse("http://localhost:26660/v2").unwrap(),
+            Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[test]
+    fn test_partition_url() {
+        assert_eq!(partition_url("Apollo"), "acc://bvn-Apollo.acme");
+        assert_eq!(partition_url("Directory"), "acc://dn.acme");
+    }
+
+    #[test]
+    fn test_routing_number_uses_identity() {
+        let expected = routing_number("acc://alice.acme");
+        assert_eq!(routing_number("acc://Alice.acme/tokens"), expected);
+        assert_eq!(routing_number("acc://abcd@alice.acme/book/1"), expected);
+        assert_ne!(routing_number("acc://bob.acme"), expected);
+    }
+
+    #[test]
+    fn test_route_prefixes_and_overrides() {
+        let table = table();
+        assert_eq!(table.route("acc://dn.acme/ledger"), Some("Directory"));
+        for name in ["alice", "bob", "carol", "dave", "erin"] {
+            let url = format!("acc://{name}.acme");
+            let expected = match routing_number(&url) >> 62 {
+                0 | 1 => "Apollo",
+                2 => "Chandrayaan",
+                _ => "Yutu",
+            };
+            assert_eq!(table.route(&url), Some(expected));
+        }
+        assert_eq!(RoutingTable::default().route("acc://alice.acme"), None);
+        assert_eq!(
+            table.partitions(),
+            vec!["Apollo", "Chandrayaan", "Yutu", "Directory"]
+        );
+    }
+
+    #[test]
+    fn test_node_endpoint_from_multiaddr() {
+        assert_eq!(
+            node_endpoint("/ip4/10.0.0.5/tcp/16593/p2p/12D3KooW", 16595).as_deref(),
+            Some("http://10.0.0.5:16595")
+        );
+        assert_eq!(
+            node_endpoint("/dns/node.example.com/tcp/16593", 443).as_deref(),
+            Some("http://node.example.com:443")
+        );
+        assert_eq!(
+            node_endpoint("/ip6/::1/tcp/16593", 80).as_deref(),
+            Some("http://[::1]:80")
+        );
+        assert_eq!(node_endpoint("/p2p/12D3KooW", 80), None);
+    }
+
+    #[tokio::test]
+    async fn test_pin_and_release() {
+        let client = offline_client().await;
+        let node = offline_client().await;
+        let table = table();
+        let partition = table.route("acc://alice.acme").unwrap().to_string();
+        let pinned = PinnedQueries::new(&client, table).with_node(&partition, node);
+
+        let txid = "acc://ABCD@alice.acme/tokens";
+        assert_eq!(pinned.pin(txid).as_deref(), Some(partition.as_str()));
+        assert_eq!(pinned.pinned_count(), 2);
+        assert_eq!(
+            pinned.pinned_partition("acc://abcd@unknown").as_deref(),
+            Some(partition.as_str())
+        );
+        assert!(pinned.pinned_partition("acc://alice.acme/tokens").is_some());
+        assert!(!std::ptr::eq(pinned.client_for(txid), &client));
+        assert!(std::ptr::eq(pinned.client_for("acc://bob.acme"), &client));
+
+        pinned.release("acc://abcd@alice.acme/tokens");
+        assert_eq!(pinned.pinned_count(), 0);
+        assert!(std::ptr::eq(pinned.client_for(txid), &client));
+    }
+}
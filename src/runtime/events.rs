@@ -0,0 +1,393 @@
+//! WebSocket event subscriptions for the V3 API
+//!
+//! [`EventStream`] opens a WebSocket to a V3 endpoint, sends a `subscribe`
+//! request with the given [`SubscribeOptions`] and yields what the node
+//! pushes back as a [`futures_util::Stream`] of [`NetworkEvent`]s. Each block
+//! event is followed by a [`NetworkEvent::Transaction`] for every transaction
+//! recorded in the block and a [`NetworkEvent::Account`] for every chain
+//! entry, so callers can react to whichever level they care about.
+//!
+//! Requires the `websocket` feature.
+
+use crate::client::AccumulateClient;
+use crate::errors::Error;
+use crate::types::SubscribeOptions;
+use futures_util::{SinkExt, Stream, StreamExt};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::net::TcpStream;
+use tokio_tungstenite::tungstenite::Message;
+use tokio_tungstenite::{connect_async, MaybeTlsStream, WebSocketStream};
+use url::Url;
+
+/// A block committed by a partition
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlockEvent {
+    /// Partition that produced the block
+    #[serde(default)]
+    pub partition: String,
+    /// Minor block index
+    #[serde(default)]
+    pub index: u64,
+    /// Block time, as reported by the node
+    #[serde(default)]
+    pub time: Option<String>,
+    /// Major block index, when the block closed one
+    #[serde(default)]
+    pub major: Option<u64>,
+    /// Chain entries recorded in the block
+    #[serde(default)]
+    pub entries: Vec<Value>,
+}
+
+/// A transaction recorded in a block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TransactionEvent {
+    /// Transaction ID (`acc://<hash>@<principal>`), when reported
+    pub txid: Option<String>,
+    /// Account whose chain recorded the transaction
+    pub account: String,
+    /// Transaction type, e.g. `sendTokens`
+    pub tx_type: String,
+    /// Execution status, e.g. `delivered`
+    pub status: Option<String>,
+    /// Minor block index
+    pub block: u64,
+    /// The transaction (`header` and `body`)
+    pub transaction: Value,
+}
+
+/// A chain entry added to an account in a block
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountEvent {
+    /// Account URL
+    pub account: String,
+    /// Chain name, e.g. `main` or `signature`
+    pub chain: String,
+    /// Index of the entry on the chain
+    pub index: Option<u64>,
+    /// Hex entry hash
+    pub entry: Option<String>,
+    /// Minor block index
+    pub block: u64,
+}
+
+/// An event pushed by a V3 subscription
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NetworkEvent {
+    /// A block was committed
+    Block(BlockEvent),
+    /// A transaction was recorded in the preceding block
+    Transaction(TransactionEvent),
+    /// An account chain was extended in the preceding block
+    Account(AccountEvent),
+    /// Network globals changed
+    Globals(Value),
+    /// The node reported an error for the subscription
+    Error(String),
+    /// An event type this SDK does not know
+    Other(Value),
+}
+
+/// Split one WebSocket frame into events
+///
+/// Accepts a JSON-RPC response or notification wrapping a single event or a
+/// list of events, as well as bare events.
+pub fn parse_events(frame: &Value) -> Vec<NetworkEvent> {
+    if let Some(error) = frame.get("error") {
+        let message = error
+            .get("message")
+            .and_then(Value::as_str)
+            .map_or_else(|| error.to_string(), str::to_string);
+        return vec![NetworkEvent::Error(message)];
+    }
+    let payload = frame.get("params").map_or_else(
+        || frame.get("result").unwrap_or(frame),
+        |params| params.get("result").unwrap_or(params),
+    );
+
+    let mut events = Vec::new();
+    match payload {
+        Value::Null => {}
+        Value::Array(items) => items.iter().for_each(|e| push_event(e, &mut events)),
+        event => push_event(event, &mut events),
+    }
+    events
+}
+
+fn push_event(event: &Value, events: &mut Vec<NetworkEvent>) {
+    let kind = event
+        .get("eventType")
+        .or_else(|| event.get("type"))
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    match kind {
+        "block" => {
+            let Ok(block) = serde_json::from_value::<BlockEvent>(event.clone()) else {
+                events.push(NetworkEvent::Other(event.clone()));
+                return;
+            };
+            let derived: Vec<NetworkEvent> = block
+                .entries
+                .iter()
+                .flat_map(|entry| entry_events(block.index, entry))
+                .collect();
+            events.push(NetworkEvent::Block(block));
+            events.extend(derived);
+        }
+        "globals" => events.push(NetworkEvent::Globals(
+            event
+                .get("values")
+                .cloned()
+                .unwrap_or_else(|| event.clone()),
+        )),
+        "error" => {
+            let err = event.get("err").unwrap_or(event);
+            let message = err
+                .get("message")
+                .and_then(Value::as_str)
+                .map_or_else(|| err.to_string(), str::to_string);
+            events.push(NetworkEvent::Error(message));
+        }
+        _ => events.push(NetworkEvent::Other(event.clone())),
+    }
+}
+
+/// Account and transaction events for one chain entry of a block
+fn entry_events(block: u64, entry: &Value) -> Vec<NetworkEvent> {
+    let str_field =
+        |value: &Value, name: &str| value.get(name).and_then(Value::as_str).map(str::to_string);
+    let Some(account) = str_field(entry, "account") else {
+        return Vec::new();
+    };
+    let chain = str_field(entry, "name").unwrap_or_default();
+    let mut events = vec![NetworkEvent::Account(AccountEvent {
+        account: account.clone(),
+        chain: chain.clone(),
+        index: entry.get("index").and_then(Value::as_u64),
+        entry: str_field(entry, "entry"),
+        block,
+    })];
+
+    let value = entry.get("value").unwrap_or(&Value::Null);
+    let message = value.get("message").unwrap_or(value);
+    if let Some(transaction) = message.get("transaction").filter(|_| chain == "main") {
+        events.push(NetworkEvent::Transaction(TransactionEvent {
+            txid: str_field(value, "id"),
+            account,
+            tx_type: transaction
+                .get("body")
+                .and_then(|b| str_field(b, "type"))
+                .unwrap_or_default(),
+            status: str_field(value, "status"),
+            block,
+            transaction: transaction.clone(),
+        }));
+    }
+    events
+}
+
+/// WebSocket URL for a V3 HTTP endpoint (`http` → `ws`, `https` → `wss`)
+pub fn websocket_url(endpoint: &Url) -> Result<Url, Error> {
+    let scheme = match endpoint.scheme() {
+        "http" | "ws" => "ws",
+        "https" | "wss" => "wss",
+        other => return Err(Error::General(format!("Unsupported URL scheme: {other}"))),
+    };
+    let mut url = endpoint.clone();
+    url.set_scheme(scheme)
+        .map_err(|()| Error::General(format!("Cannot convert {endpoint} to a WebSocket URL")))?;
+    Ok(url)
+}
+
+/// Stream of events from a V3 `subscribe` request
+///
+/// Ends when the node closes the connection. Frames that are not valid JSON
+/// are yielded as errors without ending the stream.
+pub struct EventStream {
+    socket: WebSocketStream<MaybeTlsStream<TcpStream>>,
+    pending: VecDeque<NetworkEvent>,
+}
+
+impl fmt::Debug for EventStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventStream")
+            .field("pending", &self.pending.len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventStream {
+    /// Connect to the WebSocket endpoint `url` and subscribe with `options`
+    pub async fn connect(url: &Url, options: &SubscribeOptions) -> Result<Self, Error> {
+        let (mut socket, _) = connect_async(url.as_str())
+            .await
+            .map_err(|e| Error::Transport(format!("WebSocket connect failed: {e}")))?;
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "subscribe",
+            "params": options,
+        });
+        socket
+            .send(Message::Text(request.to_string()))
+            .await
+            .map_err(|e| Error::Transport(format!("WebSocket send failed: {e}")))?;
+        Ok(Self {
+            socket,
+            pending: VecDeque::new(),
+        })
+    }
+
+    /// Close the connection
+    pub async fn close(mut self) -> Result<(), Error> {
+        self.socket
+            .close(None)
+            .await
+            .map_err(|e| Error::Transport(format!("WebSocket close failed: {e}")))
+    }
+}
+
+impl Stream for EventStream {
+    type Item = Result<NetworkEvent, Error>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Poll::Ready(Some(Ok(event)));
+            }
+            let frame = match self.socket.poll_next_unpin(cx) {
+                Poll::Pending => return Poll::Pending,
+                Poll::Ready(None | Some(Ok(Message::Close(_)))) => return Poll::Ready(None),
+                Poll::Ready(Some(Err(e))) => {
+                    return Poll::Ready(Some(Err(Error::Transport(format!("WebSocket error: {e}")))))
+                }
+                Poll::Ready(Some(Ok(Message::Text(text)))) => serde_json::from_str::<Value>(&text),
+                Poll::Ready(Some(Ok(Message::Binary(bytes)))) => serde_json::from_slice(&bytes),
+                Poll::Ready(Some(Ok(_))) => continue,
+            };
+            match frame {
+                Ok(frame) => self.pending.extend(parse_events(&frame)),
+                Err(e) => return Poll::Ready(Some(Err(e.into()))),
+            }
+        }
+    }
+}
+
+impl AccumulateClient {
+    /// Subscribe to events over a WebSocket to the client's V3 endpoint
+    ///
+    /// Set [`SubscribeOptions::partition`] or [`SubscribeOptions::account`] to
+    /// narrow the events; use [`EventStream::connect`] when the node serves
+    /// the WebSocket endpoint at a different URL.
+    pub async fn subscribe(&self, options: &SubscribeOptions) -> Result<EventStream, Error> {
+        let url = websocket_url(&self.v3_client.base_url)?;
+        EventStream::connect(&url, options).await
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_block_event() {
+        let frame = json!({
+            "jsonrpc": "2.0",
+            "method": "subscribe",
+            "params": {"result": [{
+                "eventType": "block",
+                "partition": "BVN1",
+                "index": 42,
+                "time": "2024-01-01T00:00:00Z",
+                "entries": [
+                    {
+                        "recordType": "chainEntry",
+                        "account": "acc://alice.acme/tokens",
+                        "name": "main",
+                        "type": "transaction",
+                        "index": 7,
+                        "entry": "ab".repeat(32),
+                        "value": {
+                            "recordType": "message",
+                            "id": format!("acc://{}@alice.acme/tokens", "ab".repeat(32)),
+                            "status": "delivered",
+                            "message": {"type": "transaction", "transaction": {
+                                "header": {"principal": "acc://alice.acme/tokens"},
+                                "body": {"type": "sendTokens", "to": []}
+                            }}
+                        }
+                    },
+                    {"account": "acc://alice.acme/book/1", "name": "signature", "index": 3}
+                ]
+            }]}
+        });
+
+        let events = parse_events(&frame);
+        assert_eq!(events.len(), 4);
+        assert!(
+            matches!(&events[0], NetworkEvent::Block(b) if b.partition == "BVN1" && b.index == 42)
+        );
+        assert_eq!(
+            events[1],
+            NetworkEvent::Account(AccountEvent {
+                account: "acc://alice.acme/tokens".to_string(),
+                chain: "main".to_string(),
+                index: Some(7),
+                entry: Some("ab".repeat(32)),
+                block: 42,
+            })
+        );
+        assert!(matches!(
+            &events[2],
+            NetworkEvent::Transaction(tx) if tx.tx_type == "sendTokens"
+                && tx.status.as_deref() == Some("delivered")
+                && tx.txid.as_deref().unwrap().ends_with("@alice.acme/tokens")
+        ));
+        assert!(matches!(&events[3], NetworkEvent::Account(a) if a.chain == "signature"));
+    }
+
+    #[test]
+    fn test_parse_other_frames() {
+        assert!(parse_events(&json!({"jsonrpc": "2.0", "id": 1, "result": null})).is_empty());
+        assert_eq!(
+            parse_events(&json!({"error": {"code": -32601, "message": "method not found"}})),
+            vec![NetworkEvent::Error("method not found".to_string())]
+        );
+        assert_eq!(
+            parse_events(&json!({"eventType": "error", "err": {"message": "boom"}})),
+            vec![NetworkEvent::Error("boom".to_string())]
+        );
+        assert!(matches!(
+            parse_events(&json!({"eventType": "globals", "values": {"oracle": {"price": 5}}}))[..],
+            [NetworkEvent::Globals(_)]
+        ));
+        assert!(matches!(
+            parse_events(&json!({"eventType": "surprise"}))[..],
+            [NetworkEvent::Other(_)]
+        ));
+    }
+
+    #[test]
+    fn test_websocket_url() {
+        let http = Url::parse("http://localhost:26661/v3").unwrap();
+        assert_eq!(
+            websocket_url(&http).unwrap().as_str(),
+            "ws://localhost:26661/v3"
+        );
+        let https = Url::parse("https://mainnet.accumulatenetwork.io/v3").unwrap();
+        assert_eq!(
+            websocket_url(&https).unwrap().as_str(),
+            "wss://mainnet.accumulatenetwork.io/v3"
+        );
+        assert!(websocket_url(&Url::parse("ftp://example.com").unwrap()).is_err());
+    }
+}
@@ -8,4 +8,7 @@ pub mod signing;
 pub mod signing_test_shims;
 
 pub mod rpc;
-pub mod hashing;
\ No newline at end of file
+pub mod hashing;
+
+#[cfg(feature = "websocket")]
+pub mod events;
\ No newline at end of file
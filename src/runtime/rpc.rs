@@ -53,24 +53,32 @@ impl crate::generated::api_methods::AccumulateRpc for HttpTransport {
         let res = self.client.post(&self.base_url)
             .json(&payload)
             .send().await
-            .map_err(|e| Error::General(format!("Transport error: {}", e)))?;
+            .map_err(|e| {
+                if e.is_timeout() {
+                    Error::Timeout(format!("{method}: {e}"))
+                } else {
+                    Error::Transport(e.to_string())
+                }
+            })?;
 
-        if !res.status().is_success() {
-            return Err(Error::General(format!("HTTP status error: {}", res.status().as_u16())));
-        }
-
-        let v: serde_json::Value = res.json().await
-            .map_err(|e| Error::General(format!("Failed to parse JSON response: {}", e)))?;
+        let status = res.status();
+        let v: serde_json::Value = match res.json().await {
+            Ok(v) => v,
+            Err(_) if !status.is_success() => {
+                return Err(Error::rpc(i32::from(status.as_u16()), format!("HTTP {status}")));
+            }
+            Err(e) => return Err(Error::Encoding(format!("Failed to parse JSON response: {}", e))),
+        };
 
         if let Some(e) = v.get("error") {
             let code = e.get("code").and_then(|c| c.as_i64()).unwrap_or(0) as i32;
             let message = e.get("message").and_then(|m| m.as_str()).unwrap_or("rpc error").to_string();
-            return Err(Error::General(format!("RPC error {}: {}", code, message)));
+            return Err(Error::Rpc { code, message, data: e.get("data").cloned() });
         }
 
-        let result = v.get("result").ok_or_else(|| Error::General("missing result".into()))?;
+        let result = v.get("result").ok_or_else(|| Error::Encoding("missing result".into()))?;
         let typed: TR = serde_json::from_value(result.clone())
-            .map_err(|e| Error::General(format!("Failed to deserialize result: {}", e)))?;
+            .map_err(|e| Error::Encoding(format!("Failed to deserialize result: {}", e)))?;
         Ok(typed)
     }
 }
\ No newline at end of file
@@ -0,0 +1,356 @@
+//! Recurring transactions built from templates
+//!
+//! [`Scheduler::every`] instantiates a [`TxTemplate`] on a fixed interval,
+//! then signs, submits and waits for it with a [`SmartSigner`], which suits
+//! data-oracle publishers writing an entry every few minutes.
+//!
+//! Runs never overlap: while one is in flight, further ticks (including
+//! [`Scheduler::run_once`] calls from other tasks sharing the scheduler) are
+//! reported as [`RunOutcome::Skipped`] instead of signing a second
+//! transaction. Ticks missed during a slow run are dropped, not bunched up.
+//! Each transaction also expires one interval after it is signed (see
+//! [`Scheduler::expire_after`]), so a submission that lingers cannot execute
+//! once the next one is due.
+//!
+//! Besides the caller's arguments, two built-in arguments are available to
+//! templates that use them: `run` (the run number, from 0) and `scheduledAt`
+//! (Unix seconds when the run started).
+
+use crate::generated::header::ExpireOptions;
+use crate::helpers::SmartSigner;
+use crate::templates::{TemplateError, TxTemplate};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::ops::ControlFlow;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+
+/// Delivery checks (2 seconds apart) per run
+const DEFAULT_MAX_ATTEMPTS: u32 = 30;
+
+/// Errors that stop a scheduler before it can run
+#[derive(Error, Debug)]
+pub enum SchedulerError {
+    /// The template cannot be instantiated
+    #[error(transparent)]
+    Template(#[from] TemplateError),
+
+    /// Neither the template nor the scheduler names a principal
+    #[error("Template {0} has no principal")]
+    MissingPrincipal(String),
+}
+
+/// What happened to one scheduled run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// The transaction was delivered
+    Delivered {
+        /// Transaction ID
+        txid: String,
+    },
+    /// Signing, submission or execution failed; the next run goes ahead
+    Failed {
+        /// Error message
+        reason: String,
+    },
+    /// A previous run was still in flight, nothing was signed
+    Skipped,
+}
+
+/// Report for one tick of the scheduler
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RunReport {
+    /// Run number, from 0; skipped ticks do not consume a number
+    pub run: u64,
+    /// Unix seconds when the run started
+    pub scheduled_at: u64,
+    /// Result of the run
+    pub outcome: RunOutcome,
+}
+
+type ArgsFn = Box<dyn Fn(u64) -> BTreeMap<String, Value> + Send + Sync>;
+
+/// Signs and submits a template on a fixed interval
+pub struct Scheduler {
+    template: TxTemplate,
+    interval: Duration,
+    principal: Option<String>,
+    args: Option<ArgsFn>,
+    expire_after: Option<Duration>,
+    max_attempts: u32,
+    in_flight: AtomicBool,
+    next_run: AtomicU64,
+}
+
+impl fmt::Debug for Scheduler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scheduler")
+            .field("template", &self.template.name)
+            .field("interval", &self.interval)
+            .field("principal", &self.principal)
+            .field("expire_after", &self.expire_after)
+            .field("in_flight", &self.in_flight)
+            .field("next_run", &self.next_run)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Scheduler {
+    /// Run `template` every `interval`
+    #[must_use]
+    pub fn every(interval: Duration, template: TxTemplate) -> Self {
+        Self {
+            template,
+            interval,
+            principal: None,
+            args: None,
+            expire_after: Some(interval),
+            max_attempts: DEFAULT_MAX_ATTEMPTS,
+            in_flight: AtomicBool::new(false),
+            next_run: AtomicU64::new(0),
+        }
+    }
+
+    /// Principal to use when the template does not name one
+    #[must_use]
+    pub fn with_principal(mut self, principal: impl Into<String>) -> Self {
+        self.principal = Some(principal.into());
+        self
+    }
+
+    /// Compute the template arguments for each run from its run number
+    #[must_use]
+    pub fn with_args(
+        mut self,
+        args: impl Fn(u64) -> BTreeMap<String, Value> + Send + Sync + 'static,
+    ) -> Self {
+        self.args = Some(Box::new(args));
+        self
+    }
+
+    /// How long after signing each transaction expires
+    ///
+    /// Defaults to the interval; `None` leaves expiry to the template.
+    /// Ignored when the template sets its own expiry.
+    #[must_use]
+    pub const fn expire_after(mut self, expire_after: Option<Duration>) -> Self {
+        self.expire_after = expire_after;
+        self
+    }
+
+    /// Number of 2-second delivery checks before a run is reported failed
+    #[must_use]
+    pub const fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// Whether a run is currently in flight
+    pub fn is_running(&self) -> bool {
+        self.in_flight.load(Ordering::Acquire)
+    }
+
+    /// Run the template once, now, unless a run is already in flight
+    ///
+    /// Template errors abort; network and execution failures are reported
+    /// in the [`RunReport`].
+    pub async fn run_once(
+        &self,
+        signer: &mut SmartSigner<'_>,
+    ) -> Result<RunReport, SchedulerError> {
+        let scheduled_at = unix_now();
+        if self
+            .in_flight
+            .compare_exchange(false, true, Ordering::AcqRel, Ordering::Acquire)
+            .is_err()
+        {
+            return Ok(RunReport {
+                run: self.next_run.load(Ordering::Acquire),
+                scheduled_at,
+                outcome: RunOutcome::Skipped,
+            });
+        }
+        // Cleared on drop, so a cancelled run does not block later ones
+        let _guard = InFlight(&self.in_flight);
+        let run = self.next_run.fetch_add(1, Ordering::AcqRel);
+        let result = self.execute(signer, run, scheduled_at).await;
+
+        Ok(RunReport {
+            run,
+            scheduled_at,
+            outcome: result?,
+        })
+    }
+
+    /// Run on schedule until `on_run` breaks
+    ///
+    /// The first run starts immediately. Template errors stop the loop.
+    pub async fn run<F>(
+        &self,
+        signer: &mut SmartSigner<'_>,
+        mut on_run: F,
+    ) -> Result<(), SchedulerError>
+    where
+        F: FnMut(&RunReport) -> ControlFlow<()>,
+    {
+        let mut ticks = tokio::time::interval(self.interval);
+        ticks.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Skip);
+        loop {
+            ticks.tick().await;
+            let report = self.run_once(signer).await?;
+            if on_run(&report).is_break() {
+                return Ok(());
+            }
+        }
+    }
+
+    async fn execute(
+        &self,
+        signer: &mut SmartSigner<'_>,
+        run: u64,
+        scheduled_at: u64,
+    ) -> Result<RunOutcome, SchedulerError> {
+        let placeholders = self.template.placeholders()?;
+        let mut args = self.args.as_ref().map(|f| f(run)).unwrap_or_default();
+        for (name, value) in [("run", run), ("scheduledAt", scheduled_at)] {
+            if placeholders.contains(name) {
+                args.entry(name.to_string()).or_insert_with(|| value.into());
+            }
+        }
+
+        let mut instance = self.template.instantiate(&args)?;
+        let principal = instance
+            .principal
+            .or_else(|| self.principal.clone())
+            .ok_or_else(|| SchedulerError::MissingPrincipal(self.template.name.clone()))?;
+        if instance.options.expire.is_none() {
+            instance.options.expire = self.expire_after.map(|after| ExpireOptions {
+                at_time: Some(scheduled_at + after.as_secs().max(1)),
+            });
+        }
+
+        let result = signer
+            .sign_submit_and_wait_with_options(
+                &principal,
+                &instance.body,
+                &instance.options,
+                self.max_attempts,
+            )
+            .await;
+        Ok(match (result.success, result.txid) {
+            (true, Some(txid)) => RunOutcome::Delivered { txid },
+            _ => RunOutcome::Failed {
+                reason: result.error.unwrap_or_else(|| "Unknown error".to_string()),
+            },
+        })
+    }
+}
+
+struct InFlight<'a>(&'a AtomicBool);
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.store(false, Ordering::Release);
+    }
+}
+
+fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.as_secs())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::client::AccumulateClient;
+    use ed25519_dalek::SigningKey;
+    use serde_json::json;
+
+    async fn client() -> AccumulateClient {
+        AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap()
+    }
+
+    fn template() -> TxTemplate {
+        TxTemplate::new(
+            "reading",
+            json!({"type": "writeData", "entry": {"type": "doublehash", "data": ["{{value}}"]}}),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_overlapping_run_is_skipped() {
+        let client = client().await;
+        let mut signer = SmartSigner::new(
+            &client,
+            SigningKey::from_bytes(&[7u8; 32]),
+            "acc://oracle.acme/book/1",
+        );
+        let scheduler = Scheduler::every(Duration::from_secs(60), template())
+            .with_principal("acc://oracle.acme/data")
+            .with_args(|run| BTreeMap::from([("value".to_string(), json!(format!("{run:02x}")))]));
+
+        scheduler.in_flight.store(true, Ordering::Release);
+        let report = scheduler.run_once(&mut signer).await.unwrap();
+        assert_eq!(report.outcome, RunOutcome::Skipped);
+        assert_eq!(report.run, 0);
+        assert!(scheduler.is_running());
+    }
+
+    #[tokio::test]
+    async fn test_run_reports_failure_and_releases_guard() {
+        let client = client().await;
+        let mut signer = SmartSigner::new(
+            &client,
+            SigningKey::from_bytes(&[7u8; 32]),
+            "acc://oracle.acme/book/1",
+        );
+        let scheduler = Scheduler::every(Duration::from_secs(60), template())
+            .with_principal("acc://oracle.acme/data")
+            .with_args(|run| BTreeMap::from([("value".to_string(), json!(format!("{run:02x}")))]));
+
+        // Nothing listens on the test endpoints, so the run fails
+        let report = scheduler.run_once(&mut signer).await.unwrap();
+        assert!(matches!(report.outcome, RunOutcome::Failed { .. }));
+        assert!(!scheduler.is_running());
+        assert_eq!(scheduler.run_once(&mut signer).await.unwrap().run, 1);
+    }
+
+    #[tokio::test]
+    async fn test_template_errors_stop_the_scheduler() {
+        let client = client().await;
+        let mut signer = SmartSigner::new(
+            &client,
+            SigningKey::from_bytes(&[7u8; 32]),
+            "acc://oracle.acme/book/1",
+        );
+
+        let no_principal = Scheduler::every(Duration::from_secs(1), template())
+            .with_args(|_| BTreeMap::from([("value".to_string(), json!("00"))]));
+        assert!(matches!(
+            no_principal
+                .run(&mut signer, |_| ControlFlow::Continue(()))
+                .await,
+            Err(SchedulerError::MissingPrincipal(_))
+        ));
+
+        let no_args = Scheduler::every(Duration::from_secs(1), template())
+            .with_principal("acc://oracle.acme/data");
+        assert!(matches!(
+            no_args.run_once(&mut signer).await,
+            Err(SchedulerError::Template(
+                TemplateError::MissingArgument { .. }
+            ))
+        ));
+        assert!(!no_args.is_running());
+    }
+}
@@ -0,0 +1,233 @@
+//! JSON Schema and OpenRPC export of the generated protocol types
+//!
+//! Schemas are derived from the same generated Rust types the SDK serializes,
+//! so they describe exactly what goes over the wire: hex-encoded bytes are
+//! strings, transaction bodies and signatures are tagged by `type`.
+//! [`openrpc_document`] describes every V2 API method, with the params and
+//! result types under `components/schemas`.
+//!
+//! The `export_schema` binary writes all documents to a directory:
+//!
+//! ```text
+//! cargo run --features json-schema --bin export_schema -- schema/
+//! ```
+
+use crate::generated::api_methods::method_schemas;
+use crate::generated::header::TransactionHeader;
+use crate::generated::signatures::Signature;
+use crate::generated::transactions::TransactionBody;
+use schemars::gen::{SchemaGenerator, SchemaSettings};
+use schemars::schema::RootSchema;
+use schemars::JsonSchema;
+use serde_json::{json, Map, Value};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// `OpenRPC` specification version of [`openrpc_document`]
+pub const OPENRPC_VERSION: &str = "1.2.6";
+
+const COMPONENTS_PATH: &str = "#/components/schemas/";
+
+/// JSON Schema (draft 7) for any type deriving `JsonSchema`
+#[must_use]
+pub fn schema_of<T: JsonSchema>() -> RootSchema {
+    SchemaGenerator::new(SchemaSettings::draft07()).into_root_schema_for::<T>()
+}
+
+/// JSON Schema for [`TransactionBody`]
+#[must_use]
+pub fn transaction_body_schema() -> RootSchema {
+    schema_of::<TransactionBody>()
+}
+
+/// JSON Schema for [`TransactionHeader`]
+#[must_use]
+pub fn transaction_header_schema() -> RootSchema {
+    schema_of::<TransactionHeader>()
+}
+
+/// JSON Schema for [`Signature`]
+#[must_use]
+pub fn signature_schema() -> RootSchema {
+    schema_of::<Signature>()
+}
+
+/// `OpenRPC` document for the V2 JSON-RPC API
+///
+/// Each method's params are listed by name (the API takes a single params
+/// object); their schemas and the result schemas reference
+/// `#/components/schemas`.
+#[must_use]
+pub fn openrpc_document() -> Value {
+    let mut settings = SchemaSettings::draft07();
+    settings.definitions_path = COMPONENTS_PATH.to_string();
+    let mut gen = SchemaGenerator::new(settings);
+    let methods = method_schemas(&mut gen);
+    let schemas = serde_json::to_value(gen.definitions()).unwrap_or_default();
+
+    let methods: Vec<Value> = methods
+        .into_iter()
+        .map(|method| {
+            let params = serde_json::to_value(&method.params).unwrap_or_default();
+            let result = serde_json::to_value(&method.result).unwrap_or_default();
+            json!({
+                "name": method.name,
+                "description": method.description,
+                "paramStructure": "by-name",
+                "params": content_descriptors(&params, &schemas),
+                "result": {"name": "result", "schema": result},
+            })
+        })
+        .collect();
+
+    json!({
+        "openrpc": OPENRPC_VERSION,
+        "info": {
+            "title": "Accumulate JSON-RPC API",
+            "version": env!("CARGO_PKG_VERSION"),
+        },
+        "methods": methods,
+        "components": {"schemas": schemas},
+    })
+}
+
+/// Write every schema document into `dir`, returning the files written
+///
+/// Produces `transaction-body.schema.json`, `transaction-header.schema.json`,
+/// `signature.schema.json` and `openrpc.json`.
+pub fn export_all(dir: impl AsRef<Path>) -> io::Result<Vec<PathBuf>> {
+    let dir = dir.as_ref();
+    fs::create_dir_all(dir)?;
+    let documents = [
+        (
+            "transaction-body.schema.json",
+            serde_json::to_value(transaction_body_schema()),
+        ),
+        (
+            "transaction-header.schema.json",
+            serde_json::to_value(transaction_header_schema()),
+        ),
+        (
+            "signature.schema.json",
+            serde_json::to_value(signature_schema()),
+        ),
+        ("openrpc.json", Ok(openrpc_document())),
+    ];
+
+    let mut written = Vec::with_capacity(documents.len());
+    for (name, document) in documents {
+        let path = dir.join(name);
+        let mut text = serde_json::to_string_pretty(&document?)?;
+        text.push('\n');
+        fs::write(&path, text)?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Expand a params schema into one `OpenRPC` content descriptor per property
+fn content_descriptors(params: &Value, schemas: &Value) -> Vec<Value> {
+    let resolved = params
+        .get("$ref")
+        .and_then(Value::as_str)
+        .and_then(|r| r.strip_prefix(COMPONENTS_PATH))
+        .and_then(|name| schemas.get(name))
+        .unwrap_or(params);
+
+    let Some(properties) = resolved.get("properties").and_then(Value::as_object) else {
+        return Vec::new();
+    };
+    let required: Vec<&str> = resolved
+        .get("required")
+        .and_then(Value::as_array)
+        .map(|names| names.iter().filter_map(Value::as_str).collect())
+        .unwrap_or_default();
+
+    properties
+        .iter()
+        .map(|(name, schema)| {
+            let mut descriptor = Map::new();
+            descriptor.insert("name".to_string(), json!(name));
+            descriptor.insert(
+                "required".to_string(),
+                json!(required.contains(&name.as_str())),
+            );
+            descriptor.insert("schema".to_string(), schema.clone());
+            Value::Object(descriptor)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn one_of_tags(schema: &RootSchema) -> Vec<String> {
+        let value = serde_json::to_value(schema).unwrap();
+        value["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter_map(|variant| {
+                variant["properties"]["type"]["enum"][0]
+                    .as_str()
+                    .map(str::to_string)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_transaction_body_schema_is_tagged() {
+        let tags = one_of_tags(&transaction_body_schema());
+        assert!(tags.contains(&"sendTokens".to_string()));
+        assert!(tags.contains(&"writeData".to_string()));
+
+        let value = serde_json::to_value(transaction_header_schema()).unwrap();
+        assert_eq!(value["properties"]["initiator"]["type"], "string");
+    }
+
+    #[test]
+    fn test_signature_schema_hex_fields_are_strings() {
+        let schema = signature_schema();
+        assert!(one_of_tags(&schema).contains(&"ed25519".to_string()));
+
+        let value = serde_json::to_value(&schema).unwrap();
+        let ed25519 = value["oneOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|variant| variant["properties"]["type"]["enum"][0] == "ed25519")
+            .unwrap();
+        assert_eq!(ed25519["properties"]["PublicKey"]["type"], "string");
+        assert_eq!(
+            ed25519["properties"]["TransactionHash"]["type"],
+            serde_json::json!(["string", "null"])
+        );
+    }
+
+    #[test]
+    fn test_openrpc_document_references_components() {
+        let doc = openrpc_document();
+        let methods = doc["methods"].as_array().unwrap();
+        let query = methods.iter().find(|m| m["name"] == "query").unwrap();
+        assert_eq!(query["paramStructure"], "by-name");
+
+        let result_ref = query["result"]["schema"]["$ref"].as_str().unwrap();
+        let name = result_ref.strip_prefix(COMPONENTS_PATH).unwrap();
+        assert!(doc["components"]["schemas"].get(name).is_some());
+    }
+
+    #[test]
+    fn test_export_all_writes_documents() {
+        let dir = std::env::temp_dir().join(format!("acc-schema-{}", std::process::id()));
+        let written = export_all(&dir).unwrap();
+        assert_eq!(written.len(), 4);
+        for path in &written {
+            let text = fs::read_to_string(path).unwrap();
+            serde_json::from_str::<Value>(&text).unwrap();
+        }
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
@@ -0,0 +1,1759 @@
+//! In-memory network for testing flows without a node
+//!
+//! [`SimulatedNetwork`] keeps accounts, token balances, credits and key pages
+//! in memory and executes submitted envelopes against them with a small
+//! subset of the network's rules:
+//!
+//! - every signature must verify, and its key must belong to the signing lite
+//!   identity or sit on the signing key page at the stated version;
+//! - the signer's authority must govern the principal (see [`AccountAuth`]);
+//! - the first signer pays a flat fee in credits when the transaction is
+//!   first submitted, and a key page holds the transaction in its
+//!   principal's pending set until its accept threshold is met;
+//! - `addCredits`, `createIdentity`, `createTokenAccount`, `createDataAccount`,
+//!   `createToken`, `sendTokens`, `burnTokens`, `writeData`, `createKeyPage`
+//!   and `updateKeyPage` (add, remove, update and set threshold) execute;
+//!   any other body fails.
+//!
+//! It answers the V3 methods the helpers use (`query` by default, pending,
+//! directory, data entries, main chain entries and public key hash search, `submit`, `faucet` and
+//! `network-status`) in process as an [`AccumulateRpc`] transport, or over
+//! HTTP on a loopback port with [`SimulatedNetwork::serve`], so
+//! [`QuickStart`](crate::helpers::QuickStart) and
+//! [`SmartSigner`](crate::helpers::SmartSigner) run against it unchanged and
+//! a whole ADI setup takes milliseconds:
+//!
+//! ```
+//! use accumulate_client::helpers::QuickStart;
+//! use accumulate_client::simulator::SimulatedNetwork;
+//! use std::sync::Arc;
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let network = SimulatedNetwork::new();
+//! let quick = QuickStart::from_client(Arc::new(network.client().await?));
+//!
+//! let wallet = quick.create_wallet();
+//! network.fund(&wallet.lite_token_account, 100 * accumulate_client::ACME_BASE_UNITS)?;
+//! let adi = quick.setup_adi(&wallet, "alice").await?;
+//! assert!(network.account(&adi.key_page_url).is_some());
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Only ED25519 signatures and the ACME token are modeled; `createToken`
+//! records an issuer, but no account can hold its tokens. There are no
+//! blocks, receipts or signature timestamp checks, and deposits are credited
+//! directly; a delivered `sendTokens` only reports the IDs of the synthetic
+//! deposits it would have produced.
+
+use crate::amounts::{ACME_BASE_UNITS, ACME_PRECISION};
+use crate::auth::AccountAuth;
+use crate::client::AccumulateClient;
+use crate::codec::canonical_json;
+use crate::codec::signing::{compute_data_entry_hash, sha256_bytes};
+use crate::generated::api_methods::AccumulateRpc;
+use crate::helpers::{derive_lite_identity_url, KeyPageState};
+use crate::json_rpc_client::JsonRpcError;
+use crate::multisig::{MultisigError, PartialEnvelope};
+use crate::AccOptions;
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use url::Url;
+
+/// ACME base units a faucet request credits (10 ACME)
+pub const FAUCET_AMOUNT: u64 = 10 * ACME_BASE_UNITS;
+
+/// Oracle price a new network starts with ($0.50 per ACME)
+pub const DEFAULT_ORACLE: u64 = 5000;
+
+/// The only token the simulator knows
+const ACME: &str = "acc://ACME";
+
+/// Why the simulator refused a call
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum SimulatorError {
+    /// Malformed parameters, envelope or signature
+    #[error("{0}")]
+    BadRequest(String),
+
+    /// A signer that may not sign for the principal
+    #[error("{0}")]
+    Unauthorized(String),
+
+    /// Not enough credits to pay the fee
+    #[error("{0}")]
+    InsufficientCredits(String),
+
+    /// An account or transaction that does not exist
+    #[error("{0}")]
+    NotFound(String),
+
+    /// A transaction that has already executed, or a repeated signature
+    #[error("{0}")]
+    Conflict(String),
+
+    /// A method the simulator does not answer
+    #[error("Method not found: {0}")]
+    UnknownMethod(String),
+}
+
+impl SimulatorError {
+    /// JSON-RPC error code, an HTTP status for everything but unknown methods
+    #[must_use]
+    pub const fn code(&self) -> i32 {
+        match self {
+            Self::BadRequest(_) => 400,
+            Self::Unauthorized(_) => 401,
+            Self::InsufficientCredits(_) => 402,
+            Self::NotFound(_) => 404,
+            Self::Conflict(_) => 409,
+            Self::UnknownMethod(_) => -32601,
+        }
+    }
+}
+
+impl From<SimulatorError> for crate::errors::Error {
+    fn from(e: SimulatorError) -> Self {
+        Self::rpc(e.code(), e.to_string())
+    }
+}
+
+impl From<MultisigError> for SimulatorError {
+    fn from(e: MultisigError) -> Self {
+        match e {
+            MultisigError::InvalidSignature { .. } => Self::Unauthorized(e.to_string()),
+            MultisigError::AlreadySigned(_) => Self::Conflict(e.to_string()),
+            _ => Self::BadRequest(e.to_string()),
+        }
+    }
+}
+
+/// A local network with accounts, balances, credits and key pages
+///
+/// Clones share the same state, so a test can keep one handle for
+/// assertions while a served copy answers the client.
+#[derive(Debug, Clone)]
+pub struct SimulatedNetwork {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    oracle: u64,
+    /// Faucet requests so far, to give each one its own transaction
+    faucet_requests: u64,
+    /// Accounts by normalized URL
+    accounts: BTreeMap<String, Account>,
+    /// Transactions by hash (hex)
+    transactions: BTreeMap<String, Transaction>,
+    /// Transaction hash of each signature, by signature hash (hex)
+    signatures: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Clone)]
+struct Account {
+    url: String,
+    kind: Kind,
+    /// Hashes of the transactions delivered to the account, in order
+    main_chain: Vec<String>,
+}
+
+#[derive(Debug, Clone)]
+enum Kind {
+    LiteIdentity {
+        credits: u64,
+    },
+    LiteTokenAccount {
+        balance: u128,
+    },
+    Identity {
+        authority: String,
+    },
+    KeyBook {
+        authority: String,
+        pages: u64,
+    },
+    KeyPage {
+        book: String,
+        version: u64,
+        credits: u64,
+        threshold: u64,
+        keys: Vec<String>,
+    },
+    TokenAccount {
+        authority: String,
+        balance: u128,
+    },
+    DataAccount {
+        authority: String,
+        /// Entry hash and transaction hash of each entry written, in order
+        entries: Vec<(String, String)>,
+    },
+    TokenIssuer {
+        authority: String,
+        symbol: String,
+        precision: u64,
+        supply_limit: Option<u128>,
+    },
+}
+
+#[derive(Debug)]
+struct Transaction {
+    principal: String,
+    envelope: PartialEnvelope,
+    status: Status,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Status {
+    Pending,
+    Delivered,
+    Failed(String),
+}
+
+impl Default for SimulatedNetwork {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SimulatedNetwork {
+    /// An empty network at the [`DEFAULT_ORACLE`] price
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                oracle: DEFAULT_ORACLE,
+                faucet_requests: 0,
+                accounts: BTreeMap::from([(
+                    normalize(ACME),
+                    Account::new(
+                        ACME,
+                        Kind::TokenIssuer {
+                            authority: "acc://dn.acme/operators".to_string(),
+                            symbol: "ACME".to_string(),
+                            precision: u64::from(ACME_PRECISION),
+                            supply_limit: None,
+                        },
+                    ),
+                )]),
+                transactions: BTreeMap::new(),
+                signatures: BTreeMap::new(),
+            })),
+        }
+    }
+
+    /// Set the oracle price reported by `network-status`
+    #[must_use]
+    pub fn with_oracle(self, price: u64) -> Self {
+        self.lock().oracle = price;
+        self
+    }
+
+    /// Move the oracle price of a running network, as a new oracle entry
+    /// would
+    pub fn set_oracle(&self, price: u64) {
+        self.lock().oracle = price;
+    }
+
+    /// Credit `amount` base units to a lite ACME account, creating it and
+    /// its lite identity when missing
+    ///
+    /// Works like a faucet request of any size; returns the transaction ID.
+    pub fn fund(&self, lite_token_account: &str, amount: u64) -> Result<String, SimulatorError> {
+        self.lock().fund(lite_token_account, amount)
+    }
+
+    /// The account record `query` returns for `url`, if the account exists
+    #[must_use]
+    pub fn account(&self, url: &str) -> Option<Value> {
+        self.lock()
+            .accounts
+            .get(&normalize(url))
+            .map(Account::record)
+    }
+
+    /// Answer a JSON-RPC call
+    pub fn call(&self, method: &str, params: &Value) -> Result<Value, SimulatorError> {
+        let mut state = self.lock();
+        match method {
+            "query" => state.query(params),
+            "submit" | "execute" => state.submit(params.get("envelope").unwrap_or(params)),
+            "faucet" => {
+                let account = params
+                    .get("account")
+                    .or_else(|| params.get("url"))
+                    .and_then(Value::as_str)
+                    .ok_or_else(|| SimulatorError::BadRequest("missing account".to_string()))?;
+                let txid = state.fund(account, FAUCET_AMOUNT)?;
+                Ok(
+                    json!({"status": {"txID": txid, "delivered": true, "code": "delivered"}, "success": true}),
+                )
+            }
+            "network-status" => Ok(json!({"oracle": {"price": state.oracle}})),
+            _ => Err(SimulatorError::UnknownMethod(method.to_string())),
+        }
+    }
+
+    /// Answer JSON-RPC over HTTP on a loopback port until the runtime shuts down
+    ///
+    /// Returns the base URL; the request path is ignored, so it serves both
+    /// the V2 and the V3 endpoint.
+    pub async fn serve(&self) -> std::io::Result<Url> {
+        serve_json_rpc(self.clone()).await
+    }
+
+    /// [`serve`](Self::serve) and connect a client polling every millisecond
+    ///
+    /// Transactions execute when they are submitted, so the wait helpers
+    /// see the outcome on their first poll.
+    pub async fn client(&self) -> Result<AccumulateClient, JsonRpcError> {
+        loopback_client(self.serve().await).await
+    }
+
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[async_trait]
+impl AccumulateRpc for SimulatedNetwork {
+    async fn rpc_call<TParams: Serialize + Send + Sync, TResult: for<'de> Deserialize<'de>>(
+        &self,
+        method: &str,
+        params: &TParams,
+    ) -> Result<TResult, crate::errors::Error> {
+        let params = serde_json::to_value(params)?;
+        let result = self.call(method, &params)?;
+        Ok(serde_json::from_value(result)?)
+    }
+}
+
+#[async_trait]
+impl RpcHandler for SimulatedNetwork {
+    async fn handle(
+        &self,
+        _path: &str,
+        method: &str,
+        params: Value,
+    ) -> Result<Value, (i32, String)> {
+        self.call(method, &params)
+            .map_err(|e| (e.code(), e.to_string()))
+    }
+}
+
+/// Answers JSON-RPC calls for [`serve_json_rpc`]
+#[async_trait]
+pub(crate) trait RpcHandler: Clone + Send + Sync + 'static {
+    /// The result of `method`, or an error code and message; `path` is the
+    /// request path, e.g. `/v3`
+    async fn handle(&self, path: &str, method: &str, params: Value)
+        -> Result<Value, (i32, String)>;
+}
+
+/// Answer JSON-RPC over HTTP on a loopback port with `handler` until the
+/// runtime shuts down; returns the base URL
+pub(crate) async fn serve_json_rpc<H: RpcHandler>(handler: H) -> std::io::Result<Url> {
+    let listener = TcpListener::bind(("127.0.0.1", 0)).await?;
+    let addr = listener.local_addr()?;
+    tokio::spawn(async move {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(connection(handler.clone(), stream));
+        }
+    });
+    Url::parse(&format!("http://{addr}/"))
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))
+}
+
+/// A client for the V2 and V3 endpoints under a loopback `base`, polling
+/// every millisecond
+pub(crate) async fn loopback_client(
+    base: std::io::Result<Url>,
+) -> Result<AccumulateClient, JsonRpcError> {
+    let base = base.map_err(|e| JsonRpcError::General(e.to_string()))?;
+    let endpoint = |path: &str| {
+        base.join(path)
+            .map_err(|e| JsonRpcError::General(e.to_string()))
+    };
+    let options = AccOptions {
+        poll_interval: Duration::from_millis(1),
+        ..AccOptions::default()
+    };
+    AccumulateClient::new_with_options(endpoint("v2")?, endpoint("v3")?, options).await
+}
+
+/// Serve HTTP/1.1 requests on one keep-alive connection
+async fn connection<H: RpcHandler>(handler: H, stream: TcpStream) {
+    let mut stream = BufReader::new(stream);
+    let mut line = String::new();
+    loop {
+        let mut length = 0;
+        let mut path = String::new();
+        loop {
+            line.clear();
+            match stream.read_line(&mut line).await {
+                Ok(0) | Err(_) => return,
+                Ok(_) => {}
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if path.is_empty() {
+                path = header.split(' ').nth(1).unwrap_or("/").to_string();
+            } else if let Some((name, value)) = header.split_once(':') {
+                if name.eq_ignore_ascii_case("content-length") {
+                    length = value.trim().parse().unwrap_or(0);
+                }
+            }
+        }
+        let mut body = vec![0; length];
+        if stream.read_exact(&mut body).await.is_err() {
+            return;
+        }
+        let reply = reply(&handler, &path, &body).await.to_string();
+        let response = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\n\r\n{reply}",
+            reply.len()
+        );
+        if stream
+            .get_mut()
+            .write_all(response.as_bytes())
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// The JSON-RPC response to a request body
+async fn reply<H: RpcHandler>(handler: &H, path: &str, body: &[u8]) -> Value {
+    let request: Value = match serde_json::from_slice(body) {
+        Ok(request) => request,
+        Err(e) => {
+            return json!({"jsonrpc": "2.0", "id": null, "error": {"code": -32700, "message": e.to_string()}})
+        }
+    };
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let method = request.get("method").and_then(Value::as_str).unwrap_or("");
+    let params = request.get("params").cloned().unwrap_or(Value::Null);
+    match handler.handle(path, method, params).await {
+        Ok(result) => json!({"jsonrpc": "2.0", "id": id, "result": result}),
+        Err((code, message)) => {
+            json!({"jsonrpc": "2.0", "id": id, "error": {"code": code, "message": message}})
+        }
+    }
+}
+
+impl State {
+    fn fund(&mut self, lite_token_account: &str, amount: u64) -> Result<String, SimulatorError> {
+        let url = normalize(lite_token_account);
+        let identity = lite_identity_of(&url).ok_or_else(|| {
+            SimulatorError::BadRequest(format!("{lite_token_account} is not a lite ACME account"))
+        })?;
+        self.faucet_requests += 1;
+        let transaction = json!({
+            "header": {
+                "principal": "acc://faucet",
+                "initiator": hex::encode(sha256_bytes(&self.faucet_requests.to_be_bytes())),
+            },
+            "body": {"type": "sendTokens", "to": [{"url": lite_token_account, "amount": amount.to_string()}]},
+        });
+        let envelope = PartialEnvelope::new(transaction)?;
+        let hash = hex::encode(envelope.transaction_hash());
+
+        self.accounts
+            .entry(identity.clone())
+            .or_insert_with(|| Account::new(&identity, Kind::LiteIdentity { credits: 0 }));
+        let account = self.accounts.entry(url).or_insert_with(|| {
+            Account::new(lite_token_account, Kind::LiteTokenAccount { balance: 0 })
+        });
+        match &mut account.kind {
+            Kind::LiteTokenAccount { balance } => *balance += u128::from(amount),
+            _ => {
+                return Err(SimulatorError::Conflict(format!(
+                    "{lite_token_account} is not a lite token account"
+                )))
+            }
+        }
+
+        self.transactions.insert(
+            hash.clone(),
+            Transaction {
+                principal: "acc://faucet".to_string(),
+                envelope,
+                status: Status::Delivered,
+            },
+        );
+        Ok(txid(&hash, "acc://faucet"))
+    }
+
+    fn query(&self, params: &Value) -> Result<Value, SimulatorError> {
+        let scope = params
+            .get("scope")
+            .and_then(Value::as_str)
+            .ok_or_else(|| SimulatorError::BadRequest("missing scope".to_string()))?;
+        let query_type = params
+            .pointer("/query/queryType")
+            .and_then(Value::as_str)
+            .unwrap_or("default");
+
+        if let Some(hash) = message_hash(scope) {
+            return self.query_message(&hash);
+        }
+        let url = normalize(scope);
+        let account = self
+            .accounts
+            .get(&url)
+            .ok_or_else(|| SimulatorError::NotFound(format!("{scope} not found")))?;
+        match query_type {
+            "default" => Ok(json!({"recordType": "account", "account": account.record()})),
+            "pending" => {
+                let records: Vec<Value> = self
+                    .transactions
+                    .iter()
+                    .filter(|(_, tx)| tx.status == Status::Pending && normalize(&tx.principal) == url)
+                    .map(|(hash, tx)| {
+                        json!({
+                            "recordType": "txID",
+                            "value": txid(hash, &tx.principal),
+                            "message": {"type": "transaction", "transaction": tx.envelope.transaction()},
+                        })
+                    })
+                    .collect();
+                Ok(json!({"recordType": "range", "total": records.len(), "records": records}))
+            }
+            "directory" => {
+                let expand = params
+                    .pointer("/query/range/expand")
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false);
+                let prefix = format!("{url}/");
+                let records: Vec<Value> = self
+                    .accounts
+                    .iter()
+                    .filter(|(key, _)| {
+                        key.strip_prefix(&prefix)
+                            .is_some_and(|rest| !rest.contains('/'))
+                    })
+                    .map(|(_, account)| {
+                        if expand {
+                            json!({"recordType": "account", "account": account.record()})
+                        } else {
+                            json!({"recordType": "url", "value": &account.url})
+                        }
+                    })
+                    .collect();
+                let total = records.len();
+                let start = params
+                    .pointer("/query/range/start")
+                    .and_then(Value::as_u64)
+                    .map_or(0, |start| usize::try_from(start).unwrap_or(usize::MAX).min(total));
+                let count = params
+                    .pointer("/query/range/count")
+                    .and_then(Value::as_u64)
+                    .map_or(usize::MAX, |count| usize::try_from(count).unwrap_or(usize::MAX));
+                let records: Vec<Value> = records.into_iter().skip(start).take(count).collect();
+                Ok(json!({"recordType": "range", "start": start, "total": total, "records": records}))
+            }
+            "publicKeyHashSearch" => {
+                let hash = params
+                    .pointer("/query/publicKeyHash")
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+                    .to_ascii_lowercase();
+                let records: Vec<Value> = self
+                    .accounts
+                    .values()
+                    .filter_map(|page| match &page.kind {
+                        Kind::KeyPage {
+                            book,
+                            version,
+                            keys,
+                            ..
+                        } if normalize(book) == url => {
+                            let index = keys.iter().position(|key| *key == hash)?;
+                            Some(json!({
+                                "recordType": "key",
+                                "authority": book,
+                                "signer": &page.url,
+                                "version": version,
+                                "index": index,
+                                "entry": {"publicKeyHash": hash},
+                            }))
+                        }
+                        _ => None,
+                    })
+                    .collect();
+                Ok(json!({"recordType": "range", "total": records.len(), "records": records}))
+            }
+            "data" => self.query_data_entry(account, params),
+            "chain" => Self::query_main_chain(account, params),
+            other => Err(SimulatorError::BadRequest(format!(
+                "query type {other} is not supported"
+            ))),
+        }
+    }
+
+    /// A data entry of `account` by entry hash or index, else the latest
+    /// one; a page of entries for a ranged query
+    fn query_data_entry(&self, account: &Account, params: &Value) -> Result<Value, SimulatorError> {
+        let Kind::DataAccount { entries, .. } = &account.kind else {
+            return Err(SimulatorError::BadRequest(format!(
+                "{} is not a data account",
+                account.url
+            )));
+        };
+        let entry_record = |index: usize| -> Result<Value, SimulatorError> {
+            let (entry, hash) = &entries[index];
+            Ok(json!({
+                "recordType": "chainEntry",
+                "account": &account.url,
+                "name": "data",
+                "type": "transaction",
+                "index": index,
+                "entry": entry,
+                "value": self.query_message(hash)?,
+            }))
+        };
+        if let Some(range) = params.pointer("/query/range") {
+            let start = range.get("start").and_then(Value::as_u64).unwrap_or(0);
+            let start = usize::try_from(start).unwrap_or(usize::MAX).min(entries.len());
+            let count = range.get("count").and_then(Value::as_u64).unwrap_or(u64::MAX);
+            let end = start.saturating_add(usize::try_from(count).unwrap_or(usize::MAX)).min(entries.len());
+            let records = (start..end).map(entry_record).collect::<Result<Vec<_>, _>>()?;
+            return Ok(json!({"recordType": "range", "start": start, "total": entries.len(), "records": records}));
+        }
+        let found = match (
+            params.pointer("/query/entry").and_then(Value::as_str),
+            params.pointer("/query/index").and_then(Value::as_u64),
+        ) {
+            (Some(entry), _) => entries
+                .iter()
+                .position(|(hash, _)| hash.eq_ignore_ascii_case(entry)),
+            (None, Some(index)) => usize::try_from(index)
+                .ok()
+                .filter(|i| *i < entries.len()),
+            (None, None) => entries.len().checked_sub(1),
+        };
+        let index = found.ok_or_else(|| {
+            SimulatorError::NotFound(format!("data entry of {} not found", account.url))
+        })?;
+        entry_record(index)
+    }
+
+    /// An entry of the main chain of `account` by entry hash
+    fn query_main_chain(account: &Account, params: &Value) -> Result<Value, SimulatorError> {
+        let name = params.pointer("/query/name").and_then(Value::as_str);
+        let entry = params.pointer("/query/entry").and_then(Value::as_str);
+        let (Some("main"), Some(entry)) = (name, entry) else {
+            return Err(SimulatorError::BadRequest(
+                "only main chain entries can be queried".to_string(),
+            ));
+        };
+        let index = account
+            .main_chain
+            .iter()
+            .position(|hash| hash.eq_ignore_ascii_case(entry))
+            .ok_or_else(|| {
+                SimulatorError::NotFound(format!("{entry} is not on the main chain of {}", account.url))
+            })?;
+        Ok(json!({
+            "recordType": "chainEntry",
+            "account": &account.url,
+            "name": "main",
+            "type": "transaction",
+            "index": index,
+            "entry": &account.main_chain[index],
+        }))
+    }
+
+    fn query_message(&self, hash: &str) -> Result<Value, SimulatorError> {
+        if let Some(tx) = self.transactions.get(hash) {
+            let mut record = json!({
+                "recordType": "message",
+                "id": txid(hash, &tx.principal),
+                "message": {"type": "transaction", "transaction": tx.envelope.transaction()},
+                "status": tx.status.record(),
+                "signatures": tx.envelope.signatures(),
+            });
+            let produced = produced(hash, tx);
+            if !produced.is_empty() {
+                record["produced"] =
+                    json!({"recordType": "range", "total": produced.len(), "records": produced});
+            }
+            return Ok(record);
+        }
+        let tx_hash = self
+            .signatures
+            .get(hash)
+            .ok_or_else(|| SimulatorError::NotFound(format!("message {hash} not found")))?;
+        let tx = &self.transactions[tx_hash];
+        let signature = tx
+            .envelope
+            .signatures()
+            .iter()
+            .find(|sig| signature_hash(sig) == hash)
+            .cloned()
+            .unwrap_or(Value::Null);
+        // The signature itself is delivered even while the transaction waits
+        // for more signatures
+        let status = match &tx.status {
+            Status::Pending => Status::Delivered,
+            status => status.clone(),
+        };
+        Ok(json!({
+            "recordType": "message",
+            "id": txid(hash, str_field(&signature, "signer")),
+            "message": {"type": "signature", "signature": signature, "txID": txid(tx_hash, &tx.principal)},
+            "status": status.record(),
+        }))
+    }
+
+    fn submit(&mut self, envelope: &Value) -> Result<Value, SimulatorError> {
+        let incoming = PartialEnvelope::from_envelope(envelope)?;
+        if incoming.signatures().is_empty() {
+            return Err(SimulatorError::BadRequest(
+                "envelope has no signatures".to_string(),
+            ));
+        }
+        let hash = hex::encode(incoming.transaction_hash());
+        let body = &incoming.transaction()["body"];
+
+        // A remote body signs for a transaction that is already pending
+        let (principal, tx_type) = match self.transactions.get(&hash) {
+            Some(tx) if tx.status != Status::Pending => {
+                return Err(SimulatorError::Conflict(format!(
+                    "transaction {hash} has already executed"
+                )))
+            }
+            Some(tx) => (
+                tx.principal.clone(),
+                str_field(&tx.envelope.transaction()["body"], "type").to_string(),
+            ),
+            None if matches!(str_field(body, "type"), "remoteTransaction" | "signPending") => {
+                return Err(SimulatorError::NotFound(format!(
+                    "transaction {hash} is not pending"
+                )))
+            }
+            None => (
+                str_field(&incoming.transaction()["header"], "principal").to_string(),
+                str_field(body, "type").to_string(),
+            ),
+        };
+        let auth = self
+            .accounts
+            .get(&normalize(&principal))
+            .map(|account| AccountAuth::from_account(&account.url, &account.record()))
+            .ok_or_else(|| SimulatorError::NotFound(format!("principal {principal} not found")))?;
+        for signature in incoming.signatures() {
+            let signer = str_field(signature, "signer");
+            self.check_signer(signature)?;
+            auth.check(signer, &tx_type)
+                .map_err(|e| SimulatorError::Unauthorized(e.to_string()))?;
+        }
+
+        if let Some(tx) = self.transactions.get_mut(&hash) {
+            tx.envelope.merge(&incoming)?;
+        } else {
+            self.charge(
+                str_field(&incoming.signatures()[0], "signer"),
+                fee(&tx_type),
+            )?;
+            self.transactions.insert(
+                hash.clone(),
+                Transaction {
+                    principal: principal.clone(),
+                    envelope: incoming.clone(),
+                    status: Status::Pending,
+                },
+            );
+        }
+        for signature in incoming.signatures() {
+            self.signatures
+                .insert(signature_hash(signature), hash.clone());
+        }
+        self.execute_when_ready(&hash);
+
+        let status = self.transactions[&hash].status.record();
+        let mut submissions = vec![
+            json!({"status": {"txID": txid(&hash, &principal), "code": status}, "success": true}),
+        ];
+        submissions.extend(incoming.signatures().iter().map(|signature| {
+            let id = txid(&signature_hash(signature), str_field(signature, "signer"));
+            json!({"status": {"txID": id}, "success": true})
+        }));
+        Ok(Value::Array(submissions))
+    }
+
+    /// Check that a signature's key belongs to its signer at the stated version
+    fn check_signer(&self, signature: &Value) -> Result<(), SimulatorError> {
+        let signer = str_field(signature, "signer");
+        let public_key = hex::decode(str_field(signature, "publicKey"))
+            .map_err(|e| SimulatorError::BadRequest(format!("publicKey: {e}")))?;
+        let account = self
+            .accounts
+            .get(&normalize(signer))
+            .ok_or_else(|| SimulatorError::NotFound(format!("signer {signer} not found")))?;
+        match &account.kind {
+            Kind::LiteIdentity { .. } => {
+                let owner = <[u8; 32]>::try_from(public_key.as_slice())
+                    .map(|key| normalize(&derive_lite_identity_url(&key)))
+                    .unwrap_or_default();
+                if owner != normalize(signer) {
+                    return Err(SimulatorError::Unauthorized(format!(
+                        "key {} is not authorized to sign for {signer}",
+                        hex::encode(&public_key)
+                    )));
+                }
+                Ok(())
+            }
+            Kind::KeyPage { version, .. } => {
+                let page = KeyPageState::from_account(&account.record(), signer);
+                if page.find_key_index(&public_key).is_none() {
+                    return Err(SimulatorError::Unauthorized(format!(
+                        "key {} is not authorized to sign for {signer}",
+                        hex::encode(&public_key)
+                    )));
+                }
+                let signed_version = signature
+                    .get("signerVersion")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0);
+                if signed_version != *version {
+                    return Err(SimulatorError::BadRequest(format!(
+                        "invalid signature: signed with version {signed_version} of {signer}, which is at version {version}"
+                    )));
+                }
+                Ok(())
+            }
+            _ => Err(SimulatorError::BadRequest(format!(
+                "{signer} is not a signer"
+            ))),
+        }
+    }
+
+    /// Deduct a fee from a signer's credits
+    fn charge(&mut self, signer: &str, fee: u64) -> Result<(), SimulatorError> {
+        let Some(account) = self.accounts.get_mut(&normalize(signer)) else {
+            return Err(SimulatorError::NotFound(format!(
+                "signer {signer} not found"
+            )));
+        };
+        let (Kind::LiteIdentity { credits } | Kind::KeyPage { credits, .. }) = &mut account.kind
+        else {
+            return Err(SimulatorError::BadRequest(format!(
+                "{signer} is not a signer"
+            )));
+        };
+        if *credits < fee {
+            return Err(SimulatorError::InsufficientCredits(format!(
+                "insufficient credits: {signer} has {credits}, the fee is {fee}"
+            )));
+        }
+        *credits -= fee;
+        Ok(())
+    }
+
+    /// Execute a pending transaction once a signer has met its threshold
+    ///
+    /// Execution works on a copy of the accounts, so a failed transaction
+    /// leaves them untouched.
+    fn execute_when_ready(&mut self, hash: &str) {
+        let Some(tx) = self.transactions.get(hash) else {
+            return;
+        };
+        let ready = tx.envelope.signatures().iter().any(|signature| {
+            let signer = str_field(signature, "signer");
+            match self.accounts.get(&normalize(signer)) {
+                Some(
+                    page @ Account {
+                        kind: Kind::KeyPage { .. },
+                        ..
+                    },
+                ) => matches!(
+                    tx.envelope
+                        .threshold_met(&KeyPageState::from_account(&page.record(), signer)),
+                    Ok(true)
+                ),
+                Some(_) => true,
+                None => false,
+            }
+        });
+        if !ready {
+            return;
+        }
+
+        let mut accounts = self.accounts.clone();
+        let body = &tx.envelope.transaction()["body"];
+        let status = match execute(&mut accounts, &tx.principal, hash, body, self.oracle) {
+            Ok(()) => {
+                if let Some(account) = accounts.get_mut(&normalize(&tx.principal)) {
+                    account.main_chain.push(hash.to_string());
+                }
+                self.accounts = accounts;
+                Status::Delivered
+            }
+            Err(message) => Status::Failed(message),
+        };
+        if let Some(tx) = self.transactions.get_mut(hash) {
+            tx.status = status;
+        }
+    }
+}
+
+/// Apply a transaction body to `accounts`
+fn execute(
+    accounts: &mut BTreeMap<String, Account>,
+    principal: &str,
+    hash: &str,
+    body: &Value,
+    oracle: u64,
+) -> Result<(), String> {
+    match str_field(body, "type") {
+        "addCredits" => add_credits(accounts, principal, body, oracle),
+        "createIdentity" => create_identity(accounts, body),
+        tx_type @ ("createTokenAccount" | "createDataAccount") => {
+            create_account(accounts, principal, body, tx_type == "createDataAccount")
+        }
+        "createToken" => create_token(accounts, principal, body),
+        "sendTokens" => send_tokens(accounts, principal, body),
+        "burnTokens" => debit(accounts, principal, amount(body.get("amount"))?),
+        "writeData" => match kind_mut(accounts, principal) {
+            Some(Kind::DataAccount { entries, .. }) => {
+                entries.push((data_entry_hash(&body["entry"]), hash.to_string()));
+                Ok(())
+            }
+            _ => Err(format!("{principal} is not a data account")),
+        },
+        "createKeyPage" => create_key_page(accounts, principal, body),
+        "updateKeyPage" => update_key_page(accounts, principal, body),
+        other => Err(format!("{other} is not modeled by the simulator")),
+    }
+}
+
+fn add_credits(
+    accounts: &mut BTreeMap<String, Account>,
+    principal: &str,
+    body: &Value,
+    oracle: u64,
+) -> Result<(), String> {
+    let amount = amount(body.get("amount"))?;
+    let price = body.get("oracle").and_then(Value::as_u64).unwrap_or(0);
+    if price != oracle {
+        return Err(format!(
+            "oracle price {price} does not match the network's {oracle}"
+        ));
+    }
+    let recipient = str_field(body, "recipient");
+    let key = normalize(recipient);
+    if !accounts.contains_key(&key) && is_lite_identity(&key) {
+        accounts.insert(
+            key.clone(),
+            Account::new(recipient, Kind::LiteIdentity { credits: 0 }),
+        );
+    }
+    debit(accounts, principal, amount)?;
+    let purchased = u64::try_from(amount * u128::from(oracle) / u128::from(ACME_BASE_UNITS))
+        .map_err(|_| format!("{amount} buys too many credits"))?;
+    match kind_mut(accounts, &key) {
+        Some(Kind::LiteIdentity { credits } | Kind::KeyPage { credits, .. }) => {
+            *credits += purchased;
+            Ok(())
+        }
+        _ => Err(format!("cannot add credits to {recipient}")),
+    }
+}
+
+/// Create an identity with a key book and a first page holding one key
+fn create_identity(accounts: &mut BTreeMap<String, Account>, body: &Value) -> Result<(), String> {
+    let url = str_field(body, "url");
+    let identity = normalize(url);
+    let name = &identity["acc://".len()..];
+    if name.contains('/') || name.rsplit_once('.').map_or(true, |(_, tld)| tld != "acme") {
+        return Err(format!("{url} is not a valid identity URL"));
+    }
+    let book = match body.get("keyBookUrl").and_then(Value::as_str) {
+        Some(book) if parent(&normalize(book)) == identity => book.to_string(),
+        Some(book) => return Err(format!("key book {book} is not under {url}")),
+        None => format!("{url}/book"),
+    };
+    let key_hash = str_field(body, "keyHash").to_ascii_lowercase();
+    if hex::decode(&key_hash).map_or(true, |hash| hash.len() != 32) {
+        return Err(format!("invalid key hash {key_hash:?}"));
+    }
+    let page = format!("{book}/1");
+    create(
+        accounts,
+        url,
+        Kind::Identity {
+            authority: book.clone(),
+        },
+    )?;
+    create(
+        accounts,
+        &book,
+        Kind::KeyBook {
+            authority: book.clone(),
+            pages: 1,
+        },
+    )?;
+    create(
+        accounts,
+        &page,
+        Kind::KeyPage {
+            book,
+            version: 1,
+            credits: 0,
+            threshold: 1,
+            keys: vec![key_hash],
+        },
+    )
+}
+
+/// Create a token or data account directly under the principal identity
+fn create_account(
+    accounts: &mut BTreeMap<String, Account>,
+    principal: &str,
+    body: &Value,
+    data: bool,
+) -> Result<(), String> {
+    let url = str_field(body, "url");
+    let authority = identity_authority(accounts, principal, url)?;
+    if data {
+        return create(
+            accounts,
+            url,
+            Kind::DataAccount {
+                authority,
+                entries: Vec::new(),
+            },
+        );
+    }
+    let token = str_field(body, "tokenUrl");
+    if !token.eq_ignore_ascii_case(ACME) {
+        return Err(format!("unknown token {token}"));
+    }
+    create(
+        accounts,
+        url,
+        Kind::TokenAccount {
+            authority,
+            balance: 0,
+        },
+    )
+}
+
+/// Create a token issuer directly under the principal identity
+fn create_token(
+    accounts: &mut BTreeMap<String, Account>,
+    principal: &str,
+    body: &Value,
+) -> Result<(), String> {
+    let url = str_field(body, "url");
+    let authority = identity_authority(accounts, principal, url)?;
+    let symbol = str_field(body, "symbol");
+    if symbol.is_empty() {
+        return Err("createToken has no symbol".to_string());
+    }
+    let supply_limit = match body.get("supplyLimit") {
+        None => None,
+        limit => Some(amount(limit)?),
+    };
+    create(
+        accounts,
+        url,
+        Kind::TokenIssuer {
+            authority,
+            symbol: symbol.to_string(),
+            precision: body.get("precision").and_then(Value::as_u64).unwrap_or(0),
+            supply_limit,
+        },
+    )
+}
+
+/// Authority of the identity `principal`, under which `url` is created
+fn identity_authority(
+    accounts: &BTreeMap<String, Account>,
+    principal: &str,
+    url: &str,
+) -> Result<String, String> {
+    let authority = match accounts
+        .get(&normalize(principal))
+        .map(|account| &account.kind)
+    {
+        Some(Kind::Identity { authority }) => authority.clone(),
+        _ => return Err(format!("{principal} is not an identity")),
+    };
+    if parent(&normalize(url)) != normalize(principal) {
+        return Err(format!("{url} is not directly under {principal}"));
+    }
+    Ok(authority)
+}
+
+/// Move tokens to each recipient, creating lite ACME accounts on first use
+fn send_tokens(
+    accounts: &mut BTreeMap<String, Account>,
+    principal: &str,
+    body: &Value,
+) -> Result<(), String> {
+    let recipients = body
+        .get("to")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "sendTokens has no recipients".to_string())?;
+    let mut total = 0u128;
+    for recipient in recipients {
+        let url = str_field(recipient, "url");
+        let key = normalize(url);
+        match accounts.get(&key).map(|account| &account.kind) {
+            Some(Kind::TokenAccount { .. } | Kind::LiteTokenAccount { .. }) => {}
+            Some(_) => return Err(format!("{url} is not a token account")),
+            None => {
+                let identity = lite_identity_of(&key).ok_or_else(|| format!("{url} not found"))?;
+                accounts
+                    .entry(identity.clone())
+                    .or_insert_with(|| Account::new(&identity, Kind::LiteIdentity { credits: 0 }));
+                accounts.insert(
+                    key,
+                    Account::new(url, Kind::LiteTokenAccount { balance: 0 }),
+                );
+            }
+        }
+        total += amount(recipient.get("amount"))?;
+    }
+    debit(accounts, principal, total)?;
+    for recipient in recipients {
+        if let Some(Kind::TokenAccount { balance, .. } | Kind::LiteTokenAccount { balance }) =
+            kind_mut(accounts, str_field(recipient, "url"))
+        {
+            *balance += amount(recipient.get("amount"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Append a page holding the given keys to the principal key book
+fn create_key_page(
+    accounts: &mut BTreeMap<String, Account>,
+    principal: &str,
+    body: &Value,
+) -> Result<(), String> {
+    let Some(Kind::KeyBook { pages, .. }) = kind_mut(accounts, principal) else {
+        return Err(format!("{principal} is not a key book"));
+    };
+    let keys = body
+        .get("keys")
+        .and_then(Value::as_array)
+        .map(|keys| {
+            keys.iter()
+                .map(|key| str_field(key, "keyHash").to_ascii_lowercase())
+                .collect::<Vec<_>>()
+        })
+        .unwrap_or_default();
+    if keys.is_empty() {
+        return Err("createKeyPage has no keys".to_string());
+    }
+    *pages += 1;
+    let page = format!("{principal}/{pages}");
+    create(
+        accounts,
+        &page,
+        Kind::KeyPage {
+            book: principal.to_string(),
+            version: 1,
+            credits: 0,
+            threshold: 1,
+            keys,
+        },
+    )
+}
+
+/// Apply key page operations and bump the page version
+fn update_key_page(
+    accounts: &mut BTreeMap<String, Account>,
+    principal: &str,
+    body: &Value,
+) -> Result<(), String> {
+    let Some(Kind::KeyPage {
+        version,
+        threshold,
+        keys,
+        ..
+    }) = kind_mut(accounts, principal)
+    else {
+        return Err(format!("{principal} is not a key page"));
+    };
+    let operations = body
+        .get("operation")
+        .and_then(Value::as_array)
+        .ok_or_else(|| "updateKeyPage has no operations".to_string())?;
+    for operation in operations {
+        let entry = |name: &str| {
+            operation
+                .pointer(&format!("/{name}/keyHash"))
+                .and_then(Value::as_str)
+                .map(str::to_ascii_lowercase)
+                .ok_or_else(|| format!("{name} has no key hash"))
+        };
+        let position = |keys: &[String], hash: &str| {
+            keys.iter()
+                .position(|k| k == hash)
+                .ok_or_else(|| format!("key {hash} is not on {principal}"))
+        };
+        match str_field(operation, "type") {
+            "add" => {
+                let hash = entry("entry")?;
+                if keys.contains(&hash) {
+                    return Err(format!("key {hash} is already on {principal}"));
+                }
+                keys.push(hash);
+            }
+            "remove" => {
+                let index = position(keys, &entry("entry")?)?;
+                if keys.len() == 1 {
+                    return Err(format!("cannot remove the last key of {principal}"));
+                }
+                keys.remove(index);
+                *threshold = (*threshold).min(keys.len() as u64);
+            }
+            "update" => {
+                let index = position(keys, &entry("oldEntry")?)?;
+                keys[index] = entry("newEntry")?;
+            }
+            "setThreshold" => {
+                let value = operation
+                    .get("threshold")
+                    .and_then(Value::as_u64)
+                    .unwrap_or(0);
+                if value == 0 || value > keys.len() as u64 {
+                    return Err(format!(
+                        "threshold {value} is not between 1 and {} keys",
+                        keys.len()
+                    ));
+                }
+                *threshold = value;
+            }
+            other => {
+                return Err(format!(
+                    "updateKeyPage operation {other} is not modeled by the simulator"
+                ))
+            }
+        }
+    }
+    *version += 1;
+    Ok(())
+}
+
+/// Flat fee per transaction type, in hundredths of a credit
+///
+/// Buying credits is paid for in tokens, so a fresh lite identity can do it.
+fn fee(tx_type: &str) -> u64 {
+    match tx_type {
+        "addCredits" => 0,
+        "createIdentity" => 50_000,
+        "createTokenAccount" | "createDataAccount" => 2_500,
+        _ => 100,
+    }
+}
+
+fn kind_mut<'a>(accounts: &'a mut BTreeMap<String, Account>, url: &str) -> Option<&'a mut Kind> {
+    accounts
+        .get_mut(&normalize(url))
+        .map(|account| &mut account.kind)
+}
+
+/// Insert a new account, failing if the URL is taken
+fn create(accounts: &mut BTreeMap<String, Account>, url: &str, kind: Kind) -> Result<(), String> {
+    let key = normalize(url);
+    if accounts.contains_key(&key) {
+        return Err(format!("{url} already exists"));
+    }
+    accounts.insert(key, Account::new(url, kind));
+    Ok(())
+}
+
+/// Take `amount` base units from a token account
+fn debit(
+    accounts: &mut BTreeMap<String, Account>,
+    principal: &str,
+    amount: u128,
+) -> Result<(), String> {
+    match kind_mut(accounts, principal) {
+        Some(Kind::TokenAccount { balance, .. } | Kind::LiteTokenAccount { balance }) => {
+            if *balance < amount {
+                return Err(format!(
+                    "insufficient balance: {principal} holds {balance}, needs {amount}"
+                ));
+            }
+            *balance -= amount;
+            Ok(())
+        }
+        _ => Err(format!("{principal} is not a token account")),
+    }
+}
+
+/// A token amount in base units, as a string or a number
+fn amount(value: Option<&Value>) -> Result<u128, String> {
+    match value {
+        Some(Value::String(s)) => s.parse().map_err(|_| format!("invalid amount {s:?}")),
+        Some(Value::Number(n)) => n
+            .as_u64()
+            .map(u128::from)
+            .ok_or_else(|| format!("invalid amount {n}")),
+        _ => Err("missing amount".to_string()),
+    }
+}
+
+impl Account {
+    fn new(url: &str, kind: Kind) -> Self {
+        Self {
+            url: url.trim_end_matches('/').to_string(),
+            kind,
+            main_chain: Vec::new(),
+        }
+    }
+
+    /// The account as `query` returns it
+    fn record(&self) -> Value {
+        let url = &self.url;
+        let authorities = |authority: &str| json!([{"url": authority}]);
+        match &self.kind {
+            Kind::LiteIdentity { credits } => {
+                json!({"type": "liteIdentity", "url": url, "creditBalance": credits})
+            }
+            Kind::LiteTokenAccount { balance } => {
+                json!({"type": "liteTokenAccount", "url": url, "tokenUrl": ACME, "balance": balance.to_string()})
+            }
+            Kind::Identity { authority } => {
+                json!({"type": "identity", "url": url, "authorities": authorities(authority)})
+            }
+            Kind::KeyBook { authority, pages } => {
+                json!({"type": "keyBook", "url": url, "pageCount": pages, "authorities": authorities(authority)})
+            }
+            Kind::KeyPage {
+                book,
+                version,
+                credits,
+                threshold,
+                keys,
+            } => json!({
+                "type": "keyPage",
+                "url": url,
+                "keyBook": book,
+                "version": version,
+                "creditBalance": credits,
+                "acceptThreshold": threshold,
+                "keys": keys.iter().map(|hash| json!({"publicKeyHash": hash})).collect::<Vec<_>>(),
+                "authorities": authorities(book),
+            }),
+            Kind::TokenAccount { authority, balance } => json!({
+                "type": "tokenAccount",
+                "url": url,
+                "tokenUrl": ACME,
+                "balance": balance.to_string(),
+                "authorities": authorities(authority),
+            }),
+            Kind::DataAccount { authority, entries } => json!({
+                "type": "dataAccount",
+                "url": url,
+                "entryCount": entries.len(),
+                "authorities": authorities(authority),
+            }),
+            Kind::TokenIssuer {
+                authority,
+                symbol,
+                precision,
+                supply_limit,
+            } => {
+                let mut record = json!({
+                    "type": "tokenIssuer",
+                    "url": url,
+                    "symbol": symbol,
+                    "precision": precision,
+                    "issued": "0",
+                    "authorities": authorities(authority),
+                });
+                if let Some(limit) = supply_limit {
+                    record["supplyLimit"] = json!(limit.to_string());
+                }
+                record
+            }
+        }
+    }
+}
+
+impl Status {
+    /// The status as message queries report it
+    fn record(&self) -> Value {
+        match self {
+            Self::Pending => json!({"code": "pending", "delivered": false, "pending": true}),
+            Self::Delivered => json!({"code": "delivered", "delivered": true}),
+            Self::Failed(message) => json!({
+                "code": "failed",
+                "delivered": true,
+                "failed": true,
+                "error": {"message": message},
+            }),
+        }
+    }
+}
+
+fn str_field<'v>(value: &'v Value, key: &str) -> &'v str {
+    value.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+/// Lower-case `acc://` URL without a trailing slash, the key of the account map
+fn normalize(url: &str) -> String {
+    let url = url.trim().trim_end_matches('/').to_ascii_lowercase();
+    if url.starts_with("acc://") {
+        url
+    } else {
+        format!("acc://{url}")
+    }
+}
+
+/// The URL one path segment up
+fn parent(url: &str) -> &str {
+    url.rsplit_once('/').map_or("", |(parent, _)| parent)
+}
+
+/// Whether a normalized URL has the shape of a lite identity
+fn is_lite_identity(url: &str) -> bool {
+    let host = &url["acc://".len().min(url.len())..];
+    host.len() == 48 && host.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// The lite identity of a normalized lite ACME account URL
+fn lite_identity_of(url: &str) -> Option<String> {
+    let identity = url.strip_suffix("/acme")?;
+    is_lite_identity(identity).then(|| identity.to_string())
+}
+
+/// The hash of a message scope `acc://<hash>@<account>`
+fn message_hash(scope: &str) -> Option<String> {
+    let (hash, _) = scope.strip_prefix("acc://")?.split_once('@')?;
+    (hash.len() == 64 && hash.bytes().all(|b| b.is_ascii_hexdigit()))
+        .then(|| hash.to_ascii_lowercase())
+}
+
+/// Hash of a data entry as the data query looks it up, hex
+fn data_entry_hash(entry: &Value) -> String {
+    let data: Vec<String> = entry["data"]
+        .as_array()
+        .map(|data| {
+            data.iter()
+                .filter_map(Value::as_str)
+                .map(str::to_string)
+                .collect()
+        })
+        .unwrap_or_default();
+    hex::encode(compute_data_entry_hash(
+        &data,
+        str_field(entry, "type") == "doublehash",
+    ))
+}
+
+fn txid(hash: &str, account: &str) -> String {
+    format!("acc://{hash}@{}", account.trim_start_matches("acc://"))
+}
+
+/// ID records of the synthetic deposits a delivered `sendTokens` produces,
+/// one per recipient
+fn produced(hash: &str, tx: &Transaction) -> Vec<Value> {
+    let body = &tx.envelope.transaction()["body"];
+    if tx.status != Status::Delivered || str_field(body, "type") != "sendTokens" {
+        return Vec::new();
+    }
+    body["to"]
+        .as_array()
+        .into_iter()
+        .flatten()
+        .enumerate()
+        .map(|(index, recipient)| {
+            let synthetic = hex::encode(sha256_bytes(format!("{hash}/{index}").as_bytes()));
+            json!({"recordType": "txID", "value": txid(&synthetic, str_field(recipient, "url"))})
+        })
+        .collect()
+}
+
+/// Message hash of a signature, to give it a transaction ID of its own
+fn signature_hash(signature: &Value) -> String {
+    hex::encode(sha256_bytes(canonical_json(signature).as_bytes()))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::HeaderOptions;
+    use crate::helpers::{QuickStart, SmartSigner, TxBody};
+    use crate::multisig::pending_transactions;
+    use std::time::Instant;
+
+    async fn quick_start(network: &SimulatedNetwork) -> QuickStart {
+        QuickStart::from_client(Arc::new(network.client().await.unwrap()))
+    }
+
+    #[tokio::test]
+    async fn test_setup_adi_against_simulator() {
+        let started = Instant::now();
+        let network = SimulatedNetwork::new();
+        let quick = quick_start(&network).await;
+        let wallet = quick.create_wallet();
+        network
+            .fund(&wallet.lite_token_account, 100 * ACME_BASE_UNITS)
+            .unwrap();
+
+        let adi = quick.setup_adi(&wallet, "alice").await.unwrap();
+        // 1000 credits bought for 20 ACME, 500 paid for the identity
+        assert_eq!(
+            network.account(&wallet.lite_identity).unwrap()["creditBalance"],
+            50_000
+        );
+        assert_eq!(
+            network.account(&wallet.lite_token_account).unwrap()["balance"],
+            (80 * ACME_BASE_UNITS).to_string()
+        );
+        let page = quick.get_key_page_info(&adi.key_page_url).await.unwrap();
+        assert_eq!((page.version, page.threshold, page.key_count), (1, 1, 1));
+
+        // The page needs credits of its own before it can sign
+        let result = quick.create_token_account(&adi, "tokens").await.unwrap();
+        assert!(!result.success);
+        assert!(result.error.unwrap().contains("insufficient credits"));
+        assert!(
+            quick
+                .buy_credits_for_adi(&wallet, &adi, 100)
+                .await
+                .unwrap()
+                .success
+        );
+        assert!(
+            quick
+                .create_token_account(&adi, "tokens")
+                .await
+                .unwrap()
+                .success
+        );
+        assert_eq!(
+            network.account("acc://alice.acme/tokens").unwrap()["type"],
+            "tokenAccount"
+        );
+
+        // A second identity of the same name fails during execution
+        let result = quick.setup_adi(&wallet, "alice").await.unwrap_err();
+        assert!(result.to_string().contains("already exists"), "{result}");
+
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn test_multisig_against_simulator() {
+        let network = SimulatedNetwork::new();
+        let quick = quick_start(&network).await;
+        let wallet = quick.create_wallet();
+        network
+            .fund(&wallet.lite_token_account, 100 * ACME_BASE_UNITS)
+            .unwrap();
+        let adi = quick.setup_adi(&wallet, "multi").await.unwrap();
+        assert!(
+            quick
+                .buy_credits_for_adi(&wallet, &adi, 100)
+                .await
+                .unwrap()
+                .success
+        );
+
+        let second = AccumulateClient::generate_keypair();
+        assert!(quick.add_key_to_adi(&adi, &second).await.unwrap().success);
+        assert!(
+            quick
+                .set_multi_sig_threshold(&adi, 2)
+                .await
+                .unwrap()
+                .success
+        );
+        let page = quick.get_key_page_info(&adi.key_page_url).await.unwrap();
+        assert_eq!((page.version, page.threshold, page.key_count), (3, 2, 2));
+
+        // Gathered offline: refused until both keys have signed
+        let mut first = SmartSigner::new(quick.client(), adi.keypair().clone(), &adi.key_page_url);
+        let mut other = SmartSigner::new(quick.client(), second.clone(), &adi.key_page_url);
+        first.verify_key_on_page().await.unwrap();
+        let body = TxBody::create_data_account("acc://multi.acme/data");
+        let mut partial =
+            PartialEnvelope::initiate(&first, &adi.url, &body, &HeaderOptions::default()).unwrap();
+        assert!(matches!(
+            partial.submit_when_ready(quick.client(), 5).await,
+            Err(MultisigError::ThresholdNotMet {
+                signatures: 1,
+                threshold: 2,
+                ..
+            })
+        ));
+        partial.co_sign_verified(&mut other).await.unwrap();
+        assert!(
+            partial
+                .submit_when_ready(quick.client(), 5)
+                .await
+                .unwrap()
+                .success
+        );
+        assert!(network.account("acc://multi.acme/data").is_some());
+
+        // Submitted with one signature: pending until the second key signs
+        let body = TxBody::create_data_account("acc://multi.acme/log");
+        assert!(
+            first
+                .sign_submit_and_wait(&adi.url, &body, None, 5)
+                .await
+                .success
+        );
+        assert!(network.account("acc://multi.acme/log").is_none());
+        let pending = pending_transactions(quick.client(), &adi.url)
+            .await
+            .unwrap();
+        assert_eq!(pending.len(), 1);
+        let result = other
+            .sign_pending_and_wait(&adi.url, &pending[0].hash, 5)
+            .await;
+        assert!(result.success, "{:?}", result.error);
+        assert!(network.account("acc://multi.acme/log").is_some());
+        assert!(pending_transactions(quick.client(), &adi.url)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_simulator_rejects_bad_signers() {
+        let network = SimulatedNetwork::new();
+        let quick = quick_start(&network).await;
+        let wallet = quick.create_wallet();
+        network
+            .fund(&wallet.lite_token_account, 100 * ACME_BASE_UNITS)
+            .unwrap();
+        let adi = quick.setup_adi(&wallet, "guarded").await.unwrap();
+
+        // A signature altered after signing
+        let signer = SmartSigner::new(quick.client(), adi.keypair().clone(), &adi.key_page_url);
+        let mut envelope = signer
+            .sign(
+                &adi.key_page_url,
+                &TxBody::update_key_page_set_threshold(1),
+                None,
+            )
+            .unwrap();
+        envelope["signatures"][0]["signerVersion"] = json!(7);
+        let err = network
+            .call("submit", &json!({"envelope": envelope}))
+            .unwrap_err();
+        assert!(matches!(err, SimulatorError::Unauthorized(_)), "{err}");
+
+        // A signer version the page has moved past
+        assert!(
+            quick
+                .buy_credits_for_adi(&wallet, &adi, 100)
+                .await
+                .unwrap()
+                .success
+        );
+        let second = AccumulateClient::generate_keypair();
+        assert!(quick.add_key_to_adi(&adi, &second).await.unwrap().success);
+        let envelope = signer
+            .sign(
+                &adi.key_page_url,
+                &TxBody::update_key_page_set_threshold(1),
+                None,
+            )
+            .unwrap();
+        let err = network
+            .call("submit", &json!({"envelope": envelope}))
+            .unwrap_err();
+        assert!(err.to_string().contains("which is at version 2"), "{err}");
+
+        // A key that is not on the page
+        let outsider = SmartSigner::new(
+            quick.client(),
+            AccumulateClient::generate_keypair(),
+            &adi.key_page_url,
+        );
+        let envelope = outsider
+            .sign(
+                &adi.key_page_url,
+                &TxBody::update_key_page_set_threshold(1),
+                None,
+            )
+            .unwrap();
+        let err = network
+            .call("submit", &json!({"envelope": envelope}))
+            .unwrap_err();
+        assert_eq!(err.code(), 401);
+
+        // A signer whose book is not an authority of the principal
+        let lite = SmartSigner::new(
+            quick.client(),
+            wallet.keypair().clone(),
+            &wallet.lite_identity,
+        );
+        let envelope = lite
+            .sign(
+                &adi.url,
+                &TxBody::create_data_account("acc://guarded.acme/data"),
+                None,
+            )
+            .unwrap();
+        let err = network
+            .call("submit", &json!({"envelope": envelope}))
+            .unwrap_err();
+        assert!(
+            crate::auth::AuthError::is_auth_failure(err.code(), &err.to_string()),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_simulator_as_rpc_transport() {
+        let network = SimulatedNetwork::new().with_oracle(2500);
+        let status: Value = network
+            .rpc_call("network-status", &json!({}))
+            .await
+            .unwrap();
+        assert_eq!(status["oracle"]["price"], 2500);
+
+        let account = format!("{}/ACME", derive_lite_identity_url(&[7u8; 32]));
+        let faucet: Value = network
+            .rpc_call("faucet", &json!({"account": &account}))
+            .await
+            .unwrap();
+        let id = faucet["status"]["txID"].as_str().unwrap();
+        let record: Value = network
+            .rpc_call(
+                "query",
+                &json!({"scope": id, "query": {"queryType": "default"}}),
+            )
+            .await
+            .unwrap();
+        assert_eq!(record["status"]["delivered"], true);
+        assert_eq!(
+            network.account(&account).unwrap()["balance"],
+            FAUCET_AMOUNT.to_string()
+        );
+
+        let err = network
+            .rpc_call::<_, Value>("query", &json!({"scope": "acc://nobody.acme"}))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, crate::errors::Error::Rpc { code: 404, .. }));
+        assert!(network.call("describe", &json!({})).is_err());
+    }
+}
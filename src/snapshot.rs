@@ -0,0 +1,771 @@
+//! Snapshot file decoding for off-chain analysis
+//!
+//! A snapshot captures the full state of a partition at one block: every
+//! account's main record, its chain heads and its pending transactions.
+//! [`SnapshotReader`] walks a decompressed snapshot held in memory and decodes
+//! account records into the generated protocol models, so balances and key
+//! books can be analysed without running a node.
+//!
+//! The file is a sequence of sections. Each section starts with a 16-byte
+//! header, the section type and the body length as little-endian `u64`s,
+//! followed by the body. The header section holds a field-encoded
+//! [`SnapshotHeader`]; an accounts section holds length-prefixed account
+//! records. Sections this reader does not understand are kept as raw bytes.
+
+use crate::codec::{BinaryReader, DecodingError, ValueKind, EMPTY_OBJECT};
+use crate::generated::enums::BookType;
+use crate::generated::types::{
+    KeyBook, LiteDataAccount, LiteIdentity, LiteTokenAccount, TokenAccount, TokenIssuer, ADI,
+};
+use std::path::Path;
+use thiserror::Error;
+
+/// Snapshot format version understood by this reader
+pub const SNAPSHOT_VERSION: u64 = 1;
+
+/// Section type numbers
+pub mod section_types {
+    /// Snapshot header, see [`SnapshotHeader`](super::SnapshotHeader)
+    pub const HEADER: u64 = 1;
+    /// Length-prefixed account records
+    pub const ACCOUNTS: u64 = 2;
+    /// Transaction records
+    pub const TRANSACTIONS: u64 = 3;
+    /// Signature records
+    pub const SIGNATURES: u64 = 4;
+    /// Gzip-compressed transaction records
+    pub const GZ_TRANSACTIONS: u64 = 5;
+    /// A nested snapshot
+    pub const SNAPSHOT: u64 = 6;
+}
+
+/// Account type numbers, matching Go's `protocol.AccountType`
+pub mod account_types {
+    /// Accumulate Digital Identifier (ADI)
+    pub const IDENTITY: u64 = 2;
+    /// Token issuer
+    pub const TOKEN_ISSUER: u64 = 3;
+    /// ADI token account
+    pub const TOKEN_ACCOUNT: u64 = 4;
+    /// Lite token account
+    pub const LITE_TOKEN_ACCOUNT: u64 = 5;
+    /// Key page
+    pub const KEY_PAGE: u64 = 9;
+    /// Key book
+    pub const KEY_BOOK: u64 = 10;
+    /// ADI data account
+    pub const DATA_ACCOUNT: u64 = 11;
+    /// Lite data account
+    pub const LITE_DATA_ACCOUNT: u64 = 12;
+    /// Lite identity
+    pub const LITE_IDENTITY: u64 = 15;
+}
+
+const SECTION_HEADER_LEN: usize = 16;
+
+/// Errors from snapshot decoding
+#[derive(Error, Debug)]
+pub enum SnapshotError {
+    /// Reading the snapshot file failed
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+
+    /// A record's binary encoding is malformed
+    #[error("Decoding error: {0}")]
+    Decoding(#[from] DecodingError),
+
+    /// A section header claims more bytes than the file holds
+    #[error(
+        "Section {section_type} at offset {offset} claims {size} bytes, only {available} remain"
+    )]
+    TruncatedSection {
+        /// Type of the truncated section
+        section_type: u64,
+        /// Byte offset of the section header
+        offset: usize,
+        /// Body length claimed by the header
+        size: u64,
+        /// Bytes remaining after the header
+        available: usize,
+    },
+
+    /// No section in the file is a header section
+    #[error("Snapshot has no header section")]
+    MissingHeader,
+
+    /// The header declares a version other than [`SNAPSHOT_VERSION`]
+    #[error("Unsupported snapshot version {0}")]
+    UnsupportedVersion(u64),
+
+    /// A record carries a field number this reader does not know
+    #[error("Unexpected field {field} in {record}")]
+    UnexpectedField {
+        /// Name of the record being decoded
+        record: &'static str,
+        /// The unknown field number
+        field: u32,
+    },
+}
+
+/// Snapshot header section
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SnapshotHeader {
+    /// Format version
+    pub version: u64,
+    /// Block height the snapshot was taken at
+    pub height: u64,
+    /// BPT root hash at that height
+    pub root_hash: [u8; 32],
+    /// Block time at that height
+    pub timestamp: Option<chrono::DateTime<chrono::Utc>>,
+}
+
+/// A raw section of a snapshot file
+#[derive(Debug, Clone, Copy)]
+pub struct SnapshotSection<'a> {
+    /// Section type, see [`section_types`]
+    pub section_type: u64,
+    /// Byte offset of the section header within the file
+    pub offset: usize,
+    /// Section body
+    pub data: &'a [u8],
+}
+
+/// Merkle chain state (Go `merkle.State`)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MerkleState {
+    /// Number of entries in the chain
+    pub count: i64,
+    /// Pending sub-tree roots, empty where the slot is unused
+    pub pending: Vec<Vec<u8>>,
+    /// Hashes of the current mark
+    pub hash_list: Vec<Vec<u8>>,
+}
+
+/// Head of one of an account's chains
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChainHead {
+    /// Chain name, e.g. `main` or `signature`
+    pub name: String,
+    /// Chain type (Go `merkle.ChainType`)
+    pub chain_type: u64,
+    /// Mark power of the chain
+    pub mark_power: u64,
+    /// Current chain state
+    pub head: MerkleState,
+}
+
+/// Decoded main state of an account
+#[derive(Debug, Clone, PartialEq)]
+pub enum AccountState {
+    /// Accumulate Digital Identifier (ADI)
+    Identity(ADI),
+    /// Token issuer
+    TokenIssuer(TokenIssuer),
+    /// ADI token account
+    TokenAccount(TokenAccount),
+    /// Lite token account
+    LiteTokenAccount(LiteTokenAccount),
+    /// Key book
+    KeyBook(KeyBook),
+    /// Lite data account
+    LiteDataAccount(LiteDataAccount),
+    /// Lite identity
+    LiteIdentity(LiteIdentity),
+    /// An account type this reader does not decode, with its raw encoding
+    Other {
+        /// Account type number, see [`account_types`]
+        account_type: u64,
+        /// Binary encoding of the account's main state
+        data: Vec<u8>,
+    },
+}
+
+/// One account record from a snapshot
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotAccount {
+    /// Account URL
+    pub url: String,
+    /// Main account state, if the record has one
+    pub state: Option<AccountState>,
+    /// Chain heads
+    pub chains: Vec<ChainHead>,
+    /// IDs of pending transactions
+    pub pending: Vec<String>,
+    /// Directory entries (sub-account URLs)
+    pub directory: Vec<String>,
+}
+
+/// Reader over an in-memory, decompressed snapshot file
+#[derive(Debug, Clone)]
+pub struct SnapshotReader<'a> {
+    header: SnapshotHeader,
+    sections: Vec<SnapshotSection<'a>>,
+}
+
+impl<'a> SnapshotReader<'a> {
+    /// Split a snapshot into sections and decode its header
+    pub fn new(data: &'a [u8]) -> Result<Self, SnapshotError> {
+        let sections = split_sections(data)?;
+        let header_section = sections
+            .iter()
+            .find(|s| s.section_type == section_types::HEADER)
+            .ok_or(SnapshotError::MissingHeader)?;
+        let header = decode_header(header_section.data)?;
+        if header.version != SNAPSHOT_VERSION {
+            return Err(SnapshotError::UnsupportedVersion(header.version));
+        }
+        Ok(Self { header, sections })
+    }
+
+    /// The decoded header section
+    #[must_use]
+    pub const fn header(&self) -> &SnapshotHeader {
+        &self.header
+    }
+
+    /// All sections in file order, including ones this reader does not decode
+    #[must_use]
+    pub fn sections(&self) -> &[SnapshotSection<'a>] {
+        &self.sections
+    }
+
+    /// Iterate over the account records of every accounts section
+    ///
+    /// Records are decoded lazily, so a full-state snapshot can be scanned
+    /// without holding every decoded account at once.
+    pub fn accounts(&self) -> impl Iterator<Item = Result<SnapshotAccount, SnapshotError>> + 'a {
+        let sections = self.sections.clone();
+        sections
+            .into_iter()
+            .filter(|s| s.section_type == section_types::ACCOUNTS)
+            .flat_map(|section| {
+                let mut reader = BinaryReader::new(section.data);
+                std::iter::from_fn(move || {
+                    if !reader.has_remaining() {
+                        return None;
+                    }
+                    let record = reader
+                        .read_bytes_with_length()
+                        .map_err(SnapshotError::from)
+                        .and_then(decode_account);
+                    if record.is_err() {
+                        // A framing error leaves the position undefined; stop here
+                        reader.read_remaining();
+                    }
+                    Some(record)
+                })
+            })
+    }
+}
+
+/// Read a decompressed snapshot file and decode its header and accounts
+pub fn read_snapshot(
+    path: impl AsRef<Path>,
+) -> Result<(SnapshotHeader, Vec<SnapshotAccount>), SnapshotError> {
+    let data = std::fs::read(path)?;
+    let reader = SnapshotReader::new(&data)?;
+    let accounts = reader.accounts().collect::<Result<Vec<_>, _>>()?;
+    Ok((reader.header().clone(), accounts))
+}
+
+fn split_sections(data: &[u8]) -> Result<Vec<SnapshotSection<'_>>, SnapshotError> {
+    let mut sections = Vec::new();
+    let mut offset = 0;
+    while offset < data.len() {
+        let header = data
+            .get(offset..offset + SECTION_HEADER_LEN)
+            .ok_or(DecodingError::UnexpectedEof)?;
+        let (section_type, size) = header.split_at(8);
+        let section_type = u64::from_le_bytes(
+            section_type
+                .try_into()
+                .map_err(|_| DecodingError::UnexpectedEof)?,
+        );
+        let size = u64::from_le_bytes(size.try_into().map_err(|_| DecodingError::UnexpectedEof)?);
+
+        let start = offset + SECTION_HEADER_LEN;
+        let available = data.len() - start;
+        let len = usize::try_from(size)
+            .ok()
+            .filter(|len| *len <= available)
+            .ok_or(SnapshotError::TruncatedSection {
+                section_type,
+                offset,
+                size,
+                available,
+            })?;
+
+        sections.push(SnapshotSection {
+            section_type,
+            offset,
+            data: &data[start..start + len],
+        });
+        offset = start + len;
+    }
+    Ok(sections)
+}
+
+/// Raw values of a field-encoded record, keyed by field number
+///
+/// Repeated fields appear once per element, in order.
+struct Fields<'a> {
+    values: Vec<(u32, &'a [u8])>,
+}
+
+impl<'a> Fields<'a> {
+    /// Split a record using the wire shape of each field it may contain
+    fn split(
+        record: &'static str,
+        data: &'a [u8],
+        schema: &[(u32, ValueKind)],
+    ) -> Result<Self, SnapshotError> {
+        let mut reader = BinaryReader::new(data);
+        let mut values = Vec::new();
+        while let Some(field) = reader.peek_field_number()? {
+            let kind = schema
+                .iter()
+                .find(|(n, _)| *n == field)
+                .map(|(_, kind)| *kind)
+                .ok_or(SnapshotError::UnexpectedField { record, field })?;
+            reader.read_field_number()?;
+            let start = reader.position();
+            reader.skip_value(kind)?;
+            values.push((field, &data[start..reader.position()]));
+        }
+        Ok(Self { values })
+    }
+
+    fn all(&self, field: u32) -> impl Iterator<Item = &'a [u8]> + '_ {
+        self.values
+            .iter()
+            .filter(move |(n, _)| *n == field)
+            .map(|(_, v)| *v)
+    }
+
+    fn get(&self, field: u32) -> Option<&'a [u8]> {
+        self.all(field).next()
+    }
+
+    fn uvarint(&self, field: u32) -> Result<u64, SnapshotError> {
+        self.get(field)
+            .map_or(Ok(0), |v| BinaryReader::new(v).read_uvarint())
+            .map_err(Into::into)
+    }
+
+    fn string(&self, field: u32) -> Result<String, SnapshotError> {
+        self.get(field)
+            .map_or_else(|| Ok(String::new()), |v| BinaryReader::new(v).read_string())
+            .map_err(Into::into)
+    }
+
+    fn strings(&self, field: u32) -> Result<Vec<String>, SnapshotError> {
+        self.all(field)
+            .map(|v| BinaryReader::new(v).read_string().map_err(Into::into))
+            .collect()
+    }
+
+    fn big_number(&self, field: u32) -> Result<String, SnapshotError> {
+        self.get(field)
+            .map_or_else(
+                || Ok("0".to_string()),
+                |v| {
+                    BinaryReader::new(v)
+                        .read_big_number()
+                        .map(|n| n.to_string())
+                },
+            )
+            .map_err(Into::into)
+    }
+
+    fn bytes_list(&self, field: u32) -> Result<Vec<Vec<u8>>, SnapshotError> {
+        self.all(field)
+            .map(|v| {
+                BinaryReader::new(v)
+                    .read_bytes_with_length()
+                    .map(<[u8]>::to_vec)
+                    .map_err(Into::into)
+            })
+            .collect()
+    }
+
+    /// Inner fields of a nested value
+    fn values(&self, field: u32) -> Result<Vec<&'a [u8]>, SnapshotError> {
+        self.all(field)
+            .map(|v| {
+                let bytes = BinaryReader::new(v).read_bytes_with_length()?;
+                Ok(if bytes == [EMPTY_OBJECT] {
+                    &[][..]
+                } else {
+                    bytes
+                })
+            })
+            .collect()
+    }
+}
+
+fn decode_header(data: &[u8]) -> Result<SnapshotHeader, SnapshotError> {
+    let fields = Fields::split(
+        "snapshot header",
+        data,
+        &[
+            (1, ValueKind::Uvarint),
+            (2, ValueKind::Uvarint),
+            (3, ValueKind::Hash),
+            (4, ValueKind::Varint),
+        ],
+    )?;
+    let root_hash = match fields.get(3) {
+        Some(v) => BinaryReader::new(v).read_hash()?,
+        None => [0u8; 32],
+    };
+    let timestamp = fields
+        .get(4)
+        .map(|v| BinaryReader::new(v).read_time())
+        .transpose()?;
+    Ok(SnapshotHeader {
+        version: fields.uvarint(1)?,
+        height: fields.uvarint(2)?,
+        root_hash,
+        timestamp,
+    })
+}
+
+fn decode_account(data: &[u8]) -> Result<SnapshotAccount, SnapshotError> {
+    let fields = Fields::split(
+        "account",
+        data,
+        &[
+            (1, ValueKind::Bytes),
+            (2, ValueKind::Bytes),
+            (3, ValueKind::Bytes),
+            (4, ValueKind::Bytes),
+            (5, ValueKind::Bytes),
+            (6, ValueKind::Bytes),
+        ],
+    )?;
+
+    let state = fields
+        .values(1)?
+        .first()
+        .map(|main| decode_account_state(main))
+        .transpose()?;
+    let chains = fields
+        .values(6)?
+        .into_iter()
+        .map(decode_chain)
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(SnapshotAccount {
+        url: fields.string(5)?,
+        state,
+        chains,
+        pending: fields.strings(3)?,
+        directory: fields.strings(4)?,
+    })
+}
+
+fn decode_chain(data: &[u8]) -> Result<ChainHead, SnapshotError> {
+    let fields = Fields::split(
+        "chain",
+        data,
+        &[
+            (1, ValueKind::Bytes),
+            (2, ValueKind::Uvarint),
+            (3, ValueKind::Uvarint),
+            (4, ValueKind::Bytes),
+            (5, ValueKind::Bytes),
+        ],
+    )?;
+    let head = match fields.values(4)?.first() {
+        Some(state) => decode_merkle_state(state)?,
+        None => MerkleState::default(),
+    };
+    Ok(ChainHead {
+        name: fields.string(1)?,
+        chain_type: fields.uvarint(2)?,
+        mark_power: fields.uvarint(3)?,
+        head,
+    })
+}
+
+fn decode_merkle_state(data: &[u8]) -> Result<MerkleState, SnapshotError> {
+    let fields = Fields::split(
+        "chain state",
+        data,
+        &[
+            (1, ValueKind::Varint),
+            (2, ValueKind::Bytes),
+            (3, ValueKind::Bytes),
+        ],
+    )?;
+    let count = match fields.get(1) {
+        Some(v) => BinaryReader::new(v).read_varint()?,
+        None => 0,
+    };
+    Ok(MerkleState {
+        count,
+        pending: fields.bytes_list(2)?,
+        hash_list: fields.bytes_list(3)?,
+    })
+}
+
+fn decode_account_state(data: &[u8]) -> Result<AccountState, SnapshotError> {
+    use account_types as t;
+    use ValueKind::{Bytes, Uvarint};
+
+    let mut reader = BinaryReader::new(data);
+    if !reader.read_field(1)? {
+        return Ok(AccountState::Other {
+            account_type: 0,
+            data: data.to_vec(),
+        });
+    }
+    let account_type = reader.read_uvarint()?;
+
+    let schema: &[(u32, ValueKind)] = match account_type {
+        t::IDENTITY => &[(1, Uvarint), (2, Bytes), (3, Bytes)],
+        t::TOKEN_ISSUER => &[
+            (1, Uvarint),
+            (2, Bytes),
+            (3, Bytes),
+            (4, Bytes),
+            (5, Uvarint),
+            (6, Bytes),
+            (7, Bytes),
+            (8, Bytes),
+        ],
+        t::TOKEN_ACCOUNT => &[(1, Uvarint), (2, Bytes), (3, Bytes), (4, Bytes), (5, Bytes)],
+        t::LITE_TOKEN_ACCOUNT => &[
+            (1, Uvarint),
+            (2, Bytes),
+            (3, Bytes),
+            (4, Bytes),
+            (5, Uvarint),
+        ],
+        t::KEY_BOOK => &[
+            (1, Uvarint),
+            (2, Bytes),
+            (3, Uvarint),
+            (4, Bytes),
+            (5, Uvarint),
+        ],
+        t::LITE_DATA_ACCOUNT => &[(1, Uvarint), (2, Bytes)],
+        t::LITE_IDENTITY => &[(1, Uvarint), (2, Bytes), (3, Uvarint), (4, Uvarint)],
+        _ => {
+            return Ok(AccountState::Other {
+                account_type,
+                data: data.to_vec(),
+            })
+        }
+    };
+    let f = Fields::split("account state", data, schema)?;
+
+    Ok(match account_type {
+        t::IDENTITY => AccountState::Identity(ADI { url: f.string(2)? }),
+        t::TOKEN_ISSUER => AccountState::TokenIssuer(TokenIssuer {
+            url: f.string(2)?,
+            symbol: f.string(4)?,
+            precision: f.uvarint(5)?,
+            properties: f.string(6)?,
+            issued: f.big_number(7)?,
+            supply_limit: f.big_number(8)?,
+        }),
+        t::TOKEN_ACCOUNT => AccountState::TokenAccount(TokenAccount {
+            url: f.string(2)?,
+            token_url: f.string(4)?,
+            balance: f.big_number(5)?,
+        }),
+        t::LITE_TOKEN_ACCOUNT => AccountState::LiteTokenAccount(LiteTokenAccount {
+            url: f.string(2)?,
+            token_url: f.string(3)?,
+            balance: f.big_number(4)?,
+            lock_height: f.uvarint(5)?,
+        }),
+        t::KEY_BOOK => AccountState::KeyBook(KeyBook {
+            url: f.string(2)?,
+            book_type: match f.uvarint(3)? {
+                1 => BookType::Validator,
+                2 => BookType::Operator,
+                _ => BookType::Normal,
+            },
+            page_count: f.uvarint(5)?,
+        }),
+        t::LITE_DATA_ACCOUNT => {
+            AccountState::LiteDataAccount(LiteDataAccount { url: f.string(2)? })
+        }
+        _ => AccountState::LiteIdentity(LiteIdentity {
+            url: f.string(2)?,
+            credit_balance: f.uvarint(3)?,
+            last_used_on: f.uvarint(4)?,
+        }),
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::codec::{BinaryWriter, EncodingError};
+
+    fn section(section_type: u64, body: &[u8]) -> Vec<u8> {
+        let mut out = section_type.to_le_bytes().to_vec();
+        out.extend((body.len() as u64).to_le_bytes());
+        out.extend(body);
+        out
+    }
+
+    fn header_body(version: u64) -> Vec<u8> {
+        let mut w = BinaryWriter::new();
+        w.write_uvarint_field(version, 1).unwrap();
+        w.write_uvarint_field(1234, 2).unwrap();
+        w.write_hash_field(&[9u8; 32], 3).unwrap();
+        w.write_time_field(
+            &chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+            4,
+        )
+        .unwrap();
+        w.into_bytes()
+    }
+
+    fn token_account_record() -> Vec<u8> {
+        let mut w = BinaryWriter::new();
+        w.write_value_field(1, |main| -> Result<(), EncodingError> {
+            main.write_uvarint_field(account_types::TOKEN_ACCOUNT, 1)?;
+            main.write_string_field("acc://alice.acme/tokens", 2)?;
+            main.write_value_field(3, |auth| {
+                auth.write_string_field("acc://alice.acme/book", 1)
+            })?;
+            main.write_string_field("acc://ACME", 4)?;
+            main.write_big_number_field(&num_bigint::BigUint::from(150_000_000u64), 5)
+        })
+        .unwrap();
+        w.write_string_field("acc://0011@alice.acme/tokens", 3)
+            .unwrap();
+        w.write_string_field("acc://alice.acme/tokens", 5).unwrap();
+        w.write_value_field(6, |chain| {
+            chain.write_string_field("main", 1)?;
+            chain.write_uvarint_field(1, 2)?;
+            chain.write_uvarint_field(8, 3)?;
+            chain.write_value_field(4, |state| {
+                state.write_varint_field(3, 1)?;
+                state.write_bytes_field(&[], 2)?;
+                state.write_bytes_field(&[5u8; 32], 2)
+            })
+        })
+        .unwrap();
+        w.into_bytes()
+    }
+
+    fn lite_identity_record() -> Vec<u8> {
+        let mut w = BinaryWriter::new();
+        w.write_value_field(1, |main| {
+            main.write_uvarint_field(account_types::LITE_IDENTITY, 1)?;
+            main.write_string_field("acc://0011aabb", 2)?;
+            main.write_uvarint_field(500, 3)?;
+            main.write_uvarint_field(77, 4)
+        })
+        .unwrap();
+        w.write_string_field("acc://0011aabb", 5).unwrap();
+        w.into_bytes()
+    }
+
+    fn snapshot(version: u64) -> Vec<u8> {
+        let mut accounts = BinaryWriter::new();
+        accounts
+            .write_bytes_with_length(&token_account_record())
+            .unwrap();
+        accounts
+            .write_bytes_with_length(&lite_identity_record())
+            .unwrap();
+
+        let mut file = section(section_types::HEADER, &header_body(version));
+        file.extend(section(section_types::TRANSACTIONS, &[1, 2, 3]));
+        file.extend(section(section_types::ACCOUNTS, accounts.bytes()));
+        file
+    }
+
+    #[test]
+    fn test_reads_header_and_accounts() {
+        let data = snapshot(SNAPSHOT_VERSION);
+        let reader = SnapshotReader::new(&data).unwrap();
+        assert_eq!(reader.header().height, 1234);
+        assert_eq!(reader.header().root_hash, [9u8; 32]);
+        assert_eq!(
+            reader.header().timestamp.unwrap().timestamp(),
+            1_700_000_000
+        );
+        assert_eq!(reader.sections().len(), 3);
+
+        let accounts: Vec<_> = reader.accounts().collect::<Result<_, _>>().unwrap();
+        assert_eq!(accounts.len(), 2);
+
+        let tokens = &accounts[0];
+        assert_eq!(tokens.url, "acc://alice.acme/tokens");
+        assert_eq!(
+            tokens.pending,
+            vec!["acc://0011@alice.acme/tokens".to_string()]
+        );
+        assert_eq!(
+            tokens.state,
+            Some(AccountState::TokenAccount(TokenAccount {
+                url: "acc://alice.acme/tokens".to_string(),
+                token_url: "acc://ACME".to_string(),
+                balance: "150000000".to_string(),
+            }))
+        );
+        let chain = &tokens.chains[0];
+        assert_eq!(
+            (chain.name.as_str(), chain.chain_type, chain.mark_power),
+            ("main", 1, 8)
+        );
+        assert_eq!(chain.head.count, 3);
+        assert_eq!(chain.head.pending, vec![Vec::new(), vec![5u8; 32]]);
+
+        assert_eq!(
+            accounts[1].state,
+            Some(AccountState::LiteIdentity(LiteIdentity {
+                url: "acc://0011aabb".to_string(),
+                credit_balance: 500,
+                last_used_on: 77,
+            }))
+        );
+    }
+
+    #[test]
+    fn test_rejects_bad_files() {
+        assert!(matches!(
+            SnapshotReader::new(&snapshot(2)),
+            Err(SnapshotError::UnsupportedVersion(2))
+        ));
+        assert!(matches!(
+            SnapshotReader::new(&section(section_types::ACCOUNTS, &[])),
+            Err(SnapshotError::MissingHeader)
+        ));
+
+        let mut truncated = snapshot(SNAPSHOT_VERSION);
+        truncated.truncate(truncated.len() - 1);
+        assert!(matches!(
+            SnapshotReader::new(&truncated),
+            Err(SnapshotError::TruncatedSection {
+                section_type: section_types::ACCOUNTS,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn test_unknown_account_types_are_kept_raw() {
+        let mut w = BinaryWriter::new();
+        w.write_uvarint_field(account_types::KEY_PAGE, 1).unwrap();
+        w.write_string_field("acc://alice.acme/book/1", 2).unwrap();
+        let data = w.into_bytes();
+        assert_eq!(
+            decode_account_state(&data).unwrap(),
+            AccountState::Other {
+                account_type: account_types::KEY_PAGE,
+                data
+            }
+        );
+    }
+}
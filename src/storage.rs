@@ -0,0 +1,400 @@
+//! Pluggable key-value storage for SDK state
+//!
+//! Components that keep state between runs (the withdrawal processor, the
+//! oracle history cache, the transaction template library) persist through
+//! the [`KvStore`] trait, so an embedder can back them with whatever database
+//! it already runs. Three implementations ship with the SDK:
+//!
+//! - [`MemoryKvStore`]: a map, for tests and short-lived processes
+//! - [`FileKvStore`]: one file per key in a directory
+//! - `SledKvStore`: an embedded [sled](https://docs.rs/sled) database, behind
+//!   the `sled` feature
+//!
+//! Keys are `/`-separated strings such as `withdrawal/w-1`; values are
+//! opaque bytes, usually JSON written with [`put_json`].
+
+use crate::codec::hex_utils::parse_hex_field;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+/// Errors from a storage backend
+#[derive(Error, Debug)]
+pub enum StorageError {
+    /// Reading or writing the backing medium failed
+    #[error("Storage I/O error: {0}")]
+    Io(String),
+
+    /// A stored value could not be encoded or decoded
+    #[error("Storage encoding error for {key}: {reason}")]
+    Encoding {
+        /// Key of the value
+        key: String,
+        /// What went wrong
+        reason: String,
+    },
+
+    /// Backend-specific failure
+    #[error("Storage backend error: {0}")]
+    Backend(String),
+}
+
+/// A byte-oriented key-value store
+///
+/// `put` and `delete` must not return until the change is durable: callers
+/// such as the withdrawal processor rely on it to never sign twice.
+pub trait KvStore: Send + Sync {
+    /// Read the value stored under `key`
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError>;
+
+    /// Insert or replace the value under `key`
+    fn put(&mut self, key: &str, value: &[u8]) -> Result<(), StorageError>;
+
+    /// Remove `key`; removing a missing key is not an error
+    fn delete(&mut self, key: &str) -> Result<(), StorageError>;
+
+    /// Every entry whose key starts with `prefix`, ordered by key
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, StorageError>;
+}
+
+impl<S: KvStore + ?Sized> KvStore for Box<S> {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        (**self).get(key)
+    }
+
+    fn put(&mut self, key: &str, value: &[u8]) -> Result<(), StorageError> {
+        (**self).put(key, value)
+    }
+
+    fn delete(&mut self, key: &str) -> Result<(), StorageError> {
+        (**self).delete(key)
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, StorageError> {
+        (**self).scan_prefix(prefix)
+    }
+}
+
+/// Read and decode a JSON value
+pub fn get_json<T: DeserializeOwned>(
+    store: &(impl KvStore + ?Sized),
+    key: &str,
+) -> Result<Option<T>, StorageError> {
+    store.get(key)?.map(|bytes| decode(key, &bytes)).transpose()
+}
+
+/// Encode and store a JSON value
+pub fn put_json<T: Serialize>(
+    store: &mut (impl KvStore + ?Sized),
+    key: &str,
+    value: &T,
+) -> Result<(), StorageError> {
+    let bytes = serde_json::to_vec(value).map_err(|e| StorageError::Encoding {
+        key: key.to_string(),
+        reason: e.to_string(),
+    })?;
+    store.put(key, &bytes)
+}
+
+/// Read and decode every JSON value under `prefix`, ordered by key
+pub fn scan_json<T: DeserializeOwned>(
+    store: &(impl KvStore + ?Sized),
+    prefix: &str,
+) -> Result<Vec<T>, StorageError> {
+    store
+        .scan_prefix(prefix)?
+        .into_iter()
+        .map(|(key, bytes)| decode(&key, &bytes))
+        .collect()
+}
+
+fn decode<T: DeserializeOwned>(key: &str, bytes: &[u8]) -> Result<T, StorageError> {
+    serde_json::from_slice(bytes).map_err(|e| StorageError::Encoding {
+        key: key.to_string(),
+        reason: e.to_string(),
+    })
+}
+
+/// In-memory store, for tests and for processes that need no persistence
+#[derive(Debug, Clone, Default)]
+pub struct MemoryKvStore {
+    entries: BTreeMap<String, Vec<u8>>,
+}
+
+impl MemoryKvStore {
+    /// Create an empty store
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl KvStore for MemoryKvStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        Ok(self.entries.get(key).cloned())
+    }
+
+    fn put(&mut self, key: &str, value: &[u8]) -> Result<(), StorageError> {
+        self.entries.insert(key.to_string(), value.to_vec());
+        Ok(())
+    }
+
+    fn delete(&mut self, key: &str) -> Result<(), StorageError> {
+        self.entries.remove(key);
+        Ok(())
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, StorageError> {
+        Ok(self
+            .entries
+            .range(prefix.to_string()..)
+            .take_while(|(key, _)| key.starts_with(prefix))
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect())
+    }
+}
+
+/// Store keeping one file per key in a directory
+///
+/// File names are the hex-encoded keys, so any key is safe to use. Each
+/// write goes through a temporary file, `fsync` and a rename, which keeps
+/// every value consistent across crashes. The directory belongs to the
+/// store: a scan fails on any other file in it.
+#[derive(Debug, Clone)]
+pub struct FileKvStore {
+    dir: PathBuf,
+}
+
+impl FileKvStore {
+    /// Open the store in `dir`, creating the directory if needed
+    pub fn open(dir: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let dir = dir.as_ref().to_path_buf();
+        std::fs::create_dir_all(&dir).map_err(|e| io_error(&dir, &e))?;
+        Ok(Self { dir })
+    }
+
+    /// Directory holding the entries
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn path(&self, key: &str) -> PathBuf {
+        self.dir.join(hex::encode(key))
+    }
+}
+
+impl KvStore for FileKvStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        let path = self.path(key);
+        match std::fs::read(&path) {
+            Ok(bytes) => Ok(Some(bytes)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(io_error(&path, &e)),
+        }
+    }
+
+    fn put(&mut self, key: &str, value: &[u8]) -> Result<(), StorageError> {
+        use std::io::Write;
+
+        let path = self.path(key);
+        let tmp = path.with_extension("tmp");
+        let write = || -> std::io::Result<()> {
+            let mut file = std::fs::File::create(&tmp)?;
+            file.write_all(value)?;
+            file.sync_all()?;
+            std::fs::rename(&tmp, &path)
+        };
+        write().map_err(|e| io_error(&path, &e))
+    }
+
+    fn delete(&mut self, key: &str) -> Result<(), StorageError> {
+        let path = self.path(key);
+        match std::fs::remove_file(&path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(io_error(&path, &e)),
+        }
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, StorageError> {
+        let entries = std::fs::read_dir(&self.dir).map_err(|e| io_error(&self.dir, &e))?;
+        let mut keys = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| io_error(&self.dir, &e))?;
+            let path = entry.path();
+            // Left behind by a write interrupted before its rename
+            if path.extension().is_some_and(|ext| ext == "tmp") {
+                continue;
+            }
+            let name = entry.file_name();
+            let encoding_error = |reason: String| StorageError::Encoding {
+                key: name.to_string_lossy().into_owned(),
+                reason,
+            };
+            let name_str = name
+                .to_str()
+                .ok_or_else(|| encoding_error("file name is not UTF-8".to_string()))?;
+            let bytes = parse_hex_field("file name", name_str)
+                .map_err(|e| encoding_error(e.to_string()))?;
+            let key = String::from_utf8(bytes).map_err(|e| encoding_error(e.to_string()))?;
+            if key.starts_with(prefix) {
+                keys.push(key);
+            }
+        }
+        keys.sort();
+
+        let mut found = Vec::with_capacity(keys.len());
+        for key in keys {
+            // A concurrent delete between listing and reading drops the entry
+            if let Some(value) = self.get(&key)? {
+                found.push((key, value));
+            }
+        }
+        Ok(found)
+    }
+}
+
+fn io_error(path: &Path, e: &std::io::Error) -> StorageError {
+    StorageError::Io(format!("{}: {e}", path.display()))
+}
+
+/// Store backed by an embedded sled database
+#[cfg(feature = "sled")]
+#[derive(Debug, Clone)]
+pub struct SledKvStore {
+    db: sled::Db,
+}
+
+#[cfg(feature = "sled")]
+impl SledKvStore {
+    /// Open or create the database at `path`
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, StorageError> {
+        let db = sled::open(path).map_err(|e| StorageError::Backend(e.to_string()))?;
+        Ok(Self { db })
+    }
+
+    /// Wrap an already open database
+    #[must_use]
+    pub const fn from_db(db: sled::Db) -> Self {
+        Self { db }
+    }
+}
+
+#[cfg(feature = "sled")]
+impl KvStore for SledKvStore {
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, StorageError> {
+        self.db
+            .get(key)
+            .map(|value| value.map(|v| v.to_vec()))
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    fn put(&mut self, key: &str, value: &[u8]) -> Result<(), StorageError> {
+        self.db
+            .insert(key, value)
+            .and_then(|_| self.db.flush())
+            .map(|_| ())
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    fn delete(&mut self, key: &str) -> Result<(), StorageError> {
+        self.db
+            .remove(key)
+            .and_then(|_| self.db.flush())
+            .map(|_| ())
+            .map_err(|e| StorageError::Backend(e.to_string()))
+    }
+
+    fn scan_prefix(&self, prefix: &str) -> Result<Vec<(String, Vec<u8>)>, StorageError> {
+        self.db
+            .scan_prefix(prefix)
+            .map(|entry| {
+                let (key, value) = entry.map_err(|e| StorageError::Backend(e.to_string()))?;
+                let key = String::from_utf8(key.to_vec())
+                    .map_err(|e| StorageError::Backend(e.to_string()))?;
+                Ok((key, value.to_vec()))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    fn exercise(store: &mut dyn KvStore) {
+        assert_eq!(store.get("a/1").unwrap(), None);
+        store.put("a/2", b"two").unwrap();
+        store.put("a/1", b"one").unwrap();
+        store.put("b/1", b"other").unwrap();
+        store.put("a/1", b"uno").unwrap();
+        assert_eq!(store.get("a/1").unwrap(), Some(b"uno".to_vec()));
+
+        let scanned = store.scan_prefix("a/").unwrap();
+        assert_eq!(
+            scanned,
+            vec![
+                ("a/1".to_string(), b"uno".to_vec()),
+                ("a/2".to_string(), b"two".to_vec())
+            ]
+        );
+
+        store.delete("a/1").unwrap();
+        store.delete("a/1").unwrap();
+        assert_eq!(store.get("a/1").unwrap(), None);
+        assert_eq!(store.scan_prefix("").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_memory_store() {
+        exercise(&mut MemoryKvStore::new());
+    }
+
+    #[test]
+    fn test_file_store() {
+        let dir = std::env::temp_dir().join(format!("kvstore-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        let mut store = FileKvStore::open(&dir).unwrap();
+        exercise(&mut store);
+
+        // Values survive reopening
+        let store = FileKvStore::open(&dir).unwrap();
+        assert_eq!(store.get("a/2").unwrap(), Some(b"two".to_vec()));
+
+        // Interrupted writes are skipped, foreign files are an error
+        std::fs::write(dir.join("612f33.tmp"), b"partial").unwrap();
+        assert_eq!(store.scan_prefix("").unwrap().len(), 2);
+        std::fs::write(dir.join("notes.txt"), b"foreign").unwrap();
+        assert!(matches!(
+            store.scan_prefix(""),
+            Err(StorageError::Encoding { .. })
+        ));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[cfg(feature = "sled")]
+    #[test]
+    fn test_sled_store() {
+        let db = sled::Config::new().temporary(true).open().unwrap();
+        exercise(&mut SledKvStore::from_db(db));
+    }
+
+    #[test]
+    fn test_json_helpers() {
+        let mut store: Box<dyn KvStore> = Box::new(MemoryKvStore::new());
+        put_json(&mut store, "n/1", &1u64).unwrap();
+        put_json(&mut store, "n/2", &2u64).unwrap();
+        assert_eq!(get_json::<u64>(&store, "n/2").unwrap(), Some(2));
+        assert_eq!(scan_json::<u64>(&store, "n/").unwrap(), vec![1, 2]);
+
+        store.put("n/3", b"not json").unwrap();
+        assert!(matches!(
+            scan_json::<u64>(&store, "n/"),
+            Err(StorageError::Encoding { .. })
+        ));
+    }
+}
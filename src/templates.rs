@@ -0,0 +1,549 @@
+//! Transaction templates for recurring operations
+//!
+//! A [`TxTemplate`] is a transaction body plus header options in which any
+//! string may contain `{{name}}` placeholders. Templates are plain JSON, so an
+//! application can define its recurring operations (payroll runs, periodic
+//! data writes) declaratively, keep them in a [`TemplateLibrary`], and turn
+//! one into a ready-to-sign transaction with [`TxTemplate::instantiate`]:
+//!
+//! ```text
+//! {
+//!   "name": "payroll",
+//!   "body": {"type": "sendTokens", "to": [{"url": "{{employee}}", "amount": "{{amount}}"}]},
+//!   "header": {"memo": "salary {{month}}"},
+//!   "defaults": {"amount": "500000000"}
+//! }
+//! ```
+//!
+//! A string that is exactly one placeholder is replaced by the argument value
+//! itself, so numbers and objects keep their type (`"minorBlock":
+//! "{{block}}"` becomes a number); placeholders inside longer strings are
+//! replaced by the argument's text.
+
+use crate::helpers::HeaderOptions;
+use crate::storage::{get_json, put_json, scan_json, KvStore, StorageError};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet};
+use thiserror::Error;
+
+/// Errors defining or instantiating a template
+#[derive(Error, Debug)]
+pub enum TemplateError {
+    /// A placeholder has neither an argument nor a default
+    #[error("Template {template}: missing argument {name}")]
+    MissingArgument {
+        /// Template name
+        template: String,
+        /// Placeholder name
+        name: String,
+    },
+
+    /// An argument does not match any placeholder
+    #[error("Template {template}: unknown argument {name}")]
+    UnknownArgument {
+        /// Template name
+        template: String,
+        /// Argument name
+        name: String,
+    },
+
+    /// A placeholder is not closed or has an invalid name
+    #[error("Template {template}: malformed placeholder in {text:?}")]
+    Malformed {
+        /// Template name
+        template: String,
+        /// The offending string
+        text: String,
+    },
+
+    /// The header does not describe valid header options once filled in
+    #[error("Template {template}: invalid header: {reason}")]
+    InvalidHeader {
+        /// Template name
+        template: String,
+        /// What is wrong with it
+        reason: String,
+    },
+
+    /// No template with that name in the library
+    #[error("Template {0} not found")]
+    NotFound(String),
+
+    /// Reading or writing the library failed
+    #[error(transparent)]
+    Storage(#[from] StorageError),
+}
+
+/// Header options of a template, in their JSON form
+///
+/// Every field may hold placeholders. `metadata` is hex-encoded.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateHeader {
+    /// Memo text
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<Value>,
+    /// Metadata bytes, hex-encoded
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub metadata: Option<Value>,
+    /// Expiration, as `{"atTime": <unix seconds>}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub expire: Option<Value>,
+    /// Delayed execution, as `{"minorBlock": <block>}`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hold_until: Option<Value>,
+    /// Additional signing authorities
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub authorities: Option<Value>,
+}
+
+/// A stored transaction with `{{name}}` placeholders
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxTemplate {
+    /// Unique name, used as the library key
+    pub name: String,
+    /// What the template is for
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Principal account; may hold placeholders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub principal: Option<String>,
+    /// Transaction body
+    pub body: Value,
+    /// Header options
+    #[serde(default)]
+    pub header: TemplateHeader,
+    /// Values for placeholders the caller does not supply
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub defaults: BTreeMap<String, Value>,
+}
+
+/// A template filled in with arguments, ready to sign
+#[derive(Debug, Clone)]
+pub struct TxInstance {
+    /// Principal account, if the template names one
+    pub principal: Option<String>,
+    /// Transaction body
+    pub body: Value,
+    /// Header options for [`SmartSigner::sign_with_options`](crate::SmartSigner::sign_with_options)
+    pub options: HeaderOptions,
+}
+
+impl TxTemplate {
+    /// Create a template for `body` with no header options
+    pub fn new(name: impl Into<String>, body: Value) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            principal: None,
+            body,
+            header: TemplateHeader::default(),
+            defaults: BTreeMap::new(),
+        }
+    }
+
+    /// Set the description
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Set the principal account
+    #[must_use]
+    pub fn with_principal(mut self, principal: impl Into<String>) -> Self {
+        self.principal = Some(principal.into());
+        self
+    }
+
+    /// Set the header options
+    #[must_use]
+    pub fn with_header(mut self, header: TemplateHeader) -> Self {
+        self.header = header;
+        self
+    }
+
+    /// Set a default argument value
+    #[must_use]
+    pub fn with_default(mut self, name: impl Into<String>, value: impl Into<Value>) -> Self {
+        self.defaults.insert(name.into(), value.into());
+        self
+    }
+
+    /// Names of every placeholder in the template
+    pub fn placeholders(&self) -> Result<BTreeSet<String>, TemplateError> {
+        let mut names = BTreeSet::new();
+        let header = serde_json::to_value(&self.header).unwrap_or(Value::Null);
+        let principal = self.principal.clone().map(Value::String);
+        for value in [&self.body, &header].into_iter().chain(principal.as_ref()) {
+            self.collect_placeholders(value, &mut names)?;
+        }
+        Ok(names)
+    }
+
+    /// Placeholders that have no default and must be supplied as arguments
+    pub fn required_arguments(&self) -> Result<BTreeSet<String>, TemplateError> {
+        let mut names = self.placeholders()?;
+        names.retain(|name| !self.defaults.contains_key(name));
+        Ok(names)
+    }
+
+    /// Fill in the placeholders with `args`, falling back to the defaults
+    ///
+    /// Every placeholder must be resolved, and every argument must match a
+    /// placeholder, so typos are caught rather than silently ignored.
+    pub fn instantiate(&self, args: &BTreeMap<String, Value>) -> Result<TxInstance, TemplateError> {
+        let placeholders = self.placeholders()?;
+        if let Some(name) = args.keys().find(|name| !placeholders.contains(*name)) {
+            return Err(TemplateError::UnknownArgument {
+                template: self.name.clone(),
+                name: name.clone(),
+            });
+        }
+
+        let mut values = self.defaults.clone();
+        values.extend(args.iter().map(|(k, v)| (k.clone(), v.clone())));
+
+        let body = self.substitute(&self.body, &values)?;
+        let principal = match &self.principal {
+            Some(p) => Some(text(&self.substitute(&Value::String(p.clone()), &values)?)),
+            None => None,
+        };
+        let header = serde_json::to_value(&self.header).map_err(|e| self.invalid_header(&e))?;
+        let header: TemplateHeader = serde_json::from_value(self.substitute(&header, &values)?)
+            .map_err(|e| self.invalid_header(&e))?;
+
+        Ok(TxInstance {
+            principal,
+            body,
+            options: self.header_options(header)?,
+        })
+    }
+
+    fn header_options(&self, header: TemplateHeader) -> Result<HeaderOptions, TemplateError> {
+        let metadata = header
+            .metadata
+            .map(|m| hex::decode(text(&m)))
+            .transpose()
+            .map_err(|e| self.invalid_header(&format!("metadata: {e}")))?;
+        Ok(HeaderOptions {
+            memo: header.memo.map(|m| text(&m)),
+            metadata,
+            expire: from_json(header.expire)
+                .map_err(|e| self.invalid_header(&format!("expire: {e}")))?,
+            hold_until: from_json(header.hold_until)
+                .map_err(|e| self.invalid_header(&format!("holdUntil: {e}")))?,
+            authorities: from_json(header.authorities)
+                .map_err(|e| self.invalid_header(&format!("authorities: {e}")))?,
+        })
+    }
+
+    fn invalid_header(&self, reason: &dyn std::fmt::Display) -> TemplateError {
+        TemplateError::InvalidHeader {
+            template: self.name.clone(),
+            reason: reason.to_string(),
+        }
+    }
+
+    fn collect_placeholders(
+        &self,
+        value: &Value,
+        names: &mut BTreeSet<String>,
+    ) -> Result<(), TemplateError> {
+        match value {
+            Value::String(s) => {
+                for segment in self.parse(s)? {
+                    if let Segment::Placeholder(name) = segment {
+                        names.insert(name.to_string());
+                    }
+                }
+            }
+            Value::Array(items) => {
+                for item in items {
+                    self.collect_placeholders(item, names)?;
+                }
+            }
+            Value::Object(map) => {
+                for item in map.values() {
+                    self.collect_placeholders(item, names)?;
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn substitute(
+        &self,
+        value: &Value,
+        values: &BTreeMap<String, Value>,
+    ) -> Result<Value, TemplateError> {
+        let lookup = |name: &str| {
+            values
+                .get(name)
+                .ok_or_else(|| TemplateError::MissingArgument {
+                    template: self.name.clone(),
+                    name: name.to_string(),
+                })
+        };
+        Ok(match value {
+            Value::String(s) => {
+                let segments = self.parse(s)?;
+                if let [Segment::Placeholder(name)] = segments.as_slice() {
+                    lookup(name)?.clone()
+                } else {
+                    let mut out = String::with_capacity(s.len());
+                    for segment in segments {
+                        match segment {
+                            Segment::Text(t) => out.push_str(t),
+                            Segment::Placeholder(name) => out.push_str(&text(lookup(name)?)),
+                        }
+                    }
+                    Value::String(out)
+                }
+            }
+            Value::Array(items) => Value::Array(
+                items
+                    .iter()
+                    .map(|item| self.substitute(item, values))
+                    .collect::<Result<_, _>>()?,
+            ),
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(k, v)| Ok((k.clone(), self.substitute(v, values)?)))
+                    .collect::<Result<Map<_, _>, TemplateError>>()?,
+            ),
+            other => other.clone(),
+        })
+    }
+
+    fn parse<'s>(&self, s: &'s str) -> Result<Vec<Segment<'s>>, TemplateError> {
+        let malformed = || TemplateError::Malformed {
+            template: self.name.clone(),
+            text: s.to_string(),
+        };
+        let mut segments = Vec::new();
+        let mut rest = s;
+        while let Some(open) = rest.find("{{") {
+            if open > 0 {
+                segments.push(Segment::Text(&rest[..open]));
+            }
+            let after = &rest[open + 2..];
+            let close = after.find("}}").ok_or_else(malformed)?;
+            let name = after[..close].trim();
+            let valid = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '_' | '-' | '.'));
+            if !valid {
+                return Err(malformed());
+            }
+            segments.push(Segment::Placeholder(name));
+            rest = &after[close + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest));
+        }
+        Ok(segments)
+    }
+}
+
+enum Segment<'s> {
+    Text(&'s str),
+    Placeholder(&'s str),
+}
+
+fn from_json<T: DeserializeOwned>(value: Option<Value>) -> Result<Option<T>, serde_json::Error> {
+    value.map(serde_json::from_value).transpose()
+}
+
+/// Text of a JSON value: strings without quotes, anything else as JSON
+fn text(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        other => other.to_string(),
+    }
+}
+
+/// Named templates kept in a [`KvStore`] under `template/`
+#[derive(Debug, Clone, Default)]
+pub struct TemplateLibrary<S: KvStore> {
+    kv: S,
+}
+
+impl<S: KvStore> TemplateLibrary<S> {
+    const PREFIX: &'static str = "template/";
+
+    /// Keep templates in `kv`
+    pub const fn new(kv: S) -> Self {
+        Self { kv }
+    }
+
+    /// The underlying key-value store
+    pub const fn kv(&self) -> &S {
+        &self.kv
+    }
+
+    /// Insert or replace a template, after checking its placeholders parse
+    pub fn save(&mut self, template: &TxTemplate) -> Result<(), TemplateError> {
+        template.placeholders()?;
+        put_json(&mut self.kv, &Self::key(&template.name), template)?;
+        Ok(())
+    }
+
+    /// Look up a template by name
+    pub fn get(&self, name: &str) -> Result<Option<TxTemplate>, TemplateError> {
+        Ok(get_json(&self.kv, &Self::key(name))?)
+    }
+
+    /// Every template, ordered by name
+    pub fn list(&self) -> Result<Vec<TxTemplate>, TemplateError> {
+        Ok(scan_json(&self.kv, Self::PREFIX)?)
+    }
+
+    /// Remove a template; removing a missing one is not an error
+    pub fn remove(&mut self, name: &str) -> Result<(), TemplateError> {
+        Ok(self.kv.delete(&Self::key(name))?)
+    }
+
+    /// Instantiate the named template with `args`
+    pub fn instantiate(
+        &self,
+        name: &str,
+        args: &BTreeMap<String, Value>,
+    ) -> Result<TxInstance, TemplateError> {
+        self.get(name)?
+            .ok_or_else(|| TemplateError::NotFound(name.to_string()))?
+            .instantiate(args)
+    }
+
+    fn key(name: &str) -> String {
+        format!("{}{name}", Self::PREFIX)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::TxBody;
+    use crate::storage::MemoryKvStore;
+    use serde_json::json;
+
+    fn payroll() -> TxTemplate {
+        TxTemplate::new(
+            "payroll",
+            TxBody::send_tokens_single("{{employee}}", "{{amount}}"),
+        )
+        .with_principal("acc://acme-corp.acme/payroll")
+        .with_header(TemplateHeader {
+            memo: Some(json!("salary {{month}}")),
+            hold_until: Some(json!({"minorBlock": "{{block}}"})),
+            ..TemplateHeader::default()
+        })
+        .with_default("amount", "500000000")
+    }
+
+    fn args(pairs: &[(&str, Value)]) -> BTreeMap<String, Value> {
+        pairs
+            .iter()
+            .map(|(k, v)| ((*k).to_string(), v.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_instantiate() {
+        let template = payroll();
+        assert_eq!(
+            template
+                .required_arguments()
+                .unwrap()
+                .into_iter()
+                .collect::<Vec<_>>(),
+            vec!["block", "employee", "month"]
+        );
+
+        let instance = template
+            .instantiate(&args(&[
+                ("employee", json!("acc://bob.acme/tokens")),
+                ("month", json!("2024-06")),
+                ("block", json!(1234)),
+            ]))
+            .unwrap();
+        assert_eq!(
+            instance.principal.as_deref(),
+            Some("acc://acme-corp.acme/payroll")
+        );
+        assert_eq!(instance.body["to"][0]["url"], "acc://bob.acme/tokens");
+        assert_eq!(instance.body["to"][0]["amount"], "500000000");
+        assert_eq!(instance.options.memo.as_deref(), Some("salary 2024-06"));
+        assert_eq!(instance.options.hold_until.unwrap().minor_block, Some(1234));
+    }
+
+    #[test]
+    fn test_instantiate_errors() {
+        let template = payroll();
+        assert!(matches!(
+            template.instantiate(&args(&[("employee", json!("acc://bob.acme/tokens"))])),
+            Err(TemplateError::MissingArgument { name, .. }) if name == "block" || name == "month"
+        ));
+        assert!(matches!(
+            template.instantiate(&args(&[("employe", json!("x"))])),
+            Err(TemplateError::UnknownArgument { name, .. }) if name == "employe"
+        ));
+
+        let bad = TxTemplate::new("bad", json!({"type": "writeData", "memo": "{{unclosed"}));
+        assert!(matches!(
+            bad.placeholders(),
+            Err(TemplateError::Malformed { .. })
+        ));
+
+        let block = args(&[
+            ("employee", json!("acc://bob.acme/tokens")),
+            ("month", json!("2024-06")),
+            ("block", json!("soon")),
+        ]);
+        assert!(matches!(
+            template.instantiate(&block),
+            Err(TemplateError::InvalidHeader { .. })
+        ));
+    }
+
+    #[test]
+    fn test_library() {
+        let mut library = TemplateLibrary::new(MemoryKvStore::new());
+        library.save(&payroll()).unwrap();
+        library
+            .save(
+                &TxTemplate::new(
+                    "ping",
+                    json!({"type": "writeData", "entry": {"type": "doublehash", "data": ["{{reading}}"]}}),
+                )
+                .with_description("sensor reading, hex-encoded"),
+            )
+            .unwrap();
+
+        let names: Vec<_> = library
+            .list()
+            .unwrap()
+            .into_iter()
+            .map(|t| t.name)
+            .collect();
+        assert_eq!(names, vec!["payroll", "ping"]);
+        assert_eq!(library.get("payroll").unwrap(), Some(payroll()));
+
+        let instance = library
+            .instantiate("ping", &args(&[("reading", json!(hex::encode("21.5C")))]))
+            .unwrap();
+        assert_eq!(instance.body["entry"]["data"][0], hex::encode("21.5C"));
+
+        library.remove("ping").unwrap();
+        assert!(matches!(
+            library.instantiate("ping", &BTreeMap::new()),
+            Err(TemplateError::NotFound(_))
+        ));
+    }
+}
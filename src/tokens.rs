@@ -0,0 +1,287 @@
+//! Token symbol and issuer lookup
+//!
+//! Token accounts name their token by issuer URL (`acc://ACME`,
+//! `acc://alice.acme/coin`), while wallets show a symbol.
+//! [`AccumulateClient::token_info`] reads the issuer behind a URL, and
+//! [`AccumulateClient::resolve_token`] goes the other way, from a symbol to
+//! its issuer:
+//!
+//! ```no_run
+//! # async fn run(client: &accumulate_client::AccumulateClient) -> Result<(), Box<dyn std::error::Error>> {
+//! use accumulate_client::tokens::TokenRegistry;
+//!
+//! let coin = client.token_info("acc://alice.acme/coin").await?;
+//! println!("{} has {} decimals", coin.symbol, coin.precision);
+//!
+//! let registry = TokenRegistry::new()
+//!     .with_token("USDX", "acc://stable.acme/usdx")
+//!     .with_identity("acc://alice.acme");
+//! let coin = client.resolve_token_in(&registry, "COIN").await?;
+//! # let _ = coin;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! Symbols are not unique on the network and nothing indexes them, so a
+//! symbol resolves through a [`TokenRegistry`]: the tokens it lists, then a
+//! root issuer named after the symbol (the way `acc://ACME` is), then the
+//! issuers directly under the identities it lists.
+
+use crate::acc_url::AccUrl;
+use crate::accounts::{AccountDecodeError, AccountState, TokenIssuer};
+use crate::activity::ACME_TOKEN_URL;
+use crate::client::AccumulateClient;
+use crate::json_rpc_client::JsonRpcError;
+use crate::types::{DirectoryQuery, RangeOptions};
+use std::collections::BTreeMap;
+use thiserror::Error;
+
+/// Why a token could not be looked up
+#[derive(Error, Debug)]
+pub enum TokenError {
+    /// A query failed
+    #[error(transparent)]
+    Network(#[from] JsonRpcError),
+
+    /// The account did not decode
+    #[error(transparent)]
+    Decode(#[from] AccountDecodeError),
+
+    /// The account exists but is not a token issuer
+    #[error("{url} is a {account_type} account, not a token issuer")]
+    NotAnIssuer {
+        /// Account URL
+        url: String,
+        /// The account's `type`
+        account_type: String,
+    },
+
+    /// A registered issuer has a different symbol
+    #[error("{url} issues {found}, not {expected}")]
+    SymbolMismatch {
+        /// Issuer URL from the registry
+        url: String,
+        /// Symbol looked up
+        expected: String,
+        /// Symbol the issuer reports
+        found: String,
+    },
+
+    /// No issuer with the symbol was found
+    #[error("No token issuer found for {0}")]
+    NotFound(String),
+}
+
+/// Known token issuers by symbol, and identities to search for others
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenRegistry {
+    tokens: BTreeMap<String, String>,
+    identities: Vec<String>,
+}
+
+impl Default for TokenRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TokenRegistry {
+    /// Registry that knows ACME
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            tokens: BTreeMap::from([("ACME".to_string(), ACME_TOKEN_URL.to_string())]),
+            identities: Vec::new(),
+        }
+    }
+
+    /// Register the issuer of `symbol`, replacing any earlier one
+    #[must_use]
+    pub fn with_token(mut self, symbol: &str, url: &str) -> Self {
+        self.tokens
+            .insert(symbol.to_ascii_uppercase(), url.to_string());
+        self
+    }
+
+    /// Search the token issuers directly under `identity`
+    #[must_use]
+    pub fn with_identity(mut self, identity: &str) -> Self {
+        self.identities.push(identity.to_string());
+        self
+    }
+
+    /// Registered issuer URL of `symbol`, ignoring case
+    #[must_use]
+    pub fn url_for(&self, symbol: &str) -> Option<&str> {
+        self.tokens
+            .get(&symbol.to_ascii_uppercase())
+            .map(String::as_str)
+    }
+
+    /// Registered symbol of the issuer at `url`, ignoring case
+    #[must_use]
+    pub fn symbol_for(&self, url: &str) -> Option<&str> {
+        let url = AccUrl::parse(url).ok()?;
+        self.tokens
+            .iter()
+            .find(|(_, issuer)| url == issuer.as_str())
+            .map(|(symbol, _)| symbol.as_str())
+    }
+}
+
+impl AccumulateClient {
+    /// The token issuer at `url`
+    pub async fn token_info(&self, url: &str) -> Result<TokenIssuer, TokenError> {
+        let record = self.v3().query_account(url).await?;
+        issuer(url, record.state()?)
+    }
+
+    /// The issuer of `symbol`, looked up with [`TokenRegistry::new`]
+    pub async fn resolve_token(&self, symbol: &str) -> Result<TokenIssuer, TokenError> {
+        self.resolve_token_in(&TokenRegistry::new(), symbol).await
+    }
+
+    /// The issuer of `symbol`, looked up with `registry`
+    ///
+    /// A registered issuer must report the symbol. Otherwise the root
+    /// issuer `acc://<symbol>` and then the issuers under the registry's
+    /// identities are tried, and the first whose symbol matches is returned.
+    pub async fn resolve_token_in(
+        &self,
+        registry: &TokenRegistry,
+        symbol: &str,
+    ) -> Result<TokenIssuer, TokenError> {
+        if let Some(url) = registry.url_for(symbol) {
+            let token = self.token_info(url).await?;
+            if !token.symbol.eq_ignore_ascii_case(symbol) {
+                return Err(TokenError::SymbolMismatch {
+                    url: url.to_string(),
+                    expected: symbol.to_string(),
+                    found: token.symbol,
+                });
+            }
+            return Ok(token);
+        }
+
+        if !symbol.is_empty() && symbol.chars().all(|c| c.is_ascii_alphanumeric()) {
+            match self.token_info(&format!("acc://{symbol}")).await {
+                Ok(token) if token.symbol.eq_ignore_ascii_case(symbol) => return Ok(token),
+                Ok(_) | Err(TokenError::NotAnIssuer { .. }) => {}
+                Err(TokenError::Network(e)) if e.is_not_found() => {}
+                Err(e) => return Err(e),
+            }
+        }
+
+        for identity in &registry.identities {
+            let query = DirectoryQuery {
+                range: Some(RangeOptions {
+                    expand: Some(true),
+                    ..Default::default()
+                }),
+            };
+            let directory = self.v3().query_directory(identity, query).await?;
+            for entry in directory.records {
+                let state = match entry.account {
+                    Some(account) => AccountState::from_account(&account)?,
+                    None => self.v3().query_account(&entry.url).await?.state()?,
+                };
+                if let AccountState::TokenIssuer(token) = state {
+                    if token.symbol.eq_ignore_ascii_case(symbol) {
+                        return Ok(token);
+                    }
+                }
+            }
+        }
+        Err(TokenError::NotFound(symbol.to_string()))
+    }
+}
+
+fn issuer(url: &str, state: AccountState) -> Result<TokenIssuer, TokenError> {
+    match state {
+        AccountState::TokenIssuer(token) => Ok(token),
+        other => Err(TokenError::NotAnIssuer {
+            url: url.to_string(),
+            account_type: other.to_value()["type"]
+                .as_str()
+                .unwrap_or("unknown")
+                .to_string(),
+        }),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::helpers::{QuickStart, SmartSigner, TxBody};
+    use crate::simulator::SimulatedNetwork;
+    use crate::ACME_BASE_UNITS;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_registry_lookups() {
+        let registry = TokenRegistry::new().with_token("usdx", "acc://stable.acme/usdx");
+        assert_eq!(registry.url_for("acme"), Some("acc://ACME"));
+        assert_eq!(registry.url_for("USDX"), Some("acc://stable.acme/usdx"));
+        assert_eq!(registry.symbol_for("acc://acme/"), Some("ACME"));
+        assert_eq!(registry.symbol_for("acc://stable.acme/USDX"), Some("USDX"));
+        assert_eq!(registry.symbol_for("acc://other.acme/usdx"), None);
+    }
+
+    #[tokio::test]
+    async fn test_resolve_against_simulator() {
+        let network = SimulatedNetwork::new();
+        let quick = QuickStart::from_client(Arc::new(network.client().await.unwrap()));
+        let client = quick.client();
+
+        let acme = client.resolve_token("acme").await.unwrap();
+        assert_eq!((acme.url.as_str(), acme.precision), ("acc://ACME", 8));
+
+        let wallet = quick.create_wallet();
+        network
+            .fund(&wallet.lite_token_account, 100 * ACME_BASE_UNITS)
+            .unwrap();
+        let adi = quick.setup_adi(&wallet, "issuer").await.unwrap();
+        assert!(
+            quick
+                .buy_credits_for_adi(&wallet, &adi, 100)
+                .await
+                .unwrap()
+                .success
+        );
+        let mut signer = SmartSigner::new(client, adi.keypair().clone(), &adi.key_page_url);
+        let created = signer
+            .sign_submit_and_wait(
+                &adi.url,
+                &TxBody::create_token("acc://issuer.acme/coin", "COIN", 4, Some("1000000")),
+                None,
+                5,
+            )
+            .await;
+        assert!(created.success, "{:?}", created.error);
+
+        let coin = client.token_info("acc://issuer.acme/coin").await.unwrap();
+        assert_eq!((coin.symbol.as_str(), coin.precision), ("COIN", 4));
+        assert_eq!(coin.remaining_supply(), Some(1_000_000));
+
+        // Only found by searching the identity
+        assert!(matches!(
+            client.resolve_token("coin").await,
+            Err(TokenError::NotFound(_))
+        ));
+        let registry = TokenRegistry::new().with_identity(&adi.url);
+        let found = client.resolve_token_in(&registry, "coin").await.unwrap();
+        assert_eq!(found, coin);
+
+        // A registry entry pointing at the wrong issuer
+        let registry = TokenRegistry::new().with_token("COIN", "acc://ACME");
+        assert!(matches!(
+            client.resolve_token_in(&registry, "coin").await,
+            Err(TokenError::SymbolMismatch { .. })
+        ));
+        assert!(matches!(
+            client.token_info(&adi.key_page_url).await,
+            Err(TokenError::NotAnIssuer { .. })
+        ));
+    }
+}
@@ -0,0 +1,367 @@
+//! Typed, paged transaction history from the V2 `query-tx-history` method
+//!
+//! [`AccumulateClient::query_tx_history`] fetches one page of an account's
+//! history and parses each item into a [`TxHistoryEntry`]: the transaction
+//! type plus, for token movements, the direction, amount and counterparty
+//! relative to the queried account. [`AccumulateClient::tx_history`] returns a
+//! [`TxHistory`] pager that keeps requesting pages until the history is
+//! exhausted, optionally narrowed to some transaction types with a
+//! [`TxTypeFilter`].
+
+use crate::activity::{extract_transfers, Direction};
+use crate::client::AccumulateClient;
+use crate::json_rpc_client::JsonRpcError;
+use crate::watch::TxTypeFilter;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::VecDeque;
+
+/// Entries requested per page when no page size is set
+pub const DEFAULT_PAGE_SIZE: u64 = 50;
+
+/// One transaction from an account's history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxHistoryEntry {
+    /// Transaction ID (`acc://<hash>@<principal>`), when the node reports it
+    pub txid: Option<String>,
+    /// Hex transaction hash
+    pub hash: String,
+    /// Transaction type, e.g. `sendTokens` or `syntheticDepositTokens`
+    pub tx_type: String,
+    /// Principal of the transaction
+    pub principal: String,
+    /// Whether tokens came in or went out; `None` for non-token transactions
+    pub direction: Option<Direction>,
+    /// Total amount moved, in base units
+    pub amount: Option<u128>,
+    /// The other side of the movement; `None` when there are several
+    pub counterparty: Option<String>,
+    /// The transaction as returned by the node (`header` and `body`)
+    pub transaction: Value,
+}
+
+/// One page of an account's transaction history
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxHistoryPage {
+    /// Index of the first entry of the page
+    pub start: u64,
+    /// Total number of entries in the history, when the node reports it
+    pub total: Option<u64>,
+    /// Entries of the page, in the order returned by the node
+    pub items: Vec<TxHistoryEntry>,
+}
+
+impl TxHistoryPage {
+    /// Whether no entries follow this page when pages of `count` are requested
+    #[must_use]
+    pub fn is_last(&self, count: u64) -> bool {
+        let fetched = self.items.len() as u64;
+        fetched == 0
+            || fetched < count
+            || self
+                .total
+                .is_some_and(|total| self.start + fetched >= total)
+    }
+}
+
+impl AccumulateClient {
+    /// Fetch `count` history entries of `url` starting at index `start`
+    pub async fn query_tx_history(
+        &self,
+        url: &str,
+        start: u64,
+        count: u64,
+    ) -> Result<TxHistoryPage, JsonRpcError> {
+        let response: Value = self
+            .v2_client
+            .call(
+                "query-tx-history",
+                json!({ "url": url, "start": start, "count": count }),
+            )
+            .await?;
+        Ok(parse_tx_history(url, start, &response))
+    }
+
+    /// Pager over the whole history of `url`, from index 0
+    #[must_use]
+    pub fn tx_history(&self, url: &str) -> TxHistory<'_> {
+        TxHistory {
+            client: self,
+            url: url.to_string(),
+            page_size: DEFAULT_PAGE_SIZE,
+            filter: TxTypeFilter::all(),
+            next_start: 0,
+            buffer_start: 0,
+            buffer: VecDeque::new(),
+            exhausted: false,
+        }
+    }
+}
+
+/// Paging iterator over an account's transaction history
+#[derive(Debug)]
+pub struct TxHistory<'a> {
+    client: &'a AccumulateClient,
+    url: String,
+    page_size: u64,
+    filter: TxTypeFilter,
+    next_start: u64,
+    buffer_start: u64,
+    buffer: VecDeque<TxHistoryEntry>,
+    exhausted: bool,
+}
+
+impl TxHistory<'_> {
+    /// Request `count` entries per page instead of [`DEFAULT_PAGE_SIZE`]
+    #[must_use]
+    pub fn page_size(mut self, count: u64) -> Self {
+        self.page_size = count.max(1);
+        self
+    }
+
+    /// Only yield transactions that pass `filter`
+    ///
+    /// The history query has no type filter, so skipped entries are still
+    /// fetched; pages returned by [`TxHistory::next_page`] hold only the
+    /// entries that pass and may be shorter than the page size.
+    #[must_use]
+    pub fn filter(mut self, filter: TxTypeFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Start at history index `start` instead of the beginning
+    #[must_use]
+    pub const fn start_at(mut self, start: u64) -> Self {
+        self.next_start = start;
+        self
+    }
+
+    /// The next page, or `None` once the history is exhausted
+    ///
+    /// Entries already buffered by [`TxHistory::next`] are returned first.
+    pub async fn next_page(&mut self) -> Result<Option<TxHistoryPage>, JsonRpcError> {
+        if !self.buffer.is_empty() {
+            return Ok(Some(TxHistoryPage {
+                start: self.buffer_start,
+                total: None,
+                items: self.buffer.drain(..).collect(),
+            }));
+        }
+        while !self.exhausted {
+            let page = self.fetch().await?;
+            if !page.items.is_empty() {
+                return Ok(Some(page));
+            }
+        }
+        Ok(None)
+    }
+
+    /// The next entry, or `None` once the history is exhausted
+    pub async fn next(&mut self) -> Result<Option<TxHistoryEntry>, JsonRpcError> {
+        while self.buffer.is_empty() && !self.exhausted {
+            let page = self.fetch().await?;
+            self.buffer_start = page.start;
+            self.buffer.extend(page.items);
+        }
+        Ok(self.buffer.pop_front())
+    }
+
+    async fn fetch(&mut self) -> Result<TxHistoryPage, JsonRpcError> {
+        let mut page = self
+            .client
+            .query_tx_history(&self.url, self.next_start, self.page_size)
+            .await?;
+        self.next_start += page.items.len() as u64;
+        self.exhausted = page.is_last(self.page_size);
+        if !self.filter.is_all() {
+            page.items
+                .retain(|entry| self.filter.admits_name(&entry.tx_type));
+        }
+        Ok(page)
+    }
+
+    /// Collect every remaining entry
+    pub async fn collect(mut self) -> Result<Vec<TxHistoryEntry>, JsonRpcError> {
+        let mut entries = Vec::new();
+        while let Some(entry) = self.next().await? {
+            entries.push(entry);
+        }
+        Ok(entries)
+    }
+}
+
+/// Parse a `query-tx-history` response for `account`
+///
+/// `start` is used when the response does not echo it back.
+#[must_use]
+pub fn parse_tx_history(account: &str, start: u64, response: &Value) -> TxHistoryPage {
+    let items = response
+        .get("items")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .map(|item| parse_tx_history_item(account, item))
+        .collect();
+    TxHistoryPage {
+        start: response
+            .get("start")
+            .and_then(Value::as_u64)
+            .unwrap_or(start),
+        total: response.get("total").and_then(Value::as_u64),
+        items,
+    }
+}
+
+/// Parse one `TransactionQueryResponse` item relative to `account`
+#[must_use]
+pub fn parse_tx_history_item(account: &str, item: &Value) -> TxHistoryEntry {
+    let txid = item.get("txid").and_then(Value::as_str).map(str::to_string);
+    let transaction = item.get("transaction").cloned().unwrap_or_else(|| {
+        // Older nodes only return the body as `data` and the principal as `origin`
+        let mut body = item.get("data").cloned().unwrap_or_else(|| json!({}));
+        if let (Some(body), Some(tx_type)) = (body.as_object_mut(), item.get("type")) {
+            body.entry("type").or_insert_with(|| tx_type.clone());
+        }
+        json!({ "header": { "principal": item.get("origin") }, "body": body })
+    });
+
+    let tx_type = transaction
+        .get("body")
+        .and_then(|b| b.get("type"))
+        .or_else(|| item.get("type"))
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let principal = transaction
+        .get("header")
+        .and_then(|h| h.get("principal"))
+        .or_else(|| item.get("origin"))
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    let hash = item
+        .get("transactionHash")
+        .and_then(Value::as_str)
+        .map(str::to_string)
+        .or_else(|| txid.as_deref().and_then(txid_hash))
+        .unwrap_or_default();
+
+    let transfers = extract_transfers(account, None, &hash, &transaction);
+    let direction = transfers.first().map(|t| t.direction);
+    let amount = (!transfers.is_empty()).then(|| transfers.iter().map(|t| t.amount).sum());
+    let counterparty = transfers
+        .first()
+        .filter(|first| {
+            transfers
+                .iter()
+                .all(|t| t.counterparty.eq_ignore_ascii_case(&first.counterparty))
+        })
+        .map(|t| t.counterparty.clone());
+
+    TxHistoryEntry {
+        txid,
+        hash,
+        tx_type,
+        principal,
+        direction,
+        amount,
+        counterparty,
+        transaction,
+    }
+}
+
+/// Hash part of a transaction ID (`acc://<hash>@<principal>`)
+pub(crate) fn txid_hash(txid: &str) -> Option<String> {
+    let (hash, _) = txid.strip_prefix("acc://")?.split_once('@')?;
+    Some(hash.to_string())
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    const ALICE: &str = "acc://alice.acme/tokens";
+
+    #[test]
+    fn test_parse_tx_history() {
+        let response = json!({
+            "type": "txHistory",
+            "start": 10,
+            "count": 3,
+            "total": 13,
+            "items": [
+                {
+                    "type": "sendTokens",
+                    "txid": format!("acc://{}@alice.acme/tokens", "ab".repeat(32)),
+                    "transaction": {
+                        "header": {"principal": ALICE},
+                        "body": {"type": "sendTokens", "to": [
+                            {"url": "acc://bob.acme/tokens", "amount": "150"},
+                            {"url": "acc://bob.acme/tokens", "amount": "50"}
+                        ]}
+                    }
+                },
+                {
+                    "type": "syntheticDepositTokens",
+                    "transactionHash": "cd".repeat(32),
+                    "origin": ALICE,
+                    "data": {"token": "acc://ACME", "amount": "700",
+                             "source": format!("acc://{}@carol.acme/tokens", "ef".repeat(32))}
+                },
+                {
+                    "type": "updateKeyPage",
+                    "transaction": {"header": {"principal": "acc://alice.acme/book/1"},
+                                    "body": {"type": "updateKeyPage", "operation": []}}
+                }
+            ]
+        });
+
+        let page = parse_tx_history(ALICE, 0, &response);
+        assert_eq!(page.start, 10);
+        assert_eq!(page.total, Some(13));
+        assert_eq!(page.items.len(), 3);
+        assert!(page.is_last(3));
+
+        let send = &page.items[0];
+        assert_eq!(send.hash, "ab".repeat(32));
+        assert_eq!(send.tx_type, "sendTokens");
+        assert_eq!(send.direction, Some(Direction::Outflow));
+        assert_eq!(send.amount, Some(200));
+        assert_eq!(send.counterparty.as_deref(), Some("acc://bob.acme/tokens"));
+
+        let deposit = &page.items[1];
+        assert_eq!(deposit.hash, "cd".repeat(32));
+        assert_eq!(deposit.tx_type, "syntheticDepositTokens");
+        assert_eq!(deposit.principal, ALICE);
+        assert_eq!(deposit.direction, Some(Direction::Inflow));
+        assert_eq!(deposit.amount, Some(700));
+        assert_eq!(
+            deposit.counterparty.as_deref(),
+            Some("acc://carol.acme/tokens")
+        );
+
+        let other = &page.items[2];
+        assert_eq!(other.tx_type, "updateKeyPage");
+        assert_eq!(other.direction, None);
+        assert_eq!(other.amount, None);
+        assert_eq!(other.counterparty, None);
+    }
+
+    #[test]
+    fn test_page_exhaustion() {
+        let page = |start, total, len| TxHistoryPage {
+            start,
+            total,
+            items: vec![parse_tx_history_item(ALICE, &json!({"type": "acmeFaucet"})); len],
+        };
+        assert!(page(0, None, 0).is_last(10));
+        assert!(page(0, None, 4).is_last(10));
+        assert!(!page(0, None, 10).is_last(10));
+        assert!(!page(0, Some(25), 10).is_last(10));
+        assert!(page(15, Some(25), 10).is_last(10));
+    }
+}
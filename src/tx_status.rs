@@ -0,0 +1,584 @@
+//! Transaction status tracking with typed outcomes
+//!
+//! [`TxStatus`] reads the status of a message record, whichever of the
+//! shapes the node uses (a code such as `delivered`, or an object with
+//! `delivered`, `failed` and `error`). A [`TxTracker`] polls a transaction
+//! until it is delivered or fails, backing off while it is pending, and
+//! returns a [`TxOutcome`] with the status, the last record and the IDs of
+//! the synthetic transactions it produced:
+//!
+//! ```no_run
+//! # async fn run(client: &accumulate_client::AccumulateClient, txid: &str) {
+//! use accumulate_client::tx_status::TxStatus;
+//! use std::time::Duration;
+//!
+//! let outcome = client
+//!     .tx_tracker()
+//!     .intervals(Duration::from_secs(1), Duration::from_secs(8))
+//!     .track(txid)
+//!     .await;
+//! match &outcome.status {
+//!     TxStatus::Delivered => println!("delivered, produced {:?}", outcome.produced),
+//!     TxStatus::Failed { code, message } => println!("failed ({code}): {message}"),
+//!     TxStatus::Pending | TxStatus::Unknown => println!("gave up waiting"),
+//! }
+//! # }
+//! ```
+//!
+//! A [`ConfirmationPolicy`] says how final a transaction must be before it
+//! counts: [`TxTracker::confirm`] polls until the policy is met, and the same
+//! presets select what [`SmartSigner::sign_submit_and_wait`](crate::helpers::SmartSigner::sign_submit_and_wait)
+//! and [`AccountWatcher`](crate::watch::AccountWatcher) wait for. An exchange
+//! crediting deposits can demand [`Anchored`](ConfirmationPolicy::Anchored)
+//! while a dev tool accepts [`Delivered`](ConfirmationPolicy::Delivered).
+
+use crate::client::AccumulateClient;
+use crate::query::{ChainResult, MessageRecord};
+use crate::tx_history::txid_hash;
+use crate::types::{ChainQuery, ReceiptOptions};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::time::Duration;
+
+/// Status codes that do not mean the transaction failed
+const NON_FAILURE_CODES: [&str; 5] = ["ok", "delivered", "pending", "remote", "unknown"];
+
+/// Where a transaction stands
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "status", rename_all = "camelCase")]
+pub enum TxStatus {
+    /// Received, waiting for signatures or execution
+    Pending,
+    /// Executed successfully
+    Delivered,
+    /// Rejected or failed to execute
+    Failed {
+        /// Error code, such as `insufficientBalance`
+        code: String,
+        /// Why it failed
+        message: String,
+    },
+    /// Not found, or the status could not be read
+    Unknown,
+}
+
+impl TxStatus {
+    /// Status of a message record
+    #[must_use]
+    pub fn from_record(record: &MessageRecord) -> Self {
+        if let Some(message) = record.error_message() {
+            return Self::Failed {
+                code: failure_code(record),
+                message,
+            };
+        }
+        if record.is_delivered() {
+            return Self::Delivered;
+        }
+        let pending = record
+            .status
+            .as_ref()
+            .and_then(|status| status.get("pending"))
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        match record.status_code() {
+            Some("pending" | "remote") => Self::Pending,
+            _ if pending => Self::Pending,
+            _ => Self::Unknown,
+        }
+    }
+
+    /// Whether the transaction has been executed, successfully or not
+    #[must_use]
+    pub const fn is_final(&self) -> bool {
+        matches!(self, Self::Delivered | Self::Failed { .. })
+    }
+
+    /// Whether the transaction was executed successfully
+    #[must_use]
+    pub const fn is_delivered(&self) -> bool {
+        matches!(self, Self::Delivered)
+    }
+}
+
+impl fmt::Display for TxStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Pending => f.write_str("pending"),
+            Self::Delivered => f.write_str("delivered"),
+            Self::Failed { code, message } => write!(f, "failed ({code}): {message}"),
+            Self::Unknown => f.write_str("unknown"),
+        }
+    }
+}
+
+/// Error code of a failed message: the error's own code, else the status
+/// code, else `failed`
+fn failure_code(record: &MessageRecord) -> String {
+    let error = record
+        .status
+        .as_ref()
+        .and_then(|status| status.get("error"))
+        .or(record.error.as_ref());
+    let code = error.and_then(|error| match error.get("code")? {
+        Value::String(code) => Some(code.clone()),
+        Value::Number(code) => Some(code.to_string()),
+        _ => None,
+    });
+    code.or_else(|| {
+        record
+            .status_code()
+            .filter(|code| !NON_FAILURE_CODES.contains(code))
+            .map(str::to_string)
+    })
+    .unwrap_or_else(|| "failed".to_string())
+}
+
+/// How final a transaction must be before a wait counts it as confirmed
+///
+/// Levels are ordered; each one includes those before it.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ConfirmationPolicy {
+    /// Accepted by the node, whether or not it has executed yet
+    Submitted,
+    /// Executed successfully
+    #[default]
+    Delivered,
+    /// Executed, and so was every synthetic transaction it produced
+    SyntheticDelivered,
+    /// Synthetic transactions delivered, and the node returns a receipt
+    /// proving the transaction is on its principal's main chain up to an
+    /// anchor
+    Anchored,
+}
+
+impl fmt::Display for ConfirmationPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Submitted => "submitted",
+            Self::Delivered => "delivered",
+            Self::SyntheticDelivered => "synthetic delivered",
+            Self::Anchored => "anchored",
+        })
+    }
+}
+
+/// What a [`TxTracker`] found out about a transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TxOutcome {
+    /// The tracked transaction ID
+    pub txid: String,
+    /// Last status seen
+    pub status: TxStatus,
+    /// IDs of the synthetic transactions it produced
+    #[serde(default)]
+    pub produced: Vec<String>,
+    /// Status queries sent
+    pub attempts: u32,
+    /// Last message record received
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub record: Option<MessageRecord>,
+    /// Whether the confirmation policy it was checked against is met
+    #[serde(default)]
+    pub confirmed: bool,
+}
+
+impl TxOutcome {
+    /// Whether the transaction was executed successfully
+    #[must_use]
+    pub const fn is_delivered(&self) -> bool {
+        self.status.is_delivered()
+    }
+}
+
+/// Polls a transaction's status until it is delivered or fails
+///
+/// Polls every `poll` interval at first and doubles the interval while the
+/// transaction is pending or unknown, up to `max`. By default both are the
+/// client's [`poll_interval`](crate::AccOptions::poll_interval), and the
+/// tracker gives up after the client's
+/// [`confirmation_timeout`](crate::AccOptions::confirmation_timeout).
+#[derive(Debug, Clone, Copy)]
+pub struct TxTracker<'a> {
+    client: &'a AccumulateClient,
+    poll_interval: Duration,
+    max_interval: Duration,
+    timeout: Duration,
+}
+
+impl<'a> TxTracker<'a> {
+    /// Tracker using the client's poll interval and confirmation timeout
+    #[must_use]
+    pub const fn new(client: &'a AccumulateClient) -> Self {
+        Self {
+            client,
+            poll_interval: client.options.poll_interval,
+            max_interval: client.options.poll_interval,
+            timeout: client.options.confirmation_timeout,
+        }
+    }
+
+    /// First poll interval, and the cap for back-off
+    #[must_use]
+    pub fn intervals(mut self, poll: Duration, max: Duration) -> Self {
+        self.poll_interval = poll;
+        self.max_interval = max.max(poll);
+        self
+    }
+
+    /// Give up once `timeout` has elapsed
+    #[must_use]
+    pub const fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Query the transaction's status once
+    ///
+    /// A signature ID, as returned by a submission, is followed to the
+    /// transaction it signs. A query error leaves the status
+    /// [`Unknown`](TxStatus::Unknown): the transaction may not have reached
+    /// the node yet. The outcome is checked against
+    /// [`ConfirmationPolicy::Delivered`].
+    pub async fn check(&self, txid: &str) -> TxOutcome {
+        let queries = self.client.v3();
+        let mut record = queries.query_transaction(&message_scope(txid)).await.ok();
+        let signed = record
+            .as_ref()
+            .and_then(|record| record.message.as_ref())
+            .filter(|message| message.get("type").and_then(Value::as_str) == Some("signature"))
+            .and_then(|message| message.get("txID"))
+            .and_then(Value::as_str)
+            .map(message_scope);
+        if let Some(scope) = signed {
+            record = queries.query_transaction(&scope).await.ok();
+        }
+        let status = record
+            .as_ref()
+            .map_or(TxStatus::Unknown, TxStatus::from_record);
+        TxOutcome {
+            txid: txid.to_string(),
+            confirmed: status.is_delivered(),
+            status,
+            produced: record
+                .as_ref()
+                .map(MessageRecord::produced_ids)
+                .unwrap_or_default(),
+            attempts: 1,
+            record,
+        }
+    }
+
+    /// Query the transaction once and check it against `policy`
+    ///
+    /// Synthetic transactions are queried one by one, and the receipt is
+    /// only requested once everything else is met. A receipt that does not
+    /// verify leaves the transaction unconfirmed.
+    pub async fn check_confirmed(&self, txid: &str, policy: ConfirmationPolicy) -> TxOutcome {
+        let outcome = self.check(txid).await;
+        let confirmed = match policy {
+            ConfirmationPolicy::Submitted => {
+                outcome.record.is_some() && !matches!(outcome.status, TxStatus::Failed { .. })
+            }
+            ConfirmationPolicy::Delivered => outcome.is_delivered(),
+            ConfirmationPolicy::SyntheticDelivered => {
+                outcome.is_delivered() && self.produced_delivered(&outcome.produced).await
+            }
+            ConfirmationPolicy::Anchored => {
+                outcome.is_delivered()
+                    && self.produced_delivered(&outcome.produced).await
+                    && self.is_anchored(&outcome).await
+            }
+        };
+        TxOutcome { confirmed, ..outcome }
+    }
+
+    /// Poll until the transaction is delivered or fails, or the timeout
+    /// runs out
+    pub async fn track(&self, txid: &str) -> TxOutcome {
+        self.confirm(txid, ConfirmationPolicy::Delivered).await
+    }
+
+    /// Poll until the transaction meets `policy` or fails, or the timeout
+    /// runs out
+    pub async fn confirm(&self, txid: &str, policy: ConfirmationPolicy) -> TxOutcome {
+        let deadline = tokio::time::Instant::now() + self.timeout;
+        let mut interval = self.poll_interval;
+        let mut attempts = 0;
+        loop {
+            let outcome = self.check_confirmed(txid, policy).await;
+            attempts += 1;
+            let now = tokio::time::Instant::now();
+            let failed = matches!(outcome.status, TxStatus::Failed { .. });
+            if outcome.confirmed || failed || now >= deadline {
+                return TxOutcome {
+                    attempts,
+                    ..outcome
+                };
+            }
+            tokio::time::sleep(interval.min(deadline - now)).await;
+            interval = (interval * 2).min(self.max_interval);
+        }
+    }
+
+    /// Whether every synthetic transaction in `produced` was delivered
+    async fn produced_delivered(&self, produced: &[String]) -> bool {
+        for id in produced {
+            if !self.check(id).await.is_delivered() {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Whether the node proves the transaction is on its principal's main
+    /// chain with a receipt that verifies
+    async fn is_anchored(&self, outcome: &TxOutcome) -> bool {
+        let txid = outcome
+            .record
+            .as_ref()
+            .and_then(|record| record.id.as_deref())
+            .unwrap_or(&outcome.txid);
+        let (Some(tx_hash), Some((_, principal))) = (txid_hash(txid), txid.split_once('@')) else {
+            return false;
+        };
+        let query = ChainQuery {
+            name: Some("main".to_string()),
+            entry: Some(tx_hash.clone()),
+            include_receipt: Some(ReceiptOptions {
+                for_any: Some(true),
+                for_height: None,
+            }),
+            ..Default::default()
+        };
+        let principal = format!("acc://{principal}");
+        let Ok(ChainResult::Entry(entry)) = self.client.v3().query_chain(&principal, query).await else {
+            return false;
+        };
+        entry.receipt.as_ref().is_some_and(|receipt| {
+            crate::receipts::verify_receipt(receipt).is_ok()
+                && receipt
+                    .get("start")
+                    .and_then(Value::as_str)
+                    .is_some_and(|start| start.eq_ignore_ascii_case(&tx_hash))
+        })
+    }
+}
+
+impl AccumulateClient {
+    /// Tracker for transactions submitted through this client
+    #[must_use]
+    pub const fn tx_tracker(&self) -> TxTracker<'_> {
+        TxTracker::new(self)
+    }
+}
+
+/// Query scope of a transaction ID: its hash under `unknown`, which the node
+/// resolves whatever principal the ID names
+pub(crate) fn message_scope(txid: &str) -> String {
+    let hash = txid.split('@').next().unwrap_or(txid);
+    format!("acc://{}@unknown", hash.trim_start_matches("acc://"))
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::amounts::ACME_BASE_UNITS;
+    use crate::helpers::{QuickStart, SmartSigner, TxBody};
+    use crate::mock::MockTransport;
+    use crate::simulator::{SimulatedNetwork, DEFAULT_ORACLE};
+    use serde_json::json;
+    use std::sync::Arc;
+
+    fn status(value: Value) -> TxStatus {
+        TxStatus::from_record(&serde_json::from_value(value).unwrap())
+    }
+
+    #[test]
+    fn test_status_shapes() {
+        assert_eq!(status(json!({"status": "pending"})), TxStatus::Pending);
+        assert_eq!(status(json!({"status": "remote"})), TxStatus::Pending);
+        assert_eq!(
+            status(json!({"status": {"code": "pending", "delivered": false, "pending": true}})),
+            TxStatus::Pending
+        );
+        assert_eq!(status(json!({"status": "delivered"})), TxStatus::Delivered);
+        assert_eq!(
+            status(json!({"status": {"delivered": true}})),
+            TxStatus::Delivered
+        );
+        assert_eq!(status(json!({})), TxStatus::Unknown);
+
+        let failed = status(json!({
+            "status": {"code": "failed", "delivered": true, "failed": true, "error": {"message": "no credits"}}
+        }));
+        assert_eq!(
+            failed,
+            TxStatus::Failed {
+                code: "failed".to_string(),
+                message: "no credits".to_string()
+            }
+        );
+        assert!(failed.is_final() && !failed.is_delivered());
+        assert_eq!(failed.to_string(), "failed (failed): no credits");
+
+        // The error's code, else a non-success status code
+        assert_eq!(
+            status(json!({"status": "insufficientBalance", "error": {"message": "too low"}})),
+            TxStatus::Failed {
+                code: "insufficientBalance".to_string(),
+                message: "too low".to_string()
+            }
+        );
+        assert_eq!(
+            status(json!({"status": "delivered", "error": {"code": 402, "message": "no credits"}})),
+            TxStatus::Failed {
+                code: "402".to_string(),
+                message: "no credits".to_string()
+            }
+        );
+
+        assert_eq!(
+            serde_json::to_value(&failed).unwrap(),
+            json!({"status": "failed", "code": "failed", "message": "no credits"})
+        );
+        assert_eq!(
+            message_scope("acc://ab@alice.acme/tokens"),
+            "acc://ab@unknown"
+        );
+        assert_eq!(message_scope("ab"), "acc://ab@unknown");
+    }
+
+    #[tokio::test]
+    async fn test_confirmation_policies() {
+        let mock = MockTransport::new();
+        let client = mock.client().await.unwrap();
+        let tracker = client.tx_tracker().timeout(Duration::ZERO);
+        let hash = "11".repeat(32);
+        let txid = format!("acc://{hash}@alice.acme/tokens");
+        let synthetic = format!("acc://{}@bob.acme/tokens", "22".repeat(32));
+        let record = |status: &str| {
+            json!({
+                "recordType": "message",
+                "id": &txid,
+                "status": status,
+                "message": {"type": "transaction"},
+                "produced": {"records": [{"value": &synthetic}]}
+            })
+        };
+        let receipt = |anchor: &str| {
+            json!({"recordType": "chainEntry", "index": 0, "entry": &hash, "receipt": {
+                "start": &hash, "end": &hash, "anchor": anchor, "entries": []
+            }})
+        };
+        let synthetic_delivered = json!({"recordType": "message", "id": &synthetic, "status": "delivered"});
+
+        mock.once("query", record("pending"));
+        let outcome = tracker.check_confirmed(&txid, ConfirmationPolicy::Submitted).await;
+        assert!(outcome.confirmed && !outcome.is_delivered());
+        mock.once("query", record("pending"));
+        assert!(!tracker.check_confirmed(&txid, ConfirmationPolicy::Delivered).await.confirmed);
+
+        // Delivered, but the synthetic transaction is still pending
+        mock.once("query", record("delivered"));
+        mock.once("query", json!({"recordType": "message", "status": "pending"}));
+        let outcome = tracker.confirm(&txid, ConfirmationPolicy::SyntheticDelivered).await;
+        assert!(outcome.is_delivered() && !outcome.confirmed);
+
+        mock.once("query", record("delivered"));
+        mock.once("query", synthetic_delivered.clone());
+        mock.once("query", receipt(&hash));
+        let outcome = tracker.check_confirmed(&txid, ConfirmationPolicy::Anchored).await;
+        assert!(outcome.confirmed, "{outcome:?}");
+
+        // A receipt that does not verify is no anchor
+        mock.once("query", record("delivered"));
+        mock.once("query", synthetic_delivered);
+        mock.once("query", receipt(&"33".repeat(32)));
+        assert!(!tracker.check_confirmed(&txid, ConfirmationPolicy::Anchored).await.confirmed);
+        mock.assert_called_times("query", 10);
+
+        assert!(ConfirmationPolicy::Submitted < ConfirmationPolicy::default());
+        assert!(ConfirmationPolicy::SyntheticDelivered < ConfirmationPolicy::Anchored);
+        assert_eq!(
+            serde_json::to_value(ConfirmationPolicy::SyntheticDelivered).unwrap(),
+            "syntheticDelivered"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_track_against_simulator() {
+        let network = SimulatedNetwork::new();
+        let quick = QuickStart::from_client(Arc::new(network.client().await.unwrap()));
+        let wallet = quick.create_wallet();
+        network
+            .fund(&wallet.lite_token_account, 10 * ACME_BASE_UNITS)
+            .unwrap();
+        let mut signer = SmartSigner::new(
+            quick.client(),
+            wallet.keypair().clone(),
+            &wallet.lite_identity,
+        );
+        let tracker = quick
+            .client()
+            .tx_tracker()
+            .intervals(Duration::from_millis(1), Duration::from_millis(4))
+            .timeout(Duration::from_millis(20));
+
+        let recipient = format!(
+            "{}/ACME",
+            crate::helpers::derive_lite_identity_url(&[5u8; 32])
+        );
+        let credits = TxBody::add_credits(
+            &wallet.lite_identity,
+            &ACME_BASE_UNITS.to_string(),
+            DEFAULT_ORACLE,
+        );
+        let bought = signer
+            .sign_submit_and_wait(&wallet.lite_token_account, &credits, None, 5)
+            .await;
+        assert!(bought.success, "{:?}", bought.error);
+        let body = TxBody::send_tokens_single(&recipient, &ACME_BASE_UNITS.to_string());
+        let sent = signer
+            .sign_submit_and_wait(&wallet.lite_token_account, &body, None, 5)
+            .await;
+        let outcome = tracker.track(sent.txid.as_deref().unwrap()).await;
+        assert_eq!(outcome.status, TxStatus::Delivered);
+        assert!(outcome.confirmed);
+        assert_eq!(outcome.attempts, 1);
+        assert_eq!(outcome.produced.len(), 1);
+        assert!(
+            outcome.produced[0].ends_with(&recipient[6..]),
+            "{:?}",
+            outcome.produced
+        );
+
+        // Execution fails: the failure is reported with its message
+        let body = TxBody::send_tokens_single(&recipient, &(100 * ACME_BASE_UNITS).to_string());
+        let envelope = signer
+            .sign(&wallet.lite_token_account, &body, None)
+            .unwrap();
+        let response = network
+            .call("submit", &json!({"envelope": envelope}))
+            .unwrap();
+        let txid = crate::helpers::extract_txid(&response).unwrap();
+        let outcome = tracker.track(&txid).await;
+        assert!(
+            matches!(outcome.status, TxStatus::Failed { .. }),
+            "{:?}",
+            outcome.status
+        );
+        assert!(outcome.produced.is_empty());
+
+        // Never submitted: polls with back-off until the timeout
+        let outcome = tracker
+            .track(&format!("acc://{}@unknown", "00".repeat(32)))
+            .await;
+        assert_eq!(outcome.status, TxStatus::Unknown);
+        assert!(outcome.attempts > 1 && outcome.record.is_none());
+    }
+}
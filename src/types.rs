@@ -95,6 +95,87 @@ pub struct Account {
     pub nonce: Option<i64>,
 }
 
+/// One entry of an account's authority set
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccountAuthority {
+    /// Authority URL (usually a key book)
+    pub url: String,
+    /// When set, the authority's signature is not required for transactions
+    /// from the account
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub disabled: bool,
+}
+
+/// Token account state (`tokenAccount` or `liteTokenAccount`) as returned by
+/// a V3 query
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TokenAccountState {
+    #[serde(rename = "type")]
+    pub account_type: String,
+    pub url: String,
+    pub token_url: String,
+    /// Balance in base units, as a decimal string
+    pub balance: String,
+    /// Authority set; empty for lite token accounts
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authorities: Vec<AccountAuthority>,
+    /// Major block height until which a lite token account is locked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lock_height: Option<u64>,
+}
+
+impl TokenAccountState {
+    /// Whether this is a lite token account
+    #[must_use]
+    pub fn is_lite(&self) -> bool {
+        self.account_type == "liteTokenAccount"
+    }
+
+    /// Balance in base units
+    #[must_use]
+    pub fn balance_units(&self) -> Option<u128> {
+        self.balance.parse().ok()
+    }
+
+    /// Authorities whose signatures a transfer from this account needs
+    ///
+    /// Disabled authorities are skipped. A lite token account is governed by
+    /// its lite identity alone.
+    #[must_use]
+    pub fn enabled_authorities(&self) -> Vec<String> {
+        if self.is_lite() {
+            let path = self.url.strip_prefix("acc://").unwrap_or(&self.url);
+            let identity = path.split('/').next().unwrap_or(path);
+            return vec![format!("acc://{identity}")];
+        }
+        self.authorities
+            .iter()
+            .filter(|a| !a.disabled)
+            .map(|a| a.url.clone())
+            .collect()
+    }
+
+    /// Whether any authority has its signature requirement disabled
+    #[must_use]
+    pub fn has_disabled_authorities(&self) -> bool {
+        self.authorities.iter().any(|a| a.disabled)
+    }
+
+    /// Whether transfers need approval from more than one authority
+    #[must_use]
+    pub fn requires_multiple_authorities(&self) -> bool {
+        self.enabled_authorities().len() > 1
+    }
+
+    /// Whether the account is locked at major block `height`
+    #[must_use]
+    pub fn is_locked_at(&self, height: u64) -> bool {
+        self.lock_height.is_some_and(|lock| lock > height)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct FaucetResponse {
@@ -114,6 +195,7 @@ pub struct FaucetResponse {
 
 /// Merkle receipt entry - a node in the proof path
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct MerkleReceiptEntry {
     /// Whether this hash should be applied on the right side
@@ -121,28 +203,33 @@ pub struct MerkleReceiptEntry {
     pub right: bool,
     /// The hash value at this position in the proof
     #[serde(with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub hash: Vec<u8>,
 }
 
 /// Merkle receipt - cryptographic proof of inclusion in a Merkle tree
 /// Used for anchoring transactions and proving state inclusion
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct MerkleReceipt {
     /// The starting entry hash for which we want a proof
     #[serde(with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub start: Vec<u8>,
     /// Index of the start entry in the chain
     #[serde(default)]
     pub start_index: i64,
     /// The ending entry hash at the anchor point
     #[serde(with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub end: Vec<u8>,
     /// Index of the end entry in the chain
     #[serde(default)]
     pub end_index: i64,
     /// The Merkle root hash expected after applying all proof entries
     #[serde(with = "hex::serde")]
+    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]
     pub anchor: Vec<u8>,
     /// The list of hashes in the proof path
     #[serde(default)]
@@ -959,3 +1046,53 @@ impl V3Query {
         }
     }
 }
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn test_token_account_state() {
+        let account: TokenAccountState = serde_json::from_value(json!({
+            "type": "tokenAccount",
+            "url": "acc://alice.acme/tokens",
+            "tokenUrl": "acc://ACME",
+            "balance": "150000000",
+            "authorities": [
+                {"url": "acc://alice.acme/book"},
+                {"url": "acc://compliance.acme/book"},
+                {"url": "acc://ops.acme/book", "disabled": true}
+            ]
+        }))
+        .unwrap();
+        assert!(!account.is_lite());
+        assert_eq!(account.balance_units(), Some(150_000_000));
+        assert_eq!(
+            account.enabled_authorities(),
+            vec!["acc://alice.acme/book", "acc://compliance.acme/book"]
+        );
+        assert!(account.has_disabled_authorities());
+        assert!(account.requires_multiple_authorities());
+        assert!(!account.is_locked_at(0));
+
+        let lite: TokenAccountState = serde_json::from_value(json!({
+            "type": "liteTokenAccount",
+            "url": "acc://0123abcd/ACME",
+            "tokenUrl": "acc://ACME",
+            "balance": "0",
+            "lockHeight": 50
+        }))
+        .unwrap();
+        assert!(lite.is_lite());
+        assert_eq!(lite.enabled_authorities(), vec!["acc://0123abcd"]);
+        assert!(!lite.requires_multiple_authorities());
+        assert!(lite.is_locked_at(49));
+        assert!(!lite.is_locked_at(50));
+
+        let value = serde_json::to_value(&lite).unwrap();
+        assert!(value.get("authorities").is_none());
+        assert_eq!(value["lockHeight"], 50);
+    }
+}
@@ -1,7 +1,8 @@
 //! Type matrix for comprehensive roundtrip testing
 //!
 //! This module contains all protocol types that need to be tested for
-//! encode → decode → re-encode roundtrip consistency.
+//! encode → decode → re-encode roundtrip consistency. Samples derived from
+//! the generated types themselves are in [`wire_matrix`](crate::wire_matrix).
 
 use serde::{Deserialize, Serialize};
 use crate::codec::transaction_codec::{TransactionHeader, TransactionSignature};
@@ -0,0 +1,832 @@
+//! Wire-format matrix for the generated protocol types
+//!
+//! [`types_matrix`](crate::types_matrix) round-trips hand-written samples;
+//! this module derives its samples from the generated types themselves. For
+//! every transaction body, every signature, the transaction header and every
+//! protocol type it builds a minimal JSON sample (required fields only) and a
+//! maximal one (optional fields and collections populated), and checks that
+//! each decodes, re-encodes without dropping a field, and re-encodes to the
+//! same JSON again.
+//!
+//! Samples are probed rather than written by hand: fields are added as serde
+//! reports them missing, each taking the first candidate value the type
+//! accepts, and enum values are taken from serde's list of expected variants.
+//! The matrix therefore follows the types through regeneration, so a fork can
+//! run [`verify_wire_matrix`] after changing the generator, or [`build_case`]
+//! against types of its own.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::generated::header::TransactionHeader;
+use crate::generated::signatures::Signature;
+use crate::generated::transactions::TransactionBody;
+
+const TRANSACTIONS_MANIFEST: &str = include_str!("generated/transactions_manifest.json");
+const SIGNATURES_MANIFEST: &str = include_str!("generated/signatures_manifest.json");
+const HEADER_MANIFEST: &str = include_str!("generated/header_manifest.json");
+
+/// Decode attempts allowed while completing one sample
+const MAX_STEPS: usize = 256;
+
+/// How many nested objects deep maximal samples are populated
+const MAX_DEPTH: usize = 4;
+
+const SAMPLE_URL: &str = "acc://alice.acme/tokens";
+const SAMPLE_HEX: &str = "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef";
+const SAMPLE_AMOUNT: &str = "1000";
+
+/// Why a wire-format sample failed
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum WireMatrixError {
+    /// The sample does not decode
+    #[error("sample does not decode: {0}")]
+    Decode(String),
+
+    /// The decoded value does not encode
+    #[error("value does not encode: {0}")]
+    Encode(String),
+
+    /// A field of the sample is missing after re-encoding
+    #[error("field `{0}` is dropped on re-encode")]
+    DroppedField(String),
+
+    /// Decoding and encoding a second time changed the JSON
+    #[error("re-encoding is unstable: {first} != {second}")]
+    Unstable {
+        /// JSON after the first round trip
+        first: Value,
+        /// JSON after the second round trip
+        second: Value,
+    },
+
+    /// No value satisfying the type was found
+    #[error("no sample found: {0}")]
+    Incomplete(String),
+
+    /// A generated manifest could not be read
+    #[error("invalid manifest: {0}")]
+    Manifest(String),
+}
+
+/// Round-trip check for one type, as produced by [`roundtrip`]
+pub type Codec = fn(&Value) -> Result<Value, WireMatrixError>;
+
+/// Minimal and maximal samples of one type
+#[derive(Debug, Clone)]
+pub struct WireCase {
+    /// Qualified type name, such as `transactions::AddCreditsBody`
+    pub name: String,
+    /// Sample holding only the fields the type requires
+    pub minimal: Value,
+    /// Sample with every field the probe could populate, as encoded
+    pub maximal: Value,
+    /// Round-trip check for the type
+    pub codec: Codec,
+}
+
+impl WireCase {
+    /// Round-trip both samples
+    pub fn verify(&self) -> Result<(), WireMatrixError> {
+        (self.codec)(&self.minimal)?;
+        (self.codec)(&self.maximal)?;
+        Ok(())
+    }
+}
+
+/// Decode `sample` as `T`, re-encode it, and check that the result is stable
+///
+/// Returns the re-encoded JSON. Fails when a field of `sample` is missing
+/// from it, or when decoding and encoding it again changes anything.
+pub fn roundtrip<T>(sample: &Value) -> Result<Value, WireMatrixError>
+where
+    T: Serialize + DeserializeOwned + PartialEq,
+{
+    let first: T = serde_json::from_value(sample.clone())
+        .map_err(|e| WireMatrixError::Decode(e.to_string()))?;
+    let encoded =
+        serde_json::to_value(&first).map_err(|e| WireMatrixError::Encode(e.to_string()))?;
+    if let Some(field) = dropped_field(sample, &encoded, "") {
+        return Err(WireMatrixError::DroppedField(field));
+    }
+
+    let second: T = serde_json::from_value(encoded.clone())
+        .map_err(|e| WireMatrixError::Decode(e.to_string()))?;
+    let reencoded =
+        serde_json::to_value(&second).map_err(|e| WireMatrixError::Encode(e.to_string()))?;
+    if first != second || encoded != reencoded {
+        return Err(WireMatrixError::Unstable {
+            first: encoded,
+            second: reencoded,
+        });
+    }
+    Ok(encoded)
+}
+
+/// Build the minimal and maximal samples of `T`
+///
+/// Probing starts from `seed`, such as `{"type": "sendTokens"}` for a
+/// variant of a tagged enum. `optional_fields` names fields the type omits
+/// from its JSON when unset; fields that encode as `null` or `[]` are found
+/// without being named.
+pub fn build_case<T>(
+    name: &str,
+    seed: Value,
+    optional_fields: &[&str],
+) -> Result<WireCase, WireMatrixError>
+where
+    T: Serialize + DeserializeOwned + PartialEq,
+{
+    let codec: Codec = roundtrip::<T>;
+
+    let mut minimal = seed;
+    complete(&mut minimal, vec![Vec::new()], codec)?;
+
+    let mut maximal = codec(&minimal)?;
+    for field in optional_fields {
+        for key in [(*field).to_string(), lower_camel(field)] {
+            if maximal.get(&key).is_some() || enrich(&mut maximal, &[Step::Key(key)], codec) {
+                break;
+            }
+        }
+    }
+    populate(&mut maximal, &[], codec, 0);
+    let maximal = codec(&maximal)?;
+
+    Ok(WireCase {
+        name: name.to_string(),
+        minimal,
+        maximal,
+        codec,
+    })
+}
+
+/// Build the samples of every generated type
+///
+/// Fails with one message per type no sample could be built for.
+pub fn wire_matrix() -> Result<Vec<WireCase>, Vec<String>> {
+    let mut cases = Vec::new();
+    let mut failures = Vec::new();
+    let mut add = |name: String, case: Result<WireCase, WireMatrixError>| match case {
+        Ok(case) => cases.push(case),
+        Err(e) => failures.push(format!("{name}: {e}")),
+    };
+
+    match serde_json::from_str::<BodyManifest>(TRANSACTIONS_MANIFEST) {
+        Ok(manifest) => {
+            for body in manifest.bodies {
+                let name = format!("transactions::{}", body.name);
+                let case = build_case::<TransactionBody>(
+                    &name,
+                    json!({ "type": body.wire }),
+                    &field_names(&body.fields),
+                );
+                add(name, case);
+            }
+        }
+        Err(e) => add(
+            "transactions".to_string(),
+            Err(WireMatrixError::Manifest(e.to_string())),
+        ),
+    }
+
+    match serde_json::from_str::<SignatureManifest>(SIGNATURES_MANIFEST) {
+        Ok(manifest) => {
+            for signature in manifest.signatures {
+                let name = format!("signatures::{}", signature.name);
+                let case = build_case::<Signature>(
+                    &name,
+                    json!({ "type": signature.wire }),
+                    &field_names(&signature.fields),
+                );
+                add(name, case);
+            }
+        }
+        Err(e) => add(
+            "signatures".to_string(),
+            Err(WireMatrixError::Manifest(e.to_string())),
+        ),
+    }
+
+    let name = "header::TransactionHeader".to_string();
+    match serde_json::from_str::<HeaderManifest>(HEADER_MANIFEST) {
+        Ok(manifest) => {
+            let case =
+                build_case::<TransactionHeader>(&name, json!({}), &field_names(&manifest.fields));
+            add(name, case);
+        }
+        Err(e) => add(name, Err(WireMatrixError::Manifest(e.to_string()))),
+    }
+
+    for (name, case) in protocol_cases() {
+        add(name, case);
+    }
+
+    if failures.is_empty() {
+        Ok(cases)
+    } else {
+        Err(failures)
+    }
+}
+
+/// Build the samples of every generated type and round-trip each of them
+///
+/// Returns the number of types checked, or one message per failure.
+pub fn verify_wire_matrix() -> Result<usize, Vec<String>> {
+    let cases = wire_matrix()?;
+    let failures: Vec<String> = cases
+        .iter()
+        .filter_map(|case| case.verify().err().map(|e| format!("{}: {e}", case.name)))
+        .collect();
+    if failures.is_empty() {
+        Ok(cases.len())
+    } else {
+        Err(failures)
+    }
+}
+
+macro_rules! protocol_cases {
+    ($($ty:ident),* $(,)?) => {
+        /// Cases for the structs in [`crate::generated::types`]
+        fn protocol_cases() -> Vec<(String, Result<WireCase, WireMatrixError>)> {
+            vec![$({
+                let name = concat!("types::", stringify!($ty));
+                (name.to_string(), build_case::<crate::generated::types::$ty>(name, json!({}), &[]))
+            }),*]
+        }
+    };
+}
+
+protocol_cases!(
+    ADI,
+    AccountAuth,
+    AccumulateDataEntry,
+    AcmeFaucet,
+    AcmeOracle,
+    ActivateProtocolVersion,
+    AddAccountAuthorityOperation,
+    AddCredits,
+    AddCreditsResult,
+    AddKeyOperation,
+    AnchorLedger,
+    AnchorMetadata,
+    AnnotatedReceipt,
+    AuthorityEntry,
+    AuthoritySignature,
+    BTCLegacySignature,
+    BTCSignature,
+    BlockEntry,
+    BlockLedger,
+    BlockValidatorAnchor,
+    BurnCredits,
+    BurnTokens,
+    ChainMetadata,
+    ChainParams,
+    CreateDataAccount,
+    CreateIdentity,
+    CreateKeyBook,
+    CreateKeyPage,
+    CreateLiteTokenAccount,
+    CreateToken,
+    CreateTokenAccount,
+    CreditRecipient,
+    DataAccount,
+    DelegatedSignature,
+    DirectoryAnchor,
+    DisableAccountAuthOperation,
+    DoubleHashDataEntry,
+    ED25519Signature,
+    ETHSignature,
+    EcdsaSha256Signature,
+    EmptyResult,
+    EnableAccountAuthOperation,
+    ExpireOptions,
+    FactomDataEntry,
+    FactomDataEntryWrapper,
+    FeeSchedule,
+    HoldUntilOptions,
+    IndexEntry,
+    InternalSignature,
+    IssueTokens,
+    KeyBook,
+    KeyPage,
+    KeySpec,
+    KeySpecParams,
+    LegacyED25519Signature,
+    LiteDataAccount,
+    LiteIdentity,
+    LiteTokenAccount,
+    LockAccount,
+    MetricsRequest,
+    MetricsResponse,
+    NetworkAccountUpdate,
+    NetworkDefinition,
+    NetworkGlobals,
+    NetworkLimits,
+    NetworkMaintenance,
+    Object,
+    PartitionAnchor,
+    PartitionAnchorReceipt,
+    PartitionExecutorVersion,
+    PartitionInfo,
+    PartitionSignature,
+    PartitionSyntheticLedger,
+    PendingTransactionGCOperation,
+    RCD1Signature,
+    Rational,
+    ReceiptSignature,
+    RemoteSignature,
+    RemoteTransaction,
+    RemoveAccountAuthorityOperation,
+    RemoveKeyOperation,
+    Route,
+    RouteOverride,
+    RoutingTable,
+    RsaSha256Signature,
+    SendTokens,
+    SetRejectThresholdKeyPageOperation,
+    SetResponseThresholdKeyPageOperation,
+    SetThresholdKeyPageOperation,
+    SignatureSet,
+    SyntheticBurnTokens,
+    SyntheticCreateIdentity,
+    SyntheticDepositCredits,
+    SyntheticDepositTokens,
+    SyntheticForwardTransaction,
+    SyntheticLedger,
+    SyntheticOrigin,
+    SyntheticWriteData,
+    SystemLedger,
+    SystemWriteData,
+    TokenAccount,
+    TokenIssuer,
+    TokenIssuerProof,
+    TokenRecipient,
+    Transaction,
+    TransactionHeader,
+    TransactionResultSet,
+    TransactionStatus,
+    TransferCredits,
+    TxIdSet,
+    TypedDataSignature,
+    UnknownAccount,
+    UnknownSigner,
+    UpdateAccountAuth,
+    UpdateAllowedKeyPageOperation,
+    UpdateKey,
+    UpdateKeyOperation,
+    UpdateKeyPage,
+    ValidatorInfo,
+    ValidatorPartitionInfo,
+    WriteData,
+    WriteDataResult,
+    WriteDataTo,
+);
+
+#[derive(Deserialize)]
+struct BodyManifest {
+    bodies: Vec<ManifestEntry>,
+}
+
+#[derive(Deserialize)]
+struct SignatureManifest {
+    signatures: Vec<ManifestEntry>,
+}
+
+#[derive(Deserialize)]
+struct HeaderManifest {
+    fields: Vec<ManifestField>,
+}
+
+#[derive(Deserialize)]
+struct ManifestEntry {
+    name: String,
+    wire: String,
+    fields: Vec<ManifestField>,
+}
+
+/// Manifest field: an object in the body and header manifests, a bare name
+/// in the signature manifest
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum ManifestField {
+    Named { name: String },
+    Bare(String),
+}
+
+fn field_names(fields: &[ManifestField]) -> Vec<&str> {
+    fields
+        .iter()
+        .map(|f| match f {
+            ManifestField::Named { name } | ManifestField::Bare(name) => name.as_str(),
+        })
+        .collect()
+}
+
+/// One step of a path into a JSON value
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Step {
+    Key(String),
+    Index(usize),
+}
+
+/// How the sample fared with a candidate value in place
+enum Fit {
+    /// The sample decodes
+    Done,
+    /// The candidate was accepted but other fields are still missing
+    Partial,
+    /// The candidate has the wrong type
+    Rejected,
+}
+
+/// Add required fields to `sample` until it decodes
+///
+/// Missing fields are filled in the object at the top of `stack`; when a
+/// field does not belong there, that object is complete and is popped.
+fn complete(
+    sample: &mut Value,
+    mut stack: Vec<Vec<Step>>,
+    codec: Codec,
+) -> Result<(), WireMatrixError> {
+    for _ in 0..MAX_STEPS {
+        let message = match codec(sample) {
+            Ok(_) => return Ok(()),
+            Err(WireMatrixError::Decode(message)) => message,
+            Err(e) => return Err(e),
+        };
+        let Some(field) = missing_field(&message) else {
+            return Err(WireMatrixError::Incomplete(message));
+        };
+        loop {
+            let Some(parent) = stack.last().cloned() else {
+                return Err(WireMatrixError::Incomplete(message));
+            };
+            if fill(sample, &parent, field, codec, &mut stack) {
+                break;
+            }
+            stack.pop();
+        }
+    }
+    Err(WireMatrixError::Incomplete(format!(
+        "gave up after {MAX_STEPS} attempts"
+    )))
+}
+
+/// Add `field` to the object at `parent` with the first value that fits
+fn fill(
+    sample: &mut Value,
+    parent: &[Step],
+    field: &str,
+    codec: Codec,
+    stack: &mut Vec<Vec<Step>>,
+) -> bool {
+    let vacant = at(sample, parent)
+        .and_then(Value::as_object)
+        .is_some_and(|o| !o.contains_key(field));
+    if !vacant {
+        return false;
+    }
+
+    let mut target = parent.to_vec();
+    target.push(Step::Key(field.to_string()));
+    for candidate in candidates(field) {
+        let nested = nested_path(&target, &candidate);
+        set(sample, &target, candidate);
+        match fit(sample, &target, codec) {
+            Fit::Done => return true,
+            Fit::Partial => {
+                stack.extend(nested);
+                return true;
+            }
+            Fit::Rejected => {}
+        }
+    }
+    if let Some(Value::Object(object)) = at_mut(sample, parent) {
+        object.remove(field);
+    }
+    false
+}
+
+/// Decode the sample with a candidate at `target`, substituting the first
+/// expected variant when the candidate names an unknown one
+fn fit(sample: &mut Value, target: &[Step], codec: Codec) -> Fit {
+    for _ in 0..2 {
+        let message = match codec(sample) {
+            Ok(_) => return Fit::Done,
+            Err(WireMatrixError::Decode(message)) => message,
+            Err(_) => return Fit::Rejected,
+        };
+
+        let slot = match at_mut(sample, target) {
+            Some(Value::Array(items)) => items.first_mut(),
+            slot => slot,
+        };
+        if let Some(slot) = slot {
+            if let Some(variant) = slot.as_str().and_then(|s| expected_variant(&message, s)) {
+                *slot = Value::String(variant);
+                continue;
+            }
+        }
+
+        return match (missing_field(&message), target.last()) {
+            (Some(field), Some(Step::Key(key))) if field != key => Fit::Partial,
+            _ => Fit::Rejected,
+        };
+    }
+    Fit::Rejected
+}
+
+/// Give the empty field at `target` a value that survives the round trip
+fn enrich(sample: &mut Value, target: &[Step], codec: Codec) -> bool {
+    let Some(Step::Key(key)) = target.last() else {
+        return false;
+    };
+    let as_array = matches!(at(sample, target), Some(Value::Array(_)));
+    let values = if as_array {
+        candidates(key)
+            .into_iter()
+            .filter(|c| !c.is_array())
+            .map(|c| json!([c]))
+            .collect()
+    } else {
+        candidates(key)
+    };
+
+    let original = sample.clone();
+    for candidate in values {
+        let nested = nested_path(target, &candidate);
+        set(sample, target, candidate);
+        let settled = match fit(sample, target, codec) {
+            Fit::Done => true,
+            Fit::Partial => complete(sample, nested.into_iter().collect(), codec).is_ok(),
+            Fit::Rejected => false,
+        };
+        // Keep the encoded form, which spells out the new value's own
+        // empty fields for populate to fill
+        let encoded = settled
+            .then(|| codec(sample).ok())
+            .flatten()
+            .and_then(|encoded| at(&encoded, target).filter(|v| is_populated(v)).cloned());
+        if let Some(value) = encoded {
+            set(sample, target, value);
+            return true;
+        }
+        sample.clone_from(&original);
+    }
+    false
+}
+
+/// Fill every `null` or empty field of the object at `path`, recursing into
+/// nested objects
+fn populate(sample: &mut Value, path: &[Step], codec: Codec, depth: usize) {
+    let keys: Vec<String> = match at(sample, path) {
+        Some(Value::Object(object)) => object.keys().cloned().collect(),
+        _ => return,
+    };
+    for key in keys {
+        let mut target = path.to_vec();
+        target.push(Step::Key(key));
+        if at(sample, &target).is_some_and(|v| !is_populated(v)) {
+            enrich(sample, &target, codec);
+        }
+        if depth >= MAX_DEPTH {
+            continue;
+        }
+        match at(sample, &target) {
+            Some(Value::Object(_)) => populate(sample, &target, codec, depth + 1),
+            Some(Value::Array(items)) if items.first().is_some_and(Value::is_object) => {
+                target.push(Step::Index(0));
+                populate(sample, &target, codec, depth + 1);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Values to try for a field, most plausible for its name first
+fn candidates(field: &str) -> Vec<Value> {
+    let lower = field.to_ascii_lowercase();
+    let has = |needles: &[&str]| needles.iter().any(|n| lower.ends_with(n));
+    let mut values = if has(&[
+        "hash",
+        "key",
+        "signature",
+        "data",
+        "initiator",
+        "metadata",
+        "entry",
+        "root",
+        "cause",
+    ]) {
+        vec![json!(SAMPLE_HEX), json!(SAMPLE_URL)]
+    } else if has(&["amount", "balance", "burnt", "supply", "price"]) {
+        vec![json!(SAMPLE_AMOUNT), json!(SAMPLE_URL)]
+    } else {
+        vec![json!(SAMPLE_URL), json!(SAMPLE_HEX)]
+    };
+    let lists: Vec<Value> = values.iter().map(|v| json!([v])).collect();
+    // Fixed-size byte arrays without a hex encoding are sequences of numbers
+    values.extend([
+        json!([1u8; 32].to_vec()),
+        json!(1),
+        json!(true),
+        json!({}),
+        json!([]),
+    ]);
+    values.extend(lists);
+    values.extend([json!([1]), json!([{}])]);
+    values
+}
+
+/// Where completion continues when `candidate` is placed at `target`
+fn nested_path(target: &[Step], candidate: &Value) -> Option<Vec<Step>> {
+    let mut path = target.to_vec();
+    match candidate {
+        Value::Object(_) => Some(path),
+        Value::Array(items) if items.first().is_some_and(Value::is_object) => {
+            path.push(Step::Index(0));
+            Some(path)
+        }
+        _ => None,
+    }
+}
+
+/// Field named by a serde "missing field" error
+fn missing_field(message: &str) -> Option<&str> {
+    message.split_once("missing field `")?.1.split('`').next()
+}
+
+/// First variant serde expected in place of `value`
+fn expected_variant(message: &str, value: &str) -> Option<String> {
+    let rest = message.split_once(&format!("unknown variant `{value}`"))?.1;
+    let rest = rest.split_once("expected")?.1;
+    rest.split('`').nth(1).map(str::to_string)
+}
+
+fn is_populated(value: &Value) -> bool {
+    match value {
+        Value::Null => false,
+        Value::Array(items) => !items.is_empty(),
+        _ => true,
+    }
+}
+
+fn lower_camel(name: &str) -> String {
+    let mut chars = name.chars();
+    chars.next().map_or_else(String::new, |first| {
+        first.to_ascii_lowercase().to_string() + chars.as_str()
+    })
+}
+
+/// First field of `sample` with no counterpart in `encoded`, as a path
+fn dropped_field(sample: &Value, encoded: &Value, path: &str) -> Option<String> {
+    match (sample, encoded) {
+        (Value::Object(sample), Value::Object(encoded)) => {
+            sample.iter().find_map(|(key, value)| {
+                let path = if path.is_empty() {
+                    key.clone()
+                } else {
+                    format!("{path}.{key}")
+                };
+                match encoded.get(key) {
+                    Some(encoded) => dropped_field(value, encoded, &path),
+                    None if value.is_null() => None,
+                    None => Some(path),
+                }
+            })
+        }
+        (Value::Array(sample), Value::Array(encoded)) => sample
+            .iter()
+            .zip(encoded)
+            .enumerate()
+            .find_map(|(i, (sample, encoded))| {
+                dropped_field(sample, encoded, &format!("{path}[{i}]"))
+            }),
+        _ => None,
+    }
+}
+
+fn at<'a>(value: &'a Value, path: &[Step]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, step| match step {
+        Step::Key(key) => value.get(key),
+        Step::Index(index) => value.get(index),
+    })
+}
+
+fn at_mut<'a>(value: &'a mut Value, path: &[Step]) -> Option<&'a mut Value> {
+    path.iter().try_fold(value, |value, step| match step {
+        Step::Key(key) => value.get_mut(key),
+        Step::Index(index) => value.get_mut(index),
+    })
+}
+
+/// Set the field at `path`, whose parent must be an object
+fn set(sample: &mut Value, path: &[Step], value: Value) {
+    let Some((Step::Key(key), parent)) = path.split_last() else {
+        return;
+    };
+    if let Some(Value::Object(object)) = at_mut(sample, parent) {
+        object.insert(key.clone(), value);
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wire_matrix_roundtrips() {
+        let checked = verify_wire_matrix().unwrap_or_else(|failures| {
+            unreachable!("wire matrix failures:\n{}", failures.join("\n"))
+        });
+        assert!(checked > 150);
+    }
+
+    #[test]
+    fn test_build_case_samples() {
+        let case = build_case::<TransactionBody>(
+            "transactions::AddCreditsBody",
+            json!({"type": "addCredits"}),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(case.minimal["type"], "addCredits");
+        assert!(case.minimal.get("recipient").is_some());
+        assert!(case.minimal.get("oracle").is_some());
+
+        let case = build_case::<TransactionHeader>(
+            "header::TransactionHeader",
+            json!({}),
+            &["Memo", "Metadata", "Authorities"],
+        )
+        .unwrap();
+        assert!(case.minimal.get("memo").is_none());
+        assert!(case.maximal.get("memo").is_some());
+        assert!(case.maximal["authorities"]
+            .as_array()
+            .is_some_and(|a| !a.is_empty()));
+        case.verify().unwrap();
+    }
+
+    #[test]
+    fn test_roundtrip_reports_dropped_fields() {
+        let err =
+            roundtrip::<crate::generated::types::ADI>(&json!({"Url": SAMPLE_URL, "url": "x"}))
+                .unwrap_err();
+        assert_eq!(err, WireMatrixError::DroppedField("url".to_string()));
+    }
+
+    /// Decode and re-encode the bodies of transactions captured from a node
+    ///
+    /// The captures are made with `tooling/capture_node_bodies.py`, one file
+    /// per body type; see `tests/golden/node/README.md`.
+    #[test]
+    #[ignore = "Needs transactions captured from a DevNet or testnet node in tests/golden/node"]
+    fn test_bodies_match_captured_node_records() {
+        use crate::builders::to_wire;
+        use crate::codec::unmarshal_transaction_body;
+        use crate::helpers::marshal_body_to_binary;
+
+        let captures = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("tests/golden/node");
+        let manifest: BodyManifest = serde_json::from_str(TRANSACTIONS_MANIFEST).unwrap();
+        let mut missing = Vec::new();
+        for body in manifest.bodies {
+            let Ok(capture) = std::fs::read_to_string(captures.join(format!("{}.json", body.wire)))
+            else {
+                missing.push(body.wire);
+                continue;
+            };
+            let capture: Value = serde_json::from_str(&capture).unwrap();
+            let wire = &capture["record"]["message"]["transaction"]["body"];
+            assert_eq!(wire["type"], body.wire.as_str());
+
+            let typed: TransactionBody = serde_json::from_value(wire.clone()).unwrap();
+            let encoded = to_wire(&typed).unwrap();
+            assert_eq!(&encoded, wire, "{} JSON", body.wire);
+
+            let binary = marshal_body_to_binary(wire).unwrap();
+            assert_eq!(
+                marshal_body_to_binary(&encoded).unwrap(),
+                binary,
+                "{} binary",
+                body.wire
+            );
+            let decoded = unmarshal_transaction_body(&binary).unwrap();
+            assert_eq!(
+                marshal_body_to_binary(&decoded).unwrap(),
+                binary,
+                "{} decoded",
+                body.wire
+            );
+        }
+        assert!(
+            missing.is_empty(),
+            "no node capture for: {}",
+            missing.join(", ")
+        );
+    }
+}
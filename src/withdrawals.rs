@@ -0,0 +1,588 @@
+//! Withdrawal pipeline with replay protection for custodial services
+//!
+//! [`WithdrawalProcessor`] turns withdrawal requests into sendTokens
+//! transactions exactly once. Every request carries a caller-chosen dedupe key;
+//! the signed envelope is written to a [`WithdrawalStore`] *before* it is
+//! submitted, so a crash at any point is recovered by resubmitting the same
+//! signed transaction (which the network deduplicates by hash) instead of
+//! signing a second one.
+//!
+//! The lifecycle of a record is `signed` → `submitted` → `delivered` or
+//! `failed`. [`WithdrawalProcessor::recover`] drives every unfinished record
+//! forward after a restart.
+
+use crate::helpers::{extract_txid, SmartSigner, TxBody};
+use crate::json_rpc_client::JsonRpcError;
+use crate::storage::{get_json, put_json, scan_json, KvStore, StorageError};
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use thiserror::Error;
+
+/// A withdrawal requested by a customer
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalRequest {
+    /// Dedupe key; a key is only ever paid out once
+    pub id: String,
+    /// Destination token account
+    pub to: String,
+    /// Amount in base units
+    pub amount: u64,
+}
+
+/// Where a withdrawal is in its lifecycle
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "state", rename_all = "camelCase")]
+pub enum WithdrawalState {
+    /// Signed and persisted, not yet accepted by the network
+    #[serde(rename_all = "camelCase")]
+    Signed {
+        /// The signed envelope, resubmitted as-is on recovery
+        envelope: Value,
+    },
+    /// Accepted by the network, delivery not yet confirmed
+    #[serde(rename_all = "camelCase")]
+    Submitted {
+        /// Transaction ID reported on submission
+        txid: String,
+    },
+    /// Executed successfully
+    #[serde(rename_all = "camelCase")]
+    Delivered {
+        /// Transaction ID
+        txid: String,
+    },
+    /// Rejected or failed during execution
+    #[serde(rename_all = "camelCase")]
+    Failed {
+        /// Failure message from the network
+        reason: String,
+    },
+}
+
+/// A persisted withdrawal
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalRecord {
+    /// The original request
+    pub request: WithdrawalRequest,
+    /// Hash of the signed transaction, fixed once signed
+    pub tx_hash: String,
+    /// Current state
+    #[serde(flatten)]
+    pub state: WithdrawalState,
+}
+
+impl WithdrawalRecord {
+    /// Whether the withdrawal has reached a final state
+    #[must_use]
+    pub const fn is_finished(&self) -> bool {
+        matches!(
+            self.state,
+            WithdrawalState::Delivered { .. } | WithdrawalState::Failed { .. }
+        )
+    }
+}
+
+/// Errors from the withdrawal pipeline
+#[derive(Error, Debug)]
+pub enum WithdrawalError {
+    /// The dedupe key was already used for a different destination or amount
+    #[error("Withdrawal {id} already exists with different parameters")]
+    Conflict {
+        /// The reused dedupe key
+        id: String,
+    },
+
+    /// The request itself is malformed
+    #[error("Invalid withdrawal {id}: {reason}")]
+    Invalid {
+        /// Dedupe key of the request
+        id: String,
+        /// What is wrong with it
+        reason: String,
+    },
+
+    /// Reading or writing the store failed
+    #[error("Withdrawal store error: {0}")]
+    Store(String),
+
+    /// Signing or talking to the network failed; the record is left as-is
+    #[error("Network error: {0}")]
+    Network(#[from] JsonRpcError),
+}
+
+impl From<StorageError> for WithdrawalError {
+    fn from(e: StorageError) -> Self {
+        Self::Store(e.to_string())
+    }
+}
+
+/// Durable storage for withdrawal records
+///
+/// `save` must not return until the record is durable: the processor relies
+/// on it to never sign a request twice.
+pub trait WithdrawalStore: Send + Sync {
+    /// Look up a record by dedupe key
+    fn load(&self, id: &str) -> Result<Option<WithdrawalRecord>, WithdrawalError>;
+
+    /// Insert or replace a record
+    fn save(&mut self, record: &WithdrawalRecord) -> Result<(), WithdrawalError>;
+
+    /// Every record that is not yet delivered or failed
+    fn unfinished(&self) -> Result<Vec<WithdrawalRecord>, WithdrawalError>;
+}
+
+/// In-memory store, for tests and for services with their own persistence
+#[derive(Debug, Clone, Default)]
+pub struct MemoryWithdrawalStore {
+    records: BTreeMap<String, WithdrawalRecord>,
+}
+
+impl WithdrawalStore for MemoryWithdrawalStore {
+    fn load(&self, id: &str) -> Result<Option<WithdrawalRecord>, WithdrawalError> {
+        Ok(self.records.get(id).cloned())
+    }
+
+    fn save(&mut self, record: &WithdrawalRecord) -> Result<(), WithdrawalError> {
+        self.records.insert(record.request.id.clone(), record.clone());
+        Ok(())
+    }
+
+    fn unfinished(&self) -> Result<Vec<WithdrawalRecord>, WithdrawalError> {
+        Ok(self.records.values().filter(|r| !r.is_finished()).cloned().collect())
+    }
+}
+
+/// Store backed by a single JSON file
+///
+/// The whole file is rewritten through a temporary file and a rename on every
+/// save, which keeps it consistent across crashes. Suited to modest volumes;
+/// implement [`WithdrawalStore`] over a database for more.
+#[derive(Debug)]
+pub struct FileWithdrawalStore {
+    path: PathBuf,
+    records: BTreeMap<String, WithdrawalRecord>,
+}
+
+impl FileWithdrawalStore {
+    /// Open the store at `path`, creating it on first save if it does not exist
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, WithdrawalError> {
+        let path = path.as_ref().to_path_buf();
+        let records = match std::fs::read(&path) {
+            Ok(bytes) => serde_json::from_slice::<Vec<WithdrawalRecord>>(&bytes)
+                .map_err(|e| WithdrawalError::Store(format!("{}: {e}", path.display())))?
+                .into_iter()
+                .map(|r| (r.request.id.clone(), r))
+                .collect(),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => BTreeMap::new(),
+            Err(e) => return Err(WithdrawalError::Store(format!("{}: {e}", path.display()))),
+        };
+        Ok(Self { path, records })
+    }
+}
+
+impl WithdrawalStore for FileWithdrawalStore {
+    fn load(&self, id: &str) -> Result<Option<WithdrawalRecord>, WithdrawalError> {
+        Ok(self.records.get(id).cloned())
+    }
+
+    fn save(&mut self, record: &WithdrawalRecord) -> Result<(), WithdrawalError> {
+        let mut records = self.records.clone();
+        records.insert(record.request.id.clone(), record.clone());
+
+        let data = serde_json::to_vec_pretty(&records.values().collect::<Vec<_>>())
+            .map_err(|e| WithdrawalError::Store(e.to_string()))?;
+        let tmp = self.path.with_extension("tmp");
+        let write = || -> std::io::Result<()> {
+            use std::io::Write;
+            let mut file = std::fs::File::create(&tmp)?;
+            file.write_all(&data)?;
+            file.sync_all()?;
+            std::fs::rename(&tmp, &self.path)
+        };
+        write().map_err(|e| WithdrawalError::Store(format!("{}: {e}", self.path.display())))?;
+
+        self.records = records;
+        Ok(())
+    }
+
+    fn unfinished(&self) -> Result<Vec<WithdrawalRecord>, WithdrawalError> {
+        Ok(self.records.values().filter(|r| !r.is_finished()).cloned().collect())
+    }
+}
+
+/// Store over any [`KvStore`], one entry per record under `withdrawal/`
+///
+/// Lets a service keep withdrawals in the same database as the rest of its
+/// state, e.g. a [`FileKvStore`](crate::storage::FileKvStore) directory or a
+/// sled database.
+#[derive(Debug, Clone, Default)]
+pub struct KvWithdrawalStore<S: KvStore> {
+    kv: S,
+}
+
+impl<S: KvStore> KvWithdrawalStore<S> {
+    const PREFIX: &'static str = "withdrawal/";
+
+    /// Keep records in `kv`
+    pub const fn new(kv: S) -> Self {
+        Self { kv }
+    }
+
+    /// The underlying key-value store
+    pub const fn kv(&self) -> &S {
+        &self.kv
+    }
+}
+
+impl<S: KvStore> WithdrawalStore for KvWithdrawalStore<S> {
+    fn load(&self, id: &str) -> Result<Option<WithdrawalRecord>, WithdrawalError> {
+        Ok(get_json(&self.kv, &format!("{}{id}", Self::PREFIX))?)
+    }
+
+    fn save(&mut self, record: &WithdrawalRecord) -> Result<(), WithdrawalError> {
+        let key = format!("{}{}", Self::PREFIX, record.request.id);
+        Ok(put_json(&mut self.kv, &key, record)?)
+    }
+
+    fn unfinished(&self) -> Result<Vec<WithdrawalRecord>, WithdrawalError> {
+        let records: Vec<WithdrawalRecord> = scan_json(&self.kv, Self::PREFIX)?;
+        Ok(records.into_iter().filter(|r| !r.is_finished()).collect())
+    }
+}
+
+/// Signs, submits and confirms withdrawals from one token account
+#[derive(Debug)]
+pub struct WithdrawalProcessor<'a, S: WithdrawalStore> {
+    signer: SmartSigner<'a>,
+    source: String,
+    store: S,
+    max_attempts: u32,
+}
+
+impl<'a, S: WithdrawalStore> WithdrawalProcessor<'a, S> {
+    /// Pay withdrawals out of `source`, signed by `signer`
+    pub fn new(signer: SmartSigner<'a>, source: &str, store: S) -> Self {
+        Self {
+            signer,
+            source: source.to_string(),
+            store,
+            max_attempts: 30,
+        }
+    }
+
+    /// Number of 2-second delivery checks before leaving a record `submitted`
+    #[must_use]
+    pub const fn max_attempts(mut self, attempts: u32) -> Self {
+        self.max_attempts = attempts;
+        self
+    }
+
+    /// The underlying store
+    pub const fn store(&self) -> &S {
+        &self.store
+    }
+
+    /// Sign a request and persist it without touching the network
+    ///
+    /// Returns the existing record if the dedupe key is already known with
+    /// the same destination and amount, or [`WithdrawalError::Conflict`] if it
+    /// is known with different ones. Uses the signer's cached version.
+    pub fn prepare(&mut self, request: &WithdrawalRequest) -> Result<WithdrawalRecord, WithdrawalError> {
+        if let Some(existing) = self.store.load(&request.id)? {
+            if existing.request != *request {
+                return Err(WithdrawalError::Conflict { id: request.id.clone() });
+            }
+            return Ok(existing);
+        }
+        if request.amount == 0 {
+            return Err(WithdrawalError::Invalid {
+                id: request.id.clone(),
+                reason: "amount must be positive".to_string(),
+            });
+        }
+        if !request.to.starts_with("acc://") {
+            return Err(WithdrawalError::Invalid {
+                id: request.id.clone(),
+                reason: "destination must be an acc:// URL".to_string(),
+            });
+        }
+
+        let body = TxBody::send_tokens_single(&request.to, &request.amount.to_string());
+        let envelope = self.signer.sign(&self.source, &body, Some(&request.id))?;
+        let tx_hash = envelope["signatures"][0]["transactionHash"]
+            .as_str()
+            .unwrap_or_default()
+            .to_string();
+
+        let record = WithdrawalRecord {
+            request: request.clone(),
+            tx_hash,
+            state: WithdrawalState::Signed { envelope },
+        };
+        self.store.save(&record)?;
+        Ok(record)
+    }
+
+    /// Process a withdrawal through to delivery
+    ///
+    /// Safe to call again with the same request at any time: a known dedupe
+    /// key is resumed from its persisted state and never signed again. If
+    /// delivery is not confirmed within the attempt limit the record stays
+    /// `submitted` and is picked up by [`recover`](Self::recover).
+    pub async fn process(&mut self, request: &WithdrawalRequest) -> Result<WithdrawalRecord, WithdrawalError> {
+        if self.store.load(&request.id)?.is_none() {
+            self.signer.refresh_version().await?;
+        }
+        let record = self.prepare(request)?;
+        self.advance(record).await
+    }
+
+    /// Drive every unfinished withdrawal forward, e.g. after a restart
+    ///
+    /// Records that fail with a network error are left untouched and
+    /// reported in the returned list with their current state.
+    pub async fn recover(&mut self) -> Result<Vec<WithdrawalRecord>, WithdrawalError> {
+        let mut results = Vec::new();
+        for record in self.store.unfinished()? {
+            match self.advance(record.clone()).await {
+                Ok(advanced) => results.push(advanced),
+                Err(WithdrawalError::Network(_)) => results.push(record),
+                Err(e) => return Err(e),
+            }
+        }
+        Ok(results)
+    }
+
+    async fn advance(&mut self, mut record: WithdrawalRecord) -> Result<WithdrawalRecord, WithdrawalError> {
+        if let WithdrawalState::Signed { envelope } = &record.state {
+            let submitted: Result<Value, JsonRpcError> = self
+                .signer
+                .client()
+                .v3_client
+                .call_v3("submit", json!({ "envelope": envelope }))
+                .await;
+            let state = match submitted {
+                Ok(response) => match submission_error(&response) {
+                    // The network has seen this hash before: a previous run
+                    // submitted it and crashed before recording that.
+                    Some(message) if is_duplicate(&message) => self.submitted_state(&record),
+                    Some(reason) => WithdrawalState::Failed { reason },
+                    None => WithdrawalState::Submitted {
+                        txid: extract_txid(&response).unwrap_or_else(|| self.txid(&record)),
+                    },
+                },
+                Err(e) => {
+                    if self.query_status(&record.tx_hash).await.is_none() {
+                        return Err(e.into());
+                    }
+                    self.submitted_state(&record)
+                }
+            };
+            record.state = state;
+            self.store.save(&record)?;
+        }
+
+        if let WithdrawalState::Submitted { txid } = &record.state {
+            let txid = txid.clone();
+            for attempt in 0..self.max_attempts {
+                if attempt > 0 {
+                    tokio::time::sleep(Duration::from_secs(2)).await;
+                }
+                match self.query_status(&record.tx_hash).await {
+                    Some(Ok(())) => {
+                        record.state = WithdrawalState::Delivered { txid };
+                        self.store.save(&record)?;
+                        break;
+                    }
+                    Some(Err(reason)) => {
+                        record.state = WithdrawalState::Failed { reason };
+                        self.store.save(&record)?;
+                        break;
+                    }
+                    None => {}
+                }
+            }
+        }
+
+        Ok(record)
+    }
+
+    fn submitted_state(&self, record: &WithdrawalRecord) -> WithdrawalState {
+        WithdrawalState::Submitted { txid: self.txid(record) }
+    }
+
+    fn txid(&self, record: &WithdrawalRecord) -> String {
+        format!("acc://{}@{}", record.tx_hash, self.source.trim_start_matches("acc://"))
+    }
+
+    /// `None` while unknown or pending, `Some(Ok)` once delivered, `Some(Err)` on failure
+    async fn query_status(&self, tx_hash: &str) -> Option<Result<(), String>> {
+        let result: Value = self
+            .signer
+            .client()
+            .v3_client
+            .call_v3(
+                "query",
+                json!({
+                    "scope": format!("acc://{tx_hash}@unknown"),
+                    "query": {"queryType": "default"}
+                }),
+            )
+            .await
+            .ok()?;
+        execution_outcome(&result)
+    }
+}
+
+/// Outcome of an executed transaction from its V3 message record
+fn execution_outcome(record: &Value) -> Option<Result<(), String>> {
+    let status = record.get("status")?;
+    let delivered = status.as_str() == Some("delivered")
+        || status.get("delivered").and_then(Value::as_bool).unwrap_or(false);
+    if !delivered {
+        return None;
+    }
+    let error = record
+        .get("error")
+        .or_else(|| status.get("error"))
+        .filter(|e| !e.is_null());
+    Some(error.map_or(Ok(()), |e| {
+        Err(e
+            .get("message")
+            .and_then(Value::as_str)
+            .map_or_else(|| e.to_string(), str::to_string))
+    }))
+}
+
+/// The failure message of a submit response, if any submission failed
+pub(crate) fn submission_error(response: &Value) -> Option<String> {
+    let submissions = response.as_array().map_or_else(|| vec![response], |a| a.iter().collect());
+    submissions
+        .into_iter()
+        .find(|s| s.get("success").and_then(Value::as_bool) == Some(false))
+        .map(|s| {
+            s.get("message")
+                .and_then(Value::as_str)
+                .unwrap_or("rejected")
+                .to_string()
+        })
+}
+
+pub(crate) fn is_duplicate(message: &str) -> bool {
+    let message = message.to_ascii_lowercase();
+    message.contains("already") || message.contains("duplicate")
+}
+
+#[cfg(test)]
+#[allow(clippy::unwrap_used)]
+mod tests {
+    use super::*;
+    use crate::client::AccumulateClient;
+    use ed25519_dalek::SigningKey;
+
+    async fn client() -> AccumulateClient {
+        AccumulateClient::new_with_options(
+            url::Url::parse("http://localhost:26660/v2").unwrap(),
+            url::Url::parse("http://localhost:26661/v3").unwrap(),
+            crate::AccOptions::default(),
+        )
+        .await
+        .unwrap()
+    }
+
+    fn request(id: &str, amount: u64) -> WithdrawalRequest {
+        WithdrawalRequest {
+            id: id.to_string(),
+            to: "acc://customer.acme/tokens".to_string(),
+            amount,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_prepare_signs_each_key_once() {
+        let client = client().await;
+        let signer = SmartSigner::new(&client, SigningKey::from_bytes(&[6u8; 32]), "acc://custody.acme/book/1");
+        let mut processor = WithdrawalProcessor::new(signer, "acc://custody.acme/hot", MemoryWithdrawalStore::default());
+
+        let first = processor.prepare(&request("w-1", 500)).unwrap();
+        let WithdrawalState::Signed { envelope } = &first.state else {
+            unreachable!("new withdrawals start out signed");
+        };
+        assert_eq!(envelope["transaction"][0]["header"]["memo"], "w-1");
+        assert_eq!(envelope["signatures"][0]["transactionHash"], first.tx_hash.as_str());
+
+        // Same key again: the stored envelope is returned, nothing is re-signed
+        let again = processor.prepare(&request("w-1", 500)).unwrap();
+        assert_eq!(again, first);
+
+        assert!(matches!(
+            processor.prepare(&request("w-1", 501)),
+            Err(WithdrawalError::Conflict { .. })
+        ));
+        assert!(matches!(
+            processor.prepare(&request("w-2", 0)),
+            Err(WithdrawalError::Invalid { .. })
+        ));
+        assert_eq!(processor.store().unfinished().unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_file_store_survives_restart() {
+        let path = std::env::temp_dir().join(format!("withdrawals-{}.json", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let client = client().await;
+
+        let signed = {
+            let signer = SmartSigner::new(&client, SigningKey::from_bytes(&[6u8; 32]), "acc://custody.acme/book/1");
+            let store = FileWithdrawalStore::open(&path).unwrap();
+            let mut processor = WithdrawalProcessor::new(signer, "acc://custody.acme/hot", store);
+            processor.prepare(&request("w-1", 500)).unwrap()
+        };
+
+        let mut store = FileWithdrawalStore::open(&path).unwrap();
+        assert_eq!(store.unfinished().unwrap(), vec![signed.clone()]);
+
+        let delivered = WithdrawalRecord {
+            state: WithdrawalState::Delivered { txid: "acc://x@custody.acme/hot".to_string() },
+            ..signed
+        };
+        store.save(&delivered).unwrap();
+        let store = FileWithdrawalStore::open(&path).unwrap();
+        assert!(store.unfinished().unwrap().is_empty());
+        assert_eq!(store.load("w-1").unwrap(), Some(delivered));
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_kv_store_backend() {
+        let client = client().await;
+        let signer = SmartSigner::new(&client, SigningKey::from_bytes(&[6u8; 32]), "acc://custody.acme/book/1");
+        let store = KvWithdrawalStore::new(crate::storage::MemoryKvStore::new());
+        let mut processor = WithdrawalProcessor::new(signer, "acc://custody.acme/hot", store);
+
+        let signed = processor.prepare(&request("w-1", 500)).unwrap();
+        assert_eq!(processor.prepare(&request("w-1", 500)).unwrap(), signed);
+        assert!(processor.store().kv().get("withdrawal/w-1").unwrap().is_some());
+        assert_eq!(processor.store().unfinished().unwrap(), vec![signed]);
+    }
+
+    #[test]
+    fn test_execution_outcome() {
+        assert_eq!(execution_outcome(&json!({"status": "pending"})), None);
+        assert_eq!(execution_outcome(&json!({"status": "delivered"})), Some(Ok(())));
+        assert_eq!(
+            execution_outcome(&json!({"status": "delivered", "error": {"message": "insufficient balance"}})),
+            Some(Err("insufficient balance".to_string()))
+        );
+        assert_eq!(
+            submission_error(&json!([{"success": false, "message": "transaction already delivered"}])).as_deref(),
+            Some("transaction already delivered")
+        );
+        assert!(is_duplicate("Transaction already delivered"));
+    }
+}
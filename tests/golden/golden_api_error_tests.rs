@@ -55,14 +55,14 @@ async fn api_error_shape_parity() {
         // Test with a simple query call (using minimal params)
         let params = generated::api_methods::QueryParams {
             url: "acc://test.acme".to_string(),
-            options: Some(json!({"prove": false})),
+            options: Some(QueryOptions { prove: Some(false), ..Default::default() }),
         };
 
         let err = client.query(params).await.unwrap_err();
 
         // Extract error details
         let (actual_code, actual_message) = match &err {
-            errors::Error::Rpc { code, message } => (*code, message.clone()),
+            errors::Error::Rpc { code, message, .. } => (*code, message.clone()),
             _ => panic!("Expected RPC error, got: {:?}", err),
         };
 
@@ -99,7 +99,7 @@ async fn api_error_serialization_golden() {
         errors::Error::rpc(404, "not found".to_string()),
         errors::Error::rpc(500, "server error".to_string()),
         errors::Error::General("general error".to_string()),
-        errors::Error::Network("network timeout".to_string()),
+        errors::Error::Transport("network timeout".to_string()),
         errors::Error::Encoding("encoding failed".to_string()),
     ];
 
@@ -107,7 +107,7 @@ async fn api_error_serialization_golden() {
 
     for err in errors {
         let error_info = match &err {
-            errors::Error::Rpc { code, message } => json!({
+            errors::Error::Rpc { code, message, .. } => json!({
                 "type": "rpc",
                 "code": code,
                 "message": message
@@ -116,7 +116,7 @@ async fn api_error_serialization_golden() {
                 "type": "general",
                 "message": msg
             }),
-            errors::Error::Network(msg) => json!({
+            errors::Error::Transport(msg) => json!({
                 "type": "network",
                 "message": msg
             }),
@@ -157,7 +157,7 @@ fn error_construction_golden() {
         ("from_string", errors::Error::from("string error".to_string())),
         ("from_str", errors::Error::from("str error")),
         ("general", errors::Error::General("direct general".to_string())),
-        ("network", errors::Error::Network("network issue".to_string())),
+        ("network", errors::Error::Transport("network issue".to_string())),
         ("encoding", errors::Error::Encoding("encoding problem".to_string())),
     ];
 
@@ -165,7 +165,7 @@ fn error_construction_golden() {
 
     for (name, error) in constructed_errors {
         let serialized = match &error {
-            errors::Error::Rpc { code, message } => json!({
+            errors::Error::Rpc { code, message, .. } => json!({
                 "type": "rpc",
                 "code": code,
                 "message": message
@@ -174,7 +174,7 @@ fn error_construction_golden() {
                 "type": "general",
                 "message": msg
             }),
-            errors::Error::Network(msg) => json!({
+            errors::Error::Transport(msg) => json!({
                 "type": "network",
                 "message": msg
             }),
@@ -223,7 +223,7 @@ fn signature_error_golden() {
 
     for (name, sig_error) in sig_errors {
         // Convert to main error type
-        let main_error = errors::Error::Signature(sig_error.clone());
+        let main_error = errors::Error::Signing(sig_error.clone());
 
         let serialized = json!({
             "signature_error_type": name,
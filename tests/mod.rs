@@ -17,8 +17,6 @@ pub mod golden;
 // Fuzzing and property-based tests
 pub mod fuzz;
 
-// Coverage validation tests
-pub mod coverage;
 
 // Specialized test scenarios
 pub mod specialized;
@@ -0,0 +1,383 @@
+//! Public API snapshot guard
+//!
+//! Renders every public item reachable through `pub mod`s of the crate, with
+//! its signature, to one line each, and compares the result with
+//! `tests/api/public-api.txt`. Any difference fails:
+//!
+//! - additions are blessed with `ACCUMULATE_API_SNAPSHOT=bless`;
+//! - removed or changed lines are breaking changes, and `bless` accepts them
+//!   only once `Cargo.toml` carries a semver-incompatible version to the one
+//!   recorded in the snapshot (`bless-breaking` overrides that).
+//!
+//! ```text
+//! cargo test --features api-snapshot --test public_api
+//! ACCUMULATE_API_SNAPSHOT=bless cargo test --features api-snapshot --test public_api
+//! ```
+
+#![allow(clippy::panic)]
+
+use quote::ToTokens;
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::fs;
+use std::path::{Path, PathBuf};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Fields, Ident, ImplItem, Item, Token, TraitItem, UseTree, Visibility};
+
+const SNAPSHOT: &str = "tests/api/public-api.txt";
+const CRATE: &str = "accumulate_client";
+
+/// Lines describing the public API
+#[derive(Default)]
+struct Api {
+    lines: BTreeSet<String>,
+}
+
+impl Api {
+    fn add(&mut self, cfg: &str, line: impl std::fmt::Display) {
+        self.lines.insert(format!("{line}{cfg}"));
+    }
+
+    /// Record the public items of the module `path` from `items`; `dir` is
+    /// where its child module files live
+    fn module(&mut self, path: &str, dir: &Path, items: &[Item], cfg: &str) {
+        for item in items {
+            self.item(path, dir, item, cfg);
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    fn item(&mut self, path: &str, dir: &Path, item: &Item, cfg: &str) {
+        match item {
+            Item::Mod(m) if is_pub(&m.vis) => {
+                let cfg = with_cfg(cfg, &m.attrs);
+                let name = m.ident.to_string();
+                let child = format!("{path}::{name}");
+                self.add(&cfg, format!("pub mod {child}"));
+                if let Some((_, items)) = &m.content {
+                    self.module(&child, &dir.join(&name), items, &cfg);
+                } else {
+                    let (file, child_dir) = module_file(dir, &name);
+                    let parsed = parse(&file);
+                    self.module(&child, &child_dir, &parsed.items, &cfg);
+                }
+            }
+            Item::Fn(f) if is_pub(&f.vis) => {
+                self.add(
+                    &with_cfg(cfg, &f.attrs),
+                    format!("pub {path}::{}: {}", f.sig.ident, tokens(&f.sig)),
+                );
+            }
+            Item::Struct(s) if is_pub(&s.vis) => {
+                let cfg = with_cfg(cfg, &s.attrs);
+                let name = format!("{path}::{}", s.ident);
+                self.add(&cfg, format!("pub struct {name}{}", tokens(&s.generics)));
+                self.derives(path, &s.ident, &s.attrs, &cfg);
+                self.fields(&name, &s.fields, &cfg, true);
+            }
+            Item::Enum(e) if is_pub(&e.vis) => {
+                let cfg = with_cfg(cfg, &e.attrs);
+                let name = format!("{path}::{}", e.ident);
+                self.add(&cfg, format!("pub enum {name}{}", tokens(&e.generics)));
+                self.derives(path, &e.ident, &e.attrs, &cfg);
+                for variant in &e.variants {
+                    let cfg = with_cfg(&cfg, &variant.attrs);
+                    let variant_name = format!("{name}::{}", variant.ident);
+                    self.add(&cfg, format!("pub variant {variant_name}"));
+                    self.fields(&variant_name, &variant.fields, &cfg, false);
+                }
+            }
+            Item::Trait(t) if is_pub(&t.vis) => {
+                let cfg = with_cfg(cfg, &t.attrs);
+                let name = format!("{path}::{}", t.ident);
+                let supertraits = if t.supertraits.is_empty() {
+                    String::new()
+                } else {
+                    format!(": {}", tokens(&t.supertraits))
+                };
+                self.add(
+                    &cfg,
+                    format!("pub trait {name}{}{supertraits}", tokens(&t.generics)),
+                );
+                for item in &t.items {
+                    let line = match item {
+                        TraitItem::Fn(f) => format!("{}: {}", f.sig.ident, tokens(&f.sig)),
+                        TraitItem::Type(ty) => format!("{}: {}", ty.ident, tokens(ty)),
+                        TraitItem::Const(c) => format!("{}: {}", c.ident, tokens(&c.ty)),
+                        _ => continue,
+                    };
+                    self.add(&cfg, format!("pub {name}::{line}"));
+                }
+            }
+            Item::Impl(i) => {
+                let cfg = with_cfg(cfg, &i.attrs);
+                let self_ty = tokens(&i.self_ty);
+                let generics = tokens(&i.generics);
+                if let Some((negative, trait_path, _)) = &i.trait_ {
+                    let not = if negative.is_some() { "!" } else { "" };
+                    self.add(
+                        &cfg,
+                        format!(
+                            "impl{generics} {not}{} for {self_ty} in {path}",
+                            tokens(trait_path)
+                        ),
+                    );
+                    return;
+                }
+                for item in &i.items {
+                    let line = match item {
+                        ImplItem::Fn(f) if is_pub(&f.vis) => {
+                            format!("{}: {}", f.sig.ident, tokens(&f.sig))
+                        }
+                        ImplItem::Const(c) if is_pub(&c.vis) => {
+                            format!("{}: {}", c.ident, tokens(&c.ty))
+                        }
+                        _ => continue,
+                    };
+                    self.add(
+                        &with_cfg(&cfg, item_attrs(item)),
+                        format!("pub impl{generics} {path}::{self_ty}::{line}"),
+                    );
+                }
+            }
+            Item::Const(c) if is_pub(&c.vis) => self.add(
+                &with_cfg(cfg, &c.attrs),
+                format!("pub const {path}::{}: {}", c.ident, tokens(&c.ty)),
+            ),
+            Item::Static(s) if is_pub(&s.vis) => self.add(
+                &with_cfg(cfg, &s.attrs),
+                format!("pub static {path}::{}: {}", s.ident, tokens(&s.ty)),
+            ),
+            Item::Type(t) if is_pub(&t.vis) => self.add(
+                &with_cfg(cfg, &t.attrs),
+                format!(
+                    "pub type {path}::{}{} = {}",
+                    t.ident,
+                    tokens(&t.generics),
+                    tokens(&t.ty)
+                ),
+            ),
+            Item::Use(u) if is_pub(&u.vis) => {
+                let cfg = with_cfg(cfg, &u.attrs);
+                let mut uses = Vec::new();
+                flatten_use("", &u.tree, &mut uses);
+                for used in uses {
+                    self.add(&cfg, format!("pub use {used} in {path}"));
+                }
+            }
+            Item::Macro(m) if m.attrs.iter().any(|a| a.path().is_ident("macro_export")) => {
+                if let Some(ident) = &m.ident {
+                    self.add(cfg, format!("macro {CRATE}::{ident}!"));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Record `#[derive]`d traits like written impls
+    fn derives(&mut self, path: &str, ident: &Ident, attrs: &[Attribute], cfg: &str) {
+        for attr in attrs.iter().filter(|a| a.path().is_ident("derive")) {
+            let traits = attr
+                .parse_args_with(Punctuated::<syn::Path, Token![,]>::parse_terminated)
+                .unwrap_or_default();
+            for derived in traits {
+                self.add(
+                    cfg,
+                    format!("impl {} for {ident} in {path}", tokens(&derived)),
+                );
+            }
+        }
+    }
+
+    fn fields(&mut self, owner: &str, fields: &Fields, cfg: &str, pub_only: bool) {
+        for (i, field) in fields.iter().enumerate() {
+            if pub_only && !is_pub(&field.vis) {
+                continue;
+            }
+            let name = field
+                .ident
+                .as_ref()
+                .map_or_else(|| i.to_string(), ToString::to_string);
+            self.add(
+                &with_cfg(cfg, &field.attrs),
+                format!("pub field {owner}::{name}: {}", tokens(&field.ty)),
+            );
+        }
+    }
+}
+
+const fn is_pub(vis: &Visibility) -> bool {
+    matches!(vis, Visibility::Public(_))
+}
+
+fn item_attrs(item: &ImplItem) -> &[Attribute] {
+    match item {
+        ImplItem::Fn(f) => &f.attrs,
+        ImplItem::Const(c) => &c.attrs,
+        _ => &[],
+    }
+}
+
+/// `cfg` extended with the `#[cfg]` attributes in `attrs`
+fn with_cfg(cfg: &str, attrs: &[Attribute]) -> String {
+    attrs
+        .iter()
+        .filter(|a| a.path().is_ident("cfg"))
+        .fold(cfg.to_string(), |cfg, a| {
+            format!("{cfg} #[{}]", tokens(&a.meta))
+        })
+}
+
+/// One `a::b::c` or `a::b::c as d` per leaf of a use tree
+fn flatten_use(prefix: &str, tree: &UseTree, out: &mut Vec<String>) {
+    match tree {
+        UseTree::Path(p) => flatten_use(&format!("{prefix}{}::", p.ident), &p.tree, out),
+        UseTree::Name(n) => out.push(format!("{prefix}{}", n.ident)),
+        UseTree::Rename(r) => out.push(format!("{prefix}{} as {}", r.ident, r.rename)),
+        UseTree::Glob(_) => out.push(format!("{prefix}*")),
+        UseTree::Group(g) => {
+            for tree in &g.items {
+                flatten_use(prefix, tree, out);
+            }
+        }
+    }
+}
+
+/// Render tokens with Rust-like spacing
+fn tokens(node: &impl ToTokens) -> String {
+    let mut text = node.to_token_stream().to_string();
+    for (from, to) in [
+        (" :: ", "::"),
+        (":: ", "::"),
+        (" < ", "<"),
+        ("< ", "<"),
+        (" >", ">"),
+        (" ,", ","),
+        (",)", ")"),
+        (",>", ">"),
+        (" (", "("),
+        ("( ", "("),
+        (" )", ")"),
+        (" [", "["),
+        ("[ ", "["),
+        (" ]", "]"),
+        (" :", ":"),
+        ("& ", "&"),
+        (" ;", ";"),
+        ("' ", "'"),
+    ] {
+        text = text.replace(from, to);
+    }
+    text.replace("->", " -> ").replace("  ", " ")
+}
+
+/// File holding the out-of-line module `name` declared in `dir`, and the
+/// directory of its own children
+fn module_file(dir: &Path, name: &str) -> (PathBuf, PathBuf) {
+    let flat = dir.join(format!("{name}.rs"));
+    if flat.exists() {
+        (flat, dir.join(name))
+    } else {
+        (dir.join(name).join("mod.rs"), dir.join(name))
+    }
+}
+
+fn parse(file: &Path) -> syn::File {
+    let text =
+        fs::read_to_string(file).unwrap_or_else(|e| panic!("cannot read {}: {e}", file.display()));
+    syn::parse_file(&text).unwrap_or_else(|e| panic!("cannot parse {}: {e}", file.display()))
+}
+
+fn public_api() -> BTreeSet<String> {
+    let src = Path::new(env!("CARGO_MANIFEST_DIR")).join("src");
+    let lib = parse(&src.join("lib.rs"));
+    let mut api = Api::default();
+    api.module(CRATE, &src, &lib.items, "");
+    api.lines
+}
+
+/// Whether `a` and `b` are semver-compatible versions
+fn compatible(a: &str, b: &str) -> bool {
+    let parts = |v: &str| -> Vec<u64> { v.split('.').map(|p| p.parse().unwrap_or(0)).collect() };
+    let (a, b) = (parts(a), parts(b));
+    match (a.first(), b.first()) {
+        (Some(0), Some(0)) => a.get(1) == b.get(1),
+        (a, b) => a == b,
+    }
+}
+
+#[test]
+fn public_api_matches_snapshot() {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR")).join(SNAPSHOT);
+    let version = env!("CARGO_PKG_VERSION");
+    let current = public_api();
+
+    let text = fs::read_to_string(&path).unwrap_or_default();
+    let snapshot_version = text
+        .lines()
+        .next()
+        .and_then(|header| header.strip_prefix("# accumulate-sdk "))
+        .and_then(|header| header.split_whitespace().next())
+        .unwrap_or(version)
+        .to_string();
+    let recorded: BTreeSet<String> = text
+        .lines()
+        .filter(|line| !line.starts_with('#') && !line.is_empty())
+        .map(str::to_string)
+        .collect();
+
+    let removed: Vec<_> = recorded.difference(&current).collect();
+    let added: Vec<_> = current.difference(&recorded).collect();
+    if removed.is_empty() && added.is_empty() {
+        return;
+    }
+
+    let mode = std::env::var("ACCUMULATE_API_SNAPSHOT").unwrap_or_default();
+    let bump = !compatible(&snapshot_version, version);
+    let may_bless = match mode.as_str() {
+        "bless" => removed.is_empty() || bump,
+        "bless-breaking" => true,
+        _ => false,
+    };
+    if may_bless {
+        let mut out = format!("# accumulate-sdk {version} public API\n");
+        for line in &current {
+            out.push_str(line);
+            out.push('\n');
+        }
+        fs::write(&path, out).unwrap_or_else(|e| panic!("cannot write {SNAPSHOT}: {e}"));
+        return;
+    }
+
+    let mut report = String::new();
+    if !removed.is_empty() {
+        let _ = writeln!(
+            report,
+            "{} breaking change(s) to the public API since {snapshot_version} (now {version}):",
+            removed.len()
+        );
+        for line in &removed {
+            let _ = writeln!(report, "  - {line}");
+        }
+    }
+    if !added.is_empty() {
+        let _ = writeln!(report, "{} addition(s):", added.len());
+        for line in &added {
+            let _ = writeln!(report, "  + {line}");
+        }
+    }
+    let hint = if removed.is_empty() || bump {
+        "Bless with ACCUMULATE_API_SNAPSHOT=bless"
+    } else {
+        "Bump the major version (minor before 1.0) before blessing, or use ACCUMULATE_API_SNAPSHOT=bless-breaking"
+    };
+    panic!("{report}{hint} cargo test --features api-snapshot --test public_api");
+}
+
+#[test]
+fn version_compatibility() {
+    assert!(compatible("2.2.0", "2.9.1"));
+    assert!(!compatible("2.2.0", "3.0.0"));
+    assert!(compatible("0.4.1", "0.4.7"));
+    assert!(!compatible("0.4.1", "0.5.0"));
+}
@@ -1,4 +1,6 @@
-use accumulate_client::{canonicalize, dumps_canonical, canonical_json};
+use accumulate_client::{
+    canonicalize, canonicalize_jcs, dumps_canonical, canonical_json, escape_json_string, escape_json_utf16, JcsError,
+};
 use serde_json::{json, Value};
 use std::fs;
 use std::path::Path;
@@ -182,4 +184,75 @@ fn test_canonical_json_parity_with_fixtures_using_dumps_canonical() {
             actual_dumps_canonical
         );
     }
-}
\ No newline at end of file
+}
+
+#[test]
+fn test_jcs_rfc8785_vectors() {
+    let fixture_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden/canonical/jcs_rfc8785_vectors.json");
+    let fixtures: Value = serde_json::from_str(
+        &fs::read_to_string(fixture_path).expect("Failed to read JCS vectors"),
+    )
+    .expect("Failed to parse JCS vectors");
+
+    for test_case in fixtures["testCases"].as_array().unwrap() {
+        let name = test_case["name"].as_str().unwrap();
+        let input: Value = serde_json::from_str(test_case["input"].as_str().unwrap()).unwrap();
+        let expected = test_case["expectedCanonical"].as_str().unwrap();
+
+        let actual = canonicalize_jcs(&input).unwrap();
+        assert_eq!(actual, expected, "JCS mismatch for test case '{}'", name);
+    }
+}
+
+#[test]
+fn test_jcs_rejects_unsafe_integers() {
+    let input = json!({"amount": 9007199254740993u64});
+    assert!(matches!(canonicalize_jcs(&input), Err(JcsError::UnsafeInteger(_))));
+}
+
+#[test]
+fn test_string_escaping_parity_with_fixtures() {
+    let fixture_path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/golden/canonical/string_escaping_vectors.json");
+    let fixtures: Value = serde_json::from_str(
+        &fs::read_to_string(fixture_path).expect("Failed to read string escaping vectors"),
+    )
+    .expect("Failed to parse string escaping vectors");
+
+    for test_case in fixtures["testCases"].as_array().unwrap() {
+        let name = test_case["name"].as_str().unwrap();
+        let memo = test_case["memo"].as_str().unwrap();
+        let body = json!({"type": "writeData", "memo": memo});
+
+        assert_eq!(
+            escape_json_string(memo),
+            test_case["expectedEscaped"].as_str().unwrap(),
+            "escaped string mismatch for '{}'",
+            name
+        );
+        for canonical in [canonicalize(&body), canonicalize_jcs(&body).unwrap()] {
+            assert_eq!(
+                canonical,
+                test_case["expectedCanonical"].as_str().unwrap(),
+                "canonical JSON mismatch for '{}'",
+                name
+            );
+        }
+    }
+
+    for test_case in fixtures["loneSurrogates"].as_array().unwrap() {
+        let units: Vec<u16> = test_case["utf16"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|u| u16::try_from(u.as_u64().unwrap()).unwrap())
+            .collect();
+        assert_eq!(
+            escape_json_utf16(&units),
+            test_case["expectedEscaped"].as_str().unwrap(),
+            "lone surrogate escaping mismatch for '{}'",
+            test_case["name"].as_str().unwrap()
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Parity tests for the Go binary codec primitives
+//!
+//! Sweeps every varint length boundary, every field number and every value
+//! shape the Go `encoding.Writer` produces, checking byte output against a
+//! direct port of Go's `binary.PutUvarint` and round-tripping through the reader.
+
+use accumulate_client::codec::{BinaryReader, BinaryWriter, DecodingError, ValueKind, EMPTY_OBJECT};
+use num_bigint::{BigInt, BigUint};
+use std::time::Duration;
+
+/// Port of Go's binary.PutUvarint
+fn go_put_uvarint(mut x: u64) -> Vec<u8> {
+    let mut buf = Vec::new();
+    while x >= 0x80 {
+        buf.push((x as u8) | 0x80);
+        x >>= 7;
+    }
+    buf.push(x as u8);
+    buf
+}
+
+/// Port of Go's binary.PutVarint
+fn go_put_varint(x: i64) -> Vec<u8> {
+    let mut ux = (x as u64) << 1;
+    if x < 0 {
+        ux = !ux;
+    }
+    go_put_uvarint(ux)
+}
+
+/// Values on both sides of every power of two
+fn boundary_values() -> Vec<u64> {
+    let mut values = vec![0, u64::MAX];
+    for shift in 0..64 {
+        let p = 1u64 << shift;
+        values.extend([p - 1, p, p + 1]);
+    }
+    values
+}
+
+#[test]
+fn test_uvarint_matches_go_at_every_boundary() {
+    for value in boundary_values() {
+        let expected = go_put_uvarint(value);
+        assert_eq!(BinaryWriter::encode_uvarint(value), expected, "uvarint({})", value);
+        assert_eq!(BinaryReader::new(&expected).read_uvarint().unwrap(), value);
+    }
+}
+
+#[test]
+fn test_varint_matches_go_at_every_boundary() {
+    for value in boundary_values() {
+        for signed in [value as i64, (value as i64).wrapping_neg()] {
+            let expected = go_put_varint(signed);
+            assert_eq!(BinaryWriter::encode_varint(signed), expected, "varint({})", signed);
+            assert_eq!(BinaryReader::new(&expected).read_varint().unwrap(), signed);
+        }
+    }
+}
+
+#[test]
+fn test_every_field_number_frames_like_go() {
+    for field in 1..=32u32 {
+        let mut writer = BinaryWriter::new();
+        writer.write_varint_field(-5, field).unwrap();
+        let mut expected = go_put_uvarint(u64::from(field));
+        expected.extend(go_put_varint(-5));
+        assert_eq!(writer.bytes(), expected.as_slice(), "field {}", field);
+
+        let mut reader = BinaryReader::new(writer.bytes());
+        assert!(reader.read_field(field).unwrap());
+        assert_eq!(reader.read_varint().unwrap(), -5);
+    }
+
+    let mut writer = BinaryWriter::new();
+    assert!(writer.write_varint_field(1, 0).is_err());
+    assert!(writer.write_varint_field(1, 33).is_err());
+}
+
+#[test]
+fn test_time_matches_go() {
+    for seconds in [0i64, 1, -1, 1_704_067_200, 253_402_300_799, -62_135_596_800] {
+        let time = chrono::DateTime::from_timestamp(seconds, 0).unwrap();
+        let mut writer = BinaryWriter::new();
+        writer.write_time_field(&time, 3).unwrap();
+
+        let mut expected = vec![3];
+        expected.extend(go_put_varint(seconds));
+        assert_eq!(writer.bytes(), expected.as_slice(), "time {}", seconds);
+
+        let mut reader = BinaryReader::new(writer.bytes());
+        assert!(reader.read_field(3).unwrap());
+        assert_eq!(reader.read_time().unwrap(), time);
+    }
+
+    // Sub-second precision is dropped, as in Go
+    let time = chrono::DateTime::from_timestamp(10, 999_000_000).unwrap();
+    let mut writer = BinaryWriter::new();
+    writer.write_time(&time).unwrap();
+    assert_eq!(writer.bytes(), go_put_varint(10).as_slice());
+}
+
+#[test]
+fn test_duration_matches_go() {
+    let cases = [
+        Duration::ZERO,
+        Duration::from_nanos(1),
+        Duration::from_millis(1500),
+        Duration::from_secs(3600),
+        Duration::new(86_400, 999_999_999),
+    ];
+    for duration in cases {
+        let mut writer = BinaryWriter::new();
+        writer.write_duration_field(duration, 9).unwrap();
+
+        let mut expected = vec![9];
+        expected.extend(go_put_uvarint(duration.as_secs()));
+        expected.extend(go_put_uvarint(u64::from(duration.subsec_nanos())));
+        assert_eq!(writer.bytes(), expected.as_slice(), "duration {:?}", duration);
+
+        let mut reader = BinaryReader::new(writer.bytes());
+        assert!(reader.read_field(9).unwrap());
+        assert_eq!(reader.read_duration().unwrap(), duration);
+    }
+}
+
+#[test]
+fn test_big_int_matches_go() {
+    // Go writes big.Int.Bytes(): minimal big-endian magnitude, length-prefixed
+    for value in [1u64, 255, 256, 1_000_000, u64::MAX] {
+        let big = BigInt::from(value);
+        let mut writer = BinaryWriter::new();
+        writer.write_big_int_field(&big, 2).unwrap();
+
+        let magnitude = BigUint::from(value).to_bytes_be();
+        let mut expected = vec![2];
+        expected.extend(go_put_uvarint(magnitude.len() as u64));
+        expected.extend(&magnitude);
+        assert_eq!(writer.bytes(), expected.as_slice(), "big int {}", value);
+
+        let mut reader = BinaryReader::new(writer.bytes());
+        assert!(reader.read_field(2).unwrap());
+        assert_eq!(reader.read_big_number().unwrap(), BigUint::from(value));
+    }
+}
+
+#[test]
+fn test_nested_values_frame_like_go() {
+    let mut writer = BinaryWriter::new();
+    writer
+        .write_value_field(1, |w| {
+            w.write_string_field("acc://alice.acme", 1)?;
+            w.write_value_field(2, |inner| inner.write_uvarint_field(300, 1))
+        })
+        .unwrap();
+    writer.write_value_field(2, |_| Ok(())).unwrap();
+
+    let mut inner = vec![1];
+    inner.extend(go_put_uvarint(300));
+    let mut nested = vec![1, 16];
+    nested.extend(b"acc://alice.acme");
+    nested.push(2);
+    nested.push(inner.len() as u8);
+    nested.extend(&inner);
+    let mut expected = vec![1, nested.len() as u8];
+    expected.extend(&nested);
+    expected.extend([2, 1, EMPTY_OBJECT]);
+    assert_eq!(writer.bytes(), expected.as_slice());
+
+    let mut reader = BinaryReader::new(writer.bytes());
+    assert!(reader.read_field(1).unwrap());
+    let mut outer = reader.read_value().unwrap();
+    assert!(outer.read_field(1).unwrap());
+    assert_eq!(outer.read_string().unwrap(), "acc://alice.acme");
+    assert!(outer.read_field(2).unwrap());
+    let mut innermost = outer.read_value().unwrap();
+    assert!(innermost.read_field(1).unwrap());
+    assert_eq!(innermost.read_uvarint().unwrap(), 300);
+    assert!(reader.read_field(2).unwrap());
+    assert!(!reader.read_value().unwrap().has_remaining());
+}
+
+#[test]
+fn test_skip_every_value_kind() {
+    let mut writer = BinaryWriter::new();
+    writer.write_uvarint_field(u64::MAX, 1).unwrap();
+    writer.write_varint_field(i64::MIN, 2).unwrap();
+    writer.write_bool_field(true, 3).unwrap();
+    writer.write_bytes_field(&[0xab; 200], 4).unwrap();
+    writer.write_hash_field(&[1u8; 32], 5).unwrap();
+    writer.write_duration_field(Duration::new(7, 8), 6).unwrap();
+    writer.write_string_field("tail", 7).unwrap();
+
+    let mut reader = BinaryReader::new(writer.bytes());
+    let kinds = [
+        ValueKind::Uvarint,
+        ValueKind::Varint,
+        ValueKind::Bool,
+        ValueKind::Bytes,
+        ValueKind::Hash,
+        ValueKind::Duration,
+    ];
+    for (i, kind) in kinds.into_iter().enumerate() {
+        assert_eq!(reader.skip_field(kind).unwrap() as usize, i + 1);
+    }
+    assert!(reader.read_field(7).unwrap());
+    assert_eq!(reader.read_string().unwrap(), "tail");
+    assert!(!reader.has_remaining());
+
+    // Truncated values are reported rather than skipped past the end
+    let mut reader = BinaryReader::new(&[5, 1, 2, 3]);
+    assert!(matches!(reader.skip_field(ValueKind::Hash), Err(DecodingError::UnexpectedEof)));
+}
@@ -9,5 +9,6 @@ pub mod canonical_json_test;
 pub mod codec_integration_test;
 pub mod envelope_encoding_parity;
 pub mod envelope_encoding_test;
+pub mod go_primitives_parity;
 pub mod hash_vectors_parity;
 pub mod hash_vectors_test;
\ No newline at end of file
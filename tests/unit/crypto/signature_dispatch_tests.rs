@@ -453,4 +453,29 @@ fn test_signature_count_validation() {
 
     let signatures = manifest["signatures"].as_array().unwrap();
     assert_eq!(signatures.len(), 16, "Manifest should contain 16 signatures");
-}
\ No newline at end of file
+}
+#[test]
+fn test_feature_gated_verifiers() {
+    // Invalid keys fail verification when the verifier is compiled in,
+    // and are reported as unsupported when it is not
+    use accumulate_client::errors::{Error, SignatureError};
+
+    let message = b"test message for verification";
+    let btc: Signature = serde_json::from_value(minimal_signature_json("btc")).unwrap();
+    let rsa: Signature = serde_json::from_value(minimal_signature_json("rsaSha256")).unwrap();
+    let (Signature::BTC(btc), Signature::RsaSha256(rsa)) = (btc, rsa) else {
+        panic!("unexpected signature variants");
+    };
+
+    let btc_result = btc.verify(message);
+    #[cfg(feature = "secp256k1")]
+    assert!(matches!(btc_result, Ok(false)));
+    #[cfg(not(feature = "secp256k1"))]
+    assert!(matches!(btc_result, Err(Error::Signing(SignatureError::UnsupportedType(_)))));
+
+    let rsa_result = rsa.verify(message);
+    #[cfg(feature = "rsa")]
+    assert!(matches!(rsa_result, Ok(false)));
+    #[cfg(not(feature = "rsa"))]
+    assert!(matches!(rsa_result, Err(Error::Signing(SignatureError::UnsupportedType(_)))));
+}
@@ -125,9 +125,9 @@ fn test_create_token_envelope_shape() {
     assert_eq!(body_obj["type"], "createToken", "Should have correct wire type");
 
     // Verify token-specific fields
-    assert!(body_obj.get("Url").is_some());
-    assert!(body_obj.get("Symbol").is_some());
-    assert!(body_obj.get("Precision").is_some());
+    assert!(body_obj.get("url").is_some());
+    assert!(body_obj.get("symbol").is_some());
+    assert!(body_obj.get("precision").is_some());
 
     // Write golden vector
     ensure_golden_tx_dir();
@@ -149,12 +149,12 @@ fn test_send_tokens_envelope_shape() {
         meta: None,
         to: vec![
             json::json!({
-                "Url": "acc://recipient1.acme",
-                "Amount": "100"
+                "url": "acc://recipient1.acme",
+                "amount": "100"
             }),
             json::json!({
-                "Url": "acc://recipient2.acme",
-                "Amount": "200"
+                "url": "acc://recipient2.acme",
+                "amount": "200"
             }),
         ],
     };
@@ -180,15 +180,15 @@ fn test_send_tokens_envelope_shape() {
     assert_eq!(body_obj["type"], "sendTokens", "Should have correct wire type");
 
     // Verify recipients array
-    assert!(body_obj.get("To").is_some());
-    let recipients = body_obj["To"].as_array().unwrap();
+    assert!(body_obj.get("to").is_some());
+    let recipients = body_obj["to"].as_array().unwrap();
     assert_eq!(recipients.len(), 2);
 
     // Verify recipient structure
-    assert_eq!(recipients[0]["Url"], "acc://recipient1.acme");
-    assert_eq!(recipients[0]["Amount"], "100");
-    assert_eq!(recipients[1]["Url"], "acc://recipient2.acme");
-    assert_eq!(recipients[1]["Amount"], "200");
+    assert_eq!(recipients[0]["url"], "acc://recipient1.acme");
+    assert_eq!(recipients[0]["amount"], "100");
+    assert_eq!(recipients[1]["url"], "acc://recipient2.acme");
+    assert_eq!(recipients[1]["amount"], "200");
 
     // Write golden vector
     ensure_golden_tx_dir();
@@ -343,9 +343,10 @@ fn test_canonical_json_properties() {
     assert!(json_str.contains("\"principal\""));
     assert!(json_str.contains("\"initiator\""));
 
-    // Verify body fields use PascalCase
+    // Verify body fields use camelCase as well
     assert!(json_str.contains("\"type\""));
-    assert!(json_str.contains("\"Entry\""));
+    assert!(json_str.contains("\"entry\""));
+    assert!(json_str.contains("\"writeToState\""));
 
     println!("✓ Canonical JSON properties test passed");
 }
\ No newline at end of file
@@ -371,7 +371,7 @@ fn test_error_type_validation() {
     let error_str = format!("{:?}", general_error);
     assert!(error_str.contains("test error"), "Error should contain message");
 
-    let network_error = Error::Network("network failed".to_string());
+    let network_error = Error::Transport("network failed".to_string());
     let network_str = format!("{:?}", network_error);
     assert!(network_str.contains("network failed"), "Network error should contain message");
 
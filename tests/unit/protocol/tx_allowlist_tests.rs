@@ -62,7 +62,9 @@ fn minimal_body_json(body_info: &Value) -> Value {
                 }
             };
 
-            json_obj[field_name] = value;
+            // The manifest lists the YAML names; the wire is camelCase
+            let wire_name = format!("{}{}", field_name[..1].to_lowercase(), &field_name[1..]);
+            json_obj[wire_name.as_str()] = value;
         }
     }
 
@@ -74,110 +76,110 @@ fn minimal_body_json_for_wire(wire_tag: &str) -> Value {
     match wire_tag {
         "writeData" => json::json!({
             "type": "writeData",
-            "Entry": {"type": "test"}
+            "entry": {"type": "test"}
         }),
         "createIdentity" => json::json!({
             "type": "createIdentity",
-            "Url": "acc://test.acme"
+            "url": "acc://test.acme"
         }),
         "sendTokens" => json::json!({
             "type": "sendTokens",
-            "To": [{"url": "acc://recipient.acme/tokens", "amount": "100"}]
+            "to": [{"url": "acc://recipient.acme/tokens", "amount": "100"}]
         }),
         "createToken" => json::json!({
             "type": "createToken",
-            "Url": "acc://test.acme/token",
-            "Symbol": "TEST",
-            "Precision": 8
+            "url": "acc://test.acme/token",
+            "symbol": "TEST",
+            "precision": 8
         }),
         "issueTokens" => json::json!({
             "type": "issueTokens",
-            "Recipient": "acc://test.acme/tokens",
-            "Amount": "100",
-            "To": []
+            "recipient": "acc://test.acme/tokens",
+            "amount": "100",
+            "to": []
         }),
         "acmeFaucet" => json::json!({
             "type": "acmeFaucet",
-            "Url": "acc://test.acme/tokens"
+            "url": "acc://test.acme/tokens"
         }),
         "activateProtocolVersion" => json::json!({
             "type": "activateProtocolVersion"
         }),
         "addCredits" => json::json!({
             "type": "addCredits",
-            "Recipient": "acc://test.acme/page",
-            "Amount": "100",
-            "Oracle": 500
+            "recipient": "acc://test.acme/page",
+            "amount": "100",
+            "oracle": 500
         }),
         "createDataAccount" => json::json!({
             "type": "createDataAccount",
-            "Url": "acc://test.acme/data"
+            "url": "acc://test.acme/data"
         }),
         "createKeyBook" => json::json!({
             "type": "createKeyBook",
-            "Url": "acc://test.acme/book",
-            "PublicKeyHash": "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+            "url": "acc://test.acme/book",
+            "publicKeyHash": "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
         }),
         "createKeyPage" => json::json!({
             "type": "createKeyPage",
-            "Keys": [{"publicKeyHash": "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"}]
+            "keys": [{"publicKeyHash": "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"}]
         }),
         "createTokenAccount" => json::json!({
             "type": "createTokenAccount",
-            "Url": "acc://test.acme/tokens",
-            "TokenUrl": "acc://acme"
+            "url": "acc://test.acme/tokens",
+            "tokenUrl": "acc://acme"
         }),
         "burnTokens" => json::json!({
             "type": "burnTokens",
-            "Amount": "100"
+            "amount": "100"
         }),
         "updateKey" => json::json!({
             "type": "updateKey",
-            "NewKeyHash": "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
+            "newKeyHash": "0123456789abcdef0123456789abcdef0123456789abcdef0123456789abcdef"
         }),
         "updateKeyPage" => json::json!({
             "type": "updateKeyPage",
-            "Operation": [{"type": "add"}]
+            "operation": [{"type": "add"}]
         }),
         "blockValidatorAnchor" => json::json!({
             "type": "blockValidatorAnchor",
-            "AcmeBurnt": "0"
+            "acmeBurnt": "0"
         }),
         "burnCredits" => json::json!({
             "type": "burnCredits",
-            "Amount": 100
+            "amount": 100
         }),
         "directoryAnchor" => json::json!({
             "type": "directoryAnchor",
-            "Updates": [],
-            "Receipts": [],
-            "MakeMajorBlock": 0,
-            "MakeMajorBlockTime": 0
+            "updates": [],
+            "receipts": [],
+            "makeMajorBlock": 0,
+            "makeMajorBlockTime": 0
         }),
         "lockAccount" => json::json!({
             "type": "lockAccount",
-            "Height": 1
+            "height": 1
         }),
         "transferCredits" => json::json!({
             "type": "transferCredits",
-            "To": [{"url": "acc://recipient.acme/page", "amount": 100}]
+            "to": [{"url": "acc://recipient.acme/page", "amount": 100}]
         }),
         "updateAccountAuth" => json::json!({
             "type": "updateAccountAuth",
-            "Operations": [{"type": "enable"}]
+            "operations": [{"type": "enable"}]
         }),
         "writeDataTo" => json::json!({
             "type": "writeDataTo",
-            "Recipient": "acc://test.acme/data",
-            "Entry": {"type": "test"}
+            "recipient": "acc://test.acme/data",
+            "entry": {"type": "test"}
         }),
         "systemWriteData" => json::json!({
             "type": "systemWriteData",
-            "Entry": {"type": "test"}
+            "entry": {"type": "test"}
         }),
         "networkMaintenance" => json::json!({
             "type": "networkMaintenance",
-            "Operations": []
+            "operations": []
         }),
         "createLiteTokenAccount" => json::json!({
             "type": "createLiteTokenAccount"
@@ -272,9 +274,9 @@ fn test_specific_transaction_types() {
     // WriteData transaction
     let write_data = json::json!({
         "type": "writeData",
-        "Entry": {"type": "test"},
-        "Scratch": false,
-        "WriteToState": true
+        "entry": {"type": "test"},
+        "scratch": false,
+        "writeToState": true
     });
 
     let parsed: TransactionBody = serde_json::from_value(write_data).unwrap();
@@ -284,7 +286,7 @@ fn test_specific_transaction_types() {
     // SendTokens transaction (must have at least one recipient)
     let send_tokens = json::json!({
         "type": "sendTokens",
-        "To": [{"url": "acc://recipient.acme/tokens", "amount": "100"}]
+        "to": [{"url": "acc://recipient.acme/tokens", "amount": "100"}]
     });
 
     let parsed: TransactionBody = serde_json::from_value(send_tokens).unwrap();
@@ -294,7 +296,7 @@ fn test_specific_transaction_types() {
     // CreateIdentity transaction
     let create_identity = json::json!({
         "type": "createIdentity",
-        "Url": "acc://test.acme"
+        "url": "acc://test.acme"
     });
 
     let parsed: TransactionBody = serde_json::from_value(create_identity).unwrap();
@@ -302,6 +304,42 @@ fn test_specific_transaction_types() {
     assert!(result.is_ok(), "CreateIdentity validation failed: {:?}", result.err());
 }
 
+#[test]
+fn test_pascal_case_fields_still_deserialize() {
+    // Payloads built against the older PascalCase field names decode through the aliases
+    let legacy = json::json!({
+        "type": "addCredits",
+        "Recipient": "acc://test.acme/page",
+        "Amount": "100",
+        "Oracle": 500
+    });
+    let current = json::json!({
+        "type": "addCredits",
+        "recipient": "acc://test.acme/page",
+        "amount": "100",
+        "oracle": 500
+    });
+
+    let parsed: TransactionBody = serde_json::from_value(legacy).unwrap();
+    assert_eq!(parsed, serde_json::from_value::<TransactionBody>(current.clone()).unwrap());
+    // and serialize back with the camelCase wire names
+    assert_eq!(normalize_json(&serde_json::to_value(&parsed).unwrap()), normalize_json(&current));
+
+    let legacy = json::json!({
+        "type": "createIdentity",
+        "Url": "acc://test.acme",
+        "KeyBookUrl": "acc://test.acme/book"
+    });
+    let parsed: TransactionBody = serde_json::from_value(legacy).unwrap();
+    match parsed {
+        TransactionBody::CreateIdentity(body) => {
+            assert_eq!(body.url, "acc://test.acme");
+            assert_eq!(body.key_book_url.as_deref(), Some("acc://test.acme/book"));
+        }
+        other => panic!("unexpected body {:?}", other),
+    }
+}
+
 #[test]
 fn test_transaction_count() {
     let m = manifest();
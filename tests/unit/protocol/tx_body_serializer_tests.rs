@@ -46,51 +46,53 @@ fn minimal_body_json(body_name: &str, fields: &[Value]) -> Value {
                 "rawJson" => json::json!({"type": "test"}),
                 // Complex types - provide minimal valid structures as JSON
                 "TokenRecipient" => json::json!({
-                    "Url": "acc://recipient.acme",
-                    "Amount": "100"
+                    "url": "acc://recipient.acme",
+                    "amount": "100"
                 }),
                 "CreditRecipient" => json::json!({
-                    "Url": "acc://recipient.acme",
-                    "Amount": 100
+                    "url": "acc://recipient.acme",
+                    "amount": 100
                 }),
                 "KeySpecParams" => json::json!({
-                    "KeyHash": "deadbeef",
-                    "Delegate": "acc://delegate.acme"
+                    "keyHash": "deadbeef",
+                    "delegate": "acc://delegate.acme"
                 }),
                 "DataEntry" => json::json!({
-                    "Data": "test data"
+                    "data": "test data"
                 }),
                 "ExecutorVersion" => json::json!({
-                    "Version": "1.0.0"
+                    "version": "1.0.0"
                 }),
                 "NetworkAccountUpdate" => json::json!({
-                    "Name": "test-partition",
-                    "Body": {}
+                    "name": "test-partition",
+                    "body": {}
                 }),
                 "PartitionAnchorReceipt" => json::json!({
-                    "Partition": "test-partition",
-                    "Receipt": "deadbeef"
+                    "partition": "test-partition",
+                    "receipt": "deadbeef"
                 }),
                 "NetworkMaintenanceOperation" => json::json!({
-                    "Type": "enable",
-                    "Partition": "test-partition"
+                    "type": "enable",
+                    "partition": "test-partition"
                 }),
                 "AccountAuthOperation" => json::json!({
-                    "Type": "enable",
-                    "Authority": "acc://authority.acme"
+                    "type": "enable",
+                    "authority": "acc://authority.acme"
                 }),
                 "KeyPageOperation" => json::json!({
-                    "Type": "add",
-                    "Key": "deadbeef"
+                    "type": "add",
+                    "key": "deadbeef"
                 }),
                 "TokenIssuerProof" => json::json!({
-                    "Transaction": "deadbeef",
-                    "Receipt": "feedbeef"
+                    "transaction": "deadbeef",
+                    "receipt": "feedbeef"
                 }),
                 _ => json::json!({}), // Fallback for unknown types
             };
 
-            json_obj[field_name] = value;
+            // The manifest lists the YAML names; the wire is camelCase
+            let wire_name = format!("{}{}", field_name[..1].to_lowercase(), &field_name[1..]);
+            json_obj[wire_name.as_str()] = value;
         }
     }
 
@@ -124,7 +126,7 @@ fn test_acme_faucet_body_roundtrip() {
     let back = back_result.unwrap();
 
     // Check that required fields are preserved
-    assert_eq!(val["Url"], back["Url"], "Url mismatch");
+    assert_eq!(val["url"], back["url"], "url mismatch");
 
     // Test validation
     let validate_result = body.validate();
@@ -153,9 +155,9 @@ fn test_add_credits_body_roundtrip() {
     let body = body_result.unwrap();
     let back = serde_json::to_value(&body).unwrap();
 
-    assert_eq!(val["Recipient"], back["Recipient"], "Recipient mismatch");
-    assert_eq!(val["Amount"], back["Amount"], "Amount mismatch");
-    assert_eq!(val["Oracle"], back["Oracle"], "Oracle mismatch");
+    assert_eq!(val["recipient"], back["recipient"], "recipient mismatch");
+    assert_eq!(val["amount"], back["amount"], "amount mismatch");
+    assert_eq!(val["oracle"], back["oracle"], "oracle mismatch");
 
     let validate_result = body.validate();
     assert!(validate_result.is_ok(), "AddCreditsBody validation failed: {:?}", validate_result.err());
@@ -176,14 +178,14 @@ fn test_send_tokens_body_roundtrip() {
     let mut val = minimal_body_json("SendTokensBody", fields);
 
     // SendTokensBody requires repeatable TokenRecipient fields as JSON Values
-    val["To"] = json::json!([
+    val["to"] = json::json!([
         {
-            "Url": "acc://recipient1.acme",
-            "Amount": "100"
+            "url": "acc://recipient1.acme",
+            "amount": "100"
         },
         {
-            "Url": "acc://recipient2.acme",
-            "Amount": "200"
+            "url": "acc://recipient2.acme",
+            "amount": "200"
         }
     ]);
 
@@ -195,7 +197,7 @@ fn test_send_tokens_body_roundtrip() {
     let body = body_result.unwrap();
     let back = serde_json::to_value(&body).unwrap();
 
-    assert_eq!(val["To"], back["To"], "To recipients mismatch");
+    assert_eq!(val["to"], back["to"], "to recipients mismatch");
 
     let validate_result = body.validate();
     assert!(validate_result.is_ok(), "SendTokensBody validation failed: {:?}", validate_result.err());
@@ -216,7 +218,7 @@ fn test_create_identity_body_roundtrip() {
     let mut val = minimal_body_json("CreateIdentityBody", fields);
 
     // Add optional repeatable authorities
-    val["Authorities"] = json::json!(["acc://authority1.acme", "acc://authority2.acme"]);
+    val["authorities"] = json::json!(["acc://authority1.acme", "acc://authority2.acme"]);
 
     println!("Testing CreateIdentityBody with JSON: {}", serde_json::to_string_pretty(&val).unwrap());
 
@@ -226,8 +228,8 @@ fn test_create_identity_body_roundtrip() {
     let body = body_result.unwrap();
     let back = serde_json::to_value(&body).unwrap();
 
-    assert_eq!(val["Url"], back["Url"], "Url mismatch");
-    assert_eq!(val["Authorities"], back["Authorities"], "Authorities mismatch");
+    assert_eq!(val["url"], back["url"], "url mismatch");
+    assert_eq!(val["authorities"], back["authorities"], "authorities mismatch");
 
     let validate_result = body.validate();
     assert!(validate_result.is_ok(), "CreateIdentityBody validation failed: {:?}", validate_result.err());
@@ -248,9 +250,9 @@ fn test_create_token_body_roundtrip() {
     let mut val = minimal_body_json("CreateTokenBody", fields);
 
     // Add optional fields
-    val["Properties"] = json::json!("acc://token-properties.acme");
-    val["SupplyLimit"] = json::json!("1000000");
-    val["Authorities"] = json::json!(["acc://authority1.acme"]);
+    val["properties"] = json::json!("acc://token-properties.acme");
+    val["supplyLimit"] = json::json!("1000000");
+    val["authorities"] = json::json!(["acc://authority1.acme"]);
 
     println!("Testing CreateTokenBody with JSON: {}", serde_json::to_string_pretty(&val).unwrap());
 
@@ -260,12 +262,12 @@ fn test_create_token_body_roundtrip() {
     let body = body_result.unwrap();
     let back = serde_json::to_value(&body).unwrap();
 
-    assert_eq!(val["Url"], back["Url"], "Url mismatch");
-    assert_eq!(val["Symbol"], back["Symbol"], "Symbol mismatch");
-    assert_eq!(val["Precision"], back["Precision"], "Precision mismatch");
-    assert_eq!(val["Properties"], back["Properties"], "Properties mismatch");
-    assert_eq!(val["SupplyLimit"], back["SupplyLimit"], "SupplyLimit mismatch");
-    assert_eq!(val["Authorities"], back["Authorities"], "Authorities mismatch");
+    assert_eq!(val["url"], back["url"], "url mismatch");
+    assert_eq!(val["symbol"], back["symbol"], "symbol mismatch");
+    assert_eq!(val["precision"], back["precision"], "precision mismatch");
+    assert_eq!(val["properties"], back["properties"], "properties mismatch");
+    assert_eq!(val["supplyLimit"], back["supplyLimit"], "supplyLimit mismatch");
+    assert_eq!(val["authorities"], back["authorities"], "authorities mismatch");
 
     let validate_result = body.validate();
     assert!(validate_result.is_ok(), "CreateTokenBody validation failed: {:?}", validate_result.err());
@@ -286,8 +288,8 @@ fn test_write_data_body_roundtrip() {
     let mut val = minimal_body_json("WriteDataBody", fields);
 
     // Add optional fields
-    val["Scratch"] = json::json!(true);
-    val["WriteToState"] = json::json!(false);
+    val["scratch"] = json::json!(true);
+    val["writeToState"] = json::json!(false);
 
     println!("Testing WriteDataBody with JSON: {}", serde_json::to_string_pretty(&val).unwrap());
 
@@ -297,9 +299,9 @@ fn test_write_data_body_roundtrip() {
     let body = body_result.unwrap();
     let back = serde_json::to_value(&body).unwrap();
 
-    assert_eq!(val["Entry"], back["Entry"], "Entry mismatch");
-    assert_eq!(val["Scratch"], back["Scratch"], "Scratch mismatch");
-    assert_eq!(val["WriteToState"], back["WriteToState"], "WriteToState mismatch");
+    assert_eq!(val["entry"], back["entry"], "entry mismatch");
+    assert_eq!(val["scratch"], back["scratch"], "scratch mismatch");
+    assert_eq!(val["writeToState"], back["writeToState"], "writeToState mismatch");
 
     let validate_result = body.validate();
     assert!(validate_result.is_ok(), "WriteDataBody validation failed: {:?}", validate_result.err());
@@ -315,8 +317,8 @@ fn test_transaction_body_enum_roundtrip() {
         meta: None,
         to: vec![
             json::json!({
-                "Url": "acc://recipient.acme",
-                "Amount": "100"
+                "url": "acc://recipient.acme",
+                "amount": "100"
             })
         ],
     };
@@ -340,8 +342,8 @@ fn test_transaction_body_enum_roundtrip() {
         TransactionBody::SendTokens(body) => {
             assert_eq!(body.to.len(), 1);
             // Since to is Vec<serde_json::Value>, we check the JSON structure
-            assert_eq!(body.to[0]["Url"], "acc://recipient.acme");
-            assert_eq!(body.to[0]["Amount"], "100");
+            assert_eq!(body.to[0]["url"], "acc://recipient.acme");
+            assert_eq!(body.to[0]["amount"], "100");
         },
         _ => panic!("Wrong variant deserialized"),
     }
@@ -444,11 +446,11 @@ fn test_complex_nested_types() {
     let create_key_page = CreateKeyPageBody {
         keys: vec![
             json::json!({
-                "KeyHash": "deadbeef",
-                "Delegate": "acc://delegate.acme"
+                "keyHash": "deadbeef",
+                "delegate": "acc://delegate.acme"
             }),
             json::json!({
-                "KeyHash": "feedface"
+                "keyHash": "feedface"
             })
         ],
     };
@@ -458,9 +460,9 @@ fn test_complex_nested_types() {
     let deserialized: CreateKeyPageBody = serde_json::from_value(serialized).unwrap();
 
     assert_eq!(deserialized.keys.len(), 2);
-    assert_eq!(deserialized.keys[0]["KeyHash"], "deadbeef");
-    assert_eq!(deserialized.keys[0]["Delegate"], "acc://delegate.acme");
-    assert_eq!(deserialized.keys[1]["KeyHash"], "feedface");
+    assert_eq!(deserialized.keys[0]["keyHash"], "deadbeef");
+    assert_eq!(deserialized.keys[0]["delegate"], "acc://delegate.acme");
+    assert_eq!(deserialized.keys[1]["keyHash"], "feedface");
     assert!(deserialized.keys[1].get("Delegate").is_none());
 
     let validation_result = create_key_page.validate();
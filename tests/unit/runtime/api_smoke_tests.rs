@@ -108,7 +108,7 @@ async fn smoke_query_methods() {
     // Test query method
     let query_params = generated::api_methods::QueryParams {
         url: "acc://test.acme".to_string(),
-        options: Some(json!({"prove": false, "scratch": false})),
+        options: Some(QueryOptions { prove: Some(false), scratch: Some(false), ..Default::default() }),
     };
     let query_result = client.query(query_params).await.unwrap();
 
@@ -118,7 +118,7 @@ async fn smoke_query_methods() {
     // Test query-tx method
     let query_tx_params = generated::api_methods::QueryTxParams {
         url: "0000000000000000000000000000000000000000000000000000000000000000".to_string(),
-        options: Some(json!({"wait": 0, "ignorePending": false})),
+        options: Some(TxQueryOptions { wait: Some(0), ignore_pending: Some(false), ..Default::default() }),
     };
     let query_tx_result = client.query_tx(query_tx_params).await.unwrap();
 
@@ -128,7 +128,11 @@ async fn smoke_query_methods() {
     // Test query-directory method
     let query_dir_params = generated::api_methods::QueryDirectoryParams {
         url: "acc://test.acme".to_string(),
-        options: Some(json!({"start": 0, "count": 10, "expand": true})),
+        options: Some(PagedQueryOptions {
+            start: Some(0),
+            count: Some(10),
+            query: QueryOptions { expand: Some(true), ..Default::default() },
+        }),
     };
     let query_dir_result = client.query_directory(query_dir_params).await.unwrap();
 
@@ -237,6 +241,38 @@ async fn smoke_method_names_and_serialization() {
     }
 }
 
+#[test]
+fn smoke_typed_query_options_serialization() {
+    let params = generated::api_methods::QueryDirectoryParams {
+        url: "acc://test.acme".to_string(),
+        options: Some(PagedQueryOptions {
+            start: Some(0),
+            count: Some(10),
+            query: QueryOptions { expand: Some(true), prove: Some(false), ..Default::default() },
+        }),
+    };
+    let value = serde_json::to_value(&params).unwrap();
+    assert_eq!(value, json!({
+        "url": "acc://test.acme",
+        "options": {"start": 0, "count": 10, "expand": true, "prove": false}
+    }));
+    let back: generated::api_methods::QueryDirectoryParams = serde_json::from_value(value).unwrap();
+    assert_eq!(back, params);
+
+    let params: generated::api_methods::QueryTxParams = serde_json::from_value(json!({
+        "url": "acc://test.acme",
+        "options": {"wait": 1000, "ignorePending": true, "prove": true}
+    }))
+    .unwrap();
+    let options = params.options.unwrap();
+    assert_eq!(options.wait, Some(1000));
+    assert_eq!(options.ignore_pending, Some(true));
+    assert_eq!(options.query.prove, Some(true));
+
+    let params = generated::api_methods::QueryParams { url: "acc://test.acme".to_string(), options: None };
+    assert_eq!(serde_json::to_value(&params).unwrap(), json!({"url": "acc://test.acme"}));
+}
+
 #[test]
 fn smoke_transport_trait_bounds() {
     // Verify the AccumulateRpc trait has the expected method signature
@@ -260,12 +296,12 @@ fn smoke_parameter_types_exist() {
 
     let _query_params = generated::api_methods::QueryParams {
         url: "acc://test.acme".to_string(),
-        options: Some(json!({"prove": false, "scratch": false})),
+        options: Some(QueryOptions { prove: Some(false), scratch: Some(false), ..Default::default() }),
     };
 
     let _query_tx_params = generated::api_methods::QueryTxParams {
         url: "test".to_string(),
-        options: Some(json!({"wait": 1000, "ignorePending": false})),
+        options: Some(TxQueryOptions { wait: Some(1000), ignore_pending: Some(false), ..Default::default() }),
     };
 
     let _execute_params = generated::api_methods::ExecuteParams {
@@ -274,7 +310,11 @@ fn smoke_parameter_types_exist() {
 
     let _query_dir_params = generated::api_methods::QueryDirectoryParams {
         url: "acc://test.acme".to_string(),
-        options: Some(json!({"start": 0, "count": 10, "expand": true})),
+        options: Some(PagedQueryOptions {
+            start: Some(0),
+            count: Some(10),
+            query: QueryOptions { expand: Some(true), ..Default::default() },
+        }),
     };
 
     let _faucet_params = generated::api_methods::FaucetParams {
@@ -31,7 +31,9 @@ async fn test_client_from_endpoints() {
 #[test]
 fn test_acc_options_default() {
     let options = AccOptions::default();
-    assert_eq!(options.timeout, Duration::from_secs(30));
+    assert_eq!(options.connect_timeout, Duration::from_secs(10));
+    assert_eq!(options.request_timeout, Duration::from_secs(30));
+    assert_eq!(options.confirmation_timeout, Duration::from_secs(120));
     assert!(options.headers.is_empty());
 }
 
@@ -41,11 +43,15 @@ fn test_acc_options_custom() {
     headers.insert("Authorization".to_string(), "Bearer token".to_string());
 
     let options = AccOptions {
-        timeout: Duration::from_secs(60),
+        request_timeout: Duration::from_secs(60),
+        confirmation_timeout: Duration::from_secs(600),
         headers,
+        ..Default::default()
     };
 
-    assert_eq!(options.timeout, Duration::from_secs(60));
+    assert_eq!(options.request_timeout, Duration::from_secs(60));
+    assert_eq!(options.confirmation_timeout, Duration::from_secs(600));
+    assert_eq!(options.connect_timeout, Duration::from_secs(10));
     assert_eq!(options.headers.len(), 1);
     assert_eq!(
         options.headers.get("Authorization"),
@@ -205,7 +205,7 @@ fn test_error_type_functionality() {
     let error_display = format!("{}", general_error);
     assert!(error_display.contains("test error"));
 
-    let network_error = Error::Network("network failure".to_string());
+    let network_error = Error::Transport("network failure".to_string());
     let network_display = format!("{}", network_error);
     assert!(network_display.contains("network failure"));
 
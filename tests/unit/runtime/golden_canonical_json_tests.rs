@@ -49,8 +49,8 @@ fn canonical_send_tokens_tx() {
             meta: None,
             to: vec![
                 json!({
-                    "Url": "acc://bob.acme/tokens",
-                    "Amount": "1000"
+                    "url": "acc://bob.acme/tokens",
+                    "amount": "1000"
                 })
             ]
         }
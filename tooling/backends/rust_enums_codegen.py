@@ -17,8 +17,206 @@ RUST_ROOT = r"C:\Accumulate_Stuff\opendlt-rust-v2v3-sdk\unified"
 SRC_DIR = os.path.join(RUST_ROOT, "src")
 GEN_DIR = os.path.join(SRC_DIR, "generated")
 
+# Overrides used by the `codegen` cargo feature (see build.rs)
+PROTOCOL_DIR = os.environ.get("ACCUMULATE_PROTOCOL_DIR", os.path.join(GO_REPO, "protocol"))
+GEN_DIR = os.environ.get("ACCUMULATE_CODEGEN_OUT", GEN_DIR)
+# Protocol revisions may add or remove enums; only the pinned sources are checked
+STRICT_COUNTS = "ACCUMULATE_PROTOCOL_DIR" not in os.environ
+
 EXPECTED_ENUM_COUNT = 14
 
+# Enums that derive Copy, e.g. to be set as bits in a mask
+COPY_ENUMS = {"AllowedTransactionBit", "VoteType"}
+
+# SDK helpers emitted after an enum; the YAML only describes the variants
+ENUM_EXTRAS = {
+    "VoteType": '''impl VoteType {
+    /// Get the numeric value of the vote type (matches Go protocol)
+    pub fn value(&self) -> u64 {
+        match self {
+            VoteType::Accept => 0,
+            VoteType::Reject => 1,
+            VoteType::Abstain => 2,
+            VoteType::Suggest => 3,
+        }
+    }
+
+    /// Create a VoteType from its numeric value
+    pub fn from_u64(value: u64) -> Option<Self> {
+        match value {
+            0 => Some(VoteType::Accept),
+            1 => Some(VoteType::Reject),
+            2 => Some(VoteType::Abstain),
+            3 => Some(VoteType::Suggest),
+            _ => None,
+        }
+    }
+
+    /// Get all possible vote types
+    pub fn all() -> &'static [VoteType] {
+        &[VoteType::Accept, VoteType::Reject, VoteType::Abstain, VoteType::Suggest]
+    }
+
+    /// Check if this vote type approves (accepts) the proposal
+    pub fn is_approval(&self) -> bool {
+        matches!(self, VoteType::Accept)
+    }
+
+    /// Check if this vote type rejects the proposal
+    pub fn is_rejection(&self) -> bool {
+        matches!(self, VoteType::Reject)
+    }
+
+    /// Check if this vote type is an abstention
+    pub fn is_abstention(&self) -> bool {
+        matches!(self, VoteType::Abstain)
+    }
+
+    /// Check if this vote type is a suggestion (proposal)
+    pub fn is_suggestion(&self) -> bool {
+        matches!(self, VoteType::Suggest)
+    }
+
+    /// Get string representation matching Go protocol
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VoteType::Accept => "accept",
+            VoteType::Reject => "reject",
+            VoteType::Abstain => "abstain",
+            VoteType::Suggest => "suggest",
+        }
+    }
+
+    /// Parse from string (case-insensitive)
+    pub fn from_str_case_insensitive(s: &str) -> Option<Self> {
+        match s.to_lowercase().as_str() {
+            "accept" => Some(VoteType::Accept),
+            "reject" => Some(VoteType::Reject),
+            "abstain" => Some(VoteType::Abstain),
+            "suggest" => Some(VoteType::Suggest),
+            _ => None,
+        }
+    }
+}
+
+impl Default for VoteType {
+    /// Default vote type is Accept (matches Go protocol)
+    fn default() -> Self {
+        VoteType::Accept
+    }
+}
+
+impl std::fmt::Display for VoteType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+/// Vote tallying result for counting signature votes
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VoteTally {
+    /// Number of accept votes
+    pub accept: u64,
+    /// Number of reject votes
+    pub reject: u64,
+    /// Number of abstain votes
+    pub abstain: u64,
+    /// Number of suggest votes
+    pub suggest: u64,
+}
+
+impl VoteTally {
+    /// Create an empty tally
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a vote to the tally
+    pub fn add_vote(&mut self, vote: VoteType) {
+        match vote {
+            VoteType::Accept => self.accept += 1,
+            VoteType::Reject => self.reject += 1,
+            VoteType::Abstain => self.abstain += 1,
+            VoteType::Suggest => self.suggest += 1,
+        }
+    }
+
+    /// Add multiple votes of the same type
+    pub fn add_votes(&mut self, vote: VoteType, count: u64) {
+        match vote {
+            VoteType::Accept => self.accept += count,
+            VoteType::Reject => self.reject += count,
+            VoteType::Abstain => self.abstain += count,
+            VoteType::Suggest => self.suggest += count,
+        }
+    }
+
+    /// Get the total number of votes (excluding abstentions)
+    pub fn total_active(&self) -> u64 {
+        self.accept + self.reject + self.suggest
+    }
+
+    /// Get the total number of all votes (including abstentions)
+    pub fn total(&self) -> u64 {
+        self.accept + self.reject + self.abstain + self.suggest
+    }
+
+    /// Check if the vote passes with simple majority (more accepts than rejects)
+    pub fn passes_simple_majority(&self) -> bool {
+        self.accept > self.reject
+    }
+
+    /// Check if the vote passes with strict majority (>50% accepts of active votes)
+    pub fn passes_strict_majority(&self) -> bool {
+        let total_active = self.total_active();
+        if total_active == 0 {
+            return false;
+        }
+        self.accept > total_active / 2
+    }
+
+    /// Check if the vote passes with supermajority (>=2/3 accepts of active votes)
+    pub fn passes_supermajority(&self) -> bool {
+        let total_active = self.total_active();
+        if total_active == 0 {
+            return false;
+        }
+        // Using >= 2/3 threshold: accept * 3 >= total * 2
+        self.accept * 3 >= total_active * 2
+    }
+
+    /// Check if the vote passes with given threshold (accepts as percentage of active votes)
+    pub fn passes_threshold(&self, threshold_percent: u64) -> bool {
+        if threshold_percent > 100 {
+            return false;
+        }
+        let total_active = self.total_active();
+        if total_active == 0 {
+            return threshold_percent == 0;
+        }
+        // accept * 100 >= total * threshold
+        self.accept * 100 >= total_active * threshold_percent
+    }
+
+    /// Get the acceptance percentage (of active votes)
+    pub fn acceptance_percentage(&self) -> f64 {
+        let total_active = self.total_active();
+        if total_active == 0 {
+            return 0.0;
+        }
+        (self.accept as f64 / total_active as f64) * 100.0
+    }
+
+    /// Merge another tally into this one
+    pub fn merge(&mut self, other: &VoteTally) {
+        self.accept += other.accept;
+        self.reject += other.reject;
+        self.abstain += other.abstain;
+        self.suggest += other.suggest;
+    }
+}''',
+}
+
 def load_yaml_with_anchors(file_path):
     """Load YAML file with anchor resolution."""
     try:
@@ -75,22 +273,25 @@ def generate_enum_rust_code(enum_name, variants_data):
         rust_variant = normalize_variant_name(variant_name)
         wire_tag = extract_wire_tag(variant_data)
 
-        if wire_tag:
-            # Use explicit wire tag
-            variants.append(f'    #[serde(rename = "{wire_tag}")]\n    {rust_variant},')
-        else:
+        if not wire_tag:
             # Use variant name as wire tag (convert to appropriate case)
             if enum_name == "TransactionType":
                 # Transaction types use camelCase
                 wire_tag = variant_name[0].lower() + variant_name[1:] if len(variant_name) > 1 else variant_name.lower()
-                variants.append(f'    #[serde(rename = "{wire_tag}")]\n    {rust_variant},')
             else:
                 # Most other enums use the variant name directly or lowercase
                 wire_tag = variant_name.lower()
-                variants.append(f'    #[serde(rename = "{wire_tag}")]\n    {rust_variant},')
+
+        # Remaining aliases still deserialize
+        aliases = variant_data.get("aliases") or [] if isinstance(variant_data, dict) else []
+        serde = f'rename = "{wire_tag}"'
+        serde += ''.join(f', alias = "{alias}"' for alias in aliases if alias != wire_tag)
+        variants.append(f'    #[serde({serde})]\n    {rust_variant},')
 
     # Generate the enum
-    enum_code = f'''#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+    copy = "Copy, " if enum_name in COPY_ENUMS else ""
+    enum_code = f'''#[derive(Debug, Clone, {copy}PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub enum {enum_name} {{
 {chr(10).join(variants)}
 }}'''
@@ -139,7 +340,7 @@ def main():
     print("=== Rust Enums Code Generator ===")
 
     # Load Go truth YAML
-    enums_file = os.path.join(GO_REPO, "protocol", "enums.yml")
+    enums_file = os.path.join(PROTOCOL_DIR, "enums.yml")
     if not os.path.exists(enums_file):
         print(f"ERROR: {enums_file} not found!")
         return 1
@@ -155,7 +356,9 @@ def main():
     enum_count = len(enums_data)
     print(f"Found {enum_count} enums")
 
-    if enum_count != EXPECTED_ENUM_COUNT:
+    if enum_count != EXPECTED_ENUM_COUNT and not STRICT_COUNTS:
+        print(f"WARN: found {enum_count} enums, pinned sources have {EXPECTED_ENUM_COUNT}")
+    elif enum_count != EXPECTED_ENUM_COUNT:
         print(f"ENUM_COUNT_FAIL: found {enum_count}, expected {EXPECTED_ENUM_COUNT}")
 
         expected_enums = {
@@ -200,6 +403,8 @@ use serde::{{Serialize, Deserialize}};
 
         enum_code, variant_names = generate_enum_rust_code(enum_name, enum_data)
         rust_code += enum_code + "\n\n"
+        if enum_name in ENUM_EXTRAS:
+            rust_code += ENUM_EXTRAS[enum_name] + "\n\n"
 
         # Collect wire tags for manifest
         wire_tags = []
@@ -139,6 +139,96 @@ def map_yaml_type_to_rust(yaml_type: str) -> str:
 
     return type_mapping.get(yaml_type, 'serde_json::Value')
 
+# Typed `options` for the V2 query family, keyed by params struct name.
+# Mirrors QueryOptions / QueryPagination / TxnQuery in internal/api/v2/types.yml.
+QUERY_OPTION_TYPES = {
+    'QueryParams': 'QueryOptions',
+    'QueryDirectoryParams': 'PagedQueryOptions',
+    'QueryTxParams': 'TxQueryOptions',
+    'QueryTxLocalParams': 'TxQueryOptions',
+    'QueryTxHistoryParams': 'PagedQueryOptions',
+    'QueryDataParams': 'DataEntryQueryOptions',
+    'QueryDataSetParams': 'PagedQueryOptions',
+    'QueryKeyPageIndexParams': 'KeyPageIndexQueryOptions',
+    'QueryMinorBlocksParams': 'PagedQueryOptions',
+    'QueryMajorBlocksParams': 'PagedQueryOptions',
+}
+
+QUERY_OPTION_STRUCTS = """/// General query options (`QueryOptions`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct QueryOptions {
+    /// Expand chain entries and directory listings
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub expand: Option<bool>,
+    /// Query the state at this height
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub height: Option<u64>,
+    /// Query the scratch chain instead of the main chain
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub scratch: Option<bool>,
+    /// Include a Merkle receipt
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub prove: Option<bool>,
+    /// Forward the query to the partition that owns the account
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub include_remote: Option<bool>,
+}
+
+/// Query options for paged queries (`QueryPagination` + `QueryOptions`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct PagedQueryOptions {
+    /// Index of the first result
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub start: Option<u64>,
+    /// Maximum number of results
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub count: Option<u64>,
+    #[serde(flatten)]
+    pub query: QueryOptions,
+}
+
+/// Query options for transaction lookups (`TxnQuery`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct TxQueryOptions {
+    /// Wait up to this many milliseconds for the transaction to appear
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub wait: Option<u64>,
+    /// Keep waiting while the transaction is pending
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub ignore_pending: Option<bool>,
+    #[serde(flatten)]
+    pub query: QueryOptions,
+}
+
+/// Query options for a single data entry (`DataEntryQuery`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct DataEntryQueryOptions {
+    /// Hex hash of the entry; the latest entry when absent
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub entry_hash: Option<String>,
+    #[serde(flatten)]
+    pub query: QueryOptions,
+}
+
+/// Query options for key lookups (`KeyPageIndexQuery`)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub struct KeyPageIndexQueryOptions {
+    /// Hex public key or key hash
+    pub key: String,
+    #[serde(flatten)]
+    pub query: QueryOptions,
+}"""
+
 def generate_params_struct(method: ApiMethod) -> str:
     """Generate Rust struct for method parameters"""
     struct_name = method.get_params_struct_name()
@@ -146,6 +236,7 @@ def generate_params_struct(method: ApiMethod) -> str:
     if not method.input_type:
         # No input parameters
         return f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct {struct_name} {{
     // No parameters
@@ -158,22 +249,26 @@ pub struct {struct_name} {{
     if method.input_type == 'protocol.AcmeFaucet':
         # Special case for faucet - use the transaction body type
         return f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct {struct_name} {{
     pub url: String,
 }}"""
     elif 'Query' in method.input_type:
-        # Query types typically have URL and options
+        # Query types have a URL and (typed, where known) options
+        options_type = QUERY_OPTION_TYPES.get(struct_name, 'serde_json::Value')
         return f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct {struct_name} {{
     pub url: String,
     #[serde(skip_serializing_if = "Option::is_none", default)]
-    pub options: Option<serde_json::Value>,
+    pub options: Option<{options_type}>,
 }}"""
     else:
         # Generic structure
         return f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct {struct_name} {{
     #[serde(flatten)]
@@ -187,6 +282,7 @@ def generate_result_struct(method: ApiMethod) -> str:
     if not method.output_type:
         # No output
         return f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct {struct_name} {{
     // No result data
@@ -195,6 +291,7 @@ pub struct {struct_name} {{
     # Handle union types and specific response types
     if '|' in method.output_type:
         return f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct {struct_name} {{
     #[serde(flatten)]
@@ -204,6 +301,7 @@ pub struct {struct_name} {{
     # Specific response type structures
     if method.output_type == 'StatusResponse':
         return f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct {struct_name} {{
     pub ok: bool,
@@ -214,6 +312,7 @@ pub struct {struct_name} {{
 }}"""
     elif method.output_type == 'TxResponse':
         return f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct {struct_name} {{
     pub transaction_hash: String,
@@ -227,6 +326,7 @@ pub struct {struct_name} {{
     else:
         # Generic response structure
         return f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct {struct_name} {{
     #[serde(flatten)]
@@ -295,6 +395,40 @@ pub fn __minimal_pair_for_test(method_name: &str) -> Option<(serde_json::Value,
     }}
 }}"""
 
+def generate_method_schemas(methods: List[ApiMethod]) -> str:
+    """Generate the JSON Schema registry used by the json-schema export"""
+    entries = []
+
+    for method in methods:
+        description = json.dumps(method.description)
+        entries.append(f"""        ApiMethodSchema {{
+            name: "{method.rpc_name}",
+            description: {description},
+            params: gen.subschema_for::<{method.get_params_struct_name()}>(),
+            result: gen.subschema_for::<{method.get_result_struct_name()}>(),
+        }},""")
+
+    entries_code = '\n'.join(entries)
+
+    return f"""/// JSON Schemas of an API method's params and result
+#[cfg(feature = "json-schema")]
+#[derive(Debug, Clone)]
+pub struct ApiMethodSchema {{
+    pub name: &'static str,
+    pub description: &'static str,
+    pub params: schemars::schema::Schema,
+    pub result: schemars::schema::Schema,
+}}
+
+/// Schemas for every API method, in declaration order; referenced types
+/// are added to `gen`'s definitions
+#[cfg(feature = "json-schema")]
+pub fn method_schemas(gen: &mut schemars::gen::SchemaGenerator) -> Vec<ApiMethodSchema> {{
+    vec![
+{entries_code}
+    ]
+}}"""
+
 def generate_api_methods_rs(methods: List[ApiMethod]) -> str:
     """Generate the complete api_methods.rs file"""
     timestamp = datetime.now().strftime("%Y-%m-%d %H:%M:%S")
@@ -311,6 +445,7 @@ def generate_api_methods_rs(methods: List[ApiMethod]) -> str:
     results_code = '\n\n'.join(result_structs)
     client_code = generate_client_methods(methods)
     test_helpers = generate_test_helpers(methods)
+    method_schemas = generate_method_schemas(methods)
 
     return f"""//! GENERATED FILE - DO NOT EDIT
 //! Source: internal/api/v2/methods.yml
@@ -341,6 +476,9 @@ impl<C> AccumulateClient<C> {{
     }}
 }}
 
+// Query option structures
+{QUERY_OPTION_STRUCTS}
+
 // Parameter structures
 {params_code}
 
@@ -350,7 +488,9 @@ impl<C> AccumulateClient<C> {{
 // Client implementation with strongly-typed methods
 {client_code}
 
-{test_helpers}"""
+{test_helpers}
+
+{method_schemas}"""
 
 def generate_manifest(methods: List[ApiMethod]) -> Dict[str, Any]:
     """Generate the API manifest JSON"""
@@ -13,10 +13,15 @@ import json
 from datetime import datetime
 from pathlib import Path
 
+# Overrides used by the `codegen` cargo feature (see build.rs)
+PROTOCOL_DIR = Path(os.environ.get("ACCUMULATE_PROTOCOL_DIR", "C:/Accumulate_Stuff/accumulate/protocol"))
+OUTPUT_DIR = Path(os.environ.get("ACCUMULATE_CODEGEN_OUT", "C:/Accumulate_Stuff/opendlt-rust-v2v3-sdk/unified/src/generated"))
+# Protocol revisions may add or remove signatures; only the pinned sources are checked
+STRICT_COUNTS = "ACCUMULATE_PROTOCOL_DIR" not in os.environ
 
 def load_signatures_yaml():
     """Load signature definitions from Go YAML truth source."""
-    yaml_path = Path("C:/Accumulate_Stuff/accumulate/protocol/signatures.yml")
+    yaml_path = PROTOCOL_DIR / "signatures.yml"
     if not yaml_path.exists():
         print(f"ERROR: YAML file not found: {yaml_path}")
         sys.exit(1)
@@ -135,11 +140,17 @@ def generate_signature_struct(name, fields):
         if serde_attrs:
             field_definitions.append(f'    #[serde({", ".join(serde_attrs)})]')
 
+        # Hex fields are strings on the wire
+        if field_type in ['bytes', 'hash']:
+            schema_type = 'Vec<String>' if is_repeatable else 'Option<String>' if is_optional else 'String'
+            field_definitions.append(f'    #[cfg_attr(feature = "json-schema", schemars(with = "{schema_type}"))]')
+
         field_definitions.append(f'    pub {rust_field}: {rust_type},')
 
     # Generate struct
     struct_code = f'''/// {name} signature
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct {rust_name} {{
 {chr(10).join(field_definitions)}
@@ -148,14 +159,186 @@ pub struct {rust_name} {{
     return struct_code
 
 
-def generate_acc_signature_impl(name):
-    """Generate AccSignature implementation for a signature type."""
-    wire_tag = get_wire_tag(name)
+# Cargo features that compile in each non-Ed25519 verifier
+VERIFY_FEATURES = {
+    'BTCSignature': 'secp256k1',
+    'BTCLegacySignature': 'secp256k1',
+    'ETHSignature': 'secp256k1',
+    'EcdsaSha256Signature': 'p256',
+    'TypedDataSignature': 'secp256k1',
+    'RsaSha256Signature': 'rsa',
+}
 
-    # Determine verification strategy based on signature type
-    if name == 'ED25519Signature':
-        verify_impl = """
-        use ed25519_dalek::{Signature as Ed25519Sig, VerifyingKey};
+
+# secp256k1, P-256 and RSA verifiers: the message is the signing digest,
+# which the Go core signs as-is, so it is verified as a prehash
+PREHASH_VERIFIERS = {
+    'BTCSignature': '''\
+        // BTC uses secp256k1 ECDSA with DER-encoded signatures
+        use k256::ecdsa::{Signature as K256Signature, VerifyingKey, signature::hazmat::PrehashVerifier};
+
+        if self.public_key.is_empty() || self.signature.is_empty() {
+            return Ok(false);
+        }
+
+        // Parse the public key (compressed or uncompressed SEC1 format)
+        let verifying_key = match VerifyingKey::from_sec1_bytes(&self.public_key) {
+            Ok(key) => key,
+            Err(_) => return Ok(false),
+        };
+
+        // Try to parse signature as DER format first, then as raw r||s format
+        let signature = if let Ok(sig) = K256Signature::from_der(&self.signature) {
+            sig
+        } else if self.signature.len() == 64 {
+            // Raw r||s format (32 bytes each)
+            match K256Signature::from_slice(&self.signature) {
+                Ok(sig) => sig,
+                Err(_) => return Ok(false),
+            }
+        } else {
+            return Ok(false);
+        };
+
+        // The message is the signing digest, signed as-is like the Go core does
+        Ok(verifying_key.verify_prehash(message, &signature).is_ok())''',
+    'BTCLegacySignature': '''\
+        // BTCLegacy uses same secp256k1 ECDSA as BTC
+        use k256::ecdsa::{Signature as K256Signature, VerifyingKey, signature::hazmat::PrehashVerifier};
+
+        if self.public_key.is_empty() || self.signature.is_empty() {
+            return Ok(false);
+        }
+
+        // Parse the public key (compressed or uncompressed SEC1 format)
+        let verifying_key = match VerifyingKey::from_sec1_bytes(&self.public_key) {
+            Ok(key) => key,
+            Err(_) => return Ok(false),
+        };
+
+        // Try to parse signature as DER format first, then as raw r||s format
+        let signature = if let Ok(sig) = K256Signature::from_der(&self.signature) {
+            sig
+        } else if self.signature.len() == 64 {
+            match K256Signature::from_slice(&self.signature) {
+                Ok(sig) => sig,
+                Err(_) => return Ok(false),
+            }
+        } else {
+            return Ok(false);
+        };
+
+        // The message is the signing digest, signed as-is like the Go core does
+        Ok(verifying_key.verify_prehash(message, &signature).is_ok())''',
+    'ETHSignature': '''\
+        // Ethereum-style secp256k1 ECDSA over the signing digest, signed as-is like the Go core does
+        // Signature format: r (32 bytes) || s (32 bytes) || v (1 byte recovery id)
+        use k256::ecdsa::{Signature as K256Signature, VerifyingKey, RecoveryId, signature::hazmat::PrehashVerifier};
+
+        if self.public_key.is_empty() || self.signature.is_empty() {
+            return Ok(false);
+        }
+
+        // Parse the public key (compressed or uncompressed SEC1 format)
+        let expected_key = match VerifyingKey::from_sec1_bytes(&self.public_key) {
+            Ok(key) => key,
+            Err(_) => return Ok(false),
+        };
+
+        // ETH signatures are typically 65 bytes (r || s || v) or 64 bytes (r || s)
+        if self.signature.len() == 65 {
+            // Extract r, s, and recovery id
+            let recovery_id = match RecoveryId::try_from(self.signature[64] % 4) {
+                Ok(id) => id,
+                Err(_) => return Ok(false),
+            };
+
+            let sig = match K256Signature::from_slice(&self.signature[..64]) {
+                Ok(s) => s,
+                Err(_) => return Ok(false),
+            };
+
+            // Recover the public key and compare
+            match VerifyingKey::recover_from_prehash(message, &sig, recovery_id) {
+                Ok(recovered_key) => {
+                    Ok(recovered_key == expected_key)
+                },
+                Err(_) => Ok(false),
+            }
+        } else if self.signature.len() == 64 {
+            // Standard ECDSA without recovery - verify directly
+            let signature = match K256Signature::from_slice(&self.signature) {
+                Ok(sig) => sig,
+                Err(_) => return Ok(false),
+            };
+            Ok(expected_key.verify_prehash(message, &signature).is_ok())
+        } else {
+            Ok(false)
+        }''',
+    'EcdsaSha256Signature': '''\
+        // ECDSA over NIST P-256 with SHA-256 hash
+        use p256::ecdsa::{Signature as P256Signature, VerifyingKey, signature::hazmat::PrehashVerifier};
+        use p256::pkcs8::DecodePublicKey;
+
+        if self.signature.is_empty() || self.public_key.is_empty() {
+            return Ok(false);
+        }
+
+        // The public key is PKIX DER, as the Go core's x509.MarshalPKIXPublicKey writes it
+        let verifying_key = match VerifyingKey::from_public_key_der(&self.public_key) {
+            Ok(key) => key,
+            Err(_) => return Ok(false),
+        };
+
+        // The Go core signs with ecdsa.SignASN1, so the signature is DER
+        let signature = match P256Signature::from_der(&self.signature) {
+            Ok(sig) => sig,
+            Err(_) => return Ok(false),
+        };
+
+        // The message is the signing digest, signed as-is like the Go core does
+        Ok(verifying_key.verify_prehash(message, &signature).is_ok())''',
+    'RsaSha256Signature': '''\
+        // RSA PKCS#1 v1.5 with SHA-256
+        use rsa::{RsaPublicKey, pkcs1::DecodeRsaPublicKey, pkcs8::DecodePublicKey};
+        use rsa::signature::hazmat::PrehashVerifier;
+        use rsa::pkcs1v15::{Signature, VerifyingKey};
+        use sha2::Sha256;
+
+        if self.signature.is_empty() || self.public_key.is_empty() {
+            return Ok(false);
+        }
+
+        // Try to parse the public key in different formats
+        // First try PKCS#1 DER format, then PKCS#8 DER format
+        let public_key = if let Ok(key) = RsaPublicKey::from_pkcs1_der(&self.public_key) {
+            key
+        } else if let Ok(key) = RsaPublicKey::from_public_key_der(&self.public_key) {
+            key
+        } else {
+            return Ok(false);
+        };
+
+        // Create verifying key with SHA256
+        let verifying_key: VerifyingKey<Sha256> = VerifyingKey::new(public_key);
+
+        // Parse the signature
+        let signature = match Signature::try_from(self.signature.as_slice()) {
+            Ok(sig) => sig,
+            Err(_) => return Ok(false),
+        };
+
+        // The message is the signing digest, signed as-is like the Go core does
+        Ok(verifying_key.verify_prehash(message, &signature).is_ok())''',
+}
+
+
+# Ed25519-family and system verifiers
+VERIFIERS = {
+    'LegacyED25519Signature': '''\
+        // Updated for ed25519-dalek v2.x API
+        // Legacy Ed25519 - use same verification as ED25519Signature
+        use ed25519_dalek::{Signature as Ed25519Sig, VerifyingKey, Verifier};
 
         if self.public_key.len() != 32 || self.signature.len() != 64 {
             return Ok(false);
@@ -168,20 +351,17 @@ def generate_acc_signature_impl(name):
         sig_bytes.copy_from_slice(&self.signature);
 
         match VerifyingKey::from_bytes(&pub_key_bytes) {
-            Ok(public_key) => {
-                match Ed25519Sig::from_slice(&sig_bytes) {
-                    Ok(signature) => {
-                        Ok(public_key.verify_strict(message, &signature).is_ok())
-                    },
-                    Err(_) => Ok(false),
-                }
+            Ok(verifying_key) => {
+                let signature = Ed25519Sig::from_bytes(&sig_bytes);
+                Ok(verifying_key.verify(message, &signature).is_ok())
             },
             Err(_) => Ok(false),
-        }"""
-    elif name == 'LegacyED25519Signature':
-        verify_impl = """
-        // Legacy Ed25519 - use same verification as ED25519Signature for now
-        use ed25519_dalek::{Signature as Ed25519Sig, VerifyingKey};
+        }''',
+    'RCD1Signature': '''\
+        // Updated for ed25519-dalek v2.x API
+        // RCD1 uses ED25519 internally (Factom RCD1 format)
+        // The public key format is the same as ED25519
+        use ed25519_dalek::{Signature as Ed25519Sig, VerifyingKey, Verifier};
 
         if self.public_key.len() != 32 || self.signature.len() != 64 {
             return Ok(false);
@@ -194,52 +374,268 @@ def generate_acc_signature_impl(name):
         sig_bytes.copy_from_slice(&self.signature);
 
         match VerifyingKey::from_bytes(&pub_key_bytes) {
-            Ok(public_key) => {
-                match Ed25519Sig::from_slice(&sig_bytes) {
-                    Ok(signature) => {
-                        Ok(public_key.verify_strict(message, &signature).is_ok())
-                    },
-                    Err(_) => Ok(false),
-                }
+            Ok(verifying_key) => {
+                let signature = Ed25519Sig::from_bytes(&sig_bytes);
+                Ok(verifying_key.verify(message, &signature).is_ok())
             },
             Err(_) => Ok(false),
-        }"""
-    elif name in ['ETHSignature', 'EcdsaSha256Signature']:
-        verify_impl = """
-        // ECDSA/Ethereum signature verification
-        use k256::ecdsa::{Signature as EcdsaSignature, VerifyingKey};
-        use k256::elliptic_curve::sec1::ToEncodedPoint;
-        use sha2::{Sha256, Digest};
+        }''',
+    'ED25519Signature': '''\
+        // Updated for ed25519-dalek v2.x API
+        use ed25519_dalek::{Signature as Ed25519Sig, VerifyingKey, Verifier};
 
-        // TODO: Implement proper ECDSA verification
-        // For now, basic structure validation
-        if self.signature.is_empty() || self.public_key.is_empty() {
+        if self.public_key.len() != 32 || self.signature.len() != 64 {
             return Ok(false);
         }
 
-        // Placeholder - Stage 1.4 will add proper crypto vectors
-        Ok(false)"""
-    elif name in ['RsaSha256Signature']:
-        verify_impl = """
-        // RSA signature verification - using simplified imports
-        // TODO: Implement proper RSA verification in Stage 1.4
-        // For now, basic structure validation
-        if self.signature.is_empty() || self.public_key.is_empty() {
+        let mut pub_key_bytes = [0u8; 32];
+        pub_key_bytes.copy_from_slice(&self.public_key);
+
+        let mut sig_bytes = [0u8; 64];
+        sig_bytes.copy_from_slice(&self.signature);
+
+        match VerifyingKey::from_bytes(&pub_key_bytes) {
+            Ok(verifying_key) => {
+                // In v2, Signature::from_bytes is infallible
+                let signature = Ed25519Sig::from_bytes(&sig_bytes);
+                Ok(verifying_key.verify(message, &signature).is_ok())
+            },
+            Err(_) => Ok(false),
+        }''',
+    'TypedDataSignature': '''\
+        // EIP-712 TypedData signature - uses keccak256 and secp256k1 with recovery
+        // Similar to ETHSignature but the message is already the EIP-712 typed data hash
+        use k256::ecdsa::{Signature as K256Signature, VerifyingKey, RecoveryId, signature::Verifier};
+        use sha3::{Keccak256, Digest};
+
+        if self.public_key.is_empty() || self.signature.is_empty() {
             return Ok(false);
         }
 
-        // Placeholder - Stage 1.4 will add proper crypto vectors
-        Ok(false)"""
+        // For EIP-712, the message should already be the structured data hash
+        // We hash it with keccak256 for final signing
+        let mut hasher = Keccak256::new();
+        hasher.update(message);
+        let hash = hasher.finalize();
+
+        // Parse the public key
+        let expected_key = match VerifyingKey::from_sec1_bytes(&self.public_key) {
+            Ok(key) => key,
+            Err(_) => return Ok(false),
+        };
+
+        // ETH-style signatures are typically 65 bytes (r || s || v)
+        if self.signature.len() == 65 {
+            let recovery_id = match RecoveryId::try_from(self.signature[64] % 4) {
+                Ok(id) => id,
+                Err(_) => return Ok(false),
+            };
+
+            let sig = match K256Signature::from_slice(&self.signature[..64]) {
+                Ok(s) => s,
+                Err(_) => return Ok(false),
+            };
+
+            match VerifyingKey::recover_from_prehash(&hash, &sig, recovery_id) {
+                Ok(recovered_key) => Ok(recovered_key == expected_key),
+                Err(_) => Ok(false),
+            }
+        } else if self.signature.len() == 64 {
+            let signature = match K256Signature::from_slice(&self.signature) {
+                Ok(sig) => sig,
+                Err(_) => return Ok(false),
+            };
+            Ok(expected_key.verify(&hash, &signature).is_ok())
+        } else {
+            Ok(false)
+        }''',
+    'ReceiptSignature': '''\
+        // Receipt signatures verify Merkle proofs from anchors
+        // The proof field contains the Merkle receipt that proves inclusion
+        // For now, we validate structure - full Merkle verification requires the anchor root
+
+        // Basic structural validation
+        if self.source_network.is_empty() {
+            return Ok(false);
+        }
+
+        // Receipt validation would require access to the anchor chain
+        // to verify the Merkle root. For SDK purposes, we trust well-formed receipts.
+        // Full validation happens on the validator nodes.
+        Ok(true)''',
+    'PartitionSignature': '''\
+        // Partition signatures are used for cross-partition communication
+        // They are produced by validators and verify routing between partitions
+
+        // Basic structural validation
+        if self.source_network.is_empty() || self.destination_network.is_empty() {
+            return Ok(false);
+        }
+
+        // Partition signatures are trusted when coming from validators
+        // Full validation requires access to validator sets
+        Ok(true)''',
+    'SignatureSet': '''\
+        // SignatureSet is a multi-signature container
+        // All inner signatures must verify for the set to be valid
+
+        if self.signatures.is_empty() {
+            return Ok(false);
+        }
+
+        // Verify each signature in the set
+        for sig in &self.signatures {
+            let result = match sig.as_ref() {
+                Signature::LegacyED25519(s) => s.verify(message),
+                Signature::RCD1(s) => s.verify(message),
+                Signature::ED25519(s) => s.verify(message),
+                Signature::BTC(s) => s.verify(message),
+                Signature::BTCLegacy(s) => s.verify(message),
+                Signature::ETH(s) => s.verify(message),
+                Signature::RsaSha256(s) => s.verify(message),
+                Signature::EcdsaSha256(s) => s.verify(message),
+                Signature::TypedData(s) => s.verify(message),
+                Signature::Receipt(s) => s.verify(message),
+                Signature::Partition(s) => s.verify(message),
+                Signature::Set(s) => s.verify(message),
+                Signature::Remote(s) => s.verify(message),
+                Signature::Delegated(s) => s.verify(message),
+                Signature::Internal(s) => s.verify(message),
+                Signature::Authority(s) => s.verify(message),
+            };
+
+            match result {
+                Ok(true) => continue,
+                Ok(false) => return Ok(false),
+                Err(e) => return Err(e),
+            }
+        }
+
+        Ok(true)''',
+    'RemoteSignature': '''\
+        // RemoteSignature wraps another signature for cross-partition routing
+        // Delegate verification to the inner signature
+
+        if self.destination.is_empty() {
+            return Ok(false);
+        }
+
+        // Verify the wrapped signature
+        match self.signature.as_ref() {
+            Signature::LegacyED25519(s) => s.verify(message),
+            Signature::RCD1(s) => s.verify(message),
+            Signature::ED25519(s) => s.verify(message),
+            Signature::BTC(s) => s.verify(message),
+            Signature::BTCLegacy(s) => s.verify(message),
+            Signature::ETH(s) => s.verify(message),
+            Signature::RsaSha256(s) => s.verify(message),
+            Signature::EcdsaSha256(s) => s.verify(message),
+            Signature::TypedData(s) => s.verify(message),
+            Signature::Receipt(s) => s.verify(message),
+            Signature::Partition(s) => s.verify(message),
+            Signature::Set(s) => s.verify(message),
+            Signature::Remote(s) => s.verify(message),
+            Signature::Delegated(s) => s.verify(message),
+            Signature::Internal(s) => s.verify(message),
+            Signature::Authority(s) => s.verify(message),
+        }''',
+    'DelegatedSignature': '''\
+        // DelegatedSignature wraps a signature from a delegated authority
+        // Delegate verification to the inner signature
+
+        if self.delegator.is_empty() {
+            return Ok(false);
+        }
+
+        // Verify the wrapped signature
+        match self.signature.as_ref() {
+            Signature::LegacyED25519(s) => s.verify(message),
+            Signature::RCD1(s) => s.verify(message),
+            Signature::ED25519(s) => s.verify(message),
+            Signature::BTC(s) => s.verify(message),
+            Signature::BTCLegacy(s) => s.verify(message),
+            Signature::ETH(s) => s.verify(message),
+            Signature::RsaSha256(s) => s.verify(message),
+            Signature::EcdsaSha256(s) => s.verify(message),
+            Signature::TypedData(s) => s.verify(message),
+            Signature::Receipt(s) => s.verify(message),
+            Signature::Partition(s) => s.verify(message),
+            Signature::Set(s) => s.verify(message),
+            Signature::Remote(s) => s.verify(message),
+            Signature::Delegated(s) => s.verify(message),
+            Signature::Internal(s) => s.verify(message),
+            Signature::Authority(s) => s.verify(message),
+        }''',
+    'InternalSignature': '''\
+        // InternalSignature is used for system-generated transactions
+        // It contains cause and transaction hash references
+
+        // Internal signatures are valid if they have proper hash references
+        // They are generated by the system, not user signatures
+        let zero_hash = [0u8; 32];
+        if self.cause == zero_hash || self.transaction_hash == zero_hash {
+            return Ok(false);
+        }
+
+        // Internal signatures are trusted - they come from the validator
+        Ok(true)''',
+    'AuthoritySignature': '''\
+        // AuthoritySignature represents approval from an authority (key book/key page)
+        // It's a structural signature that tracks authority approval chains
+
+        // Basic structural validation
+        if self.origin.is_empty() || self.authority.is_empty() {
+            return Ok(false);
+        }
+
+        if self.tx_i_d.is_empty() || self.cause.is_empty() {
+            return Ok(false);
+        }
+
+        // Authority signatures are valid if they have proper structure
+        // The actual authorization check is done by the validator
+        Ok(true)''',
+}
+
+
+def generate_acc_signature_impl(name):
+    """Generate AccSignature implementation for a signature type."""
+    wire_tag = get_wire_tag(name)
+
+    # Determine verification strategy based on signature type
+    if name in PREHASH_VERIFIERS:
+        verify_impl = PREHASH_VERIFIERS[name]
+    elif name in VERIFIERS:
+        verify_impl = VERIFIERS[name]
     else:
-        # For non-standard types: RCD1, BTC, Internal, Partition, Receipt, Remote, etc.
+        # Signature types added to the YAML after this generator never verify
         verify_impl = """
-        // Non-standard signature type - serialization/deserialization only
-        // TODO: Stage 1.4 will implement proper verification logic
         Ok(false)"""
 
+    verify_impl = verify_impl.strip('\n')
+    message = 'message' if 'message' in verify_impl else '_message'
+
+    feature = VERIFY_FEATURES.get(name)
+    if feature:
+        return f'''impl AccSignature for {name} {{
+    #[cfg(feature = "{feature}")]
+    fn verify(&self, {message}: &[u8]) -> Result<bool, crate::errors::Error> {{
+{verify_impl}
+    }}
+
+    #[cfg(not(feature = "{feature}"))]
+    fn verify(&self, _message: &[u8]) -> Result<bool, crate::errors::Error> {{
+        Err(unsupported_signature("{wire_tag}", "{feature}"))
+    }}
+
+    fn sig_type(&self) -> &'static str {{
+        "{wire_tag}"
+    }}
+}}'''
+
     return f'''impl AccSignature for {name} {{
-    fn verify(&self, message: &[u8]) -> Result<bool, crate::errors::Error> {{
-        {verify_impl}
+    fn verify(&self, {message}: &[u8]) -> Result<bool, crate::errors::Error> {{
+{verify_impl}
     }}
 
     fn sig_type(&self) -> &'static str {{
@@ -248,6 +644,85 @@ def generate_acc_signature_impl(name):
 }}'''
 
 
+# SDK vote helpers appended to `impl Signature`
+SIGNATURE_IMPL_EXTRAS = '''
+    /// Get the vote from this signature
+    /// Returns VoteType::Accept for signatures that don't have a vote field
+    pub fn get_vote(&self) -> crate::generated::enums::VoteType {
+        use crate::generated::enums::VoteType;
+        match self {
+            Signature::LegacyED25519(s) => s.vote.unwrap_or(VoteType::Accept),
+            Signature::RCD1(s) => s.vote.unwrap_or(VoteType::Accept),
+            Signature::ED25519(s) => s.vote.unwrap_or(VoteType::Accept),
+            Signature::BTC(s) => s.vote.unwrap_or(VoteType::Accept),
+            Signature::BTCLegacy(s) => s.vote.unwrap_or(VoteType::Accept),
+            Signature::ETH(s) => s.vote.unwrap_or(VoteType::Accept),
+            Signature::RsaSha256(s) => s.vote.unwrap_or(VoteType::Accept),
+            Signature::EcdsaSha256(s) => s.vote.unwrap_or(VoteType::Accept),
+            Signature::TypedData(s) => s.vote.unwrap_or(VoteType::Accept),
+            // System signatures always vote Accept
+            Signature::Receipt(_) => VoteType::Accept,
+            Signature::Partition(_) => VoteType::Accept,
+            Signature::Internal(_) => VoteType::Accept,
+            // SignatureSet has its own vote
+            Signature::Set(s) => s.vote.unwrap_or(VoteType::Accept),
+            // Wrapper signatures delegate to inner (signature is Box<Signature>, not Option)
+            Signature::Remote(s) => s.signature.get_vote(),
+            Signature::Delegated(s) => s.signature.get_vote(),
+            Signature::Authority(s) => s.vote.unwrap_or(VoteType::Accept),
+        }
+    }
+
+    /// Check if this signature votes to accept
+    pub fn votes_accept(&self) -> bool {
+        self.get_vote().is_approval()
+    }
+
+    /// Check if this signature votes to reject
+    pub fn votes_reject(&self) -> bool {
+        self.get_vote().is_rejection()
+    }
+
+    /// Check if this signature abstains
+    pub fn votes_abstain(&self) -> bool {
+        self.get_vote().is_abstention()
+    }
+
+    /// Tally votes from multiple signatures
+    pub fn tally_votes(signatures: &[Signature]) -> crate::generated::enums::VoteTally {
+        let mut tally = crate::generated::enums::VoteTally::new();
+        for sig in signatures {
+            tally.add_vote(sig.get_vote());
+        }
+        tally
+    }
+
+    /// Tally votes from multiple signatures (nested - includes nested signatures in sets)
+    pub fn tally_votes_nested(signatures: &[Signature]) -> crate::generated::enums::VoteTally {
+        let mut tally = crate::generated::enums::VoteTally::new();
+        for sig in signatures {
+            match sig {
+                Signature::Set(s) => {
+                    // For SignatureSet, use its vote field
+                    tally.add_vote(s.vote.unwrap_or(crate::generated::enums::VoteType::Accept));
+                }
+                Signature::Remote(s) => {
+                    // signature is Box<Signature>, not Option
+                    tally.add_vote(s.signature.get_vote());
+                }
+                Signature::Delegated(s) => {
+                    // signature is Box<Signature>, not Option
+                    tally.add_vote(s.signature.get_vote());
+                }
+                _ => {
+                    tally.add_vote(sig.get_vote());
+                }
+            }
+        }
+        tally
+    }'''
+
+
 def generate_signature_enum(signatures_data):
     """Generate the main Signature enum with serde dispatch."""
     variants = []
@@ -263,6 +738,7 @@ def generate_signature_enum(signatures_data):
 
     enum_code = f'''/// Main signature dispatch enum
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum Signature {{
 {chr(10).join(variants)}
@@ -274,6 +750,7 @@ impl Signature {{
 {chr(10).join(wire_tags)}
         }}
     }}
+{SIGNATURE_IMPL_EXTRAS}
 }}'''
 
     return enum_code
@@ -282,8 +759,8 @@ impl Signature {{
 def generate_signatures_rust_file(signatures_data):
     """Generate the complete signatures.rs file."""
     # Header
-    header = f'''// GENERATED FILE - DO NOT EDIT
-// Source: protocol/signatures.yml | Generated: {datetime.now().strftime('%Y-%m-%d %H:%M:%S')}
+    header = f'''//! GENERATED FILE - DO NOT EDIT
+//! Source: protocol/signatures.yml | Generated: {datetime.now().strftime('%Y-%m-%d %H:%M:%S')}
 
 use serde::{{Serialize, Deserialize}};
 use hex;
@@ -383,6 +860,8 @@ mod hex_vec_hash {{
 }}
 
 // Helper module for vector of bytes hex serialization
+// Kept for potential future use with Vec<Vec<u8>> fields
+#[allow(dead_code)]
 mod hex_vec_bytes {{
     use serde::{{Deserialize, Deserializer, Serialize, Serializer}};
 
@@ -419,6 +898,15 @@ pub enum SignatureError {{
     UnsupportedType,
 }}
 
+/// Error for signature types whose verifier is compiled out
+#[cfg(not(all(feature = "secp256k1", feature = "p256", feature = "rsa")))]
+fn unsupported_signature(sig_type: &str, feature: &str) -> crate::errors::Error {{
+    crate::errors::SignatureError::UnsupportedType(format!(
+        "{{sig_type}} (verification requires the `{{feature}}` feature)"
+    ))
+    .into()
+}}
+
 /// Main signature trait for verification
 pub trait AccSignature {{
     fn verify(&self, message: &[u8]) -> Result<bool, crate::errors::Error>;
@@ -477,7 +965,9 @@ def main():
 
     # Validate exactly 16 signatures
     signature_count = len(signatures_data)
-    if signature_count != 16:
+    if signature_count != 16 and not STRICT_COUNTS:
+        print(f"WARN: found {signature_count} signatures, pinned sources have 16")
+    elif signature_count != 16:
         print(f"ERROR: Expected exactly 16 signatures, found {signature_count}")
         print("Found signatures:")
         for name in signatures_data:
@@ -493,7 +983,7 @@ def main():
     rust_code = generate_signatures_rust_file(signatures_data)
 
     # Write signatures.rs
-    output_dir = Path("C:/Accumulate_Stuff/opendlt-rust-v2v3-sdk/unified/src/generated")
+    output_dir = OUTPUT_DIR
     output_dir.mkdir(parents=True, exist_ok=True)
 
     rust_file = output_dir / "signatures.rs"
@@ -28,6 +28,503 @@ SRC_DIR = RUST_ROOT / "src"
 GEN_DIR = SRC_DIR / "generated"
 EXPECTED_COUNT = 33  # Target for Phase 2.2-2.3
 
+# Overrides used by the `codegen` cargo feature (see build.rs)
+PROTOCOL_DIR = Path(os.environ.get("ACCUMULATE_PROTOCOL_DIR", GO_REPO / "protocol"))
+GEN_DIR = Path(os.environ.get("ACCUMULATE_CODEGEN_OUT", GEN_DIR))
+
+# Shared serde and validation helpers emitted ahead of the bodies
+HELPERS = '''\
+mod hex_option_vec {
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S>(value: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match value {
+            Some(bytes) => serializer.serialize_str(&hex::encode(bytes)),
+            None => serializer.serialize_none(),
+        }
+    }
+
+    pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<Vec<u8>>, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        let opt: Option<String> = Option::deserialize(deserializer)?;
+        match opt {
+            Some(hex_str) => {
+                hex::decode(&hex_str).map(Some).map_err(D::Error::custom)
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Validates that a string is a valid Accumulate URL
+/// Accumulate URLs must:
+/// - Start with "acc://"
+/// - Contain only ASCII characters
+/// - Have at least one character after the scheme
+/// - Not contain whitespace or control characters
+fn validate_accumulate_url(url: &str, field_name: &str) -> Result<(), Error> {
+    if url.is_empty() {
+        return Err(ValidationError::RequiredFieldMissing(field_name.to_string()).into());
+    }
+
+    if !url.starts_with("acc://") {
+        return Err(ValidationError::InvalidUrl(
+            format!("{}: must start with 'acc://', got '{}'", field_name, url)
+        ).into());
+    }
+
+    if !url.is_ascii() {
+        return Err(ValidationError::InvalidUrl(
+            format!("{}: must contain only ASCII characters", field_name)
+        ).into());
+    }
+
+    // Check for whitespace or control characters
+    if url.chars().any(|c| c.is_whitespace() || c.is_control()) {
+        return Err(ValidationError::InvalidUrl(
+            format!("{}: must not contain whitespace or control characters", field_name)
+        ).into());
+    }
+
+    // Must have content after acc://
+    if url.len() <= 6 {
+        return Err(ValidationError::InvalidUrl(
+            format!("{}: URL path cannot be empty", field_name)
+        ).into());
+    }
+
+    Ok(())
+}
+
+/// Validates that an amount string represents a valid positive integer
+fn validate_amount_string(amount: &str, field_name: &str) -> Result<(), Error> {
+    if amount.is_empty() {
+        return Err(ValidationError::RequiredFieldMissing(field_name.to_string()).into());
+    }
+
+    // Check if it's a valid integer (can be very large, so use string validation)
+    if !amount.chars().all(|c| c.is_ascii_digit()) {
+        return Err(ValidationError::InvalidAmount(
+            format!("{}: must be a valid non-negative integer, got '{}'", field_name, amount)
+        ).into());
+    }
+
+    // Check it's not all zeros (unless it's literally "0")
+    if amount != "0" && amount.chars().all(|c| c == '0') {
+        return Err(ValidationError::InvalidAmount(
+            format!("{}: invalid zero representation", field_name)
+        ).into());
+    }
+
+    Ok(())
+}
+
+/// Validates a list of authority URLs
+fn validate_authorities(authorities: &Option<Vec<String>>) -> Result<(), Error> {
+    if let Some(ref auths) = authorities {
+        for (i, auth) in auths.iter().enumerate() {
+            validate_accumulate_url(auth, &format!("authorities[{}]", i))?;
+        }
+    }
+    Ok(())
+}'''
+
+# Per-body validation; bodies added to the YAML after this generator accept anything
+VALIDATORS = {
+    'AcmeFaucet': '''\
+        // Faucet URL must be a valid Accumulate URL (typically a lite token account)
+        validate_accumulate_url(&self.url, "url")?;
+        Ok(())''',
+    'ActivateProtocolVersion': '''\
+        // Version is optional, but if present must not be empty
+        if let Some(ref version) = self.version {
+            if version.is_empty() {
+                return Err(ValidationError::InvalidFieldValue {
+                    field: "version".to_string(),
+                    reason: "version string cannot be empty if provided".to_string(),
+                }.into());
+            }
+        }
+        Ok(())''',
+    'AddCredits': '''\
+        // Recipient must be a valid Accumulate URL
+        validate_accumulate_url(&self.recipient, "recipient")?;
+
+        // Amount must be a valid positive integer string
+        validate_amount_string(&self.amount, "amount")?;
+
+        // Oracle price must be positive (represents ACME price in credits)
+        if self.oracle == 0 {
+            return Err(ValidationError::InvalidFieldValue {
+                field: "oracle".to_string(),
+                reason: "oracle price must be positive".to_string(),
+            }.into());
+        }
+
+        Ok(())''',
+    'BlockValidatorAnchor': '''\
+        // AcmeBurnt must be a valid amount string (can be zero for no burn)
+        validate_amount_string(&self.acme_burnt, "acmeBurnt")?;
+        Ok(())''',
+    'BurnCredits': '''\
+        // Amount must be positive (can't burn zero credits)
+        if self.amount == 0 {
+            return Err(ValidationError::InvalidAmount(
+                "amount: must be greater than zero to burn credits".to_string()
+            ).into());
+        }
+        Ok(())''',
+    'BurnTokens': '''\
+        // Amount must be a valid positive integer string (can't burn zero)
+        validate_amount_string(&self.amount, "amount")?;
+
+        // Ensure amount is not "0"
+        if self.amount == "0" {
+            return Err(ValidationError::InvalidAmount(
+                "amount: must be greater than zero to burn tokens".to_string()
+            ).into());
+        }
+        Ok(())''',
+    'CreateDataAccount': '''\
+        // URL must be a valid Accumulate URL
+        validate_accumulate_url(&self.url, "url")?;
+
+        // Validate authorities if provided
+        validate_authorities(&self.authorities)?;
+
+        Ok(())''',
+    'CreateIdentity': '''\
+        // URL must be a valid Accumulate URL for the new identity
+        validate_accumulate_url(&self.url, "url")?;
+
+        // If key_book_url is provided, it must be valid
+        if let Some(ref key_book_url) = self.key_book_url {
+            validate_accumulate_url(key_book_url, "keyBookUrl")?;
+        }
+
+        // If key_hash is provided, it should be 32 bytes (SHA-256 hash)
+        if let Some(ref key_hash) = self.key_hash {
+            if key_hash.len() != 32 {
+                return Err(ValidationError::InvalidHash {
+                    expected: 32,
+                    actual: key_hash.len(),
+                }.into());
+            }
+        }
+
+        // Validate authorities if provided
+        validate_authorities(&self.authorities)?;
+
+        Ok(())''',
+    'CreateKeyBook': '''\
+        // URL must be a valid Accumulate URL
+        validate_accumulate_url(&self.url, "url")?;
+
+        // Public key hash must be 32 bytes (SHA-256)
+        if self.public_key_hash.len() != 32 {
+            return Err(ValidationError::InvalidHash {
+                expected: 32,
+                actual: self.public_key_hash.len(),
+            }.into());
+        }
+
+        // Validate authorities if provided
+        validate_authorities(&self.authorities)?;
+
+        Ok(())''',
+    'CreateKeyPage': '''\
+        // Must have at least one key entry
+        if self.keys.is_empty() {
+            return Err(ValidationError::EmptyCollection(
+                "keys: at least one key is required".to_string()
+            ).into());
+        }
+
+        // Each key entry should be a valid JSON object
+        for (i, key) in self.keys.iter().enumerate() {
+            if !key.is_object() {
+                return Err(ValidationError::InvalidFieldValue {
+                    field: format!("keys[{}]", i),
+                    reason: "each key must be a valid key specification object".to_string(),
+                }.into());
+            }
+        }
+
+        Ok(())''',
+    'CreateLiteTokenAccount': '''\
+        // No fields to validate - lite token accounts are created implicitly
+        Ok(())''',
+    'CreateToken': '''\
+        // URL must be a valid Accumulate URL
+        validate_accumulate_url(&self.url, "url")?;
+
+        // Symbol must be non-empty and alphanumeric (1-10 characters typically)
+        if self.symbol.is_empty() {
+            return Err(ValidationError::InvalidTokenSymbol(
+                "symbol cannot be empty".to_string()
+            ).into());
+        }
+
+        if !self.symbol.chars().all(|c| c.is_ascii_alphanumeric()) {
+            return Err(ValidationError::InvalidTokenSymbol(
+                format!("symbol must be alphanumeric, got '{}'", self.symbol)
+            ).into());
+        }
+
+        if self.symbol.len() > 10 {
+            return Err(ValidationError::InvalidTokenSymbol(
+                format!("symbol too long (max 10 chars), got {} chars", self.symbol.len())
+            ).into());
+        }
+
+        // Precision must be between 0 and 18 (standard decimal precision)
+        if self.precision > 18 {
+            return Err(ValidationError::InvalidPrecision(self.precision).into());
+        }
+
+        // If supply_limit is provided, it must be a valid amount
+        if let Some(ref supply_limit) = self.supply_limit {
+            validate_amount_string(supply_limit, "supplyLimit")?;
+        }
+
+        // Validate authorities if provided
+        validate_authorities(&self.authorities)?;
+
+        Ok(())''',
+    'CreateTokenAccount': '''\
+        // URL must be a valid Accumulate URL
+        validate_accumulate_url(&self.url, "url")?;
+
+        // TokenUrl must be a valid Accumulate URL
+        validate_accumulate_url(&self.token_url, "tokenUrl")?;
+
+        // Validate authorities if provided
+        validate_authorities(&self.authorities)?;
+
+        Ok(())''',
+    'DirectoryAnchor': '''\
+        // System transaction - validates structure only
+        // Updates and receipts can be empty arrays
+        // MakeMajorBlock and MakeMajorBlockTime are informational
+        Ok(())''',
+    'IssueTokens': '''\
+        // Recipients are listed in `to`; the superseded single recipient needs
+        // both of its fields
+        match (&self.recipient, &self.amount) {
+            (Some(recipient), Some(amount)) => {
+                // Recipient must be a valid Accumulate URL
+                validate_accumulate_url(recipient, "recipient")?;
+
+                // Amount must be a valid positive integer string
+                validate_amount_string(amount, "amount")?;
+
+                // Amount must be positive for issuance
+                if amount == "0" {
+                    return Err(ValidationError::InvalidAmount(
+                        "amount: must be greater than zero to issue tokens".to_string()
+                    ).into());
+                }
+            }
+            (None, None) if !self.to.is_empty() => {}
+            (None, None) => {
+                return Err(ValidationError::EmptyCollection(
+                    "to: at least one recipient is required".to_string()
+                ).into());
+            }
+            _ => {
+                return Err(ValidationError::RequiredFieldMissing(
+                    "recipient and amount must be set together".to_string()
+                ).into());
+            }
+        }
+
+        Ok(())''',
+    'LockAccount': '''\
+        // Height must be positive (lock until block height)
+        if self.height == 0 {
+            return Err(ValidationError::InvalidFieldValue {
+                field: "height".to_string(),
+                reason: "lock height must be greater than zero".to_string(),
+            }.into());
+        }
+        Ok(())''',
+    'NetworkMaintenance': '''\
+        // System transaction - operations can be empty
+        // Validation of individual operations is done at the protocol level
+        Ok(())''',
+    'RemoteTransaction': '''\
+        // If hash is provided, it should be 32 bytes (SHA-256)
+        if let Some(ref hash) = self.hash {
+            if hash.len() != 32 {
+                return Err(ValidationError::InvalidHash {
+                    expected: 32,
+                    actual: hash.len(),
+                }.into());
+            }
+        }
+        Ok(())''',
+    'SendTokens': '''\
+        // Must have at least one recipient
+        if self.to.is_empty() {
+            return Err(ValidationError::EmptyCollection(
+                "to: at least one recipient is required".to_string()
+            ).into());
+        }
+
+        // Each recipient should be a valid object with url and amount
+        for (i, recipient) in self.to.iter().enumerate() {
+            if !recipient.is_object() {
+                return Err(ValidationError::InvalidFieldValue {
+                    field: format!("to[{}]", i),
+                    reason: "each recipient must be a valid object with url and amount".to_string(),
+                }.into());
+            }
+
+            // Validate url field if present
+            if let Some(url) = recipient.get("url").and_then(|v| v.as_str()) {
+                validate_accumulate_url(url, &format!("to[{}].url", i))?;
+            }
+
+            // Validate amount field if present
+            if let Some(amount) = recipient.get("amount").and_then(|v| v.as_str()) {
+                validate_amount_string(amount, &format!("to[{}].amount", i))?;
+            }
+        }
+
+        // If hash is provided, it should be 32 bytes
+        if let Some(ref hash) = self.hash {
+            if hash.len() != 32 {
+                return Err(ValidationError::InvalidHash {
+                    expected: 32,
+                    actual: hash.len(),
+                }.into());
+            }
+        }
+
+        Ok(())''',
+    'SystemGenesis': '''\
+        // System transaction - no fields to validate
+        Ok(())''',
+    'SystemWriteData': '''\
+        // Entry must be a valid object
+        if !self.entry.is_object() {
+            return Err(ValidationError::InvalidFieldValue {
+                field: "entry".to_string(),
+                reason: "entry must be a valid data entry object".to_string(),
+            }.into());
+        }
+        Ok(())''',
+    'TransferCredits': '''\
+        // Must have at least one recipient
+        if self.to.is_empty() {
+            return Err(ValidationError::EmptyCollection(
+                "to: at least one credit recipient is required".to_string()
+            ).into());
+        }
+
+        // Each recipient should be a valid object
+        for (i, recipient) in self.to.iter().enumerate() {
+            if !recipient.is_object() {
+                return Err(ValidationError::InvalidFieldValue {
+                    field: format!("to[{}]", i),
+                    reason: "each recipient must be a valid object with url and amount".to_string(),
+                }.into());
+            }
+
+            // Validate url field if present
+            if let Some(url) = recipient.get("url").and_then(|v| v.as_str()) {
+                validate_accumulate_url(url, &format!("to[{}].url", i))?;
+            }
+        }
+
+        Ok(())''',
+    'UpdateAccountAuth': '''\
+        // Must have at least one operation
+        if self.operations.is_empty() {
+            return Err(ValidationError::EmptyCollection(
+                "operations: at least one auth operation is required".to_string()
+            ).into());
+        }
+
+        // Each operation should be a valid object
+        for (i, op) in self.operations.iter().enumerate() {
+            if !op.is_object() {
+                return Err(ValidationError::InvalidFieldValue {
+                    field: format!("operations[{}]", i),
+                    reason: "each operation must be a valid auth operation object".to_string(),
+                }.into());
+            }
+        }
+
+        Ok(())''',
+    'UpdateKey': '''\
+        // New key hash must be 32 bytes (SHA-256)
+        if self.new_key_hash.len() != 32 {
+            return Err(ValidationError::InvalidHash {
+                expected: 32,
+                actual: self.new_key_hash.len(),
+            }.into());
+        }
+        Ok(())''',
+    'UpdateKeyPage': '''\
+        // Must have at least one operation
+        if self.operation.is_empty() {
+            return Err(ValidationError::EmptyCollection(
+                "operation: at least one key page operation is required".to_string()
+            ).into());
+        }
+
+        // Each operation should be a valid object
+        for (i, op) in self.operation.iter().enumerate() {
+            if !op.is_object() {
+                return Err(ValidationError::InvalidFieldValue {
+                    field: format!("operation[{}]", i),
+                    reason: "each operation must be a valid key page operation object".to_string(),
+                }.into());
+            }
+        }
+
+        Ok(())''',
+    'WriteData': '''\
+        // Entry must be a valid object
+        if !self.entry.is_object() {
+            return Err(ValidationError::InvalidFieldValue {
+                field: "entry".to_string(),
+                reason: "entry must be a valid data entry object".to_string(),
+            }.into());
+        }
+        Ok(())''',
+    'WriteDataTo': '''\
+        // Recipient must be a valid Accumulate URL
+        validate_accumulate_url(&self.recipient, "recipient")?;
+
+        // Entry must be a valid object
+        if !self.entry.is_object() {
+            return Err(ValidationError::InvalidFieldValue {
+                field: "entry".to_string(),
+                reason: "entry must be a valid data entry object".to_string(),
+            }.into());
+        }
+
+        Ok(())''',
+}
+
+# Superseded fields the network omits, emitted as optional; `issueTokens` lists its recipients in `to`
+LEGACY_FIELDS = {
+    'IssueTokens': {'Recipient', 'Amount'},
+}
+
+# Wire tags that older payloads still use for a body
+VARIANT_ALIASES = {
+    'RemoteTransaction': ['signPending'],
+}
+
 class TransactionBody:
     """Represents a parsed transaction body from YAML"""
 
@@ -103,10 +600,10 @@ class TransactionBody:
 def load_yaml_files() -> Dict[str, Any]:
     """Load all relevant YAML files"""
     yaml_files = {
-        'transaction': GO_REPO / "protocol" / "transaction.yml",
-        'user_transactions': GO_REPO / "protocol" / "user_transactions.yml",
-        'system': GO_REPO / "protocol" / "system.yml",
-        'synthetic_transactions': GO_REPO / "protocol" / "synthetic_transactions.yml"
+        'transaction': PROTOCOL_DIR / "transaction.yml",
+        'user_transactions': PROTOCOL_DIR / "user_transactions.yml",
+        'system': PROTOCOL_DIR / "system.yml",
+        'synthetic_transactions': PROTOCOL_DIR / "synthetic_transactions.yml"
     }
 
     loaded = {}
@@ -241,7 +738,7 @@ def generate_rust_struct(body: TransactionBody) -> str:
     for field in body.fields:
         field_name = field.get('name', '')
         field_type = field.get('type', 'unknown')
-        optional = field.get('optional', False)
+        optional = field.get('optional', False) or field_name in LEGACY_FIELDS.get(body.name, set())
         repeatable = field.get('repeatable', False)
 
         if not field_name:
@@ -252,10 +749,25 @@ def generate_rust_struct(body: TransactionBody) -> str:
         # Convert field name to snake_case for Rust
         snake_case_name = camel_to_snake_case(field_name)
 
-        # Add serde rename if needed
-        if snake_case_name != field_name:
+        # Wire names are camelCase; accept the YAML's PascalCase from older payloads
+        wire_name = field_name[:1].lower() + field_name[1:]
+        if wire_name != field_name:
+            field_lines.append(f'    #[serde(rename = "{wire_name}", alias = "{field_name}")]')
+        elif snake_case_name != field_name:
             field_lines.append(f'    #[serde(rename = "{field_name}")]')
 
+        # Hex-encoded on the wire; optional fields are omitted when unset
+        is_hex = field_type in ['bytes', 'hash', 'txid'] and not repeatable
+        if optional and is_hex:
+            field_lines.append('    #[serde(skip_serializing_if = "Option::is_none", default)]')
+            field_lines.append('    #[serde(with = "hex_option_vec")]')
+            field_lines.append('    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]')
+        elif optional:
+            field_lines.append('    #[serde(skip_serializing_if = "Option::is_none")]')
+        elif is_hex:
+            field_lines.append('    #[serde(with = "hex::serde")]')
+            field_lines.append('    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]')
+
         field_lines.append(f'    pub {snake_case_name}: {rust_type},')
 
     # If no fields, add a comment
@@ -265,6 +777,7 @@ def generate_rust_struct(body: TransactionBody) -> str:
     fields_str = '\n'.join(field_lines)
 
     return f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct {struct_name} {{
 {fields_str}
@@ -281,9 +794,7 @@ def generate_validation_impl(body: TransactionBody) -> str:
     """Generate validation implementation for a transaction body"""
     struct_name = body.get_struct_name()
 
-    # For now, generate stub validation methods
-    # TODO: Parse actual constraints from YAML
-    validation_body = "        // TODO: constrained by YAML\n        Ok(())"
+    validation_body = VALIDATORS.get(body.name, "        Ok(())")
 
     return f"""impl {struct_name} {{
     pub fn validate(&self) -> Result<(), Error> {{
@@ -298,11 +809,14 @@ def generate_transaction_body_enum(bodies: List[TransactionBody]) -> str:
     for body in bodies:
         wire_tag = body.wire_tag
         struct_name = body.get_struct_name()
-        variants.append(f'    #[serde(rename = "{wire_tag}")]\n    {body.name}({struct_name}),')
+        serde = f'rename = "{wire_tag}"'
+        serde += ''.join(f', alias = "{alias}"' for alias in VARIANT_ALIASES.get(body.name, []))
+        variants.append(f'    #[serde({serde})]\n    {body.name}({struct_name}),')
 
     variants_str = '\n'.join(variants)
 
     return f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum TransactionBody {{
 {variants_str}
@@ -338,6 +852,7 @@ def generate_test_helpers(bodies: List[TransactionBody]) -> str:
         for field in body.fields:
             if not field.get('optional', False):
                 field_name = field.get('name', '')
+                field_name = field_name[:1].lower() + field_name[1:]
                 field_type = field.get('type', 'unknown')
                 repeatable = field.get('repeatable', False)
 
@@ -408,7 +923,9 @@ def generate_transactions_rs(bodies: List[TransactionBody]) -> str:
 //! Generated: {timestamp}
 
 use serde::{{Serialize, Deserialize}};
-use crate::errors::Error;
+use crate::errors::{{Error, ValidationError}};
+
+{HELPERS}
 
 """
 
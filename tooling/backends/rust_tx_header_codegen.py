@@ -149,6 +149,7 @@ def generate_nested_type_structs(nested_types: Dict[str, List[HeaderField]]) ->
         fields_str = '\n'.join(field_lines) if field_lines else '    // No fields'
 
         struct_code = f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct {type_name} {{
 {fields_str}
@@ -190,14 +191,17 @@ def generate_header_struct(fields: List[HeaderField]) -> str:
         if field.yaml_type in ['hash', 'bytes'] and 'Vec<u8>' in rust_type:
             if field.optional:
                 field_lines.append(f'    #[serde(with = "hex_option_vec")]')
+                field_lines.append(f'    #[cfg_attr(feature = "json-schema", schemars(with = "Option<String>"))]')
             else:
                 field_lines.append(f'    #[serde(with = "hex::serde")]')
+                field_lines.append(f'    #[cfg_attr(feature = "json-schema", schemars(with = "String"))]')
 
         field_lines.append(f'    pub {snake_case_name}: {rust_type},')
 
     fields_str = '\n'.join(field_lines)
 
     return f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(rename_all = "camelCase")]
 pub struct TransactionHeader {{
 {fields_str}
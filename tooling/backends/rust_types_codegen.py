@@ -220,6 +220,7 @@ class RustTypeGenerator:
         fields = node_data.get("fields", [])
 
         struct_code = f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 pub struct {type_name} {{
 {self.generate_struct_fields(fields)}
 }}"""
@@ -231,6 +232,7 @@ pub struct {type_name} {{
         # Get enum values from the original definition
         # This requires looking up the original YAML data
         enum_code = f"""#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[repr(u64)]
 pub enum {type_name} {{
     Unknown = 0,
@@ -242,6 +244,7 @@ pub enum {type_name} {{
     def generate_union_type(self, type_name: str, node_data: Dict[str, Any]) -> str:
         """Generate Rust enum for union types"""
         union_code = f"""#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[cfg_attr(feature = "json-schema", derive(schemars::JsonSchema))]
 #[serde(tag = "type")]
 pub enum {type_name} {{
     // Union variants would be populated based on the union definition
@@ -0,0 +1,109 @@
+#!/usr/bin/env python3
+"""
+Capture transaction records from an Accumulate node for the wire-parity test.
+
+Usage: python3 tooling/capture_node_bodies.py <v3 endpoint> <account or message URL>...
+
+For each account URL, reads the account's main chain with an expanded V3
+`query`; for each message ID (acc://<hash>@<account>), queries the message.
+The first message record of each body type is written to
+tests/golden/node/<type>.json together with where and when it was captured.
+Existing captures are kept unless the same type is captured again. Prints
+the body types that still have no capture.
+
+Example against a local DevNet:
+
+    python3 tooling/capture_node_bodies.py http://localhost:26660/v3 \\
+        acc://dn.acme/anchors acc://bvn-BVN1.acme/anchors acc://alice.acme/tokens
+"""
+
+import datetime
+import json
+import sys
+import urllib.request
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parent.parent
+CAPTURES = ROOT / "tests" / "golden" / "node"
+MANIFEST = ROOT / "src" / "generated" / "transactions_manifest.json"
+PAGE = 100
+
+
+def call(endpoint, method, params):
+    request = json.dumps({"jsonrpc": "2.0", "id": 1, "method": method, "params": params}).encode()
+    headers = {"Content-Type": "application/json"}
+    with urllib.request.urlopen(urllib.request.Request(endpoint, request, headers), timeout=30) as response:
+        answer = json.load(response)
+    if "error" in answer:
+        raise RuntimeError(f"{method} {json.dumps(params)}: {answer['error']}")
+    return answer["result"]
+
+
+def message_records(value):
+    """Yield every message record carrying a transaction, however deeply nested"""
+    if isinstance(value, dict):
+        message = value.get("message")
+        if value.get("recordType") == "message" and isinstance(message, dict) and "transaction" in message:
+            yield value
+            return
+        for child in value.values():
+            yield from message_records(child)
+    elif isinstance(value, list):
+        for child in value:
+            yield from message_records(child)
+
+
+def read(endpoint, url):
+    """Yield (params, result) for each query needed to read `url`"""
+    if "@" in url:
+        params = {"scope": url}
+        yield params, call(endpoint, "query", params)
+        return
+    start, total = 0, 1
+    while start < total:
+        params = {
+            "scope": url,
+            "query": {"queryType": "chain", "name": "main", "range": {"start": start, "count": PAGE, "expand": True}},
+        }
+        result = call(endpoint, "query", params)
+        yield params, result
+        start, total = start + PAGE, result.get("total", 0)
+
+
+def main():
+    if len(sys.argv) < 3:
+        print(__doc__.strip())
+        return 2
+    endpoint, urls = sys.argv[1], sys.argv[2:]
+    network = call(endpoint, "network-status", {}).get("network", {}).get("networkName", "unknown")
+    CAPTURES.mkdir(parents=True, exist_ok=True)
+
+    captured = set()
+    for url in urls:
+        for params, result in read(endpoint, url):
+            for record in message_records(result):
+                body_type = record["message"]["transaction"]["body"]["type"]
+                if body_type in captured:
+                    continue
+                captured.add(body_type)
+                capture = {
+                    "endpoint": endpoint,
+                    "network": network,
+                    "capturedAt": datetime.datetime.now(datetime.timezone.utc).isoformat(timespec="seconds"),
+                    "query": params,
+                    "record": record,
+                }
+                path = CAPTURES / f"{body_type}.json"
+                path.write_text(json.dumps(capture, indent=2) + "\n", encoding="utf-8")
+                print(f"captured {body_type} from {record.get('id', url)}")
+
+    have = {path.stem for path in CAPTURES.glob("*.json")}
+    wanted = [body["wire"] for body in json.loads(MANIFEST.read_text(encoding="utf-8"))["bodies"]]
+    missing = [wire for wire in wanted if wire not in have]
+    if missing:
+        print(f"no capture yet for {len(missing)} body types: {', '.join(missing)}")
+    return 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())
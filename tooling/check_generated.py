@@ -0,0 +1,65 @@
+#!/usr/bin/env python3
+"""
+Check that the bundled src/generated modules match what the generators emit.
+
+Usage: python3 tooling/check_generated.py <accumulate protocol/ directory>
+
+Runs the enum, signature and transaction generators into a temporary
+directory and diffs each module against src/generated, ignoring the
+timestamp line. Exits 1 if any module differs.
+"""
+
+import difflib
+import os
+import subprocess
+import sys
+import tempfile
+from pathlib import Path
+
+ROOT = Path(__file__).resolve().parent.parent
+BACKENDS = ROOT / "tooling" / "backends"
+BUNDLED = ROOT / "src" / "generated"
+
+GENERATORS = [
+    ("rust_enums_codegen.py", "enums.rs"),
+    ("rust_signatures_codegen.py", "signatures.rs"),
+    ("rust_transactions_codegen.py", "transactions.rs"),
+]
+
+
+def strip_timestamp(source):
+    return [line for line in source.splitlines(keepends=True) if "Generated: " not in line]
+
+
+def main():
+    if len(sys.argv) != 2:
+        print(__doc__.strip())
+        return 2
+    protocol_dir = Path(sys.argv[1]).resolve()
+
+    failed = False
+    with tempfile.TemporaryDirectory() as out_dir:
+        env = dict(os.environ, ACCUMULATE_PROTOCOL_DIR=str(protocol_dir), ACCUMULATE_CODEGEN_OUT=out_dir)
+        for script, module in GENERATORS:
+            result = subprocess.run([sys.executable, str(BACKENDS / script)], env=env,
+                                    capture_output=True, text=True)
+            if result.returncode != 0:
+                print(f"FAIL {script}\n{result.stdout}{result.stderr}")
+                failed = True
+                continue
+
+            generated = strip_timestamp((Path(out_dir) / module).read_text(encoding="utf-8"))
+            bundled = strip_timestamp((BUNDLED / module).read_text(encoding="utf-8"))
+            diff = list(difflib.unified_diff(bundled, generated, f"src/generated/{module}", f"{script} output"))
+            if diff:
+                sys.stdout.writelines(diff)
+                print(f"\nFAIL {module} differs from the generator output")
+                failed = True
+            else:
+                print(f"OK {module}")
+
+    return 1 if failed else 0
+
+
+if __name__ == "__main__":
+    sys.exit(main())